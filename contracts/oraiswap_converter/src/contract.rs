@@ -1,19 +1,26 @@
 use cosmwasm_std::{
-    entry_point, from_binary, to_binary, Addr, Attribute, Binary, CosmosMsg, Decimal, Deps,
-    DepsMut, Env, MessageInfo, Response, StdError, StdResult,
+    coins, entry_point, from_binary, to_binary, Addr, Attribute, BankMsg, Binary, CanonicalAddr,
+    CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult, Storage,
+    Uint128, WasmMsg,
 };
-use cw20::Cw20ReceiveMsg;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
 use oraiswap::math::Converter128;
+use oraiswap::tokenfactory::{burn_msg, create_denom_msg, mint_msg, tokenfactory_denom};
 
 use crate::state::{
-    read_config, read_token_ratio, store_config, store_token_ratio, token_ratio_remove, Config,
+    read_config, read_convert_limit, read_convert_stats, read_token_ratio, read_wrapped_denom,
+    read_wrapped_token, store_config, store_convert_limit, store_convert_stats, store_token_ratio,
+    store_wrapped_denom, store_wrapped_token, token_ratio_remove, Config, WrappedToken,
 };
 
 use oraiswap::converter::{
-    ConfigResponse, ConvertInfoResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg,
-    QueryMsg, TokenInfo, TokenRatio,
+    ConfigResponse, ConvertInfoResponse, ConvertLimit, ConvertLimitResponse, Cw20HookMsg,
+    ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, TokenInfo, TokenRatio, WrappedTokenResponse,
 };
 
+const SECONDS_PER_DAY: u64 = 86400;
+const MAX_FEE_BPS: u16 = 10000;
+
 use oraiswap::asset::{Asset, AssetInfo};
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -40,9 +47,19 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
         ExecuteMsg::UpdateConfig { owner } => update_config(deps, info, owner),
         ExecuteMsg::UpdatePair { from, to } => update_pair(deps, info, from, to),
         ExecuteMsg::UnregisterPair { from } => unregister_pair(deps, info, from),
+        ExecuteMsg::UpdateConvertLimit { from, limit } => {
+            update_convert_limit(deps, info, from, limit)
+        }
         ExecuteMsg::Convert {} => convert(deps, env, info),
         ExecuteMsg::ConvertReverse { from_asset } => convert_reverse(deps, env, info, from_asset),
         ExecuteMsg::WithdrawTokens { asset_infos } => withdraw_tokens(deps, env, info, asset_infos),
+        ExecuteMsg::RegisterWrappedToken { cw20, subdenom } => {
+            register_wrapped_token(deps, env, info, cw20, subdenom)
+        }
+        ExecuteMsg::UpdateWrappedToken { cw20, paused } => {
+            update_wrapped_token(deps, info, cw20, paused)
+        }
+        ExecuteMsg::Unwrap {} => unwrap(deps, env, info),
     }
 }
 
@@ -62,7 +79,7 @@ pub fn update_config(deps: DepsMut, info: MessageInfo, owner: Addr) -> StdResult
 
 pub fn receive_cw20(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     cw20_msg: Cw20ReceiveMsg,
 ) -> StdResult<Response> {
@@ -71,7 +88,13 @@ pub fn receive_cw20(
             // check permission
             let token_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
             let token_ratio = read_token_ratio(deps.storage, token_raw.as_slice())?;
-            let amount = cw20_msg.amount * token_ratio.ratio;
+            let (amount, fee_amount) = apply_convert_limit(
+                deps.storage,
+                &env,
+                token_raw.as_slice(),
+                cw20_msg.amount,
+                cw20_msg.amount * token_ratio.ratio,
+            )?;
             let message = Asset {
                 info: token_ratio.info,
                 amount: amount.clone(),
@@ -82,11 +105,16 @@ pub fn receive_cw20(
                 deps.api.addr_validate(cw20_msg.sender.as_str())?,
             )?;
 
-            Ok(Response::new().add_message(message).add_attributes(vec![
+            let mut response = Response::new().add_message(message).add_attributes(vec![
                 ("action", "convert_token"),
                 ("from_amount", &cw20_msg.amount.to_string()),
                 ("to_amount", &amount.to_string()),
-            ]))
+            ]);
+            if let Some(fee_amount) = fee_amount {
+                response = response.add_attribute("fee_amount", fee_amount);
+            }
+
+            Ok(response)
         }
         Ok(Cw20HookMsg::ConvertReverse { from }) => {
             let asset_key = from.to_vec(deps.api)?;
@@ -118,6 +146,10 @@ pub fn receive_cw20(
                 return Err(StdError::generic_err("invalid cw20 hook message"));
             }
         }
+        Ok(Cw20HookMsg::Wrap {}) => {
+            let sender = deps.api.addr_validate(cw20_msg.sender.as_str())?;
+            wrap(deps, env, info.sender, sender, cw20_msg.amount)
+        }
         Err(_) => Err(StdError::generic_err("invalid cw20 hook message")),
     }
 }
@@ -157,11 +189,83 @@ pub fn unregister_pair(deps: DepsMut, info: MessageInfo, from: TokenInfo) -> Std
     let asset_key = from.info.to_vec(deps.api)?;
 
     token_ratio_remove(deps.storage, &asset_key);
+    store_convert_limit(deps.storage, &asset_key, None)?;
 
     Ok(Response::new().add_attribute("action", "unregister_convert_info"))
 }
 
-pub fn convert(deps: DepsMut, _env: Env, info: MessageInfo) -> StdResult<Response> {
+pub fn update_convert_limit(
+    deps: DepsMut,
+    info: MessageInfo,
+    from: AssetInfo,
+    limit: Option<ConvertLimit>,
+) -> StdResult<Response> {
+    let config: Config = read_config(deps.storage)?;
+    if config.owner != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let asset_key = from.to_vec(deps.api)?;
+    // the pair must be registered
+    read_token_ratio(deps.storage, &asset_key)?;
+
+    if let Some(limit) = &limit {
+        if limit.fee_bps > MAX_FEE_BPS {
+            return Err(StdError::generic_err(format!(
+                "fee_bps must not exceed {}",
+                MAX_FEE_BPS
+            )));
+        }
+    }
+
+    store_convert_limit(deps.storage, &asset_key, limit.as_ref())?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "update_convert_limit"),
+        ("from", &from.to_string()),
+    ]))
+}
+
+/// Takes the conversion fee and checks the daily cap of the pair if it has a limit,
+/// returns the amount to pay and the fee taken
+fn apply_convert_limit(
+    storage: &mut dyn Storage,
+    env: &Env,
+    asset_key: &[u8],
+    from_amount: Uint128,
+    to_amount: Uint128,
+) -> StdResult<(Uint128, Option<Uint128>)> {
+    let limit = match read_convert_limit(storage, asset_key)? {
+        Some(limit) => limit,
+        None => return Ok((to_amount, None)),
+    };
+
+    // the daily volume is reset on a new day
+    let day = env.block.time.seconds() / SECONDS_PER_DAY;
+    let mut stats = read_convert_stats(storage, asset_key)?;
+    if stats.day != day {
+        stats.day = day;
+        stats.daily_volume = Uint128::zero();
+    }
+
+    stats.daily_volume = stats.daily_volume.checked_add(from_amount)?;
+    if let Some(daily_cap) = limit.daily_cap {
+        if stats.daily_volume > daily_cap {
+            return Err(StdError::generic_err(format!(
+                "Daily conversion cap exceeded, remaining amount today is {}",
+                daily_cap.saturating_sub(stats.daily_volume - from_amount)
+            )));
+        }
+    }
+
+    let fee_amount = limit.compute_fee(to_amount);
+    stats.collected_fee += fee_amount;
+    store_convert_stats(storage, asset_key, &stats)?;
+
+    Ok((to_amount.checked_sub(fee_amount)?, Some(fee_amount)))
+}
+
+pub fn convert(deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Response> {
     let mut messages: Vec<CosmosMsg> = vec![];
     let mut attributes: Vec<Attribute> = vec![];
     attributes.push(("action", "convert_token").into());
@@ -172,9 +276,18 @@ pub fn convert(deps: DepsMut, _env: Env, info: MessageInfo) -> StdResult<Respons
         attributes.push(("denom", native_coin.denom.clone()).into());
         attributes.push(("from_amount", amount.to_string()).into());
         let token_ratio = read_token_ratio(deps.storage, asset_key)?;
-        let to_amount = amount * token_ratio.ratio;
+        let (to_amount, fee_amount) = apply_convert_limit(
+            deps.storage,
+            &env,
+            asset_key,
+            amount,
+            amount * token_ratio.ratio,
+        )?;
 
         attributes.push(("to_amount", to_amount).into());
+        if let Some(fee_amount) = fee_amount {
+            attributes.push(("fee_amount", fee_amount).into());
+        }
 
         let message = Asset {
             info: token_ratio.info,
@@ -223,11 +336,158 @@ pub fn convert_reverse(
     }
 }
 
+pub fn register_wrapped_token(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20: Addr,
+    subdenom: String,
+) -> StdResult<Response> {
+    let config: Config = read_config(deps.storage)?;
+    if config.owner != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let cw20_raw = deps.api.addr_canonicalize(cw20.as_str())?;
+    if read_wrapped_token(deps.storage, &cw20_raw)?.is_some() {
+        return Err(StdError::generic_err("cw20 token is already wrapped"));
+    }
+    let denom = tokenfactory_denom(&env.contract.address, &subdenom);
+    if read_wrapped_denom(deps.storage, &denom)?.is_some() {
+        return Err(StdError::generic_err("wrapped denom is already used"));
+    }
+
+    store_wrapped_token(
+        deps.storage,
+        &cw20_raw,
+        &WrappedToken {
+            denom: denom.clone(),
+            supply: Uint128::zero(),
+            paused: false,
+        },
+    )?;
+    store_wrapped_denom(deps.storage, &denom, &cw20_raw)?;
+
+    Ok(Response::new()
+        .add_message(create_denom_msg(&env.contract.address, &subdenom)?)
+        .add_attributes(vec![
+            ("action", "register_wrapped_token"),
+            ("cw20", cw20.as_str()),
+            ("denom", &denom),
+        ]))
+}
+
+pub fn update_wrapped_token(
+    deps: DepsMut,
+    info: MessageInfo,
+    cw20: Addr,
+    paused: bool,
+) -> StdResult<Response> {
+    let config: Config = read_config(deps.storage)?;
+    if config.owner != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let cw20_raw = deps.api.addr_canonicalize(cw20.as_str())?;
+    let mut wrapped_token = read_wrapped_token(deps.storage, &cw20_raw)?
+        .ok_or_else(|| StdError::generic_err("cw20 token is not wrapped"))?;
+    wrapped_token.paused = paused;
+    store_wrapped_token(deps.storage, &cw20_raw, &wrapped_token)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "update_wrapped_token"),
+        ("cw20", cw20.as_str()),
+        ("paused", &paused.to_string()),
+    ]))
+}
+
+/// the wrapped token of the cw20 token, wrapping and unwrapping are rejected while it is paused
+fn load_wrapped_token(storage: &dyn Storage, cw20: &CanonicalAddr) -> StdResult<WrappedToken> {
+    let wrapped_token = read_wrapped_token(storage, cw20)?
+        .ok_or_else(|| StdError::generic_err("cw20 token is not wrapped"))?;
+    if wrapped_token.paused {
+        return Err(StdError::generic_err("wrapping the cw20 token is paused"));
+    }
+    Ok(wrapped_token)
+}
+
+/// mint the wrapped denom 1:1 for the cw20 tokens received and send it to the sender
+pub fn wrap(
+    deps: DepsMut,
+    env: Env,
+    cw20: Addr,
+    sender: Addr,
+    amount: Uint128,
+) -> StdResult<Response> {
+    let cw20_raw = deps.api.addr_canonicalize(cw20.as_str())?;
+    let mut wrapped_token = load_wrapped_token(deps.storage, &cw20_raw)?;
+    wrapped_token.supply = wrapped_token.supply.checked_add(amount)?;
+    store_wrapped_token(deps.storage, &cw20_raw, &wrapped_token)?;
+
+    Ok(Response::new()
+        .add_messages(vec![
+            mint_msg(&env.contract.address, &wrapped_token.denom, amount)?,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: sender.to_string(),
+                amount: coins(amount.u128(), &wrapped_token.denom),
+            }),
+        ])
+        .add_attributes(vec![
+            ("action", "wrap"),
+            ("cw20", cw20.as_str()),
+            ("denom", &wrapped_token.denom),
+            ("amount", &amount.to_string()),
+        ]))
+}
+
+/// burn the wrapped denoms sent as funds and return the backing cw20 tokens 1:1
+pub fn unwrap(deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Response> {
+    if info.funds.is_empty() {
+        return Err(StdError::generic_err("no wrapped denom to unwrap"));
+    }
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut attributes: Vec<Attribute> = vec![("action", "unwrap").into()];
+    for native_coin in info.funds {
+        let cw20_raw = read_wrapped_denom(deps.storage, &native_coin.denom)?
+            .ok_or_else(|| StdError::generic_err("denom is not a wrapped cw20 token"))?;
+        let mut wrapped_token = load_wrapped_token(deps.storage, &cw20_raw)?;
+        wrapped_token.supply = wrapped_token.supply.checked_sub(native_coin.amount)?;
+        store_wrapped_token(deps.storage, &cw20_raw, &wrapped_token)?;
+
+        let cw20 = deps.api.addr_humanize(&cw20_raw)?;
+        messages.push(burn_msg(
+            &env.contract.address,
+            &native_coin.denom,
+            native_coin.amount,
+        )?);
+        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: cw20.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: info.sender.to_string(),
+                amount: native_coin.amount,
+            })?,
+            funds: vec![],
+        }));
+        attributes.push(("denom", native_coin.denom).into());
+        attributes.push(("cw20", cw20).into());
+        attributes.push(("amount", native_coin.amount).into());
+    }
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attributes(attributes))
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => to_binary(&query_config(deps)?),
         QueryMsg::ConvertInfo { asset_info } => to_binary(&query_convert_info(deps, asset_info)?),
+        QueryMsg::ConvertLimit { asset_info } => {
+            to_binary(&query_convert_limit(deps, env, asset_info)?)
+        }
+        QueryMsg::WrappedToken { cw20 } => to_binary(&query_wrapped_token(deps, cw20)?),
     }
 }
 
@@ -246,6 +506,39 @@ pub fn query_convert_info(deps: Deps, asset_info: AssetInfo) -> StdResult<Conver
     Ok(ConvertInfoResponse { token_ratio })
 }
 
+pub fn query_convert_limit(
+    deps: Deps,
+    env: Env,
+    asset_info: AssetInfo,
+) -> StdResult<ConvertLimitResponse> {
+    let asset_key = asset_info.to_vec(deps.api)?;
+    let stats = read_convert_stats(deps.storage, &asset_key)?;
+    let daily_volume = if stats.day == env.block.time.seconds() / SECONDS_PER_DAY {
+        stats.daily_volume
+    } else {
+        Uint128::zero()
+    };
+
+    Ok(ConvertLimitResponse {
+        limit: read_convert_limit(deps.storage, &asset_key)?,
+        daily_volume,
+        collected_fee: stats.collected_fee,
+    })
+}
+
+pub fn query_wrapped_token(deps: Deps, cw20: Addr) -> StdResult<WrappedTokenResponse> {
+    let wrapped_token =
+        read_wrapped_token(deps.storage, &deps.api.addr_canonicalize(cw20.as_str())?)?
+            .ok_or_else(|| StdError::generic_err("cw20 token is not wrapped"))?;
+
+    Ok(WrappedTokenResponse {
+        cw20,
+        denom: wrapped_token.denom,
+        supply: wrapped_token.supply,
+        paused: wrapped_token.paused,
+    })
+}
+
 pub fn withdraw_tokens(
     deps: DepsMut,
     env: Env,
@@ -261,7 +554,14 @@ pub fn withdraw_tokens(
     let mut attributes: Vec<Attribute> = vec![("action", "withdraw_tokens").into()];
 
     for asset in asset_infos {
-        let balance = asset.query_pool(&deps.querier, env.contract.address.clone())?;
+        let mut balance = asset.query_pool(&deps.querier, env.contract.address.clone())?;
+        // the cw20 tokens backing the wrapped supply are not withdrawable
+        if let AssetInfo::Token { contract_addr } = &asset {
+            let cw20_raw = deps.api.addr_canonicalize(contract_addr.as_str())?;
+            if let Some(wrapped_token) = read_wrapped_token(deps.storage, &cw20_raw)? {
+                balance = balance.saturating_sub(wrapped_token.supply);
+            }
+        }
         let message = Asset {
             info: asset,
             amount: balance.clone(),
@@ -1,18 +1,39 @@
 use cosmwasm_schema::cw_serde;
 
-use cosmwasm_std::{CanonicalAddr, StdResult, Storage};
+use cosmwasm_std::{CanonicalAddr, StdResult, Storage, Uint128};
 use cosmwasm_storage::{singleton, singleton_read, Bucket, ReadonlyBucket};
 
 static KEY_CONFIG: &[u8] = b"config";
 static KEY_TOKEN_RATIO: &[u8] = b"token_ratio";
+static KEY_CONVERT_LIMIT: &[u8] = b"convert_limit";
+static KEY_CONVERT_STATS: &[u8] = b"convert_stats";
+static KEY_WRAPPED_TOKEN: &[u8] = b"wrapped_token";
+static KEY_WRAPPED_DENOM: &[u8] = b"wrapped_denom";
 
-use oraiswap::converter::TokenRatio;
+use oraiswap::converter::{ConvertLimit, TokenRatio};
 
 #[cw_serde]
 pub struct Config {
     pub owner: CanonicalAddr,
 }
 
+#[cw_serde]
+#[derive(Default)]
+pub struct ConvertStats {
+    // day index of the daily volume, block time in seconds / 86400
+    pub day: u64,
+    pub daily_volume: Uint128,
+    pub collected_fee: Uint128,
+}
+
+/// tokenfactory denom minted 1:1 for the cw20 token held by the converter
+#[cw_serde]
+pub struct WrappedToken {
+    pub denom: String,
+    pub supply: Uint128,
+    pub paused: bool,
+}
+
 pub fn store_config(storage: &mut dyn Storage, config: &Config) -> StdResult<()> {
     singleton(storage, KEY_CONFIG).save(config)
 }
@@ -40,3 +61,66 @@ pub fn read_token_ratio(storage: &dyn Storage, asset_key: &[u8]) -> StdResult<To
 pub fn token_ratio_remove<'a>(storage: &'a mut dyn Storage, asset_key: &[u8]) {
     Bucket::<'a, TokenRatio>::new(storage, KEY_TOKEN_RATIO).remove(asset_key)
 }
+
+pub fn store_convert_limit(
+    storage: &mut dyn Storage,
+    asset_key: &[u8],
+    limit: Option<&ConvertLimit>,
+) -> StdResult<()> {
+    let mut bucket: Bucket<ConvertLimit> = Bucket::new(storage, KEY_CONVERT_LIMIT);
+    match limit {
+        Some(limit) => bucket.save(asset_key, limit),
+        None => {
+            bucket.remove(asset_key);
+            Ok(())
+        }
+    }
+}
+
+pub fn read_convert_limit(
+    storage: &dyn Storage,
+    asset_key: &[u8],
+) -> StdResult<Option<ConvertLimit>> {
+    ReadonlyBucket::new(storage, KEY_CONVERT_LIMIT).may_load(asset_key)
+}
+
+pub fn store_convert_stats(
+    storage: &mut dyn Storage,
+    asset_key: &[u8],
+    stats: &ConvertStats,
+) -> StdResult<()> {
+    Bucket::new(storage, KEY_CONVERT_STATS).save(asset_key, stats)
+}
+
+pub fn read_convert_stats(storage: &dyn Storage, asset_key: &[u8]) -> StdResult<ConvertStats> {
+    Ok(ReadonlyBucket::new(storage, KEY_CONVERT_STATS)
+        .may_load(asset_key)?
+        .unwrap_or_default())
+}
+
+pub fn store_wrapped_token(
+    storage: &mut dyn Storage,
+    cw20: &CanonicalAddr,
+    wrapped_token: &WrappedToken,
+) -> StdResult<()> {
+    Bucket::new(storage, KEY_WRAPPED_TOKEN).save(cw20.as_slice(), wrapped_token)
+}
+
+pub fn read_wrapped_token(
+    storage: &dyn Storage,
+    cw20: &CanonicalAddr,
+) -> StdResult<Option<WrappedToken>> {
+    ReadonlyBucket::new(storage, KEY_WRAPPED_TOKEN).may_load(cw20.as_slice())
+}
+
+pub fn store_wrapped_denom(
+    storage: &mut dyn Storage,
+    denom: &str,
+    cw20: &CanonicalAddr,
+) -> StdResult<()> {
+    Bucket::new(storage, KEY_WRAPPED_DENOM).save(denom.as_bytes(), cw20)
+}
+
+pub fn read_wrapped_denom(storage: &dyn Storage, denom: &str) -> StdResult<Option<CanonicalAddr>> {
+    ReadonlyBucket::new(storage, KEY_WRAPPED_DENOM).may_load(denom.as_bytes())
+}
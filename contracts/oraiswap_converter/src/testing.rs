@@ -1,16 +1,20 @@
 use std::str::FromStr;
 
 use cosmwasm_std::{
-    attr, coin,
+    attr, coin, from_binary,
     testing::{mock_dependencies, mock_dependencies_with_balance, mock_env, mock_info},
     to_binary, Addr, BankMsg, CosmosMsg, Decimal, StdError, SubMsg, Uint128, WasmMsg,
 };
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
 use oraiswap::{
     asset::{AssetInfo, ORAI_DENOM},
-    converter::{Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg, TokenInfo},
+    converter::{
+        ConvertLimit, ConvertLimitResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg,
+        TokenInfo, WrappedTokenResponse,
+    },
     math::Converter128,
     testing::ATOM_DENOM,
+    tokenfactory::{burn_msg, create_denom_msg, mint_msg, tokenfactory_denom},
 };
 
 use crate::contract::{execute, instantiate, query};
@@ -274,6 +278,136 @@ fn test_remove_pair() {
     };
 }
 
+#[test]
+fn test_convert_limit() {
+    let mut deps = mock_dependencies();
+
+    let msg = InstantiateMsg {};
+    let info = mock_info("addr", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let msg = ExecuteMsg::UpdatePair {
+        from: TokenInfo {
+            info: AssetInfo::NativeToken {
+                denom: ORAI_DENOM.to_string(),
+            },
+            decimals: 6,
+        },
+        to: TokenInfo {
+            info: AssetInfo::Token {
+                contract_addr: Addr::unchecked("asset2"),
+            },
+            decimals: 6,
+        },
+    };
+    let info = mock_info("addr", &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // 1% fee and at most 1000 orai converted per day
+    let limit = ConvertLimit {
+        fee_bps: 100,
+        daily_cap: Some(Uint128::from(1000u128)),
+    };
+    let msg = ExecuteMsg::UpdateConvertLimit {
+        from: AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        limit: Some(limit.clone()),
+    };
+
+    // unauthorized
+    let info = mock_info("addr0000", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap_err();
+    assert_eq!(res, StdError::generic_err("unauthorized"));
+
+    // fee is too high
+    let info = mock_info("addr", &[]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        ExecuteMsg::UpdateConvertLimit {
+            from: AssetInfo::NativeToken {
+                denom: ORAI_DENOM.to_string(),
+            },
+            limit: Some(ConvertLimit {
+                fee_bps: 10001,
+                daily_cap: None,
+            }),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(res, StdError::generic_err("fee_bps must not exceed 10000"));
+
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let info = mock_info("addr0000", &[coin(600u128, ORAI_DENOM)]);
+    let res = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::Convert {}).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "asset2".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "addr0000".to_string(),
+                amount: Uint128::from(594u128)
+            })
+            .unwrap(),
+            funds: vec![]
+        }))]
+    );
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "convert_token"),
+            attr("denom", ORAI_DENOM),
+            attr("from_amount", "600"),
+            attr("to_amount", "594"),
+            attr("fee_amount", "6"),
+        ]
+    );
+
+    // exceeds the daily cap
+    let info = mock_info("addr0000", &[coin(500u128, ORAI_DENOM)]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        ExecuteMsg::Convert {},
+    )
+    .unwrap_err();
+    assert_eq!(
+        res,
+        StdError::generic_err("Daily conversion cap exceeded, remaining amount today is 400")
+    );
+
+    // the daily volume is reset on the next day
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(86400);
+    let _res = execute(deps.as_mut(), env.clone(), info, ExecuteMsg::Convert {}).unwrap();
+
+    let res: ConvertLimitResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            env,
+            QueryMsg::ConvertLimit {
+                asset_info: AssetInfo::NativeToken {
+                    denom: ORAI_DENOM.to_string(),
+                },
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res,
+        ConvertLimitResponse {
+            limit: Some(limit),
+            daily_volume: Uint128::from(500u128),
+            collected_fee: Uint128::from(11u128),
+        }
+    );
+}
+
 #[test]
 fn test_withdraw_tokens() {
     let mut deps = mock_dependencies_with_balance(&[
@@ -350,3 +484,166 @@ fn test_withdraw_tokens() {
         _ => panic!("Must return unauthorized"),
     };
 }
+
+#[test]
+fn test_wrap_cw20() {
+    let mut deps = mock_dependencies();
+
+    let msg = InstantiateMsg {};
+    let info = mock_info("addr", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let env = mock_env();
+    let denom = tokenfactory_denom(&env.contract.address, "usdt");
+    let msg = ExecuteMsg::RegisterWrappedToken {
+        cw20: Addr::unchecked("usdt_token"),
+        subdenom: "usdt".to_string(),
+    };
+
+    // unauthorized
+    let info = mock_info("addr0000", &[]);
+    let res = execute(deps.as_mut(), env.clone(), info, msg.clone()).unwrap_err();
+    assert_eq!(res, StdError::generic_err("unauthorized"));
+
+    let info = mock_info("addr", &[]);
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(
+            create_denom_msg(&env.contract.address, "usdt").unwrap()
+        )]
+    );
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+    assert_eq!(res, StdError::generic_err("cw20 token is already wrapped"));
+
+    // wrap 100 usdt tokens into the native denom
+    let wrap_msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr0000".to_string(),
+        amount: Uint128::from(100u128),
+        msg: to_binary(&Cw20HookMsg::Wrap {}).unwrap(),
+    });
+    let info = mock_info("usdt_token", &[]);
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), wrap_msg.clone()).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(mint_msg(&env.contract.address, &denom, Uint128::from(100u128)).unwrap()),
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "addr0000".to_string(),
+                amount: vec![coin(100u128, denom.clone())],
+            })),
+        ]
+    );
+
+    // other cw20 tokens can not be wrapped
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("asset2", &[]),
+        wrap_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(res, StdError::generic_err("cw20 token is not wrapped"));
+
+    // paused token can not be wrapped nor unwrapped
+    let pause_msg = |paused: bool| ExecuteMsg::UpdateWrappedToken {
+        cw20: Addr::unchecked("usdt_token"),
+        paused,
+    };
+    let _res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr", &[]),
+        pause_msg(true),
+    )
+    .unwrap();
+    let res = execute(deps.as_mut(), env.clone(), info, wrap_msg).unwrap_err();
+    assert_eq!(
+        res,
+        StdError::generic_err("wrapping the cw20 token is paused")
+    );
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0000", &[coin(40u128, denom.clone())]),
+        ExecuteMsg::Unwrap {},
+    )
+    .unwrap_err();
+    assert_eq!(
+        res,
+        StdError::generic_err("wrapping the cw20 token is paused")
+    );
+    let _res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr", &[]),
+        pause_msg(false),
+    )
+    .unwrap();
+
+    // unwrap burns the denom and returns the cw20 tokens
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0000", &[coin(40u128, denom.clone())]),
+        ExecuteMsg::Unwrap {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(burn_msg(&env.contract.address, &denom, Uint128::from(40u128)).unwrap()),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "usdt_token".to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: "addr0000".to_string(),
+                    amount: Uint128::from(40u128),
+                })
+                .unwrap(),
+                funds: vec![],
+            })),
+        ]
+    );
+
+    let res: WrappedTokenResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::WrappedToken {
+                cw20: Addr::unchecked("usdt_token"),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res,
+        WrappedTokenResponse {
+            cw20: Addr::unchecked("usdt_token"),
+            denom: denom.clone(),
+            supply: Uint128::from(60u128),
+            paused: false,
+        }
+    );
+
+    // can not unwrap more than the wrapped supply
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0000", &[coin(61u128, denom)]),
+        ExecuteMsg::Unwrap {},
+    );
+    assert!(res.is_err());
+
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info("addr0000", &[coin(10u128, ORAI_DENOM)]),
+        ExecuteMsg::Unwrap {},
+    )
+    .unwrap_err();
+    assert_eq!(
+        res,
+        StdError::generic_err("denom is not a wrapped cw20 token")
+    );
+}
@@ -1,25 +1,42 @@
 use std::convert::TryFrom;
+use std::str::FromStr;
 
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 
 use cosmwasm_std::{
-    to_binary, Addr, Binary, CanonicalAddr, Deps, DepsMut, Env, MessageInfo, Reply, Response,
-    StdError, StdResult, SubMsg, WasmMsg,
+    coin, to_binary, Addr, Binary, CanonicalAddr, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env,
+    MessageInfo, Order, Reply, Response, StdError, StdResult, SubMsg, Uint128, WasmMsg,
 };
+use cw20::Cw20ExecuteMsg;
 use oraiswap::error::ContractError;
+use oraiswap::oracle::OracleContract;
+use oraiswap::pagination::Cursor;
 use oraiswap::querier::query_pair_info_from_pair;
 use oraiswap::response::MsgInstantiateContractResponse;
 
-use crate::state::{read_pairs, Config, CONFIG, PAIRS};
+use crate::state::{
+    read_pair_template, read_pair_templates, read_pairs, Config, PairTvl, CONFIG, PAIRS,
+    PAIR_TEMPLATES, PAIR_TVLS, TOTAL_TVL, WEIGHTED_PAIRS,
+};
 
-use oraiswap::asset::{pair_key, AssetInfo, PairInfo, PairInfoRaw};
+use oraiswap::asset::{pair_key, Asset, AssetInfo, PairInfo, PairInfoRaw, ORAI_DENOM};
 use oraiswap::factory::{
-    ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, PairsResponse, QueryMsg,
+    ConfigResponse, CreatePairParams, ExecuteMsg, InstantiateMsg, MigrateMsg, PairTemplate,
+    PairType, PairsResponse, QueryMsg, TotalTvlResponse, TvlResponse,
+};
+use oraiswap::pair::{
+    ExecuteMsg as PairExecuteMsg, InstantiateMsg as PairInstantiateMsg, LpTokenInfo, LpTokenType,
+    DEFAULT_COMMISSION_RATE,
+};
+use oraiswap::weighted_pair::{
+    validate_weighted_asset_infos, weighted_pair_key, InstantiateMsg as WeightedPairInstantiateMsg,
+    QueryMsg as WeightedPairQueryMsg, WeightedAssetInfo, WeightedAssetInfoRaw, WeightedPairInfo,
+    WeightedPairInfoRaw, WeightedPairResponse,
 };
-use oraiswap::pair::{InstantiateMsg as PairInstantiateMsg, DEFAULT_COMMISSION_RATE};
 
 const INSTANTIATE_REPLY_ID: u64 = 1;
+const WEIGHTED_INSTANTIATE_REPLY_ID: u64 = 2;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -36,6 +53,8 @@ pub fn instantiate(
         commission_rate: msg
             .commission_rate
             .unwrap_or(DEFAULT_COMMISSION_RATE.to_string()),
+        guardian: None,
+        staking_contract: None,
     };
 
     CONFIG.save(deps.storage, &config)?;
@@ -55,17 +74,66 @@ pub fn execute(
             owner,
             token_code_id,
             pair_code_id,
-        } => execute_update_config(deps, env, info, owner, token_code_id, pair_code_id),
+            commission_rate,
+            guardian,
+            staking_contract,
+        } => execute_update_config(
+            deps,
+            env,
+            info,
+            owner,
+            token_code_id,
+            pair_code_id,
+            commission_rate,
+            guardian,
+            staking_contract,
+        ),
+        ExecuteMsg::UpdatePairTemplate { template } => {
+            execute_update_pair_template(deps, info, template)
+        }
         ExecuteMsg::CreatePair {
             asset_infos,
             pair_admin,
-        } => execute_create_pair(deps, env, info, asset_infos, pair_admin),
+            pair_type,
+            lp_token_info,
+            lp_token_type,
+        } => execute_create_pair(
+            deps,
+            env,
+            info,
+            asset_infos,
+            pair_admin,
+            pair_type,
+            lp_token_info,
+            lp_token_type,
+        ),
+        ExecuteMsg::CreateWeightedPair {
+            asset_infos,
+            pair_admin,
+            lp_token_info,
+        } => execute_create_weighted_pair(deps, env, info, asset_infos, pair_admin, lp_token_info),
+        ExecuteMsg::CreatePairs { pairs } => execute_create_pairs(deps, env, info, pairs),
         ExecuteMsg::AddPair { pair_info } => execute_add_pair_manually(deps, env, info, pair_info),
+        ExecuteMsg::ProvideLiquidityToPair {
+            asset_infos,
+            assets,
+            slippage_tolerance,
+            auto_stake,
+        } => execute_provide_liquidity_to_pair(
+            deps,
+            env,
+            info,
+            asset_infos,
+            assets,
+            slippage_tolerance,
+            auto_stake,
+        ),
         ExecuteMsg::MigrateContract {
             contract_addr,
             new_code_id,
             msg,
         } => migrate_pair(deps, env, info, contract_addr, new_code_id, msg),
+        ExecuteMsg::UpdateTvl { cursor, limit } => execute_update_tvl(deps, env, cursor, limit),
     }
 }
 
@@ -95,6 +163,7 @@ pub fn migrate_pair(
 }
 
 // Only owner can execute it
+#[allow(clippy::too_many_arguments)]
 pub fn execute_update_config(
     deps: DepsMut,
     _env: Env,
@@ -102,6 +171,9 @@ pub fn execute_update_config(
     owner: Option<String>,
     token_code_id: Option<u64>,
     pair_code_id: Option<u64>,
+    commission_rate: Option<String>,
+    guardian: Option<String>,
+    staking_contract: Option<String>,
 ) -> Result<Response, ContractError> {
     let mut config = CONFIG.load(deps.storage)?;
 
@@ -122,20 +194,145 @@ pub fn execute_update_config(
         config.pair_code_id = pair_code_id;
     }
 
+    if let Some(commission_rate) = commission_rate {
+        validate_commission_rate(&commission_rate)?;
+        config.commission_rate = commission_rate;
+    }
+
+    if let Some(guardian) = guardian {
+        config.guardian = Some(deps.api.addr_canonicalize(&guardian)?);
+    }
+
+    if let Some(staking_contract) = staking_contract {
+        config.staking_contract = Some(deps.api.addr_canonicalize(&staking_contract)?);
+    }
+
     CONFIG.save(deps.storage, &config)?;
 
     Ok(Response::new().add_attribute("action", "update_config"))
 }
 
+// Only owner can execute it
+pub fn execute_update_pair_template(
+    deps: DepsMut,
+    info: MessageInfo,
+    template: PairTemplate,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if deps.api.addr_canonicalize(info.sender.as_str())? != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    validate_commission_rate(&template.commission_rate)?;
+
+    PAIR_TEMPLATES.save(deps.storage, template.pair_type.as_bytes(), &template)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "update_pair_template"),
+        ("pair_code_id", &template.pair_code_id.to_string()),
+        ("commission_rate", &template.commission_rate),
+    ]))
+}
+
+fn validate_commission_rate(commission_rate: &str) -> StdResult<()> {
+    let rate = Decimal::from_str(commission_rate)?;
+    if rate >= Decimal::one() {
+        return Err(StdError::generic_err("commission rate must be less than 1"));
+    }
+    Ok(())
+}
+
 // Anyone can execute it to create swap pair
+#[allow(clippy::too_many_arguments)]
 pub fn execute_create_pair(
     deps: DepsMut,
     env: Env,
     _info: MessageInfo,
     asset_infos: [AssetInfo; 2],
     pair_admin: Option<String>,
+    pair_type: Option<PairType>,
+    lp_token_info: Option<LpTokenInfo>,
+    lp_token_type: Option<LpTokenType>,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let create_pair_msg = create_pair_submsg(
+        deps,
+        &env,
+        &config,
+        &asset_infos,
+        pair_admin,
+        pair_type,
+        lp_token_info,
+        lp_token_type,
+    )?;
+
+    Ok(Response::new()
+        .add_submessage(create_pair_msg)
+        .add_attributes(vec![
+            ("action", "create_pair"),
+            ("pair", &format!("{}-{}", asset_infos[0], asset_infos[1])),
+        ]))
+}
+
+// Anyone can execute it to create a batch of swap pairs, each pair is registered by its own reply
+pub fn execute_create_pairs(
+    mut deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    pairs: Vec<CreatePairParams>,
 ) -> Result<Response, ContractError> {
+    if pairs.is_empty() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "No pairs to create",
+        )));
+    }
+
     let config: Config = CONFIG.load(deps.storage)?;
+    let mut create_pair_msgs = vec![];
+    let mut pair_names = vec![];
+    for pair in pairs {
+        create_pair_msgs.push(create_pair_submsg(
+            deps.branch(),
+            &env,
+            &config,
+            &pair.asset_infos,
+            pair.pair_admin,
+            pair.pair_type,
+            pair.lp_token_info,
+            pair.lp_token_type,
+        )?);
+        pair_names.push(format!("{}-{}", pair.asset_infos[0], pair.asset_infos[1]));
+    }
+
+    Ok(Response::new()
+        .add_submessages(create_pair_msgs)
+        .add_attributes(vec![
+            ("action", "create_pairs"),
+            ("pairs", &pair_names.join(",")),
+        ]))
+}
+
+/// reserve the pair key then instantiate the pair, the reply fills in the pair and liquidity token addresses
+#[allow(clippy::too_many_arguments)]
+fn create_pair_submsg(
+    deps: DepsMut,
+    env: &Env,
+    config: &Config,
+    asset_infos: &[AssetInfo; 2],
+    pair_admin: Option<String>,
+    pair_type: Option<PairType>,
+    lp_token_info: Option<LpTokenInfo>,
+    lp_token_type: Option<LpTokenType>,
+) -> Result<SubMsg, ContractError> {
+    let pair_type = pair_type.unwrap_or_default();
+    if pair_type == PairType::Weighted {
+        return Err(ContractError::Std(StdError::generic_err(
+            "weighted pairs are created with CreateWeightedPair",
+        )));
+    }
+    let template = read_pair_template(deps.storage, config, pair_type)?;
     let raw_infos = [
         asset_infos[0].to_raw(deps.api)?,
         asset_infos[1].to_raw(deps.api)?,
@@ -156,29 +353,113 @@ pub fn execute_create_pair(
             liquidity_token: CanonicalAddr::from(vec![]),
             contract_addr: CanonicalAddr::from(vec![]),
             asset_infos: raw_infos,
-            commission_rate: config.commission_rate.clone(),
+            commission_rate: template.commission_rate.clone(),
+            lp_denom: None,
         },
     )?;
 
+    Ok(SubMsg::reply_on_success(
+        WasmMsg::Instantiate {
+            code_id: template.pair_code_id,
+            funds: vec![],
+            admin: Some(pair_admin.unwrap_or(env.contract.address.to_string())),
+            label: "pair".to_string(),
+            msg: to_binary(&PairInstantiateMsg {
+                oracle_addr: deps.api.addr_humanize(&config.oracle_addr)?,
+                asset_infos: asset_infos.clone(),
+                token_code_id: config.token_code_id,
+                commission_rate: Some(template.commission_rate),
+                lp_token_info,
+                guardian: config
+                    .guardian
+                    .as_ref()
+                    .map(|guardian| deps.api.addr_humanize(guardian))
+                    .transpose()?,
+                staking_contract: config
+                    .staking_contract
+                    .as_ref()
+                    .map(|staking_contract| deps.api.addr_humanize(staking_contract))
+                    .transpose()?,
+                lp_token_type,
+            })?,
+        },
+        INSTANTIATE_REPLY_ID,
+    ))
+}
+
+// Anyone can execute it to create a weighted pool, the reply fills in the pool and liquidity token addresses
+pub fn execute_create_weighted_pair(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    asset_infos: Vec<WeightedAssetInfo>,
+    pair_admin: Option<String>,
+    lp_token_info: Option<LpTokenInfo>,
+) -> Result<Response, ContractError> {
+    validate_weighted_asset_infos(&asset_infos)?;
+
+    let config: Config = CONFIG.load(deps.storage)?;
+    let template = read_pair_template(deps.storage, &config, PairType::Weighted)?;
+    let raw_infos = asset_infos
+        .iter()
+        .map(|asset_info| {
+            Ok(WeightedAssetInfoRaw {
+                info: asset_info.info.to_raw(deps.api)?,
+                weight: asset_info.weight,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let pair_key = weighted_pair_key(
+        &raw_infos
+            .iter()
+            .map(|asset_info| asset_info.info.clone())
+            .collect::<Vec<_>>(),
+    );
+
+    // can not update pair once updated
+    if let Ok(Some(_)) = WEIGHTED_PAIRS.may_load(deps.storage, &pair_key) {
+        return Err(ContractError::PairExisted {});
+    }
+
+    WEIGHTED_PAIRS.save(
+        deps.storage,
+        &pair_key,
+        &WeightedPairInfoRaw {
+            oracle_addr: config.oracle_addr.clone(),
+            liquidity_token: CanonicalAddr::from(vec![]),
+            contract_addr: CanonicalAddr::from(vec![]),
+            asset_infos: raw_infos,
+            commission_rate: template.commission_rate.clone(),
+        },
+    )?;
+
+    let pair_name = asset_infos
+        .iter()
+        .map(|asset_info| asset_info.info.to_string())
+        .collect::<Vec<_>>()
+        .join("-");
+
     Ok(Response::new()
         .add_submessage(SubMsg::reply_on_success(
             WasmMsg::Instantiate {
-                code_id: config.pair_code_id,
+                code_id: template.pair_code_id,
                 funds: vec![],
                 admin: Some(pair_admin.unwrap_or(env.contract.address.to_string())),
-                label: "pair".to_string(),
-                msg: to_binary(&PairInstantiateMsg {
+                label: "weighted pair".to_string(),
+                msg: to_binary(&WeightedPairInstantiateMsg {
                     oracle_addr: deps.api.addr_humanize(&config.oracle_addr)?,
-                    asset_infos: asset_infos.clone(),
+                    asset_infos,
                     token_code_id: config.token_code_id,
-                    commission_rate: Some(config.commission_rate),
+                    commission_rate: Some(template.commission_rate),
+                    lp_token_info,
                 })?,
             },
-            INSTANTIATE_REPLY_ID,
+            WEIGHTED_INSTANTIATE_REPLY_ID,
         ))
         .add_attributes(vec![
-            ("action", "create_pair"),
-            ("pair", &format!("{}-{}", asset_infos[0], asset_infos[1])),
+            ("action", "create_weighted_pair"),
+            ("pair", &pair_name),
         ]))
 }
 
@@ -220,6 +501,7 @@ pub fn execute_add_pair_manually(
                 .addr_canonicalize(pair_info.contract_addr.as_str())?,
             asset_infos: raw_infos,
             commission_rate: pair_info.commission_rate.clone(),
+            lp_denom: pair_info.lp_denom.clone(),
         },
     )?;
 
@@ -232,9 +514,154 @@ pub fn execute_add_pair_manually(
     ]))
 }
 
-/// This just stores the result for future query
+// Anyone can execute it to provide liquidity without knowing the pair address
+pub fn execute_provide_liquidity_to_pair(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asset_infos: [AssetInfo; 2],
+    assets: [Asset; 2],
+    slippage_tolerance: Option<Decimal>,
+    auto_stake: Option<bool>,
+) -> Result<Response, ContractError> {
+    let pair_info = query_pair(deps.as_ref(), asset_infos)?;
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut funds: Vec<Coin> = vec![];
+    for asset in assets.iter() {
+        if !pair_info.asset_infos.contains(&asset.info) {
+            return Err(ContractError::AssetMismatch {});
+        }
+        asset.assert_sent_native_token_balance(&info)?;
+
+        match &asset.info {
+            AssetInfo::NativeToken { denom } => {
+                if !asset.amount.is_zero() {
+                    funds.push(coin(asset.amount.u128(), denom));
+                }
+            }
+            // pull the token from the sender, then let the pair pull it from the factory
+            AssetInfo::Token { contract_addr } => {
+                messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: contract_addr.to_string(),
+                    msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+                        owner: info.sender.to_string(),
+                        recipient: env.contract.address.to_string(),
+                        amount: asset.amount,
+                    })?,
+                    funds: vec![],
+                }));
+                messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: contract_addr.to_string(),
+                    msg: to_binary(&Cw20ExecuteMsg::IncreaseAllowance {
+                        spender: pair_info.contract_addr.to_string(),
+                        amount: asset.amount,
+                        expires: None,
+                    })?,
+                    funds: vec![],
+                }));
+            }
+        }
+    }
+    // coins must be sorted by denom
+    funds.sort_by(|a, b| a.denom.cmp(&b.denom));
+
+    // LP tokens are minted or staked for the sender
+    messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: pair_info.contract_addr.to_string(),
+        msg: to_binary(&PairExecuteMsg::ProvideLiquidity {
+            assets: assets.clone(),
+            slippage_tolerance,
+            receiver: Some(info.sender.clone()),
+            auto_stake,
+        })?,
+        funds,
+    }));
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "provide_liquidity_to_pair"),
+        ("pair", pair_info.contract_addr.as_str()),
+        ("sender", info.sender.as_str()),
+        ("assets", &format!("{}, {}", assets[0], assets[1])),
+    ]))
+}
+
+/// This stores the result for future query and returns the registered pair info as data
+/// Value the reserves of a page of pairs in orai at the oracle exchange rates,
+/// a pair with one asset unknown to the oracle is valued at twice the other reserve
+pub fn execute_update_tvl(
+    deps: DepsMut,
+    env: Env,
+    cursor: Option<String>,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let oracle_contract = OracleContract(deps.api.addr_humanize(&config.oracle_addr)?);
+
+    let cursor = match cursor {
+        Some(cursor) => Some(Cursor::decode(&cursor)?),
+        None => None,
+    };
+    let (pairs, next_cursor) = read_pairs(deps.storage, deps.api, cursor, limit)?;
+
+    let mut total_tvl = TOTAL_TVL.may_load(deps.storage)?.unwrap_or_default();
+    for pair in pairs.iter() {
+        let values = pair
+            .asset_infos
+            .iter()
+            .map(|asset_info| {
+                let amount = asset_info.query_pool(&deps.querier, pair.contract_addr.clone())?;
+                Ok(oracle_contract
+                    .query_convert_amount(
+                        &deps.querier,
+                        asset_info.to_string(),
+                        ORAI_DENOM.to_string(),
+                        amount,
+                    )
+                    .ok()
+                    .map(|res| res.amount))
+            })
+            .collect::<StdResult<Vec<Option<Uint128>>>>()?;
+        let tvl = match (values[0], values[1]) {
+            (Some(value0), Some(value1)) => value0 + value1,
+            (Some(value), None) | (None, Some(value)) => value * Uint128::from(2u128),
+            (None, None) => Uint128::zero(),
+        };
+
+        let pair_key = pair_key(&[
+            pair.asset_infos[0].to_raw(deps.api)?,
+            pair.asset_infos[1].to_raw(deps.api)?,
+        ]);
+        if let Some(prev) = PAIR_TVLS.may_load(deps.storage, &pair_key)? {
+            total_tvl = total_tvl.checked_sub(prev.tvl)?;
+        }
+        total_tvl += tvl;
+        PAIR_TVLS.save(
+            deps.storage,
+            &pair_key,
+            &PairTvl {
+                tvl,
+                updated_at: env.block.time.seconds(),
+            },
+        )?;
+    }
+    TOTAL_TVL.save(deps.storage, &total_tvl)?;
+
+    Ok(Response::new()
+        .add_attributes(vec![
+            ("action", "update_tvl".to_string()),
+            ("updated_pairs", pairs.len().to_string()),
+            ("total_tvl", total_tvl.to_string()),
+        ])
+        .add_attributes(next_cursor.map(|next_cursor| ("next_cursor", next_cursor))))
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    if msg.id == WEIGHTED_INSTANTIATE_REPLY_ID {
+        return register_weighted_pair(deps, msg);
+    }
+
     let data = msg.result.unwrap().data.unwrap();
     let res = MsgInstantiateContractResponse::try_from(data.as_slice()).map_err(|_| {
         StdError::parse_err("MsgInstantiateContractResponse", "failed to parse data")
@@ -257,13 +684,67 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
         .api
         .addr_canonicalize(pair_info.liquidity_token.as_str())?;
     pair_info_raw.contract_addr = deps.api.addr_canonicalize(pair_contract.as_str())?;
+    pair_info_raw.lp_denom = pair_info.lp_denom;
 
     PAIRS.save(deps.storage, &pair_key, &pair_info_raw)?;
 
-    Ok(Response::new().add_attributes(vec![
-        ("pair_contract_address", pair_contract.as_str()),
-        ("liquidity_token_addr", pair_info.liquidity_token.as_str()),
-    ]))
+    // the registered pair is the data of CreatePair, the last one of a CreatePairs batch
+    let pair_info = pair_info_raw.to_normal(deps.api)?;
+
+    Ok(Response::new()
+        .set_data(to_binary(&pair_info)?)
+        .add_attributes(vec![
+            ("action", "register_pair"),
+            (
+                "pair",
+                &format!("{}-{}", pair_info.asset_infos[0], pair_info.asset_infos[1]),
+            ),
+            ("pair_contract_address", pair_info.contract_addr.as_str()),
+            ("liquidity_token_addr", pair_info.liquidity_token.as_str()),
+            ("commission_rate", &pair_info.commission_rate),
+        ]))
+}
+
+fn register_weighted_pair(deps: DepsMut, msg: Reply) -> Result<Response, ContractError> {
+    let data = msg.result.unwrap().data.unwrap();
+    let res = MsgInstantiateContractResponse::try_from(data.as_slice()).map_err(|_| {
+        StdError::parse_err("MsgInstantiateContractResponse", "failed to parse data")
+    })?;
+
+    let pair_contract = Addr::unchecked(res.address);
+    let res: WeightedPairResponse = deps
+        .querier
+        .query_wasm_smart(pair_contract.clone(), &WeightedPairQueryMsg::Pair {})?;
+    let pair_key = weighted_pair_key(
+        &res.info
+            .asset_infos
+            .iter()
+            .map(|asset_info| asset_info.info.to_raw(deps.api))
+            .collect::<StdResult<Vec<_>>>()?,
+    );
+
+    let mut pair_info_raw = WEIGHTED_PAIRS.load(deps.storage, &pair_key)?;
+    if !pair_info_raw.contract_addr.is_empty() {
+        return Err(ContractError::PairRegistered {});
+    }
+
+    pair_info_raw.liquidity_token = deps
+        .api
+        .addr_canonicalize(res.info.liquidity_token.as_str())?;
+    pair_info_raw.contract_addr = deps.api.addr_canonicalize(pair_contract.as_str())?;
+
+    WEIGHTED_PAIRS.save(deps.storage, &pair_key, &pair_info_raw)?;
+
+    let pair_info = pair_info_raw.to_normal(deps.api)?;
+
+    Ok(Response::new()
+        .set_data(to_binary(&pair_info)?)
+        .add_attributes(vec![
+            ("action", "register_weighted_pair"),
+            ("pair_contract_address", pair_info.contract_addr.as_str()),
+            ("liquidity_token_addr", pair_info.liquidity_token.as_str()),
+            ("commission_rate", &pair_info.commission_rate),
+        ]))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -271,9 +752,19 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => to_binary(&query_config(deps)?),
         QueryMsg::Pair { asset_infos } => to_binary(&query_pair(deps, asset_infos)?),
-        QueryMsg::Pairs { start_after, limit } => {
-            to_binary(&query_pairs(deps, start_after, limit)?)
+        QueryMsg::Pairs {
+            start_after,
+            limit,
+            cursor,
+        } => to_binary(&query_pairs(deps, start_after, limit, cursor)?),
+        QueryMsg::WeightedPair { asset_infos } => {
+            to_binary(&query_weighted_pair(deps, asset_infos)?)
         }
+        QueryMsg::Tvl { asset_infos } => to_binary(&query_tvl(deps, asset_infos)?),
+        QueryMsg::TotalTvl {} => to_binary(&TotalTvlResponse {
+            total_tvl: TOTAL_TVL.may_load(deps.storage)?.unwrap_or_default(),
+            denom: ORAI_DENOM.to_string(),
+        }),
     }
 }
 
@@ -284,6 +775,16 @@ pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
         owner: deps.api.addr_humanize(&state.owner)?,
         token_code_id: state.token_code_id,
         pair_code_id: state.pair_code_id,
+        commission_rate: state.commission_rate,
+        pair_templates: read_pair_templates(deps.storage)?,
+        guardian: state
+            .guardian
+            .map(|guardian| deps.api.addr_humanize(&guardian))
+            .transpose()?,
+        staking_contract: state
+            .staking_contract
+            .map(|staking_contract| deps.api.addr_humanize(&staking_contract))
+            .transpose()?,
     };
 
     Ok(resp)
@@ -298,22 +799,56 @@ pub fn query_pair(deps: Deps, asset_infos: [AssetInfo; 2]) -> StdResult<PairInfo
     pair_info.to_normal(deps.api)
 }
 
+pub fn query_weighted_pair(deps: Deps, asset_infos: Vec<AssetInfo>) -> StdResult<WeightedPairInfo> {
+    let pair_key = weighted_pair_key(
+        &asset_infos
+            .iter()
+            .map(|asset_info| asset_info.to_raw(deps.api))
+            .collect::<StdResult<Vec<_>>>()?,
+    );
+    let pair_info: WeightedPairInfoRaw = WEIGHTED_PAIRS.load(deps.storage, &pair_key)?;
+    pair_info.to_normal(deps.api)
+}
+
+pub fn query_tvl(deps: Deps, asset_infos: [AssetInfo; 2]) -> StdResult<TvlResponse> {
+    let pair_key = pair_key(&[
+        asset_infos[0].to_raw(deps.api)?,
+        asset_infos[1].to_raw(deps.api)?,
+    ]);
+    // only registered pairs are valued
+    PAIRS.load(deps.storage, &pair_key)?;
+    let pair_tvl = PAIR_TVLS.may_load(deps.storage, &pair_key)?;
+
+    Ok(TvlResponse {
+        asset_infos,
+        tvl: pair_tvl
+            .as_ref()
+            .map(|pair_tvl| pair_tvl.tvl)
+            .unwrap_or_default(),
+        updated_at: pair_tvl.map(|pair_tvl| pair_tvl.updated_at),
+    })
+}
+
 pub fn query_pairs(
     deps: Deps,
     start_after: Option<[AssetInfo; 2]>,
     limit: Option<u32>,
+    cursor: Option<String>,
 ) -> StdResult<PairsResponse> {
-    let start_after = if let Some(start_after) = start_after {
-        Some([
-            start_after[0].to_raw(deps.api)?,
-            start_after[1].to_raw(deps.api)?,
-        ])
-    } else {
-        None
+    let cursor = match (cursor, start_after) {
+        (Some(cursor), _) => Some(Cursor::decode(&cursor)?),
+        (None, Some(start_after)) => Some(Cursor::new(
+            pair_key(&[
+                start_after[0].to_raw(deps.api)?,
+                start_after[1].to_raw(deps.api)?,
+            ]),
+            Order::Ascending,
+        )),
+        (None, None) => None,
     };
 
-    let pairs: Vec<PairInfo> = read_pairs(deps.storage, deps.api, start_after, limit)?;
-    let resp = PairsResponse { pairs };
+    let (pairs, next_cursor) = read_pairs(deps.storage, deps.api, cursor, limit)?;
+    let resp = PairsResponse { pairs, next_cursor };
 
     Ok(resp)
 }
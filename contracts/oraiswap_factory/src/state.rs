@@ -1,8 +1,11 @@
 use cosmwasm_schema::cw_serde;
 
-use cosmwasm_std::{Api, CanonicalAddr, Order, StdResult, Storage};
+use cosmwasm_std::{Api, CanonicalAddr, Order, StdError, StdResult, Storage, Uint128};
 use cw_storage_plus::{Bound, Item, Map};
-use oraiswap::asset::{AssetInfoRaw, PairInfo, PairInfoRaw};
+use oraiswap::asset::{PairInfo, PairInfoRaw};
+use oraiswap::factory::{PairTemplate, PairType};
+use oraiswap::pagination::{next_cursor, Cursor};
+use oraiswap::weighted_pair::WeightedPairInfoRaw;
 
 #[cw_serde]
 pub struct Config {
@@ -11,6 +14,8 @@ pub struct Config {
     pub pair_code_id: u64,
     pub token_code_id: u64,
     pub commission_rate: String,
+    pub guardian: Option<CanonicalAddr>,
+    pub staking_contract: Option<CanonicalAddr>,
 }
 
 // put the length bytes at the first for compatibility with legacy singleton store
@@ -19,41 +24,92 @@ pub const CONFIG: Item<Config> = Item::new("\u{0}\u{6}config");
 // store temporary pair info while waiting for deployment
 pub const PAIRS: Map<&[u8], PairInfoRaw> = Map::new("pairs");
 
+// weighted pools keyed by the sorted keys of all their assets, also reserved while waiting for deployment
+pub const WEIGHTED_PAIRS: Map<&[u8], WeightedPairInfoRaw> = Map::new("weighted_pairs");
+
+/// value of the reserves of a pair in orai at the last UpdateTvl
+#[cw_serde]
+pub struct PairTvl {
+    pub tvl: Uint128,
+    pub updated_at: u64,
+}
+
+// value locked per pair key and in all pairs, refreshed by the permissionless UpdateTvl crank
+pub const PAIR_TVLS: Map<&[u8], PairTvl> = Map::new("pair_tvls");
+pub const TOTAL_TVL: Item<Uint128> = Item::new("total_tvl");
+
+// parameters applied at CreatePair, keyed by pair type
+pub const PAIR_TEMPLATES: Map<&[u8], PairTemplate> = Map::new("pair_templates");
+
+/// load the template of the pair type, xyk pairs fall back to the code id and commission rate of the config
+pub fn read_pair_template(
+    storage: &dyn Storage,
+    config: &Config,
+    pair_type: PairType,
+) -> StdResult<PairTemplate> {
+    match PAIR_TEMPLATES.may_load(storage, pair_type.as_bytes())? {
+        Some(template) => Ok(template),
+        None => match pair_type {
+            PairType::Xyk => Ok(PairTemplate {
+                pair_type,
+                pair_code_id: config.pair_code_id,
+                commission_rate: config.commission_rate.clone(),
+            }),
+            // weighted pairs have their own code, there is nothing to fall back to
+            PairType::Weighted => Err(StdError::generic_err(
+                "pair template of weighted pairs is not set",
+            )),
+        },
+    }
+}
+
+pub fn read_pair_templates(storage: &dyn Storage) -> StdResult<Vec<PairTemplate>> {
+    PAIR_TEMPLATES
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, template)| template))
+        .collect()
+}
+
 // settings for pagination
 const MAX_LIMIT: u32 = 30;
 const DEFAULT_LIMIT: u32 = 10;
+
+/// read pairs continuing after the cursor, together with the cursor of the next page
 pub fn read_pairs(
     storage: &dyn Storage,
     api: &dyn Api,
-    start_after: Option<[AssetInfoRaw; 2]>,
+    cursor: Option<Cursor>,
     limit: Option<u32>,
-) -> StdResult<Vec<PairInfo>> {
+) -> StdResult<(Vec<PairInfo>, Option<String>)> {
     let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
-    let start = calc_range_start(start_after).map(Bound::ExclusiveRaw);
+    let (start, end, order) = match cursor {
+        Some(cursor) => {
+            let (start, end) = cursor.range_bounds();
+            (
+                start.map(Bound::InclusiveRaw),
+                end.map(Bound::ExclusiveRaw),
+                cursor.order,
+            )
+        }
+        None => (None, None, Order::Ascending),
+    };
 
-    PAIRS
-        .range(storage, start, None, Order::Ascending)
+    let items = PAIRS
+        .range(storage, start, end, order)
         .take(limit)
-        .map(|item| {
-            let (_, v) = item?;
-            v.to_normal(api)
-        })
-        .collect::<StdResult<Vec<PairInfo>>>()
-}
+        .collect::<StdResult<Vec<(Vec<u8>, PairInfoRaw)>>>()?;
+    let next_cursor = next_cursor(
+        items.last().map(|(key, _)| key.clone()),
+        order,
+        items.len(),
+        limit,
+    );
+    let pairs = items
+        .into_iter()
+        .map(|(_, v)| v.to_normal(api))
+        .collect::<StdResult<Vec<PairInfo>>>()?;
 
-// this will set the first key after the provided key, by appending a 1 byte
-fn calc_range_start(start_after: Option<[AssetInfoRaw; 2]>) -> Option<Vec<u8>> {
-    start_after.map(|asset_infos| {
-        let mut asset_infos = asset_infos.to_vec();
-        asset_infos.sort_by(|a, b| a.as_bytes().cmp(b.as_bytes()));
-
-        let mut v = [asset_infos[0].as_bytes(), asset_infos[1].as_bytes()]
-            .concat()
-            .as_slice()
-            .to_vec();
-        v.push(1);
-        v
-    })
+    Ok((pairs, next_cursor))
 }
 
 #[cfg(test)]
@@ -65,7 +121,7 @@ mod test {
     use cosmwasm_storage::{
         bucket, bucket_read, singleton, singleton_read, Bucket, ReadonlyBucket,
     };
-    use oraiswap::asset::pair_key;
+    use oraiswap::asset::{pair_key, AssetInfoRaw};
     use oraiswap::pair::DEFAULT_COMMISSION_RATE;
     const KEY_CONFIG: &[u8] = b"config";
 
@@ -87,6 +143,8 @@ mod test {
                 pair_code_id: 1,
                 token_code_id: 1,
                 commission_rate: DEFAULT_COMMISSION_RATE.to_string(),
+                guardian: None,
+                staking_contract: None,
             },
         )
         .unwrap();
@@ -119,6 +177,21 @@ mod test {
         pair_bucket.load(&[asset_infos[0].as_bytes(), asset_infos[1].as_bytes()].concat())
     }
 
+    // this will set the first key after the provided key, by appending a 1 byte
+    fn calc_range_start(start_after: Option<[AssetInfoRaw; 2]>) -> Option<Vec<u8>> {
+        start_after.map(|asset_infos| {
+            let mut asset_infos = asset_infos.to_vec();
+            asset_infos.sort_by(|a, b| a.as_bytes().cmp(b.as_bytes()));
+
+            let mut v = [asset_infos[0].as_bytes(), asset_infos[1].as_bytes()]
+                .concat()
+                .as_slice()
+                .to_vec();
+            v.push(1);
+            v
+        })
+    }
+
     pub fn legacy_read_pairs(
         storage: &dyn Storage,
         api: &dyn Api,
@@ -154,6 +227,7 @@ mod test {
             contract_addr: deps.api.addr_canonicalize("pair0000").unwrap(),
             liquidity_token: deps.api.addr_canonicalize("liquidity0000").unwrap(),
             commission_rate: DEFAULT_COMMISSION_RATE.to_string(),
+            lp_denom: None,
         };
 
         let pair_info2 = PairInfoRaw {
@@ -169,6 +243,7 @@ mod test {
             contract_addr: deps.api.addr_canonicalize("pair0001").unwrap(),
             liquidity_token: deps.api.addr_canonicalize("liquidity0001").unwrap(),
             commission_rate: DEFAULT_COMMISSION_RATE.to_string(),
+            lp_denom: None,
         };
 
         store_pair(&mut deps.storage, &pair_info).unwrap();
@@ -189,7 +264,7 @@ mod test {
         );
 
         assert_eq!(
-            read_pairs(&deps.storage, &deps.api, None, None),
+            read_pairs(&deps.storage, &deps.api, None, None).map(|(pairs, _)| pairs),
             legacy_read_pairs(&deps.storage, &deps.api, None, None),
         );
     }
@@ -1,10 +1,18 @@
-use cosmwasm_std::Addr;
-use oraiswap::asset::{AssetInfo, PairInfo};
+use cosmwasm_std::{from_binary, Addr, Coin, Decimal, Uint128};
+use oraiswap::asset::{Asset, AssetInfo, PairInfo, ORAI_DENOM};
 
 use oraiswap::create_entry_points_testing;
-use oraiswap::pair::DEFAULT_COMMISSION_RATE;
-use oraiswap::querier::query_pair_info_from_pair;
-use oraiswap::testing::MockApp;
+use oraiswap::factory::{
+    ConfigResponse, CreatePairParams, ExecuteMsg, PairTemplate, PairType, QueryMsg,
+    TotalTvlResponse, TvlResponse,
+};
+use oraiswap::oracle::ExecuteMsg as OracleExecuteMsg;
+use oraiswap::pair::{LpTokenInfo, DEFAULT_COMMISSION_RATE};
+use oraiswap::querier::{query_pair_info_from_pair, query_token_balance, query_token_info};
+use oraiswap::testing::{MockApp, APP_OWNER, ATOM_DENOM};
+use oraiswap::weighted_pair::{
+    QueryMsg as WeightedPairQueryMsg, WeightedAssetInfo, WeightedPairInfo, WeightedPairResponse,
+};
 
 #[test]
 fn create_pair() {
@@ -46,11 +54,58 @@ fn create_pair() {
             liquidity_token: pair_info.liquidity_token,
             contract_addr,
             asset_infos,
-            commission_rate: DEFAULT_COMMISSION_RATE.into()
+            commission_rate: DEFAULT_COMMISSION_RATE.into(),
+            lp_denom: None,
         }
     );
 }
 
+#[test]
+fn create_pair_returns_pair_info() {
+    let mut app = MockApp::new(&[]);
+    app.set_token_contract(Box::new(create_entry_points_testing!(oraiswap_token)));
+    app.set_oracle_contract(Box::new(create_entry_points_testing!(oraiswap_oracle)));
+
+    app.set_factory_and_pair_contract(
+        Box::new(create_entry_points_testing!(crate).with_reply(crate::contract::reply)),
+        Box::new(
+            create_entry_points_testing!(oraiswap_pair).with_reply(oraiswap_pair::contract::reply),
+        ),
+    );
+
+    let contract_addr = app.create_token("assetA");
+    let asset_infos = [
+        AssetInfo::Token { contract_addr },
+        AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+    ];
+
+    let res = app
+        .execute(
+            Addr::unchecked(APP_OWNER),
+            app.factory_addr.clone(),
+            &ExecuteMsg::CreatePair {
+                asset_infos: asset_infos.clone(),
+                pair_admin: None,
+                pair_type: None,
+                lp_token_info: None,
+                lp_token_type: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+    // the addresses come with the response, no follow-up query is needed
+    let pair_info: PairInfo = from_binary(&res.data.unwrap()).unwrap();
+    assert_eq!(pair_info, app.query_pair(asset_infos).unwrap());
+    assert!(res.events.iter().any(|event| event
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "liquidity_token_addr"
+            && attr.value == pair_info.liquidity_token.as_str())));
+}
+
 #[test]
 fn add_pair() {
     let mut app = MockApp::new(&[]);
@@ -82,6 +137,7 @@ fn add_pair() {
         contract_addr: Addr::unchecked("contract_addr"),
         asset_infos: asset_infos.clone(),
         commission_rate: DEFAULT_COMMISSION_RATE.into(),
+        lp_denom: None,
     };
 
     // add pair
@@ -90,3 +146,567 @@ fn add_pair() {
     let pair_res = app.query_pair(asset_infos.clone()).unwrap();
     assert_eq!(pair_res, pair_info);
 }
+
+#[test]
+fn create_pair_with_lp_token_info() {
+    let mut app = MockApp::new(&[]);
+    app.set_token_contract(Box::new(create_entry_points_testing!(oraiswap_token)));
+    app.set_oracle_contract(Box::new(create_entry_points_testing!(oraiswap_oracle)));
+
+    app.set_factory_and_pair_contract(
+        Box::new(create_entry_points_testing!(crate).with_reply(crate::contract::reply)),
+        Box::new(
+            create_entry_points_testing!(oraiswap_pair).with_reply(oraiswap_pair::contract::reply),
+        ),
+    );
+
+    let contract_addr1 = app.create_token("assetA");
+    let contract_addr2 = app.create_token("assetB");
+
+    let asset_infos = [
+        AssetInfo::Token {
+            contract_addr: contract_addr1,
+        },
+        AssetInfo::Token {
+            contract_addr: contract_addr2,
+        },
+    ];
+
+    app.execute(
+        Addr::unchecked(APP_OWNER),
+        app.factory_addr.clone(),
+        &ExecuteMsg::CreatePair {
+            asset_infos: asset_infos.clone(),
+            pair_admin: None,
+            pair_type: None,
+            lp_token_info: Some(LpTokenInfo {
+                name: Some("assetA-assetB liquidity token".to_string()),
+                symbol: Some("uLP-AB".to_string()),
+                marketing: None,
+            }),
+            lp_token_type: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let pair_res = app.query_pair(asset_infos).unwrap();
+    let token_info = query_token_info(&app.as_querier(), pair_res.liquidity_token).unwrap();
+    assert_eq!(token_info.name, "assetA-assetB liquidity token");
+    assert_eq!(token_info.symbol, "uLP-AB");
+}
+
+#[test]
+fn create_pairs() {
+    let mut app = MockApp::new(&[]);
+    app.set_token_contract(Box::new(create_entry_points_testing!(oraiswap_token)));
+    app.set_oracle_contract(Box::new(create_entry_points_testing!(oraiswap_oracle)));
+
+    app.set_factory_and_pair_contract(
+        Box::new(create_entry_points_testing!(crate).with_reply(crate::contract::reply)),
+        Box::new(
+            create_entry_points_testing!(oraiswap_pair).with_reply(oraiswap_pair::contract::reply),
+        ),
+    );
+
+    let contract_addr1 = app.create_token("assetA");
+    let contract_addr2 = app.create_token("assetB");
+
+    let orai_info = AssetInfo::NativeToken {
+        denom: ORAI_DENOM.to_string(),
+    };
+    let asset_infos_list = [
+        [
+            AssetInfo::Token {
+                contract_addr: contract_addr1,
+            },
+            orai_info.clone(),
+        ],
+        [
+            AssetInfo::Token {
+                contract_addr: contract_addr2,
+            },
+            orai_info,
+        ],
+    ];
+    let create_pairs_msg = |asset_infos_list: &[[AssetInfo; 2]]| ExecuteMsg::CreatePairs {
+        pairs: asset_infos_list
+            .iter()
+            .map(|asset_infos| CreatePairParams {
+                asset_infos: asset_infos.clone(),
+                pair_admin: None,
+                pair_type: None,
+                lp_token_info: None,
+                lp_token_type: None,
+            })
+            .collect(),
+    };
+
+    // empty batch
+    let res = app.execute(
+        Addr::unchecked(APP_OWNER),
+        app.factory_addr.clone(),
+        &create_pairs_msg(&[]),
+        &[],
+    );
+    app.assert_fail(res);
+
+    app.execute(
+        Addr::unchecked(APP_OWNER),
+        app.factory_addr.clone(),
+        &create_pairs_msg(&asset_infos_list),
+        &[],
+    )
+    .unwrap();
+
+    // every pair is registered with its own addresses
+    for asset_infos in asset_infos_list.iter() {
+        let pair_res = app.query_pair(asset_infos.clone()).unwrap();
+        let pair_info =
+            query_pair_info_from_pair(&app.as_querier(), pair_res.contract_addr.clone()).unwrap();
+        assert_eq!(pair_res.asset_infos, *asset_infos);
+        assert_eq!(pair_res.liquidity_token, pair_info.liquidity_token);
+    }
+
+    // the whole batch fails when one of the pairs exists
+    let res = app.execute(
+        Addr::unchecked(APP_OWNER),
+        app.factory_addr.clone(),
+        &create_pairs_msg(&asset_infos_list[1..]),
+        &[],
+    );
+    app.assert_fail(res);
+}
+
+#[test]
+fn update_pair_template() {
+    let mut app = MockApp::new(&[]);
+    app.set_token_contract(Box::new(create_entry_points_testing!(oraiswap_token)));
+    app.set_oracle_contract(Box::new(create_entry_points_testing!(oraiswap_oracle)));
+
+    app.set_factory_and_pair_contract(
+        Box::new(create_entry_points_testing!(crate).with_reply(crate::contract::reply)),
+        Box::new(
+            create_entry_points_testing!(oraiswap_pair).with_reply(oraiswap_pair::contract::reply),
+        ),
+    );
+
+    let config: ConfigResponse = app
+        .query(app.factory_addr.clone(), &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(config.commission_rate, DEFAULT_COMMISSION_RATE);
+    assert!(config.pair_templates.is_empty());
+
+    let template = PairTemplate {
+        pair_type: PairType::Xyk,
+        pair_code_id: config.pair_code_id,
+        commission_rate: "0.001".to_string(),
+    };
+
+    // only owner can update pair template
+    let res = app.execute(
+        Addr::unchecked("addr0000"),
+        app.factory_addr.clone(),
+        &ExecuteMsg::UpdatePairTemplate {
+            template: template.clone(),
+        },
+        &[],
+    );
+    app.assert_fail(res);
+
+    // commission rate must be less than 1
+    let res = app.execute(
+        Addr::unchecked(APP_OWNER),
+        app.factory_addr.clone(),
+        &ExecuteMsg::UpdatePairTemplate {
+            template: PairTemplate {
+                commission_rate: "1".to_string(),
+                ..template.clone()
+            },
+        },
+        &[],
+    );
+    app.assert_fail(res);
+
+    app.execute(
+        Addr::unchecked(APP_OWNER),
+        app.factory_addr.clone(),
+        &ExecuteMsg::UpdatePairTemplate {
+            template: template.clone(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let config: ConfigResponse = app
+        .query(app.factory_addr.clone(), &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(config.pair_templates, vec![template]);
+
+    // new pairs use the template parameters
+    let contract_addr1 = app.create_token("assetA");
+    let contract_addr2 = app.create_token("assetB");
+    let asset_infos = [
+        AssetInfo::Token {
+            contract_addr: contract_addr1,
+        },
+        AssetInfo::Token {
+            contract_addr: contract_addr2,
+        },
+    ];
+    let contract_addr = app.create_pair(asset_infos.clone()).unwrap();
+
+    let pair_info = query_pair_info_from_pair(&app.as_querier(), contract_addr).unwrap();
+    assert_eq!(pair_info.commission_rate, "0.001");
+    let pair_res = app.query_pair(asset_infos).unwrap();
+    assert_eq!(pair_res.commission_rate, "0.001");
+}
+
+#[test]
+fn provide_liquidity_to_pair() {
+    let mut app = MockApp::new(&[(
+        &"addr0000".to_string(),
+        &[Coin {
+            denom: ORAI_DENOM.to_string(),
+            amount: Uint128::from(1000u128),
+        }],
+    )]);
+    app.set_token_contract(Box::new(create_entry_points_testing!(oraiswap_token)));
+    app.set_oracle_contract(Box::new(create_entry_points_testing!(oraiswap_oracle)));
+
+    app.set_factory_and_pair_contract(
+        Box::new(create_entry_points_testing!(crate).with_reply(crate::contract::reply)),
+        Box::new(
+            create_entry_points_testing!(oraiswap_pair).with_reply(oraiswap_pair::contract::reply),
+        ),
+    );
+
+    let token_addr = app.set_token_balances(&[(
+        &"assetA".to_string(),
+        &[(&"addr0000".to_string(), &Uint128::from(1000u128))],
+    )])[0]
+        .clone();
+
+    let asset_infos = [
+        AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        AssetInfo::Token {
+            contract_addr: token_addr.clone(),
+        },
+    ];
+    let contract_addr = app.create_pair(asset_infos.clone()).unwrap();
+
+    // the factory pulls the token then lets the pair pull it
+    app.execute(
+        Addr::unchecked("addr0000"),
+        token_addr.clone(),
+        &cw20::Cw20ExecuteMsg::IncreaseAllowance {
+            spender: app.factory_addr.to_string(),
+            amount: Uint128::from(100u128),
+            expires: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let msg = ExecuteMsg::ProvideLiquidityToPair {
+        asset_infos: asset_infos.clone(),
+        assets: [
+            Asset {
+                info: asset_infos[0].clone(),
+                amount: Uint128::from(100u128),
+            },
+            Asset {
+                info: asset_infos[1].clone(),
+                amount: Uint128::from(100u128),
+            },
+        ],
+        slippage_tolerance: None,
+        auto_stake: None,
+    };
+
+    // native asset must be sent along
+    let res = app.execute(
+        Addr::unchecked("addr0000"),
+        app.factory_addr.clone(),
+        &msg,
+        &[],
+    );
+    app.assert_fail(res);
+
+    app.execute(
+        Addr::unchecked("addr0000"),
+        app.factory_addr.clone(),
+        &msg,
+        &[Coin {
+            denom: ORAI_DENOM.to_string(),
+            amount: Uint128::from(100u128),
+        }],
+    )
+    .unwrap();
+
+    // LP tokens are minted to the sender, nothing is left in the factory
+    let pair_info = query_pair_info_from_pair(&app.as_querier(), contract_addr.clone()).unwrap();
+    assert_eq!(
+        query_token_balance(
+            &app.as_querier(),
+            pair_info.liquidity_token,
+            Addr::unchecked("addr0000")
+        )
+        .unwrap(),
+        Uint128::from(100u128)
+    );
+    assert_eq!(
+        query_token_balance(&app.as_querier(), token_addr.clone(), contract_addr).unwrap(),
+        Uint128::from(100u128)
+    );
+    assert_eq!(
+        query_token_balance(&app.as_querier(), token_addr, app.factory_addr.clone()).unwrap(),
+        Uint128::zero()
+    );
+}
+
+#[test]
+fn create_weighted_pair() {
+    let mut app = MockApp::new(&[]);
+    app.set_token_contract(Box::new(create_entry_points_testing!(oraiswap_token)));
+    app.set_oracle_contract(Box::new(create_entry_points_testing!(oraiswap_oracle)));
+
+    app.set_factory_and_pair_contract(
+        Box::new(create_entry_points_testing!(crate).with_reply(crate::contract::reply)),
+        Box::new(
+            create_entry_points_testing!(oraiswap_pair).with_reply(oraiswap_pair::contract::reply),
+        ),
+    );
+    let weighted_pair_code_id = app.upload(Box::new(
+        create_entry_points_testing!(oraiswap_weighted_pair)
+            .with_reply(oraiswap_weighted_pair::contract::reply),
+    ));
+
+    let contract_addr1 = app.create_token("assetA");
+    let contract_addr2 = app.create_token("assetB");
+    let asset_infos = vec![
+        WeightedAssetInfo {
+            info: AssetInfo::NativeToken {
+                denom: ORAI_DENOM.to_string(),
+            },
+            weight: Decimal::percent(50),
+        },
+        WeightedAssetInfo {
+            info: AssetInfo::Token {
+                contract_addr: contract_addr1,
+            },
+            weight: Decimal::percent(30),
+        },
+        WeightedAssetInfo {
+            info: AssetInfo::Token {
+                contract_addr: contract_addr2,
+            },
+            weight: Decimal::percent(20),
+        },
+    ];
+    let create_msg = ExecuteMsg::CreateWeightedPair {
+        asset_infos: asset_infos.clone(),
+        pair_admin: None,
+        lp_token_info: None,
+    };
+
+    // weighted pairs need their template
+    let res = app.execute(
+        Addr::unchecked(APP_OWNER),
+        app.factory_addr.clone(),
+        &create_msg,
+        &[],
+    );
+    app.assert_fail(res);
+
+    app.execute(
+        Addr::unchecked(APP_OWNER),
+        app.factory_addr.clone(),
+        &ExecuteMsg::UpdatePairTemplate {
+            template: PairTemplate {
+                pair_type: PairType::Weighted,
+                pair_code_id: weighted_pair_code_id,
+                commission_rate: "0.002".to_string(),
+            },
+        },
+        &[],
+    )
+    .unwrap();
+
+    // the xyk CreatePair does not create weighted pairs
+    let res = app.execute(
+        Addr::unchecked(APP_OWNER),
+        app.factory_addr.clone(),
+        &ExecuteMsg::CreatePair {
+            asset_infos: [asset_infos[0].info.clone(), asset_infos[1].info.clone()],
+            pair_admin: None,
+            pair_type: Some(PairType::Weighted),
+            lp_token_info: None,
+            lp_token_type: None,
+        },
+        &[],
+    );
+    app.assert_fail(res);
+
+    app.execute(
+        Addr::unchecked(APP_OWNER),
+        app.factory_addr.clone(),
+        &create_msg,
+        &[],
+    )
+    .unwrap();
+
+    // the pair is found with the assets in any order
+    let pair_info: WeightedPairInfo = app
+        .query(
+            app.factory_addr.clone(),
+            &QueryMsg::WeightedPair {
+                asset_infos: vec![
+                    asset_infos[2].info.clone(),
+                    asset_infos[0].info.clone(),
+                    asset_infos[1].info.clone(),
+                ],
+            },
+        )
+        .unwrap();
+    assert_eq!(pair_info.asset_infos, asset_infos);
+    assert_eq!(pair_info.commission_rate, "0.002");
+
+    let pair_res: WeightedPairResponse = app
+        .query(
+            pair_info.contract_addr.clone(),
+            &WeightedPairQueryMsg::Pair {},
+        )
+        .unwrap();
+    assert_eq!(pair_res.info, pair_info);
+
+    // can not create the same pool twice
+    let res = app.execute(
+        Addr::unchecked(APP_OWNER),
+        app.factory_addr.clone(),
+        &create_msg,
+        &[],
+    );
+    app.assert_fail(res);
+}
+
+#[test]
+fn update_tvl() {
+    let mut app = MockApp::new(&[]);
+    app.set_token_contract(Box::new(create_entry_points_testing!(oraiswap_token)));
+    app.set_oracle_contract(Box::new(create_entry_points_testing!(oraiswap_oracle)));
+
+    app.set_factory_and_pair_contract(
+        Box::new(create_entry_points_testing!(crate).with_reply(crate::contract::reply)),
+        Box::new(
+            create_entry_points_testing!(oraiswap_pair).with_reply(oraiswap_pair::contract::reply),
+        ),
+    );
+
+    let token_addr = app.create_token("assetA");
+    let orai_atom = [
+        AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: ATOM_DENOM.to_string(),
+        },
+    ];
+    let atom_token = [
+        AssetInfo::NativeToken {
+            denom: ATOM_DENOM.to_string(),
+        },
+        AssetInfo::Token {
+            contract_addr: token_addr,
+        },
+    ];
+    let orai_atom_pair = app.create_pair(orai_atom.clone()).unwrap();
+    let atom_token_pair = app.create_pair(atom_token.clone()).unwrap();
+
+    // the reserves are the balances of the pairs
+    app.set_balances(&[
+        (
+            &ORAI_DENOM.to_string(),
+            &[(&orai_atom_pair.to_string(), &Uint128::from(1000u128))],
+        ),
+        (
+            &ATOM_DENOM.to_string(),
+            &[
+                (&orai_atom_pair.to_string(), &Uint128::from(2000u128)),
+                (&atom_token_pair.to_string(), &Uint128::from(100u128)),
+            ],
+        ),
+    ]);
+
+    let set_atom_rate = |app: &mut MockApp, exchange_rate: Decimal| {
+        app.execute(
+            Addr::unchecked(APP_OWNER),
+            app.oracle_addr.clone(),
+            &OracleExecuteMsg::UpdateExchangeRate {
+                denom: ATOM_DENOM.to_string(),
+                exchange_rate,
+            },
+            &[],
+        )
+        .unwrap();
+    };
+    let update_tvl = |app: &mut MockApp, cursor: Option<String>, limit: Option<u32>| {
+        let res = app
+            .execute(
+                Addr::unchecked("anyone"),
+                app.factory_addr.clone(),
+                &ExecuteMsg::UpdateTvl { cursor, limit },
+                &[],
+            )
+            .unwrap();
+        res.events
+            .iter()
+            .flat_map(|event| event.attributes.iter())
+            .find(|attr| attr.key == "next_cursor")
+            .map(|attr| attr.value.clone())
+    };
+    let query_tvl = |app: &MockApp, asset_infos: [AssetInfo; 2]| {
+        app.query::<TvlResponse, _>(app.factory_addr.clone(), &QueryMsg::Tvl { asset_infos })
+            .unwrap()
+            .tvl
+    };
+
+    // 1 atom is worth 0.5 orai
+    set_atom_rate(&mut app, Decimal::percent(50));
+
+    // the crank goes through the pairs page by page
+    let next_cursor = update_tvl(&mut app, None, Some(1));
+    assert!(next_cursor.is_some());
+    let res: TotalTvlResponse = app
+        .query(app.factory_addr.clone(), &QueryMsg::TotalTvl {})
+        .unwrap();
+    assert!(res.total_tvl == Uint128::from(2000u128) || res.total_tvl == Uint128::from(100u128));
+
+    assert_eq!(update_tvl(&mut app, next_cursor, None), None);
+
+    // 1000 orai and 2000 atom, the token without exchange rate counts as much as the 100 atom
+    assert_eq!(query_tvl(&app, orai_atom.clone()), Uint128::from(2000u128));
+    assert_eq!(query_tvl(&app, atom_token.clone()), Uint128::from(100u128));
+    let res: TotalTvlResponse = app
+        .query(app.factory_addr.clone(), &QueryMsg::TotalTvl {})
+        .unwrap();
+    assert_eq!(
+        res,
+        TotalTvlResponse {
+            total_tvl: Uint128::from(2100u128),
+            denom: ORAI_DENOM.to_string(),
+        }
+    );
+
+    // the total follows the new exchange rates
+    set_atom_rate(&mut app, Decimal::one());
+    update_tvl(&mut app, None, None);
+    assert_eq!(query_tvl(&app, orai_atom), Uint128::from(3000u128));
+    assert_eq!(query_tvl(&app, atom_token), Uint128::from(200u128));
+    let res: TotalTvlResponse = app
+        .query(app.factory_addr.clone(), &QueryMsg::TotalTvl {})
+        .unwrap();
+    assert_eq!(res.total_tvl, Uint128::from(3200u128));
+}
@@ -0,0 +1,411 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+
+use cosmwasm_std::{
+    to_binary, Addr, Api, Attribute, Binary, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo,
+    Response, StdError, StdResult, Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+
+use crate::state::{Config, CONFIG, SWAP_ROUTES, TOTAL_BURNED};
+
+use oraiswap::asset::{Asset, AssetInfo};
+use oraiswap::error::ContractError;
+use oraiswap::fee_collector::{
+    ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, Recipient, SwapRouteResponse,
+    TotalBurnedResponse,
+};
+use oraiswap::querier::query_token_balance;
+use oraiswap::router::{RouterController, SwapOperation};
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    validate_recipients(deps.api, &msg.recipients)?;
+
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            owner: deps
+                .api
+                .addr_canonicalize(msg.owner.unwrap_or(info.sender).as_str())?,
+            router: deps.api.addr_canonicalize(msg.router.as_str())?,
+            target_asset_info: msg.target_asset_info.to_raw(deps.api)?,
+            recipients: msg.recipients,
+            burn_token: msg
+                .burn_token
+                .map(|addr| deps.api.addr_canonicalize(addr.as_str()))
+                .transpose()?,
+        },
+    )?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::UpdateConfig {
+            owner,
+            router,
+            target_asset_info,
+            recipients,
+            burn_token,
+        } => update_config(
+            deps,
+            info,
+            owner,
+            router,
+            target_asset_info,
+            recipients,
+            burn_token,
+        ),
+        ExecuteMsg::UpdateSwapRoute {
+            asset_info,
+            operations,
+        } => update_swap_route(deps, info, asset_info, operations),
+        ExecuteMsg::Swap { asset_infos } => swap(deps, env, asset_infos),
+        ExecuteMsg::Distribute {} => distribute(deps, env),
+        ExecuteMsg::BuybackAndBurn { asset, max_spread } => {
+            buyback_and_burn(deps, env, info, asset, max_spread)
+        }
+        ExecuteMsg::BurnReceived { prev_balance } => burn_received(deps, env, info, prev_balance),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+pub fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    owner: Option<Addr>,
+    router: Option<Addr>,
+    target_asset_info: Option<AssetInfo>,
+    recipients: Option<Vec<Recipient>>,
+    burn_token: Option<Addr>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if config.owner != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(owner) = owner {
+        config.owner = deps.api.addr_canonicalize(owner.as_str())?;
+    }
+
+    if let Some(router) = router {
+        config.router = deps.api.addr_canonicalize(router.as_str())?;
+    }
+
+    if let Some(target_asset_info) = target_asset_info {
+        config.target_asset_info = target_asset_info.to_raw(deps.api)?;
+    }
+
+    if let Some(recipients) = recipients {
+        validate_recipients(deps.api, &recipients)?;
+        config.recipients = recipients;
+    }
+
+    if let Some(burn_token) = burn_token {
+        config.burn_token = Some(deps.api.addr_canonicalize(burn_token.as_str())?);
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+pub fn update_swap_route(
+    deps: DepsMut,
+    info: MessageInfo,
+    asset_info: AssetInfo,
+    operations: Vec<SwapOperation>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let asset_key = asset_info.to_vec(deps.api)?;
+    if operations.is_empty() {
+        SWAP_ROUTES.remove(deps.storage, &asset_key);
+        return Ok(Response::new().add_attribute("action", "remove_swap_route"));
+    }
+
+    // the route must start from the asset and end with the target asset
+    let target_asset_info = config.target_asset_info.to_normal(deps.api)?;
+    if !operations[0].get_offer_asset_info().eq(&asset_info)
+        || !operations[operations.len() - 1]
+            .get_target_asset_info()
+            .eq(&target_asset_info)
+    {
+        return Err(ContractError::AssetMismatch {});
+    }
+
+    SWAP_ROUTES.save(deps.storage, &asset_key, &operations)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "update_swap_route"),
+        ("asset_info", &asset_info.to_string()),
+    ]))
+}
+
+/// Swap
+/// Anyone can swap the collected fees into the target asset
+pub fn swap(
+    deps: DepsMut,
+    env: Env,
+    asset_infos: Vec<AssetInfo>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let router = RouterController(deps.api.addr_humanize(&config.router)?.to_string());
+    let target_asset_info = config.target_asset_info.to_normal(deps.api)?;
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut attributes: Vec<Attribute> = vec![("action", "swap").into()];
+    for asset_info in asset_infos {
+        if asset_info.eq(&target_asset_info) {
+            continue;
+        }
+
+        let amount = asset_info.query_pool(&deps.querier, env.contract.address.clone())?;
+        if amount.is_zero() {
+            continue;
+        }
+
+        let asset_key = asset_info.to_vec(deps.api)?;
+        let operations = match SWAP_ROUTES.may_load(deps.storage, &asset_key)? {
+            Some(operations) => operations,
+            None => vec![SwapOperation::OraiSwap {
+                offer_asset_info: asset_info.clone(),
+                ask_asset_info: target_asset_info.clone(),
+            }],
+        };
+
+        attributes.push(("offer_asset", format!("{}{}", amount, asset_info)).into());
+        messages.push(router.execute_operations(asset_info, amount, operations, None, None)?);
+    }
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attributes(attributes))
+}
+
+/// Distribute
+/// Anyone can distribute the target asset balance to the recipients by weight
+pub fn distribute(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let target_asset_info = config.target_asset_info.to_normal(deps.api)?;
+    let balance = target_asset_info.query_pool(&deps.querier, env.contract.address)?;
+    let total_weight: u64 = config.recipients.iter().map(|r| r.weight).sum();
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut attributes: Vec<Attribute> = vec![("action", "distribute").into()];
+    for recipient in config.recipients {
+        let amount = balance.multiply_ratio(recipient.weight, total_weight);
+        if amount.is_zero() {
+            continue;
+        }
+
+        attributes.push(("recipient", recipient.address.to_string()).into());
+        attributes.push(("amount", amount.to_string()).into());
+        messages.push(
+            Asset {
+                info: target_asset_info.clone(),
+                amount,
+            }
+            .into_msg(None, &deps.querier, recipient.address)?,
+        );
+    }
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attributes(attributes))
+}
+
+// Only owner can execute it
+pub fn buyback_and_burn(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asset: AssetInfo,
+    max_spread: Option<Decimal>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let burn_token = match config.burn_token {
+        Some(burn_token) => deps.api.addr_humanize(&burn_token)?,
+        None => return Err(StdError::generic_err("burn token is not set").into()),
+    };
+    let burn_asset_info = AssetInfo::Token {
+        contract_addr: burn_token,
+    };
+
+    let amount = asset.query_pool(&deps.querier, env.contract.address.clone())?;
+    if amount.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    // the collected burn token is burned directly, other assets are bought back first
+    let prev_balance = if asset.eq(&burn_asset_info) {
+        Uint128::zero()
+    } else {
+        let prev_balance =
+            burn_asset_info.query_pool(&deps.querier, env.contract.address.clone())?;
+        let router = RouterController(deps.api.addr_humanize(&config.router)?.to_string());
+        let operations = vec![SwapOperation::OraiSwap {
+            offer_asset_info: asset.clone(),
+            ask_asset_info: burn_asset_info,
+        }];
+
+        // protect the buyback from price moves with the simulated return amount
+        let minimum_receive = match max_spread {
+            Some(max_spread) => {
+                if max_spread > Decimal::one() {
+                    return Err(ContractError::InvalidExceedOneSlippage {});
+                }
+                let simulation = router.simulate_swap(&deps.querier, amount, operations.clone())?;
+                Some(simulation.amount * (Decimal::one() - max_spread))
+            }
+            None => None,
+        };
+
+        messages.push(router.execute_operations(
+            asset.clone(),
+            amount,
+            operations,
+            minimum_receive,
+            None,
+        )?);
+        prev_balance
+    };
+
+    messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: env.contract.address.to_string(),
+        msg: to_binary(&ExecuteMsg::BurnReceived { prev_balance })?,
+        funds: vec![],
+    }));
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "buyback_and_burn"),
+        ("offer_asset", &format!("{}{}", amount, asset)),
+    ]))
+}
+
+pub fn burn_received(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    prev_balance: Uint128,
+) -> Result<Response, ContractError> {
+    if env.contract.address != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let burn_token = match config.burn_token {
+        Some(burn_token) => deps.api.addr_humanize(&burn_token)?,
+        None => return Err(StdError::generic_err("burn token is not set").into()),
+    };
+
+    let balance = query_token_balance(&deps.querier, burn_token.clone(), env.contract.address)?;
+    let burn_amount = balance.checked_sub(prev_balance)?;
+    if burn_amount.is_zero() {
+        return Ok(Response::new().add_attribute("action", "burn_received"));
+    }
+
+    let total_burned = TOTAL_BURNED
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        .checked_add(burn_amount)?;
+    TOTAL_BURNED.save(deps.storage, &total_burned)?;
+
+    Ok(Response::new()
+        .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: burn_token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Burn {
+                amount: burn_amount,
+            })?,
+            funds: vec![],
+        }))
+        .add_attributes(vec![
+            ("action", "burn_received"),
+            ("burn_amount", &burn_amount.to_string()),
+            ("total_burned", &total_burned.to_string()),
+        ]))
+}
+
+fn validate_recipients(api: &dyn Api, recipients: &[Recipient]) -> StdResult<()> {
+    if recipients.is_empty() {
+        return Err(StdError::generic_err("recipients must not be empty"));
+    }
+
+    for recipient in recipients {
+        api.addr_validate(recipient.address.as_str())?;
+        if recipient.weight == 0 {
+            return Err(StdError::generic_err("recipient weight must be positive"));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::SwapRoute { asset_info } => to_binary(&query_swap_route(deps, asset_info)?),
+        QueryMsg::TotalBurned {} => to_binary(&query_total_burned(deps)?),
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let state = CONFIG.load(deps.storage)?;
+    let resp = ConfigResponse {
+        owner: deps.api.addr_humanize(&state.owner)?,
+        router: deps.api.addr_humanize(&state.router)?,
+        target_asset_info: state.target_asset_info.to_normal(deps.api)?,
+        recipients: state.recipients,
+        burn_token: state
+            .burn_token
+            .map(|addr| deps.api.addr_humanize(&addr))
+            .transpose()?,
+    };
+
+    Ok(resp)
+}
+
+pub fn query_swap_route(deps: Deps, asset_info: AssetInfo) -> StdResult<SwapRouteResponse> {
+    let state = CONFIG.load(deps.storage)?;
+    let asset_key = asset_info.to_vec(deps.api)?;
+    let operations = match SWAP_ROUTES.may_load(deps.storage, &asset_key)? {
+        Some(operations) => operations,
+        None => vec![SwapOperation::OraiSwap {
+            offer_asset_info: asset_info,
+            ask_asset_info: state.target_asset_info.to_normal(deps.api)?,
+        }],
+    };
+
+    Ok(SwapRouteResponse { operations })
+}
+
+pub fn query_total_burned(deps: Deps) -> StdResult<TotalBurnedResponse> {
+    let amount = TOTAL_BURNED.may_load(deps.storage)?.unwrap_or_default();
+    Ok(TotalBurnedResponse { amount })
+}
@@ -0,0 +1,23 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{CanonicalAddr, Uint128};
+use cw_storage_plus::{Item, Map};
+use oraiswap::asset::AssetInfoRaw;
+use oraiswap::fee_collector::Recipient;
+use oraiswap::router::SwapOperation;
+
+#[cw_serde]
+pub struct Config {
+    pub owner: CanonicalAddr,
+    pub router: CanonicalAddr,
+    pub target_asset_info: AssetInfoRaw,
+    pub recipients: Vec<Recipient>,
+    pub burn_token: Option<CanonicalAddr>,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// custom router operations to swap an asset into the target asset, keyed by asset key
+pub const SWAP_ROUTES: Map<&[u8], Vec<SwapOperation>> = Map::new("swap_routes");
+
+/// total amount of the burn token burned by BuybackAndBurn
+pub const TOTAL_BURNED: Item<Uint128> = Item::new("total_burned");
@@ -0,0 +1,236 @@
+use crate::contract::{execute, instantiate, query_config, query_swap_route};
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_dependencies_with_balance, mock_env, mock_info,
+};
+use cosmwasm_std::{coin, to_binary, Addr, BankMsg, CosmosMsg, StdError, SubMsg, Uint128, WasmMsg};
+use oraiswap::asset::{AssetInfo, ORAI_DENOM};
+use oraiswap::create_entry_points_testing;
+use oraiswap::error::ContractError;
+use oraiswap::fee_collector::{
+    ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg, Recipient, TotalBurnedResponse,
+};
+use oraiswap::querier::query_token_info;
+use oraiswap::router::{ExecuteMsg as RouterExecuteMsg, SwapOperation};
+use oraiswap::testing::{MockApp, ATOM_DENOM};
+
+fn default_instantiate_msg() -> InstantiateMsg {
+    InstantiateMsg {
+        owner: None,
+        router: Addr::unchecked("router"),
+        target_asset_info: AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        recipients: vec![
+            Recipient {
+                address: Addr::unchecked("treasury"),
+                weight: 1,
+            },
+            Recipient {
+                address: Addr::unchecked("staking"),
+                weight: 3,
+            },
+        ],
+        burn_token: None,
+    }
+}
+
+#[test]
+fn proper_initialization() {
+    let mut deps = mock_dependencies();
+
+    // recipients must not be empty
+    let msg = InstantiateMsg {
+        recipients: vec![],
+        ..default_instantiate_msg()
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap_err();
+
+    let msg = default_instantiate_msg();
+    let _res = instantiate(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    let config = query_config(deps.as_ref()).unwrap();
+    assert_eq!(
+        config,
+        ConfigResponse {
+            owner: Addr::unchecked("owner"),
+            router: Addr::unchecked("router"),
+            target_asset_info: AssetInfo::NativeToken {
+                denom: ORAI_DENOM.to_string(),
+            },
+            recipients: default_instantiate_msg().recipients,
+            burn_token: None,
+        }
+    );
+
+    // only owner can update config
+    let msg = ExecuteMsg::UpdateConfig {
+        owner: Some(Addr::unchecked("addr0000")),
+        router: None,
+        target_asset_info: None,
+        recipients: None,
+        burn_token: None,
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+}
+
+#[test]
+fn swap_and_distribute() {
+    let mut deps =
+        mock_dependencies_with_balance(&[coin(1000u128, ORAI_DENOM), coin(500u128, ATOM_DENOM)]);
+
+    let msg = default_instantiate_msg();
+    let _res = instantiate(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    let atom_info = AssetInfo::NativeToken {
+        denom: ATOM_DENOM.to_string(),
+    };
+    let orai_info = AssetInfo::NativeToken {
+        denom: ORAI_DENOM.to_string(),
+    };
+
+    // the default route is the direct pair with target asset
+    let route = query_swap_route(deps.as_ref(), atom_info.clone()).unwrap();
+    assert_eq!(
+        route.operations,
+        vec![SwapOperation::OraiSwap {
+            offer_asset_info: atom_info.clone(),
+            ask_asset_info: orai_info.clone(),
+        }]
+    );
+
+    // route must end with the target asset
+    let msg = ExecuteMsg::UpdateSwapRoute {
+        asset_info: atom_info.clone(),
+        operations: vec![SwapOperation::OraiSwap {
+            offer_asset_info: atom_info.clone(),
+            ask_asset_info: AssetInfo::NativeToken {
+                denom: "usdt".to_string(),
+            },
+        }],
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::AssetMismatch {});
+
+    // anyone can swap, target asset is skipped
+    let msg = ExecuteMsg::Swap {
+        asset_infos: vec![atom_info.clone(), orai_info.clone()],
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "router".to_string(),
+            msg: to_binary(&RouterExecuteMsg::ExecuteSwapOperations {
+                operations: vec![SwapOperation::OraiSwap {
+                    offer_asset_info: atom_info,
+                    ask_asset_info: orai_info,
+                }],
+                minimum_receive: None,
+                to: None,
+                callback: None,
+            })
+            .unwrap(),
+            funds: vec![coin(500u128, ATOM_DENOM)],
+        }))]
+    );
+
+    // anyone can distribute, 1/4 to treasury and 3/4 to staking
+    let msg = ExecuteMsg::Distribute {};
+    let res = execute(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "treasury".to_string(),
+                amount: vec![coin(250u128, ORAI_DENOM)],
+            })),
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "staking".to_string(),
+                amount: vec![coin(750u128, ORAI_DENOM)],
+            })),
+        ]
+    );
+}
+
+#[test]
+fn buyback_and_burn() {
+    let mut deps = mock_dependencies();
+    let msg = default_instantiate_msg();
+    let _res = instantiate(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    // burn token is not set
+    let msg = ExecuteMsg::BuybackAndBurn {
+        asset: AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        max_spread: None,
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Std(StdError::generic_err("burn token is not set"))
+    );
+
+    // only contract itself can burn received tokens
+    let msg = ExecuteMsg::BurnReceived {
+        prev_balance: Uint128::zero(),
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // burn collected ORAIX directly
+    let mut app = MockApp::new(&[]);
+    app.set_token_contract(Box::new(create_entry_points_testing!(oraiswap_token)));
+    let oraix_addr = app.create_token("oraix");
+    app.set_token_balances(&[(
+        &"oraix".to_string(),
+        &[(&"addr0000".to_string(), &Uint128::from(1000u128))],
+    )]);
+
+    let code_id = app.upload(Box::new(create_entry_points_testing!(crate)));
+    let fee_collector_addr = app
+        .instantiate(
+            code_id,
+            Addr::unchecked("owner"),
+            &InstantiateMsg {
+                burn_token: Some(oraix_addr.clone()),
+                ..default_instantiate_msg()
+            },
+            &[],
+            "fee_collector",
+        )
+        .unwrap();
+
+    app.execute(
+        Addr::unchecked("addr0000"),
+        oraix_addr.clone(),
+        &cw20::Cw20ExecuteMsg::Transfer {
+            recipient: fee_collector_addr.to_string(),
+            amount: Uint128::from(100u128),
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute(
+        Addr::unchecked("owner"),
+        fee_collector_addr.clone(),
+        &ExecuteMsg::BuybackAndBurn {
+            asset: AssetInfo::Token {
+                contract_addr: oraix_addr.clone(),
+            },
+            max_spread: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let res: TotalBurnedResponse = app
+        .query(fee_collector_addr, &QueryMsg::TotalBurned {})
+        .unwrap();
+    assert_eq!(res.amount, Uint128::from(100u128));
+
+    let token_info = query_token_info(&app.as_querier(), oraix_addr).unwrap();
+    assert_eq!(token_info.total_supply, Uint128::from(900u128));
+}
@@ -0,0 +1,99 @@
+use cosmwasm_std::{Addr, CanonicalAddr, Deps, DepsMut, MessageInfo, Response, StdResult, Storage};
+use oraiswap::error::ContractError;
+use oraiswap::limit_order::{ContractInfo, Role, RoleHolder, RolesResponse};
+
+use crate::state::{read_config, read_role_holders, read_roles, store_roles};
+
+/// the admin and super admins hold every role, other addresses the roles granted to them
+pub fn has_role(
+    storage: &dyn Storage,
+    contract_info: &ContractInfo,
+    addr: &CanonicalAddr,
+    role: Role,
+) -> StdResult<bool> {
+    if contract_info.admin.eq(addr) {
+        return Ok(true);
+    }
+    let roles = read_roles(storage, addr)?;
+    Ok(roles.contains(&Role::SuperAdmin) || roles.contains(&role))
+}
+
+/// check the sender holds the role, return the contract info for the caller to update
+pub fn assert_role(
+    storage: &dyn Storage,
+    sender_addr: &CanonicalAddr,
+    role: Role,
+) -> Result<ContractInfo, ContractError> {
+    let contract_info = read_config(storage)?;
+    if !has_role(storage, &contract_info, sender_addr, role)? {
+        return Err(ContractError::Unauthorized {});
+    }
+    Ok(contract_info)
+}
+
+pub fn execute_grant_role(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: Addr,
+    role: Role,
+) -> Result<Response, ContractError> {
+    let sender_addr = deps.api.addr_canonicalize(info.sender.as_str())?;
+    assert_role(deps.storage, &sender_addr, Role::SuperAdmin)?;
+
+    let addr = deps.api.addr_canonicalize(address.as_str())?;
+    let mut roles = read_roles(deps.storage, &addr)?;
+    if !roles.contains(&role) {
+        roles.push(role);
+        store_roles(deps.storage, &addr, &roles)?;
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "grant_role"),
+        ("address", address.as_str()),
+        ("role", role.as_str()),
+    ]))
+}
+
+pub fn execute_revoke_role(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: Addr,
+    role: Role,
+) -> Result<Response, ContractError> {
+    let sender_addr = deps.api.addr_canonicalize(info.sender.as_str())?;
+    assert_role(deps.storage, &sender_addr, Role::SuperAdmin)?;
+
+    let addr = deps.api.addr_canonicalize(address.as_str())?;
+    let mut roles = read_roles(deps.storage, &addr)?;
+    roles.retain(|granted| granted.ne(&role));
+    store_roles(deps.storage, &addr, &roles)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "revoke_role"),
+        ("address", address.as_str()),
+        ("role", role.as_str()),
+    ]))
+}
+
+pub fn query_roles(
+    deps: Deps,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> StdResult<RolesResponse> {
+    let start_after = match start_after {
+        Some(start_after) => Some(deps.api.addr_canonicalize(start_after.as_str())?),
+        None => None,
+    };
+
+    let holders = read_role_holders(deps.storage, start_after, limit)?
+        .into_iter()
+        .map(|(addr, roles)| {
+            Ok(RoleHolder {
+                address: deps.api.addr_humanize(&addr)?,
+                roles,
+            })
+        })
+        .collect::<StdResult<Vec<RoleHolder>>>()?;
+
+    Ok(RolesResponse { holders })
+}
@@ -1,6 +1,11 @@
-use cosmwasm_schema::write_api;
+use std::env::current_dir;
 
-use oraiswap::limit_order::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use cosmwasm_schema::{export_schema, schema_for, write_api};
+
+use oraiswap::limit_order::{
+    Cw20HookMsg, ExecuteMsg, InstantiateMsg, MatchOrdersDryRunResponse, MigrateMsg,
+    OrderPermitPayload, QueryMsg,
+};
 
 fn main() {
     write_api! {
@@ -9,4 +14,13 @@ fn main() {
         query: QueryMsg,
         migrate: MigrateMsg
     }
+
+    // the responses of the queries are exported by write_api from their returns,
+    // the cw20 hook, the signed permit and the data of the execute messages are not
+    let mut out_dir = current_dir().unwrap();
+    out_dir.push("schema");
+    out_dir.push("raw");
+    export_schema(&schema_for!(Cw20HookMsg), &out_dir);
+    export_schema(&schema_for!(OrderPermitPayload), &out_dir);
+    export_schema(&schema_for!(MatchOrdersDryRunResponse), &out_dir);
 }
@@ -2,27 +2,45 @@
 use cosmwasm_std::entry_point;
 
 use cosmwasm_std::{
-    from_binary, to_binary, Addr, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Response,
-    StdResult, Uint128,
+    from_binary, to_binary, Addr, BankMsg, Binary, Coin, Decimal, Deps, DepsMut, Env, Event,
+    MessageInfo, QuerierWrapper, Reply, Response, StdError, StdResult, Storage, SubMsgResult,
+    Uint128, WasmMsg,
 };
 use oraiswap::error::ContractError;
 
+use crate::admin::{assert_role, execute_grant_role, execute_revoke_role, has_role, query_roles};
+use crate::incentive::{claim_incentives, query_incentive_points};
 use crate::order::{
-    cancel_order, query_last_order_id, query_order, query_orderbook,
-    query_orderbook_is_matchable, query_orderbooks, query_orders, remove_pair, submit_order, execute_matching_orders,
+    cancel_order, claim_escrow, claim_rebates, escrow_failed_payout, execute_matching_orders,
+    execute_matching_orders_dry_run, import_orders, query_candles, query_escrow,
+    query_export_orders, query_last_order_id, query_maker_rebates, query_matching_rules,
+    query_order, query_order_by_client_id, query_order_position, query_orderbook,
+    query_orderbook_is_matchable, query_orderbooks, query_orders, query_orders_at_price,
+    query_pair_sequence, query_pair_stats, query_total_escrow, refund_delisted_orders, remove_pair,
+    submit_order, update_order,
 };
 use crate::orderbook::OrderBook;
 use crate::state::{
-    init_last_order_id, read_config, read_orderbook, store_config, store_orderbook,
+    increase_order_nonce, init_last_order_id, read_all_orderbooks, read_config, read_order_nonce,
+    read_order_signer, read_orderbook, read_paused, read_registry, rebuild_order_escrow,
+    store_allow_import_orders, store_config, store_order_signer, store_orderbook, store_paused,
+    store_registry,
+};
+use crate::tick::{
+    query_malformed_tick_keys, query_tick, query_ticks_with_end, DEFAULT_PRICE_PRECISION,
 };
-use crate::tick::{query_tick, query_ticks_with_end};
 
-use cw20::Cw20ReceiveMsg;
+use cw2::{get_contract_version, set_contract_version};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
 use oraiswap::asset::{pair_key, Asset, AssetInfo};
+use oraiswap::converter::{ConvertInfoResponse, QueryMsg as ConverterQueryMsg};
 use oraiswap::limit_order::{
-    ContractInfo, ContractInfoResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg,
-    OrderDirection, QueryMsg,
+    ContractInfo, ContractInfoResponse, Cw20HookMsg, DirectionAndAssets, EventMode, ExecuteMsg,
+    InstantiateMsg, MigrateMsg, OrderBookSettings, OrderDirection, OrderPermit, OrderPermitPayload,
+    OrderSignerResponse, QueryMsg, Role,
 };
+use oraiswap::querier::{query_asset_decimals, query_pool};
+use sha2::{Digest, Sha256};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:oraiswap_limit_order";
@@ -66,11 +84,19 @@ pub fn instantiate(
         } else {
             default_spread_address
         },
+        guardian: None,
+        maker_rebate_rate: None,
+        event_mode: None,
+        rewarder: None,
+        referral: None,
+        oracle: None,
+        converter: None,
     };
 
     store_config(deps.storage, &config)?;
 
     init_last_order_id(deps.storage)?;
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     Ok(Response::default())
 }
@@ -78,18 +104,42 @@ pub fn instantiate(
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::Receive(msg) => receive_cw20(deps, info, msg),
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
         ExecuteMsg::UpdateAdmin { admin } => execute_update_admin(deps, info, admin),
+        ExecuteMsg::GrantRole { address, role } => execute_grant_role(deps, info, address, role),
+        ExecuteMsg::RevokeRole { address, role } => execute_revoke_role(deps, info, address, role),
         ExecuteMsg::UpdateConfig {
             reward_address,
             spread_address,
             commission_rate,
-        } => execute_update_config(deps, info, reward_address, spread_address, commission_rate),
+            guardian,
+            maker_rebate_rate,
+            event_mode,
+            rewarder,
+            referral,
+            oracle,
+            converter,
+        } => execute_update_config(
+            deps,
+            info,
+            reward_address,
+            spread_address,
+            commission_rate,
+            guardian,
+            maker_rebate_rate,
+            event_mode,
+            rewarder,
+            referral,
+            oracle,
+            converter,
+        ),
+        ExecuteMsg::Pause {} => execute_set_paused(deps, info, true),
+        ExecuteMsg::Unpause {} => execute_set_paused(deps, info, false),
         ExecuteMsg::CreateOrderBookPair {
             base_coin_info,
             quote_coin_info,
@@ -103,94 +153,131 @@ pub fn execute(
             spread,
             min_quote_coin_amount,
         ),
-        ExecuteMsg::SubmitOrder { direction, assets } => {
-            let pair_key = pair_key(&[
-                assets[0].to_raw(deps.api)?.info,
-                assets[1].to_raw(deps.api)?.info,
-            ]);
-            let orderbook_pair = read_orderbook(deps.storage, &pair_key)?;
-
-            // if sell then paid asset must be ask asset, this way we've just assumed that we offer usdt and ask for orai
-            // for execute order, it is direct match(user has known it is buy or sell) so no order is needed
-            // Buy: wanting ask asset(orai) => paid offer asset(usdt)
-            // Sell: paid ask asset(orai) => wating offer asset(usdt)
-            let paid_asset: &Asset;
-            let quote_asset: &Asset;
-
-            if orderbook_pair.base_coin_info.to_normal(deps.api)? == assets[0].info {
-                paid_asset = match direction {
-                    OrderDirection::Buy => &assets[1],
-                    OrderDirection::Sell => &assets[0],
-                };
-                quote_asset = &assets[1];
-            } else {
-                paid_asset = match direction {
-                    OrderDirection::Buy => &assets[0],
-                    OrderDirection::Sell => &assets[1],
-                };
-                quote_asset = &assets[0];
-            }
-
-            // if paid asset is cw20, we check it in Cw20HookMessage
-            if !paid_asset.is_native_token() {
-                return Err(ContractError::MustProvideNativeToken {});
-            }
-
-            paid_asset.assert_sent_native_token_balance(&info)?;
-
-            // require minimum amount for quote asset
-            if quote_asset.amount.lt(&orderbook_pair.min_quote_coin_amount) {
-                return Err(ContractError::TooSmallQuoteAsset {
-                    quote_coin: quote_asset.info.to_string(),
-                    min_quote_amount: orderbook_pair.min_quote_coin_amount,
-                });
-            }
-
-            // then submit order
-            if orderbook_pair.base_coin_info.to_normal(deps.api)? == assets[0].info {
-                match direction {
-                    OrderDirection::Buy => submit_order(
-                        deps,
-                        info.sender,
-                        &pair_key,
-                        direction,
-                        [assets[1].clone(), assets[0].clone()],
-                    ),
-                    OrderDirection::Sell => submit_order(
-                        deps,
-                        info.sender,
-                        &pair_key,
-                        direction,
-                        [assets[0].clone(), assets[1].clone()],
-                    ),
-                }
-            } else {
-                match direction {
-                    OrderDirection::Buy => submit_order(
-                        deps,
-                        info.sender,
-                        &pair_key,
-                        direction,
-                        [assets[0].clone(), assets[1].clone()],
-                    ),
-                    OrderDirection::Sell => submit_order(
-                        deps,
-                        info.sender,
-                        &pair_key,
-                        direction,
-                        [assets[1].clone(), assets[0].clone()],
-                    ),
-                }
-            }
+        ExecuteMsg::UpdateOrderBookPair {
+            asset_infos,
+            settings,
+        } => execute_update_orderbook_pair(deps, info, asset_infos, settings),
+        ExecuteMsg::UpdateOrderBookOperator {
+            asset_infos,
+            operator,
+        } => execute_update_orderbook_operator(deps, info, asset_infos, operator),
+        ExecuteMsg::UpdateOrderBookAuction {
+            asset_infos,
+            interval_seconds,
+        } => execute_update_orderbook_auction(deps, info, asset_infos, interval_seconds),
+        ExecuteMsg::UpdateOrderBookParams {
+            asset_infos,
+            spread,
+            min_quote_coin_amount,
+            paused,
+            price_precision,
+            min_quote_notional,
+            min_fill_ratio,
+            min_fill_base,
+        } => execute_update_orderbook_params(
+            deps,
+            info,
+            asset_infos,
+            spread,
+            min_quote_coin_amount,
+            paused,
+            price_precision,
+            min_quote_notional,
+            min_fill_ratio,
+            min_fill_base,
+        ),
+        ExecuteMsg::SubmitOrder {
+            direction,
+            assets,
+            client_id,
+        } => {
+            let bidder = info.sender.clone();
+            execute_submit_order(deps, env, info, bidder, direction, assets, client_id)
+        }
+        ExecuteMsg::SubmitQuoteOrder {
+            asset_infos,
+            direction,
+            price,
+            quote_amount,
+            client_id,
+        } => execute_submit_quote_order(
+            deps,
+            env,
+            info,
+            asset_infos,
+            direction,
+            price,
+            quote_amount,
+            client_id,
+        ),
+        ExecuteMsg::SubmitOrderOnBehalf {
+            order,
+            signature,
+            nonce,
+        } => execute_submit_order_on_behalf(deps, env, info, order, signature, nonce),
+        ExecuteMsg::SetOrderSigner { pubkey } => execute_set_order_signer(deps, info, pubkey),
+        ExecuteMsg::SubmitOrders {
+            direction_and_assets,
+        } => {
+            let provided_assets = info
+                .funds
+                .iter()
+                .map(|coin| Asset {
+                    info: AssetInfo::NativeToken {
+                        denom: coin.denom.clone(),
+                    },
+                    amount: coin.amount,
+                })
+                .collect();
+            execute_submit_orders(
+                deps,
+                env,
+                info.sender,
+                provided_assets,
+                direction_and_assets,
+            )
         }
         ExecuteMsg::CancelOrder {
             order_id,
             asset_infos,
-        } => cancel_order(deps, info, order_id, asset_infos),
+        } => cancel_order(deps, env, info, order_id, asset_infos),
+        ExecuteMsg::UpdateOrder { order_id, assets } => {
+            assert_not_paused(deps.storage)?;
+
+            // only the native offer asset can be sent along
+            let provided_asset = match info.funds.as_slice() {
+                [] => None,
+                [coin] => Some(Asset {
+                    info: AssetInfo::NativeToken {
+                        denom: coin.denom.clone(),
+                    },
+                    amount: coin.amount,
+                }),
+                _ => return Err(ContractError::AssetMismatch {}),
+            };
+            update_order(deps, info.sender, order_id, assets, provided_asset)
+        }
         ExecuteMsg::ExecuteOrderBookPair { asset_infos, limit } => {
-            execute_matching_orders(deps, info, asset_infos, limit)
+            assert_not_paused(deps.storage)?;
+            execute_matching_orders(deps, env, info, asset_infos, limit)
+        }
+        ExecuteMsg::MatchOrdersDryRun { asset_infos, limit } => {
+            execute_matching_orders_dry_run(deps, info, asset_infos, limit)
         }
-        ExecuteMsg::RemoveOrderBookPair { asset_infos } => remove_pair(deps, info, asset_infos),
+        ExecuteMsg::RemoveOrderBookPair {
+            asset_infos,
+            grace_period_seconds,
+        } => remove_pair(deps, env, info, asset_infos, grace_period_seconds),
+        ExecuteMsg::RefundDelistedOrders { asset_infos, limit } => {
+            refund_delisted_orders(deps, env, info, asset_infos, limit)
+        }
+        ExecuteMsg::ImportOrders {
+            asset_infos,
+            orders,
+        } => import_orders(deps, info, asset_infos, orders),
+        ExecuteMsg::ClaimRebates {} => claim_rebates(deps, info),
+        ExecuteMsg::ClaimEscrow {} => claim_escrow(deps, info),
+        ExecuteMsg::ClaimIncentives {} => claim_incentives(deps, info),
     }
 }
 
@@ -199,13 +286,8 @@ pub fn execute_update_admin(
     info: MessageInfo,
     admin: Addr,
 ) -> Result<Response, ContractError> {
-    let mut contract_info = read_config(deps.storage)?;
     let sender_addr = deps.api.addr_canonicalize(info.sender.as_str())?;
-
-    // check authorized
-    if contract_info.admin.ne(&sender_addr) {
-        return Err(ContractError::Unauthorized {});
-    }
+    let mut contract_info = assert_role(deps.storage, &sender_addr, Role::SuperAdmin)?;
 
     // update new admin
     contract_info.admin = deps.api.addr_canonicalize(admin.as_str())?;
@@ -214,20 +296,38 @@ pub fn execute_update_admin(
     Ok(Response::new().add_attributes(vec![("action", "execute_update_admin")]))
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn execute_update_config(
     deps: DepsMut,
     info: MessageInfo,
     reward_address: Option<Addr>,
     spread_address: Option<Addr>,
     commission_rate: Option<String>,
+    guardian: Option<Addr>,
+    maker_rebate_rate: Option<Decimal>,
+    event_mode: Option<EventMode>,
+    rewarder: Option<Addr>,
+    referral: Option<Addr>,
+    oracle: Option<Addr>,
+    converter: Option<Addr>,
 ) -> Result<Response, ContractError> {
-    let mut contract_info = read_config(deps.storage)?;
     let sender_addr = deps.api.addr_canonicalize(info.sender.as_str())?;
 
-    // check authorized
-    if contract_info.admin.ne(&sender_addr) {
-        return Err(ContractError::Unauthorized {});
-    }
+    // fee manager can only update the fee rates, the addresses and modes need a super admin
+    let role = if reward_address.is_none()
+        && spread_address.is_none()
+        && guardian.is_none()
+        && event_mode.is_none()
+        && rewarder.is_none()
+        && referral.is_none()
+        && oracle.is_none()
+        && converter.is_none()
+    {
+        Role::FeeManager
+    } else {
+        Role::SuperAdmin
+    };
+    let mut contract_info = assert_role(deps.storage, &sender_addr, role)?;
 
     // update new reward address
     if let Some(reward_address) = reward_address {
@@ -244,11 +344,82 @@ pub fn execute_update_config(
         contract_info.commission_rate = commission_rate;
     }
 
+    // update new guardian
+    if let Some(guardian) = guardian {
+        contract_info.guardian = Some(deps.api.addr_canonicalize(guardian.as_str())?);
+    }
+
+    // update new maker rebate rate, zero rate disables the rebates
+    if let Some(maker_rebate_rate) = maker_rebate_rate {
+        if maker_rebate_rate > Decimal::one() {
+            return Err(ContractError::Std(StdError::generic_err(
+                "Maker rebate rate must not exceed 1",
+            )));
+        }
+        contract_info.maker_rebate_rate = if maker_rebate_rate.is_zero() {
+            None
+        } else {
+            Some(maker_rebate_rate)
+        };
+    }
+
+    // update new event mode
+    if let Some(event_mode) = event_mode {
+        contract_info.event_mode = Some(event_mode);
+    }
+
+    // update new rewarder paying the incentive points
+    if let Some(rewarder) = rewarder {
+        contract_info.rewarder = Some(deps.api.addr_canonicalize(rewarder.as_str())?);
+    }
+
+    // update new referral contract the referrers are paid through
+    if let Some(referral) = referral {
+        contract_info.referral = Some(deps.api.addr_canonicalize(referral.as_str())?);
+    }
+
+    // update new oracle converting the fees quoted in usd
+    if let Some(oracle) = oracle {
+        contract_info.oracle = Some(deps.api.addr_canonicalize(oracle.as_str())?);
+    }
+
+    // update new converter the equivalent assets of the order books are resolved with
+    if let Some(converter) = converter {
+        contract_info.converter = Some(deps.api.addr_canonicalize(converter.as_str())?);
+    }
+
     store_config(deps.storage, &contract_info)?;
 
     Ok(Response::new().add_attributes(vec![("action", "execute_update_config")]))
 }
 
+pub fn execute_set_paused(
+    deps: DepsMut,
+    info: MessageInfo,
+    paused: bool,
+) -> Result<Response, ContractError> {
+    let contract_info = read_config(deps.storage)?;
+    let sender_addr = deps.api.addr_canonicalize(info.sender.as_str())?;
+
+    // check authorized, both pauser and guardian can pause or unpause
+    if contract_info.guardian.as_ref() != Some(&sender_addr)
+        && !has_role(deps.storage, &contract_info, &sender_addr, Role::Pauser)?
+    {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    store_paused(deps.storage, paused)?;
+
+    Ok(Response::new().add_attributes(vec![("action", if paused { "pause" } else { "unpause" })]))
+}
+
+fn assert_not_paused(storage: &dyn Storage) -> Result<(), ContractError> {
+    if read_paused(storage) {
+        return Err(ContractError::Paused {});
+    }
+    Ok(())
+}
+
 pub fn execute_create_pair(
     deps: DepsMut,
     info: MessageInfo,
@@ -257,14 +428,24 @@ pub fn execute_create_pair(
     spread: Option<Decimal>,
     min_quote_coin_amount: Uint128,
 ) -> Result<Response, ContractError> {
-    let contract_info = read_config(deps.storage)?;
     let sender_addr = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let contract_info = assert_role(deps.storage, &sender_addr, Role::Operator)?;
 
-    // check authorized
-    if contract_info.admin.ne(&sender_addr) {
-        return Err(ContractError::Unauthorized {});
+    // an asset can not be traded against itself, or against the asset the converter converts it into
+    let converter = match contract_info.converter {
+        Some(converter) => Some(deps.api.addr_humanize(&converter)?),
+        None => None,
+    };
+    if converted_asset_info(&deps.querier, converter.as_ref(), &base_coin_info).eq(
+        &converted_asset_info(&deps.querier, converter.as_ref(), &quote_coin_info),
+    ) {
+        return Err(ContractError::IdenticalOrderBookAssets {
+            base_coin: base_coin_info.to_string(),
+            quote_coin: quote_coin_info.to_string(),
+        });
     }
 
+    // the pair key sorts the assets, so the reversed pair is the same order book
     let pair_key = pair_key(&[
         base_coin_info.to_raw(deps.api)?,
         quote_coin_info.to_raw(deps.api)?,
@@ -277,11 +458,28 @@ pub fn execute_create_pair(
         return Err(ContractError::OrderBookAlreadyExists {});
     }
 
+    // prices of the order book are quoted in whole coins
+    let registry = match read_registry(deps.storage)? {
+        Some(registry) => Some(deps.api.addr_humanize(&registry)?),
+        None => None,
+    };
     let order_book = OrderBook {
-        base_coin_info: base_coin_info.to_raw(deps.api)?,
-        quote_coin_info: quote_coin_info.to_raw(deps.api)?,
-        spread,
         min_quote_coin_amount,
+        base_decimals: Some(query_asset_decimals(
+            &deps.querier,
+            registry.clone(),
+            &base_coin_info,
+        )?),
+        quote_decimals: Some(query_asset_decimals(
+            &deps.querier,
+            registry,
+            &quote_coin_info,
+        )?),
+        ..OrderBook::new(
+            base_coin_info.to_raw(deps.api)?,
+            quote_coin_info.to_raw(deps.api)?,
+            spread,
+        )
     };
     store_orderbook(deps.storage, &pair_key, &order_book)?;
 
@@ -293,8 +491,825 @@ pub fn execute_create_pair(
     ]))
 }
 
+/// the asset the converter converts the asset into, or the asset itself when it is not registered
+fn converted_asset_info(
+    querier: &QuerierWrapper,
+    converter: Option<&Addr>,
+    asset_info: &AssetInfo,
+) -> AssetInfo {
+    converter
+        .and_then(|converter| {
+            querier
+                .query_wasm_smart::<ConvertInfoResponse>(
+                    converter,
+                    &ConverterQueryMsg::ConvertInfo {
+                        asset_info: asset_info.clone(),
+                    },
+                )
+                .ok()
+        })
+        .map(|res| res.token_ratio.info)
+        .unwrap_or_else(|| asset_info.clone())
+}
+
+pub fn execute_update_orderbook_pair(
+    deps: DepsMut,
+    info: MessageInfo,
+    asset_infos: [AssetInfo; 2],
+    settings: OrderBookSettings,
+) -> Result<Response, ContractError> {
+    let sender_addr = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let contract_info = assert_role(deps.storage, &sender_addr, Role::Operator)?;
+
+    let pair_key = pair_key(&[
+        asset_infos[0].to_raw(deps.api)?,
+        asset_infos[1].to_raw(deps.api)?,
+    ]);
+    let mut order_book = read_orderbook(deps.storage, &pair_key)?;
+
+    // only the given settings are updated, a zero or empty value removes the setting
+    let OrderBookSettings {
+        min_order_lifetime_seconds,
+        cancel_fee_rate,
+        free_cancel_blocks,
+        max_executions_per_block,
+        price_reference,
+        max_price_deviation,
+        relayer_fee_rate,
+        min_relayer_fee,
+        max_relayer_fee,
+        fee_usd_denom,
+        max_orders_per_user,
+        incentive_price_band,
+    } = settings;
+    if let Some(min_order_lifetime_seconds) = min_order_lifetime_seconds {
+        order_book.min_order_lifetime_seconds = if min_order_lifetime_seconds == 0 {
+            None
+        } else {
+            Some(min_order_lifetime_seconds)
+        };
+    }
+    if let Some(cancel_fee_rate) = cancel_fee_rate {
+        if cancel_fee_rate > Decimal::one() {
+            return Err(ContractError::Std(StdError::generic_err(
+                "cancel fee rate must not be greater than 1",
+            )));
+        }
+        order_book.cancel_fee_rate = if cancel_fee_rate.is_zero() {
+            None
+        } else {
+            Some(cancel_fee_rate)
+        };
+    }
+    if let Some(free_cancel_blocks) = free_cancel_blocks {
+        order_book.free_cancel_blocks = if free_cancel_blocks == 0 {
+            None
+        } else {
+            Some(free_cancel_blocks)
+        };
+    }
+    if let Some(max_executions_per_block) = max_executions_per_block {
+        order_book.max_executions_per_block = if max_executions_per_block == 0 {
+            None
+        } else {
+            Some(max_executions_per_block)
+        };
+    }
+    if let Some(price_reference) = price_reference {
+        order_book.price_reference = if price_reference.as_str().is_empty() {
+            None
+        } else {
+            // reference pair must provide both assets of the order book
+            let pool = query_pool(&deps.querier, price_reference.clone())?;
+            for asset_info in asset_infos.iter() {
+                if !pool.assets.iter().any(|asset| asset.info.eq(asset_info)) {
+                    return Err(ContractError::Std(StdError::generic_err(
+                        "price reference pair does not match the order book",
+                    )));
+                }
+            }
+            Some(deps.api.addr_canonicalize(price_reference.as_str())?)
+        };
+    }
+    if let Some(max_price_deviation) = max_price_deviation {
+        order_book.max_price_deviation = if max_price_deviation.is_zero() {
+            None
+        } else {
+            Some(max_price_deviation)
+        };
+    }
+    if let Some(relayer_fee_rate) = relayer_fee_rate {
+        if relayer_fee_rate > Decimal::one() {
+            return Err(ContractError::Std(StdError::generic_err(
+                "relayer fee rate must not be greater than 1",
+            )));
+        }
+        order_book.relayer_fee_rate = if relayer_fee_rate.is_zero() {
+            None
+        } else {
+            Some(relayer_fee_rate)
+        };
+    }
+    if let Some(min_relayer_fee) = min_relayer_fee {
+        order_book.min_relayer_fee = if min_relayer_fee.is_zero() {
+            None
+        } else {
+            Some(min_relayer_fee)
+        };
+    }
+    if let Some(max_relayer_fee) = max_relayer_fee {
+        order_book.max_relayer_fee = if max_relayer_fee.is_zero() {
+            None
+        } else {
+            Some(max_relayer_fee)
+        };
+    }
+    if let (Some(min_relayer_fee), Some(max_relayer_fee)) =
+        (order_book.min_relayer_fee, order_book.max_relayer_fee)
+    {
+        if min_relayer_fee > max_relayer_fee {
+            return Err(ContractError::Std(StdError::generic_err(
+                "min relayer fee must not be greater than max relayer fee",
+            )));
+        }
+    }
+    if let Some(fee_usd_denom) = fee_usd_denom {
+        order_book.fee_usd_denom = if fee_usd_denom.is_empty() {
+            None
+        } else {
+            // fees quoted in usd are converted at match time, so the oracle must be set first
+            if contract_info.oracle.is_none() {
+                return Err(ContractError::Std(StdError::generic_err(
+                    "oracle is not set to convert the usd fees",
+                )));
+            }
+            Some(fee_usd_denom)
+        };
+    }
+    if let Some(max_orders_per_user) = max_orders_per_user {
+        order_book.max_orders_per_user = if max_orders_per_user == 0 {
+            None
+        } else {
+            Some(max_orders_per_user)
+        };
+    }
+    if let Some(incentive_price_band) = incentive_price_band {
+        if incentive_price_band > Decimal::one() {
+            return Err(ContractError::Std(StdError::generic_err(
+                "incentive price band must not be greater than 1",
+            )));
+        }
+        order_book.incentive_price_band = if incentive_price_band.is_zero() {
+            None
+        } else {
+            Some(incentive_price_band)
+        };
+    }
+    store_orderbook(deps.storage, &pair_key, &order_book)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "update_orderbook_pair"),
+        (
+            "pair",
+            &format!("{} - {}", &asset_infos[0], &asset_infos[1]),
+        ),
+        (
+            "min_order_lifetime_seconds",
+            &order_book
+                .min_order_lifetime_seconds
+                .unwrap_or_default()
+                .to_string(),
+        ),
+        (
+            "cancel_fee_rate",
+            &format!("{:.5}", order_book.cancel_fee_rate.unwrap_or_default()),
+        ),
+        (
+            "free_cancel_blocks",
+            &order_book
+                .free_cancel_blocks
+                .unwrap_or_default()
+                .to_string(),
+        ),
+        (
+            "max_executions_per_block",
+            &order_book
+                .max_executions_per_block
+                .unwrap_or_default()
+                .to_string(),
+        ),
+        (
+            "max_price_deviation",
+            &format!("{:.5}", order_book.max_price_deviation.unwrap_or_default()),
+        ),
+        (
+            "relayer_fee_rate",
+            &format!("{:.5}", order_book.relayer_fee_rate.unwrap_or_default()),
+        ),
+        (
+            "max_orders_per_user",
+            &order_book
+                .max_orders_per_user
+                .unwrap_or_default()
+                .to_string(),
+        ),
+    ]))
+}
+
+pub fn execute_update_orderbook_operator(
+    deps: DepsMut,
+    info: MessageInfo,
+    asset_infos: [AssetInfo; 2],
+    operator: Option<Addr>,
+) -> Result<Response, ContractError> {
+    let sender_addr = deps.api.addr_canonicalize(info.sender.as_str())?;
+    assert_role(deps.storage, &sender_addr, Role::Operator)?;
+
+    let pair_key = pair_key(&[
+        asset_infos[0].to_raw(deps.api)?,
+        asset_infos[1].to_raw(deps.api)?,
+    ]);
+    let mut order_book = read_orderbook(deps.storage, &pair_key)?;
+    order_book.operator = match &operator {
+        Some(operator) => Some(deps.api.addr_canonicalize(operator.as_str())?),
+        None => None,
+    };
+    store_orderbook(deps.storage, &pair_key, &order_book)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "update_orderbook_operator"),
+        (
+            "pair",
+            &format!("{} - {}", &asset_infos[0], &asset_infos[1]),
+        ),
+        (
+            "operator",
+            operator
+                .as_ref()
+                .map(|operator| operator.as_str())
+                .unwrap_or_default(),
+        ),
+    ]))
+}
+
+pub fn execute_update_orderbook_auction(
+    deps: DepsMut,
+    info: MessageInfo,
+    asset_infos: [AssetInfo; 2],
+    interval_seconds: Option<u64>,
+) -> Result<Response, ContractError> {
+    let sender_addr = deps.api.addr_canonicalize(info.sender.as_str())?;
+    assert_role(deps.storage, &sender_addr, Role::Operator)?;
+
+    if interval_seconds == Some(0) {
+        return Err(ContractError::Std(StdError::generic_err(
+            "auction interval must be greater than 0",
+        )));
+    }
+
+    let pair_key = pair_key(&[
+        asset_infos[0].to_raw(deps.api)?,
+        asset_infos[1].to_raw(deps.api)?,
+    ]);
+    let mut order_book = read_orderbook(deps.storage, &pair_key)?;
+    order_book.auction_interval_seconds = interval_seconds;
+    store_orderbook(deps.storage, &pair_key, &order_book)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "update_orderbook_auction"),
+        (
+            "pair",
+            &format!("{} - {}", &asset_infos[0], &asset_infos[1]),
+        ),
+        (
+            "interval_seconds",
+            &interval_seconds.unwrap_or_default().to_string(),
+        ),
+    ]))
+}
+
+/// operator of the order book or Operator role update its non-critical parameters
+#[allow(clippy::too_many_arguments)]
+pub fn execute_update_orderbook_params(
+    deps: DepsMut,
+    info: MessageInfo,
+    asset_infos: [AssetInfo; 2],
+    spread: Option<Decimal>,
+    min_quote_coin_amount: Option<Uint128>,
+    paused: Option<bool>,
+    price_precision: Option<u32>,
+    min_quote_notional: Option<Uint128>,
+    min_fill_ratio: Option<Decimal>,
+    min_fill_base: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    let contract_info = read_config(deps.storage)?;
+    let sender_addr = deps.api.addr_canonicalize(info.sender.as_str())?;
+
+    let pair_key = pair_key(&[
+        asset_infos[0].to_raw(deps.api)?,
+        asset_infos[1].to_raw(deps.api)?,
+    ]);
+    let mut order_book = read_orderbook(deps.storage, &pair_key)?;
+
+    // check authorized
+    if order_book.operator.as_ref() != Some(&sender_addr)
+        && !has_role(deps.storage, &contract_info, &sender_addr, Role::Operator)?
+    {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(spread) = spread {
+        if spread >= Decimal::one() {
+            return Err(ContractError::Std(StdError::generic_err(
+                "spread must be less than 1",
+            )));
+        }
+        order_book.spread = Some(spread);
+    }
+    if let Some(min_quote_coin_amount) = min_quote_coin_amount {
+        order_book.min_quote_coin_amount = min_quote_coin_amount;
+    }
+    if let Some(paused) = paused {
+        order_book.paused = Some(paused);
+    }
+    if let Some(price_precision) = price_precision {
+        if price_precision > Decimal::DECIMAL_PLACES {
+            return Err(ContractError::Std(StdError::generic_err(
+                "price precision must not exceed 18",
+            )));
+        }
+        order_book.price_precision = Some(price_precision);
+    }
+    if let Some(min_quote_notional) = min_quote_notional {
+        order_book.min_quote_notional = if min_quote_notional.is_zero() {
+            None
+        } else {
+            Some(min_quote_notional)
+        };
+    }
+    if let Some(min_fill_ratio) = min_fill_ratio {
+        if min_fill_ratio > Decimal::one() {
+            return Err(ContractError::Std(StdError::generic_err(
+                "min fill ratio must not be greater than 1",
+            )));
+        }
+        order_book.min_fill_ratio = if min_fill_ratio.is_zero() {
+            None
+        } else {
+            Some(min_fill_ratio)
+        };
+    }
+    if let Some(min_fill_base) = min_fill_base {
+        order_book.min_fill_base = if min_fill_base.is_zero() {
+            None
+        } else {
+            Some(min_fill_base)
+        };
+    }
+    store_orderbook(deps.storage, &pair_key, &order_book)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "update_orderbook_params"),
+        (
+            "pair",
+            &format!("{} - {}", &asset_infos[0], &asset_infos[1]),
+        ),
+        (
+            "spread",
+            &format!("{:.5}", order_book.spread.unwrap_or_default()),
+        ),
+        (
+            "min_quote_coin_amount",
+            &order_book.min_quote_coin_amount.to_string(),
+        ),
+        ("paused", &order_book.paused.unwrap_or_default().to_string()),
+        (
+            "price_precision",
+            &order_book
+                .price_precision
+                .unwrap_or(DEFAULT_PRICE_PRECISION)
+                .to_string(),
+        ),
+        (
+            "min_quote_notional",
+            &order_book
+                .min_quote_notional
+                .unwrap_or_default()
+                .to_string(),
+        ),
+        (
+            "min_fill_ratio",
+            &order_book.min_fill_ratio.unwrap_or_default().to_string(),
+        ),
+        (
+            "min_fill_base",
+            &order_book.min_fill_base.unwrap_or_default().to_string(),
+        ),
+    ]))
+}
+
+/// submit an order of the bidder, who is the sender unless the order is submitted on behalf of it
+pub fn execute_submit_order(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    bidder: Addr,
+    direction: OrderDirection,
+    assets: [Asset; 2],
+    client_id: Option<String>,
+) -> Result<Response, ContractError> {
+    assert_not_paused(deps.storage)?;
+
+    let pair_key = pair_key(&[
+        assets[0].to_raw(deps.api)?.info,
+        assets[1].to_raw(deps.api)?.info,
+    ]);
+    let orderbook_pair = read_orderbook(deps.storage, &pair_key)?;
+
+    // if sell then paid asset must be ask asset, this way we've just assumed that we offer usdt and ask for orai
+    // for execute order, it is direct match(user has known it is buy or sell) so no order is needed
+    // Buy: wanting ask asset(orai) => paid offer asset(usdt)
+    // Sell: paid ask asset(orai) => wating offer asset(usdt)
+    let paid_asset: &Asset;
+    let quote_asset: &Asset;
+
+    if orderbook_pair.base_coin_info.to_normal(deps.api)? == assets[0].info {
+        paid_asset = match direction {
+            OrderDirection::Buy => &assets[1],
+            OrderDirection::Sell => &assets[0],
+        };
+        quote_asset = &assets[1];
+    } else {
+        paid_asset = match direction {
+            OrderDirection::Buy => &assets[0],
+            OrderDirection::Sell => &assets[1],
+        };
+        quote_asset = &assets[0];
+    }
+
+    // if paid asset is cw20, it is pulled from the bidder's allowance
+    // as an alternative to the Send hook
+    let transfer_msg = match &paid_asset.info {
+        AssetInfo::Token { contract_addr } => Some(WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+                owner: bidder.to_string(),
+                recipient: env.contract.address.to_string(),
+                amount: paid_asset.amount,
+            })?,
+            funds: vec![],
+        }),
+        AssetInfo::NativeToken { .. } => {
+            // native funds can only come from the bidder itself
+            if bidder != info.sender {
+                return Err(ContractError::MustProvideNativeToken {});
+            }
+            paid_asset.assert_sent_native_token_balance(&info)?;
+            None
+        }
+    };
+
+    // require minimum amount for quote asset
+    if quote_asset.amount.lt(&orderbook_pair.min_quote_coin_amount) {
+        return Err(ContractError::TooSmallQuoteAsset {
+            quote_coin: quote_asset.info.to_string(),
+            min_quote_amount: orderbook_pair.min_quote_coin_amount,
+        });
+    }
+
+    // refund other native tokens sent along with the paid asset
+    let refund_funds: Vec<Coin> = info
+        .funds
+        .iter()
+        .filter(|coin| {
+            !paid_asset.info.eq(&AssetInfo::NativeToken {
+                denom: coin.denom.clone(),
+            })
+        })
+        .cloned()
+        .collect();
+
+    // then submit order
+    let res = if orderbook_pair.base_coin_info.to_normal(deps.api)? == assets[0].info {
+        match direction {
+            OrderDirection::Buy => submit_order(
+                deps,
+                &env,
+                bidder.clone(),
+                &pair_key,
+                direction,
+                [assets[1].clone(), assets[0].clone()],
+                client_id,
+            ),
+            OrderDirection::Sell => submit_order(
+                deps,
+                &env,
+                bidder.clone(),
+                &pair_key,
+                direction,
+                [assets[0].clone(), assets[1].clone()],
+                client_id,
+            ),
+        }
+    } else {
+        match direction {
+            OrderDirection::Buy => submit_order(
+                deps,
+                &env,
+                bidder.clone(),
+                &pair_key,
+                direction,
+                [assets[0].clone(), assets[1].clone()],
+                client_id,
+            ),
+            OrderDirection::Sell => submit_order(
+                deps,
+                &env,
+                bidder.clone(),
+                &pair_key,
+                direction,
+                [assets[1].clone(), assets[0].clone()],
+                client_id,
+            ),
+        }
+    }?;
+
+    let res = match transfer_msg {
+        Some(transfer_msg) => res.add_message(transfer_msg),
+        None => res,
+    };
+
+    if refund_funds.is_empty() {
+        Ok(res)
+    } else {
+        Ok(res.add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: refund_funds,
+        }))
+    }
+}
+
+/// submit an order entered as a quote amount at a price, so clients do not round the base amount themselves
+#[allow(clippy::too_many_arguments)]
+pub fn execute_submit_quote_order(
+    deps: DepsMut,
+    env: Env,
+    mut info: MessageInfo,
+    asset_infos: [AssetInfo; 2],
+    direction: OrderDirection,
+    price: Decimal,
+    quote_amount: Uint128,
+    client_id: Option<String>,
+) -> Result<Response, ContractError> {
+    if price.is_zero() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "price must not be zero",
+        )));
+    }
+
+    let pair_key = pair_key(&[
+        asset_infos[0].to_raw(deps.api)?,
+        asset_infos[1].to_raw(deps.api)?,
+    ]);
+    let orderbook_pair = read_orderbook(deps.storage, &pair_key)?;
+
+    // the quote amount is rounded down to the base amount, so the order price is the closest one not above the price
+    let base_amount = orderbook_pair.to_base_amount(quote_amount, price)?;
+    let quote_amount = orderbook_pair.to_quote_amount(base_amount, price)?;
+    if base_amount.is_zero() || quote_amount.is_zero() {
+        return Err(ContractError::AssetMustNotBeZero {});
+    }
+
+    let assets = [
+        Asset {
+            info: orderbook_pair.base_coin_info.to_normal(deps.api)?,
+            amount: base_amount,
+        },
+        Asset {
+            info: orderbook_pair.quote_coin_info.to_normal(deps.api)?,
+            amount: quote_amount,
+        },
+    ];
+    let paid_asset = match direction {
+        OrderDirection::Buy => &assets[1],
+        OrderDirection::Sell => &assets[0],
+    };
+
+    // the bidder can not know the rounded paid amount, native funds sent above it are refunded
+    let mut refund_funds = vec![];
+    if let AssetInfo::NativeToken { denom } = &paid_asset.info {
+        for coin in info.funds.iter_mut() {
+            if coin.denom.eq(denom) && coin.amount > paid_asset.amount {
+                refund_funds.push(Coin {
+                    denom: denom.clone(),
+                    amount: coin.amount - paid_asset.amount,
+                });
+                coin.amount = paid_asset.amount;
+            }
+        }
+    }
+
+    let bidder = info.sender.clone();
+    let res = execute_submit_order(
+        deps,
+        env,
+        info,
+        bidder.clone(),
+        direction,
+        assets,
+        client_id,
+    )?;
+
+    if refund_funds.is_empty() {
+        Ok(res)
+    } else {
+        Ok(res.add_message(BankMsg::Send {
+            to_address: bidder.to_string(),
+            amount: refund_funds,
+        }))
+    }
+}
+
+/// submit an order permit signed by the bidder, the sender only pays the gas
+pub fn execute_submit_order_on_behalf(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    order: OrderPermit,
+    signature: Binary,
+    nonce: u64,
+) -> Result<Response, ContractError> {
+    let bidder = deps.api.addr_canonicalize(order.bidder.as_str())?;
+    let pubkey = read_order_signer(deps.storage, &bidder)?.ok_or(ContractError::Unauthorized {})?;
+
+    // each permit can only be submitted once
+    let expected_nonce = read_order_nonce(deps.storage, &bidder)?;
+    if nonce != expected_nonce {
+        return Err(ContractError::InvalidNonce {
+            expected: expected_nonce,
+        });
+    }
+
+    let payload = to_binary(&OrderPermitPayload {
+        contract_addr: env.contract.address.clone(),
+        chain_id: env.block.chain_id.clone(),
+        nonce,
+        order: order.clone(),
+    })?;
+    let message_hash = Sha256::digest(payload.as_slice());
+    if !deps
+        .api
+        .secp256k1_verify(&message_hash, &signature, &pubkey)
+        .unwrap_or(false)
+    {
+        return Err(ContractError::InvalidSignature {});
+    }
+    increase_order_nonce(deps.storage, &bidder)?;
+
+    let res = execute_submit_order(
+        deps,
+        env,
+        info.clone(),
+        order.bidder,
+        order.direction,
+        order.assets,
+        order.client_id,
+    )?;
+
+    Ok(res.add_attributes(vec![
+        ("submitted_by", info.sender.to_string()),
+        ("nonce", nonce.to_string()),
+    ]))
+}
+
+pub fn execute_set_order_signer(
+    deps: DepsMut,
+    info: MessageInfo,
+    pubkey: Option<Binary>,
+) -> Result<Response, ContractError> {
+    let bidder = deps.api.addr_canonicalize(info.sender.as_str())?;
+    store_order_signer(deps.storage, &bidder, pubkey.as_ref())?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "set_order_signer"),
+        ("bidder_addr", info.sender.as_str()),
+        (
+            "pubkey",
+            &pubkey.map(|pubkey| pubkey.to_base64()).unwrap_or_default(),
+        ),
+    ]))
+}
+
+/// submit orders in one transaction, provided assets must cover exactly the total paid assets of the orders,
+/// other provided assets are refunded
+pub fn execute_submit_orders(
+    mut deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    provided_assets: Vec<Asset>,
+    direction_and_assets: Vec<DirectionAndAssets>,
+) -> Result<Response, ContractError> {
+    assert_not_paused(deps.storage)?;
+
+    if direction_and_assets.is_empty() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "no orders to submit",
+        )));
+    }
+
+    // validate all orders and sum up paid assets before storing any order
+    let mut paid_assets: Vec<Asset> = vec![];
+    let mut orders: Vec<(Vec<u8>, OrderDirection, [Asset; 2])> = vec![];
+    for DirectionAndAssets { direction, assets } in direction_and_assets {
+        let pair_key = pair_key(&[
+            assets[0].to_raw(deps.api)?.info,
+            assets[1].to_raw(deps.api)?.info,
+        ]);
+        let orderbook_pair = read_orderbook(deps.storage, &pair_key)?;
+
+        // offer asset is the paid asset, same as SubmitOrder
+        let is_base_first = orderbook_pair.base_coin_info.to_normal(deps.api)? == assets[0].info;
+        let (offer_asset, ask_asset) = match (is_base_first, direction) {
+            (true, OrderDirection::Buy) | (false, OrderDirection::Sell) => {
+                (assets[1].clone(), assets[0].clone())
+            }
+            (true, OrderDirection::Sell) | (false, OrderDirection::Buy) => {
+                (assets[0].clone(), assets[1].clone())
+            }
+        };
+        let quote_asset = if is_base_first {
+            &assets[1]
+        } else {
+            &assets[0]
+        };
+
+        // require minimum amount for quote asset
+        if quote_asset.amount.lt(&orderbook_pair.min_quote_coin_amount) {
+            return Err(ContractError::TooSmallQuoteAsset {
+                quote_coin: quote_asset.info.to_string(),
+                min_quote_amount: orderbook_pair.min_quote_coin_amount,
+            });
+        }
+
+        match paid_assets
+            .iter_mut()
+            .find(|paid_asset| paid_asset.info.eq(&offer_asset.info))
+        {
+            Some(paid_asset) => paid_asset.amount += offer_asset.amount,
+            None => paid_assets.push(offer_asset.clone()),
+        }
+
+        orders.push((pair_key, direction, [offer_asset, ask_asset]));
+    }
+
+    // provided assets must cover exactly the paid assets
+    for paid_asset in paid_assets.iter() {
+        let provided_amount = provided_assets
+            .iter()
+            .find(|provided_asset| provided_asset.info.eq(&paid_asset.info))
+            .map(|provided_asset| provided_asset.amount)
+            .unwrap_or_default();
+        if provided_amount != paid_asset.amount {
+            return Err(ContractError::AssetMismatch {});
+        }
+    }
+
+    let mut messages = vec![];
+    for provided_asset in provided_assets {
+        if !paid_assets
+            .iter()
+            .any(|paid_asset| paid_asset.info.eq(&provided_asset.info))
+        {
+            messages.push(provided_asset.into_msg(None, &deps.querier, sender.clone())?);
+        }
+    }
+
+    let mut events = vec![];
+    for (pair_key, direction, assets) in orders {
+        let res = submit_order(
+            deps.branch(),
+            &env,
+            sender.clone(),
+            &pair_key,
+            direction,
+            assets,
+            None,
+        )?;
+        events.push(Event::new("submit_order").add_attributes(res.attributes));
+    }
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attributes(vec![
+            ("action", "submit_orders"),
+            ("bidder_addr", sender.as_str()),
+        ])
+        .add_events(events))
+}
+
 pub fn receive_cw20(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     cw20_msg: Cw20ReceiveMsg,
 ) -> Result<Response, ContractError> {
@@ -308,7 +1323,13 @@ pub fn receive_cw20(
     };
 
     match from_binary(&cw20_msg.msg) {
-        Ok(Cw20HookMsg::SubmitOrder { direction, assets }) => {
+        Ok(Cw20HookMsg::SubmitOrder {
+            direction,
+            assets,
+            client_id,
+        }) => {
+            assert_not_paused(deps.storage)?;
+
             let pair_key = pair_key(&[
                 assets[0].to_raw(deps.api)?.info,
                 assets[1].to_raw(deps.api)?.info,
@@ -348,50 +1369,88 @@ pub fn receive_cw20(
                 match direction {
                     OrderDirection::Buy => submit_order(
                         deps,
+                        &env,
                         sender,
                         &pair_key,
                         direction,
                         [assets[1].clone(), assets[0].clone()],
+                        client_id,
                     ),
                     OrderDirection::Sell => submit_order(
                         deps,
+                        &env,
                         sender,
                         &pair_key,
                         direction,
                         [assets[0].clone(), assets[1].clone()],
+                        client_id,
                     ),
                 }
             } else {
                 match direction {
                     OrderDirection::Buy => submit_order(
                         deps,
+                        &env,
                         sender,
                         &pair_key,
                         direction,
                         [assets[0].clone(), assets[1].clone()],
+                        client_id,
                     ),
                     OrderDirection::Sell => submit_order(
                         deps,
+                        &env,
                         sender,
                         &pair_key,
                         direction,
                         [assets[1].clone(), assets[0].clone()],
+                        client_id,
                     ),
                 }
             }
         }
+        Ok(Cw20HookMsg::SubmitOrders {
+            direction_and_assets,
+        }) => execute_submit_orders(
+            deps,
+            env,
+            sender,
+            vec![provided_asset],
+            direction_and_assets,
+        ),
+        Ok(Cw20HookMsg::UpdateOrder { order_id, assets }) => {
+            assert_not_paused(deps.storage)?;
+            update_order(deps, sender, order_id, assets, Some(provided_asset))
+        }
+        Ok(Cw20HookMsg::ExecutePair { asset_infos, limit }) => {
+            assert_not_paused(deps.storage)?;
+
+            // the token only carries the call of a keeper holding cw20, so it is sent back
+            let refund_msg = provided_asset.into_msg(None, &deps.querier, sender.clone())?;
+            let relayer_info = MessageInfo {
+                sender,
+                funds: vec![],
+            };
+            Ok(
+                execute_matching_orders(deps, env, relayer_info, asset_infos, limit)?
+                    .add_message(refund_msg),
+            )
+        }
         Err(_) => Err(ContractError::InvalidCw20HookMessage {}),
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::ContractInfo {} => to_binary(&query_contract_info(deps)?),
         QueryMsg::Order {
             order_id,
             asset_infos,
         } => to_binary(&query_order(deps, asset_infos, order_id)?),
+        QueryMsg::OrderByClientId { bidder, client_id } => {
+            to_binary(&query_order_by_client_id(deps, bidder, client_id)?)
+        }
         QueryMsg::OrderBook { asset_infos } => to_binary(&query_orderbook(deps, asset_infos)?),
         QueryMsg::OrderBooks {
             start_after,
@@ -405,6 +1464,7 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             start_after,
             limit,
             order_by,
+            cursor,
         } => to_binary(&query_orders(
             deps,
             asset_infos,
@@ -413,8 +1473,38 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             start_after,
             limit,
             order_by,
+            cursor,
         )?),
         QueryMsg::LastOrderId {} => to_binary(&query_last_order_id(deps)?),
+        QueryMsg::MatchingRules {} => to_binary(&query_matching_rules(deps)?),
+        QueryMsg::PairSequence { asset_infos } => {
+            to_binary(&query_pair_sequence(deps, asset_infos)?)
+        }
+        QueryMsg::MalformedTickKeys {
+            asset_infos,
+            direction,
+            limit,
+        } => to_binary(&query_malformed_tick_keys(
+            deps.storage,
+            &pair_key(&[
+                asset_infos[0].to_raw(deps.api)?,
+                asset_infos[1].to_raw(deps.api)?,
+            ]),
+            direction,
+            limit,
+        )?),
+        QueryMsg::OrdersAtPrice {
+            asset_infos,
+            price,
+            direction,
+            limit,
+        } => to_binary(&query_orders_at_price(
+            deps,
+            asset_infos,
+            price,
+            direction,
+            limit,
+        )?),
         QueryMsg::Tick {
             price,
             asset_infos,
@@ -450,6 +1540,35 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::OrderBookMatchable { asset_infos } => {
             to_binary(&query_orderbook_is_matchable(deps, asset_infos)?)
         }
+        QueryMsg::ExportOrders {
+            asset_infos,
+            start_after,
+            limit,
+        } => to_binary(&query_export_orders(deps, asset_infos, start_after, limit)?),
+        QueryMsg::OrderPosition {
+            asset_infos,
+            order_id,
+        } => to_binary(&query_order_position(deps, asset_infos, order_id)?),
+        QueryMsg::PairStats {
+            asset_infos,
+            period,
+        } => to_binary(&query_pair_stats(deps, asset_infos, period)?),
+        QueryMsg::Candles {
+            asset_infos,
+            interval,
+            start,
+            end,
+        } => to_binary(&query_candles(deps, asset_infos, interval, start, end)?),
+        QueryMsg::MakerRebates { address } => to_binary(&query_maker_rebates(deps, address)?),
+        QueryMsg::Escrow { address } => to_binary(&query_escrow(deps, address)?),
+        QueryMsg::IncentivePoints { address } => to_binary(&query_incentive_points(deps, address)?),
+        QueryMsg::TotalEscrow { asset_info } => {
+            to_binary(&query_total_escrow(deps, env, asset_info)?)
+        }
+        QueryMsg::OrderSigner { bidder } => to_binary(&query_order_signer(deps, bidder)?),
+        QueryMsg::Roles { start_after, limit } => {
+            to_binary(&query_roles(deps, start_after, limit)?)
+        }
     }
 }
 
@@ -459,10 +1578,73 @@ pub fn query_contract_info(deps: Deps) -> StdResult<ContractInfoResponse> {
         version: info.version,
         name: info.name,
         admin: deps.api.addr_humanize(&info.admin)?,
+        guardian: info
+            .guardian
+            .map(|guardian| deps.api.addr_humanize(&guardian))
+            .transpose()?,
+        paused: read_paused(deps.storage),
+        maker_rebate_rate: info.maker_rebate_rate,
+        event_mode: info.event_mode.unwrap_or_default(),
+        rewarder: info
+            .rewarder
+            .map(|rewarder| deps.api.addr_humanize(&rewarder))
+            .transpose()?,
+        referral: info
+            .referral
+            .map(|referral| deps.api.addr_humanize(&referral))
+            .transpose()?,
+        oracle: info
+            .oracle
+            .map(|oracle| deps.api.addr_humanize(&oracle))
+            .transpose()?,
+        converter: info
+            .converter
+            .map(|converter| deps.api.addr_humanize(&converter))
+            .transpose()?,
+    })
+}
+
+pub fn query_order_signer(deps: Deps, bidder: Addr) -> StdResult<OrderSignerResponse> {
+    let bidder = deps.api.addr_canonicalize(bidder.as_str())?;
+    Ok(OrderSignerResponse {
+        pubkey: read_order_signer(deps.storage, &bidder)?,
+        nonce: read_order_nonce(deps.storage, &bidder)?,
     })
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    // trader payouts only reply on error
+    match msg.result {
+        SubMsgResult::Err(error) => escrow_failed_payout(deps, msg.id, error),
+        SubMsgResult::Ok(_) => Err(ContractError::Std(StdError::generic_err(format!(
+            "unexpected reply id: {}",
+            msg.id
+        )))),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> StdResult<Response> {
+    if let Some(allow_import_orders) = msg.allow_import_orders {
+        store_allow_import_orders(deps.storage, allow_import_orders)?;
+    }
+
+    if let Some(registry) = msg.registry {
+        store_registry(
+            deps.storage,
+            &deps.api.addr_canonicalize(registry.as_str())?,
+        )?;
+    }
+
+    // the total escrow is tracked on order updates since the cw2 version is stored,
+    // so the orders of a deployment without it are counted once
+    if get_contract_version(deps.storage).is_err() {
+        for orderbook in read_all_orderbooks(deps.storage)? {
+            rebuild_order_escrow(deps.storage, &orderbook.get_pair_key())?;
+        }
+    }
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
     Ok(Response::default())
 }
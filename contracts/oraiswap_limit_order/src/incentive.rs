@@ -0,0 +1,138 @@
+use cosmwasm_std::{
+    to_binary, Addr, CanonicalAddr, CosmosMsg, Decimal, Deps, DepsMut, MessageInfo,
+    Order as OrderBy, Response, StdError, StdResult, Storage, Uint128, WasmMsg,
+};
+use oraiswap::error::ContractError;
+use oraiswap::limit_order::{IncentivePointsResponse, OrderDirection};
+use oraiswap::rewarder::ExecuteMsg as RewarderExecuteMsg;
+
+use crate::orderbook::OrderBook;
+use crate::state::{
+    increase_incentive_points, read_config, read_incentive_points, remove_incentive_points,
+};
+use crate::tick::query_ticks_prices;
+
+// bound the resting orders snapshotted on each side of the order book at every matching
+const MAX_INCENTIVE_TICKS: u32 = 10;
+const MAX_INCENTIVE_ORDERS: usize = 30;
+
+/// snapshot the resting orders within incentive_price_band of the mid price, each maker accrues
+/// the remaining base amount of its orders as incentive points, return the total points accrued
+pub fn accrue_incentive_points(
+    storage: &mut dyn Storage,
+    orderbook_pair: &OrderBook,
+) -> StdResult<Uint128> {
+    let incentive_price_band = match orderbook_pair.incentive_price_band {
+        Some(incentive_price_band) => incentive_price_band,
+        None => return Ok(Uint128::zero()),
+    };
+
+    let (best_buy_price, found_buy, _) = orderbook_pair.highest_price(storage, OrderDirection::Buy);
+    let (best_sell_price, found_sell, _) =
+        orderbook_pair.lowest_price(storage, OrderDirection::Sell);
+    if !found_buy || !found_sell {
+        return Ok(Uint128::zero());
+    }
+
+    let mid_price = (best_buy_price + best_sell_price) * Decimal::percent(50);
+    let lower_price = mid_price * (Decimal::one() - incentive_price_band);
+    let upper_price = mid_price * (Decimal::one() + incentive_price_band);
+
+    let pair_key = orderbook_pair.get_pair_key();
+    let mut maker_points: Vec<(CanonicalAddr, Uint128)> = vec![];
+    for (direction, order_by) in [
+        // best buy prices are the highest, best sell prices are the lowest
+        (OrderDirection::Buy, OrderBy::Descending),
+        (OrderDirection::Sell, OrderBy::Ascending),
+    ] {
+        let mut remaining_orders = MAX_INCENTIVE_ORDERS;
+        let prices = query_ticks_prices(
+            storage,
+            &pair_key,
+            direction,
+            None,
+            Some(MAX_INCENTIVE_TICKS),
+            Some(order_by.into()),
+        );
+        for price in prices
+            .into_iter()
+            .take_while(|price| *price >= lower_price && *price <= upper_price)
+        {
+            if remaining_orders == 0 {
+                break;
+            }
+            let orders = orderbook_pair
+                .orders_at(
+                    storage,
+                    price,
+                    direction,
+                    None,
+                    Some(remaining_orders as u32),
+                )
+                .unwrap_or_default();
+            remaining_orders -= orders.len();
+
+            for order in orders {
+                let points = order.get_remaining_base_amount();
+                match maker_points
+                    .iter_mut()
+                    .find(|(maker, _)| *maker == order.bidder_addr)
+                {
+                    Some((_, total_points)) => *total_points += points,
+                    None => maker_points.push((order.bidder_addr, points)),
+                }
+            }
+        }
+    }
+
+    let mut total_points = Uint128::zero();
+    for (maker, points) in maker_points {
+        if points.is_zero() {
+            continue;
+        }
+        increase_incentive_points(storage, &maker, points)?;
+        total_points += points;
+    }
+
+    Ok(total_points)
+}
+
+/// take the incentive points of the maker, the rewarder pays them in its incentive asset
+pub fn claim_incentives(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let contract_info = read_config(deps.storage)?;
+    let rewarder = contract_info
+        .rewarder
+        .ok_or_else(|| StdError::generic_err("Rewarder is not set"))?;
+
+    let maker = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let points = read_incentive_points(deps.storage, &maker)?;
+    if points.is_zero() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "No incentive points to claim",
+        )));
+    }
+
+    remove_incentive_points(deps.storage, &maker);
+
+    Ok(Response::new()
+        .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: deps.api.addr_humanize(&rewarder)?.to_string(),
+            msg: to_binary(&RewarderExecuteMsg::DistributeOrderBookIncentive {
+                recipient: info.sender.clone(),
+                points,
+            })?,
+            funds: vec![],
+        }))
+        .add_attributes(vec![
+            ("action", "claim_incentives"),
+            ("maker", info.sender.as_str()),
+            ("points", &points.to_string()),
+        ]))
+}
+
+pub fn query_incentive_points(deps: Deps, address: Addr) -> StdResult<IncentivePointsResponse> {
+    let maker = deps.api.addr_canonicalize(address.as_str())?;
+    Ok(IncentivePointsResponse {
+        points: read_incentive_points(deps.storage, &maker)?,
+    })
+}
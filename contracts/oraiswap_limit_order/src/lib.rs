@@ -1,7 +1,9 @@
+pub mod admin;
 pub mod contract;
 pub mod orderbook;
 pub mod state;
 
+mod incentive;
 mod order;
 mod tick;
 
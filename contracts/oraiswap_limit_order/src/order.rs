@@ -1,28 +1,63 @@
 use std::convert::TryFrom;
 use std::str::FromStr;
 
-use crate::orderbook::{BulkOrders, Executor, Order, OrderBook};
+use crate::admin::assert_role;
+use crate::incentive::accrue_incentive_points;
+use crate::orderbook::{
+    scaled_price, sort_by_match_priority, BulkOrders, Candle, Executor, Order, OrderBook, PairStats,
+};
 use crate::state::{
-    increase_last_order_id, read_config, read_last_order_id, read_order, read_orderbook,
-    read_orderbooks, read_orders, read_orders_with_indexer, read_reward, remove_order,
-    remove_orderbook, store_order, store_reward, DEFAULT_LIMIT, MAX_LIMIT, PREFIX_ORDER_BY_BIDDER,
-    PREFIX_ORDER_BY_DIRECTION, PREFIX_ORDER_BY_PRICE, PREFIX_TICK,
+    count_bidder_orders, increase_escrow, increase_last_order_id, increase_maker_rebate,
+    read_all_orderbooks, read_allow_import_orders, read_candles, read_config, read_escrow,
+    read_last_order_id, read_maker_rebates, read_order, read_order_by_client_id,
+    read_order_created_at, read_order_created_height, read_order_escrow, read_orderbook,
+    read_orderbooks, read_orders, read_orders_with_indexer, read_pair_stats, read_pending_payout,
+    read_reward, read_tick_orders, remove_escrow, remove_maker_rebates, remove_order,
+    remove_orderbook, store_last_order_id, store_order, store_order_created_at,
+    store_order_created_height, store_orderbook, store_pending_payouts, store_reward,
+    update_candles, update_pair_stats, DEFAULT_LIMIT, MAX_LIMIT, PAIR_STATS_PERIOD,
+    PREFIX_ORDER_BY_BIDDER, PREFIX_ORDER_BY_DIRECTION, PREFIX_ORDER_BY_PRICE, PREFIX_TICK,
+};
+use crate::tick::{
+    price_from_key, price_level_range, query_ticks_in_range, DEFAULT_PRICE_PRECISION,
 };
 use cosmwasm_std::{
-    attr, Addr, Attribute, CanonicalAddr, CosmosMsg, Decimal, Deps, DepsMut, Event, MessageInfo,
-    Order as OrderBy, Response, StdResult, Storage, Uint128,
+    attr, to_binary, Addr, Api, Attribute, CanonicalAddr, CosmosMsg, Decimal, Deps, DepsMut, Env,
+    Event, MessageInfo, Order as OrderBy, QuerierWrapper, Response, StdError, StdResult, Storage,
+    SubMsg, Uint128,
 };
 
 use cosmwasm_storage::ReadonlyBucket;
 use oraiswap::asset::{pair_key, Asset, AssetInfo};
 use oraiswap::error::ContractError;
 use oraiswap::limit_order::{
-    LastOrderIdResponse, OrderBookMatchableResponse, OrderBookResponse, OrderBooksResponse,
-    OrderDirection, OrderFilter, OrderResponse, OrderStatus, OrdersResponse,
+    CandleInterval, CandleResponse, CandlesResponse, ContractInfo, EscrowResponse, EventMode,
+    ExportOrdersResponse, LastOrderIdResponse, MakerRebatesResponse, MatchOrdersDryRunResponse,
+    MatchingRulesResponse, OrderBookMatchableResponse, OrderBookResponse, OrderBooksResponse,
+    OrderDirection, OrderFilter, OrderPositionResponse, OrderRecord, OrderResponse, OrderStatus,
+    OrdersResponse, PairSequenceResponse, PairStatsResponse, PriceLevelResponse, Role,
+    TotalEscrowResponse,
+};
+use oraiswap::oracle::OracleContract;
+use oraiswap::pagination::{next_cursor, Cursor};
+use oraiswap::querier::{calc_range_start, query_pool};
+use oraiswap::referral::{
+    asset_into_rewards_msg, QueryMsg as ReferralQueryMsg, ReferralResponse, ReferralReward,
 };
 
 const RELAY_FEE: u128 = 300u128;
 
+// a price level whose remaining volume is at most this amount is considered filled when matching
+const MIN_VOLUME: Uint128 = Uint128::new(10);
+
+// matching rules returned by the MatchingRules query, see sort_by_match_priority
+const PRICE_PRIORITY: &str = "best_price";
+const TIME_PRIORITY: &str = "lowest_order_id";
+const MATCH_PRICE: &str = "buy_price_or_clearing_price";
+
+// client ids are kept short since they are stored with the order and in an index
+const MAX_CLIENT_ID_LENGTH: usize = 64;
+
 struct Payment {
     address: Addr,
     asset: Asset,
@@ -30,32 +65,85 @@ struct Payment {
 
 pub fn submit_order(
     deps: DepsMut,
+    env: &Env,
     sender: Addr,
     pair_key: &[u8],
     direction: OrderDirection,
     assets: [Asset; 2],
+    client_id: Option<String>,
 ) -> Result<Response, ContractError> {
     if assets[0].amount.is_zero() || assets[1].amount.is_zero() {
         return Err(ContractError::AssetMustNotBeZero {});
     }
 
+    let bidder_addr = deps.api.addr_canonicalize(sender.as_str())?;
+    if let Some(client_id) = &client_id {
+        if client_id.is_empty() || client_id.len() > MAX_CLIENT_ID_LENGTH {
+            return Err(ContractError::Std(StdError::generic_err(format!(
+                "client_id must be 1 to {} characters",
+                MAX_CLIENT_ID_LENGTH
+            ))));
+        }
+        if read_order_by_client_id(deps.storage, &bidder_addr, client_id)?.is_some() {
+            return Err(ContractError::Std(StdError::generic_err(format!(
+                "client_id {} is used by an open order",
+                client_id
+            ))));
+        }
+    }
+
     let order_id = increase_last_order_id(deps.storage)?;
+    let order = Order {
+        order_id,
+        direction,
+        bidder_addr,
+        offer_amount: assets[0].to_raw(deps.api)?.amount,
+        ask_amount: assets[1].to_raw(deps.api)?.amount,
+        filled_offer_amount: Uint128::zero(),
+        filled_ask_amount: Uint128::zero(),
+        status: OrderStatus::Open,
+        client_id,
+    };
+
+    let mut orderbook_pair = read_orderbook(deps.storage, pair_key)?;
+    if orderbook_pair.paused.unwrap_or_default() {
+        return Err(ContractError::Paused {});
+    }
+    if orderbook_pair.delisting_time.is_some() {
+        return Err(ContractError::OrderBookDelisting {});
+    }
+    assert_price_in_band(
+        deps.as_ref(),
+        &orderbook_pair,
+        order.get_price(orderbook_pair.decimals_diff())?,
+    )?;
+    assert_min_quote_notional(&orderbook_pair, &order)?;
+
+    if let Some(max_orders_per_user) = orderbook_pair.max_orders_per_user {
+        let open_orders = count_bidder_orders(
+            deps.storage,
+            pair_key,
+            &order.bidder_addr,
+            max_orders_per_user as usize,
+        );
+        if open_orders >= max_orders_per_user as usize {
+            return Err(ContractError::MaxOrdersPerUserReached {
+                max_orders_per_user,
+            });
+        }
+    }
 
     store_order(
         deps.storage,
         &pair_key,
-        &Order {
-            order_id,
-            direction,
-            bidder_addr: deps.api.addr_canonicalize(sender.as_str())?,
-            offer_amount: assets[0].to_raw(deps.api)?.amount,
-            ask_amount: assets[1].to_raw(deps.api)?.amount,
-            filled_offer_amount: Uint128::zero(),
-            filled_ask_amount: Uint128::zero(),
-            status: OrderStatus::Open,
-        },
+        &order,
+        orderbook_pair.decimals_diff(),
         true,
     )?;
+    store_order_created_at(deps.storage, pair_key, order_id, env.block.time.seconds())?;
+    store_order_created_height(deps.storage, pair_key, order_id, env.block.height)?;
+    orderbook_pair.record_order(order_id, direction);
+    store_orderbook(deps.storage, pair_key, &orderbook_pair)?;
 
     Ok(Response::new().add_attributes(vec![
         ("action", "submit_order"),
@@ -78,8 +166,86 @@ pub fn submit_order(
     ]))
 }
 
+/// reject order whose price deviates more than max_price_deviation from the reference pool price
+fn assert_price_in_band(
+    deps: Deps,
+    orderbook_pair: &OrderBook,
+    price: Decimal,
+) -> Result<(), ContractError> {
+    let (price_reference, max_price_deviation) = match (
+        &orderbook_pair.price_reference,
+        orderbook_pair.max_price_deviation,
+    ) {
+        (Some(price_reference), Some(max_price_deviation)) => {
+            (price_reference, max_price_deviation)
+        }
+        _ => return Ok(()),
+    };
+
+    let pool = query_pool(&deps.querier, deps.api.addr_humanize(price_reference)?)?;
+    let base_coin_info = orderbook_pair.base_coin_info.to_normal(deps.api)?;
+    let quote_coin_info = orderbook_pair.quote_coin_info.to_normal(deps.api)?;
+    let mut base_reserve = Uint128::zero();
+    let mut quote_reserve = Uint128::zero();
+    for asset in pool.assets.iter() {
+        if asset.info.eq(&base_coin_info) {
+            base_reserve = asset.amount;
+        } else if asset.info.eq(&quote_coin_info) {
+            quote_reserve = asset.amount;
+        }
+    }
+
+    // empty pool has no reference price
+    if base_reserve.is_zero() || quote_reserve.is_zero() {
+        return Ok(());
+    }
+
+    let reference_price =
+        scaled_price(quote_reserve, base_reserve, orderbook_pair.decimals_diff())?;
+    let deviation = if price > reference_price {
+        price - reference_price
+    } else {
+        reference_price - price
+    };
+
+    if deviation > reference_price * max_price_deviation {
+        return Err(ContractError::PriceOutOfBand {
+            price,
+            reference_price,
+        });
+    }
+
+    Ok(())
+}
+
+/// reject order whose remaining base amount is worth less than min_quote_notional at the order price,
+/// so dust orders at extreme prices can not fill the ticks
+fn assert_min_quote_notional(
+    orderbook_pair: &OrderBook,
+    order: &Order,
+) -> Result<(), ContractError> {
+    let min_quote_notional = match orderbook_pair.min_quote_notional {
+        Some(min_quote_notional) => min_quote_notional,
+        None => return Ok(()),
+    };
+
+    let notional = orderbook_pair.to_quote_amount(
+        order.get_remaining_base_amount(),
+        order.get_price(orderbook_pair.decimals_diff())?,
+    )?;
+    if notional < min_quote_notional {
+        return Err(ContractError::TooSmallQuoteNotional {
+            notional,
+            min_quote_notional,
+        });
+    }
+
+    Ok(())
+}
+
 pub fn cancel_order(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     order_id: u64,
     asset_infos: [AssetInfo; 2],
@@ -97,25 +263,56 @@ pub fn cancel_order(
 
     // Compute refund asset
     let left_offer_amount = order.offer_amount.checked_sub(order.filled_offer_amount)?;
+    let offer_info = match order.direction {
+        OrderDirection::Buy => orderbook_pair.quote_coin_info.to_normal(deps.api)?,
+        OrderDirection::Sell => orderbook_pair.base_coin_info.to_normal(deps.api)?,
+    };
+
+    // charge cancel fee if the order is cancelled too soon, to discourage spoofing orders
+    let cancel_fee_amount = compute_cancel_fee(
+        deps.storage,
+        &env,
+        &pair_key,
+        &orderbook_pair,
+        &order,
+        left_offer_amount,
+    )?;
 
     let bidder_refund = Asset {
-        info: match order.direction {
-            OrderDirection::Buy => orderbook_pair.quote_coin_info.to_normal(deps.api)?,
-            OrderDirection::Sell => orderbook_pair.base_coin_info.to_normal(deps.api)?,
-        },
-        amount: left_offer_amount,
+        info: offer_info.clone(),
+        amount: left_offer_amount.checked_sub(cancel_fee_amount)?,
     };
 
     // Build refund msg
-    let messages = if left_offer_amount > Uint128::zero() {
-        vec![bidder_refund
-            .clone()
-            .into_msg(None, &deps.querier, deps.api.addr_humanize(&order.bidder_addr)?)?]
-    } else {
-        vec![]
-    };
+    let mut messages = vec![];
+    if !bidder_refund.amount.is_zero() {
+        messages.push(bidder_refund.clone().into_msg(
+            None,
+            &deps.querier,
+            deps.api.addr_humanize(&order.bidder_addr)?,
+        )?);
+    }
+    if !cancel_fee_amount.is_zero() {
+        let contract_info = read_config(deps.storage)?;
+        messages.push(
+            Asset {
+                info: offer_info,
+                amount: cancel_fee_amount,
+            }
+            .into_msg(
+                None,
+                &deps.querier,
+                deps.api.addr_humanize(&contract_info.reward_address)?,
+            )?,
+        );
+    }
 
-    remove_order(deps.storage, &pair_key, &order)?;
+    remove_order(
+        deps.storage,
+        &pair_key,
+        &order,
+        orderbook_pair.decimals_diff(),
+    )?;
 
     Ok(Response::new().add_messages(messages).add_attributes(vec![
         ("action", "cancel_order"),
@@ -137,10 +334,193 @@ pub fn cancel_order(
         ("offer_amount", &order.offer_amount.to_string()),
         ("ask_amount", &order.ask_amount.to_string()),
         ("bidder_refund", &bidder_refund.to_string()),
+        ("cancel_fee", &cancel_fee_amount.to_string()),
+    ]))
+}
+
+/// amend the amounts of an open order, an increase of the offer amount must be paid with the provided asset
+/// and a decrease is refunded to the bidder. The order keeps its id and moves to the tick of the new price
+pub fn update_order(
+    deps: DepsMut,
+    sender: Addr,
+    order_id: u64,
+    assets: [Asset; 2],
+    provided_asset: Option<Asset>,
+) -> Result<Response, ContractError> {
+    if assets[0].amount.is_zero() || assets[1].amount.is_zero() {
+        return Err(ContractError::AssetMustNotBeZero {});
+    }
+
+    let pair_key = pair_key(&[
+        assets[0].to_raw(deps.api)?.info,
+        assets[1].to_raw(deps.api)?.info,
+    ]);
+    let orderbook_pair = read_orderbook(deps.storage, &pair_key)?;
+    if orderbook_pair.paused.unwrap_or_default() {
+        return Err(ContractError::Paused {});
+    }
+    if orderbook_pair.delisting_time.is_some() {
+        return Err(ContractError::OrderBookDelisting {});
+    }
+
+    let order = read_order(deps.storage, &pair_key, order_id)?;
+    if order.bidder_addr != deps.api.addr_canonicalize(sender.as_str())? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let base_coin_info = orderbook_pair.base_coin_info.to_normal(deps.api)?;
+    let quote_coin_info = orderbook_pair.quote_coin_info.to_normal(deps.api)?;
+    let offer_info = match order.direction {
+        OrderDirection::Buy => quote_coin_info.clone(),
+        OrderDirection::Sell => base_coin_info,
+    };
+    let (offer_asset, ask_asset) = if assets[0].info.eq(&offer_info) {
+        (&assets[0], &assets[1])
+    } else {
+        (&assets[1], &assets[0])
+    };
+    let quote_asset = if offer_asset.info.eq(&quote_coin_info) {
+        offer_asset
+    } else {
+        ask_asset
+    };
+
+    // the filled part of the order can not be amended
+    if offer_asset.amount <= order.filled_offer_amount
+        || ask_asset.amount <= order.filled_ask_amount
+    {
+        return Err(ContractError::OrderFulfilled { order_id });
+    }
+
+    // require minimum amount for quote asset
+    if quote_asset.amount.lt(&orderbook_pair.min_quote_coin_amount) {
+        return Err(ContractError::TooSmallQuoteAsset {
+            quote_coin: quote_asset.info.to_string(),
+            min_quote_amount: orderbook_pair.min_quote_coin_amount,
+        });
+    }
+
+    // the provided asset must be the offer asset and cover the whole increase
+    let top_up_amount = offer_asset.amount.saturating_sub(order.offer_amount);
+    let refund_amount = order.offer_amount.saturating_sub(offer_asset.amount);
+    let paid_amount = match &provided_asset {
+        Some(provided_asset) if provided_asset.info.eq(&offer_info) => provided_asset.amount,
+        Some(_) => return Err(ContractError::AssetMismatch {}),
+        None => Uint128::zero(),
+    };
+    if paid_amount != top_up_amount {
+        return Err(ContractError::AssetMismatch {});
+    }
+
+    let updated_order = Order {
+        offer_amount: offer_asset.amount,
+        ask_amount: ask_asset.amount,
+        ..order.clone()
+    };
+    assert_price_in_band(
+        deps.as_ref(),
+        &orderbook_pair,
+        updated_order.get_price(orderbook_pair.decimals_diff())?,
+    )?;
+    assert_min_quote_notional(&orderbook_pair, &updated_order)?;
+
+    // re-index the order under its new price, keeping the created time and height for the cancel fee
+    let created_at = read_order_created_at(deps.storage, &pair_key, order_id);
+    let created_height = read_order_created_height(deps.storage, &pair_key, order_id);
+    remove_order(
+        deps.storage,
+        &pair_key,
+        &order,
+        orderbook_pair.decimals_diff(),
+    )?;
+    store_order(
+        deps.storage,
+        &pair_key,
+        &updated_order,
+        orderbook_pair.decimals_diff(),
+        true,
+    )?;
+    if let Some(created_at) = created_at {
+        store_order_created_at(deps.storage, &pair_key, order_id, created_at)?;
+    }
+    if let Some(created_height) = created_height {
+        store_order_created_height(deps.storage, &pair_key, order_id, created_height)?;
+    }
+
+    let mut messages = vec![];
+    if !refund_amount.is_zero() {
+        messages.push(
+            Asset {
+                info: offer_info,
+                amount: refund_amount,
+            }
+            .into_msg(None, &deps.querier, sender.clone())?,
+        );
+    }
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "update_order"),
+        ("order_id", &order_id.to_string()),
+        ("direction", &format!("{:?}", order.direction)),
+        ("bidder_addr", sender.as_str()),
+        (
+            "offer_asset",
+            &format!("{} {}", &offer_asset.amount, &offer_asset.info),
+        ),
+        (
+            "ask_asset",
+            &format!("{} {}", &ask_asset.amount, &ask_asset.info),
+        ),
+        ("top_up_amount", &top_up_amount.to_string()),
+        ("refund_amount", &refund_amount.to_string()),
     ]))
 }
 
-fn to_events(order: &Order, human_bidder: String, fee: String) -> Event {
+fn compute_cancel_fee(
+    storage: &dyn Storage,
+    env: &Env,
+    pair_key: &[u8],
+    orderbook_pair: &OrderBook,
+    order: &Order,
+    left_offer_amount: Uint128,
+) -> Result<Uint128, ContractError> {
+    let min_order_lifetime_seconds = match orderbook_pair.min_order_lifetime_seconds {
+        Some(lifetime) => lifetime,
+        None => return Ok(Uint128::zero()),
+    };
+
+    // orders submitted before tracking created time are free to cancel
+    let created_at = match read_order_created_at(storage, pair_key, order.order_id) {
+        Some(created_at) => created_at,
+        None => return Ok(Uint128::zero()),
+    };
+
+    if env.block.time.seconds() >= created_at + min_order_lifetime_seconds {
+        return Ok(Uint128::zero());
+    }
+
+    // orders untouched by matching for free_cancel_blocks are not spoofing the order book
+    if let (Some(free_cancel_blocks), Some(created_height)) = (
+        orderbook_pair.free_cancel_blocks,
+        read_order_created_height(storage, pair_key, order.order_id),
+    ) {
+        if order.filled_offer_amount.is_zero()
+            && env.block.height >= created_height + free_cancel_blocks
+        {
+            return Ok(Uint128::zero());
+        }
+    }
+
+    match orderbook_pair.cancel_fee_rate {
+        Some(cancel_fee_rate) => Ok(left_offer_amount * cancel_fee_rate),
+        None => Err(ContractError::OrderLifetimeNotReached {
+            order_id: order.order_id,
+            min_order_lifetime_seconds,
+        }),
+    }
+}
+
+fn to_events(order: &Order, human_bidder: String, fee: String, relayer_fee: String) -> Event {
     let attrs: Vec<Attribute> = [
         attr("status", format!("{:?}", order.status)),
         attr("bidder_addr", human_bidder),
@@ -151,6 +531,7 @@ fn to_events(order: &Order, human_bidder: String, fee: String) -> Event {
         attr("ask_amount", order.ask_amount.to_string()),
         attr("filled_ask_amount", order.filled_ask_amount.to_string()),
         attr("fee", fee),
+        attr("relayer_fee", relayer_fee),
     ]
     .to_vec();
     Event::new("matched_order").add_attributes(attrs)
@@ -192,10 +573,76 @@ fn transfer_reward(
     }
 }
 
+/// share of the commission paid by each referred trader, grouped by the commission asset
+fn referral_rewards(
+    querier: &QuerierWrapper,
+    referral_addr: &Addr,
+    commissions: Vec<Payment>,
+) -> Vec<(AssetInfo, Vec<ReferralReward>)> {
+    // one query per trader and asset
+    let mut trader_commissions: Vec<Payment> = vec![];
+    for commission in commissions {
+        if let Some(existing) = trader_commissions.iter_mut().find(|existing| {
+            existing.address == commission.address && existing.asset.info == commission.asset.info
+        }) {
+            existing.asset.amount += commission.asset.amount;
+        } else {
+            trader_commissions.push(commission);
+        }
+    }
+
+    let mut rewards: Vec<(AssetInfo, Vec<ReferralReward>)> = vec![];
+    for commission in trader_commissions {
+        // a trader is unreferred when the referral contract can not be queried, so the
+        // matching does not depend on it
+        let referral: ReferralResponse = match querier.query_wasm_smart(
+            referral_addr,
+            &ReferralQueryMsg::Referral {
+                trader: commission.address,
+            },
+        ) {
+            Ok(referral) => referral,
+            Err(_) => continue,
+        };
+        let referrer = match referral.referrer {
+            Some(referrer) => referrer,
+            None => continue,
+        };
+        let amount = commission
+            .asset
+            .amount
+            .multiply_ratio(referral.rate_bps, 10000u128);
+        if amount.is_zero() {
+            continue;
+        }
+
+        let index = match rewards
+            .iter()
+            .position(|(info, _)| *info == commission.asset.info)
+        {
+            Some(index) => index,
+            None => {
+                rewards.push((commission.asset.info, vec![]));
+                rewards.len() - 1
+            }
+        };
+        let asset_rewards = &mut rewards[index].1;
+        match asset_rewards
+            .iter_mut()
+            .find(|reward| reward.referrer == referrer)
+        {
+            Some(reward) => reward.amount += amount,
+            None => asset_rewards.push(ReferralReward { referrer, amount }),
+        }
+    }
+    rewards
+}
+
 fn process_list_trader(
     deps: &DepsMut,
     traders: Vec<Payment>,
-    messages: &mut Vec<CosmosMsg>,
+    payouts: &mut Vec<(CanonicalAddr, Asset)>,
+    sub_messages: &mut Vec<SubMsg>,
 ) -> StdResult<()> {
     let mut minimalist_trader: Vec<Payment> = vec![];
     for trader in traders {
@@ -211,11 +658,19 @@ fn process_list_trader(
 
     for trader in minimalist_trader {
         if !trader.asset.amount.is_zero() {
-            messages.push(trader.asset.into_msg(
-                None,
-                &deps.querier,
-                deps.api.addr_validate(trader.address.as_str())?,
-            )?);
+            // a failing payout is escrowed on reply, so it does not revert the whole matching
+            sub_messages.push(SubMsg::reply_on_error(
+                trader.asset.into_msg(
+                    None,
+                    &deps.querier,
+                    deps.api.addr_validate(trader.address.as_str())?,
+                )?,
+                payouts.len() as u64,
+            ));
+            payouts.push((
+                deps.api.addr_canonicalize(trader.address.as_str())?,
+                trader.asset,
+            ));
         }
     }
 
@@ -264,10 +719,13 @@ fn transfer_spread(
     }
 }
 
+/// match the ticks from the best prices, each at the buy price, or all at the clearing price
+/// of the call market, where only the ticks that cross it are matched
 fn execute_bulk_orders(
     deps: &DepsMut,
     orderbook_pair: OrderBook,
     limit: Option<u32>,
+    clearing_price: Option<Decimal>,
 ) -> StdResult<(Vec<BulkOrders>, Vec<BulkOrders>)> {
     let pair_key = &orderbook_pair.get_pair_key();
 
@@ -289,164 +747,303 @@ fn execute_bulk_orders(
 
     let mut i = 0;
     let mut j = 0;
-    let min_vol = Uint128::from(10u128);
 
     let mut best_buy_price_list = vec![];
     let mut best_sell_price_list = vec![];
-    let mut buy_bulk_orders_list = vec![];
-    let mut sell_bulk_orders_list = vec![];
-
-    while i < limit && j < limit {
-        if best_sell_price_list.len() <= j {
-            if let Some(Ok((k, _))) = sell_cursor.next() {
-                let price = Decimal::raw(u128::from_be_bytes(k.try_into().unwrap()));
-                best_sell_price_list.push(price);
-            } else {
-                break;
+    let mut buy_bulk_orders_list: Vec<BulkOrders> = vec![];
+    let mut sell_bulk_orders_list: Vec<BulkOrders> = vec![];
+
+    // the buy price level and its state when the current sell price level started matching,
+    // so the matches of a sell price level can be rewound
+    let mut taker_start: (usize, Option<BulkOrders>) = (0, None);
+    // a rewound sell price level is matched again up to this amount, then matching stops
+    let mut taker_cap: Option<(usize, Uint128)> = None;
+
+    loop {
+        while i < limit && j < limit && taker_cap.map_or(true, |(capped, _)| j <= capped) {
+            if best_sell_price_list.len() <= j {
+                // skip malformed tick keys instead of failing the whole pair
+                if let Some(price) = sell_cursor.find_map(|item| price_from_key(&item.ok()?.0)) {
+                    best_sell_price_list.push(price);
+                } else {
+                    break;
+                }
             }
-        }
-        let sell_price = best_sell_price_list[j];
+            let sell_price = best_sell_price_list[j];
 
-        if best_buy_price_list.len() <= i {
-            if let Some(Ok((k, _))) = buy_cursor.next() {
-                let price = Decimal::raw(u128::from_be_bytes(k.try_into().unwrap()));
-                best_buy_price_list.push(price);
-            } else {
-                break;
+            if best_buy_price_list.len() <= i {
+                if let Some(price) = buy_cursor.find_map(|item| price_from_key(&item.ok()?.0)) {
+                    best_buy_price_list.push(price);
+                } else {
+                    break;
+                }
             }
-        }
-
-        let buy_price = best_buy_price_list[i];
-
-        if buy_price < sell_price {
-            break;
-        }
 
-        let match_price = buy_price;
+            let buy_price = best_buy_price_list[i];
 
-        if buy_bulk_orders_list.len() <= i {
-            if let Some(orders) = orderbook_pair.query_orders_by_price_and_direction(
-                deps.as_ref().storage,
-                buy_price,
-                OrderDirection::Buy,
-                None,
-            ) {
-                if orders.len() == 0 {
-                    continue;
-                }
-                let bulk = BulkOrders::from_orders(&orders, buy_price, OrderDirection::Buy);
-                buy_bulk_orders_list.push(bulk);
-            } else {
+            if buy_price < sell_price {
                 break;
             }
-        };
-
-        if sell_bulk_orders_list.len() <= j {
-            if let Some(orders) = orderbook_pair.query_orders_by_price_and_direction(
-                deps.as_ref().storage,
-                sell_price,
-                OrderDirection::Sell,
-                None,
-            ) {
-                if orders.len() == 0 {
-                    continue;
+            if let Some(clearing_price) = clearing_price {
+                if buy_price < clearing_price || sell_price > clearing_price {
+                    break;
                 }
-                let bulk = BulkOrders::from_orders(&orders, sell_price, OrderDirection::Sell);
-                sell_bulk_orders_list.push(bulk);
-            } else {
-                break;
             }
-        };
 
-        let buy_bulk_orders = &mut buy_bulk_orders_list[i];
-        let sell_bulk_orders = &mut sell_bulk_orders_list[j];
+            let match_price = clearing_price.unwrap_or(buy_price);
+
+            if buy_bulk_orders_list.len() <= i {
+                if let Some(orders) = orderbook_pair.query_orders_by_price_and_direction(
+                    deps.as_ref().storage,
+                    buy_price,
+                    OrderDirection::Buy,
+                    None,
+                ) {
+                    if orders.len() == 0 {
+                        continue;
+                    }
+                    let mut bulk =
+                        BulkOrders::from_orders(&orders, match_price, OrderDirection::Buy);
+                    // at the clearing price buy orders only spend the quote asset needed for their
+                    // ask amount, the rest of their offer is refunded once they are fulfilled
+                    if clearing_price.is_some() {
+                        bulk.volume = Uint128::min(
+                            bulk.volume,
+                            orderbook_pair.to_quote_amount(bulk.ask_volume, match_price)?,
+                        );
+                    }
+                    buy_bulk_orders_list.push(bulk);
+                } else {
+                    break;
+                }
+            };
+
+            if sell_bulk_orders_list.len() <= j {
+                if let Some(orders) = orderbook_pair.query_orders_by_price_and_direction(
+                    deps.as_ref().storage,
+                    sell_price,
+                    OrderDirection::Sell,
+                    None,
+                ) {
+                    if orders.len() == 0 {
+                        continue;
+                    }
+                    let mut bulk = BulkOrders::from_orders(
+                        &orders,
+                        clearing_price.unwrap_or(sell_price),
+                        OrderDirection::Sell,
+                    );
+                    if let Some((capped, cap)) = taker_cap {
+                        if capped == j {
+                            bulk.volume = cap;
+                        }
+                    }
+                    taker_start = (i, buy_bulk_orders_list.get(i).cloned());
+                    sell_bulk_orders_list.push(bulk);
+                } else {
+                    break;
+                }
+            };
+
+            let buy_bulk_orders = &mut buy_bulk_orders_list[i];
+            let sell_bulk_orders = &mut sell_bulk_orders_list[j];
 
-        let lef_sell_offer = sell_bulk_orders.volume;
-        let lef_sell_ask = Uint128::from(lef_sell_offer * match_price);
+            let lef_sell_offer = sell_bulk_orders.volume;
+            let lef_sell_ask = orderbook_pair.to_quote_amount(lef_sell_offer, match_price)?;
 
-        let sell_ask_amount = Uint128::min(buy_bulk_orders.volume, lef_sell_ask);
+            let sell_ask_amount = Uint128::min(buy_bulk_orders.volume, lef_sell_ask);
 
-        // multiply by decimal atomics because we want to get good round values
-        let sell_offer_amount = Uint128::min(
-            Uint128::from(sell_ask_amount * Decimal::one().atomics())
-                .checked_div(match_price.atomics())
-                .unwrap(),
-            lef_sell_offer,
-        );
+            let sell_offer_amount = Uint128::min(
+                orderbook_pair.to_base_amount(sell_ask_amount, match_price)?,
+                lef_sell_offer,
+            );
 
-        if sell_ask_amount.is_zero() || sell_offer_amount.is_zero() {
-            continue;
-        }
+            if sell_ask_amount.is_zero() || sell_offer_amount.is_zero() {
+                continue;
+            }
 
-        sell_bulk_orders.filled_volume += sell_offer_amount;
-        sell_bulk_orders.filled_ask_volume += sell_ask_amount;
+            sell_bulk_orders.filled_volume += sell_offer_amount;
+            sell_bulk_orders.filled_ask_volume += sell_ask_amount;
 
-        buy_bulk_orders.filled_volume += sell_ask_amount;
-        buy_bulk_orders.filled_ask_volume += sell_offer_amount;
+            buy_bulk_orders.filled_volume += sell_ask_amount;
+            buy_bulk_orders.filled_ask_volume += sell_offer_amount;
 
-        buy_bulk_orders.volume = buy_bulk_orders.volume.checked_sub(sell_ask_amount)?;
-        sell_bulk_orders.volume = sell_bulk_orders.volume.checked_sub(sell_offer_amount)?;
+            buy_bulk_orders.volume = buy_bulk_orders.volume.checked_sub(sell_ask_amount)?;
+            sell_bulk_orders.volume = sell_bulk_orders.volume.checked_sub(sell_offer_amount)?;
 
-        if buy_bulk_orders.filled_ask_volume >= buy_bulk_orders.ask_volume {
-            buy_bulk_orders.spread_volume = buy_bulk_orders
-                .filled_ask_volume
-                .checked_sub(buy_bulk_orders.ask_volume)?;
-            buy_bulk_orders.filled_ask_volume = buy_bulk_orders
-                .filled_ask_volume
-                .checked_sub(buy_bulk_orders.spread_volume)?;
-            buy_bulk_orders.ask_volume = Uint128::zero();
-        }
-        if sell_bulk_orders.filled_ask_volume >= sell_bulk_orders.ask_volume {
-            sell_bulk_orders.spread_volume = sell_bulk_orders
-                .filled_ask_volume
-                .checked_sub(sell_bulk_orders.ask_volume)?;
-            sell_bulk_orders.filled_ask_volume = sell_bulk_orders
-                .filled_ask_volume
-                .checked_sub(sell_bulk_orders.spread_volume)?;
-            sell_bulk_orders.ask_volume = Uint128::zero();
+            if buy_bulk_orders.filled_ask_volume >= buy_bulk_orders.ask_volume {
+                buy_bulk_orders.spread_volume = buy_bulk_orders
+                    .filled_ask_volume
+                    .checked_sub(buy_bulk_orders.ask_volume)?;
+                buy_bulk_orders.filled_ask_volume = buy_bulk_orders
+                    .filled_ask_volume
+                    .checked_sub(buy_bulk_orders.spread_volume)?;
+                buy_bulk_orders.ask_volume = Uint128::zero();
+            }
+            if sell_bulk_orders.filled_ask_volume >= sell_bulk_orders.ask_volume {
+                sell_bulk_orders.spread_volume = sell_bulk_orders
+                    .filled_ask_volume
+                    .checked_sub(sell_bulk_orders.ask_volume)?;
+                sell_bulk_orders.filled_ask_volume = sell_bulk_orders
+                    .filled_ask_volume
+                    .checked_sub(sell_bulk_orders.spread_volume)?;
+                sell_bulk_orders.ask_volume = Uint128::zero();
+            }
+
+            if buy_bulk_orders.volume <= MIN_VOLUME {
+                // buy out
+                buy_bulk_orders.ask_volume = Uint128::zero();
+                i += 1;
+            }
+            if sell_bulk_orders.volume <= MIN_VOLUME {
+                // sell out
+                sell_bulk_orders.ask_volume = Uint128::zero();
+                j += 1;
+            }
         }
 
-        if buy_bulk_orders.volume <= min_vol {
-            // buy out
-            buy_bulk_orders.ask_volume = Uint128::zero();
-            i += 1;
+        // only the last matched sell price level can be partially filled, its orders are the takers.
+        // When the partially filled sell order gets less than the min fill of its remaining amount,
+        // the sell price level is rewound and matched again with the fully filled orders only,
+        // the buy orders are never skipped so a dust buy order can not block the matching
+        let taker_fill = match sell_bulk_orders_list.get(j) {
+            Some(bulk) if !bulk.filled_volume.is_zero() => bulk.filled_volume,
+            _ => break,
+        };
+        let cap = min_fill_amount(&orderbook_pair, &sell_bulk_orders_list[j]);
+        if cap == taker_fill {
+            break;
         }
-        if sell_bulk_orders.volume <= min_vol {
-            // sell out
-            sell_bulk_orders.ask_volume = Uint128::zero();
-            j += 1;
+
+        let (start, buy_bulk_orders) = taker_start.clone();
+        buy_bulk_orders_list.truncate(start);
+        buy_bulk_orders_list.extend(buy_bulk_orders);
+        sell_bulk_orders_list.truncate(j);
+        i = start;
+        if cap.is_zero() {
+            break;
         }
+        taker_cap = Some((j, cap));
     }
 
     return Ok((buy_bulk_orders_list, sell_bulk_orders_list));
 }
 
+/// the filled amount of the sell orders of a price level that passes the min fill ratio,
+/// the orders are filled in matching priority so only the last filled one can be partially filled
+fn min_fill_amount(orderbook_pair: &OrderBook, bulk: &BulkOrders) -> Uint128 {
+    let mut unassigned = bulk.filled_volume;
+    let mut amount = Uint128::zero();
+    for order in bulk.orders.iter() {
+        let remaining = order.get_remaining_offer_amount();
+        if unassigned >= remaining {
+            amount += remaining;
+            unassigned -= remaining;
+            continue;
+        }
+        if orderbook_pair.is_min_fill(unassigned, remaining) {
+            amount += unassigned;
+        }
+        break;
+    }
+    amount
+}
+
+/// min and max relayer fees of the order book in base asset,
+/// the fees quoted in a usd stable denom are converted through the oracle at the current rate
+fn relayer_fee_caps(
+    deps: &DepsMut,
+    contract_info: &ContractInfo,
+    orderbook_pair: &OrderBook,
+) -> StdResult<(Option<Uint128>, Option<Uint128>)> {
+    let caps = (
+        orderbook_pair.min_relayer_fee,
+        orderbook_pair.max_relayer_fee,
+    );
+    let fee_usd_denom = match &orderbook_pair.fee_usd_denom {
+        Some(fee_usd_denom) => fee_usd_denom,
+        None => return Ok(caps),
+    };
+    let oracle = contract_info
+        .oracle
+        .as_ref()
+        .ok_or_else(|| StdError::generic_err("oracle is not set to convert the usd fees"))?;
+    let oracle_contract = OracleContract(deps.api.addr_humanize(oracle)?);
+
+    let base_denom = orderbook_pair
+        .base_coin_info
+        .to_normal(deps.api)?
+        .to_string();
+    let to_base_amount = |usd_amount: Option<Uint128>| -> StdResult<Option<Uint128>> {
+        usd_amount
+            .map(|usd_amount| {
+                oracle_contract
+                    .query_convert_amount(
+                        &deps.querier,
+                        fee_usd_denom.as_str(),
+                        base_denom.as_str(),
+                        usd_amount,
+                    )
+                    .map(|res| res.amount)
+            })
+            .transpose()
+    };
+
+    Ok((to_base_amount(caps.0)?, to_base_amount(caps.1)?))
+}
+
+/// relayer fee of a fill in the asset the trader receives, flat RELAY_FEE unless the order book sets a rate
+fn calculate_relayer_fee(
+    orderbook_pair: &OrderBook,
+    relayer_fee_caps: (Option<Uint128>, Option<Uint128>),
+    amount: Uint128,
+    direction: OrderDirection,
+    price: Decimal,
+) -> StdResult<Uint128> {
+    // caps are denominated in base asset, sell orders receive quote asset
+    let to_ask_asset = |base_amount: Uint128| match direction {
+        OrderDirection::Buy => Ok(base_amount),
+        OrderDirection::Sell => orderbook_pair.to_quote_amount(base_amount, price),
+    };
+
+    match orderbook_pair.relayer_fee_rate {
+        Some(relayer_fee_rate) => {
+            let mut relayer_fee = amount * relayer_fee_rate;
+            let (min_relayer_fee, max_relayer_fee) = relayer_fee_caps;
+            if let Some(min_relayer_fee) = min_relayer_fee {
+                relayer_fee = Uint128::max(relayer_fee, to_ask_asset(min_relayer_fee)?);
+            }
+            if let Some(max_relayer_fee) = max_relayer_fee {
+                relayer_fee = Uint128::min(relayer_fee, to_ask_asset(max_relayer_fee)?);
+            }
+            Ok(relayer_fee)
+        }
+        None => to_ask_asset(Uint128::from(RELAY_FEE)),
+    }
+}
+
 fn calculate_fee(
     deps: &DepsMut,
     amount: Uint128,
-    relayer_usdt_fee: Uint128,
+    relayer_fee: Uint128,
     direction: OrderDirection,
     trader_ask_asset: &mut Asset,
     reward: &mut Executor,
     relayer: &mut Executor,
-) -> Uint128 {
+) -> (Uint128, Uint128) {
     let reward_fee: Uint128;
-    let relayer_fee: Uint128;
     let contract_info = read_config(deps.storage).unwrap();
     let commission_rate = Decimal::from_str(&contract_info.commission_rate).unwrap();
 
     reward_fee = amount * commission_rate;
+    let relayer_fee = Uint128::min(relayer_fee, amount);
 
     match direction {
         OrderDirection::Buy => {
-            relayer_fee = Uint128::min(Uint128::from(RELAY_FEE), amount);
-
             reward.reward_assets[0].amount += reward_fee;
             relayer.reward_assets[0].amount += relayer_fee;
         }
         OrderDirection::Sell => {
-            relayer_fee = Uint128::min(relayer_usdt_fee, amount);
-
             reward.reward_assets[1].amount += reward_fee;
             relayer.reward_assets[1].amount += relayer_fee;
         }
@@ -456,17 +1053,22 @@ fn calculate_fee(
         .amount
         .checked_sub(reward_fee + relayer_fee)
         .unwrap();
-    return relayer_fee + reward_fee;
+    return (reward_fee, relayer_fee);
 }
 
+#[allow(clippy::too_many_arguments)]
 fn process_orders(
     deps: &DepsMut,
     orderbook_pair: &OrderBook,
+    relayer_fee_caps: (Option<Uint128>, Option<Uint128>),
     bulk_orders: &mut Vec<BulkOrders>,
     bulk_traders: &mut Vec<Payment>,
     reward: &mut Executor,
     relayer: &mut Executor,
-) {
+    stats: &mut PairStats,
+    maker_volumes: &mut Vec<Payment>,
+    commissions: &mut Vec<Payment>,
+) -> StdResult<()> {
     for bulk in bulk_orders.iter_mut() {
         let mut trader_ask_asset = Asset {
             info: match bulk.direction {
@@ -475,9 +1077,8 @@ fn process_orders(
             },
             amount: Uint128::zero(),
         };
-        let relayer_usdt_fee = Uint128::from(RELAY_FEE) * bulk.price;
 
-        for order in bulk.orders.iter_mut() {
+        for (index, order) in bulk.orders.iter_mut().enumerate() {
             let filled_offer = Uint128::min(
                 order
                     .offer_amount
@@ -505,15 +1106,58 @@ fn process_orders(
 
             if !filled_ask.is_zero() {
                 trader_ask_asset.amount = filled_ask;
-                calculate_fee(
+                let (reward_fee, relayer_fee) = calculate_fee(
                     deps,
                     filled_ask,
-                    relayer_usdt_fee,
+                    calculate_relayer_fee(
+                        orderbook_pair,
+                        relayer_fee_caps,
+                        filled_ask,
+                        bulk.direction,
+                        bulk.price,
+                    )?,
                     bulk.direction,
                     &mut trader_ask_asset,
                     reward,
                     relayer,
                 );
+                bulk.relayer_fees[index] += relayer_fee;
+                if !reward_fee.is_zero() {
+                    commissions.push(Payment {
+                        address: deps.api.addr_humanize(&order.bidder_addr).unwrap(),
+                        asset: Asset {
+                            info: trader_ask_asset.info.clone(),
+                            amount: reward_fee,
+                        },
+                    });
+                }
+
+                stats.total_trades += 1;
+                match bulk.direction {
+                    OrderDirection::Buy => {
+                        // buyers pay fees in base asset
+                        stats.buy_commission +=
+                            orderbook_pair.to_quote_amount(reward_fee, bulk.price)?;
+                        stats.relayer_fee +=
+                            orderbook_pair.to_quote_amount(relayer_fee, bulk.price)?;
+
+                        // buy orders are makers, their price is the matched price
+                        maker_volumes.push(Payment {
+                            address: deps.api.addr_humanize(&order.bidder_addr).unwrap(),
+                            asset: Asset {
+                                info: orderbook_pair.quote_coin_info.to_normal(deps.api).unwrap(),
+                                amount: filled_offer,
+                            },
+                        });
+                    }
+                    OrderDirection::Sell => {
+                        // count volume once, from the sell side
+                        stats.base_volume += filled_offer;
+                        stats.quote_volume += filled_ask;
+                        stats.sell_commission += reward_fee;
+                        stats.relayer_fee += relayer_fee;
+                    }
+                }
                 if !trader_ask_asset.amount.is_zero() {
                     let trader_payment: Payment = Payment {
                         address: deps.api.addr_humanize(&order.bidder_addr).unwrap(),
@@ -527,12 +1171,48 @@ fn process_orders(
             }
         }
     }
+
+    Ok(())
 }
 
-pub fn execute_matching_orders(
-    deps: DepsMut,
-    info: MessageInfo,
-    asset_infos: [AssetInfo; 2],
+/// credit maker rebates in matching order until the taker commission is used up, return the total rebate
+fn credit_maker_rebates(
+    storage: &mut dyn Storage,
+    api: &dyn Api,
+    maker_volumes: Vec<Payment>,
+    maker_rebate_rate: Decimal,
+    taker_commission: Uint128,
+) -> StdResult<Uint128> {
+    let mut total_rebate = Uint128::zero();
+    for maker_volume in maker_volumes {
+        let amount = Uint128::min(
+            maker_volume.asset.amount * maker_rebate_rate,
+            taker_commission.checked_sub(total_rebate)?,
+        );
+        if amount.is_zero() {
+            continue;
+        }
+
+        increase_maker_rebate(
+            storage,
+            &api.addr_canonicalize(maker_volume.address.as_str())?,
+            &maker_volume.asset.info.to_vec(api)?,
+            &Asset {
+                info: maker_volume.asset.info,
+                amount,
+            },
+        )?;
+        total_rebate += amount;
+    }
+
+    Ok(total_rebate)
+}
+
+pub fn execute_matching_orders(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asset_infos: [AssetInfo; 2],
     limit: Option<u32>,
 ) -> Result<Response, ContractError> {
     let contract_info = read_config(deps.storage)?;
@@ -541,8 +1221,53 @@ pub fn execute_matching_orders(
         asset_infos[0].to_raw(deps.api)?,
         asset_infos[1].to_raw(deps.api)?,
     ]);
-    let orderbook_pair = read_orderbook(deps.storage, &pair_key)?;
+    let mut orderbook_pair = read_orderbook(deps.storage, &pair_key)?;
+    if orderbook_pair.paused.unwrap_or_default() {
+        return Err(ContractError::Paused {});
+    }
+    // a delisting order book keeps matching during its grace period only
+    if let Some(delisting_time) = orderbook_pair.delisting_time {
+        if env.block.time.seconds() >= delisting_time {
+            return Err(ContractError::OrderBookDelisted { delisting_time });
+        }
+    }
+
+    // executions beyond the limit of the block are a no-op, so relayers racing in a block do not fail
+    // and the gas spent matching an order book in a block stays bounded
+    let executions_in_block = if orderbook_pair.last_executed_height == Some(env.block.height) {
+        orderbook_pair.executions_in_block.unwrap_or_default()
+    } else {
+        0
+    };
+    if let Some(max_executions_per_block) = orderbook_pair.max_executions_per_block {
+        if executions_in_block >= max_executions_per_block {
+            return Ok(Response::new().add_attributes(vec![
+                ("action", "execute_orderbook_pair"),
+                (
+                    "pair",
+                    &format!("{} - {}", &asset_infos[0], &asset_infos[1]),
+                ),
+                ("throttled", "true"),
+                ("executions_in_block", &executions_in_block.to_string()),
+            ]));
+        }
+    }
 
+    // a call market is matched once per interval, there is no clearing price when no orders cross
+    let clearing_price = match orderbook_pair.auction_interval_seconds {
+        Some(interval_seconds) => {
+            let next_auction_time =
+                orderbook_pair.last_auction_time.unwrap_or_default() + interval_seconds;
+            if env.block.time.seconds() < next_auction_time {
+                return Err(ContractError::AuctionNotDue { next_auction_time });
+            }
+            orderbook_pair.last_auction_time = Some(env.block.time.seconds());
+            orderbook_pair.find_clearing_price(deps.storage, limit)
+        }
+        None => None,
+    };
+
+    let relayer_fee_caps = relayer_fee_caps(&deps, &contract_info, &orderbook_pair)?;
     let reward_wallet = contract_info.reward_address;
 
     let reward_assets = [
@@ -573,55 +1298,112 @@ pub fn execute_matching_orders(
     let mut total_reward: Vec<String> = Vec::new();
 
     let mut total_orders: u64 = 0;
-
-    let (mut buy_list, mut sell_list) = execute_bulk_orders(&deps, orderbook_pair.clone(), limit)?;
+    let event_mode = contract_info.event_mode.unwrap_or_default();
+    let mut stats = PairStats::default();
+    let mut maker_volumes: Vec<Payment> = vec![];
+    let mut commissions: Vec<Payment> = vec![];
+    let prev_quote_commission = reward.reward_assets[1].amount;
+
+    let (mut buy_list, mut sell_list) =
+        execute_bulk_orders(&deps, orderbook_pair.clone(), limit, clearing_price)?;
+
+    // buy orders are makers, the prices of the filled ones are the matched prices in matching order
+    let matched_prices: Vec<Decimal> = buy_list
+        .iter()
+        .filter(|bulk| !bulk.filled_ask_volume.is_zero())
+        .map(|bulk| bulk.price)
+        .collect();
 
     process_orders(
         &deps,
         &orderbook_pair,
+        relayer_fee_caps,
         &mut buy_list,
         &mut list_bidder,
         &mut reward,
         &mut relayer,
-    );
+        &mut stats,
+        &mut maker_volumes,
+        &mut commissions,
+    )?;
 
     process_orders(
         &deps,
         &orderbook_pair,
+        relayer_fee_caps,
         &mut sell_list,
         &mut list_asker,
         &mut reward,
         &mut relayer,
-    );
+        &mut stats,
+        &mut maker_volumes,
+        &mut commissions,
+    )?;
 
+    let mut list_refund: Vec<Payment> = vec![];
     for bulk in buy_list.iter_mut() {
-        for buy_order in bulk.orders.iter_mut() {
+        for (buy_order, relayer_fee) in bulk.orders.iter_mut().zip(bulk.relayer_fees.iter()) {
             if buy_order.status != OrderStatus::Open {
                 total_orders += 1;
-                buy_order.match_order(deps.storage, &pair_key).unwrap();
-                ret_events.push(to_events(
-                    &buy_order,
-                    deps.api.addr_humanize(&buy_order.bidder_addr)?.to_string(),
-                    format!("{} {}", "1000", &reward.reward_assets[0].info),
-                ));
+                if clearing_price.is_some() && buy_order.status == OrderStatus::Fulfilled {
+                    let refund_amount = buy_order.get_remaining_offer_amount();
+                    if !refund_amount.is_zero() {
+                        list_refund.push(Payment {
+                            address: deps.api.addr_humanize(&buy_order.bidder_addr)?,
+                            asset: Asset {
+                                info: orderbook_pair.quote_coin_info.to_normal(deps.api)?,
+                                amount: refund_amount,
+                            },
+                        });
+                    }
+                }
+                buy_order
+                    .match_order(deps.storage, &pair_key, orderbook_pair.decimals_diff())
+                    .unwrap();
+                if event_mode == EventMode::Verbose {
+                    ret_events.push(to_events(
+                        &buy_order,
+                        deps.api.addr_humanize(&buy_order.bidder_addr)?.to_string(),
+                        format!("{} {}", "1000", &reward.reward_assets[0].info),
+                        format!("{} {}", relayer_fee, &relayer.reward_assets[0].info),
+                    ));
+                }
             }
         }
     }
 
     for bulk in sell_list.iter_mut() {
-        for sell_order in bulk.orders.iter_mut() {
+        for (sell_order, relayer_fee) in bulk.orders.iter_mut().zip(bulk.relayer_fees.iter()) {
             if sell_order.status != OrderStatus::Open {
                 total_orders += 1;
-                sell_order.match_order(deps.storage, &pair_key).unwrap();
-                ret_events.push(to_events(
-                    &sell_order,
-                    deps.api.addr_humanize(&sell_order.bidder_addr)?.to_string(),
-                    format!("{} {}", "2000", &reward.reward_assets[1].info),
-                ));
+                sell_order
+                    .match_order(deps.storage, &pair_key, orderbook_pair.decimals_diff())
+                    .unwrap();
+                if event_mode == EventMode::Verbose {
+                    ret_events.push(to_events(
+                        &sell_order,
+                        deps.api.addr_humanize(&sell_order.bidder_addr)?.to_string(),
+                        format!("{} {}", "2000", &reward.reward_assets[1].info),
+                        format!("{} {}", relayer_fee, &relayer.reward_assets[1].info),
+                    ));
+                }
             }
         }
     }
 
+    // aggregate instead of one event per order, fees are valued in quote asset
+    if event_mode == EventMode::Compact && total_orders > 0 {
+        ret_events.push(Event::new("matched_orders").add_attributes(vec![
+            attr("total_matched_orders", total_orders.to_string()),
+            attr("base_volume", stats.base_volume.to_string()),
+            attr("quote_volume", stats.quote_volume.to_string()),
+            attr(
+                "total_fees",
+                (stats.buy_commission + stats.sell_commission + stats.relayer_fee).to_string(),
+            ),
+        ]));
+    }
+
     transfer_spread(
         &deps,
         &orderbook_pair,
@@ -629,54 +1411,487 @@ pub fn execute_matching_orders(
         &mut sell_list,
         &mut messages,
     );
+    // rounding at the clearing price can leave a spread on buy orders as well
+    if clearing_price.is_some() {
+        transfer_spread(
+            &deps,
+            &orderbook_pair,
+            OrderDirection::Buy,
+            &mut buy_list,
+            &mut messages,
+        );
+    }
 
-    process_list_trader(&deps, list_bidder, &mut messages)?;
-    process_list_trader(&deps, list_asker, &mut messages)?;
+    let mut payouts: Vec<(CanonicalAddr, Asset)> = vec![];
+    let mut sub_messages: Vec<SubMsg> = vec![];
+    process_list_trader(&deps, list_bidder, &mut payouts, &mut sub_messages)?;
+    process_list_trader(&deps, list_asker, &mut payouts, &mut sub_messages)?;
+    process_list_trader(&deps, list_refund, &mut payouts, &mut sub_messages)?;
+
+    // referrers are paid their share out of the commission of this matching, a failing referral
+    // contract escrows the share for the reward address instead of reverting the matching
+    if let Some(referral) = &contract_info.referral {
+        let referral_addr = deps.api.addr_humanize(referral)?;
+        for (asset_info, rewards) in referral_rewards(&deps.querier, &referral_addr, commissions) {
+            let total_rewards = rewards
+                .iter()
+                .fold(Uint128::zero(), |total, reward| total + reward.amount);
+            let reward_asset = reward
+                .reward_assets
+                .iter_mut()
+                .find(|reward_asset| reward_asset.info == asset_info)
+                .unwrap();
+            reward_asset.amount = reward_asset.amount.checked_sub(total_rewards)?;
+
+            let paid_asset = Asset {
+                info: asset_info,
+                amount: total_rewards,
+            };
+            sub_messages.push(SubMsg::reply_on_error(
+                asset_into_rewards_msg(referral_addr.clone(), paid_asset.clone(), rewards)?,
+                payouts.len() as u64,
+            ));
+            payouts.push((reward.address.clone(), paid_asset));
+        }
+    }
+
+    if !payouts.is_empty() {
+        store_pending_payouts(deps.storage, payouts)?;
+    }
+
+    // rebate makers out of the commission charged on the sell orders of this matching
+    if let Some(maker_rebate_rate) = contract_info.maker_rebate_rate {
+        let taker_commission = reward.reward_assets[1]
+            .amount
+            .checked_sub(prev_quote_commission)?;
+        let total_rebate = credit_maker_rebates(
+            deps.storage,
+            deps.api,
+            maker_volumes,
+            maker_rebate_rate,
+            taker_commission,
+        )?;
+        reward.reward_assets[1].amount -= total_rebate;
+        stats.maker_rebate += total_rebate;
+    }
 
     transfer_reward(&deps, &mut reward, &mut total_reward, &mut messages);
     transfer_reward(&deps, &mut relayer, &mut total_reward, &mut messages);
 
     store_reward(deps.storage, &pair_key, &reward)?;
     store_reward(deps.storage, &pair_key, &relayer)?;
+    if stats.total_trades > 0 {
+        let period = env.block.time.seconds() / PAIR_STATS_PERIOD;
+        update_pair_stats(deps.storage, &pair_key, period, &stats)?;
+        if let Some(candle) = Candle::from_trades(&matched_prices, &stats) {
+            update_candles(deps.storage, &pair_key, env.block.time.seconds(), &candle)?;
+        }
+    }
+
+    // the resting depth is snapshotted only when orders are matched, so it can not be farmed
+    // by executing an order book without trades
+    let incentive_points = if total_orders > 0 {
+        accrue_incentive_points(deps.storage, &orderbook_pair)?
+    } else {
+        Uint128::zero()
+    };
+
+    // keepers and monitoring follow the last execution and the matched orders of the order book
+    orderbook_pair.last_executed_height = Some(env.block.height);
+    orderbook_pair.last_executed_by = Some(deps.api.addr_canonicalize(info.sender.as_str())?);
+    orderbook_pair.executions_in_block = Some(executions_in_block + 1);
+    orderbook_pair.orders_matched_total =
+        Some(orderbook_pair.orders_matched_total.unwrap_or_default() + total_orders);
+    store_orderbook(deps.storage, &pair_key, &orderbook_pair)?;
+
     Ok(Response::new()
         .add_messages(messages)
+        .add_submessages(sub_messages)
         .add_attributes(vec![
             ("action", "execute_orderbook_pair"),
+            ("event_mode", event_mode.as_str()),
             (
                 "pair",
                 &format!("{} - {}", &asset_infos[0], &asset_infos[1]),
             ),
             ("total_matched_orders", &total_orders.to_string()),
             ("executor_reward", &format!("{:?}", &total_reward)),
+            ("incentive_points", &incentive_points.to_string()),
         ])
+        .add_attributes(clearing_price.map(|price| ("clearing_price", price.to_string())))
         .add_events(ret_events))
 }
 
-pub fn remove_pair(
+pub fn execute_matching_orders_dry_run(
     deps: DepsMut,
     info: MessageInfo,
     asset_infos: [AssetInfo; 2],
+    limit: Option<u32>,
 ) -> Result<Response, ContractError> {
     let contract_info = read_config(deps.storage)?;
+    let relayer_addr = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let pair_key = pair_key(&[
+        asset_infos[0].to_raw(deps.api)?,
+        asset_infos[1].to_raw(deps.api)?,
+    ]);
+    let orderbook_pair = read_orderbook(deps.storage, &pair_key)?;
+    let base_info = orderbook_pair.base_coin_info.to_normal(deps.api)?;
+    let quote_info = orderbook_pair.quote_coin_info.to_normal(deps.api)?;
+    let relayer_fee_caps = relayer_fee_caps(&deps, &contract_info, &orderbook_pair)?;
+
+    let reward_assets = [
+        Asset {
+            info: base_info.clone(),
+            amount: Uint128::zero(),
+        },
+        Asset {
+            info: quote_info.clone(),
+            amount: Uint128::zero(),
+        },
+    ];
+    let mut reward = process_reward(
+        deps.storage,
+        &pair_key,
+        contract_info.reward_address,
+        reward_assets.clone(),
+    );
+    let mut relayer = process_reward(deps.storage, &pair_key, relayer_addr, reward_assets);
+
+    let mut list_bidder: Vec<Payment> = vec![];
+    let mut list_asker: Vec<Payment> = vec![];
+
+    // same computation as execute_matching_orders, but nothing is stored and a call market
+    // is simulated at its current clearing price whether the auction is due or not
+    let mut stats = PairStats::default();
+    let clearing_price = match orderbook_pair.auction_interval_seconds {
+        Some(_) => orderbook_pair.find_clearing_price(deps.storage, limit),
+        None => None,
+    };
+    let (mut buy_list, mut sell_list) =
+        execute_bulk_orders(&deps, orderbook_pair.clone(), limit, clearing_price)?;
+
+    process_orders(
+        &deps,
+        &orderbook_pair,
+        relayer_fee_caps,
+        &mut buy_list,
+        &mut list_bidder,
+        &mut reward,
+        &mut relayer,
+        &mut stats,
+        &mut vec![],
+        &mut vec![],
+    )?;
+
+    process_orders(
+        &deps,
+        &orderbook_pair,
+        relayer_fee_caps,
+        &mut sell_list,
+        &mut list_asker,
+        &mut reward,
+        &mut relayer,
+        &mut stats,
+        &mut vec![],
+        &mut vec![],
+    )?;
+
+    let orders = buy_list
+        .iter()
+        .chain(sell_list.iter())
+        .flat_map(|bulk| bulk.orders.iter())
+        .filter(|order| order.status != OrderStatus::Open)
+        .map(|order| order.to_response(deps.api, base_info.clone(), quote_info.clone()))
+        .collect::<StdResult<Vec<OrderResponse>>>()?;
+
+    Ok(Response::new()
+        .set_data(to_binary(&MatchOrdersDryRunResponse {
+            total_matched_orders: orders.len() as u64,
+            orders,
+        })?)
+        .add_attributes(vec![
+            ("action", "match_orders_dry_run"),
+            (
+                "pair",
+                &format!("{} - {}", &asset_infos[0], &asset_infos[1]),
+            ),
+        ]))
+}
+
+pub fn claim_rebates(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let maker = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let rebates = read_maker_rebates(deps.storage, &maker)?;
+    if rebates.is_empty() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "No rebates to claim",
+        )));
+    }
+
+    remove_maker_rebates(deps.storage, &maker)?;
+
+    let messages = rebates
+        .iter()
+        .map(|rebate| rebate.into_msg(None, &deps.querier, info.sender.clone()))
+        .collect::<StdResult<Vec<CosmosMsg>>>()?;
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "claim_rebates"),
+        ("maker", info.sender.as_str()),
+        (
+            "rebates",
+            &rebates
+                .iter()
+                .map(|rebate| rebate.to_string())
+                .collect::<Vec<String>>()
+                .join(","),
+        ),
+    ]))
+}
+
+/// keep a trader payout that failed to transfer in the escrow of the trader, reply id is the payout index
+pub fn escrow_failed_payout(
+    deps: DepsMut,
+    payout_id: u64,
+    error: String,
+) -> Result<Response, ContractError> {
+    let (trader, asset) = read_pending_payout(deps.storage, payout_id)?;
+    increase_escrow(deps.storage, &trader, &asset.info.to_vec(deps.api)?, &asset)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "escrow_failed_payout"),
+        ("trader", deps.api.addr_humanize(&trader)?.as_str()),
+        ("asset", &asset.to_string()),
+        ("error", &error),
+    ]))
+}
+
+pub fn claim_escrow(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let trader = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let assets = read_escrow(deps.storage, &trader)?;
+    if assets.is_empty() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "No escrow to claim",
+        )));
+    }
+
+    remove_escrow(deps.storage, &trader)?;
+
+    // a transfer that still fails reverts the claim and keeps the escrow
+    let messages = assets
+        .iter()
+        .map(|asset| asset.into_msg(None, &deps.querier, info.sender.clone()))
+        .collect::<StdResult<Vec<CosmosMsg>>>()?;
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "claim_escrow"),
+        ("trader", info.sender.as_str()),
+        (
+            "assets",
+            &assets
+                .iter()
+                .map(|asset| asset.to_string())
+                .collect::<Vec<String>>()
+                .join(","),
+        ),
+    ]))
+}
+
+pub fn remove_pair(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asset_infos: [AssetInfo; 2],
+    grace_period_seconds: Option<u64>,
+) -> Result<Response, ContractError> {
     let sender_addr = deps.api.addr_canonicalize(info.sender.as_str())?;
+    assert_role(deps.storage, &sender_addr, Role::Operator)?;
 
-    if contract_info.admin.ne(&sender_addr) {
-        return Err(ContractError::Unauthorized {});
+    let pair_key = pair_key(&[
+        asset_infos[0].to_raw(deps.api)?,
+        asset_infos[1].to_raw(deps.api)?,
+    ]);
+
+    // with a grace period the order book is wound down over several transactions,
+    // since refunding all of its orders at once may exceed the gas limit
+    let grace_period_seconds = match grace_period_seconds {
+        Some(grace_period_seconds) => grace_period_seconds,
+        None => {
+            remove_orderbook(deps.storage, &pair_key);
+            return Ok(Response::new().add_attributes(vec![
+                ("action", "remove_orderbook_pair"),
+                (
+                    "pair",
+                    &format!("{} - {}", &asset_infos[0], &asset_infos[1]),
+                ),
+            ]));
+        }
+    };
+
+    let mut orderbook_pair = read_orderbook(deps.storage, &pair_key)?;
+    if orderbook_pair.delisting_time.is_some() {
+        return Err(ContractError::OrderBookDelisting {});
+    }
+    let delisting_time = env.block.time.seconds() + grace_period_seconds;
+    orderbook_pair.delisting_time = Some(delisting_time);
+    store_orderbook(deps.storage, &pair_key, &orderbook_pair)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "delist_orderbook_pair"),
+        (
+            "pair",
+            &format!("{} - {}", &asset_infos[0], &asset_infos[1]),
+        ),
+        ("delisting_time", &delisting_time.to_string()),
+    ]))
+}
+
+pub fn refund_delisted_orders(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asset_infos: [AssetInfo; 2],
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let sender_addr = deps.api.addr_canonicalize(info.sender.as_str())?;
+    assert_role(deps.storage, &sender_addr, Role::Operator)?;
+
+    let pair_key = pair_key(&[
+        asset_infos[0].to_raw(deps.api)?,
+        asset_infos[1].to_raw(deps.api)?,
+    ]);
+    let orderbook_pair = read_orderbook(deps.storage, &pair_key)?;
+    match orderbook_pair.delisting_time {
+        Some(delisting_time) if env.block.time.seconds() >= delisting_time => {}
+        delisting_time => return Err(ContractError::OrderBookNotDelisted { delisting_time }),
+    }
+
+    let base_coin_info = orderbook_pair.base_coin_info.to_normal(deps.api)?;
+    let quote_coin_info = orderbook_pair.quote_coin_info.to_normal(deps.api)?;
+    let orders = read_orders(
+        deps.storage,
+        &pair_key,
+        None,
+        limit,
+        Some(OrderBy::Ascending),
+    )?;
+
+    let mut messages = vec![];
+    for order in orders.iter() {
+        let refund = Asset {
+            info: match order.direction {
+                OrderDirection::Buy => quote_coin_info.clone(),
+                OrderDirection::Sell => base_coin_info.clone(),
+            },
+            amount: order.offer_amount.checked_sub(order.filled_offer_amount)?,
+        };
+        if !refund.amount.is_zero() {
+            messages.push(refund.into_msg(
+                None,
+                &deps.querier,
+                deps.api.addr_humanize(&order.bidder_addr)?,
+            )?);
+        }
+        remove_order(
+            deps.storage,
+            &pair_key,
+            order,
+            orderbook_pair.decimals_diff(),
+        )?;
+    }
+
+    // the order book is removed once its last order is refunded
+    let remaining = read_orders(
+        deps.storage,
+        &pair_key,
+        None,
+        Some(1),
+        Some(OrderBy::Ascending),
+    )?;
+    let removed = remaining.is_empty();
+    if removed {
+        remove_orderbook(deps.storage, &pair_key);
+    }
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "refund_delisted_orders"),
+        (
+            "pair",
+            &format!("{} - {}", &base_coin_info, &quote_coin_info),
+        ),
+        ("refunded_orders", &orders.len().to_string()),
+        ("orderbook_removed", &removed.to_string()),
+    ]))
+}
+
+pub fn import_orders(
+    deps: DepsMut,
+    info: MessageInfo,
+    asset_infos: [AssetInfo; 2],
+    orders: Vec<OrderRecord>,
+) -> Result<Response, ContractError> {
+    let sender_addr = deps.api.addr_canonicalize(info.sender.as_str())?;
+    assert_role(deps.storage, &sender_addr, Role::SuperAdmin)?;
+
+    if !read_allow_import_orders(deps.storage) {
+        return Err(ContractError::Std(StdError::generic_err(
+            "importing orders is not allowed",
+        )));
     }
 
     let pair_key = pair_key(&[
         asset_infos[0].to_raw(deps.api)?,
         asset_infos[1].to_raw(deps.api)?,
     ]);
+    // the order book must be created before importing its orders
+    let mut orderbook_pair = read_orderbook(deps.storage, &pair_key)?;
+    if orderbook_pair.delisting_time.is_some() {
+        return Err(ContractError::OrderBookDelisting {});
+    }
+
+    let mut last_order_id = read_last_order_id(deps.storage)?;
+    let total_orders = orders.len();
+    for record in orders {
+        if read_order(deps.storage, &pair_key, record.order_id).is_ok() {
+            return Err(ContractError::Std(StdError::generic_err(format!(
+                "order {} already exists",
+                record.order_id
+            ))));
+        }
+
+        let order = Order {
+            order_id: record.order_id,
+            status: record.status,
+            direction: record.direction,
+            bidder_addr: deps.api.addr_canonicalize(record.bidder_addr.as_str())?,
+            offer_amount: record.offer_amount,
+            ask_amount: record.ask_amount,
+            filled_offer_amount: record.filled_offer_amount,
+            filled_ask_amount: record.filled_ask_amount,
+            client_id: None,
+        };
+        store_order(
+            deps.storage,
+            &pair_key,
+            &order,
+            orderbook_pair.decimals_diff(),
+            true,
+        )?;
+
+        last_order_id = last_order_id.max(order.order_id);
+        orderbook_pair.record_order(order.order_id, order.direction);
+    }
 
-    remove_orderbook(deps.storage, &pair_key);
+    // new orders must not reuse imported order ids
+    store_last_order_id(deps.storage, last_order_id)?;
+    store_orderbook(deps.storage, &pair_key, &orderbook_pair)?;
 
     Ok(Response::new().add_attributes(vec![
-        ("action", "remove_orderbook_pair"),
+        ("action", "import_orders"),
         (
             "pair",
             &format!("{} - {}", &asset_infos[0], &asset_infos[1]),
         ),
+        ("total_orders", &total_orders.to_string()),
     ]))
 }
 
@@ -699,6 +1914,60 @@ pub fn query_order(
     )
 }
 
+pub fn query_order_by_client_id(
+    deps: Deps,
+    bidder: Addr,
+    client_id: String,
+) -> StdResult<OrderResponse> {
+    let bidder_addr = deps.api.addr_canonicalize(bidder.as_str())?;
+    let (pair_key, order_id) = read_order_by_client_id(deps.storage, &bidder_addr, &client_id)?
+        .ok_or_else(|| StdError::not_found(format!("order with client_id {}", client_id)))?;
+    let orderbook_pair = read_orderbook(deps.storage, &pair_key)?;
+    let order = read_order(deps.storage, &pair_key, order_id)?;
+
+    order.to_response(
+        deps.api,
+        orderbook_pair.base_coin_info.to_normal(deps.api)?,
+        orderbook_pair.quote_coin_info.to_normal(deps.api)?,
+    )
+}
+
+pub fn query_export_orders(
+    deps: Deps,
+    asset_infos: [AssetInfo; 2],
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<ExportOrdersResponse> {
+    let pair_key = pair_key(&[
+        asset_infos[0].to_raw(deps.api)?,
+        asset_infos[1].to_raw(deps.api)?,
+    ]);
+
+    let orders = read_orders(
+        deps.storage,
+        &pair_key,
+        start_after,
+        limit,
+        Some(OrderBy::Ascending),
+    )?
+    .into_iter()
+    .map(|order| {
+        Ok(OrderRecord {
+            order_id: order.order_id,
+            status: order.status,
+            direction: order.direction,
+            bidder_addr: deps.api.addr_humanize(&order.bidder_addr)?,
+            offer_amount: order.offer_amount,
+            ask_amount: order.ask_amount,
+            filled_offer_amount: order.filled_offer_amount,
+            filled_ask_amount: order.filled_ask_amount,
+        })
+    })
+    .collect::<StdResult<Vec<OrderRecord>>>()?;
+
+    Ok(ExportOrdersResponse { orders })
+}
+
 pub fn query_orders(
     deps: Deps,
     asset_infos: [AssetInfo; 2],
@@ -707,25 +1976,34 @@ pub fn query_orders(
     start_after: Option<u64>,
     limit: Option<u32>,
     order_by: Option<i32>,
+    cursor: Option<String>,
 ) -> StdResult<OrdersResponse> {
-    let order_by = order_by.map_or(None, |val| OrderBy::try_from(val).ok());
+    let (start_after, order_by) = match cursor {
+        Some(cursor) => {
+            // the cursor key is the big endian order id
+            let cursor = Cursor::decode(&cursor)?;
+            let order_id = <[u8; 8]>::try_from(cursor.key.as_slice())
+                .map_err(|_| StdError::generic_err("Invalid cursor"))?;
+            (Some(u64::from_be_bytes(order_id)), Some(cursor.order))
+        }
+        None => (
+            start_after,
+            order_by.map_or(None, |val| OrderBy::try_from(val).ok()),
+        ),
+    };
     let pair_key = pair_key(&[
         asset_infos[0].to_raw(deps.api)?,
         asset_infos[1].to_raw(deps.api)?,
     ]);
     let orderbook_pair = read_orderbook(deps.storage, &pair_key)?;
 
-    let (direction_filter, direction_key): (Box<dyn Fn(&OrderDirection) -> bool>, Vec<u8>) =
-        match direction {
-            // copy value to closure
-            Some(d) => (Box::new(move |x| d.eq(x)), d.as_bytes().to_vec()),
-            None => (Box::new(|_| true), OrderDirection::Buy.as_bytes().to_vec()),
-        };
+    let direction_filter = |x: &OrderDirection| direction.map_or(true, |d| d.eq(x));
+    let direction_key = direction.unwrap_or(OrderDirection::Buy).as_bytes().to_vec();
 
     let orders: Option<Vec<Order>> = match filter {
         OrderFilter::Bidder(bidder_addr) => {
             let bidder_addr_raw = deps.api.addr_canonicalize(&bidder_addr)?;
-            read_orders_with_indexer::<OrderDirection>(
+            read_orders_with_indexer::<OrderDirection, _>(
                 deps.storage,
                 &[
                     PREFIX_ORDER_BY_BIDDER,
@@ -738,17 +2016,17 @@ pub fn query_orders(
                 order_by,
             )?
         }
-        OrderFilter::Tick {} => read_orders_with_indexer::<u64>(
+        OrderFilter::Tick {} => read_orders_with_indexer::<u64, _>(
             deps.storage,
             &[PREFIX_TICK, &pair_key, &direction_key],
-            Box::new(|_| true),
+            |_| true,
             start_after,
             limit,
             order_by,
         )?,
         OrderFilter::Price(price) => {
             let price_key = price.atomics().to_be_bytes();
-            read_orders_with_indexer::<OrderDirection>(
+            read_orders_with_indexer::<OrderDirection, _>(
                 deps.storage,
                 &[PREFIX_ORDER_BY_PRICE, &pair_key, &price_key],
                 direction_filter,
@@ -758,10 +2036,11 @@ pub fn query_orders(
             )?
         }
         OrderFilter::None => match direction {
-            Some(_) => read_orders_with_indexer::<OrderDirection>(
+            // the direction index only holds orders of the direction, no need to filter
+            Some(_) => read_orders_with_indexer::<OrderDirection, _>(
                 deps.storage,
                 &[PREFIX_ORDER_BY_DIRECTION, &pair_key, &direction_key],
-                direction_filter,
+                |_| true,
                 start_after,
                 limit,
                 order_by,
@@ -776,9 +2055,21 @@ pub fn query_orders(
         },
     };
 
+    let orders = orders.unwrap_or_default();
+    let next_cursor = next_cursor(
+        orders
+            .last()
+            .map(|order| order.order_id.to_be_bytes().to_vec()),
+        match order_by {
+            Some(OrderBy::Ascending) => OrderBy::Ascending,
+            _ => OrderBy::Descending,
+        },
+        orders.len(),
+        limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize,
+    );
+
     let resp = OrdersResponse {
         orders: orders
-            .unwrap_or_default()
             .iter()
             .map(|order| {
                 order.to_response(
@@ -788,11 +2079,266 @@ pub fn query_orders(
                 )
             })
             .collect::<StdResult<Vec<OrderResponse>>>()?,
+        next_cursor,
     };
 
     Ok(resp)
 }
 
+/// orders of the price level the price rounds to, so clients do not need the exact stored price.
+/// Ticks are listed ascending by price and orders first in first out at each tick, up to limit
+pub fn query_orders_at_price(
+    deps: Deps,
+    asset_infos: [AssetInfo; 2],
+    price: Decimal,
+    direction: OrderDirection,
+    limit: Option<u32>,
+) -> StdResult<PriceLevelResponse> {
+    let pair_key = pair_key(&[
+        asset_infos[0].to_raw(deps.api)?,
+        asset_infos[1].to_raw(deps.api)?,
+    ]);
+    let orderbook_pair = read_orderbook(deps.storage, &pair_key)?;
+    let base_coin_info = orderbook_pair.base_coin_info.to_normal(deps.api)?;
+    let quote_coin_info = orderbook_pair.quote_coin_info.to_normal(deps.api)?;
+
+    let (price, lower_price, upper_price) = price_level_range(
+        price,
+        orderbook_pair
+            .price_precision
+            .unwrap_or(DEFAULT_PRICE_PRECISION),
+    );
+    let ticks = query_ticks_in_range(deps.storage, &pair_key, direction, lower_price, upper_price)?;
+
+    let mut limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT);
+    let mut total_orders = 0u64;
+    let mut total_base_amount = Uint128::zero();
+    let mut orders: Vec<OrderResponse> = vec![];
+    for tick in ticks {
+        total_orders += tick.total_orders;
+        total_base_amount += tick.total_base_amount;
+        if limit == 0 {
+            continue;
+        }
+
+        let price_key = tick.price.atomics().to_be_bytes();
+        let tick_orders = read_orders_with_indexer::<OrderDirection, _>(
+            deps.storage,
+            &[PREFIX_ORDER_BY_PRICE, &pair_key, &price_key],
+            |x| direction.eq(x),
+            None,
+            Some(limit),
+            Some(OrderBy::Ascending),
+        )?
+        .unwrap_or_default();
+        limit -= tick_orders.len() as u32;
+
+        for order in sort_by_match_priority(tick_orders, orderbook_pair.decimals_diff())? {
+            orders.push(order.to_response(
+                deps.api,
+                base_coin_info.clone(),
+                quote_coin_info.clone(),
+            )?);
+        }
+    }
+
+    Ok(PriceLevelResponse {
+        price,
+        total_orders,
+        total_base_amount,
+        orders,
+    })
+}
+
+/// remaining offer amount of the orders on the same side before the order, at the order price
+/// and at all better prices
+pub fn query_order_position(
+    deps: Deps,
+    asset_infos: [AssetInfo; 2],
+    order_id: u64,
+) -> StdResult<OrderPositionResponse> {
+    let pair_key = pair_key(&[
+        asset_infos[0].to_raw(deps.api)?,
+        asset_infos[1].to_raw(deps.api)?,
+    ]);
+    let order = read_order(deps.storage, &pair_key, order_id)?;
+    let orderbook_pair = read_orderbook(deps.storage, &pair_key)?;
+    let price = order.get_price(orderbook_pair.decimals_diff())?;
+    let price_key = price.atomics().to_be_bytes();
+
+    // orders at the same price are matched first in first out
+    let mut position = 1u64;
+    let mut amount_ahead_at_price = Uint128::zero();
+    for order_ahead in read_tick_orders(
+        deps.storage,
+        &pair_key,
+        &price_key,
+        order.direction,
+        Some(&order_id.to_be_bytes()),
+    )? {
+        position += 1;
+        amount_ahead_at_price += order_ahead
+            .offer_amount
+            .checked_sub(order_ahead.filled_offer_amount)?;
+    }
+
+    // buy orders with higher price or sell orders with lower price are matched first
+    let tick_bucket: ReadonlyBucket<u64> = ReadonlyBucket::multilevel(
+        deps.storage,
+        &[PREFIX_TICK, &pair_key, order.direction.as_bytes()],
+    );
+    let (start, end) = match order.direction {
+        OrderDirection::Buy => (calc_range_start(Some(price_key.to_vec())), None),
+        OrderDirection::Sell => (None, Some(price_key.to_vec())),
+    };
+    let better_price_keys = tick_bucket
+        .range(start.as_deref(), end.as_deref(), OrderBy::Ascending)
+        .map(|item| item.map(|(key, _)| key))
+        .collect::<StdResult<Vec<Vec<u8>>>>()?;
+
+    let mut total_amount_ahead = amount_ahead_at_price;
+    for better_price_key in better_price_keys {
+        for order_ahead in read_tick_orders(
+            deps.storage,
+            &pair_key,
+            &better_price_key,
+            order.direction,
+            None,
+        )? {
+            total_amount_ahead += order_ahead
+                .offer_amount
+                .checked_sub(order_ahead.filled_offer_amount)?;
+        }
+    }
+
+    Ok(OrderPositionResponse {
+        order_id,
+        direction: order.direction,
+        price,
+        position,
+        amount_ahead_at_price,
+        total_amount_ahead,
+    })
+}
+
+pub fn query_pair_stats(
+    deps: Deps,
+    asset_infos: [AssetInfo; 2],
+    period: Option<u64>,
+) -> StdResult<PairStatsResponse> {
+    let pair_key = pair_key(&[
+        asset_infos[0].to_raw(deps.api)?,
+        asset_infos[1].to_raw(deps.api)?,
+    ]);
+    let orderbook_pair = read_orderbook(deps.storage, &pair_key)?;
+    let stats = read_pair_stats(deps.storage, &pair_key, period)?;
+
+    Ok(PairStatsResponse {
+        base_coin_info: orderbook_pair.base_coin_info.to_normal(deps.api)?,
+        quote_coin_info: orderbook_pair.quote_coin_info.to_normal(deps.api)?,
+        period,
+        base_volume: stats.base_volume,
+        quote_volume: stats.quote_volume,
+        buy_commission: stats.buy_commission,
+        sell_commission: stats.sell_commission,
+        relayer_fee: stats.relayer_fee,
+        maker_rebate: stats.maker_rebate,
+        total_trades: stats.total_trades,
+    })
+}
+
+pub fn query_candles(
+    deps: Deps,
+    asset_infos: [AssetInfo; 2],
+    interval: CandleInterval,
+    start: Option<u64>,
+    end: Option<u64>,
+) -> StdResult<CandlesResponse> {
+    let pair_key = pair_key(&[
+        asset_infos[0].to_raw(deps.api)?,
+        asset_infos[1].to_raw(deps.api)?,
+    ]);
+    let orderbook_pair = read_orderbook(deps.storage, &pair_key)?;
+    let candles = read_candles(deps.storage, &pair_key, interval, start, end)?
+        .into_iter()
+        .map(|(start_time, candle)| CandleResponse {
+            start_time,
+            open: candle.open,
+            high: candle.high,
+            low: candle.low,
+            close: candle.close,
+            base_volume: candle.base_volume,
+            quote_volume: candle.quote_volume,
+        })
+        .collect();
+
+    Ok(CandlesResponse {
+        base_coin_info: orderbook_pair.base_coin_info.to_normal(deps.api)?,
+        quote_coin_info: orderbook_pair.quote_coin_info.to_normal(deps.api)?,
+        interval,
+        candles,
+    })
+}
+
+pub fn query_maker_rebates(deps: Deps, address: Addr) -> StdResult<MakerRebatesResponse> {
+    let maker = deps.api.addr_canonicalize(address.as_str())?;
+    Ok(MakerRebatesResponse {
+        rebates: read_maker_rebates(deps.storage, &maker)?,
+    })
+}
+
+pub fn query_escrow(deps: Deps, address: Addr) -> StdResult<EscrowResponse> {
+    let trader = deps.api.addr_canonicalize(address.as_str())?;
+    Ok(EscrowResponse {
+        assets: read_escrow(deps.storage, &trader)?,
+    })
+}
+
+pub fn query_total_escrow(
+    deps: Deps,
+    env: Env,
+    asset_info: AssetInfo,
+) -> StdResult<TotalEscrowResponse> {
+    let asset_info_raw = asset_info.to_raw(deps.api)?;
+
+    // buy orders offer the quote asset, sell orders offer the base asset
+    let mut total_escrow = Uint128::zero();
+    for orderbook in read_all_orderbooks(deps.storage)? {
+        let pair_key = orderbook.get_pair_key();
+        if orderbook.quote_coin_info == asset_info_raw {
+            total_escrow += read_order_escrow(deps.storage, &pair_key, OrderDirection::Buy)?;
+        }
+        if orderbook.base_coin_info == asset_info_raw {
+            total_escrow += read_order_escrow(deps.storage, &pair_key, OrderDirection::Sell)?;
+        }
+    }
+
+    // the balance also holds failed payouts and unclaimed rebates, so it should never be less
+    let balance = asset_info.query_pool(&deps.querier, env.contract.address)?;
+
+    Ok(TotalEscrowResponse {
+        asset_info,
+        total_escrow,
+        balance,
+        deficit: total_escrow.saturating_sub(balance),
+    })
+}
+
+pub fn query_matching_rules(deps: Deps) -> StdResult<MatchingRulesResponse> {
+    let contract_info = read_config(deps.storage)?;
+
+    Ok(MatchingRulesResponse {
+        price_priority: PRICE_PRIORITY.to_string(),
+        time_priority: TIME_PRIORITY.to_string(),
+        match_price: MATCH_PRICE.to_string(),
+        dust_volume: MIN_VOLUME,
+        default_limit: DEFAULT_LIMIT,
+        max_limit: MAX_LIMIT,
+        commission_rate: contract_info.commission_rate,
+        relayer_fee: Uint128::from(RELAY_FEE),
+    })
+}
+
 pub fn query_last_order_id(deps: Deps) -> StdResult<LastOrderIdResponse> {
     let last_order_id = read_last_order_id(deps.storage)?;
     let resp = LastOrderIdResponse { last_order_id };
@@ -824,6 +2370,25 @@ pub fn query_orderbook(deps: Deps, asset_infos: [AssetInfo; 2]) -> StdResult<Ord
     ob.to_response(deps.api)
 }
 
+pub fn query_pair_sequence(
+    deps: Deps,
+    asset_infos: [AssetInfo; 2],
+) -> StdResult<PairSequenceResponse> {
+    let pair_key = pair_key(&[
+        asset_infos[0].to_raw(deps.api)?,
+        asset_infos[1].to_raw(deps.api)?,
+    ]);
+    let ob = read_orderbook(deps.storage, &pair_key)?;
+
+    Ok(PairSequenceResponse {
+        base_coin_info: ob.base_coin_info.to_normal(deps.api)?,
+        quote_coin_info: ob.quote_coin_info.to_normal(deps.api)?,
+        last_order_id: ob.last_order_id,
+        buy_order_count: ob.buy_order_count.unwrap_or_default(),
+        sell_order_count: ob.sell_order_count.unwrap_or_default(),
+    })
+}
+
 pub fn query_orderbook_is_matchable(
     deps: Deps,
     asset_infos: [AssetInfo; 2],
@@ -1,4 +1,4 @@
-use std::convert::TryInto;
+use std::cmp::Ordering;
 
 use cosmwasm_schema::cw_serde;
 use cosmwasm_storage::ReadonlyBucket;
@@ -7,14 +7,16 @@ use oraiswap::{
     limit_order::{OrderBookResponse, OrderDirection, OrderResponse, OrderStatus},
 };
 
-use cosmwasm_std::{Api, CanonicalAddr, Decimal, Order as OrderBy, StdResult, Storage, Uint128};
+use cosmwasm_std::{
+    Api, CanonicalAddr, Decimal, Order as OrderBy, StdResult, Storage, Uint128, Uint256,
+};
 
 use crate::{
     state::{
         read_orders, read_orders_with_indexer, remove_order, store_order, PREFIX_ORDER_BY_PRICE,
         PREFIX_TICK,
     },
-    tick::{query_ticks_prices, query_ticks_prices_with_end},
+    tick::{price_from_key, query_ticks_prices, query_ticks_prices_with_end},
 };
 
 #[cw_serde]
@@ -27,6 +29,28 @@ pub struct Order {
     pub ask_amount: Uint128,
     pub filled_offer_amount: Uint128,
     pub filled_ask_amount: Uint128,
+    pub client_id: Option<String>,
+}
+
+/// matching priority of orders of the same direction, the best price first (highest for buy, lowest for sell),
+/// then the lowest order id which is the earliest submitted
+fn compare_match_priority(a: &(Decimal, Order), b: &(Decimal, Order)) -> Ordering {
+    let price_priority = a.0.cmp(&b.0);
+    let price_priority = match a.1.direction {
+        OrderDirection::Buy => price_priority.reverse(),
+        OrderDirection::Sell => price_priority,
+    };
+    price_priority.then(a.1.order_id.cmp(&b.1.order_id))
+}
+
+/// sort orders of the same direction by matching priority, the prices are computed once per order
+pub fn sort_by_match_priority(orders: Vec<Order>, decimals_diff: i32) -> StdResult<Vec<Order>> {
+    let mut priced_orders = orders
+        .into_iter()
+        .map(|order| Ok((order.get_price(decimals_diff)?, order)))
+        .collect::<StdResult<Vec<(Decimal, Order)>>>()?;
+    priced_orders.sort_by(compare_match_priority);
+    Ok(priced_orders.into_iter().map(|(_, order)| order).collect())
 }
 
 #[cw_serde]
@@ -35,6 +59,33 @@ pub struct Executor {
     pub reward_assets: [Asset; 2],
 }
 
+/// matched volume and collected fees of an order book, fees are valued in quote asset at the matched price
+#[cw_serde]
+#[derive(Default)]
+pub struct PairStats {
+    pub base_volume: Uint128,
+    pub quote_volume: Uint128,
+    // commission charged on filled buy orders
+    pub buy_commission: Uint128,
+    // commission charged on filled sell orders
+    pub sell_commission: Uint128,
+    pub relayer_fee: Uint128,
+    // part of sell_commission rebated to the makers
+    pub maker_rebate: Uint128,
+    pub total_trades: u64,
+}
+
+/// matched prices and volume of an order book during a candle interval
+#[cw_serde]
+pub struct Candle {
+    pub open: Decimal,
+    pub high: Decimal,
+    pub low: Decimal,
+    pub close: Decimal,
+    pub base_volume: Uint128,
+    pub quote_volume: Uint128,
+}
+
 impl Order {
     // create new order given a price and an offer amount
     pub fn new(
@@ -60,6 +111,7 @@ impl Order {
             filled_offer_amount: Uint128::zero(),
             filled_ask_amount: Uint128::zero(),
             status: OrderStatus::Open,
+            client_id: None,
         }
     }
 
@@ -75,21 +127,40 @@ impl Order {
         }
     }
 
-    pub fn match_order(&mut self, storage: &mut dyn Storage, pair_key: &[u8]) -> StdResult<u64> {
+    pub fn match_order(
+        &mut self,
+        storage: &mut dyn Storage,
+        pair_key: &[u8],
+        decimals_diff: i32,
+    ) -> StdResult<u64> {
         if self.status == OrderStatus::Fulfilled {
             // When status is Fulfilled, remove order
-            remove_order(storage, pair_key, self)
+            remove_order(storage, pair_key, self, decimals_diff)
         } else {
             // update order
-            store_order(storage, pair_key, self, false)
+            store_order(storage, pair_key, self, decimals_diff, false)
         }
     }
 
-    // The price will be calculated by the number of base coins divided by the number of quote coins
-    pub fn get_price(&self) -> Decimal {
+    // remaining base amount, that is the ask amount of buy orders or the offer amount of sell orders
+    pub fn get_remaining_base_amount(&self) -> Uint128 {
         match self.direction {
-            OrderDirection::Buy => Decimal::from_ratio(self.offer_amount, self.ask_amount),
-            OrderDirection::Sell => Decimal::from_ratio(self.ask_amount, self.offer_amount),
+            OrderDirection::Buy => self.ask_amount.saturating_sub(self.filled_ask_amount),
+            OrderDirection::Sell => self.offer_amount.saturating_sub(self.filled_offer_amount),
+        }
+    }
+
+    // remaining offer amount, that is still held by the contract for the order
+    pub fn get_remaining_offer_amount(&self) -> Uint128 {
+        self.offer_amount.saturating_sub(self.filled_offer_amount)
+    }
+
+    // The price will be calculated by the number of quote coins divided by the number of base coins,
+    // in whole coins given the decimals diff of the order book
+    pub fn get_price(&self, decimals_diff: i32) -> StdResult<Decimal> {
+        match self.direction {
+            OrderDirection::Buy => scaled_price(self.offer_amount, self.ask_amount, decimals_diff),
+            OrderDirection::Sell => scaled_price(self.ask_amount, self.offer_amount, decimals_diff),
         }
     }
 
@@ -120,10 +191,35 @@ impl Order {
             },
             filled_offer_amount: self.filled_offer_amount,
             filled_ask_amount: self.filled_ask_amount,
+            client_id: self.client_id.clone(),
         })
     }
 }
 
+/// the ratio of atomics is scaled by 10^decimals_diff to get a price in whole coins,
+/// return the multiplier and the divisor
+fn decimals_scale(decimals_diff: i32) -> (Uint256, Uint256) {
+    let scale = Uint256::from(10u128).pow(decimals_diff.unsigned_abs());
+    if decimals_diff >= 0 {
+        (scale, Uint256::one())
+    } else {
+        (Uint256::one(), scale)
+    }
+}
+
+/// price of a quote amount per base amount in atomics, decimals_diff is base decimals minus quote decimals
+pub fn scaled_price(
+    quote_amount: Uint128,
+    base_amount: Uint128,
+    decimals_diff: i32,
+) -> StdResult<Decimal> {
+    let (multiplier, divisor) = decimals_scale(decimals_diff);
+    let atomics =
+        Uint256::from(quote_amount) * Uint256::from(Decimal::one().atomics()) * multiplier
+            / (Uint256::from(base_amount) * divisor);
+    Ok(Decimal::raw(Uint128::try_from(atomics)?.u128()))
+}
+
 /// Ticks are stored in Ordered database, so we just need to process at 50 recent ticks is ok
 #[cw_serde]
 pub struct OrderBook {
@@ -131,6 +227,55 @@ pub struct OrderBook {
     pub quote_coin_info: AssetInfoRaw,
     pub spread: Option<Decimal>,
     pub min_quote_coin_amount: Uint128,
+    // orders whose remaining base amount is worth less than this quote amount at the order price are rejected
+    pub min_quote_notional: Option<Uint128>,
+    // a partially filled sell order must be filled at least this rate of its remaining base amount,
+    // or min_fill_base, otherwise it keeps resting untouched to avoid dust partial fills
+    pub min_fill_ratio: Option<Decimal>,
+    pub min_fill_base: Option<Uint128>,
+    // orders cancelled before this lifetime are charged the cancel fee, or rejected without it
+    pub min_order_lifetime_seconds: Option<u64>,
+    pub cancel_fee_rate: Option<Decimal>,
+    // orders never filled for this number of blocks since submitted are cancelled without the cancel fee
+    pub free_cancel_blocks: Option<u64>,
+    // bounds the gas spent matching in a block, extra executions in the same block are a no-op
+    pub max_executions_per_block: Option<u32>,
+    // pair contract whose pool price is the reference to reject orders with fat-finger price
+    pub price_reference: Option<CanonicalAddr>,
+    pub max_price_deviation: Option<Decimal>,
+    // relayer fee as a rate of the filled amount, capped in base asset, flat fee when not set
+    pub relayer_fee_rate: Option<Decimal>,
+    pub min_relayer_fee: Option<Uint128>,
+    pub max_relayer_fee: Option<Uint128>,
+    // usd stable denom the relayer fee caps are quoted in, converted into base asset through the oracle
+    pub fee_usd_denom: Option<String>,
+    // bounds the open orders of a bidder, so the storage and matching of the order book stay bounded
+    pub max_orders_per_user: Option<u32>,
+    // resting orders within this rate of the mid price accrue incentive points at each matching
+    pub incentive_price_band: Option<Decimal>,
+    // decimals of the assets, queried at creation so prices are quoted in whole coins,
+    // order books created before have no decimals and quote prices in atomics
+    pub base_decimals: Option<u8>,
+    pub quote_decimals: Option<u8>,
+    // decimal places the prices of OrdersAtPrice are rounded to, DEFAULT_PRICE_PRECISION when not set
+    pub price_precision: Option<u32>,
+    // set by admin to update spread, min quote coin amount and paused state of this order book
+    pub operator: Option<CanonicalAddr>,
+    pub paused: Option<bool>,
+    // call market mode, orders are matched at most once per interval at a single clearing price
+    pub auction_interval_seconds: Option<u64>,
+    pub last_auction_time: Option<u64>,
+    // last ExecuteOrderBookPair of this order book and the total orders it has matched
+    pub last_executed_height: Option<u64>,
+    pub last_executed_by: Option<CanonicalAddr>,
+    pub executions_in_block: Option<u32>,
+    pub orders_matched_total: Option<u64>,
+    // latest order id created in this order book and the orders created by direction, so indexers can detect gaps
+    pub last_order_id: Option<u64>,
+    pub buy_order_count: Option<u64>,
+    pub sell_order_count: Option<u64>,
+    // set when the order book is delisted, matching stops at this time and remaining orders get refunded
+    pub delisting_time: Option<u64>,
 }
 
 impl OrderBook {
@@ -144,6 +289,36 @@ impl OrderBook {
             quote_coin_info,
             spread,
             min_quote_coin_amount: Uint128::zero(),
+            min_quote_notional: None,
+            min_fill_ratio: None,
+            min_fill_base: None,
+            min_order_lifetime_seconds: None,
+            cancel_fee_rate: None,
+            free_cancel_blocks: None,
+            max_executions_per_block: None,
+            price_reference: None,
+            max_price_deviation: None,
+            relayer_fee_rate: None,
+            min_relayer_fee: None,
+            max_relayer_fee: None,
+            fee_usd_denom: None,
+            max_orders_per_user: None,
+            incentive_price_band: None,
+            price_precision: None,
+            base_decimals: None,
+            quote_decimals: None,
+            operator: None,
+            paused: None,
+            auction_interval_seconds: None,
+            last_auction_time: None,
+            last_executed_height: None,
+            last_executed_by: None,
+            executions_in_block: None,
+            orders_matched_total: None,
+            last_order_id: None,
+            buy_order_count: None,
+            sell_order_count: None,
+            delisting_time: None,
         }
     }
 
@@ -153,6 +328,42 @@ impl OrderBook {
             quote_coin_info: self.quote_coin_info.to_normal(api)?,
             spread: self.spread,
             min_quote_coin_amount: self.min_quote_coin_amount,
+            min_quote_notional: self.min_quote_notional,
+            min_fill_ratio: self.min_fill_ratio,
+            min_fill_base: self.min_fill_base,
+            min_order_lifetime_seconds: self.min_order_lifetime_seconds,
+            cancel_fee_rate: self.cancel_fee_rate,
+            free_cancel_blocks: self.free_cancel_blocks,
+            max_executions_per_block: self.max_executions_per_block,
+            price_reference: match &self.price_reference {
+                Some(price_reference) => Some(api.addr_humanize(price_reference)?),
+                None => None,
+            },
+            max_price_deviation: self.max_price_deviation,
+            relayer_fee_rate: self.relayer_fee_rate,
+            min_relayer_fee: self.min_relayer_fee,
+            max_relayer_fee: self.max_relayer_fee,
+            fee_usd_denom: self.fee_usd_denom.clone(),
+            max_orders_per_user: self.max_orders_per_user,
+            incentive_price_band: self.incentive_price_band,
+            price_precision: self.price_precision,
+            base_decimals: self.base_decimals,
+            quote_decimals: self.quote_decimals,
+            operator: match &self.operator {
+                Some(operator) => Some(api.addr_humanize(operator)?),
+                None => None,
+            },
+            paused: self.paused.unwrap_or_default(),
+            auction_interval_seconds: self.auction_interval_seconds,
+            last_auction_time: self.last_auction_time,
+            last_executed_height: self.last_executed_height,
+            last_executed_by: match &self.last_executed_by {
+                Some(last_executed_by) => Some(api.addr_humanize(last_executed_by)?),
+                None => None,
+            },
+            executions_in_block: self.executions_in_block.unwrap_or_default(),
+            orders_matched_total: self.orders_matched_total.unwrap_or_default(),
+            delisting_time: self.delisting_time,
         })
     }
 
@@ -163,9 +374,61 @@ impl OrderBook {
         )
     }
 
+    /// base decimals minus quote decimals, 0 when the decimals are not stored
+    pub fn decimals_diff(&self) -> i32 {
+        match (self.base_decimals, self.quote_decimals) {
+            (Some(base_decimals), Some(quote_decimals)) => {
+                base_decimals as i32 - quote_decimals as i32
+            }
+            _ => 0,
+        }
+    }
+
+    /// quote amount of a base amount at the price, rounded down
+    pub fn to_quote_amount(&self, base_amount: Uint128, price: Decimal) -> StdResult<Uint128> {
+        let (multiplier, divisor) = decimals_scale(self.decimals_diff());
+        let amount = Uint256::from(base_amount) * Uint256::from(price.atomics()) * divisor
+            / (Uint256::from(Decimal::one().atomics()) * multiplier);
+        Ok(Uint128::try_from(amount)?)
+    }
+
+    /// base amount of a quote amount at the price, rounded down
+    pub fn to_base_amount(&self, quote_amount: Uint128, price: Decimal) -> StdResult<Uint128> {
+        let (multiplier, divisor) = decimals_scale(self.decimals_diff());
+        let amount =
+            Uint256::from(quote_amount) * Uint256::from(Decimal::one().atomics()) * multiplier
+                / (Uint256::from(price.atomics()) * divisor);
+        Ok(Uint128::try_from(amount)?)
+    }
+
+    /// whether a match filling the base amount of the remaining base amount passes the min fill ratio,
+    /// filling at least min_fill_base or all the remaining always passes
+    pub fn is_min_fill(&self, filled_amount: Uint128, remaining_amount: Uint128) -> bool {
+        let min_fill_ratio = match self.min_fill_ratio {
+            Some(min_fill_ratio) => min_fill_ratio,
+            None => return true,
+        };
+        let min_fill_base = self
+            .min_fill_base
+            .map_or(remaining_amount, |min_fill_base| {
+                Uint128::min(min_fill_base, remaining_amount)
+            });
+        filled_amount >= remaining_amount * min_fill_ratio || filled_amount >= min_fill_base
+    }
+
+    /// track the sequence of the orders created in this order book
+    pub fn record_order(&mut self, order_id: u64, direction: OrderDirection) {
+        self.last_order_id = Some(self.last_order_id.unwrap_or_default().max(order_id));
+        let count = match direction {
+            OrderDirection::Buy => &mut self.buy_order_count,
+            OrderDirection::Sell => &mut self.sell_order_count,
+        };
+        *count = Some(count.unwrap_or_default() + 1);
+    }
+
     pub fn add_order(&mut self, storage: &mut dyn Storage, order: &Order) -> StdResult<u64> {
         let pair_key = &self.get_pair_key();
-        store_order(storage, pair_key, order, true)
+        store_order(storage, pair_key, order, self.decimals_diff(), true)
     }
 
     fn best_price(
@@ -180,12 +443,15 @@ impl OrderBook {
         let position_bucket: ReadonlyBucket<u64> =
             ReadonlyBucket::multilevel(storage, tick_namespaces);
 
-        if let Some(item) = position_bucket.range(None, None, price_increasing).next() {
-            if let Ok((price_key, total_orders)) = item {
-                // price is rounded already
-                let price = Decimal::raw(u128::from_be_bytes(price_key.try_into().unwrap()));
-                return (price, true, total_orders);
-            }
+        // price is rounded already, malformed tick keys are skipped
+        if let Some((price, total_orders)) = position_bucket
+            .range(None, None, price_increasing)
+            .find_map(|item| {
+                let (price_key, total_orders) = item.ok()?;
+                Some((price_from_key(&price_key)?, total_orders))
+            })
+        {
+            return (price, true, total_orders);
         }
 
         // return default
@@ -224,14 +490,14 @@ impl OrderBook {
         limit: Option<u32>,
     ) -> Option<Vec<Order>> {
         let pair_key = &self.get_pair_key();
-        read_orders_with_indexer::<OrderDirection>(
+        read_orders_with_indexer::<OrderDirection, _>(
             storage,
             &[
                 PREFIX_ORDER_BY_PRICE,
                 pair_key,
                 &price.atomics().to_be_bytes(),
             ],
-            Box::new(move |item| direction.eq(item)),
+            |item| direction.eq(item),
             start_after,
             limit,
             Some(OrderBy::Ascending), // first in first out
@@ -267,14 +533,7 @@ impl OrderBook {
                 &[PREFIX_TICK, pair_key, OrderDirection::Buy.as_bytes()],
             )
             .range(None, None, OrderBy::Descending)
-            .filter_map(|item| {
-                if let Ok((price_key, _)) = item {
-                    let buy_price =
-                        Decimal::raw(u128::from_be_bytes(price_key.try_into().unwrap()));
-                    return Some(buy_price);
-                }
-                None
-            })
+            .filter_map(|item| price_from_key(&item.ok()?.0))
             .collect::<Vec<Decimal>>();
 
             let tick_namespaces = &[PREFIX_TICK, pair_key, OrderDirection::Sell.as_bytes()];
@@ -283,10 +542,7 @@ impl OrderBook {
             if let Some(sell_price) = ReadonlyBucket::<u64>::multilevel(storage, tick_namespaces)
                 .range(None, None, OrderBy::Ascending)
                 .find_map(|item| {
-                    if let Ok((price_key, _)) = item {
-                        let sell_price =
-                            Decimal::raw(u128::from_be_bytes(price_key.try_into().unwrap()));
-
+                    if let Some(sell_price) = item.ok().and_then(|(k, _)| price_from_key(&k)) {
                         for buy_price in &buy_price_list {
                             if buy_price.ge(&sell_price)
                                 && buy_price.le(&(sell_price * spread_factor))
@@ -410,6 +666,71 @@ impl OrderBook {
         return Some((best_buy_price_list, best_sell_price_list));
     }
 
+    /// find the single price of the call market that maximizes the matched base volume among the
+    /// prices of the first `limit` ticks of each side, ties are broken by the smallest imbalance
+    /// between both sides then by the lowest price
+    pub fn find_clearing_price(
+        &self,
+        storage: &dyn Storage,
+        limit: Option<u32>,
+    ) -> Option<Decimal> {
+        let pair_key = &self.get_pair_key();
+        let tick_volumes = |direction: OrderDirection, order_by: i32| {
+            query_ticks_prices(storage, pair_key, direction, None, limit, Some(order_by))
+                .into_iter()
+                .map(|price| {
+                    let volume: Uint128 = self
+                        .query_orders_by_price_and_direction(storage, price, direction, None)
+                        .unwrap_or_default()
+                        .iter()
+                        .map(|order| order.get_remaining_base_amount().u128())
+                        .sum::<u128>()
+                        .into();
+                    (price, volume)
+                })
+                .collect::<Vec<(Decimal, Uint128)>>()
+        };
+        let buy_ticks = tick_volumes(OrderDirection::Buy, 2);
+        let sell_ticks = tick_volumes(OrderDirection::Sell, 1);
+
+        // (price, matched volume, imbalance)
+        let mut clearing: Option<(Decimal, Uint128, Uint128)> = None;
+        for (price, _) in buy_ticks.iter().chain(sell_ticks.iter()) {
+            let demand: Uint128 = buy_ticks
+                .iter()
+                .filter(|(buy_price, _)| buy_price >= price)
+                .map(|(_, volume)| volume.u128())
+                .sum::<u128>()
+                .into();
+            let supply: Uint128 = sell_ticks
+                .iter()
+                .filter(|(sell_price, _)| sell_price <= price)
+                .map(|(_, volume)| volume.u128())
+                .sum::<u128>()
+                .into();
+            let matched = Uint128::min(demand, supply);
+            if matched.is_zero() {
+                continue;
+            }
+            let imbalance = Uint128::max(demand, supply) - matched;
+
+            let better = match clearing {
+                None => true,
+                Some((clearing_price, clearing_matched, clearing_imbalance)) => {
+                    matched > clearing_matched
+                        || (matched == clearing_matched
+                            && (imbalance < clearing_imbalance
+                                || (imbalance == clearing_imbalance && *price < clearing_price)))
+                }
+            };
+            if better {
+                clearing = Some((*price, matched, imbalance));
+            }
+        }
+
+        clearing.map(|(price, _, _)| price)
+    }
+
     /// return the largest matchable amount of orders when matching orders at single price, that is total buy volume to sell at that price
     /// based on best buy price and best sell price, do the filling
     pub fn find_match_amount_at_price(
@@ -446,15 +767,17 @@ impl OrderBook {
         let price_key = price.atomics().to_be_bytes();
 
         // there is a limit, and we just match a batch with maximum orders reach the limit step by step
-        read_orders_with_indexer::<OrderDirection>(
+        read_orders_with_indexer::<OrderDirection, _>(
             storage,
             &[PREFIX_ORDER_BY_PRICE, pair_key, &price_key],
-            Box::new(move |x| direction.eq(x)),
+            |x| direction.eq(x),
             None,
             limit,
             Some(OrderBy::Ascending), // if mean we process from first to last order in the orderlist
         )
         .unwrap_or_default()
+        // stored orders are indexed by their price, so it can not overflow here
+        .and_then(|orders| sort_by_match_priority(orders, self.decimals_diff()).ok())
     }
 }
 
@@ -467,6 +790,42 @@ impl Executor {
     }
 }
 
+impl PairStats {
+    pub fn add(&mut self, other: &PairStats) {
+        self.base_volume += other.base_volume;
+        self.quote_volume += other.quote_volume;
+        self.buy_commission += other.buy_commission;
+        self.sell_commission += other.sell_commission;
+        self.relayer_fee += other.relayer_fee;
+        self.maker_rebate += other.maker_rebate;
+        self.total_trades += other.total_trades;
+    }
+}
+
+impl Candle {
+    /// candle of a matching given its matched prices in matching order, none without trades
+    pub fn from_trades(prices: &[Decimal], stats: &PairStats) -> Option<Self> {
+        let (open, close) = (*prices.first()?, *prices.last()?);
+        Some(Candle {
+            open,
+            high: prices.iter().copied().max()?,
+            low: prices.iter().copied().min()?,
+            close,
+            base_volume: stats.base_volume,
+            quote_volume: stats.quote_volume,
+        })
+    }
+
+    pub fn add(&mut self, other: &Candle) {
+        self.high = Decimal::max(self.high, other.high);
+        self.low = Decimal::min(self.low, other.low);
+        self.close = other.close;
+        self.base_volume += other.base_volume;
+        self.quote_volume += other.quote_volume;
+    }
+}
+
+#[derive(Clone)]
 pub struct BulkOrders {
     pub orders: Vec<Order>,
     pub direction: OrderDirection,
@@ -476,6 +835,8 @@ pub struct BulkOrders {
     pub ask_volume: Uint128,
     pub filled_ask_volume: Uint128,
     pub spread_volume: Uint128,
+    // relayer fee charged on each order, in the asset the order receives
+    pub relayer_fees: Vec<Uint128>,
 }
 
 impl BulkOrders {
@@ -501,6 +862,7 @@ impl BulkOrders {
             ask_volume,
             filled_ask_volume,
             spread_volume,
+            relayer_fees: vec![Uint128::zero(); orders.len()],
         };
     }
 }
@@ -1,17 +1,26 @@
-use cosmwasm_std::{CanonicalAddr, Order as OrderBy, StdResult, Storage};
+use cosmwasm_std::{
+    Binary, CanonicalAddr, Order as OrderBy, StdError, StdResult, Storage, Uint128,
+};
 use cosmwasm_storage::{singleton, singleton_read, Bucket, ReadonlyBucket};
 use oraiswap::{
-    limit_order::{ContractInfo, OrderDirection},
+    asset::Asset,
+    limit_order::{CandleInterval, ContractInfo, OrderDirection, Role},
     querier::calc_range_start,
 };
 use serde::{de::DeserializeOwned, Serialize};
 
-use crate::orderbook::{Executor, Order, OrderBook};
+use crate::orderbook::{Candle, Executor, Order, OrderBook, PairStats};
 
 // settings for pagination
 pub const MAX_LIMIT: u32 = 100;
 pub const DEFAULT_LIMIT: u32 = 10;
 
+// pair stats are also accumulated per day, period is the block time divided by this
+pub const PAIR_STATS_PERIOD: u64 = 86400;
+
+// candles kept per interval of an order book
+pub const CANDLE_RETENTION: u64 = 1000;
+
 pub fn init_last_order_id(storage: &mut dyn Storage) -> StdResult<()> {
     singleton(storage, KEY_LAST_ORDER_ID).save(&0u64)
 }
@@ -24,6 +33,10 @@ pub fn read_last_order_id(storage: &dyn Storage) -> StdResult<u64> {
     singleton_read(storage, KEY_LAST_ORDER_ID).load()
 }
 
+pub fn store_last_order_id(storage: &mut dyn Storage, last_order_id: u64) -> StdResult<()> {
+    singleton(storage, KEY_LAST_ORDER_ID).save(&last_order_id)
+}
+
 pub fn store_config(storage: &mut dyn Storage, config: &ContractInfo) -> StdResult<()> {
     singleton(storage, CONTRACT_INFO).save(config)
 }
@@ -32,6 +45,32 @@ pub fn read_config(storage: &dyn Storage) -> StdResult<ContractInfo> {
     singleton_read(storage, CONTRACT_INFO).load()
 }
 
+pub fn store_paused(storage: &mut dyn Storage, paused: bool) -> StdResult<()> {
+    singleton(storage, KEY_PAUSED).save(&paused)
+}
+
+pub fn read_paused(storage: &dyn Storage) -> bool {
+    singleton_read(storage, KEY_PAUSED).load().unwrap_or(false)
+}
+
+pub fn store_allow_import_orders(storage: &mut dyn Storage, allowed: bool) -> StdResult<()> {
+    singleton(storage, KEY_ALLOW_IMPORT_ORDERS).save(&allowed)
+}
+
+pub fn read_allow_import_orders(storage: &dyn Storage) -> bool {
+    singleton_read(storage, KEY_ALLOW_IMPORT_ORDERS)
+        .load()
+        .unwrap_or(false)
+}
+
+pub fn store_registry(storage: &mut dyn Storage, registry: &CanonicalAddr) -> StdResult<()> {
+    singleton(storage, KEY_REGISTRY).save(registry)
+}
+
+pub fn read_registry(storage: &dyn Storage) -> StdResult<Option<CanonicalAddr>> {
+    singleton_read(storage, KEY_REGISTRY).may_load()
+}
+
 pub fn store_reward(
     storage: &mut dyn Storage,
     pair_key: &[u8],
@@ -49,6 +88,286 @@ pub fn read_reward(
     ReadonlyBucket::multilevel(storage, &[PREFIX_REWARD, pair_key]).load(address)
 }
 
+/// add matched stats to both the all time and the period stats of a pair
+pub fn update_pair_stats(
+    storage: &mut dyn Storage,
+    pair_key: &[u8],
+    period: u64,
+    stats: &PairStats,
+) -> StdResult<()> {
+    let mut total_stats = read_pair_stats(storage, pair_key, None)?;
+    total_stats.add(stats);
+    Bucket::new(storage, PREFIX_PAIR_STATS).save(pair_key, &total_stats)?;
+
+    let mut period_stats = read_pair_stats(storage, pair_key, Some(period))?;
+    period_stats.add(stats);
+    Bucket::multilevel(storage, &[PREFIX_PAIR_STATS_BY_PERIOD, pair_key])
+        .save(&period.to_be_bytes(), &period_stats)
+}
+
+/// return all time stats if period is None, pairs without any trade return empty stats
+pub fn read_pair_stats(
+    storage: &dyn Storage,
+    pair_key: &[u8],
+    period: Option<u64>,
+) -> StdResult<PairStats> {
+    let stats = match period {
+        Some(period) => {
+            ReadonlyBucket::multilevel(storage, &[PREFIX_PAIR_STATS_BY_PERIOD, pair_key])
+                .may_load(&period.to_be_bytes())?
+        }
+        None => ReadonlyBucket::new(storage, PREFIX_PAIR_STATS).may_load(pair_key)?,
+    };
+    Ok(stats.unwrap_or_default())
+}
+
+/// add a matching to the candles of every interval containing the block time, opening a candle
+/// drops the ones of the interval older than the retention
+pub fn update_candles(
+    storage: &mut dyn Storage,
+    pair_key: &[u8],
+    time: u64,
+    candle: &Candle,
+) -> StdResult<()> {
+    for interval in CandleInterval::ALL {
+        let seconds = interval.seconds();
+        let start_time = time - time % seconds;
+        let mut bucket =
+            Bucket::<Candle>::multilevel(storage, &[PREFIX_CANDLE, pair_key, interval.as_bytes()]);
+
+        match bucket.may_load(&start_time.to_be_bytes())? {
+            Some(mut current) => {
+                current.add(candle);
+                bucket.save(&start_time.to_be_bytes(), &current)?;
+            }
+            None => {
+                bucket.save(&start_time.to_be_bytes(), candle)?;
+
+                let min_start_time = start_time.saturating_sub((CANDLE_RETENTION - 1) * seconds);
+                let expired_keys = bucket
+                    .range(
+                        None,
+                        Some(&min_start_time.to_be_bytes()),
+                        OrderBy::Ascending,
+                    )
+                    .map(|item| item.map(|item| item.0))
+                    .collect::<StdResult<Vec<Vec<u8>>>>()?;
+                for key in expired_keys {
+                    bucket.remove(&key);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// candles of the interval with start time in [start, end), by start time
+pub fn read_candles(
+    storage: &dyn Storage,
+    pair_key: &[u8],
+    interval: CandleInterval,
+    start: Option<u64>,
+    end: Option<u64>,
+) -> StdResult<Vec<(u64, Candle)>> {
+    let start = start.map(|start| start.to_be_bytes());
+    let end = end.map(|end| end.to_be_bytes());
+    ReadonlyBucket::<Candle>::multilevel(storage, &[PREFIX_CANDLE, pair_key, interval.as_bytes()])
+        .range(
+            start.as_ref().map(|start| start.as_slice()),
+            end.as_ref().map(|end| end.as_slice()),
+            OrderBy::Ascending,
+        )
+        .map(|item| {
+            let (key, candle) = item?;
+            let start_time = key
+                .try_into()
+                .map(u64::from_be_bytes)
+                .map_err(|_| StdError::generic_err("invalid candle key"))?;
+            Ok((start_time, candle))
+        })
+        .collect()
+}
+
+/// add rebate to the claimable balance of a maker, asset_key is the key of the rebate asset info
+pub fn increase_maker_rebate(
+    storage: &mut dyn Storage,
+    maker: &CanonicalAddr,
+    asset_key: &[u8],
+    rebate: &Asset,
+) -> StdResult<()> {
+    let mut bucket = Bucket::<Asset>::multilevel(storage, &[PREFIX_MAKER_REBATE, maker.as_slice()]);
+    let mut total_rebate = bucket.may_load(asset_key)?.unwrap_or(Asset {
+        info: rebate.info.clone(),
+        amount: Uint128::zero(),
+    });
+    total_rebate.amount += rebate.amount;
+    bucket.save(asset_key, &total_rebate)
+}
+
+pub fn read_maker_rebates(storage: &dyn Storage, maker: &CanonicalAddr) -> StdResult<Vec<Asset>> {
+    ReadonlyBucket::multilevel(storage, &[PREFIX_MAKER_REBATE, maker.as_slice()])
+        .range(None, None, OrderBy::Ascending)
+        .map(|item| item.map(|item| item.1))
+        .collect()
+}
+
+pub fn increase_incentive_points(
+    storage: &mut dyn Storage,
+    maker: &CanonicalAddr,
+    points: Uint128,
+) -> StdResult<()> {
+    let mut bucket = Bucket::<Uint128>::new(storage, PREFIX_INCENTIVE_POINTS);
+    let total_points = bucket.may_load(maker.as_slice())?.unwrap_or_default() + points;
+    bucket.save(maker.as_slice(), &total_points)
+}
+
+pub fn read_incentive_points(storage: &dyn Storage, maker: &CanonicalAddr) -> StdResult<Uint128> {
+    ReadonlyBucket::new(storage, PREFIX_INCENTIVE_POINTS)
+        .may_load(maker.as_slice())
+        .map(|points| points.unwrap_or_default())
+}
+
+pub fn remove_incentive_points(storage: &mut dyn Storage, maker: &CanonicalAddr) {
+    Bucket::<Uint128>::new(storage, PREFIX_INCENTIVE_POINTS).remove(maker.as_slice())
+}
+
+pub fn remove_maker_rebates(storage: &mut dyn Storage, maker: &CanonicalAddr) -> StdResult<()> {
+    let mut bucket = Bucket::<Asset>::multilevel(storage, &[PREFIX_MAKER_REBATE, maker.as_slice()]);
+    let keys = bucket
+        .range(None, None, OrderBy::Ascending)
+        .map(|item| item.map(|item| item.0))
+        .collect::<StdResult<Vec<Vec<u8>>>>()?;
+    for key in keys {
+        bucket.remove(&key);
+    }
+    Ok(())
+}
+
+/// trader payouts sent as submessages by the last matching, the reply id is the index of the payout
+pub fn store_pending_payouts(
+    storage: &mut dyn Storage,
+    payouts: Vec<(CanonicalAddr, Asset)>,
+) -> StdResult<()> {
+    singleton(storage, KEY_PENDING_PAYOUTS).save(&payouts)
+}
+
+pub fn read_pending_payout(
+    storage: &dyn Storage,
+    payout_id: u64,
+) -> StdResult<(CanonicalAddr, Asset)> {
+    let payouts: Vec<(CanonicalAddr, Asset)> =
+        singleton_read(storage, KEY_PENDING_PAYOUTS).load()?;
+    payouts
+        .get(payout_id as usize)
+        .cloned()
+        .ok_or_else(|| StdError::not_found(format!("pending payout {}", payout_id)))
+}
+
+/// add a failed payout to the escrow of a trader, asset_key is the key of the escrowed asset info
+pub fn increase_escrow(
+    storage: &mut dyn Storage,
+    trader: &CanonicalAddr,
+    asset_key: &[u8],
+    asset: &Asset,
+) -> StdResult<()> {
+    let mut bucket = Bucket::<Asset>::multilevel(storage, &[PREFIX_ESCROW, trader.as_slice()]);
+    let mut total_escrow = bucket.may_load(asset_key)?.unwrap_or(Asset {
+        info: asset.info.clone(),
+        amount: Uint128::zero(),
+    });
+    total_escrow.amount += asset.amount;
+    bucket.save(asset_key, &total_escrow)
+}
+
+pub fn read_escrow(storage: &dyn Storage, trader: &CanonicalAddr) -> StdResult<Vec<Asset>> {
+    ReadonlyBucket::multilevel(storage, &[PREFIX_ESCROW, trader.as_slice()])
+        .range(None, None, OrderBy::Ascending)
+        .map(|item| item.map(|item| item.1))
+        .collect()
+}
+
+pub fn remove_escrow(storage: &mut dyn Storage, trader: &CanonicalAddr) -> StdResult<()> {
+    let mut bucket = Bucket::<Asset>::multilevel(storage, &[PREFIX_ESCROW, trader.as_slice()]);
+    let keys = bucket
+        .range(None, None, OrderBy::Ascending)
+        .map(|item| item.map(|item| item.0))
+        .collect::<StdResult<Vec<Vec<u8>>>>()?;
+    for key in keys {
+        bucket.remove(&key);
+    }
+    Ok(())
+}
+
+pub fn store_order_signer(
+    storage: &mut dyn Storage,
+    bidder: &CanonicalAddr,
+    pubkey: Option<&Binary>,
+) -> StdResult<()> {
+    let mut bucket = Bucket::<Binary>::new(storage, PREFIX_ORDER_SIGNER);
+    match pubkey {
+        Some(pubkey) => bucket.save(bidder.as_slice(), pubkey),
+        None => {
+            bucket.remove(bidder.as_slice());
+            Ok(())
+        }
+    }
+}
+
+pub fn read_order_signer(
+    storage: &dyn Storage,
+    bidder: &CanonicalAddr,
+) -> StdResult<Option<Binary>> {
+    ReadonlyBucket::new(storage, PREFIX_ORDER_SIGNER).may_load(bidder.as_slice())
+}
+
+/// roles granted to the address, no roles removes the address
+pub fn store_roles(
+    storage: &mut dyn Storage,
+    addr: &CanonicalAddr,
+    roles: &[Role],
+) -> StdResult<()> {
+    let mut bucket = Bucket::<Vec<Role>>::new(storage, PREFIX_ROLES);
+    if roles.is_empty() {
+        bucket.remove(addr.as_slice());
+        Ok(())
+    } else {
+        bucket.save(addr.as_slice(), &roles.to_vec())
+    }
+}
+
+pub fn read_roles(storage: &dyn Storage, addr: &CanonicalAddr) -> StdResult<Vec<Role>> {
+    ReadonlyBucket::new(storage, PREFIX_ROLES)
+        .may_load(addr.as_slice())
+        .map(|roles| roles.unwrap_or_default())
+}
+
+pub fn read_role_holders(
+    storage: &dyn Storage,
+    start_after: Option<CanonicalAddr>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(CanonicalAddr, Vec<Role>)>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = calc_range_start(start_after.map(|addr| addr.to_vec()));
+    ReadonlyBucket::new(storage, PREFIX_ROLES)
+        .range(start.as_deref(), None, OrderBy::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(addr, roles)| (CanonicalAddr::from(addr), roles)))
+        .collect()
+}
+
+/// nonce the next order permit of the bidder must be signed with
+pub fn read_order_nonce(storage: &dyn Storage, bidder: &CanonicalAddr) -> StdResult<u64> {
+    ReadonlyBucket::new(storage, PREFIX_ORDER_NONCE)
+        .may_load(bidder.as_slice())
+        .map(|nonce| nonce.unwrap_or_default())
+}
+
+pub fn increase_order_nonce(storage: &mut dyn Storage, bidder: &CanonicalAddr) -> StdResult<u64> {
+    let nonce = read_order_nonce(storage, bidder)? + 1;
+    Bucket::new(storage, PREFIX_ORDER_NONCE).save(bidder.as_slice(), &nonce)?;
+    Ok(nonce)
+}
+
 pub fn store_orderbook(
     storage: &mut dyn Storage,
     pair_key: &[u8],
@@ -81,6 +400,13 @@ pub fn read_orderbooks(
         .collect()
 }
 
+pub fn read_all_orderbooks(storage: &dyn Storage) -> StdResult<Vec<OrderBook>> {
+    ReadonlyBucket::new(storage, PREFIX_ORDER_BOOK)
+        .range(None, None, OrderBy::Ascending)
+        .map(|item| item.map(|item| item.1))
+        .collect()
+}
+
 pub fn remove_orderbook<'a>(storage: &'a mut dyn Storage, pair_key: &[u8]) {
     Bucket::<'a, OrderBook>::new(storage, PREFIX_ORDER_BOOK).remove(pair_key)
 }
@@ -89,10 +415,38 @@ pub fn store_order(
     storage: &mut dyn Storage,
     pair_key: &[u8],
     order: &Order,
+    decimals_diff: i32,
     inserted: bool,
 ) -> StdResult<u64> {
     let order_id_key = &order.order_id.to_be_bytes();
-    let price_key = order.get_price().atomics().to_be_bytes();
+    let price_key = order.get_price(decimals_diff)?.atomics().to_be_bytes();
+
+    // replace the remaining base amount of the previous order state in the tick volume
+    let prev_order = ReadonlyBucket::<Order>::multilevel(storage, &[PREFIX_ORDER, pair_key])
+        .may_load(order_id_key)?;
+    let prev_base_amount = prev_order
+        .as_ref()
+        .map(|prev_order| prev_order.get_remaining_base_amount())
+        .unwrap_or_default();
+    let tick_volume_namespaces = &[PREFIX_TICK_VOLUME, pair_key, order.direction.as_bytes()];
+    let tick_volume = ReadonlyBucket::<Uint128>::multilevel(storage, tick_volume_namespaces)
+        .may_load(&price_key)?
+        .unwrap_or_default()
+        .saturating_sub(prev_base_amount)
+        + order.get_remaining_base_amount();
+    Bucket::multilevel(storage, tick_volume_namespaces).save(&price_key, &tick_volume)?;
+
+    // likewise replace the remaining offer amount in the total escrow of the side
+    let prev_offer_amount = prev_order
+        .map(|prev_order| prev_order.get_remaining_offer_amount())
+        .unwrap_or_default();
+    update_order_escrow(
+        storage,
+        pair_key,
+        order.direction,
+        prev_offer_amount,
+        order.get_remaining_offer_amount(),
+    )?;
 
     Bucket::multilevel(storage, &[PREFIX_ORDER, pair_key]).save(order_id_key, order)?;
 
@@ -137,14 +491,89 @@ pub fn store_order(
     )
     .save(order_id_key, &order.direction)?;
 
+    if inserted {
+        if let Some(client_id) = &order.client_id {
+            Bucket::multilevel(
+                storage,
+                &[PREFIX_ORDER_BY_CLIENT_ID, order.bidder_addr.as_slice()],
+            )
+            .save(client_id.as_bytes(), &(pair_key.to_vec(), order.order_id))?;
+        }
+    }
+
     Ok(total_tick_orders)
 }
 
-pub fn remove_order(storage: &mut dyn Storage, pair_key: &[u8], order: &Order) -> StdResult<u64> {
+pub fn store_order_created_at(
+    storage: &mut dyn Storage,
+    pair_key: &[u8],
+    order_id: u64,
+    created_at: u64,
+) -> StdResult<()> {
+    Bucket::multilevel(storage, &[PREFIX_ORDER_CREATED_AT, pair_key])
+        .save(&order_id.to_be_bytes(), &created_at)
+}
+
+/// orders submitted before the created time was tracked return None
+pub fn read_order_created_at(storage: &dyn Storage, pair_key: &[u8], order_id: u64) -> Option<u64> {
+    ReadonlyBucket::multilevel(storage, &[PREFIX_ORDER_CREATED_AT, pair_key])
+        .may_load(&order_id.to_be_bytes())
+        .unwrap_or_default()
+}
+
+pub fn store_order_created_height(
+    storage: &mut dyn Storage,
+    pair_key: &[u8],
+    order_id: u64,
+    created_height: u64,
+) -> StdResult<()> {
+    Bucket::multilevel(storage, &[PREFIX_ORDER_CREATED_HEIGHT, pair_key])
+        .save(&order_id.to_be_bytes(), &created_height)
+}
+
+/// orders submitted before the created height was tracked return None
+pub fn read_order_created_height(
+    storage: &dyn Storage,
+    pair_key: &[u8],
+    order_id: u64,
+) -> Option<u64> {
+    ReadonlyBucket::multilevel(storage, &[PREFIX_ORDER_CREATED_HEIGHT, pair_key])
+        .may_load(&order_id.to_be_bytes())
+        .unwrap_or_default()
+}
+
+pub fn remove_order(
+    storage: &mut dyn Storage,
+    pair_key: &[u8],
+    order: &Order,
+    decimals_diff: i32,
+) -> StdResult<u64> {
     let order_id_key = &order.order_id.to_be_bytes();
-    let price_key = order.get_price().atomics().to_be_bytes();
+    let price_key = order.get_price(decimals_diff)?.atomics().to_be_bytes();
+
+    // the stored order may not have the latest filled amounts
+    let stored_order = ReadonlyBucket::<Order>::multilevel(storage, &[PREFIX_ORDER, pair_key])
+        .may_load(order_id_key)?;
+    let stored_base_amount = stored_order
+        .as_ref()
+        .map(|stored_order| stored_order.get_remaining_base_amount())
+        .unwrap_or_default();
+
+    // the whole remaining offer leaves the escrow, either paid out or refunded
+    if let Some(stored_order) = stored_order {
+        update_order_escrow(
+            storage,
+            pair_key,
+            order.direction,
+            stored_order.get_remaining_offer_amount(),
+            Uint128::zero(),
+        )?;
+    }
 
     Bucket::<Order>::multilevel(storage, &[PREFIX_ORDER, pair_key]).remove(order_id_key);
+    Bucket::<u64>::multilevel(storage, &[PREFIX_ORDER_CREATED_AT, pair_key]).remove(order_id_key);
+    Bucket::<u64>::multilevel(storage, &[PREFIX_ORDER_CREATED_HEIGHT, pair_key])
+        .remove(order_id_key);
 
     // not found means total is 0
     let tick_namespaces = &[PREFIX_TICK, pair_key, order.direction.as_bytes()];
@@ -155,13 +584,22 @@ pub fn remove_order(storage: &mut dyn Storage, pair_key: &[u8], order: &Order) -
     // substract one order, if total is 0 mean not existed
     if total_tick_orders > 0 {
         total_tick_orders -= 1;
+        let tick_volume_namespaces = &[PREFIX_TICK_VOLUME, pair_key, order.direction.as_bytes()];
         if total_tick_orders > 0 {
             // save total orders for a tick
             Bucket::multilevel(storage, tick_namespaces)
                 .save(&price_key, &total_tick_orders)
                 .unwrap();
+
+            let tick_volume =
+                ReadonlyBucket::<Uint128>::multilevel(storage, tick_volume_namespaces)
+                    .may_load(&price_key)?
+                    .unwrap_or_default()
+                    .saturating_sub(stored_base_amount);
+            Bucket::multilevel(storage, tick_volume_namespaces).save(&price_key, &tick_volume)?;
         } else {
             Bucket::<u64>::multilevel(storage, tick_namespaces).remove(&price_key);
+            Bucket::<Uint128>::multilevel(storage, tick_volume_namespaces).remove(&price_key);
         }
     }
 
@@ -189,23 +627,97 @@ pub fn remove_order(storage: &mut dyn Storage, pair_key: &[u8], order: &Order) -
     )
     .remove(order_id_key);
 
+    if let Some(client_id) = &order.client_id {
+        Bucket::<(Vec<u8>, u64)>::multilevel(
+            storage,
+            &[PREFIX_ORDER_BY_CLIENT_ID, order.bidder_addr.as_slice()],
+        )
+        .remove(client_id.as_bytes());
+    }
+
     // return total orders belong to the tick
     Ok(total_tick_orders)
 }
 
+/// replace the previous remaining offer amount of an order with the new one in the total escrow
+fn update_order_escrow(
+    storage: &mut dyn Storage,
+    pair_key: &[u8],
+    direction: OrderDirection,
+    prev_amount: Uint128,
+    amount: Uint128,
+) -> StdResult<()> {
+    let total_escrow =
+        read_order_escrow(storage, pair_key, direction)?.saturating_sub(prev_amount) + amount;
+    Bucket::multilevel(storage, &[PREFIX_ORDER_ESCROW, pair_key])
+        .save(direction.as_bytes(), &total_escrow)
+}
+
+/// total remaining offer amount of the open orders in a side of the order book
+pub fn read_order_escrow(
+    storage: &dyn Storage,
+    pair_key: &[u8],
+    direction: OrderDirection,
+) -> StdResult<Uint128> {
+    Ok(
+        ReadonlyBucket::multilevel(storage, &[PREFIX_ORDER_ESCROW, pair_key])
+            .may_load(direction.as_bytes())?
+            .unwrap_or_default(),
+    )
+}
+
+/// recompute the total escrow of both sides from the stored orders of the order book
+pub fn rebuild_order_escrow(storage: &mut dyn Storage, pair_key: &[u8]) -> StdResult<()> {
+    let mut buy_escrow = Uint128::zero();
+    let mut sell_escrow = Uint128::zero();
+    for item in ReadonlyBucket::<Order>::multilevel(storage, &[PREFIX_ORDER, pair_key]).range(
+        None,
+        None,
+        OrderBy::Ascending,
+    ) {
+        let (_, order) = item?;
+        match order.direction {
+            OrderDirection::Buy => buy_escrow += order.get_remaining_offer_amount(),
+            OrderDirection::Sell => sell_escrow += order.get_remaining_offer_amount(),
+        }
+    }
+
+    let mut bucket = Bucket::multilevel(storage, &[PREFIX_ORDER_ESCROW, pair_key]);
+    bucket.save(OrderDirection::Buy.as_bytes(), &buy_escrow)?;
+    bucket.save(OrderDirection::Sell.as_bytes(), &sell_escrow)
+}
+
 pub fn read_order(storage: &dyn Storage, pair_key: &[u8], order_id: u64) -> StdResult<Order> {
     ReadonlyBucket::multilevel(storage, &[PREFIX_ORDER, pair_key]).load(&order_id.to_be_bytes())
 }
 
-/// read_orders_with_indexer: namespace is PREFIX + PAIR_KEY + INDEXER
-pub fn read_orders_with_indexer<T: Serialize + DeserializeOwned>(
+/// pair key and order id of the open order of a bidder with the client id
+pub fn read_order_by_client_id(
+    storage: &dyn Storage,
+    bidder_addr: &CanonicalAddr,
+    client_id: &str,
+) -> StdResult<Option<(Vec<u8>, u64)>> {
+    ReadonlyBucket::multilevel(
+        storage,
+        &[PREFIX_ORDER_BY_CLIENT_ID, bidder_addr.as_slice()],
+    )
+    .may_load(client_id.as_bytes())
+}
+
+/// read_orders_with_indexer: namespace is PREFIX + PAIR_KEY + INDEXER, filter is applied on the indexed value.
+/// Direction has its own index, so it only needs filtering on the price and bidder indexes
+pub fn read_orders_with_indexer<T, F>(
     storage: &dyn Storage,
     namespaces: &[&[u8]],
-    filter: Box<dyn Fn(&T) -> bool>,
+    filter: F,
     start_after: Option<u64>,
     limit: Option<u32>,
     order_by: Option<OrderBy>,
-) -> StdResult<Option<Vec<Order>>> {
+) -> StdResult<Option<Vec<Order>>>
+where
+    T: Serialize + DeserializeOwned,
+    F: Fn(&T) -> bool,
+{
     let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
     let start_after = start_after.map(|id| id.to_be_bytes().to_vec());
     let (start, end, order_by) = match order_by {
@@ -225,6 +737,42 @@ pub fn read_orders_with_indexer<T: Serialize + DeserializeOwned>(
         .collect()
 }
 
+/// count the open orders of a bidder in an order book, stop counting at limit
+pub fn count_bidder_orders(
+    storage: &dyn Storage,
+    pair_key: &[u8],
+    bidder_addr: &CanonicalAddr,
+    limit: usize,
+) -> usize {
+    ReadonlyBucket::<OrderDirection>::multilevel(
+        storage,
+        &[PREFIX_ORDER_BY_BIDDER, pair_key, bidder_addr.as_slice()],
+    )
+    .range(None, None, OrderBy::Ascending)
+    .take(limit)
+    .count()
+}
+
+/// read all orders of a direction at a price, ascending by order id until end (exclusive)
+pub fn read_tick_orders(
+    storage: &dyn Storage,
+    pair_key: &[u8],
+    price_key: &[u8],
+    direction: OrderDirection,
+    end: Option<&[u8]>,
+) -> StdResult<Vec<Order>> {
+    let position_indexer: ReadonlyBucket<OrderDirection> =
+        ReadonlyBucket::multilevel(storage, &[PREFIX_ORDER_BY_PRICE, pair_key, price_key]);
+    let order_bucket: ReadonlyBucket<Order> =
+        ReadonlyBucket::multilevel(storage, &[PREFIX_ORDER, pair_key]);
+
+    position_indexer
+        .range(None, end, OrderBy::Ascending)
+        .filter(|item| item.as_ref().map_or(true, |(_, item)| direction.eq(item)))
+        .map(|item| order_bucket.load(&item?.0))
+        .collect()
+}
+
 pub fn read_orders(
     storage: &dyn Storage,
     pair_key: &[u8],
@@ -251,11 +799,29 @@ pub fn read_orders(
 
 static KEY_LAST_ORDER_ID: &[u8] = b"last_order_id"; // should use big int? guess no need
 static CONTRACT_INFO: &[u8] = b"contract_info"; // contract info
+static KEY_PAUSED: &[u8] = b"paused"; // user operations are paused by admin or guardian
+static KEY_ALLOW_IMPORT_ORDERS: &[u8] = b"allow_import_orders"; // set on migration to import exported orders
+static KEY_REGISTRY: &[u8] = b"registry"; // registry providing the decimals of the order book assets
 static PREFIX_ORDER_BOOK: &[u8] = b"order_book"; // store config for an order book like min ask amount and min sell amount
 static PREFIX_ORDER: &[u8] = b"order"; // this is orderbook
+static PREFIX_ORDER_CREATED_AT: &[u8] = b"order_created_at"; // block time in seconds when an order is submitted
+static PREFIX_ORDER_CREATED_HEIGHT: &[u8] = b"order_created_height"; // block height when an order is submitted
 static PREFIX_REWARD: &[u8] = b"reward_wallet"; // executor that running matching engine for orderbook pair
+static PREFIX_MAKER_REBATE: &[u8] = b"maker_rebate"; // claimable rebates of a maker, by asset
+static KEY_PENDING_PAYOUTS: &[u8] = b"pending_payouts"; // trader payouts of the matching being settled
+static PREFIX_ESCROW: &[u8] = b"escrow"; // trader payouts that failed to transfer, by asset
+static PREFIX_ORDER_ESCROW: &[u8] = b"order_escrow"; // remaining offer amount of the open orders, by order book side
+static PREFIX_INCENTIVE_POINTS: &[u8] = b"incentive_points"; // claimable incentive points of a maker
+static PREFIX_ORDER_SIGNER: &[u8] = b"order_signer"; // public key signing the order permits of a bidder
+static PREFIX_ROLES: &[u8] = b"roles"; // roles granted by a super admin, by address
+static PREFIX_ORDER_NONCE: &[u8] = b"order_nonce"; // next nonce of the order permits of a bidder
+static PREFIX_PAIR_STATS: &[u8] = b"pair_stats"; // all time matched volume and fees of an orderbook pair
+static PREFIX_PAIR_STATS_BY_PERIOD: &[u8] = b"pair_stats_by_period"; // daily matched volume and fees of an orderbook pair
+static PREFIX_CANDLE: &[u8] = b"candle"; // matched prices and volume of an orderbook pair, by interval and start time
 
 pub static PREFIX_ORDER_BY_BIDDER: &[u8] = b"order_by_bidder"; // order from a bidder
 pub static PREFIX_ORDER_BY_PRICE: &[u8] = b"order_by_price"; // this where orders belong to tick
 pub static PREFIX_ORDER_BY_DIRECTION: &[u8] = b"order_by_direction"; // order from the direction
+pub static PREFIX_ORDER_BY_CLIENT_ID: &[u8] = b"order_by_client_id"; // open order of a bidder by client id
 pub static PREFIX_TICK: &[u8] = b"tick"; // this is tick with value is the total orders
+pub static PREFIX_TICK_VOLUME: &[u8] = b"tick_volume"; // remaining base amount of the orders in a tick
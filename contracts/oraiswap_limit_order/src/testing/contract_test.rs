@@ -1,16 +1,43 @@
+use std::cell::Cell;
 use std::str::FromStr;
 
-use cosmwasm_std::{to_binary, Addr, Coin, Decimal, StdError, Uint128};
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_dependencies_with_balance, mock_env, mock_info,
+};
+use cosmwasm_std::{
+    attr, coin, coins, from_binary, from_slice, to_binary, Addr, BankMsg, Binary, Coin,
+    ContractResult, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo, Reply, ReplyOn, Response,
+    StdError, StdResult, SubMsg, SubMsgResult, SystemResult, Uint128, WasmMsg, WasmQuery,
+};
 use oraiswap::create_entry_points_testing;
+use oraiswap::cw_multi_test::ContractWrapper;
+use oraiswap::error::ContractError;
+use oraiswap::querier::query_token_balance;
 use oraiswap::testing::{AttributeUtil, MockApp, ATOM_DENOM};
 
 use oraiswap::asset::{Asset, AssetInfo, ORAI_DENOM};
+use oraiswap::converter::{ConvertInfoResponse, QueryMsg as ConverterQueryMsg, TokenRatio};
 use oraiswap::limit_order::{
-    Cw20HookMsg, ExecuteMsg, InstantiateMsg, LastOrderIdResponse, OrderBookMatchableResponse,
-    OrderBookResponse, OrderBooksResponse, OrderDirection, OrderFilter, OrderResponse, OrderStatus,
-    OrdersResponse, QueryMsg, TicksResponse,
+    CandleInterval, CandleResponse, CandlesResponse, ContractInfoResponse, Cw20HookMsg,
+    DirectionAndAssets, EscrowResponse, EventMode, ExecuteMsg, ExportOrdersResponse,
+    IncentivePointsResponse, InstantiateMsg, LastOrderIdResponse, MakerRebatesResponse,
+    MatchOrdersDryRunResponse, MatchingRulesResponse, MigrateMsg, OrderBookMatchableResponse,
+    OrderBookResponse, OrderBookSettings, OrderBooksResponse, OrderDirection, OrderFilter,
+    OrderPermit, OrderPermitPayload, OrderPositionResponse, OrderRecord, OrderResponse,
+    OrderSignerResponse, OrderStatus, OrdersResponse, PairSequenceResponse, PairStatsResponse,
+    PriceLevelResponse, QueryMsg, Role, RoleHolder, RolesResponse, TicksResponse,
+    TotalEscrowResponse,
+};
+
+use oraiswap::oracle::{ConvertAmountResponse, OracleExchangeQuery};
+use oraiswap::pair::PoolResponse;
+use oraiswap::referral::{
+    ExecuteMsg as ReferralExecuteMsg, QueryMsg as ReferralQueryMsg, ReferralResponse,
+    ReferralReward,
 };
+use oraiswap::rewarder::ExecuteMsg as RewarderExecuteMsg;
 
+use crate::contract::{execute, instantiate, migrate, query, reply};
 use crate::jsonstr;
 const USDT_DENOM: &str = "usdt";
 
@@ -62,7 +89,9 @@ fn submit_order() {
         reward_address: None,
         spread_address:None,
     };
-    let code_id = app.upload(Box::new(create_entry_points_testing!(crate)));
+    let code_id = app.upload(Box::new(
+        create_entry_points_testing!(crate).with_reply(crate::contract::reply),
+    ));
     let limit_order_addr = app
         .instantiate(
             code_id,
@@ -128,6 +157,7 @@ fn submit_order() {
                 amount: Uint128::from(1000000u128),
             },
         ],
+        client_id: None,
     };
 
     // offer asset is null
@@ -155,6 +185,7 @@ fn submit_order() {
                 amount: Uint128::from(50u128),
             },
         ],
+        client_id: None,
     };
 
     // Offer ammount 5 usdt (min 10 usdt) is too low
@@ -186,6 +217,7 @@ fn submit_order() {
                 amount: Uint128::from(150u128),
             },
         ],
+        client_id: None,
     };
 
     let _ = app
@@ -216,6 +248,7 @@ fn submit_order() {
                 amount: Uint128::from(0u128),
             },
         ],
+        client_id: None,
     };
 
     // Asset must not be zero
@@ -246,6 +279,7 @@ fn submit_order() {
                 amount: Uint128::from(12345678u128),
             },
         ],
+        client_id: None,
     };
 
     // paid 11111111 usdt to get 12345678 orai
@@ -278,6 +312,7 @@ fn submit_order() {
                 amount: Uint128::from(70000u128),
             },
         ],
+        client_id: None,
     };
 
     // paid 70000 orai to get 20000 usdt
@@ -313,6 +348,7 @@ fn submit_order() {
         filled_ask_amount: Uint128::zero(),
         direction: OrderDirection::Buy,
         status: OrderStatus::Open,
+        client_id: None,
     };
 
     let order_2 = OrderResponse {
@@ -334,6 +370,7 @@ fn submit_order() {
         filled_ask_amount: Uint128::zero(),
         direction: OrderDirection::Buy,
         status: OrderStatus::Open,
+        client_id: None,
     };
 
     let order_3 = OrderResponse {
@@ -355,6 +392,7 @@ fn submit_order() {
         filled_ask_amount: Uint128::zero(),
         direction: OrderDirection::Sell,
         status: OrderStatus::Open,
+        client_id: None,
     };
 
     assert_eq!(
@@ -451,6 +489,7 @@ fn submit_order() {
                     amount: Uint128::from(1212121u128),
                 },
             ],
+            client_id: None,
         })
         .unwrap(),
     };
@@ -475,6 +514,7 @@ fn submit_order() {
                 amount: Uint128::from(1234567u128),
             },
         ],
+        client_id: None,
     };
 
     // paid 1234567 orai to get 1111111 token
@@ -509,6 +549,7 @@ fn submit_order() {
         filled_ask_amount: Uint128::zero(),
         direction: OrderDirection::Buy,
         status: OrderStatus::Open,
+        client_id: None,
     };
 
     let order_5 = OrderResponse {
@@ -530,6 +571,7 @@ fn submit_order() {
         filled_ask_amount: Uint128::zero(),
         direction: OrderDirection::Sell,
         status: OrderStatus::Open,
+        client_id: None,
     };
 
     assert_eq!(
@@ -615,7 +657,9 @@ fn cancel_order_native_token() {
         reward_address: None,
         spread_address:None,
     };
-    let code_id = app.upload(Box::new(create_entry_points_testing!(crate)));
+    let code_id = app.upload(Box::new(
+        create_entry_points_testing!(crate).with_reply(crate::contract::reply),
+    ));
     let limit_order_addr = app
         .instantiate(
             code_id,
@@ -660,6 +704,7 @@ fn cancel_order_native_token() {
                 amount: Uint128::from(6666666u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -690,6 +735,7 @@ fn cancel_order_native_token() {
                 amount: Uint128::from(6666666u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -839,6 +885,7 @@ fn cancel_order_native_token() {
                 amount: Uint128::from(1000000u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -954,7 +1001,9 @@ fn cancel_order_token() {
         reward_address: None,
         spread_address:None,
     };
-    let code_id = app.upload(Box::new(create_entry_points_testing!(crate)));
+    let code_id = app.upload(Box::new(
+        create_entry_points_testing!(crate).with_reply(crate::contract::reply),
+    ));
     let limit_order_addr = app
         .instantiate(
             code_id,
@@ -1020,6 +1069,7 @@ fn cancel_order_token() {
                     amount: Uint128::from(4567890u128),
                 },
             ],
+            client_id: None,
         })
         .unwrap(),
     };
@@ -1043,6 +1093,7 @@ fn cancel_order_token() {
                     amount: Uint128::from(3333335u128),
                 },
             ],
+            client_id: None,
         })
         .unwrap(),
     };
@@ -1066,6 +1117,7 @@ fn cancel_order_token() {
                     amount: Uint128::from(3333335u128),
                 },
             ],
+            client_id: None,
         })
         .unwrap(),
     };
@@ -1116,6 +1168,7 @@ fn cancel_order_token() {
                     amount: Uint128::from(2334455u128),
                 },
             ],
+            client_id: None,
         })
         .unwrap(),
     };
@@ -1272,7 +1325,9 @@ fn execute_pair_native_token() {
         reward_address: None,
         spread_address:None,
     };
-    let code_id = app.upload(Box::new(create_entry_points_testing!(crate)));
+    let code_id = app.upload(Box::new(
+        create_entry_points_testing!(crate).with_reply(crate::contract::reply),
+    ));
     let limit_order_addr = app
         .instantiate(
             code_id,
@@ -1319,6 +1374,7 @@ fn execute_pair_native_token() {
                 amount: Uint128::from(10000u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -1350,6 +1406,7 @@ fn execute_pair_native_token() {
                 amount: Uint128::from(10000u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -1381,6 +1438,7 @@ fn execute_pair_native_token() {
                 amount: Uint128::from(14000u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -1412,6 +1470,7 @@ fn execute_pair_native_token() {
                 amount: Uint128::from(10000u128),
             },
         ],
+        client_id: None,
     };
 
     // offer usdt, ask for orai
@@ -1444,6 +1503,7 @@ fn execute_pair_native_token() {
                 amount: Uint128::from(8800u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -1475,6 +1535,7 @@ fn execute_pair_native_token() {
                 amount: Uint128::from(14000u128),
             },
         ],
+        client_id: None,
     };
 
     // offer orai, ask for atom
@@ -1507,6 +1568,7 @@ fn execute_pair_native_token() {
                 amount: Uint128::from(2000u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -1538,6 +1600,7 @@ fn execute_pair_native_token() {
                 amount: Uint128::from(1200u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -1569,6 +1632,7 @@ fn execute_pair_native_token() {
                 amount: Uint128::from(10000u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -1600,6 +1664,7 @@ fn execute_pair_native_token() {
                 amount: Uint128::from(6789u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -1631,6 +1696,7 @@ fn execute_pair_native_token() {
                 amount: Uint128::from(1000u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -1662,6 +1728,7 @@ fn execute_pair_native_token() {
                 amount: Uint128::from(1000u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -1693,6 +1760,7 @@ fn execute_pair_native_token() {
                 amount: Uint128::from(1000u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -1724,6 +1792,7 @@ fn execute_pair_native_token() {
                 amount: Uint128::from(1000u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -1755,6 +1824,7 @@ fn execute_pair_native_token() {
                 amount: Uint128::from(10000u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -1786,6 +1856,7 @@ fn execute_pair_native_token() {
                 amount: Uint128::from(9700u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -1817,6 +1888,7 @@ fn execute_pair_native_token() {
                 amount: Uint128::from(13000u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -1848,6 +1920,7 @@ fn execute_pair_native_token() {
                 amount: Uint128::from(5000u128),
             },
         ],
+        client_id: None,
     };
 
     // offer usdt, ask for orai
@@ -1880,6 +1953,7 @@ fn execute_pair_native_token() {
                 amount: Uint128::from(4400u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -1911,6 +1985,7 @@ fn execute_pair_native_token() {
                 amount: Uint128::from(7000u128),
             },
         ],
+        client_id: None,
     };
 
     // offer orai, ask for atom
@@ -1943,6 +2018,7 @@ fn execute_pair_native_token() {
                 amount: Uint128::from(2000u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -1974,6 +2050,7 @@ fn execute_pair_native_token() {
                 amount: Uint128::from(1200u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -2005,6 +2082,7 @@ fn execute_pair_native_token() {
                 amount: Uint128::from(10000u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -2036,6 +2114,7 @@ fn execute_pair_native_token() {
                 amount: Uint128::from(6789u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -2067,6 +2146,7 @@ fn execute_pair_native_token() {
                 amount: Uint128::from(1000u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -2098,6 +2178,7 @@ fn execute_pair_native_token() {
                 amount: Uint128::from(1000u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -2129,6 +2210,7 @@ fn execute_pair_native_token() {
                 amount: Uint128::from(1000u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -2160,6 +2242,7 @@ fn execute_pair_native_token() {
                 amount: Uint128::from(1000u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -2191,6 +2274,7 @@ fn execute_pair_native_token() {
                 amount: Uint128::from(2000u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -2222,6 +2306,7 @@ fn execute_pair_native_token() {
                 amount: Uint128::from(1200u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -2253,6 +2338,7 @@ fn execute_pair_native_token() {
                 amount: Uint128::from(1200u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -2487,7 +2573,9 @@ fn execute_pair_cw20_token() {
         reward_address: None,
         spread_address:None,
     };
-    let code_id = app.upload(Box::new(create_entry_points_testing!(crate)));
+    let code_id = app.upload(Box::new(
+        create_entry_points_testing!(crate).with_reply(crate::contract::reply),
+    ));
     let limit_order_addr = app
         .instantiate(
             code_id,
@@ -2534,6 +2622,7 @@ fn execute_pair_cw20_token() {
                 amount: Uint128::from(10000u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -2565,6 +2654,7 @@ fn execute_pair_cw20_token() {
                 amount: Uint128::from(9700u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -2599,6 +2689,7 @@ fn execute_pair_cw20_token() {
                     amount: Uint128::from(13000u128),
                 },
             ],
+            client_id: None,
         })
         .unwrap(),
     };
@@ -2630,6 +2721,7 @@ fn execute_pair_cw20_token() {
                     amount: Uint128::from(5000u128),
                 },
             ],
+            client_id: None,
         })
         .unwrap(),
     };
@@ -2664,6 +2756,7 @@ fn execute_pair_cw20_token() {
                     amount: Uint128::from(4400u128),
                 },
             ],
+            client_id: None,
         })
         .unwrap(),
     };
@@ -2697,6 +2790,7 @@ fn execute_pair_cw20_token() {
                     amount: Uint128::from(7000u128),
                 },
             ],
+            client_id: None,
         })
         .unwrap(),
     };
@@ -2728,6 +2822,7 @@ fn execute_pair_cw20_token() {
                 amount: Uint128::from(2000u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -2762,6 +2857,7 @@ fn execute_pair_cw20_token() {
                     amount: Uint128::from(1200u128),
                 },
             ],
+            client_id: None,
         })
         .unwrap(),
     };
@@ -2795,6 +2891,7 @@ fn execute_pair_cw20_token() {
                     amount: Uint128::from(10000u128),
                 },
             ],
+            client_id: None,
         })
         .unwrap(),
     };
@@ -2825,6 +2922,7 @@ fn execute_pair_cw20_token() {
                 amount: Uint128::from(6789u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -2859,6 +2957,7 @@ fn execute_pair_cw20_token() {
                     amount: Uint128::from(1000u128),
                 },
             ],
+            client_id: None,
         })
         .unwrap(),
     };
@@ -2892,6 +2991,7 @@ fn execute_pair_cw20_token() {
                     amount: Uint128::from(1000u128),
                 },
             ],
+            client_id: None,
         })
         .unwrap(),
     };
@@ -2922,6 +3022,7 @@ fn execute_pair_cw20_token() {
                 amount: Uint128::from(1000u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -2953,6 +3054,7 @@ fn execute_pair_cw20_token() {
                 amount: Uint128::from(1000u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -2984,6 +3086,7 @@ fn execute_pair_cw20_token() {
                 amount: Uint128::from(10000u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -3015,6 +3118,7 @@ fn execute_pair_cw20_token() {
                 amount: Uint128::from(9700u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -3049,6 +3153,7 @@ fn execute_pair_cw20_token() {
                     amount: Uint128::from(13000u128),
                 },
             ],
+            client_id: None,
         })
         .unwrap(),
     };
@@ -3082,6 +3187,7 @@ fn execute_pair_cw20_token() {
                     amount: Uint128::from(5000u128),
                 },
             ],
+            client_id: None,
         })
         .unwrap(),
     };
@@ -3116,6 +3222,7 @@ fn execute_pair_cw20_token() {
                     amount: Uint128::from(4400u128),
                 },
             ],
+            client_id: None,
         })
         .unwrap(),
     };
@@ -3149,6 +3256,7 @@ fn execute_pair_cw20_token() {
                     amount: Uint128::from(7000u128),
                 },
             ],
+            client_id: None,
         })
         .unwrap(),
     };
@@ -3180,6 +3288,7 @@ fn execute_pair_cw20_token() {
                 amount: Uint128::from(2000u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -3214,6 +3323,7 @@ fn execute_pair_cw20_token() {
                     amount: Uint128::from(1200u128),
                 },
             ],
+            client_id: None,
         })
         .unwrap(),
     };
@@ -3247,6 +3357,7 @@ fn execute_pair_cw20_token() {
                     amount: Uint128::from(10000u128),
                 },
             ],
+            client_id: None,
         })
         .unwrap(),
     };
@@ -3277,6 +3388,7 @@ fn execute_pair_cw20_token() {
                 amount: Uint128::from(6789u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -3311,6 +3423,7 @@ fn execute_pair_cw20_token() {
                     amount: Uint128::from(1000u128),
                 },
             ],
+            client_id: None,
         })
         .unwrap(),
     };
@@ -3344,6 +3457,7 @@ fn execute_pair_cw20_token() {
                     amount: Uint128::from(1000u128),
                 },
             ],
+            client_id: None,
         })
         .unwrap(),
     };
@@ -3374,6 +3488,7 @@ fn execute_pair_cw20_token() {
                 amount: Uint128::from(1000u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -3405,6 +3520,7 @@ fn execute_pair_cw20_token() {
                 amount: Uint128::from(1000u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -3436,6 +3552,7 @@ fn execute_pair_cw20_token() {
                 amount: Uint128::from(2000u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -3470,6 +3587,7 @@ fn execute_pair_cw20_token() {
                     amount: Uint128::from(1200u128),
                 },
             ],
+            client_id: None,
         })
         .unwrap(),
     };
@@ -3503,6 +3621,7 @@ fn execute_pair_cw20_token() {
                     amount: Uint128::from(1200u128),
                 },
             ],
+            client_id: None,
         })
         .unwrap(),
     };
@@ -3668,7 +3787,9 @@ fn spread_test() {
         reward_address: None,
         spread_address:None,
     };
-    let code_id = app.upload(Box::new(create_entry_points_testing!(crate)));
+    let code_id = app.upload(Box::new(
+        create_entry_points_testing!(crate).with_reply(crate::contract::reply),
+    ));
     let limit_order_addr = app
         .instantiate(
             code_id,
@@ -3715,6 +3836,7 @@ fn spread_test() {
                 amount: Uint128::from(20000u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -3746,6 +3868,7 @@ fn spread_test() {
                 amount: Uint128::from(30000u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -3777,6 +3900,7 @@ fn spread_test() {
                 amount: Uint128::from(15000u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -3808,6 +3932,7 @@ fn spread_test() {
                 amount: Uint128::from(41000u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -3839,6 +3964,7 @@ fn spread_test() {
                 amount: Uint128::from(19000u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -3870,6 +3996,7 @@ fn spread_test() {
                 amount: Uint128::from(44800u128),
             },
         ],
+        client_id: None,
     };
 
     // offer usdt, ask for orai
@@ -3902,6 +4029,7 @@ fn spread_test() {
                 amount: Uint128::from(28100u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -3933,6 +4061,7 @@ fn spread_test() {
                 amount: Uint128::from(50000u128),
             },
         ],
+        client_id: None,
     };
 
     // offer usdt, ask for orai
@@ -4121,7 +4250,9 @@ fn reward_to_executor_test() {
         reward_address: None,
         spread_address:None,
     };
-    let code_id = app.upload(Box::new(create_entry_points_testing!(crate)));
+    let code_id = app.upload(Box::new(
+        create_entry_points_testing!(crate).with_reply(crate::contract::reply),
+    ));
     let limit_order_addr = app
         .instantiate(
             code_id,
@@ -4168,6 +4299,7 @@ fn reward_to_executor_test() {
                 amount: Uint128::from(618000u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -4199,6 +4331,7 @@ fn reward_to_executor_test() {
                 amount: Uint128::from(100000u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -4230,6 +4363,7 @@ fn reward_to_executor_test() {
                 amount: Uint128::from(600000u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -4261,6 +4395,7 @@ fn reward_to_executor_test() {
                 amount: Uint128::from(100000u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -4432,7 +4567,9 @@ fn mock_basic_query_data() -> (MockApp, Addr) {
         reward_address: None,
         spread_address:None,
     };
-    let code_id = app.upload(Box::new(create_entry_points_testing!(crate)));
+    let code_id = app.upload(Box::new(
+        create_entry_points_testing!(crate).with_reply(crate::contract::reply),
+    ));
     let limit_order_addr = app
         .instantiate(
             code_id,
@@ -4485,6 +4622,7 @@ fn query_matchable() {
                 amount: Uint128::from(20000u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -4516,6 +4654,7 @@ fn query_matchable() {
                 amount: Uint128::from(30000u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -4569,6 +4708,7 @@ fn query_matchable() {
                 amount: Uint128::from(44800u128),
             },
         ],
+        client_id: None,
     };
 
     // offer usdt, ask for orai
@@ -4623,6 +4763,7 @@ fn query_matchable() {
                 amount: Uint128::from(22000u128),
             },
         ],
+        client_id: None,
     };
 
     // offer usdt, ask for orai
@@ -4712,7 +4853,9 @@ fn remove_orderbook_pair() {
         spread_address:None,
     };
 
-    let code_id = app.upload(Box::new(create_entry_points_testing!(crate)));
+    let code_id = app.upload(Box::new(
+        create_entry_points_testing!(crate).with_reply(crate::contract::reply),
+    ));
     let limit_order_addr = app
         .instantiate(
             code_id,
@@ -4759,6 +4902,7 @@ fn remove_orderbook_pair() {
                 amount: Uint128::from(12345u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -4790,6 +4934,7 @@ fn remove_orderbook_pair() {
                 amount: Uint128::from(9700u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -4821,6 +4966,7 @@ fn remove_orderbook_pair() {
                 amount: Uint128::from(13000u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -4852,6 +4998,7 @@ fn remove_orderbook_pair() {
                 amount: Uint128::from(1499u128),
             },
         ],
+        client_id: None,
     };
 
     // offer orai, ask for atom
@@ -4886,6 +5033,7 @@ fn remove_orderbook_pair() {
         filled_ask_amount: Uint128::zero(),
         direction: OrderDirection::Buy,
         status: OrderStatus::Open,
+        client_id: None,
     };
 
     assert_eq!(
@@ -4917,6 +5065,7 @@ fn remove_orderbook_pair() {
                 denom: ORAI_DENOM.to_string(),
             },
         ],
+        grace_period_seconds: None,
     };
 
     let res = app
@@ -4947,6 +5096,7 @@ fn remove_orderbook_pair() {
                 start_after: None,
                 limit: None,
                 order_by: None,
+                cursor: None,
             },
         )
         .unwrap_err();
@@ -5040,7 +5190,9 @@ fn orders_querier() {
         reward_address: None,
         spread_address:None,
     };
-    let code_id = app.upload(Box::new(create_entry_points_testing!(crate)));
+    let code_id = app.upload(Box::new(
+        create_entry_points_testing!(crate).with_reply(crate::contract::reply),
+    ));
     let limit_order_addr = app
         .instantiate(
             code_id,
@@ -5135,6 +5287,7 @@ fn orders_querier() {
                 amount: Uint128::from(1000000u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -5169,6 +5322,7 @@ fn orders_querier() {
                     amount: Uint128::from(1000000u128),
                 },
             ],
+            client_id: None,
         })
         .unwrap(),
     };
@@ -5201,6 +5355,7 @@ fn orders_querier() {
                     amount: Uint128::from(12345678u128),
                 },
             ],
+            client_id: None,
         })
         .unwrap(),
     };
@@ -5233,6 +5388,7 @@ fn orders_querier() {
                     amount: Uint128::from(22000000u128),
                 },
             ],
+            client_id: None,
         })
         .unwrap(),
     };
@@ -5265,6 +5421,7 @@ fn orders_querier() {
         filled_ask_amount: Uint128::zero(),
         direction: OrderDirection::Buy,
         status: OrderStatus::Open,
+        client_id: None,
     };
 
     let order_2 = OrderResponse {
@@ -5286,6 +5443,7 @@ fn orders_querier() {
         filled_ask_amount: Uint128::zero(),
         direction: OrderDirection::Buy,
         status: OrderStatus::Open,
+        client_id: None,
     };
 
     let all_order = OrdersResponse {
@@ -5309,6 +5467,7 @@ fn orders_querier() {
                 filled_offer_amount: Uint128::zero(),
                 filled_ask_amount: Uint128::zero(),
                 status: OrderStatus::Open,
+                client_id: None,
             },
             OrderResponse {
                 order_id: 3u64,
@@ -5329,6 +5488,7 @@ fn orders_querier() {
                 filled_offer_amount: Uint128::zero(),
                 filled_ask_amount: Uint128::zero(),
                 status: OrderStatus::Open,
+                client_id: None,
             },
             OrderResponse {
                 order_id: 2u64,
@@ -5349,14 +5509,17 @@ fn orders_querier() {
                 filled_offer_amount: Uint128::zero(),
                 filled_ask_amount: Uint128::zero(),
                 status: OrderStatus::Open,
+                client_id: None,
             },
         ]
         .to_vec(),
+        next_cursor: None,
     };
 
     assert_eq!(
         OrdersResponse {
             orders: vec![order_2.clone(),],
+            next_cursor: None,
         },
         app.query::<OrdersResponse, _>(
             limit_order_addr.clone(),
@@ -5374,6 +5537,7 @@ fn orders_querier() {
                 start_after: None,
                 limit: None,
                 order_by: Some(1),
+                cursor: None,
             }
         )
         .unwrap()
@@ -5396,10 +5560,16 @@ fn orders_querier() {
                 start_after: None,
                 limit: None,
                 order_by: None,
+                cursor: None,
             },
         )
         .unwrap();
     println!("[LOG] [1] - query all buy order: {}", jsonstr!(test));
+    assert!(!test.orders.is_empty());
+    assert!(test
+        .orders
+        .iter()
+        .all(|order| order.direction == OrderDirection::Buy));
 
     let test = app
         .query::<OrdersResponse, _>(
@@ -5418,10 +5588,16 @@ fn orders_querier() {
                 start_after: None,
                 limit: None,
                 order_by: None,
+                cursor: None,
             },
         )
         .unwrap();
     println!("[LOG] [2] - query all sell order: {}", jsonstr!(test));
+    assert!(!test.orders.is_empty());
+    assert!(test
+        .orders
+        .iter()
+        .all(|order| order.direction == OrderDirection::Sell));
 
     let test = app
         .query::<OrdersResponse, _>(
@@ -5440,6 +5616,7 @@ fn orders_querier() {
                 start_after: None,
                 limit: None,
                 order_by: None,
+                cursor: None,
             },
         )
         .unwrap();
@@ -5448,6 +5625,7 @@ fn orders_querier() {
     assert_eq!(
         OrdersResponse {
             orders: vec![order_1.clone()],
+            next_cursor: None,
         },
         app.query::<OrdersResponse, _>(
             limit_order_addr.clone(),
@@ -5465,6 +5643,7 @@ fn orders_querier() {
                 start_after: None,
                 limit: None,
                 order_by: Some(1),
+                cursor: None,
             }
         )
         .unwrap()
@@ -5489,14 +5668,51 @@ fn orders_querier() {
                 start_after: None,
                 limit: None,
                 order_by: Some(2),
+                cursor: None,
             }
         )
         .unwrap()
     );
 
+    // DESC pages with cursor
+    let mut cursor = None;
+    let mut orders = vec![];
+    loop {
+        let res = app
+            .query::<OrdersResponse, _>(
+                limit_order_addr.clone(),
+                &QueryMsg::Orders {
+                    asset_infos: [
+                        AssetInfo::Token {
+                            contract_addr: token_addrs[1].clone(),
+                        },
+                        AssetInfo::Token {
+                            contract_addr: token_addrs[0].clone(),
+                        },
+                    ],
+                    direction: None,
+                    filter: OrderFilter::None,
+                    start_after: None,
+                    limit: Some(1),
+                    order_by: Some(2),
+                    cursor: cursor.clone(),
+                },
+            )
+            .unwrap();
+        orders.extend(res.orders);
+        cursor = res.next_cursor;
+        if cursor.is_none() {
+            break;
+        }
+    }
+    assert_eq!(all_order.orders, orders);
+
     // different bidder
     assert_eq!(
-        OrdersResponse { orders: vec![] },
+        OrdersResponse {
+            orders: vec![],
+            next_cursor: None,
+        },
         app.query::<OrdersResponse, _>(
             limit_order_addr.clone(),
             &QueryMsg::Orders {
@@ -5513,6 +5729,7 @@ fn orders_querier() {
                 start_after: None,
                 limit: None,
                 order_by: None,
+                cursor: None,
             }
         )
         .unwrap()
@@ -5522,6 +5739,7 @@ fn orders_querier() {
     assert_eq!(
         OrdersResponse {
             orders: vec![order_1],
+            next_cursor: None,
         },
         app.query::<OrdersResponse, _>(
             limit_order_addr.clone(),
@@ -5539,6 +5757,7 @@ fn orders_querier() {
                 start_after: Some(2u64),
                 limit: None,
                 order_by: Some(2),
+                cursor: None,
             }
         )
         .unwrap()
@@ -5546,7 +5765,10 @@ fn orders_querier() {
 
     // start after ASC
     assert_eq!(
-        OrdersResponse { orders: vec![] },
+        OrdersResponse {
+            orders: vec![],
+            next_cursor: None,
+        },
         app.query::<OrdersResponse, _>(
             limit_order_addr.clone(),
             &QueryMsg::Orders {
@@ -5563,6 +5785,7 @@ fn orders_querier() {
                 start_after: Some(1u64),
                 limit: None,
                 order_by: Some(1),
+                cursor: None,
             }
         )
         .unwrap()
@@ -5608,6 +5831,7 @@ fn orders_querier() {
                     start_after: None,
                     limit: None,
                     order_by: Some(1),
+                    cursor: None,
                 },
             )
             .unwrap();
@@ -5636,6 +5860,7 @@ fn test_query_ticks_start_after() {
                 amount: Uint128::from(20000u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -5667,6 +5892,7 @@ fn test_query_ticks_start_after() {
                 amount: Uint128::from(30000u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -5755,6 +5981,7 @@ fn test_query_ticks_with_end() {
                 amount: Uint128::from(20000u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -5786,6 +6013,7 @@ fn test_query_ticks_with_end() {
                 amount: Uint128::from(30000u128),
             },
         ],
+        client_id: None,
     };
 
     let _res = app
@@ -5846,3 +6074,6252 @@ fn test_query_ticks_with_end() {
     assert_eq!(result.ticks.len(), 1);
     assert_eq!(result.ticks[0].price, Decimal::from_str("3").unwrap());
 }
+
+#[test]
+fn pause_by_guardian() {
+    let mut app = MockApp::new(&[(
+        &"addr0000".to_string(),
+        &[Coin {
+            denom: USDT_DENOM.to_string(),
+            amount: Uint128::from(1000000000u128),
+        }],
+    )]);
+
+    let msg = InstantiateMsg {
+        name: None,
+        version: None,
+        admin: None,
+        commission_rate: None,
+        reward_address: None,
+        spread_address: None,
+    };
+    let code_id = app.upload(Box::new(
+        create_entry_points_testing!(crate).with_reply(crate::contract::reply),
+    ));
+    let limit_order_addr = app
+        .instantiate(
+            code_id,
+            Addr::unchecked("addr0000"),
+            &msg,
+            &[],
+            "limit order",
+        )
+        .unwrap();
+
+    // create order book for pair [orai, usdt]
+    let msg = ExecuteMsg::CreateOrderBookPair {
+        base_coin_info: AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        quote_coin_info: AssetInfo::NativeToken {
+            denom: USDT_DENOM.to_string(),
+        },
+        spread: None,
+        min_quote_coin_amount: Uint128::from(10u128),
+    };
+    let _res = app
+        .execute(
+            Addr::unchecked("addr0000"),
+            limit_order_addr.clone(),
+            &msg,
+            &[],
+        )
+        .unwrap();
+
+    // set guardian
+    let msg = ExecuteMsg::UpdateConfig {
+        reward_address: None,
+        spread_address: None,
+        commission_rate: None,
+        guardian: Some(Addr::unchecked("guardian")),
+        maker_rebate_rate: None,
+        event_mode: None,
+        rewarder: None,
+        referral: None,
+        oracle: None,
+        converter: None,
+    };
+    let _res = app
+        .execute(
+            Addr::unchecked("addr0000"),
+            limit_order_addr.clone(),
+            &msg,
+            &[],
+        )
+        .unwrap();
+
+    // only admin or guardian can pause
+    let res = app.execute(
+        Addr::unchecked("addr0001"),
+        limit_order_addr.clone(),
+        &ExecuteMsg::Pause {},
+        &[],
+    );
+    app.assert_fail(res);
+
+    let _res = app
+        .execute(
+            Addr::unchecked("guardian"),
+            limit_order_addr.clone(),
+            &ExecuteMsg::Pause {},
+            &[],
+        )
+        .unwrap();
+
+    let contract_info = app
+        .query::<ContractInfoResponse, _>(limit_order_addr.clone(), &QueryMsg::ContractInfo {})
+        .unwrap();
+    assert_eq!(contract_info.guardian, Some(Addr::unchecked("guardian")));
+    assert!(contract_info.paused);
+
+    let submit_order_msg = ExecuteMsg::SubmitOrder {
+        direction: OrderDirection::Buy,
+        assets: [
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: USDT_DENOM.to_string(),
+                },
+                amount: Uint128::from(1000000u128),
+            },
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: ORAI_DENOM.to_string(),
+                },
+                amount: Uint128::from(1000000u128),
+            },
+        ],
+        client_id: None,
+    };
+    let funds = [Coin {
+        denom: USDT_DENOM.to_string(),
+        amount: Uint128::from(1000000u128),
+    }];
+
+    // can not submit order while paused
+    let res = app.execute(
+        Addr::unchecked("addr0000"),
+        limit_order_addr.clone(),
+        &submit_order_msg,
+        &funds,
+    );
+    app.assert_fail(res);
+
+    // admin unpause then order can be submitted
+    let _res = app
+        .execute(
+            Addr::unchecked("addr0000"),
+            limit_order_addr.clone(),
+            &ExecuteMsg::Unpause {},
+            &[],
+        )
+        .unwrap();
+
+    let _res = app
+        .execute(
+            Addr::unchecked("addr0000"),
+            limit_order_addr.clone(),
+            &submit_order_msg,
+            &funds,
+        )
+        .unwrap();
+}
+
+#[test]
+fn match_orders_dry_run() {
+    let mut app = MockApp::new(&[
+        (
+            &"addr0000".to_string(),
+            &[Coin {
+                denom: USDT_DENOM.to_string(),
+                amount: Uint128::from(1000000u128),
+            }],
+        ),
+        (
+            &"addr0001".to_string(),
+            &[Coin {
+                denom: ORAI_DENOM.to_string(),
+                amount: Uint128::from(1000000u128),
+            }],
+        ),
+    ]);
+
+    let msg = InstantiateMsg {
+        name: None,
+        version: None,
+        admin: None,
+        commission_rate: None,
+        reward_address: None,
+        spread_address: None,
+    };
+    let code_id = app.upload(Box::new(
+        create_entry_points_testing!(crate).with_reply(crate::contract::reply),
+    ));
+    let limit_order_addr = app
+        .instantiate(
+            code_id,
+            Addr::unchecked("addr0000"),
+            &msg,
+            &[],
+            "limit order",
+        )
+        .unwrap();
+
+    let asset_infos = [
+        AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: USDT_DENOM.to_string(),
+        },
+    ];
+
+    // create order book for pair [orai, usdt]
+    let msg = ExecuteMsg::CreateOrderBookPair {
+        base_coin_info: asset_infos[0].clone(),
+        quote_coin_info: asset_infos[1].clone(),
+        spread: None,
+        min_quote_coin_amount: Uint128::from(10u128),
+    };
+    let _res = app
+        .execute(
+            Addr::unchecked("addr0000"),
+            limit_order_addr.clone(),
+            &msg,
+            &[],
+        )
+        .unwrap();
+
+    // buy 1000 orai with 1000 usdt and sell 1000 orai for 1000 usdt
+    for (sender, direction, denom) in [
+        ("addr0000", OrderDirection::Buy, USDT_DENOM),
+        ("addr0001", OrderDirection::Sell, ORAI_DENOM),
+    ] {
+        let msg = ExecuteMsg::SubmitOrder {
+            direction,
+            assets: [
+                Asset {
+                    info: asset_infos[0].clone(),
+                    amount: Uint128::from(1000u128),
+                },
+                Asset {
+                    info: asset_infos[1].clone(),
+                    amount: Uint128::from(1000u128),
+                },
+            ],
+            client_id: None,
+        };
+        let _res = app
+            .execute(
+                Addr::unchecked(sender),
+                limit_order_addr.clone(),
+                &msg,
+                &[Coin {
+                    denom: denom.to_string(),
+                    amount: Uint128::from(1000u128),
+                }],
+            )
+            .unwrap();
+    }
+
+    let res = app
+        .execute(
+            Addr::unchecked("addr0002"),
+            limit_order_addr.clone(),
+            &ExecuteMsg::MatchOrdersDryRun {
+                asset_infos: asset_infos.clone(),
+                limit: None,
+            },
+            &[],
+        )
+        .unwrap();
+    let dry_run: MatchOrdersDryRunResponse = from_binary(&res.data.unwrap()).unwrap();
+    assert_eq!(dry_run.total_matched_orders, 2);
+    assert!(dry_run
+        .orders
+        .iter()
+        .all(|order| order.status == OrderStatus::Fulfilled));
+
+    // the orders are still open
+    for order_id in [1u64, 2u64] {
+        let order = app
+            .query::<OrderResponse, _>(
+                limit_order_addr.clone(),
+                &QueryMsg::Order {
+                    order_id,
+                    asset_infos: asset_infos.clone(),
+                },
+            )
+            .unwrap();
+        assert_eq!(order.status, OrderStatus::Open);
+        assert_eq!(order.filled_offer_amount, Uint128::zero());
+    }
+}
+
+#[test]
+fn pair_stats() {
+    let mut app = MockApp::new(&[
+        (
+            &"addr0000".to_string(),
+            &[Coin {
+                denom: USDT_DENOM.to_string(),
+                amount: Uint128::from(1000000u128),
+            }],
+        ),
+        (
+            &"addr0001".to_string(),
+            &[Coin {
+                denom: ORAI_DENOM.to_string(),
+                amount: Uint128::from(1000000u128),
+            }],
+        ),
+    ]);
+
+    let msg = InstantiateMsg {
+        name: None,
+        version: None,
+        admin: None,
+        commission_rate: None,
+        reward_address: None,
+        spread_address: None,
+    };
+    let code_id = app.upload(Box::new(
+        create_entry_points_testing!(crate).with_reply(crate::contract::reply),
+    ));
+    let limit_order_addr = app
+        .instantiate(
+            code_id,
+            Addr::unchecked("addr0000"),
+            &msg,
+            &[],
+            "limit order",
+        )
+        .unwrap();
+
+    let asset_infos = [
+        AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: USDT_DENOM.to_string(),
+        },
+    ];
+
+    // create order book for pair [orai, usdt]
+    let msg = ExecuteMsg::CreateOrderBookPair {
+        base_coin_info: asset_infos[0].clone(),
+        quote_coin_info: asset_infos[1].clone(),
+        spread: None,
+        min_quote_coin_amount: Uint128::from(10u128),
+    };
+    let _res = app
+        .execute(
+            Addr::unchecked("addr0000"),
+            limit_order_addr.clone(),
+            &msg,
+            &[],
+        )
+        .unwrap();
+
+    // buy 1000 orai with 2000 usdt and sell 1000 orai for 2000 usdt
+    for (sender, direction, denom, amount) in [
+        ("addr0000", OrderDirection::Buy, USDT_DENOM, 2000u128),
+        ("addr0001", OrderDirection::Sell, ORAI_DENOM, 1000u128),
+    ] {
+        let msg = ExecuteMsg::SubmitOrder {
+            direction,
+            assets: [
+                Asset {
+                    info: asset_infos[0].clone(),
+                    amount: Uint128::from(1000u128),
+                },
+                Asset {
+                    info: asset_infos[1].clone(),
+                    amount: Uint128::from(2000u128),
+                },
+            ],
+            client_id: None,
+        };
+        let _res = app
+            .execute(
+                Addr::unchecked(sender),
+                limit_order_addr.clone(),
+                &msg,
+                &[Coin {
+                    denom: denom.to_string(),
+                    amount: Uint128::from(amount),
+                }],
+            )
+            .unwrap();
+    }
+
+    // no trade yet
+    let stats = app
+        .query::<PairStatsResponse, _>(
+            limit_order_addr.clone(),
+            &QueryMsg::PairStats {
+                asset_infos: asset_infos.clone(),
+                period: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(stats.total_trades, 0);
+    assert_eq!(stats.quote_volume, Uint128::zero());
+
+    let _res = app
+        .execute(
+            Addr::unchecked("addr0002"),
+            limit_order_addr.clone(),
+            &ExecuteMsg::ExecuteOrderBookPair {
+                asset_infos: asset_infos.clone(),
+                limit: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+    // buyer fees are paid in orai and valued at price 2
+    let expected = PairStatsResponse {
+        base_coin_info: asset_infos[0].clone(),
+        quote_coin_info: asset_infos[1].clone(),
+        period: None,
+        base_volume: Uint128::from(1000u128),
+        quote_volume: Uint128::from(2000u128),
+        buy_commission: Uint128::from(2u128),
+        sell_commission: Uint128::from(2u128),
+        relayer_fee: Uint128::from(1200u128),
+        maker_rebate: Uint128::zero(),
+        total_trades: 2,
+    };
+    let stats = app
+        .query::<PairStatsResponse, _>(
+            limit_order_addr.clone(),
+            &QueryMsg::PairStats {
+                asset_infos: asset_infos.clone(),
+                period: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(stats, expected);
+
+    // all trades happened in the current day
+    let period = mock_env().block.time.seconds() / 86400;
+    let stats = app
+        .query::<PairStatsResponse, _>(
+            limit_order_addr.clone(),
+            &QueryMsg::PairStats {
+                asset_infos: asset_infos.clone(),
+                period: Some(period),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        stats,
+        PairStatsResponse {
+            period: Some(period),
+            ..expected
+        }
+    );
+
+    let stats = app
+        .query::<PairStatsResponse, _>(
+            limit_order_addr.clone(),
+            &QueryMsg::PairStats {
+                asset_infos,
+                period: Some(period - 1),
+            },
+        )
+        .unwrap();
+    assert_eq!(stats.total_trades, 0);
+}
+
+#[test]
+fn candles() {
+    let mut app = MockApp::new(&[
+        (
+            &"addr0000".to_string(),
+            &[Coin {
+                denom: USDT_DENOM.to_string(),
+                amount: Uint128::from(1000000u128),
+            }],
+        ),
+        (
+            &"addr0001".to_string(),
+            &[Coin {
+                denom: ORAI_DENOM.to_string(),
+                amount: Uint128::from(1000000u128),
+            }],
+        ),
+    ]);
+
+    let msg = InstantiateMsg {
+        name: None,
+        version: None,
+        admin: None,
+        commission_rate: None,
+        reward_address: None,
+        spread_address: None,
+    };
+    let code_id = app.upload(Box::new(
+        create_entry_points_testing!(crate).with_reply(crate::contract::reply),
+    ));
+    let limit_order_addr = app
+        .instantiate(
+            code_id,
+            Addr::unchecked("addr0000"),
+            &msg,
+            &[],
+            "limit order",
+        )
+        .unwrap();
+
+    let asset_infos = [
+        AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: USDT_DENOM.to_string(),
+        },
+    ];
+
+    let msg = ExecuteMsg::CreateOrderBookPair {
+        base_coin_info: asset_infos[0].clone(),
+        quote_coin_info: asset_infos[1].clone(),
+        spread: None,
+        min_quote_coin_amount: Uint128::from(10u128),
+    };
+    let _res = app
+        .execute(
+            Addr::unchecked("addr0000"),
+            limit_order_addr.clone(),
+            &msg,
+            &[],
+        )
+        .unwrap();
+
+    // 1000 orai are matched at price 2, then 1000 orai at price 3
+    for quote_amount in [2000u128, 3000u128] {
+        for (sender, direction, denom, amount) in [
+            ("addr0000", OrderDirection::Buy, USDT_DENOM, quote_amount),
+            ("addr0001", OrderDirection::Sell, ORAI_DENOM, 1000u128),
+        ] {
+            let msg = ExecuteMsg::SubmitOrder {
+                direction,
+                assets: [
+                    Asset {
+                        info: asset_infos[0].clone(),
+                        amount: Uint128::from(1000u128),
+                    },
+                    Asset {
+                        info: asset_infos[1].clone(),
+                        amount: Uint128::from(quote_amount),
+                    },
+                ],
+                client_id: None,
+            };
+            let _res = app
+                .execute(
+                    Addr::unchecked(sender),
+                    limit_order_addr.clone(),
+                    &msg,
+                    &[Coin {
+                        denom: denom.to_string(),
+                        amount: Uint128::from(amount),
+                    }],
+                )
+                .unwrap();
+        }
+
+        let _res = app
+            .execute(
+                Addr::unchecked("addr0002"),
+                limit_order_addr.clone(),
+                &ExecuteMsg::ExecuteOrderBookPair {
+                    asset_infos: asset_infos.clone(),
+                    limit: None,
+                },
+                &[],
+            )
+            .unwrap();
+    }
+
+    // both matchings happened in the same hour
+    let hour_start = mock_env().block.time.seconds() / 3600 * 3600;
+    let res = app
+        .query::<CandlesResponse, _>(
+            limit_order_addr.clone(),
+            &QueryMsg::Candles {
+                asset_infos: asset_infos.clone(),
+                interval: CandleInterval::Hour,
+                start: None,
+                end: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(res.interval, CandleInterval::Hour);
+    assert_eq!(
+        res.candles,
+        vec![CandleResponse {
+            start_time: hour_start,
+            open: Decimal::from_str("2").unwrap(),
+            high: Decimal::from_str("3").unwrap(),
+            low: Decimal::from_str("2").unwrap(),
+            close: Decimal::from_str("3").unwrap(),
+            base_volume: Uint128::from(2000u128),
+            quote_volume: Uint128::from(5000u128),
+        }]
+    );
+
+    // minute candles split the same volume
+    let res = app
+        .query::<CandlesResponse, _>(
+            limit_order_addr.clone(),
+            &QueryMsg::Candles {
+                asset_infos: asset_infos.clone(),
+                interval: CandleInterval::Minute,
+                start: Some(hour_start),
+                end: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        res.candles
+            .iter()
+            .map(|candle| candle.base_volume)
+            .sum::<Uint128>(),
+        Uint128::from(2000u128)
+    );
+
+    // end is exclusive
+    let day_start = mock_env().block.time.seconds() / 86400 * 86400;
+    let res = app
+        .query::<CandlesResponse, _>(
+            limit_order_addr.clone(),
+            &QueryMsg::Candles {
+                asset_infos,
+                interval: CandleInterval::Day,
+                start: None,
+                end: Some(day_start),
+            },
+        )
+        .unwrap();
+    assert!(res.candles.is_empty());
+}
+
+#[test]
+fn relayer_fee_rate() {
+    let mut app = MockApp::new(&[
+        (
+            &"addr0000".to_string(),
+            &[Coin {
+                denom: USDT_DENOM.to_string(),
+                amount: Uint128::from(1000000u128),
+            }],
+        ),
+        (
+            &"addr0001".to_string(),
+            &[Coin {
+                denom: ORAI_DENOM.to_string(),
+                amount: Uint128::from(1000000u128),
+            }],
+        ),
+    ]);
+
+    let msg = InstantiateMsg {
+        name: None,
+        version: None,
+        admin: None,
+        commission_rate: None,
+        reward_address: None,
+        spread_address: None,
+    };
+    let code_id = app.upload(Box::new(
+        create_entry_points_testing!(crate).with_reply(crate::contract::reply),
+    ));
+    let limit_order_addr = app
+        .instantiate(
+            code_id,
+            Addr::unchecked("addr0000"),
+            &msg,
+            &[],
+            "limit order",
+        )
+        .unwrap();
+
+    let asset_infos = [
+        AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: USDT_DENOM.to_string(),
+        },
+    ];
+
+    // create order book for pair [orai, usdt]
+    let msg = ExecuteMsg::CreateOrderBookPair {
+        base_coin_info: asset_infos[0].clone(),
+        quote_coin_info: asset_infos[1].clone(),
+        spread: None,
+        min_quote_coin_amount: Uint128::from(10u128),
+    };
+    let _res = app
+        .execute(
+            Addr::unchecked("addr0000"),
+            limit_order_addr.clone(),
+            &msg,
+            &[],
+        )
+        .unwrap();
+
+    // rate must not exceed 1
+    let update_msg = |relayer_fee_rate: Decimal| ExecuteMsg::UpdateOrderBookPair {
+        asset_infos: asset_infos.clone(),
+        settings: OrderBookSettings {
+            relayer_fee_rate: Some(relayer_fee_rate),
+            min_relayer_fee: Some(Uint128::from(20u128)),
+            max_relayer_fee: Some(Uint128::from(50u128)),
+            ..Default::default()
+        },
+    };
+    let res = app.execute(
+        Addr::unchecked("addr0000"),
+        limit_order_addr.clone(),
+        &update_msg(Decimal::percent(101)),
+        &[],
+    );
+    app.assert_fail(res);
+
+    // 1% of the filled amount, at least 20 orai and at most 50 orai
+    let _res = app
+        .execute(
+            Addr::unchecked("addr0000"),
+            limit_order_addr.clone(),
+            &update_msg(Decimal::percent(1)),
+            &[],
+        )
+        .unwrap();
+
+    let order_book = app
+        .query::<OrderBookResponse, _>(
+            limit_order_addr.clone(),
+            &QueryMsg::OrderBook {
+                asset_infos: asset_infos.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(order_book.relayer_fee_rate, Some(Decimal::percent(1)));
+    assert_eq!(order_book.min_relayer_fee, Some(Uint128::from(20u128)));
+    assert_eq!(order_book.max_relayer_fee, Some(Uint128::from(50u128)));
+
+    // buy 1000 orai with 2000 usdt and sell 1000 orai for 2000 usdt
+    for (sender, direction, denom, amount) in [
+        ("addr0000", OrderDirection::Buy, USDT_DENOM, 2000u128),
+        ("addr0001", OrderDirection::Sell, ORAI_DENOM, 1000u128),
+    ] {
+        let msg = ExecuteMsg::SubmitOrder {
+            direction,
+            assets: [
+                Asset {
+                    info: asset_infos[0].clone(),
+                    amount: Uint128::from(1000u128),
+                },
+                Asset {
+                    info: asset_infos[1].clone(),
+                    amount: Uint128::from(2000u128),
+                },
+            ],
+            client_id: None,
+        };
+        let _res = app
+            .execute(
+                Addr::unchecked(sender),
+                limit_order_addr.clone(),
+                &msg,
+                &[Coin {
+                    denom: denom.to_string(),
+                    amount: Uint128::from(amount),
+                }],
+            )
+            .unwrap();
+    }
+
+    let res = app
+        .execute(
+            Addr::unchecked("addr0002"),
+            limit_order_addr.clone(),
+            &ExecuteMsg::ExecuteOrderBookPair {
+                asset_infos: asset_infos.clone(),
+                limit: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+    // buyer pays the min fee of 20 orai, seller pays 20 orai valued at price 2
+    let relayer_fees: Vec<String> = res
+        .events
+        .iter()
+        .filter(|event| event.ty == "wasm-matched_order")
+        .flat_map(|event| event.attributes.iter())
+        .filter(|attr| attr.key == "relayer_fee")
+        .map(|attr| attr.value.clone())
+        .collect();
+    assert_eq!(
+        relayer_fees,
+        vec![format!("20 {}", ORAI_DENOM), format!("40 {}", USDT_DENOM)]
+    );
+
+    let stats = app
+        .query::<PairStatsResponse, _>(
+            limit_order_addr.clone(),
+            &QueryMsg::PairStats {
+                asset_infos,
+                period: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(stats.relayer_fee, Uint128::from(80u128));
+
+    // 1000 orai minus 1 orai commission and 20 orai relayer fee
+    assert_eq!(
+        app.query_balance(Addr::unchecked("addr0000"), ORAI_DENOM.to_string())
+            .unwrap(),
+        Uint128::from(979u128)
+    );
+}
+
+#[test]
+fn execute_pair_by_cw20_hook() {
+    let mut app = MockApp::new(&[
+        (
+            &"addr0000".to_string(),
+            &[Coin {
+                denom: USDT_DENOM.to_string(),
+                amount: Uint128::from(2000000u128),
+            }],
+        ),
+        (
+            &"addr0001".to_string(),
+            &[Coin {
+                denom: ORAI_DENOM.to_string(),
+                amount: Uint128::from(1000000u128),
+            }],
+        ),
+    ]);
+    app.set_token_contract(Box::new(create_entry_points_testing!(oraiswap_token)));
+    let token_addrs = app.set_token_balances(&[(
+        &"assetA".to_string(),
+        &[(&"relayer".to_string(), &Uint128::from(1000u128))],
+    )]);
+
+    let msg = InstantiateMsg {
+        name: None,
+        version: None,
+        admin: None,
+        commission_rate: None,
+        reward_address: None,
+        spread_address: None,
+    };
+    let code_id = app.upload(Box::new(
+        create_entry_points_testing!(crate).with_reply(crate::contract::reply),
+    ));
+    let limit_order_addr = app
+        .instantiate(
+            code_id,
+            Addr::unchecked("addr0000"),
+            &msg,
+            &[],
+            "limit order",
+        )
+        .unwrap();
+
+    let asset_infos = [
+        AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: USDT_DENOM.to_string(),
+        },
+    ];
+
+    // create order book for pair [orai, usdt]
+    let msg = ExecuteMsg::CreateOrderBookPair {
+        base_coin_info: asset_infos[0].clone(),
+        quote_coin_info: asset_infos[1].clone(),
+        spread: None,
+        min_quote_coin_amount: Uint128::zero(),
+    };
+    let _res = app
+        .execute(
+            Addr::unchecked("addr0000"),
+            limit_order_addr.clone(),
+            &msg,
+            &[],
+        )
+        .unwrap();
+
+    // buy 1000000 orai with 2000000 usdt and sell 1000000 orai for 2000000 usdt
+    for (sender, direction, denom, amount) in [
+        ("addr0000", OrderDirection::Buy, USDT_DENOM, 2000000u128),
+        ("addr0001", OrderDirection::Sell, ORAI_DENOM, 1000000u128),
+    ] {
+        let msg = ExecuteMsg::SubmitOrder {
+            direction,
+            assets: [
+                Asset {
+                    info: asset_infos[0].clone(),
+                    amount: Uint128::from(1000000u128),
+                },
+                Asset {
+                    info: asset_infos[1].clone(),
+                    amount: Uint128::from(2000000u128),
+                },
+            ],
+            client_id: None,
+        };
+        let _res = app
+            .execute(
+                Addr::unchecked(sender),
+                limit_order_addr.clone(),
+                &msg,
+                &[Coin {
+                    denom: denom.to_string(),
+                    amount: Uint128::from(amount),
+                }],
+            )
+            .unwrap();
+    }
+
+    // relayer only holding cw20 executes the pair, the sent token is returned
+    let _res = app
+        .execute(
+            Addr::unchecked("relayer"),
+            token_addrs[0].clone(),
+            &cw20::Cw20ExecuteMsg::Send {
+                contract: limit_order_addr.to_string(),
+                amount: Uint128::from(1u128),
+                msg: to_binary(&Cw20HookMsg::ExecutePair {
+                    asset_infos: asset_infos.clone(),
+                    limit: None,
+                })
+                .unwrap(),
+            },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        query_token_balance(
+            &app.as_querier(),
+            token_addrs[0].clone(),
+            Addr::unchecked("relayer")
+        )
+        .unwrap(),
+        Uint128::from(1000u128)
+    );
+
+    // 1000000 orai minus 1000 orai commission and 300 orai relayer fee
+    assert_eq!(
+        app.query_balance(Addr::unchecked("addr0000"), ORAI_DENOM.to_string())
+            .unwrap(),
+        Uint128::from(998700u128)
+    );
+    let res = app
+        .query::<OrdersResponse, _>(
+            limit_order_addr,
+            &QueryMsg::Orders {
+                asset_infos,
+                direction: None,
+                filter: OrderFilter::None,
+                start_after: None,
+                limit: None,
+                order_by: None,
+                cursor: None,
+            },
+        )
+        .unwrap();
+    assert!(res.orders.is_empty());
+}
+
+#[test]
+fn orderbook_operator() {
+    let mut app = MockApp::new(&[(
+        &"addr0000".to_string(),
+        &[Coin {
+            denom: USDT_DENOM.to_string(),
+            amount: Uint128::from(1000000u128),
+        }],
+    )]);
+
+    let msg = InstantiateMsg {
+        name: None,
+        version: None,
+        admin: None,
+        commission_rate: None,
+        reward_address: None,
+        spread_address: None,
+    };
+    let code_id = app.upload(Box::new(
+        create_entry_points_testing!(crate).with_reply(crate::contract::reply),
+    ));
+    let limit_order_addr = app
+        .instantiate(
+            code_id,
+            Addr::unchecked("addr0000"),
+            &msg,
+            &[],
+            "limit order",
+        )
+        .unwrap();
+
+    let asset_infos = [
+        AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: USDT_DENOM.to_string(),
+        },
+    ];
+
+    // create order book for pair [orai, usdt]
+    let msg = ExecuteMsg::CreateOrderBookPair {
+        base_coin_info: asset_infos[0].clone(),
+        quote_coin_info: asset_infos[1].clone(),
+        spread: None,
+        min_quote_coin_amount: Uint128::from(10u128),
+    };
+    let _res = app
+        .execute(
+            Addr::unchecked("addr0000"),
+            limit_order_addr.clone(),
+            &msg,
+            &[],
+        )
+        .unwrap();
+
+    // only admin can set the operator
+    let msg = ExecuteMsg::UpdateOrderBookOperator {
+        asset_infos: asset_infos.clone(),
+        operator: Some(Addr::unchecked("operator")),
+    };
+    let res = app.execute(
+        Addr::unchecked("operator"),
+        limit_order_addr.clone(),
+        &msg,
+        &[],
+    );
+    app.assert_fail(res);
+    let _res = app
+        .execute(
+            Addr::unchecked("addr0000"),
+            limit_order_addr.clone(),
+            &msg,
+            &[],
+        )
+        .unwrap();
+
+    // only operator or admin can update the parameters
+    let msg = ExecuteMsg::UpdateOrderBookParams {
+        asset_infos: asset_infos.clone(),
+        spread: None,
+        min_quote_coin_amount: Some(Uint128::from(20u128)),
+        paused: Some(true),
+        price_precision: None,
+        min_quote_notional: None,
+        min_fill_ratio: None,
+        min_fill_base: None,
+    };
+    let res = app.execute(
+        Addr::unchecked("addr0001"),
+        limit_order_addr.clone(),
+        &msg,
+        &[],
+    );
+    app.assert_fail(res);
+    let _res = app
+        .execute(
+            Addr::unchecked("operator"),
+            limit_order_addr.clone(),
+            &msg,
+            &[],
+        )
+        .unwrap();
+
+    let order_book = app
+        .query::<OrderBookResponse, _>(
+            limit_order_addr.clone(),
+            &QueryMsg::OrderBook {
+                asset_infos: asset_infos.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(order_book.operator, Some(Addr::unchecked("operator")));
+    assert_eq!(order_book.min_quote_coin_amount, Uint128::from(20u128));
+    assert!(order_book.paused);
+
+    // paused order book rejects new orders
+    let submit_msg = ExecuteMsg::SubmitOrder {
+        direction: OrderDirection::Buy,
+        assets: [
+            Asset {
+                info: asset_infos[0].clone(),
+                amount: Uint128::from(1000u128),
+            },
+            Asset {
+                info: asset_infos[1].clone(),
+                amount: Uint128::from(1000u128),
+            },
+        ],
+        client_id: None,
+    };
+    let funds = [Coin {
+        denom: USDT_DENOM.to_string(),
+        amount: Uint128::from(1000u128),
+    }];
+    let res = app.execute(
+        Addr::unchecked("addr0000"),
+        limit_order_addr.clone(),
+        &submit_msg,
+        &funds,
+    );
+    app.assert_fail(res);
+
+    let _res = app
+        .execute(
+            Addr::unchecked("operator"),
+            limit_order_addr.clone(),
+            &ExecuteMsg::UpdateOrderBookParams {
+                asset_infos: asset_infos.clone(),
+                spread: None,
+                min_quote_coin_amount: None,
+                paused: Some(false),
+                price_precision: None,
+                min_quote_notional: None,
+                min_fill_ratio: None,
+                min_fill_base: None,
+            },
+            &[],
+        )
+        .unwrap();
+    let _res = app
+        .execute(
+            Addr::unchecked("addr0000"),
+            limit_order_addr.clone(),
+            &submit_msg,
+            &funds,
+        )
+        .unwrap();
+
+    // removed operator can not update the parameters anymore
+    let _res = app
+        .execute(
+            Addr::unchecked("addr0000"),
+            limit_order_addr.clone(),
+            &ExecuteMsg::UpdateOrderBookOperator {
+                asset_infos: asset_infos.clone(),
+                operator: None,
+            },
+            &[],
+        )
+        .unwrap();
+    let res = app.execute(
+        Addr::unchecked("operator"),
+        limit_order_addr.clone(),
+        &msg,
+        &[],
+    );
+    app.assert_fail(res);
+}
+
+#[test]
+fn maker_rebates() {
+    let mut app = MockApp::new(&[
+        (
+            &"addr0000".to_string(),
+            &[Coin {
+                denom: USDT_DENOM.to_string(),
+                amount: Uint128::from(1000000u128),
+            }],
+        ),
+        (
+            &"addr0001".to_string(),
+            &[Coin {
+                denom: ORAI_DENOM.to_string(),
+                amount: Uint128::from(1000000u128),
+            }],
+        ),
+    ]);
+
+    let msg = InstantiateMsg {
+        name: None,
+        version: None,
+        admin: None,
+        commission_rate: None,
+        reward_address: None,
+        spread_address: None,
+    };
+    let code_id = app.upload(Box::new(
+        create_entry_points_testing!(crate).with_reply(crate::contract::reply),
+    ));
+    let limit_order_addr = app
+        .instantiate(
+            code_id,
+            Addr::unchecked("addr0000"),
+            &msg,
+            &[],
+            "limit order",
+        )
+        .unwrap();
+
+    // rebate half of the default commission rate to makers
+    let msg = ExecuteMsg::UpdateConfig {
+        reward_address: None,
+        spread_address: None,
+        commission_rate: None,
+        guardian: None,
+        maker_rebate_rate: Some(Decimal::from_str("0.0005").unwrap()),
+        event_mode: None,
+        rewarder: None,
+        referral: None,
+        oracle: None,
+        converter: None,
+    };
+    let _res = app
+        .execute(
+            Addr::unchecked("addr0000"),
+            limit_order_addr.clone(),
+            &msg,
+            &[],
+        )
+        .unwrap();
+    let contract_info = app
+        .query::<ContractInfoResponse, _>(limit_order_addr.clone(), &QueryMsg::ContractInfo {})
+        .unwrap();
+    assert_eq!(
+        contract_info.maker_rebate_rate,
+        Some(Decimal::from_str("0.0005").unwrap())
+    );
+
+    let asset_infos = [
+        AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: USDT_DENOM.to_string(),
+        },
+    ];
+
+    // create order book for pair [orai, usdt]
+    let msg = ExecuteMsg::CreateOrderBookPair {
+        base_coin_info: asset_infos[0].clone(),
+        quote_coin_info: asset_infos[1].clone(),
+        spread: None,
+        min_quote_coin_amount: Uint128::from(10u128),
+    };
+    let _res = app
+        .execute(
+            Addr::unchecked("addr0000"),
+            limit_order_addr.clone(),
+            &msg,
+            &[],
+        )
+        .unwrap();
+
+    // buy 1000 orai with 2000 usdt and sell 1000 orai for 2000 usdt
+    for (sender, direction, denom, amount) in [
+        ("addr0000", OrderDirection::Buy, USDT_DENOM, 2000u128),
+        ("addr0001", OrderDirection::Sell, ORAI_DENOM, 1000u128),
+    ] {
+        let msg = ExecuteMsg::SubmitOrder {
+            direction,
+            assets: [
+                Asset {
+                    info: asset_infos[0].clone(),
+                    amount: Uint128::from(1000u128),
+                },
+                Asset {
+                    info: asset_infos[1].clone(),
+                    amount: Uint128::from(2000u128),
+                },
+            ],
+            client_id: None,
+        };
+        let _res = app
+            .execute(
+                Addr::unchecked(sender),
+                limit_order_addr.clone(),
+                &msg,
+                &[Coin {
+                    denom: denom.to_string(),
+                    amount: Uint128::from(amount),
+                }],
+            )
+            .unwrap();
+    }
+
+    let _res = app
+        .execute(
+            Addr::unchecked("addr0002"),
+            limit_order_addr.clone(),
+            &ExecuteMsg::ExecuteOrderBookPair {
+                asset_infos: asset_infos.clone(),
+                limit: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+    // the buyer is the maker, rebated 2000 * 0.0005 usdt out of the seller commission
+    let res = app
+        .query::<MakerRebatesResponse, _>(
+            limit_order_addr.clone(),
+            &QueryMsg::MakerRebates {
+                address: Addr::unchecked("addr0000"),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        res.rebates,
+        vec![Asset {
+            info: asset_infos[1].clone(),
+            amount: Uint128::from(1u128),
+        }]
+    );
+    let res = app
+        .query::<MakerRebatesResponse, _>(
+            limit_order_addr.clone(),
+            &QueryMsg::MakerRebates {
+                address: Addr::unchecked("addr0001"),
+            },
+        )
+        .unwrap();
+    assert_eq!(res.rebates, vec![]);
+
+    let stats = app
+        .query::<PairStatsResponse, _>(
+            limit_order_addr.clone(),
+            &QueryMsg::PairStats {
+                asset_infos: asset_infos.clone(),
+                period: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(stats.sell_commission, Uint128::from(2u128));
+    assert_eq!(stats.maker_rebate, Uint128::from(1u128));
+
+    let _res = app
+        .execute(
+            Addr::unchecked("addr0000"),
+            limit_order_addr.clone(),
+            &ExecuteMsg::ClaimRebates {},
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        app.query_balance(Addr::unchecked("addr0000"), USDT_DENOM.to_string())
+            .unwrap(),
+        Uint128::from(998001u128)
+    );
+
+    // nothing left to claim
+    let res = app.execute(
+        Addr::unchecked("addr0000"),
+        limit_order_addr.clone(),
+        &ExecuteMsg::ClaimRebates {},
+        &[],
+    );
+    app.assert_fail(res);
+}
+
+#[test]
+fn compact_event_mode() {
+    let mut app = MockApp::new(&[
+        (
+            &"addr0000".to_string(),
+            &[Coin {
+                denom: USDT_DENOM.to_string(),
+                amount: Uint128::from(1000000u128),
+            }],
+        ),
+        (
+            &"addr0001".to_string(),
+            &[Coin {
+                denom: ORAI_DENOM.to_string(),
+                amount: Uint128::from(1000000u128),
+            }],
+        ),
+    ]);
+
+    let msg = InstantiateMsg {
+        name: None,
+        version: None,
+        admin: None,
+        commission_rate: None,
+        reward_address: None,
+        spread_address: None,
+    };
+    let code_id = app.upload(Box::new(
+        create_entry_points_testing!(crate).with_reply(crate::contract::reply),
+    ));
+    let limit_order_addr = app
+        .instantiate(
+            code_id,
+            Addr::unchecked("addr0000"),
+            &msg,
+            &[],
+            "limit order",
+        )
+        .unwrap();
+
+    let contract_info = app
+        .query::<ContractInfoResponse, _>(limit_order_addr.clone(), &QueryMsg::ContractInfo {})
+        .unwrap();
+    assert_eq!(contract_info.event_mode, EventMode::Verbose);
+
+    let msg = ExecuteMsg::UpdateConfig {
+        reward_address: None,
+        spread_address: None,
+        commission_rate: None,
+        guardian: None,
+        maker_rebate_rate: None,
+        event_mode: Some(EventMode::Compact),
+        rewarder: None,
+        referral: None,
+        oracle: None,
+        converter: None,
+    };
+    let _res = app
+        .execute(
+            Addr::unchecked("addr0000"),
+            limit_order_addr.clone(),
+            &msg,
+            &[],
+        )
+        .unwrap();
+
+    let asset_infos = [
+        AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: USDT_DENOM.to_string(),
+        },
+    ];
+
+    // create order book for pair [orai, usdt]
+    let msg = ExecuteMsg::CreateOrderBookPair {
+        base_coin_info: asset_infos[0].clone(),
+        quote_coin_info: asset_infos[1].clone(),
+        spread: None,
+        min_quote_coin_amount: Uint128::from(10u128),
+    };
+    let _res = app
+        .execute(
+            Addr::unchecked("addr0000"),
+            limit_order_addr.clone(),
+            &msg,
+            &[],
+        )
+        .unwrap();
+
+    // buy 1000 orai with 2000 usdt and sell 1000 orai for 2000 usdt
+    for (sender, direction, denom, amount) in [
+        ("addr0000", OrderDirection::Buy, USDT_DENOM, 2000u128),
+        ("addr0001", OrderDirection::Sell, ORAI_DENOM, 1000u128),
+    ] {
+        let msg = ExecuteMsg::SubmitOrder {
+            direction,
+            assets: [
+                Asset {
+                    info: asset_infos[0].clone(),
+                    amount: Uint128::from(1000u128),
+                },
+                Asset {
+                    info: asset_infos[1].clone(),
+                    amount: Uint128::from(2000u128),
+                },
+            ],
+            client_id: None,
+        };
+        let _res = app
+            .execute(
+                Addr::unchecked(sender),
+                limit_order_addr.clone(),
+                &msg,
+                &[Coin {
+                    denom: denom.to_string(),
+                    amount: Uint128::from(amount),
+                }],
+            )
+            .unwrap();
+    }
+
+    let res = app
+        .execute(
+            Addr::unchecked("addr0002"),
+            limit_order_addr.clone(),
+            &ExecuteMsg::ExecuteOrderBookPair {
+                asset_infos: asset_infos.clone(),
+                limit: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+    // no event per order, only the aggregated one
+    assert!(!res
+        .events
+        .iter()
+        .any(|event| event.ty == "wasm-matched_order"));
+    let event = res
+        .events
+        .iter()
+        .find(|event| event.ty == "wasm-matched_orders")
+        .unwrap();
+    assert_eq!(
+        event.attributes[1..].to_vec(),
+        vec![
+            attr("total_matched_orders", "2"),
+            attr("base_volume", "1000"),
+            attr("quote_volume", "2000"),
+            attr("total_fees", "1204"),
+        ]
+    );
+}
+
+#[test]
+fn export_and_import_orders() {
+    let mut deps = mock_dependencies();
+
+    let msg = InstantiateMsg {
+        name: None,
+        version: None,
+        admin: None,
+        commission_rate: None,
+        reward_address: Some(Addr::unchecked("reward")),
+        spread_address: Some(Addr::unchecked("spread")),
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    let asset_infos = [
+        AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: USDT_DENOM.to_string(),
+        },
+    ];
+
+    let msg = ExecuteMsg::CreateOrderBookPair {
+        base_coin_info: asset_infos[0].clone(),
+        quote_coin_info: asset_infos[1].clone(),
+        spread: None,
+        min_quote_coin_amount: Uint128::from(10u128),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    let orders = vec![
+        OrderRecord {
+            order_id: 5,
+            status: OrderStatus::Open,
+            direction: OrderDirection::Buy,
+            bidder_addr: Addr::unchecked("addr0000"),
+            offer_amount: Uint128::from(1000u128),
+            ask_amount: Uint128::from(500u128),
+            filled_offer_amount: Uint128::zero(),
+            filled_ask_amount: Uint128::zero(),
+        },
+        OrderRecord {
+            order_id: 7,
+            status: OrderStatus::PartialFilled,
+            direction: OrderDirection::Sell,
+            bidder_addr: Addr::unchecked("addr0001"),
+            offer_amount: Uint128::from(500u128),
+            ask_amount: Uint128::from(1100u128),
+            filled_offer_amount: Uint128::from(100u128),
+            filled_ask_amount: Uint128::from(220u128),
+        },
+    ];
+    let import_msg = ExecuteMsg::ImportOrders {
+        asset_infos: asset_infos.clone(),
+        orders: orders.clone(),
+    };
+
+    // importing is disabled until migrating with the flag
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        import_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Std(StdError::generic_err("importing orders is not allowed"))
+    );
+
+    migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg {
+            allow_import_orders: Some(true),
+            registry: None,
+        },
+    )
+    .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        import_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        import_msg.clone(),
+    )
+    .unwrap();
+
+    // can not import the same orders twice
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        import_msg,
+    )
+    .unwrap_err();
+
+    let res: ExportOrdersResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::ExportOrders {
+                asset_infos: asset_infos.clone(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.orders, orders);
+
+    let res: ExportOrdersResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::ExportOrders {
+                asset_infos,
+                start_after: Some(5),
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.orders, orders[1..].to_vec());
+
+    // the last order id continues after the imported orders
+    let res: LastOrderIdResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::LastOrderId {}).unwrap()).unwrap();
+    assert_eq!(res.last_order_id, 7);
+}
+
+#[test]
+fn cancel_order_within_min_lifetime() {
+    let mut deps = mock_dependencies();
+
+    let msg = InstantiateMsg {
+        name: None,
+        version: None,
+        admin: None,
+        commission_rate: None,
+        reward_address: Some(Addr::unchecked("reward")),
+        spread_address: Some(Addr::unchecked("spread")),
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    let asset_infos = [
+        AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: USDT_DENOM.to_string(),
+        },
+    ];
+
+    let msg = ExecuteMsg::CreateOrderBookPair {
+        base_coin_info: asset_infos[0].clone(),
+        quote_coin_info: asset_infos[1].clone(),
+        spread: None,
+        min_quote_coin_amount: Uint128::zero(),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    // only admin can update the order book
+    let update_msg = ExecuteMsg::UpdateOrderBookPair {
+        asset_infos: asset_infos.clone(),
+        settings: OrderBookSettings {
+            min_order_lifetime_seconds: Some(60),
+            ..Default::default()
+        },
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        update_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        update_msg,
+    )
+    .unwrap();
+
+    let submit_msg = ExecuteMsg::SubmitOrder {
+        direction: OrderDirection::Buy,
+        assets: [
+            Asset {
+                info: asset_infos[0].clone(),
+                amount: Uint128::from(1000u128),
+            },
+            Asset {
+                info: asset_infos[1].clone(),
+                amount: Uint128::from(2000u128),
+            },
+        ],
+        client_id: None,
+    };
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &coins(2000, USDT_DENOM)),
+        submit_msg,
+    )
+    .unwrap();
+
+    let cancel_msg = ExecuteMsg::CancelOrder {
+        order_id: 1,
+        asset_infos: asset_infos.clone(),
+    };
+
+    // without cancel fee, the order can not be cancelled within its lifetime
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        cancel_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::OrderLifetimeNotReached {
+            order_id: 1,
+            min_order_lifetime_seconds: 60,
+        }
+    );
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        ExecuteMsg::UpdateOrderBookPair {
+            asset_infos: asset_infos.clone(),
+            settings: OrderBookSettings {
+                min_order_lifetime_seconds: Some(60),
+                cancel_fee_rate: Some(Decimal::percent(1)),
+                ..Default::default()
+            },
+        },
+    )
+    .unwrap();
+
+    let res: OrderBookResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::OrderBook {
+                asset_infos: asset_infos.clone(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.min_order_lifetime_seconds, Some(60));
+    assert_eq!(res.cancel_fee_rate, Some(Decimal::percent(1)));
+
+    // cancel within lifetime, 1% of the remaining offer amount goes to the reward address
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(10);
+    let res = execute(deps.as_mut(), env, mock_info("addr0000", &[]), cancel_msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(BankMsg::Send {
+                to_address: "addr0000".to_string(),
+                amount: coins(1980, USDT_DENOM),
+            }),
+            SubMsg::new(BankMsg::Send {
+                to_address: "reward".to_string(),
+                amount: coins(20, USDT_DENOM),
+            }),
+        ]
+    );
+
+    // cancel after lifetime is free
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0001", &coins(2000, USDT_DENOM)),
+        ExecuteMsg::SubmitOrder {
+            direction: OrderDirection::Buy,
+            assets: [
+                Asset {
+                    info: asset_infos[0].clone(),
+                    amount: Uint128::from(1000u128),
+                },
+                Asset {
+                    info: asset_infos[1].clone(),
+                    amount: Uint128::from(2000u128),
+                },
+            ],
+            client_id: None,
+        },
+    )
+    .unwrap();
+
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(60);
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info("addr0001", &[]),
+        ExecuteMsg::CancelOrder {
+            order_id: 2,
+            asset_infos,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: "addr0001".to_string(),
+            amount: coins(2000, USDT_DENOM),
+        })]
+    );
+}
+
+#[test]
+fn cancel_unfilled_order_after_free_cancel_blocks() {
+    let mut deps = mock_dependencies();
+
+    let msg = InstantiateMsg {
+        name: None,
+        version: None,
+        admin: None,
+        commission_rate: None,
+        reward_address: Some(Addr::unchecked("reward")),
+        spread_address: Some(Addr::unchecked("spread")),
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    let asset_infos = [
+        AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: USDT_DENOM.to_string(),
+        },
+    ];
+
+    let msg = ExecuteMsg::CreateOrderBookPair {
+        base_coin_info: asset_infos[0].clone(),
+        quote_coin_info: asset_infos[1].clone(),
+        spread: None,
+        min_quote_coin_amount: Uint128::zero(),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        ExecuteMsg::UpdateOrderBookPair {
+            asset_infos: asset_infos.clone(),
+            settings: OrderBookSettings {
+                min_order_lifetime_seconds: Some(60),
+                cancel_fee_rate: Some(Decimal::percent(1)),
+                free_cancel_blocks: Some(5),
+                ..Default::default()
+            },
+        },
+    )
+    .unwrap();
+
+    for _ in 0..2 {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("addr0000", &coins(2000, USDT_DENOM)),
+            ExecuteMsg::SubmitOrder {
+                direction: OrderDirection::Buy,
+                assets: [
+                    Asset {
+                        info: asset_infos[0].clone(),
+                        amount: Uint128::from(1000u128),
+                    },
+                    Asset {
+                        info: asset_infos[1].clone(),
+                        amount: Uint128::from(2000u128),
+                    },
+                ],
+                client_id: None,
+            },
+        )
+        .unwrap();
+    }
+
+    let cancel = |deps: DepsMut, blocks: u64, order_id: u64| {
+        let mut env = mock_env();
+        env.block.height += blocks;
+        env.block.time = env.block.time.plus_seconds(10);
+        execute(
+            deps,
+            env,
+            mock_info("addr0000", &[]),
+            ExecuteMsg::CancelOrder {
+                order_id,
+                asset_infos: asset_infos.clone(),
+            },
+        )
+        .unwrap()
+    };
+
+    // still charged the cancel fee before free_cancel_blocks
+    let res = cancel(deps.as_mut(), 4, 1);
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(BankMsg::Send {
+                to_address: "addr0000".to_string(),
+                amount: coins(1980, USDT_DENOM),
+            }),
+            SubMsg::new(BankMsg::Send {
+                to_address: "reward".to_string(),
+                amount: coins(20, USDT_DENOM),
+            }),
+        ]
+    );
+
+    // never filled for free_cancel_blocks, the whole offer is refunded within the lifetime
+    let res = cancel(deps.as_mut(), 5, 2);
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: "addr0000".to_string(),
+            amount: coins(2000, USDT_DENOM),
+        })]
+    );
+}
+
+#[test]
+fn pair_sequence() {
+    let mut deps = mock_dependencies();
+
+    let msg = InstantiateMsg {
+        name: None,
+        version: None,
+        admin: None,
+        commission_rate: None,
+        reward_address: None,
+        spread_address: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    let asset_infos = [
+        AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: USDT_DENOM.to_string(),
+        },
+    ];
+    let msg = ExecuteMsg::CreateOrderBookPair {
+        base_coin_info: asset_infos[0].clone(),
+        quote_coin_info: asset_infos[1].clone(),
+        spread: None,
+        min_quote_coin_amount: Uint128::zero(),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    let query_sequence = |deps: Deps| -> PairSequenceResponse {
+        from_binary(
+            &query(
+                deps,
+                mock_env(),
+                QueryMsg::PairSequence {
+                    asset_infos: asset_infos.clone(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap()
+    };
+    assert_eq!(
+        query_sequence(deps.as_ref()),
+        PairSequenceResponse {
+            base_coin_info: asset_infos[0].clone(),
+            quote_coin_info: asset_infos[1].clone(),
+            last_order_id: None,
+            buy_order_count: 0,
+            sell_order_count: 0,
+        }
+    );
+
+    for (direction, denom) in [
+        (OrderDirection::Buy, USDT_DENOM),
+        (OrderDirection::Sell, ORAI_DENOM),
+        (OrderDirection::Buy, USDT_DENOM),
+    ] {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("addr0000", &coins(1000, denom)),
+            ExecuteMsg::SubmitOrder {
+                direction,
+                assets: [
+                    Asset {
+                        info: asset_infos[0].clone(),
+                        amount: Uint128::from(1000u128),
+                    },
+                    Asset {
+                        info: asset_infos[1].clone(),
+                        amount: Uint128::from(1000u128),
+                    },
+                ],
+                client_id: None,
+            },
+        )
+        .unwrap();
+    }
+
+    // cancelled orders are still counted
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::CancelOrder {
+            order_id: 3,
+            asset_infos: asset_infos.clone(),
+        },
+    )
+    .unwrap();
+
+    assert_eq!(
+        query_sequence(deps.as_ref()),
+        PairSequenceResponse {
+            base_coin_info: asset_infos[0].clone(),
+            quote_coin_info: asset_infos[1].clone(),
+            last_order_id: Some(3),
+            buy_order_count: 2,
+            sell_order_count: 1,
+        }
+    );
+}
+
+#[test]
+fn submit_quote_order() {
+    let mut deps = mock_dependencies();
+
+    let msg = InstantiateMsg {
+        name: None,
+        version: None,
+        admin: None,
+        commission_rate: None,
+        reward_address: None,
+        spread_address: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    let asset_infos = [
+        AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: USDT_DENOM.to_string(),
+        },
+    ];
+    let msg = ExecuteMsg::CreateOrderBookPair {
+        base_coin_info: asset_infos[0].clone(),
+        quote_coin_info: asset_infos[1].clone(),
+        spread: None,
+        min_quote_coin_amount: Uint128::zero(),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    let quote_order = |direction: OrderDirection, price: Decimal, quote_amount: u128| {
+        ExecuteMsg::SubmitQuoteOrder {
+            asset_infos: asset_infos.clone(),
+            direction,
+            price,
+            quote_amount: Uint128::from(quote_amount),
+            client_id: None,
+        }
+    };
+    let query_order = |deps: Deps, order_id: u64| -> OrderResponse {
+        from_binary(
+            &query(
+                deps,
+                mock_env(),
+                QueryMsg::Order {
+                    order_id,
+                    asset_infos: asset_infos.clone(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap()
+    };
+
+    // 1001 usdt at price 2.5 buys 400 orai for 1000 usdt, the rest is refunded
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &coins(1001, USDT_DENOM)),
+        quote_order(OrderDirection::Buy, Decimal::from_ratio(5u128, 2u128), 1001),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: "addr0000".to_string(),
+            amount: coins(1, USDT_DENOM),
+        })]
+    );
+    let order = query_order(deps.as_ref(), 1);
+    assert_eq!(order.offer_asset.amount, Uint128::from(1000u128));
+    assert_eq!(order.ask_asset.amount, Uint128::from(400u128));
+
+    // selling for 1000 usdt at price 2.5 pays 400 orai
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0001", &coins(500, ORAI_DENOM)),
+        quote_order(
+            OrderDirection::Sell,
+            Decimal::from_ratio(5u128, 2u128),
+            1000,
+        ),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: "addr0001".to_string(),
+            amount: coins(100, ORAI_DENOM),
+        })]
+    );
+    let order = query_order(deps.as_ref(), 2);
+    assert_eq!(order.offer_asset.amount, Uint128::from(400u128));
+    assert_eq!(order.ask_asset.amount, Uint128::from(1000u128));
+
+    // not enough funds for the derived base amount
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0001", &coins(399, ORAI_DENOM)),
+        quote_order(
+            OrderDirection::Sell,
+            Decimal::from_ratio(5u128, 2u128),
+            1000,
+        ),
+    );
+    assert!(res.is_err());
+
+    // the quote amount is worth less than one base unit
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &coins(1, USDT_DENOM)),
+        quote_order(OrderDirection::Buy, Decimal::from_ratio(5u128, 2u128), 1),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::AssetMustNotBeZero {});
+}
+
+#[test]
+fn matching_rules() {
+    let mut deps = mock_dependencies();
+
+    let msg = InstantiateMsg {
+        name: None,
+        version: None,
+        admin: None,
+        commission_rate: Some("0.002".to_string()),
+        reward_address: None,
+        spread_address: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    let rules: MatchingRulesResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::MatchingRules {}).unwrap())
+            .unwrap();
+    assert_eq!(
+        rules,
+        MatchingRulesResponse {
+            price_priority: "best_price".to_string(),
+            time_priority: "lowest_order_id".to_string(),
+            match_price: "buy_price_or_clearing_price".to_string(),
+            dust_volume: Uint128::from(10u128),
+            default_limit: 10,
+            max_limit: 100,
+            commission_rate: "0.002".to_string(),
+            relayer_fee: Uint128::from(300u128),
+        }
+    );
+}
+
+#[test]
+fn throttle_executions_per_block() {
+    let mut deps = mock_dependencies();
+
+    let msg = InstantiateMsg {
+        name: None,
+        version: None,
+        admin: None,
+        commission_rate: None,
+        reward_address: None,
+        spread_address: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    let asset_infos = [
+        AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: USDT_DENOM.to_string(),
+        },
+    ];
+    let msg = ExecuteMsg::CreateOrderBookPair {
+        base_coin_info: asset_infos[0].clone(),
+        quote_coin_info: asset_infos[1].clone(),
+        spread: None,
+        min_quote_coin_amount: Uint128::zero(),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    let update_msg = |max_executions_per_block: Option<u32>| ExecuteMsg::UpdateOrderBookPair {
+        asset_infos: asset_infos.clone(),
+        settings: OrderBookSettings {
+            max_executions_per_block,
+            ..Default::default()
+        },
+    };
+
+    // zero removes the limit
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        update_msg(Some(0)),
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        update_msg(Some(2)),
+    )
+    .unwrap();
+
+    let execute_msg = ExecuteMsg::ExecuteOrderBookPair {
+        asset_infos: asset_infos.clone(),
+        limit: None,
+    };
+    let query_orderbook = |deps: Deps| {
+        from_binary::<OrderBookResponse>(
+            &query(
+                deps,
+                mock_env(),
+                QueryMsg::OrderBook {
+                    asset_infos: asset_infos.clone(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap()
+    };
+
+    for _ in 0..2 {
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("relayer", &[]),
+            execute_msg.clone(),
+        )
+        .unwrap();
+        assert!(!res.attributes.contains(&attr("throttled", "true")));
+    }
+    let order_book = query_orderbook(deps.as_ref());
+    assert_eq!(order_book.max_executions_per_block, Some(2));
+    assert_eq!(order_book.executions_in_block, 2);
+
+    // a third execution in the same block is a no-op
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("relayer2", &[]),
+        execute_msg.clone(),
+    )
+    .unwrap();
+    assert!(res.attributes.contains(&attr("throttled", "true")));
+    assert!(res.messages.is_empty());
+    let order_book = query_orderbook(deps.as_ref());
+    assert_eq!(order_book.executions_in_block, 2);
+    assert_eq!(
+        order_book.last_executed_by,
+        Some(Addr::unchecked("relayer"))
+    );
+
+    // the counter restarts in the next block
+    let mut env = mock_env();
+    env.block.height += 1;
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("relayer2", &[]),
+        execute_msg,
+    )
+    .unwrap();
+    assert!(!res.attributes.contains(&attr("throttled", "true")));
+    let order_book = query_orderbook(deps.as_ref());
+    assert_eq!(order_book.last_executed_height, Some(env.block.height));
+    assert_eq!(order_book.executions_in_block, 1);
+}
+
+#[test]
+fn pay_referrers_from_commission() {
+    let mut deps = mock_dependencies();
+
+    // addr0000 is referred at 20% of its commission, addr0001 has no referrer
+    deps.querier.update_wasm(|query| match query {
+        WasmQuery::Smart { contract_addr, msg } if contract_addr == "referral" => {
+            match from_binary(msg).unwrap() {
+                ReferralQueryMsg::Referral { trader } => {
+                    let referrer = match trader.as_str() {
+                        "addr0000" => Some(Addr::unchecked("referrer")),
+                        _ => None,
+                    };
+                    SystemResult::Ok(ContractResult::Ok(
+                        to_binary(&ReferralResponse {
+                            trader,
+                            rate_bps: if referrer.is_some() { 2000 } else { 0 },
+                            referrer,
+                        })
+                        .unwrap(),
+                    ))
+                }
+                _ => panic!("unexpected query"),
+            }
+        }
+        _ => panic!("unexpected query"),
+    });
+
+    let msg = InstantiateMsg {
+        name: None,
+        version: None,
+        admin: None,
+        commission_rate: None,
+        reward_address: Some(Addr::unchecked("reward")),
+        spread_address: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    // only a super admin can set the referral contract
+    let msg = ExecuteMsg::UpdateConfig {
+        reward_address: None,
+        spread_address: None,
+        commission_rate: None,
+        guardian: None,
+        maker_rebate_rate: None,
+        event_mode: None,
+        rewarder: None,
+        referral: Some(Addr::unchecked("referral")),
+        oracle: None,
+        converter: None,
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+    let contract_info: ContractInfoResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::ContractInfo {}).unwrap()).unwrap();
+    assert_eq!(contract_info.referral, Some(Addr::unchecked("referral")));
+
+    let asset_infos = [
+        AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: USDT_DENOM.to_string(),
+        },
+    ];
+    let msg = ExecuteMsg::CreateOrderBookPair {
+        base_coin_info: asset_infos[0].clone(),
+        quote_coin_info: asset_infos[1].clone(),
+        spread: None,
+        min_quote_coin_amount: Uint128::zero(),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    // buy 1000000 orai with 2000000 usdt and sell 1000000 orai for 2000000 usdt
+    for (sender, direction, denom, amount) in [
+        ("addr0000", OrderDirection::Buy, USDT_DENOM, 2000000u128),
+        ("addr0001", OrderDirection::Sell, ORAI_DENOM, 1000000u128),
+    ] {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(sender, &[coin(amount, denom)]),
+            ExecuteMsg::SubmitOrder {
+                direction,
+                assets: [
+                    Asset {
+                        info: asset_infos[0].clone(),
+                        amount: Uint128::from(1000000u128),
+                    },
+                    Asset {
+                        info: asset_infos[1].clone(),
+                        amount: Uint128::from(2000000u128),
+                    },
+                ],
+                client_id: None,
+            },
+        )
+        .unwrap();
+    }
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("relayer", &[]),
+        ExecuteMsg::ExecuteOrderBookPair {
+            asset_infos: asset_infos.clone(),
+            limit: None,
+        },
+    )
+    .unwrap();
+
+    // the referrer is paid 20% of the 1000 orai commission of the buyer, the seller is unreferred
+    let referral_msgs: Vec<&SubMsg> = res
+        .messages
+        .iter()
+        .filter(|sub_msg| {
+            matches!(&sub_msg.msg, CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, .. })
+                if contract_addr == "referral")
+        })
+        .collect();
+    assert_eq!(referral_msgs.len(), 1);
+    assert_eq!(referral_msgs[0].reply_on, ReplyOn::Error);
+    assert_eq!(
+        referral_msgs[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "referral".to_string(),
+            funds: vec![coin(200u128, ORAI_DENOM)],
+            msg: to_binary(&ReferralExecuteMsg::RecordRewards {
+                rewards: vec![ReferralReward {
+                    referrer: Addr::unchecked("referrer"),
+                    amount: Uint128::from(200u128),
+                }],
+            })
+            .unwrap(),
+        })
+    );
+
+    // a failing referral contract does not revert the matching, the share is escrowed for the
+    // reward address
+    reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: referral_msgs[0].id,
+            result: SubMsgResult::Err("referral failed".to_string()),
+        },
+    )
+    .unwrap();
+    let res: EscrowResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Escrow {
+                address: Addr::unchecked("reward"),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.assets,
+        vec![Asset {
+            info: asset_infos[0].clone(),
+            amount: Uint128::from(200u128),
+        }]
+    );
+}
+
+#[test]
+fn execute_orderbook_pair_resumes_after_limit() {
+    let mut deps = mock_dependencies();
+
+    let msg = InstantiateMsg {
+        name: None,
+        version: None,
+        admin: None,
+        commission_rate: None,
+        reward_address: None,
+        spread_address: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    let asset_infos = [
+        AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: USDT_DENOM.to_string(),
+        },
+    ];
+    let msg = ExecuteMsg::CreateOrderBookPair {
+        base_coin_info: asset_infos[0].clone(),
+        quote_coin_info: asset_infos[1].clone(),
+        spread: None,
+        min_quote_coin_amount: Uint128::zero(),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    // buy 1000 orai at 2 and 1.9, sell 1000 orai at 1.5 and 1.6
+    let submit_order =
+        |deps: DepsMut, sender: &str, direction: OrderDirection, quote_amount: u128| {
+            let funds = match direction {
+                OrderDirection::Buy => coin(quote_amount, USDT_DENOM),
+                OrderDirection::Sell => coin(1000u128, ORAI_DENOM),
+            };
+            execute(
+                deps,
+                mock_env(),
+                mock_info(sender, &[funds]),
+                ExecuteMsg::SubmitOrder {
+                    direction,
+                    assets: [
+                        Asset {
+                            info: asset_infos[0].clone(),
+                            amount: Uint128::from(1000u128),
+                        },
+                        Asset {
+                            info: asset_infos[1].clone(),
+                            amount: Uint128::from(quote_amount),
+                        },
+                    ],
+                    client_id: None,
+                },
+            )
+            .unwrap();
+        };
+    submit_order(deps.as_mut(), "addr0000", OrderDirection::Buy, 2000);
+    submit_order(deps.as_mut(), "addr0000", OrderDirection::Buy, 1900);
+    submit_order(deps.as_mut(), "addr0001", OrderDirection::Sell, 1500);
+    submit_order(deps.as_mut(), "addr0001", OrderDirection::Sell, 1600);
+
+    let execute_msg = ExecuteMsg::ExecuteOrderBookPair {
+        asset_infos: asset_infos.clone(),
+        limit: Some(1),
+    };
+    let order_msg = |order_id: u64| QueryMsg::Order {
+        order_id,
+        asset_infos: asset_infos.clone(),
+    };
+
+    // the limit stops matching at the best price levels
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("relayer", &[]),
+        execute_msg.clone(),
+    )
+    .unwrap();
+    assert!(res.attributes.contains(&attr("total_matched_orders", "2")));
+    assert!(query(deps.as_ref(), mock_env(), order_msg(1)).is_err());
+    assert!(query(deps.as_ref(), mock_env(), order_msg(3)).is_err());
+
+    // the matched orders are removed, so the next call resumes at the next price levels
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("relayer", &[]),
+        execute_msg,
+    )
+    .unwrap();
+    assert!(res.attributes.contains(&attr("total_matched_orders", "2")));
+    assert!(query(deps.as_ref(), mock_env(), order_msg(2)).is_err());
+    assert!(query(deps.as_ref(), mock_env(), order_msg(4)).is_err());
+}
+
+#[test]
+fn relayer_fee_quoted_in_usd() {
+    let mut deps = mock_dependencies();
+
+    // 1 usd is worth 2 orai
+    deps.querier.update_wasm(|query| match query {
+        WasmQuery::Smart { contract_addr, msg } if contract_addr == "oracle" => {
+            match from_binary(msg).unwrap() {
+                OracleExchangeQuery::ConvertAmount { from, to, amount } => {
+                    assert_eq!((from.as_str(), to.as_str()), ("usd", ORAI_DENOM));
+                    SystemResult::Ok(ContractResult::Ok(
+                        to_binary(&ConvertAmountResponse {
+                            amount: amount * Uint128::from(2u128),
+                            path: vec![],
+                        })
+                        .unwrap(),
+                    ))
+                }
+                _ => panic!("unexpected query"),
+            }
+        }
+        _ => panic!("unexpected query"),
+    });
+
+    let msg = InstantiateMsg {
+        name: None,
+        version: None,
+        admin: None,
+        commission_rate: None,
+        reward_address: None,
+        spread_address: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    let asset_infos = [
+        AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: USDT_DENOM.to_string(),
+        },
+    ];
+    let msg = ExecuteMsg::CreateOrderBookPair {
+        base_coin_info: asset_infos[0].clone(),
+        quote_coin_info: asset_infos[1].clone(),
+        spread: None,
+        min_quote_coin_amount: Uint128::zero(),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    // 1% of the filled amount, at least 10 usd and at most 50 usd
+    let update_msg = ExecuteMsg::UpdateOrderBookPair {
+        asset_infos: asset_infos.clone(),
+        settings: OrderBookSettings {
+            relayer_fee_rate: Some(Decimal::percent(1)),
+            min_relayer_fee: Some(Uint128::from(10u128)),
+            max_relayer_fee: Some(Uint128::from(50u128)),
+            fee_usd_denom: Some("usd".to_string()),
+            ..Default::default()
+        },
+    };
+
+    // the fees can not be converted without the oracle
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        update_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Std(StdError::generic_err(
+            "oracle is not set to convert the usd fees"
+        ))
+    );
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        ExecuteMsg::UpdateConfig {
+            reward_address: None,
+            spread_address: None,
+            commission_rate: None,
+            guardian: None,
+            maker_rebate_rate: None,
+            event_mode: None,
+            rewarder: None,
+            referral: None,
+            oracle: Some(Addr::unchecked("oracle")),
+            converter: None,
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        update_msg,
+    )
+    .unwrap();
+
+    let order_book: OrderBookResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::OrderBook {
+                asset_infos: asset_infos.clone(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(order_book.fee_usd_denom, Some("usd".to_string()));
+
+    // buy 1000 orai with 2000 usdt and sell 1000 orai for 2000 usdt
+    for (sender, direction, funds) in [
+        ("addr0000", OrderDirection::Buy, coin(2000u128, USDT_DENOM)),
+        ("addr0001", OrderDirection::Sell, coin(1000u128, ORAI_DENOM)),
+    ] {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(sender, &[funds]),
+            ExecuteMsg::SubmitOrder {
+                direction,
+                assets: [
+                    Asset {
+                        info: asset_infos[0].clone(),
+                        amount: Uint128::from(1000u128),
+                    },
+                    Asset {
+                        info: asset_infos[1].clone(),
+                        amount: Uint128::from(2000u128),
+                    },
+                ],
+                client_id: None,
+            },
+        )
+        .unwrap();
+    }
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("relayer", &[]),
+        ExecuteMsg::ExecuteOrderBookPair {
+            asset_infos,
+            limit: None,
+        },
+    )
+    .unwrap();
+
+    // the min fee of 10 usd is 20 orai for the buyer, and 20 orai valued at price 2 for the seller
+    let relayer_fees: Vec<String> = res
+        .events
+        .iter()
+        .flat_map(|event| event.attributes.iter())
+        .filter(|attr| attr.key == "relayer_fee")
+        .map(|attr| attr.value.clone())
+        .collect();
+    assert_eq!(
+        relayer_fees,
+        vec![format!("20 {}", ORAI_DENOM), format!("40 {}", USDT_DENOM)]
+    );
+}
+
+#[test]
+fn execute_orderbook_pair_with_min_fill_ratio() {
+    let mut deps = mock_dependencies();
+
+    let msg = InstantiateMsg {
+        name: None,
+        version: None,
+        admin: None,
+        commission_rate: None,
+        reward_address: None,
+        spread_address: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    let asset_infos = [
+        AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: USDT_DENOM.to_string(),
+        },
+    ];
+    let msg = ExecuteMsg::CreateOrderBookPair {
+        base_coin_info: asset_infos[0].clone(),
+        quote_coin_info: asset_infos[1].clone(),
+        spread: None,
+        min_quote_coin_amount: Uint128::zero(),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    let update_msg =
+        |min_fill_ratio: &str, min_fill_base: u128| ExecuteMsg::UpdateOrderBookParams {
+            asset_infos: asset_infos.clone(),
+            spread: None,
+            min_quote_coin_amount: None,
+            paused: None,
+            price_precision: None,
+            min_quote_notional: None,
+            min_fill_ratio: Some(Decimal::from_str(min_fill_ratio).unwrap()),
+            min_fill_base: Some(Uint128::from(min_fill_base)),
+        };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        update_msg("1.1", 0),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Std(StdError::generic_err(
+            "min fill ratio must not be greater than 1"
+        ))
+    );
+
+    // a match must fill half of the remaining base amount of the sell price level
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        update_msg("0.5", 0),
+    )
+    .unwrap();
+    assert!(res.attributes.contains(&attr("min_fill_ratio", "0.5")));
+    assert!(res.attributes.contains(&attr("min_fill_base", "0")));
+
+    let submit_order = |deps: DepsMut,
+                        sender: &str,
+                        direction: OrderDirection,
+                        base_amount: u128,
+                        quote_amount: u128| {
+        let funds = match direction {
+            OrderDirection::Buy => coin(quote_amount, USDT_DENOM),
+            OrderDirection::Sell => coin(base_amount, ORAI_DENOM),
+        };
+        execute(
+            deps,
+            mock_env(),
+            mock_info(sender, &[funds]),
+            ExecuteMsg::SubmitOrder {
+                direction,
+                assets: [
+                    Asset {
+                        info: asset_infos[0].clone(),
+                        amount: Uint128::from(base_amount),
+                    },
+                    Asset {
+                        info: asset_infos[1].clone(),
+                        amount: Uint128::from(quote_amount),
+                    },
+                ],
+                client_id: None,
+            },
+        )
+        .unwrap();
+    };
+    // a dust buy order at the best price does not block the matching, the sell order
+    // is filled by the dust buy order then by the buy price level at 1.9
+    submit_order(deps.as_mut(), "addr0000", OrderDirection::Buy, 20, 40);
+    submit_order(deps.as_mut(), "addr0002", OrderDirection::Buy, 1000, 1900);
+    submit_order(deps.as_mut(), "addr0001", OrderDirection::Sell, 1000, 1500);
+
+    let execute_msg = ExecuteMsg::ExecuteOrderBookPair {
+        asset_infos: asset_infos.clone(),
+        limit: None,
+    };
+    let order_msg = |order_id: u64| QueryMsg::Order {
+        order_id,
+        asset_infos: asset_infos.clone(),
+    };
+    let query_order = |deps: Deps, order_id: u64| -> OrderResponse {
+        from_binary(&query(deps, mock_env(), order_msg(order_id)).unwrap()).unwrap()
+    };
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("relayer", &[]),
+        execute_msg.clone(),
+    )
+    .unwrap();
+    assert!(query(deps.as_ref(), mock_env(), order_msg(1)).is_err());
+    assert!(query(deps.as_ref(), mock_env(), order_msg(3)).is_err());
+    let order = query_order(deps.as_ref(), 2);
+    assert_eq!(order.status, OrderStatus::PartialFilled);
+    assert_eq!(order.filled_ask_amount, Uint128::from(980u128));
+    assert_eq!(order.filled_offer_amount, Uint128::from(1862u128));
+
+    // the 20 orai left to buy would fill 10 orai of the 1000 orai sell order, it keeps resting
+    // and the sell order before it at the same price is matched alone
+    submit_order(deps.as_mut(), "addr0001", OrderDirection::Sell, 10, 15);
+    submit_order(deps.as_mut(), "addr0003", OrderDirection::Sell, 1000, 1500);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("relayer", &[]),
+        execute_msg.clone(),
+    )
+    .unwrap();
+    assert!(query(deps.as_ref(), mock_env(), order_msg(4)).is_err());
+    let order = query_order(deps.as_ref(), 5);
+    assert_eq!(order.status, OrderStatus::Open);
+    assert_eq!(order.filled_offer_amount, Uint128::zero());
+    let order = query_order(deps.as_ref(), 2);
+    assert_eq!(order.status, OrderStatus::PartialFilled);
+    assert_eq!(order.filled_ask_amount, Uint128::from(990u128));
+
+    // filling min_fill_base passes the min fill ratio
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        update_msg("0.5", 10),
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("relayer", &[]),
+        execute_msg,
+    )
+    .unwrap();
+    assert!(query(deps.as_ref(), mock_env(), order_msg(2)).is_err());
+    let order = query_order(deps.as_ref(), 5);
+    assert_eq!(order.status, OrderStatus::PartialFilled);
+    assert_eq!(order.filled_offer_amount, Uint128::from(10u128));
+}
+
+#[test]
+fn create_orderbook_pair_with_equivalent_assets() {
+    let mut deps = mock_dependencies();
+
+    let msg = InstantiateMsg {
+        name: None,
+        version: None,
+        admin: None,
+        commission_rate: None,
+        reward_address: Some(Addr::unchecked("reward")),
+        spread_address: Some(Addr::unchecked("spread")),
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    let orai = AssetInfo::NativeToken {
+        denom: ORAI_DENOM.to_string(),
+    };
+    let usdt = AssetInfo::NativeToken {
+        denom: USDT_DENOM.to_string(),
+    };
+    let legacy_orai = AssetInfo::NativeToken {
+        denom: "legacy_orai".to_string(),
+    };
+    let create_pair =
+        |base_coin_info: &AssetInfo, quote_coin_info: &AssetInfo| ExecuteMsg::CreateOrderBookPair {
+            base_coin_info: base_coin_info.clone(),
+            quote_coin_info: quote_coin_info.clone(),
+            spread: None,
+            min_quote_coin_amount: Uint128::from(10u128),
+        };
+
+    // an asset can not be paired with itself
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        create_pair(&orai, &orai),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::IdenticalOrderBookAssets {
+            base_coin: orai.to_string(),
+            quote_coin: orai.to_string(),
+        }
+    );
+
+    // the reversed pair has the same key as the created pair
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        create_pair(&orai, &usdt),
+    )
+    .unwrap();
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        create_pair(&usdt, &orai),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::OrderBookAlreadyExists {});
+
+    // the converter converts the legacy orai into orai
+    deps.querier.update_wasm(|query| match query {
+        WasmQuery::Smart { contract_addr, msg } if contract_addr == "converter" => {
+            match from_binary(msg).unwrap() {
+                ConverterQueryMsg::ConvertInfo {
+                    asset_info: AssetInfo::NativeToken { denom },
+                } if denom == "legacy_orai" => SystemResult::Ok(ContractResult::Ok(
+                    to_binary(&ConvertInfoResponse {
+                        token_ratio: TokenRatio {
+                            info: AssetInfo::NativeToken {
+                                denom: ORAI_DENOM.to_string(),
+                            },
+                            ratio: Decimal::one(),
+                        },
+                    })
+                    .unwrap(),
+                )),
+                _ => SystemResult::Ok(ContractResult::Err("not found".to_string())),
+            }
+        }
+        _ => SystemResult::Ok(ContractResult::Err("unknown contract".to_string())),
+    });
+
+    // without the converter the legacy asset is a different asset
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        create_pair(&legacy_orai, &usdt),
+    )
+    .unwrap();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        ExecuteMsg::UpdateConfig {
+            reward_address: None,
+            spread_address: None,
+            commission_rate: None,
+            guardian: None,
+            maker_rebate_rate: None,
+            event_mode: None,
+            rewarder: None,
+            referral: None,
+            oracle: None,
+            converter: Some(Addr::unchecked("converter")),
+        },
+    )
+    .unwrap();
+    let res: ContractInfoResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::ContractInfo {}).unwrap()).unwrap();
+    assert_eq!(res.converter, Some(Addr::unchecked("converter")));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        create_pair(&orai, &legacy_orai),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::IdenticalOrderBookAssets {
+            base_coin: orai.to_string(),
+            quote_coin: legacy_orai.to_string(),
+        }
+    );
+}
+
+#[test]
+fn update_orderbook_pair_keeps_omitted_settings() {
+    let mut deps = mock_dependencies();
+
+    // reference pool of the order book assets
+    deps.querier.update_wasm(|_| {
+        SystemResult::Ok(ContractResult::Ok(
+            to_binary(&PoolResponse {
+                assets: [
+                    Asset {
+                        info: AssetInfo::NativeToken {
+                            denom: ORAI_DENOM.to_string(),
+                        },
+                        amount: Uint128::from(1000000u128),
+                    },
+                    Asset {
+                        info: AssetInfo::NativeToken {
+                            denom: USDT_DENOM.to_string(),
+                        },
+                        amount: Uint128::from(2000000u128),
+                    },
+                ],
+                total_share: Uint128::from(1000000u128),
+            })
+            .unwrap(),
+        ))
+    });
+
+    let msg = InstantiateMsg {
+        name: None,
+        version: None,
+        admin: None,
+        commission_rate: None,
+        reward_address: Some(Addr::unchecked("reward")),
+        spread_address: Some(Addr::unchecked("spread")),
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    // the usd denom of the relayer fees needs the oracle
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        ExecuteMsg::UpdateConfig {
+            reward_address: None,
+            spread_address: None,
+            commission_rate: None,
+            guardian: None,
+            maker_rebate_rate: None,
+            event_mode: None,
+            rewarder: None,
+            referral: None,
+            oracle: Some(Addr::unchecked("oracle")),
+            converter: None,
+        },
+    )
+    .unwrap();
+
+    let asset_infos = [
+        AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: USDT_DENOM.to_string(),
+        },
+    ];
+
+    let msg = ExecuteMsg::CreateOrderBookPair {
+        base_coin_info: asset_infos[0].clone(),
+        quote_coin_info: asset_infos[1].clone(),
+        spread: None,
+        min_quote_coin_amount: Uint128::zero(),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    let query_orderbook = |deps: Deps| {
+        from_binary::<OrderBookResponse>(
+            &query(
+                deps,
+                mock_env(),
+                QueryMsg::OrderBook {
+                    asset_infos: asset_infos.clone(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap()
+    };
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        ExecuteMsg::UpdateOrderBookPair {
+            asset_infos: asset_infos.clone(),
+            settings: OrderBookSettings {
+                min_order_lifetime_seconds: Some(60),
+                cancel_fee_rate: Some(Decimal::percent(1)),
+                free_cancel_blocks: Some(5),
+                max_executions_per_block: Some(2),
+                price_reference: Some(Addr::unchecked("pair")),
+                max_price_deviation: Some(Decimal::percent(10)),
+                relayer_fee_rate: Some(Decimal::percent(1)),
+                min_relayer_fee: Some(Uint128::from(20u128)),
+                max_relayer_fee: Some(Uint128::from(50u128)),
+                fee_usd_denom: Some("usd".to_string()),
+                max_orders_per_user: Some(10),
+                incentive_price_band: Some(Decimal::percent(5)),
+            },
+        },
+    )
+    .unwrap();
+    let mut expected = query_orderbook(deps.as_ref());
+    assert_eq!(expected.min_order_lifetime_seconds, Some(60));
+    assert_eq!(expected.price_reference, Some(Addr::unchecked("pair")));
+    assert_eq!(expected.fee_usd_denom, Some("usd".to_string()));
+    assert_eq!(expected.incentive_price_band, Some(Decimal::percent(5)));
+
+    // each update only changes its own setting, a zero or empty value removes it
+    let updates: Vec<(OrderBookSettings, fn(&mut OrderBookResponse))> = vec![
+        (OrderBookSettings::default(), |_| {}),
+        (
+            OrderBookSettings {
+                min_order_lifetime_seconds: Some(0),
+                ..Default::default()
+            },
+            |res| res.min_order_lifetime_seconds = None,
+        ),
+        (
+            OrderBookSettings {
+                cancel_fee_rate: Some(Decimal::percent(2)),
+                ..Default::default()
+            },
+            |res| res.cancel_fee_rate = Some(Decimal::percent(2)),
+        ),
+        (
+            OrderBookSettings {
+                free_cancel_blocks: Some(10),
+                ..Default::default()
+            },
+            |res| res.free_cancel_blocks = Some(10),
+        ),
+        (
+            OrderBookSettings {
+                max_executions_per_block: Some(0),
+                ..Default::default()
+            },
+            |res| res.max_executions_per_block = None,
+        ),
+        (
+            OrderBookSettings {
+                price_reference: Some(Addr::unchecked("")),
+                ..Default::default()
+            },
+            |res| res.price_reference = None,
+        ),
+        (
+            OrderBookSettings {
+                max_price_deviation: Some(Decimal::percent(20)),
+                ..Default::default()
+            },
+            |res| res.max_price_deviation = Some(Decimal::percent(20)),
+        ),
+        (
+            OrderBookSettings {
+                relayer_fee_rate: Some(Decimal::percent(2)),
+                ..Default::default()
+            },
+            |res| res.relayer_fee_rate = Some(Decimal::percent(2)),
+        ),
+        (
+            OrderBookSettings {
+                min_relayer_fee: Some(Uint128::zero()),
+                ..Default::default()
+            },
+            |res| res.min_relayer_fee = None,
+        ),
+        (
+            OrderBookSettings {
+                max_relayer_fee: Some(Uint128::from(40u128)),
+                ..Default::default()
+            },
+            |res| res.max_relayer_fee = Some(Uint128::from(40u128)),
+        ),
+        (
+            OrderBookSettings {
+                fee_usd_denom: Some(String::new()),
+                ..Default::default()
+            },
+            |res| res.fee_usd_denom = None,
+        ),
+        (
+            OrderBookSettings {
+                max_orders_per_user: Some(20),
+                ..Default::default()
+            },
+            |res| res.max_orders_per_user = Some(20),
+        ),
+        (
+            OrderBookSettings {
+                incentive_price_band: Some(Decimal::zero()),
+                ..Default::default()
+            },
+            |res| res.incentive_price_band = None,
+        ),
+    ];
+    for (settings, apply) in updates {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin", &[]),
+            ExecuteMsg::UpdateOrderBookPair {
+                asset_infos: asset_infos.clone(),
+                settings,
+            },
+        )
+        .unwrap();
+        apply(&mut expected);
+        assert_eq!(query_orderbook(deps.as_ref()), expected);
+    }
+
+    // omitted settings deserialize as kept
+    let settings: OrderBookSettings = from_slice(br#"{"max_orders_per_user":30}"#).unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        ExecuteMsg::UpdateOrderBookPair {
+            asset_infos: asset_infos.clone(),
+            settings,
+        },
+    )
+    .unwrap();
+    expected.max_orders_per_user = Some(30);
+    assert_eq!(query_orderbook(deps.as_ref()), expected);
+}
+
+#[test]
+fn submit_order_with_price_band() {
+    let mut deps = mock_dependencies();
+
+    // reference pool price is 2 usdt per orai
+    deps.querier.update_wasm(|_| {
+        SystemResult::Ok(ContractResult::Ok(
+            to_binary(&PoolResponse {
+                assets: [
+                    Asset {
+                        info: AssetInfo::NativeToken {
+                            denom: ORAI_DENOM.to_string(),
+                        },
+                        amount: Uint128::from(1000000u128),
+                    },
+                    Asset {
+                        info: AssetInfo::NativeToken {
+                            denom: USDT_DENOM.to_string(),
+                        },
+                        amount: Uint128::from(2000000u128),
+                    },
+                ],
+                total_share: Uint128::from(1000000u128),
+            })
+            .unwrap(),
+        ))
+    });
+
+    let msg = InstantiateMsg {
+        name: None,
+        version: None,
+        admin: None,
+        commission_rate: None,
+        reward_address: None,
+        spread_address: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    let asset_infos = [
+        AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: USDT_DENOM.to_string(),
+        },
+    ];
+
+    let msg = ExecuteMsg::CreateOrderBookPair {
+        base_coin_info: asset_infos[0].clone(),
+        quote_coin_info: asset_infos[1].clone(),
+        spread: None,
+        min_quote_coin_amount: Uint128::zero(),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    // allow 10% deviation from the orai/usdt pair pool price
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        ExecuteMsg::UpdateOrderBookPair {
+            asset_infos: asset_infos.clone(),
+            settings: OrderBookSettings {
+                price_reference: Some(Addr::unchecked("pair")),
+                max_price_deviation: Some(Decimal::percent(10)),
+                ..Default::default()
+            },
+        },
+    )
+    .unwrap();
+
+    // buy orai at 20 usdt, a fat-finger price
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &coins(20000, USDT_DENOM)),
+        ExecuteMsg::SubmitOrder {
+            direction: OrderDirection::Buy,
+            assets: [
+                Asset {
+                    info: asset_infos[0].clone(),
+                    amount: Uint128::from(1000u128),
+                },
+                Asset {
+                    info: asset_infos[1].clone(),
+                    amount: Uint128::from(20000u128),
+                },
+            ],
+            client_id: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::PriceOutOfBand {
+            price: Decimal::from_ratio(20u128, 1u128),
+            reference_price: Decimal::from_str("2").unwrap(),
+        }
+    );
+
+    // buy orai at 2.1 usdt is within the band
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &coins(2100, USDT_DENOM)),
+        ExecuteMsg::SubmitOrder {
+            direction: OrderDirection::Buy,
+            assets: [
+                Asset {
+                    info: asset_infos[0].clone(),
+                    amount: Uint128::from(1000u128),
+                },
+                Asset {
+                    info: asset_infos[1].clone(),
+                    amount: Uint128::from(2100u128),
+                },
+            ],
+            client_id: None,
+        },
+    )
+    .unwrap();
+
+    // sell orai at 0.2 usdt
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &coins(1000, ORAI_DENOM)),
+        ExecuteMsg::SubmitOrder {
+            direction: OrderDirection::Sell,
+            assets: [
+                Asset {
+                    info: asset_infos[0].clone(),
+                    amount: Uint128::from(1000u128),
+                },
+                Asset {
+                    info: asset_infos[1].clone(),
+                    amount: Uint128::from(200u128),
+                },
+            ],
+            client_id: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::PriceOutOfBand {
+            price: Decimal::from_ratio(1u128, 5u128),
+            reference_price: Decimal::from_str("2").unwrap(),
+        }
+    );
+
+    let res: OrderBookResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::OrderBook { asset_infos },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.price_reference, Some(Addr::unchecked("pair")));
+    assert_eq!(res.max_price_deviation, Some(Decimal::percent(10)));
+}
+
+#[test]
+fn submit_order_with_max_orders_per_user() {
+    let mut deps = mock_dependencies();
+
+    let msg = InstantiateMsg {
+        name: None,
+        version: None,
+        admin: None,
+        commission_rate: None,
+        reward_address: None,
+        spread_address: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    let asset_infos = [
+        AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: USDT_DENOM.to_string(),
+        },
+    ];
+
+    let msg = ExecuteMsg::CreateOrderBookPair {
+        base_coin_info: asset_infos[0].clone(),
+        quote_coin_info: asset_infos[1].clone(),
+        spread: None,
+        min_quote_coin_amount: Uint128::zero(),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    let update_msg = |max_orders_per_user: Option<u32>| ExecuteMsg::UpdateOrderBookPair {
+        asset_infos: asset_infos.clone(),
+        settings: OrderBookSettings {
+            max_orders_per_user,
+            ..Default::default()
+        },
+    };
+
+    // only admin can update, and zero removes the limit
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        update_msg(Some(2)),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        update_msg(Some(0)),
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        update_msg(Some(2)),
+    )
+    .unwrap();
+
+    let submit_msg = ExecuteMsg::SubmitOrder {
+        direction: OrderDirection::Buy,
+        assets: [
+            Asset {
+                info: asset_infos[0].clone(),
+                amount: Uint128::from(1000u128),
+            },
+            Asset {
+                info: asset_infos[1].clone(),
+                amount: Uint128::from(2000u128),
+            },
+        ],
+        client_id: None,
+    };
+    for _ in 0..2 {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("addr0000", &coins(2000, USDT_DENOM)),
+            submit_msg.clone(),
+        )
+        .unwrap();
+    }
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &coins(2000, USDT_DENOM)),
+        submit_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::MaxOrdersPerUserReached {
+            max_orders_per_user: 2
+        }
+    );
+
+    // other bidders are not affected
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0001", &coins(2000, USDT_DENOM)),
+        submit_msg.clone(),
+    )
+    .unwrap();
+
+    // cancelling an order frees a slot
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::CancelOrder {
+            order_id: 1,
+            asset_infos: asset_infos.clone(),
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &coins(2000, USDT_DENOM)),
+        submit_msg,
+    )
+    .unwrap();
+
+    let res: OrderBookResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::OrderBook { asset_infos },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.max_orders_per_user, Some(2));
+}
+
+#[test]
+fn orderbook_incentives() {
+    let mut deps = mock_dependencies();
+
+    let msg = InstantiateMsg {
+        name: None,
+        version: None,
+        admin: None,
+        commission_rate: None,
+        reward_address: None,
+        spread_address: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    let asset_infos = [
+        AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: USDT_DENOM.to_string(),
+        },
+    ];
+
+    let msg = ExecuteMsg::CreateOrderBookPair {
+        base_coin_info: asset_infos[0].clone(),
+        quote_coin_info: asset_infos[1].clone(),
+        spread: None,
+        min_quote_coin_amount: Uint128::zero(),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    // resting orders within 10% of the mid price accrue incentive points
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        ExecuteMsg::UpdateOrderBookPair {
+            asset_infos: asset_infos.clone(),
+            settings: OrderBookSettings {
+                incentive_price_band: Some(Decimal::percent(10)),
+                ..Default::default()
+            },
+        },
+    )
+    .unwrap();
+
+    // a matching buy and sell at 2, resting orders at 1.9 and 2.1 in the band and at 1 out of the band
+    for (sender, direction, quote_amount) in [
+        ("addr0000", OrderDirection::Buy, 2000u128),
+        ("addr0001", OrderDirection::Sell, 2000u128),
+        ("addr0002", OrderDirection::Buy, 1900u128),
+        ("addr0003", OrderDirection::Sell, 2100u128),
+        ("addr0002", OrderDirection::Buy, 1000u128),
+    ] {
+        let funds = match direction {
+            OrderDirection::Buy => coins(quote_amount, USDT_DENOM),
+            OrderDirection::Sell => coins(1000, ORAI_DENOM),
+        };
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(sender, &funds),
+            ExecuteMsg::SubmitOrder {
+                direction,
+                assets: [
+                    Asset {
+                        info: asset_infos[0].clone(),
+                        amount: Uint128::from(1000u128),
+                    },
+                    Asset {
+                        info: asset_infos[1].clone(),
+                        amount: Uint128::from(quote_amount),
+                    },
+                ],
+                client_id: None,
+            },
+        )
+        .unwrap();
+    }
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("relayer", &[]),
+        ExecuteMsg::ExecuteOrderBookPair {
+            asset_infos: asset_infos.clone(),
+            limit: None,
+        },
+    )
+    .unwrap();
+    assert!(res.attributes.contains(&attr("incentive_points", "2000")));
+
+    let query_points = |deps: Deps, address: &str| {
+        from_binary::<IncentivePointsResponse>(
+            &query(
+                deps,
+                mock_env(),
+                QueryMsg::IncentivePoints {
+                    address: Addr::unchecked(address),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap()
+        .points
+    };
+    assert_eq!(
+        query_points(deps.as_ref(), "addr0002"),
+        Uint128::from(1000u128)
+    );
+    assert_eq!(
+        query_points(deps.as_ref(), "addr0003"),
+        Uint128::from(1000u128)
+    );
+    assert_eq!(query_points(deps.as_ref(), "addr0000"), Uint128::zero());
+
+    // no trades, no points
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("relayer", &[]),
+        ExecuteMsg::ExecuteOrderBookPair {
+            asset_infos: asset_infos.clone(),
+            limit: None,
+        },
+    )
+    .unwrap();
+    assert!(res.attributes.contains(&attr("incentive_points", "0")));
+
+    // the rewarder must be set to claim
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0002", &[]),
+        ExecuteMsg::ClaimIncentives {},
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Std(StdError::generic_err("Rewarder is not set"))
+    );
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        ExecuteMsg::UpdateConfig {
+            reward_address: None,
+            spread_address: None,
+            commission_rate: None,
+            guardian: None,
+            maker_rebate_rate: None,
+            event_mode: None,
+            rewarder: Some(Addr::unchecked("rewarder")),
+            referral: None,
+            oracle: None,
+            converter: None,
+        },
+    )
+    .unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0002", &[]),
+        ExecuteMsg::ClaimIncentives {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(WasmMsg::Execute {
+            contract_addr: "rewarder".to_string(),
+            msg: to_binary(&RewarderExecuteMsg::DistributeOrderBookIncentive {
+                recipient: Addr::unchecked("addr0002"),
+                points: Uint128::from(1000u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        })]
+    );
+    assert_eq!(query_points(deps.as_ref(), "addr0002"), Uint128::zero());
+
+    // nothing left to claim
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0002", &[]),
+        ExecuteMsg::ClaimIncentives {},
+    )
+    .unwrap_err();
+}
+
+#[test]
+fn submit_orders_in_batch() {
+    let mut deps = mock_dependencies();
+
+    let msg = InstantiateMsg {
+        name: None,
+        version: None,
+        admin: None,
+        commission_rate: None,
+        reward_address: None,
+        spread_address: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    let asset_infos = [
+        AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: USDT_DENOM.to_string(),
+        },
+    ];
+
+    let msg = ExecuteMsg::CreateOrderBookPair {
+        base_coin_info: asset_infos[0].clone(),
+        quote_coin_info: asset_infos[1].clone(),
+        spread: None,
+        min_quote_coin_amount: Uint128::zero(),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    // ladder two buy orders and one sell order
+    let direction_and_assets: Vec<DirectionAndAssets> = [
+        (OrderDirection::Buy, 1000u128, 2000u128),
+        (OrderDirection::Buy, 1000u128, 1900u128),
+        (OrderDirection::Sell, 500u128, 1100u128),
+    ]
+    .iter()
+    .map(|(direction, orai_amount, usdt_amount)| DirectionAndAssets {
+        direction: *direction,
+        assets: [
+            Asset {
+                info: asset_infos[0].clone(),
+                amount: Uint128::from(*orai_amount),
+            },
+            Asset {
+                info: asset_infos[1].clone(),
+                amount: Uint128::from(*usdt_amount),
+            },
+        ],
+    })
+    .collect();
+    let msg = ExecuteMsg::SubmitOrders {
+        direction_and_assets,
+    };
+
+    // sent funds do not cover all orders
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(
+            "addr0000",
+            &[
+                Coin {
+                    denom: ORAI_DENOM.to_string(),
+                    amount: Uint128::from(500u128),
+                },
+                Coin {
+                    denom: USDT_DENOM.to_string(),
+                    amount: Uint128::from(2000u128),
+                },
+            ],
+        ),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::AssetMismatch {});
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(
+            "addr0000",
+            &[
+                Coin {
+                    denom: ORAI_DENOM.to_string(),
+                    amount: Uint128::from(500u128),
+                },
+                Coin {
+                    denom: USDT_DENOM.to_string(),
+                    amount: Uint128::from(3900u128),
+                },
+            ],
+        ),
+        msg,
+    )
+    .unwrap();
+
+    let res: LastOrderIdResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::LastOrderId {}).unwrap()).unwrap();
+    assert_eq!(res.last_order_id, 3);
+
+    let res: OrderResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Order {
+                order_id: 2,
+                asset_infos: asset_infos.clone(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.direction, OrderDirection::Buy);
+    assert_eq!(res.offer_asset.amount, Uint128::from(1900u128));
+    assert_eq!(res.ask_asset.amount, Uint128::from(1000u128));
+
+    let res: OrderResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Order {
+                order_id: 3,
+                asset_infos,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.direction, OrderDirection::Sell);
+    assert_eq!(res.offer_asset.amount, Uint128::from(500u128));
+    assert_eq!(res.ask_asset.amount, Uint128::from(1100u128));
+}
+
+#[test]
+fn query_order_position() {
+    let mut deps = mock_dependencies();
+
+    let msg = InstantiateMsg {
+        name: None,
+        version: None,
+        admin: None,
+        commission_rate: None,
+        reward_address: None,
+        spread_address: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    let asset_infos = [
+        AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: USDT_DENOM.to_string(),
+        },
+    ];
+
+    let msg = ExecuteMsg::CreateOrderBookPair {
+        base_coin_info: asset_infos[0].clone(),
+        quote_coin_info: asset_infos[1].clone(),
+        spread: None,
+        min_quote_coin_amount: Uint128::zero(),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    // buy orders at price 2, 2.1, 2, 2 then sell orders at price 2, 1.9
+    for (direction, orai_amount, usdt_amount) in [
+        (OrderDirection::Buy, 1000u128, 2000u128),
+        (OrderDirection::Buy, 1000u128, 2100u128),
+        (OrderDirection::Buy, 2000u128, 4000u128),
+        (OrderDirection::Buy, 500u128, 1000u128),
+        (OrderDirection::Sell, 1000u128, 2000u128),
+        (OrderDirection::Sell, 1000u128, 1900u128),
+    ] {
+        let funds = match direction {
+            OrderDirection::Buy => coins(usdt_amount, USDT_DENOM),
+            OrderDirection::Sell => coins(orai_amount, ORAI_DENOM),
+        };
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("addr0000", &funds),
+            ExecuteMsg::SubmitOrder {
+                direction,
+                assets: [
+                    Asset {
+                        info: asset_infos[0].clone(),
+                        amount: Uint128::from(orai_amount),
+                    },
+                    Asset {
+                        info: asset_infos[1].clone(),
+                        amount: Uint128::from(usdt_amount),
+                    },
+                ],
+                client_id: None,
+            },
+        )
+        .unwrap();
+    }
+
+    let res: OrderPositionResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::OrderPosition {
+                asset_infos: asset_infos.clone(),
+                order_id: 4,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res,
+        OrderPositionResponse {
+            order_id: 4,
+            direction: OrderDirection::Buy,
+            price: Decimal::from_str("2").unwrap(),
+            position: 3,
+            amount_ahead_at_price: Uint128::from(6000u128),
+            total_amount_ahead: Uint128::from(8100u128),
+        }
+    );
+
+    // sell orders at the same price are not counted, the cheaper sell order is ahead
+    let res: OrderPositionResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::OrderPosition {
+                asset_infos,
+                order_id: 5,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res,
+        OrderPositionResponse {
+            order_id: 5,
+            direction: OrderDirection::Sell,
+            price: Decimal::from_str("2").unwrap(),
+            position: 1,
+            amount_ahead_at_price: Uint128::zero(),
+            total_amount_ahead: Uint128::from(1000u128),
+        }
+    );
+}
+
+#[test]
+fn submit_order_with_extra_denoms() {
+    let mut deps = mock_dependencies();
+
+    let msg = InstantiateMsg {
+        name: None,
+        version: None,
+        admin: None,
+        commission_rate: None,
+        reward_address: None,
+        spread_address: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    let asset_infos = [
+        AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: USDT_DENOM.to_string(),
+        },
+    ];
+
+    let msg = ExecuteMsg::CreateOrderBookPair {
+        base_coin_info: asset_infos[0].clone(),
+        quote_coin_info: asset_infos[1].clone(),
+        spread: None,
+        min_quote_coin_amount: Uint128::zero(),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::SubmitOrder {
+        direction: OrderDirection::Buy,
+        assets: [
+            Asset {
+                info: asset_infos[0].clone(),
+                amount: Uint128::from(1000u128),
+            },
+            Asset {
+                info: asset_infos[1].clone(),
+                amount: Uint128::from(2000u128),
+            },
+        ],
+        client_id: None,
+    };
+
+    // paid asset amount must still match
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(
+            "addr0000",
+            &[
+                Coin {
+                    denom: ATOM_DENOM.to_string(),
+                    amount: Uint128::from(100u128),
+                },
+                Coin {
+                    denom: USDT_DENOM.to_string(),
+                    amount: Uint128::from(1000u128),
+                },
+            ],
+        ),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Std(StdError::generic_err(
+            "Native token balance mismatch between the argument and the transferred"
+        ))
+    );
+
+    // other denoms are refunded to the bidder
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(
+            "addr0000",
+            &[
+                Coin {
+                    denom: ATOM_DENOM.to_string(),
+                    amount: Uint128::from(100u128),
+                },
+                Coin {
+                    denom: ORAI_DENOM.to_string(),
+                    amount: Uint128::from(300u128),
+                },
+                Coin {
+                    denom: USDT_DENOM.to_string(),
+                    amount: Uint128::from(2000u128),
+                },
+            ],
+        ),
+        msg,
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: "addr0000".to_string(),
+            amount: vec![
+                Coin {
+                    denom: ATOM_DENOM.to_string(),
+                    amount: Uint128::from(100u128),
+                },
+                Coin {
+                    denom: ORAI_DENOM.to_string(),
+                    amount: Uint128::from(300u128),
+                },
+            ],
+        })]
+    );
+
+    // batch submission refunds other denoms too
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(
+            "addr0000",
+            &[
+                Coin {
+                    denom: ATOM_DENOM.to_string(),
+                    amount: Uint128::from(100u128),
+                },
+                Coin {
+                    denom: USDT_DENOM.to_string(),
+                    amount: Uint128::from(2000u128),
+                },
+            ],
+        ),
+        ExecuteMsg::SubmitOrders {
+            direction_and_assets: vec![DirectionAndAssets {
+                direction: OrderDirection::Buy,
+                assets: [
+                    Asset {
+                        info: asset_infos[0].clone(),
+                        amount: Uint128::from(1000u128),
+                    },
+                    Asset {
+                        info: asset_infos[1].clone(),
+                        amount: Uint128::from(2000u128),
+                    },
+                ],
+            }],
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: "addr0000".to_string(),
+            amount: coins(100, ATOM_DENOM),
+        })]
+    );
+
+    let res: LastOrderIdResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::LastOrderId {}).unwrap()).unwrap();
+    assert_eq!(res.last_order_id, 2);
+}
+
+#[test]
+fn submit_order_token_with_allowance() {
+    let mut app = MockApp::new(&[]);
+    app.set_token_contract(Box::new(create_entry_points_testing!(oraiswap_token)));
+
+    let token_addrs = app.set_token_balances(&[(
+        &"assetA".to_string(),
+        &[(&"addr0000".to_string(), &Uint128::from(1000000u128))],
+    )]);
+
+    let msg = InstantiateMsg {
+        name: None,
+        version: None,
+        admin: None,
+        commission_rate: None,
+        reward_address: None,
+        spread_address: None,
+    };
+    let code_id = app.upload(Box::new(
+        create_entry_points_testing!(crate).with_reply(crate::contract::reply),
+    ));
+    let limit_order_addr = app
+        .instantiate(
+            code_id,
+            Addr::unchecked("addr0000"),
+            &msg,
+            &[],
+            "limit order",
+        )
+        .unwrap();
+
+    let asset_infos = [
+        AssetInfo::Token {
+            contract_addr: token_addrs[0].clone(),
+        },
+        AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+    ];
+
+    // create order book for pair [token_addrs[0], orai]
+    let msg = ExecuteMsg::CreateOrderBookPair {
+        base_coin_info: asset_infos[0].clone(),
+        quote_coin_info: asset_infos[1].clone(),
+        spread: None,
+        min_quote_coin_amount: Uint128::from(10u128),
+    };
+    let _res = app
+        .execute(
+            Addr::unchecked("addr0000"),
+            limit_order_addr.clone(),
+            &msg,
+            &[],
+        )
+        .unwrap();
+
+    // sell 1000 assetA for 2000 orai, paid from allowance instead of Send hook
+    let msg = ExecuteMsg::SubmitOrder {
+        direction: OrderDirection::Sell,
+        assets: [
+            Asset {
+                info: asset_infos[0].clone(),
+                amount: Uint128::from(1000u128),
+            },
+            Asset {
+                info: asset_infos[1].clone(),
+                amount: Uint128::from(2000u128),
+            },
+        ],
+        client_id: None,
+    };
+
+    // no allowance yet
+    let res = app.execute(
+        Addr::unchecked("addr0000"),
+        limit_order_addr.clone(),
+        &msg,
+        &[],
+    );
+    app.assert_fail(res);
+
+    let _res = app
+        .execute(
+            Addr::unchecked("addr0000"),
+            token_addrs[0].clone(),
+            &cw20::Cw20ExecuteMsg::IncreaseAllowance {
+                spender: limit_order_addr.to_string(),
+                amount: Uint128::from(1000u128),
+                expires: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+    let _res = app
+        .execute(
+            Addr::unchecked("addr0000"),
+            limit_order_addr.clone(),
+            &msg,
+            &[],
+        )
+        .unwrap();
+
+    let order = app
+        .query::<OrderResponse, _>(
+            limit_order_addr.clone(),
+            &QueryMsg::Order {
+                order_id: 1,
+                asset_infos: asset_infos.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(order.bidder_addr, "addr0000");
+    assert_eq!(order.direction, OrderDirection::Sell);
+    assert_eq!(order.offer_asset.amount, Uint128::from(1000u128));
+    assert_eq!(order.ask_asset.amount, Uint128::from(2000u128));
+
+    // offer amount is transferred to the limit order contract
+    for (address, balance) in [
+        ("addr0000", 999000u128),
+        (limit_order_addr.as_str(), 1000u128),
+    ] {
+        let res = app
+            .query::<cw20::BalanceResponse, _>(
+                token_addrs[0].clone(),
+                &cw20::Cw20QueryMsg::Balance {
+                    address: address.to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(res.balance, Uint128::from(balance));
+    }
+}
+
+thread_local! {
+    // whether the blacklist token refuses transfers to the blacklisted recipient
+    static BLACKLIST_ENABLED: Cell<bool> = Cell::new(true);
+}
+
+// cw20 token that refuses transfers to a blacklisted recipient
+fn blacklist_token_execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: cw20::Cw20ExecuteMsg,
+) -> StdResult<Response> {
+    if let cw20::Cw20ExecuteMsg::Transfer { recipient, .. } = &msg {
+        if recipient.eq("blacklisted") && BLACKLIST_ENABLED.with(|enabled| enabled.get()) {
+            return Err(StdError::generic_err("recipient is blacklisted"));
+        }
+    }
+    oraiswap_token::contract::execute(deps, env, info, msg)
+        .map_err(|err| StdError::generic_err(err.to_string()))
+}
+
+#[test]
+fn escrow_failed_payout() {
+    let mut app = MockApp::new(&[(
+        &"blacklisted".to_string(),
+        &[Coin {
+            denom: ORAI_DENOM.to_string(),
+            amount: Uint128::from(2000000u128),
+        }],
+    )]);
+    app.set_token_contract(Box::new(ContractWrapper::new(
+        blacklist_token_execute,
+        oraiswap_token::contract::instantiate,
+        oraiswap_token::contract::query,
+    )));
+    let token_addrs = app.set_token_balances(&[(
+        &"assetA".to_string(),
+        &[(&"addr0001".to_string(), &Uint128::from(1000000u128))],
+    )]);
+
+    let msg = InstantiateMsg {
+        name: None,
+        version: None,
+        admin: None,
+        commission_rate: None,
+        reward_address: None,
+        spread_address: None,
+    };
+    let code_id = app.upload(Box::new(
+        create_entry_points_testing!(crate).with_reply(crate::contract::reply),
+    ));
+    let limit_order_addr = app
+        .instantiate(
+            code_id,
+            Addr::unchecked("addr0000"),
+            &msg,
+            &[],
+            "limit order",
+        )
+        .unwrap();
+
+    let asset_infos = [
+        AssetInfo::Token {
+            contract_addr: token_addrs[0].clone(),
+        },
+        AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+    ];
+
+    // create order book for pair [assetA, orai]
+    let msg = ExecuteMsg::CreateOrderBookPair {
+        base_coin_info: asset_infos[0].clone(),
+        quote_coin_info: asset_infos[1].clone(),
+        spread: None,
+        min_quote_coin_amount: Uint128::zero(),
+    };
+    let _res = app
+        .execute(
+            Addr::unchecked("addr0000"),
+            limit_order_addr.clone(),
+            &msg,
+            &[],
+        )
+        .unwrap();
+
+    // buy 1000000 assetA with 2000000 orai and sell 1000000 assetA for 2000000 orai
+    let order_assets = [
+        Asset {
+            info: asset_infos[0].clone(),
+            amount: Uint128::from(1000000u128),
+        },
+        Asset {
+            info: asset_infos[1].clone(),
+            amount: Uint128::from(2000000u128),
+        },
+    ];
+    let _res = app
+        .execute(
+            Addr::unchecked("blacklisted"),
+            limit_order_addr.clone(),
+            &ExecuteMsg::SubmitOrder {
+                direction: OrderDirection::Buy,
+                assets: order_assets.clone(),
+                client_id: None,
+            },
+            &[Coin {
+                denom: ORAI_DENOM.to_string(),
+                amount: Uint128::from(2000000u128),
+            }],
+        )
+        .unwrap();
+    let _res = app
+        .execute(
+            Addr::unchecked("addr0001"),
+            token_addrs[0].clone(),
+            &cw20::Cw20ExecuteMsg::Send {
+                contract: limit_order_addr.to_string(),
+                amount: Uint128::from(1000000u128),
+                msg: to_binary(&Cw20HookMsg::SubmitOrder {
+                    direction: OrderDirection::Sell,
+                    assets: order_assets,
+                    client_id: None,
+                })
+                .unwrap(),
+            },
+            &[],
+        )
+        .unwrap();
+
+    // the assetA payout to the buyer fails but the matching still settles
+    let res = app
+        .execute(
+            Addr::unchecked("addr0002"),
+            limit_order_addr.clone(),
+            &ExecuteMsg::ExecuteOrderBookPair {
+                asset_infos: asset_infos.clone(),
+                limit: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+    let escrow_attrs: Vec<(String, String)> = res
+        .events
+        .iter()
+        .filter(|event| {
+            event
+                .attributes
+                .iter()
+                .any(|attr| attr.key == "action" && attr.value == "escrow_failed_payout")
+        })
+        .flat_map(|event| event.attributes.iter())
+        .filter(|attr| attr.key == "trader" || attr.key == "asset")
+        .map(|attr| (attr.key.clone(), attr.value.clone()))
+        .collect();
+    // 1000000 assetA minus 1000 commission and 300 relayer fee
+    assert_eq!(
+        escrow_attrs,
+        vec![
+            ("trader".to_string(), "blacklisted".to_string()),
+            ("asset".to_string(), format!("998700{}", token_addrs[0])),
+        ]
+    );
+
+    // seller receives 2000000 orai minus 2000 commission and 600 relayer fee
+    assert_eq!(
+        app.query_balance(Addr::unchecked("addr0001"), ORAI_DENOM.to_string())
+            .unwrap(),
+        Uint128::from(1997400u128)
+    );
+    assert_eq!(
+        query_token_balance(
+            &app.as_querier(),
+            token_addrs[0].clone(),
+            Addr::unchecked("blacklisted")
+        )
+        .unwrap(),
+        Uint128::zero()
+    );
+
+    // both orders are filled
+    let res = app
+        .query::<OrdersResponse, _>(
+            limit_order_addr.clone(),
+            &QueryMsg::Orders {
+                asset_infos,
+                direction: None,
+                filter: OrderFilter::None,
+                start_after: None,
+                limit: None,
+                order_by: None,
+                cursor: None,
+            },
+        )
+        .unwrap();
+    assert!(res.orders.is_empty());
+
+    let escrow = Asset {
+        info: AssetInfo::Token {
+            contract_addr: token_addrs[0].clone(),
+        },
+        amount: Uint128::from(998700u128),
+    };
+    let res = app
+        .query::<EscrowResponse, _>(
+            limit_order_addr.clone(),
+            &QueryMsg::Escrow {
+                address: Addr::unchecked("blacklisted"),
+            },
+        )
+        .unwrap();
+    assert_eq!(res.assets, vec![escrow.clone()]);
+
+    // claim reverts while the transfer still fails, the escrow is kept
+    let res = app.execute(
+        Addr::unchecked("blacklisted"),
+        limit_order_addr.clone(),
+        &ExecuteMsg::ClaimEscrow {},
+        &[],
+    );
+    app.assert_fail(res);
+    let res = app
+        .query::<EscrowResponse, _>(
+            limit_order_addr.clone(),
+            &QueryMsg::Escrow {
+                address: Addr::unchecked("blacklisted"),
+            },
+        )
+        .unwrap();
+    assert_eq!(res.assets, vec![escrow]);
+
+    BLACKLIST_ENABLED.with(|enabled| enabled.set(false));
+    let _res = app
+        .execute(
+            Addr::unchecked("blacklisted"),
+            limit_order_addr.clone(),
+            &ExecuteMsg::ClaimEscrow {},
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        query_token_balance(
+            &app.as_querier(),
+            token_addrs[0].clone(),
+            Addr::unchecked("blacklisted")
+        )
+        .unwrap(),
+        Uint128::from(998700u128)
+    );
+
+    // nothing left to claim
+    let res = app
+        .query::<EscrowResponse, _>(
+            limit_order_addr.clone(),
+            &QueryMsg::Escrow {
+                address: Addr::unchecked("blacklisted"),
+            },
+        )
+        .unwrap();
+    assert_eq!(res.assets, vec![]);
+    let res = app.execute(
+        Addr::unchecked("blacklisted"),
+        limit_order_addr,
+        &ExecuteMsg::ClaimEscrow {},
+        &[],
+    );
+    app.assert_fail(res);
+}
+
+#[test]
+fn submit_order_with_client_id() {
+    let mut app = MockApp::new(&[(
+        &"addr0000".to_string(),
+        &[Coin {
+            denom: USDT_DENOM.to_string(),
+            amount: Uint128::from(1000000u128),
+        }],
+    )]);
+
+    let msg = InstantiateMsg {
+        name: None,
+        version: None,
+        admin: None,
+        commission_rate: None,
+        reward_address: None,
+        spread_address: None,
+    };
+    let code_id = app.upload(Box::new(
+        create_entry_points_testing!(crate).with_reply(crate::contract::reply),
+    ));
+    let limit_order_addr = app
+        .instantiate(
+            code_id,
+            Addr::unchecked("addr0000"),
+            &msg,
+            &[],
+            "limit order",
+        )
+        .unwrap();
+
+    let asset_infos = [
+        AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: USDT_DENOM.to_string(),
+        },
+    ];
+
+    // create order book for pair [orai, usdt]
+    let msg = ExecuteMsg::CreateOrderBookPair {
+        base_coin_info: asset_infos[0].clone(),
+        quote_coin_info: asset_infos[1].clone(),
+        spread: None,
+        min_quote_coin_amount: Uint128::zero(),
+    };
+    let _res = app
+        .execute(
+            Addr::unchecked("addr0000"),
+            limit_order_addr.clone(),
+            &msg,
+            &[],
+        )
+        .unwrap();
+
+    // buy 1000 orai with 2000 usdt
+    let submit_order = |app: &mut MockApp, client_id: &str| {
+        app.execute(
+            Addr::unchecked("addr0000"),
+            limit_order_addr.clone(),
+            &ExecuteMsg::SubmitOrder {
+                direction: OrderDirection::Buy,
+                assets: [
+                    Asset {
+                        info: asset_infos[0].clone(),
+                        amount: Uint128::from(1000u128),
+                    },
+                    Asset {
+                        info: asset_infos[1].clone(),
+                        amount: Uint128::from(2000u128),
+                    },
+                ],
+                client_id: Some(client_id.to_string()),
+            },
+            &[Coin {
+                denom: USDT_DENOM.to_string(),
+                amount: Uint128::from(2000u128),
+            }],
+        )
+    };
+
+    // client id must be 1 to 64 characters
+    for client_id in ["", "a".repeat(65).as_str()] {
+        let res = submit_order(&mut app, client_id);
+        app.assert_fail(res);
+    }
+
+    submit_order(&mut app, "order-1").unwrap();
+    let order_by_client_id = |app: &MockApp| {
+        app.query::<OrderResponse, _>(
+            limit_order_addr.clone(),
+            &QueryMsg::OrderByClientId {
+                bidder: Addr::unchecked("addr0000"),
+                client_id: "order-1".to_string(),
+            },
+        )
+    };
+    let order = order_by_client_id(&app).unwrap();
+    assert_eq!(order.order_id, 1);
+    assert_eq!(order.client_id, Some("order-1".to_string()));
+
+    // client id is unique among the open orders of the bidder
+    let res = submit_order(&mut app, "order-1");
+    app.assert_fail(res);
+
+    // the client id can be reused once the order is closed
+    let _res = app
+        .execute(
+            Addr::unchecked("addr0000"),
+            limit_order_addr.clone(),
+            &ExecuteMsg::CancelOrder {
+                order_id: 1,
+                asset_infos: asset_infos.clone(),
+            },
+            &[],
+        )
+        .unwrap();
+    assert!(order_by_client_id(&app).is_err());
+
+    submit_order(&mut app, "order-1").unwrap();
+    assert_eq!(order_by_client_id(&app).unwrap().order_id, 2);
+}
+
+#[test]
+fn query_orders_at_price() {
+    let mut deps = mock_dependencies();
+
+    let msg = InstantiateMsg {
+        name: None,
+        version: None,
+        admin: None,
+        commission_rate: None,
+        reward_address: None,
+        spread_address: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    let asset_infos = [
+        AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: USDT_DENOM.to_string(),
+        },
+    ];
+
+    let msg = ExecuteMsg::CreateOrderBookPair {
+        base_coin_info: asset_infos[0].clone(),
+        quote_coin_info: asset_infos[1].clone(),
+        spread: None,
+        min_quote_coin_amount: Uint128::zero(),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    // buy orders at 0.333333333333333333 and 0.333333222222296296, both round to 0.333333, and at 0.5
+    for (sender, base_amount, quote_amount) in [
+        ("addr0000", 3000u128, 1000u128),
+        ("addr0001", 2999999u128, 999999u128),
+        ("addr0002", 1000u128, 500u128),
+    ] {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(sender, &coins(quote_amount, USDT_DENOM)),
+            ExecuteMsg::SubmitOrder {
+                direction: OrderDirection::Buy,
+                assets: [
+                    Asset {
+                        info: asset_infos[0].clone(),
+                        amount: Uint128::from(base_amount),
+                    },
+                    Asset {
+                        info: asset_infos[1].clone(),
+                        amount: Uint128::from(quote_amount),
+                    },
+                ],
+                client_id: None,
+            },
+        )
+        .unwrap();
+    }
+
+    let query_price_level = |deps: Deps, price: &str| {
+        from_binary::<PriceLevelResponse>(
+            &query(
+                deps,
+                mock_env(),
+                QueryMsg::OrdersAtPrice {
+                    asset_infos: asset_infos.clone(),
+                    price: Decimal::from_str(price).unwrap(),
+                    direction: OrderDirection::Buy,
+                    limit: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap()
+    };
+
+    // the exact price filter misses the orders
+    let res: OrdersResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Orders {
+                asset_infos: asset_infos.clone(),
+                direction: Some(OrderDirection::Buy),
+                filter: OrderFilter::Price(Decimal::from_str("0.333333").unwrap()),
+                start_after: None,
+                limit: None,
+                order_by: None,
+                cursor: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert!(res.orders.is_empty());
+
+    let res = query_price_level(deps.as_ref(), "0.3333333");
+    assert_eq!(res.price, Decimal::from_str("0.333333").unwrap());
+    assert_eq!(res.total_orders, 2);
+    assert_eq!(res.total_base_amount, Uint128::from(3002999u128));
+    // ascending by price
+    assert_eq!(
+        res.orders
+            .iter()
+            .map(|order| order.bidder_addr.as_str())
+            .collect::<Vec<&str>>(),
+        vec!["addr0001", "addr0000"]
+    );
+
+    let res = query_price_level(deps.as_ref(), "0.4999996");
+    assert_eq!(res.price, Decimal::percent(50));
+    assert_eq!(res.total_orders, 1);
+    assert_eq!(res.orders[0].bidder_addr, "addr0002");
+
+    // a price precision above 18 decimal places is rejected
+    let update_msg = |price_precision: u32| ExecuteMsg::UpdateOrderBookParams {
+        asset_infos: asset_infos.clone(),
+        spread: None,
+        min_quote_coin_amount: None,
+        paused: None,
+        price_precision: Some(price_precision),
+        min_quote_notional: None,
+        min_fill_ratio: None,
+        min_fill_base: None,
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        update_msg(19),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Std(StdError::generic_err("price precision must not exceed 18"))
+    );
+
+    // with 18 decimal places the price level is the exact price
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        update_msg(18),
+    )
+    .unwrap();
+    let res = query_price_level(deps.as_ref(), "0.333333333333333333");
+    assert_eq!(res.total_orders, 1);
+    assert_eq!(res.orders[0].bidder_addr, "addr0000");
+}
+
+#[test]
+fn query_total_escrow() {
+    let mut deps =
+        mock_dependencies_with_balance(&[coin(3000u128, ORAI_DENOM), coin(1000u128, USDT_DENOM)]);
+
+    let msg = InstantiateMsg {
+        name: None,
+        version: None,
+        admin: None,
+        commission_rate: None,
+        reward_address: None,
+        spread_address: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    let asset_infos = [
+        AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: USDT_DENOM.to_string(),
+        },
+    ];
+
+    let msg = ExecuteMsg::CreateOrderBookPair {
+        base_coin_info: asset_infos[0].clone(),
+        quote_coin_info: asset_infos[1].clone(),
+        spread: None,
+        min_quote_coin_amount: Uint128::zero(),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    // buy 2000 orai for 1000 usdt, sell 3000 orai for 1500 usdt
+    for (sender, direction, funds) in [
+        ("addr0000", OrderDirection::Buy, coin(1000u128, USDT_DENOM)),
+        ("addr0001", OrderDirection::Sell, coin(3000u128, ORAI_DENOM)),
+    ] {
+        let base_amount = match direction {
+            OrderDirection::Buy => 2000u128,
+            OrderDirection::Sell => 3000u128,
+        };
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(sender, &[funds.clone()]),
+            ExecuteMsg::SubmitOrder {
+                direction,
+                assets: [
+                    Asset {
+                        info: asset_infos[0].clone(),
+                        amount: Uint128::from(base_amount),
+                    },
+                    Asset {
+                        info: asset_infos[1].clone(),
+                        amount: Uint128::from(base_amount / 2),
+                    },
+                ],
+                client_id: None,
+            },
+        )
+        .unwrap();
+    }
+
+    let query_total_escrow = |deps: Deps, asset_info: &AssetInfo| {
+        from_binary::<TotalEscrowResponse>(
+            &query(
+                deps,
+                mock_env(),
+                QueryMsg::TotalEscrow {
+                    asset_info: asset_info.clone(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap()
+    };
+
+    let res = query_total_escrow(deps.as_ref(), &asset_infos[0]);
+    assert_eq!(res.total_escrow, Uint128::from(3000u128));
+    assert_eq!(res.balance, Uint128::from(3000u128));
+    assert_eq!(res.deficit, Uint128::zero());
+    let res = query_total_escrow(deps.as_ref(), &asset_infos[1]);
+    assert_eq!(res.total_escrow, Uint128::from(1000u128));
+
+    // a deployment without the cw2 version rebuilds the totals once on migration
+    cw2::CONTRACT.remove(deps.as_mut().storage);
+    migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg {
+            allow_import_orders: None,
+            registry: None,
+        },
+    )
+    .unwrap();
+    let res = query_total_escrow(deps.as_ref(), &asset_infos[0]);
+    assert_eq!(res.total_escrow, Uint128::from(3000u128));
+    assert!(cw2::get_contract_version(deps.as_ref().storage).is_ok());
+
+    // the buy order is fulfilled, the sell order keeps the unfilled offer in escrow
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("relayer", &[]),
+        ExecuteMsg::ExecuteOrderBookPair {
+            asset_infos: asset_infos.clone(),
+            limit: None,
+        },
+    )
+    .unwrap();
+
+    let res = query_total_escrow(deps.as_ref(), &asset_infos[1]);
+    assert_eq!(res.total_escrow, Uint128::zero());
+
+    let order: OrderResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Order {
+                order_id: 2,
+                asset_infos: asset_infos.clone(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert!(!order.filled_offer_amount.is_zero());
+    let res = query_total_escrow(deps.as_ref(), &asset_infos[0]);
+    assert_eq!(
+        res.total_escrow,
+        order.offer_asset.amount - order.filled_offer_amount
+    );
+
+    // cancelling releases the rest of the escrow
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0001", &[]),
+        ExecuteMsg::CancelOrder {
+            order_id: 2,
+            asset_infos: asset_infos.clone(),
+        },
+    )
+    .unwrap();
+    let res = query_total_escrow(deps.as_ref(), &asset_infos[0]);
+    assert_eq!(res.total_escrow, Uint128::zero());
+}
+
+#[test]
+fn submit_order_on_behalf() {
+    use k256::ecdsa::{signature::Signer, Signature, SigningKey};
+
+    let mut deps = mock_dependencies();
+
+    // token info of asset0000 queried for its decimals
+    deps.querier.update_wasm(|_| {
+        SystemResult::Ok(ContractResult::Ok(
+            to_binary(&cw20::TokenInfoResponse {
+                name: "asset0000".to_string(),
+                symbol: "ASSET".to_string(),
+                decimals: 6,
+                total_supply: Uint128::from(1000000u128),
+            })
+            .unwrap(),
+        ))
+    });
+    let msg = InstantiateMsg {
+        name: None,
+        version: None,
+        admin: Some(Addr::unchecked("admin")),
+        commission_rate: None,
+        reward_address: None,
+        spread_address: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    let asset_infos = [
+        AssetInfo::Token {
+            contract_addr: Addr::unchecked("asset0000"),
+        },
+        AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+    ];
+    let msg = ExecuteMsg::CreateOrderBookPair {
+        base_coin_info: asset_infos[0].clone(),
+        quote_coin_info: asset_infos[1].clone(),
+        spread: None,
+        min_quote_coin_amount: Uint128::zero(),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    let signing_key = SigningKey::from_bytes(&[1u8; 32]).unwrap();
+    let pubkey = Binary::from(
+        signing_key
+            .verifying_key()
+            .to_encoded_point(true)
+            .as_bytes(),
+    );
+
+    // sell 1000 asset0000 for 2000 orai, paid from the bidder's allowance
+    let order = OrderPermit {
+        bidder: Addr::unchecked("addr0000"),
+        direction: OrderDirection::Sell,
+        assets: [
+            Asset {
+                info: asset_infos[0].clone(),
+                amount: Uint128::from(1000u128),
+            },
+            Asset {
+                info: asset_infos[1].clone(),
+                amount: Uint128::from(2000u128),
+            },
+        ],
+        client_id: None,
+    };
+    let sign = |order: &OrderPermit, nonce: u64| {
+        let payload = to_binary(&OrderPermitPayload {
+            contract_addr: mock_env().contract.address,
+            chain_id: mock_env().block.chain_id,
+            nonce,
+            order: order.clone(),
+        })
+        .unwrap();
+        let signature: Signature = signing_key.sign(payload.as_slice());
+        Binary::from(signature.as_ref())
+    };
+    let submit_msg =
+        |order: &OrderPermit, signature: Binary, nonce: u64| ExecuteMsg::SubmitOrderOnBehalf {
+            order: order.clone(),
+            signature,
+            nonce,
+        };
+
+    // the bidder has not registered a signer yet
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("relayer", &[]),
+        submit_msg(&order, sign(&order, 0), 0),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::SetOrderSigner {
+            pubkey: Some(pubkey.clone()),
+        },
+    )
+    .unwrap();
+
+    // the relayer pays the gas, the offer is pulled from the bidder
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("relayer", &[]),
+        submit_msg(&order, sign(&order, 0), 0),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(WasmMsg::Execute {
+            contract_addr: "asset0000".to_string(),
+            msg: to_binary(&cw20::Cw20ExecuteMsg::TransferFrom {
+                owner: "addr0000".to_string(),
+                recipient: mock_env().contract.address.to_string(),
+                amount: Uint128::from(1000u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        })]
+    );
+
+    let order_res: OrderResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Order {
+                order_id: 1,
+                asset_infos: asset_infos.clone(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(order_res.bidder_addr, "addr0000");
+    assert_eq!(order_res.offer_asset.amount, Uint128::from(1000u128));
+
+    // the permit can not be replayed
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("relayer", &[]),
+        submit_msg(&order, sign(&order, 0), 0),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidNonce { expected: 1 });
+
+    // the signature must cover the submitted order
+    let mut tampered_order = order.clone();
+    tampered_order.assets[1].amount = Uint128::from(1000u128);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("relayer", &[]),
+        submit_msg(&tampered_order, sign(&order, 1), 1),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidSignature {});
+
+    let res: OrderSignerResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::OrderSigner {
+                bidder: Addr::unchecked("addr0000"),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res,
+        OrderSignerResponse {
+            pubkey: Some(pubkey),
+            nonce: 1,
+        }
+    );
+}
+
+#[test]
+fn call_market_auction() {
+    let mut deps =
+        mock_dependencies_with_balance(&[coin(2000u128, ORAI_DENOM), coin(3400u128, USDT_DENOM)]);
+
+    let msg = InstantiateMsg {
+        name: None,
+        version: None,
+        admin: None,
+        commission_rate: None,
+        reward_address: None,
+        spread_address: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    let asset_infos = [
+        AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: USDT_DENOM.to_string(),
+        },
+    ];
+
+    let msg = ExecuteMsg::CreateOrderBookPair {
+        base_coin_info: asset_infos[0].clone(),
+        quote_coin_info: asset_infos[1].clone(),
+        spread: None,
+        min_quote_coin_amount: Uint128::zero(),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    // only admin can switch to the call market
+    let msg = ExecuteMsg::UpdateOrderBookAuction {
+        asset_infos: asset_infos.clone(),
+        interval_seconds: Some(60),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    // buy 2000 orai at 1.2 and 1000 orai at 1.0, sell 1000 orai at 0.9 and 1000 orai at 1.1
+    for (sender, direction, base_amount, quote_amount) in [
+        ("addr0000", OrderDirection::Buy, 2000u128, 2400u128),
+        ("addr0001", OrderDirection::Buy, 1000u128, 1000u128),
+        ("addr0002", OrderDirection::Sell, 1000u128, 900u128),
+        ("addr0003", OrderDirection::Sell, 1000u128, 1100u128),
+    ] {
+        let funds = match direction {
+            OrderDirection::Buy => coin(quote_amount, USDT_DENOM),
+            OrderDirection::Sell => coin(base_amount, ORAI_DENOM),
+        };
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(sender, &[funds]),
+            ExecuteMsg::SubmitOrder {
+                direction,
+                assets: [
+                    Asset {
+                        info: asset_infos[0].clone(),
+                        amount: Uint128::from(base_amount),
+                    },
+                    Asset {
+                        info: asset_infos[1].clone(),
+                        amount: Uint128::from(quote_amount),
+                    },
+                ],
+                client_id: None,
+            },
+        )
+        .unwrap();
+    }
+
+    // 2000 orai are matched at 1.1 and at 1.2, the lowest of them clears the market
+    let execute_orderbook = |deps: DepsMut, env: Env| {
+        execute(
+            deps,
+            env,
+            mock_info("relayer", &[]),
+            ExecuteMsg::ExecuteOrderBookPair {
+                asset_infos: asset_infos.clone(),
+                limit: None,
+            },
+        )
+    };
+    let res = execute_orderbook(deps.as_mut(), mock_env()).unwrap();
+    assert!(res.attributes.contains(&attr("clearing_price", "1.1")));
+    assert!(res.attributes.contains(&attr("total_matched_orders", "3")));
+
+    // the first buyer only spends 2200 usdt and gets the rest of the offer back
+    assert!(res.messages.contains(&SubMsg::reply_on_error(
+        BankMsg::Send {
+            to_address: "addr0000".to_string(),
+            amount: coins(200u128, USDT_DENOM),
+        },
+        3
+    )));
+
+    let order: OrderResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Order {
+                order_id: 2,
+                asset_infos: asset_infos.clone(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(order.status, OrderStatus::Open);
+
+    // the next auction is one interval later
+    let err = execute_orderbook(deps.as_mut(), mock_env()).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::AuctionNotDue {
+            next_auction_time: mock_env().block.time.seconds() + 60
+        }
+    );
+
+    let res: OrderBookResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::OrderBook {
+                asset_infos: asset_infos.clone(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.auction_interval_seconds, Some(60));
+    assert_eq!(res.last_auction_time, Some(mock_env().block.time.seconds()));
+
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(60);
+    let res = execute_orderbook(deps.as_mut(), env).unwrap();
+    assert!(res
+        .attributes
+        .iter()
+        .all(|attribute| attribute.key != "clearing_price"));
+}
+
+#[test]
+fn delist_orderbook_pair() {
+    let mut deps = mock_dependencies_with_balance(&[coin(3000u128, USDT_DENOM)]);
+
+    let msg = InstantiateMsg {
+        name: None,
+        version: None,
+        admin: None,
+        commission_rate: None,
+        reward_address: None,
+        spread_address: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    let asset_infos = [
+        AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: USDT_DENOM.to_string(),
+        },
+    ];
+
+    let msg = ExecuteMsg::CreateOrderBookPair {
+        base_coin_info: asset_infos[0].clone(),
+        quote_coin_info: asset_infos[1].clone(),
+        spread: None,
+        min_quote_coin_amount: Uint128::zero(),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    // each bidder buys 2000 orai for 1000 usdt
+    let submit_order = ExecuteMsg::SubmitOrder {
+        direction: OrderDirection::Buy,
+        assets: [
+            Asset {
+                info: asset_infos[0].clone(),
+                amount: Uint128::from(2000u128),
+            },
+            Asset {
+                info: asset_infos[1].clone(),
+                amount: Uint128::from(1000u128),
+            },
+        ],
+        client_id: None,
+    };
+    for sender in ["addr0000", "addr0001", "addr0002"] {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(sender, &coins(1000u128, USDT_DENOM)),
+            submit_order.clone(),
+        )
+        .unwrap();
+    }
+
+    // only admin can delist
+    let delist = ExecuteMsg::RemoveOrderBookPair {
+        asset_infos: asset_infos.clone(),
+        grace_period_seconds: Some(100),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        delist.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), delist).unwrap();
+
+    let delisting_time = mock_env().block.time.seconds() + 100;
+    let orderbook: OrderBookResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::OrderBook {
+                asset_infos: asset_infos.clone(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(orderbook.delisting_time, Some(delisting_time));
+
+    // new orders are rejected, matching continues during the grace period
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0003", &coins(1000u128, USDT_DENOM)),
+        submit_order,
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::OrderBookDelisting {});
+
+    let execute_orderbook_pair = ExecuteMsg::ExecuteOrderBookPair {
+        asset_infos: asset_infos.clone(),
+        limit: None,
+    };
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("relayer", &[]),
+        execute_orderbook_pair.clone(),
+    )
+    .unwrap();
+
+    // refunds wait for the end of the grace period
+    let refund = ExecuteMsg::RefundDelistedOrders {
+        asset_infos: asset_infos.clone(),
+        limit: Some(2),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        refund.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::OrderBookNotDelisted {
+            delisting_time: Some(delisting_time)
+        }
+    );
+
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(100);
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("relayer", &[]),
+        execute_orderbook_pair,
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::OrderBookDelisted { delisting_time });
+
+    // first batch refunds the two oldest orders and keeps the order book
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("admin", &[]),
+        refund.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(BankMsg::Send {
+                to_address: "addr0000".to_string(),
+                amount: coins(1000u128, USDT_DENOM),
+            }),
+            SubMsg::new(BankMsg::Send {
+                to_address: "addr0001".to_string(),
+                amount: coins(1000u128, USDT_DENOM),
+            }),
+        ]
+    );
+    assert!(res.attributes.contains(&attr("orderbook_removed", "false")));
+
+    // last batch removes the order book
+    let res = execute(deps.as_mut(), env, mock_info("admin", &[]), refund).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: "addr0002".to_string(),
+            amount: coins(1000u128, USDT_DENOM),
+        })]
+    );
+    assert!(res.attributes.contains(&attr("orderbook_removed", "true")));
+
+    query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::OrderBook { asset_infos },
+    )
+    .unwrap_err();
+    let res: TotalEscrowResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::TotalEscrow {
+                asset_info: AssetInfo::NativeToken {
+                    denom: USDT_DENOM.to_string(),
+                },
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.total_escrow, Uint128::zero());
+}
+
+#[test]
+fn role_permissions() {
+    let mut deps = mock_dependencies();
+
+    let msg = InstantiateMsg {
+        name: None,
+        version: None,
+        admin: None,
+        commission_rate: None,
+        reward_address: None,
+        spread_address: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    let create_pair = ExecuteMsg::CreateOrderBookPair {
+        base_coin_info: AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        quote_coin_info: AssetInfo::NativeToken {
+            denom: USDT_DENOM.to_string(),
+        },
+        spread: None,
+        min_quote_coin_amount: Uint128::zero(),
+    };
+    let update_fee = ExecuteMsg::UpdateConfig {
+        reward_address: None,
+        spread_address: None,
+        commission_rate: Some("0.002".to_string()),
+        guardian: None,
+        maker_rebate_rate: None,
+        event_mode: None,
+        rewarder: None,
+        referral: None,
+        oracle: None,
+        converter: None,
+    };
+    let grant_role = |role: Role| ExecuteMsg::GrantRole {
+        address: Addr::unchecked("operator"),
+        role,
+    };
+
+    // without roles, only the admin can operate
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("operator", &[]),
+        create_pair.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // only super admin can grant roles
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("operator", &[]),
+        grant_role(Role::Operator),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        grant_role(Role::Operator),
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("operator", &[]),
+        create_pair,
+    )
+    .unwrap();
+
+    // operator can not update the fees or pause
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("operator", &[]),
+        update_fee.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("operator", &[]),
+        ExecuteMsg::Pause {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // fee manager can update the fee rates but not the addresses
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        grant_role(Role::FeeManager),
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("operator", &[]),
+        update_fee,
+    )
+    .unwrap();
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("operator", &[]),
+        ExecuteMsg::UpdateConfig {
+            reward_address: Some(Addr::unchecked("operator")),
+            spread_address: None,
+            commission_rate: None,
+            guardian: None,
+            maker_rebate_rate: None,
+            event_mode: None,
+            rewarder: None,
+            referral: None,
+            oracle: None,
+            converter: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        grant_role(Role::Pauser),
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("operator", &[]),
+        ExecuteMsg::Pause {},
+    )
+    .unwrap();
+
+    let res: RolesResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Roles {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.holders,
+        vec![RoleHolder {
+            address: Addr::unchecked("operator"),
+            roles: vec![Role::Operator, Role::FeeManager, Role::Pauser],
+        }]
+    );
+
+    // a granted super admin can grant and revoke roles
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        ExecuteMsg::GrantRole {
+            address: Addr::unchecked("super_admin"),
+            role: Role::SuperAdmin,
+        },
+    )
+    .unwrap();
+    for role in [Role::Operator, Role::FeeManager, Role::Pauser] {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("super_admin", &[]),
+            ExecuteMsg::RevokeRole {
+                address: Addr::unchecked("operator"),
+                role,
+            },
+        )
+        .unwrap();
+    }
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("operator", &[]),
+        ExecuteMsg::Unpause {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // the operator without roles is removed from the holders
+    let res: RolesResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Roles {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.holders,
+        vec![RoleHolder {
+            address: Addr::unchecked("super_admin"),
+            roles: vec![Role::SuperAdmin],
+        }]
+    );
+}
+
+#[test]
+fn update_order_token() {
+    let mut app = MockApp::new(&[]);
+    app.set_token_contract(Box::new(create_entry_points_testing!(oraiswap_token)));
+
+    let token_addrs = app.set_token_balances(&[
+        (
+            &"assetA".to_string(),
+            &[
+                (&"addr0000".to_string(), &Uint128::from(1000000u128)),
+                (&"addr0001".to_string(), &Uint128::from(1000000u128)),
+            ],
+        ),
+        (
+            &"assetB".to_string(),
+            &[(&"addr0000".to_string(), &Uint128::from(1000000u128))],
+        ),
+    ]);
+
+    let msg = InstantiateMsg {
+        name: None,
+        version: None,
+        admin: None,
+        commission_rate: None,
+        reward_address: None,
+        spread_address: None,
+    };
+    let code_id = app.upload(Box::new(
+        create_entry_points_testing!(crate).with_reply(crate::contract::reply),
+    ));
+    let limit_order_addr = app
+        .instantiate(
+            code_id,
+            Addr::unchecked("addr0000"),
+            &msg,
+            &[],
+            "limit order",
+        )
+        .unwrap();
+
+    let asset_infos = [
+        AssetInfo::Token {
+            contract_addr: token_addrs[0].clone(),
+        },
+        AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+    ];
+
+    // create order book for pair [token_addrs[0], orai]
+    let msg = ExecuteMsg::CreateOrderBookPair {
+        base_coin_info: asset_infos[0].clone(),
+        quote_coin_info: asset_infos[1].clone(),
+        spread: None,
+        min_quote_coin_amount: Uint128::from(10u128),
+    };
+    let _res = app
+        .execute(
+            Addr::unchecked("addr0000"),
+            limit_order_addr.clone(),
+            &msg,
+            &[],
+        )
+        .unwrap();
+
+    let order_assets = |offer_amount: u128, ask_amount: u128| {
+        [
+            Asset {
+                info: asset_infos[0].clone(),
+                amount: Uint128::from(offer_amount),
+            },
+            Asset {
+                info: asset_infos[1].clone(),
+                amount: Uint128::from(ask_amount),
+            },
+        ]
+    };
+
+    // sell 1000 assetA for 2000 orai
+    let _res = app
+        .execute(
+            Addr::unchecked("addr0000"),
+            token_addrs[0].clone(),
+            &cw20::Cw20ExecuteMsg::Send {
+                contract: limit_order_addr.to_string(),
+                amount: Uint128::from(1000u128),
+                msg: to_binary(&Cw20HookMsg::SubmitOrder {
+                    direction: OrderDirection::Sell,
+                    assets: order_assets(1000, 2000),
+                    client_id: None,
+                })
+                .unwrap(),
+            },
+            &[],
+        )
+        .unwrap();
+
+    let update_order_msg = |amount: u128| cw20::Cw20ExecuteMsg::Send {
+        contract: limit_order_addr.to_string(),
+        amount: Uint128::from(amount),
+        msg: to_binary(&Cw20HookMsg::UpdateOrder {
+            order_id: 1,
+            assets: order_assets(1500, 3000),
+        })
+        .unwrap(),
+    };
+
+    // the sent amount must equal the increase of the offer amount
+    let res = app.execute(
+        Addr::unchecked("addr0000"),
+        token_addrs[0].clone(),
+        &update_order_msg(400),
+        &[],
+    );
+    app.assert_fail(res);
+
+    // the sent token must be the offer asset
+    let res = app.execute(
+        Addr::unchecked("addr0000"),
+        token_addrs[1].clone(),
+        &update_order_msg(500),
+        &[],
+    );
+    app.assert_fail(res);
+
+    // only the bidder can update the order
+    let res = app.execute(
+        Addr::unchecked("addr0001"),
+        token_addrs[0].clone(),
+        &update_order_msg(500),
+        &[],
+    );
+    app.assert_fail(res);
+
+    // a token offer can not be topped up without sending the token
+    let res = app.execute(
+        Addr::unchecked("addr0000"),
+        limit_order_addr.clone(),
+        &ExecuteMsg::UpdateOrder {
+            order_id: 1,
+            assets: order_assets(1500, 3000),
+        },
+        &[],
+    );
+    app.assert_fail(res);
+
+    let _res = app
+        .execute(
+            Addr::unchecked("addr0000"),
+            token_addrs[0].clone(),
+            &update_order_msg(500),
+            &[],
+        )
+        .unwrap();
+
+    let order = app
+        .query::<OrderResponse, _>(
+            limit_order_addr.clone(),
+            &QueryMsg::Order {
+                order_id: 1,
+                asset_infos: asset_infos.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(order.offer_asset.amount, Uint128::from(1500u128));
+    assert_eq!(order.ask_asset.amount, Uint128::from(3000u128));
+
+    // decrease the offer amount, the difference is refunded
+    let _res = app
+        .execute(
+            Addr::unchecked("addr0000"),
+            limit_order_addr.clone(),
+            &ExecuteMsg::UpdateOrder {
+                order_id: 1,
+                assets: order_assets(1200, 3000),
+            },
+            &[],
+        )
+        .unwrap();
+
+    let order = app
+        .query::<OrderResponse, _>(
+            limit_order_addr.clone(),
+            &QueryMsg::Order {
+                order_id: 1,
+                asset_infos: asset_infos.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(order.offer_asset.amount, Uint128::from(1200u128));
+    assert_eq!(order.ask_asset.amount, Uint128::from(3000u128));
+
+    for (address, balance) in [
+        ("addr0000", 998800u128),
+        (limit_order_addr.as_str(), 1200u128),
+    ] {
+        let res = app
+            .query::<cw20::BalanceResponse, _>(
+                token_addrs[0].clone(),
+                &cw20::Cw20QueryMsg::Balance {
+                    address: address.to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(res.balance, Uint128::from(balance));
+    }
+}
+
+#[test]
+fn submit_order_with_min_quote_notional() {
+    let mut deps = mock_dependencies_with_balance(&[coin(1000u128, USDT_DENOM)]);
+
+    let msg = InstantiateMsg {
+        name: None,
+        version: None,
+        admin: None,
+        commission_rate: None,
+        reward_address: None,
+        spread_address: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    let asset_infos = [
+        AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: USDT_DENOM.to_string(),
+        },
+    ];
+
+    let msg = ExecuteMsg::CreateOrderBookPair {
+        base_coin_info: asset_infos[0].clone(),
+        quote_coin_info: asset_infos[1].clone(),
+        spread: None,
+        min_quote_coin_amount: Uint128::zero(),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::UpdateOrderBookParams {
+        asset_infos: asset_infos.clone(),
+        spread: None,
+        min_quote_coin_amount: None,
+        paused: None,
+        price_precision: None,
+        min_quote_notional: Some(Uint128::from(1000u128)),
+        min_fill_ratio: None,
+        min_fill_base: None,
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    let order_assets = |base_amount: u128, quote_amount: u128| {
+        [
+            Asset {
+                info: asset_infos[0].clone(),
+                amount: Uint128::from(base_amount),
+            },
+            Asset {
+                info: asset_infos[1].clone(),
+                amount: Uint128::from(quote_amount),
+            },
+        ]
+    };
+
+    // sell 10 orai at price 1 is worth 10 usdt
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[coin(10u128, ORAI_DENOM)]),
+        ExecuteMsg::SubmitOrder {
+            direction: OrderDirection::Sell,
+            assets: order_assets(10, 10),
+            client_id: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::TooSmallQuoteNotional {
+            notional: Uint128::from(10u128),
+            min_quote_notional: Uint128::from(1000u128),
+        }
+    );
+
+    // buy 100 orai at price 10 is worth 1000 usdt
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[coin(1000u128, USDT_DENOM)]),
+        ExecuteMsg::SubmitOrder {
+            direction: OrderDirection::Buy,
+            assets: order_assets(100, 1000),
+            client_id: None,
+        },
+    )
+    .unwrap();
+
+    // amending the order below the min quote notional is rejected
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::UpdateOrder {
+            order_id: 1,
+            assets: order_assets(100, 500),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::TooSmallQuoteNotional {
+            notional: Uint128::from(500u128),
+            min_quote_notional: Uint128::from(1000u128),
+        }
+    );
+
+    // zero removes the min quote notional
+    let msg = ExecuteMsg::UpdateOrderBookParams {
+        asset_infos: asset_infos.clone(),
+        spread: None,
+        min_quote_coin_amount: None,
+        paused: None,
+        price_precision: None,
+        min_quote_notional: Some(Uint128::zero()),
+        min_fill_ratio: None,
+        min_fill_base: None,
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::UpdateOrder {
+            order_id: 1,
+            assets: order_assets(100, 500),
+        },
+    )
+    .unwrap();
+}
+
+#[test]
+fn orderbook_execution_metadata() {
+    let mut deps =
+        mock_dependencies_with_balance(&[coin(3000u128, ORAI_DENOM), coin(1000u128, USDT_DENOM)]);
+
+    let msg = InstantiateMsg {
+        name: None,
+        version: None,
+        admin: None,
+        commission_rate: None,
+        reward_address: None,
+        spread_address: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    let asset_infos = [
+        AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: USDT_DENOM.to_string(),
+        },
+    ];
+
+    let msg = ExecuteMsg::CreateOrderBookPair {
+        base_coin_info: asset_infos[0].clone(),
+        quote_coin_info: asset_infos[1].clone(),
+        spread: None,
+        min_quote_coin_amount: Uint128::zero(),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    let query_orderbook = |deps: Deps| {
+        from_binary::<OrderBookResponse>(
+            &query(
+                deps,
+                mock_env(),
+                QueryMsg::OrderBook {
+                    asset_infos: asset_infos.clone(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap()
+    };
+    let order_book = query_orderbook(deps.as_ref());
+    assert_eq!(order_book.last_executed_height, None);
+    assert_eq!(order_book.last_executed_by, None);
+    assert_eq!(order_book.orders_matched_total, 0);
+
+    // buy 2000 orai for 1000 usdt, sell 3000 orai for 1500 usdt
+    for (sender, direction, funds) in [
+        ("addr0000", OrderDirection::Buy, coin(1000u128, USDT_DENOM)),
+        ("addr0001", OrderDirection::Sell, coin(3000u128, ORAI_DENOM)),
+    ] {
+        let base_amount = match direction {
+            OrderDirection::Buy => 2000u128,
+            OrderDirection::Sell => 3000u128,
+        };
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(sender, &[funds.clone()]),
+            ExecuteMsg::SubmitOrder {
+                direction,
+                assets: [
+                    Asset {
+                        info: asset_infos[0].clone(),
+                        amount: Uint128::from(base_amount),
+                    },
+                    Asset {
+                        info: asset_infos[1].clone(),
+                        amount: Uint128::from(base_amount / 2),
+                    },
+                ],
+                client_id: None,
+            },
+        )
+        .unwrap();
+    }
+
+    let msg = ExecuteMsg::ExecuteOrderBookPair {
+        asset_infos: asset_infos.clone(),
+        limit: None,
+    };
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("relayer", &[]),
+        msg.clone(),
+    )
+    .unwrap();
+
+    let order_book = query_orderbook(deps.as_ref());
+    assert_eq!(
+        order_book.last_executed_height,
+        Some(mock_env().block.height)
+    );
+    assert_eq!(
+        order_book.last_executed_by,
+        Some(Addr::unchecked("relayer"))
+    );
+    assert_eq!(order_book.orders_matched_total, 2);
+
+    // an execution without crossing orders is recorded without matched orders
+    let mut env = mock_env();
+    env.block.height += 10;
+    execute(deps.as_mut(), env.clone(), mock_info("relayer2", &[]), msg).unwrap();
+
+    let order_book = query_orderbook(deps.as_ref());
+    assert_eq!(order_book.last_executed_height, Some(env.block.height));
+    assert_eq!(
+        order_book.last_executed_by,
+        Some(Addr::unchecked("relayer2"))
+    );
+    assert_eq!(order_book.orders_matched_total, 2);
+}
@@ -1,16 +1,17 @@
 use std::str::FromStr;
 
-use cosmwasm_std::{testing::mock_dependencies, Api, Decimal};
+use cosmwasm_std::{testing::mock_dependencies, Api, Binary, Decimal, StdError, Uint128};
+use cosmwasm_storage::Bucket;
 use oraiswap::{
     asset::{AssetInfoRaw, ORAI_DENOM},
-    limit_order::OrderDirection,
+    limit_order::{OrderDirection, OrderStatus},
     testing::ATOM_DENOM,
 };
 
 use crate::{
-    orderbook::{Order, OrderBook},
-    state::{increase_last_order_id, init_last_order_id},
-    tick::query_ticks_prices,
+    orderbook::{scaled_price, Order, OrderBook},
+    state::{increase_last_order_id, init_last_order_id, remove_order, PREFIX_TICK},
+    tick::{query_malformed_tick_keys, query_tick, query_ticks_prices, query_ticks_with_end},
 };
 
 #[test]
@@ -95,7 +96,7 @@ fn initialize() {
             "insert order id: {}, direction: {:?}, price: {}, total orders: {}",
             order.order_id,
             order.direction,
-            order.get_price(),
+            order.get_price(0).unwrap(),
             total_orders
         );
     }
@@ -189,7 +190,7 @@ fn buy_orders_at() {
             "insert order id: {}, direction: {:?}, price: {}, total orders: {}",
             order.order_id,
             order.direction,
-            order.get_price(),
+            order.get_price(0).unwrap(),
             total_orders
         );
     }
@@ -277,7 +278,7 @@ fn sell_orders_at() {
             "insert order id: {}, direction: {:?}, price: {}, total orders: {}",
             order.order_id,
             order.direction,
-            order.get_price(),
+            order.get_price(0).unwrap(),
             total_orders
         );
     }
@@ -418,7 +419,7 @@ fn highest_lowest_price() {
                 "insert order id: {}, direction: {:?}, price: {}, total orders: {}",
                 order.order_id,
                 order.direction,
-                order.get_price(),
+                order.get_price(0).unwrap(),
                 total_orders
             );
         }
@@ -453,3 +454,231 @@ fn highest_lowest_price() {
         }
     }
 }
+
+#[test]
+fn tick_total_base_amount() {
+    let mut deps = mock_dependencies();
+
+    let offer_info = AssetInfoRaw::NativeToken {
+        denom: ORAI_DENOM.to_string(),
+    };
+    let ask_info = AssetInfoRaw::NativeToken {
+        denom: ATOM_DENOM.to_string(),
+    };
+
+    let bidder_addr = deps.api.addr_canonicalize("addr0000").unwrap();
+    init_last_order_id(deps.as_mut().storage).unwrap();
+
+    let price = Decimal::from_str("2").unwrap();
+    let mut ob = OrderBook::new(ask_info, offer_info, None);
+    let pair_key = &ob.get_pair_key();
+
+    // buy orders ask for base amount, sell orders offer base amount
+    let mut orders = vec![];
+    for (direction, ask_amount) in [
+        (OrderDirection::Buy, 10000u128),
+        (OrderDirection::Buy, 5000u128),
+        (OrderDirection::Sell, 20000u128),
+    ] {
+        let order = Order::new(
+            increase_last_order_id(deps.as_mut().storage).unwrap(),
+            bidder_addr.clone(),
+            direction,
+            price,
+            ask_amount.into(),
+        );
+        ob.add_order(deps.as_mut().storage, &order).unwrap();
+        orders.push(order);
+    }
+
+    let tick = query_tick(deps.as_ref().storage, pair_key, OrderDirection::Buy, price).unwrap();
+    assert_eq!(tick.total_orders, 2);
+    assert_eq!(tick.total_base_amount, Uint128::from(15000u128));
+    let tick = query_tick(deps.as_ref().storage, pair_key, OrderDirection::Sell, price).unwrap();
+    assert_eq!(tick.total_orders, 1);
+    assert_eq!(tick.total_base_amount, Uint128::from(10000u128));
+
+    // partially filled order only counts its remaining amount
+    orders[0].fill_order(4000u128.into(), 8000u128.into());
+    orders[0]
+        .match_order(deps.as_mut().storage, pair_key, 0)
+        .unwrap();
+    let tick = query_tick(deps.as_ref().storage, pair_key, OrderDirection::Buy, price).unwrap();
+    assert_eq!(tick.total_base_amount, Uint128::from(11000u128));
+
+    // cancelled order is removed from the tick
+    remove_order(deps.as_mut().storage, pair_key, &orders[1], 0).unwrap();
+    let tick = query_tick(deps.as_ref().storage, pair_key, OrderDirection::Buy, price).unwrap();
+    assert_eq!(tick.total_orders, 1);
+    assert_eq!(tick.total_base_amount, Uint128::from(6000u128));
+
+    // fulfilled order removes the tick
+    orders[0].fill_order(6000u128.into(), 12000u128.into());
+    orders[0]
+        .match_order(deps.as_mut().storage, pair_key, 0)
+        .unwrap();
+    let ticks = query_ticks_with_end(
+        deps.as_ref().storage,
+        pair_key,
+        OrderDirection::Buy,
+        None,
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+    assert!(ticks.ticks.is_empty());
+}
+
+#[test]
+fn skip_malformed_tick_keys() {
+    let mut deps = mock_dependencies();
+
+    let offer_info = AssetInfoRaw::NativeToken {
+        denom: ORAI_DENOM.to_string(),
+    };
+    let ask_info = AssetInfoRaw::NativeToken {
+        denom: ATOM_DENOM.to_string(),
+    };
+
+    let bidder_addr = deps.api.addr_canonicalize("addr0000").unwrap();
+    init_last_order_id(deps.as_mut().storage).unwrap();
+
+    let mut ob = OrderBook::new(ask_info, offer_info, None);
+    let pair_key = &ob.get_pair_key();
+
+    let price = Decimal::from_str("2").unwrap();
+    let order = Order::new(
+        increase_last_order_id(deps.as_mut().storage).unwrap(),
+        bidder_addr,
+        OrderDirection::Buy,
+        price,
+        10000u128.into(),
+    );
+    ob.add_order(deps.as_mut().storage, &order).unwrap();
+
+    // a short key sorts after every price key
+    Bucket::multilevel(
+        deps.as_mut().storage,
+        &[PREFIX_TICK, pair_key, OrderDirection::Buy.as_bytes()],
+    )
+    .save(b"bad", &1u64)
+    .unwrap();
+
+    let (highest_price, found, total_orders) =
+        ob.highest_price(deps.as_ref().storage, OrderDirection::Buy);
+    assert!(found);
+    assert_eq!(highest_price, price);
+    assert_eq!(total_orders, 1);
+
+    let ticks = query_ticks_with_end(
+        deps.as_ref().storage,
+        pair_key,
+        OrderDirection::Buy,
+        None,
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+    assert_eq!(ticks.ticks.len(), 1);
+    assert_eq!(ticks.ticks[0].price, price);
+
+    let res = query_malformed_tick_keys(deps.as_ref().storage, pair_key, OrderDirection::Buy, None)
+        .unwrap();
+    assert_eq!(res.keys, vec![Binary::from(b"bad".to_vec())]);
+}
+
+#[test]
+fn price_in_whole_coins() {
+    let mut deps = mock_dependencies();
+
+    let offer_info = AssetInfoRaw::NativeToken {
+        denom: ORAI_DENOM.to_string(),
+    };
+    let ask_info = AssetInfoRaw::NativeToken {
+        denom: ATOM_DENOM.to_string(),
+    };
+
+    let bidder_addr = deps.api.addr_canonicalize("addr0000").unwrap();
+
+    // 18 decimals base asset quoted in 6 decimals quote asset
+    let mut ob = OrderBook {
+        base_decimals: Some(18),
+        quote_decimals: Some(6),
+        ..OrderBook::new(ask_info, offer_info, None)
+    };
+    let pair_key = &ob.get_pair_key();
+    assert_eq!(ob.decimals_diff(), 12);
+
+    let new_order = |order_id: u64, direction, offer_amount: u128, ask_amount: u128| Order {
+        order_id,
+        status: OrderStatus::Open,
+        direction,
+        bidder_addr: bidder_addr.clone(),
+        offer_amount: offer_amount.into(),
+        ask_amount: ask_amount.into(),
+        filled_offer_amount: Uint128::zero(),
+        filled_ask_amount: Uint128::zero(),
+        client_id: None,
+    };
+
+    // buy 1 base coin for 3000 quote coins, sell 0.5 base coin for 1500 quote coins
+    let buy_order = new_order(1, OrderDirection::Buy, 3000000000, 1000000000000000000);
+    let sell_order = new_order(2, OrderDirection::Sell, 500000000000000000, 1500000000);
+    let price = Decimal::from_str("3000").unwrap();
+    assert_eq!(buy_order.get_price(ob.decimals_diff()).unwrap(), price);
+    assert_eq!(sell_order.get_price(ob.decimals_diff()).unwrap(), price);
+    // without decimals the price is the ratio of the atomics
+    assert_eq!(
+        buy_order.get_price(0).unwrap(),
+        Decimal::from_str("0.000000003").unwrap()
+    );
+
+    ob.add_order(deps.as_mut().storage, &buy_order).unwrap();
+    ob.add_order(deps.as_mut().storage, &sell_order).unwrap();
+    let tick = query_tick(deps.as_ref().storage, pair_key, OrderDirection::Buy, price).unwrap();
+    assert_eq!(tick.total_orders, 1);
+    assert_eq!(
+        tick.total_base_amount,
+        Uint128::from(1000000000000000000u128)
+    );
+    let tick = query_tick(deps.as_ref().storage, pair_key, OrderDirection::Sell, price).unwrap();
+    assert_eq!(tick.total_orders, 1);
+
+    assert_eq!(
+        ob.to_quote_amount(Uint128::from(500000000000000000u128), price)
+            .unwrap(),
+        Uint128::from(1500000000u128)
+    );
+    assert_eq!(
+        ob.to_base_amount(Uint128::from(1500000000u128), price)
+            .unwrap(),
+        Uint128::from(500000000000000000u128)
+    );
+    // amounts beyond Uint128 return an error instead of panicking
+    let err = ob.to_base_amount(Uint128::MAX, price).unwrap_err();
+    assert!(matches!(err, StdError::ConversionOverflow { .. }));
+    let err = scaled_price(Uint128::MAX, Uint128::one(), 0).unwrap_err();
+    assert!(matches!(err, StdError::ConversionOverflow { .. }));
+
+    // 6 decimals base asset quoted in 18 decimals quote asset
+    let ob = OrderBook {
+        base_decimals: Some(6),
+        quote_decimals: Some(18),
+        ..ob
+    };
+    let buy_order = new_order(3, OrderDirection::Buy, 2000000000000000, 1000000);
+    assert_eq!(
+        buy_order.get_price(ob.decimals_diff()).unwrap(),
+        Decimal::from_str("0.002").unwrap()
+    );
+    assert_eq!(
+        ob.to_quote_amount(
+            Uint128::from(1000000u128),
+            Decimal::from_str("0.002").unwrap()
+        )
+        .unwrap(),
+        Uint128::from(2000000000000000u128)
+    );
+}
@@ -1,13 +1,16 @@
 use std::convert::{TryFrom, TryInto};
 
-use cosmwasm_std::{Decimal, Order as OrderBy, StdResult, Storage};
-use cosmwasm_storage::ReadonlyBucket;
+use cosmwasm_std::{from_slice, Binary, Decimal, Order as OrderBy, StdResult, Storage, Uint128};
+use cosmwasm_storage::{ReadonlyBucket, ReadonlyPrefixedStorage};
 use oraiswap::{
-    limit_order::{OrderDirection, TickResponse, TicksResponse},
+    limit_order::{MalformedTickKeysResponse, OrderDirection, TickResponse, TicksResponse},
     querier::calc_range_start,
 };
 
-use crate::state::{DEFAULT_LIMIT, MAX_LIMIT, PREFIX_TICK};
+use crate::state::{DEFAULT_LIMIT, MAX_LIMIT, PREFIX_TICK, PREFIX_TICK_VOLUME};
+
+// decimal places of the price levels when the order book does not set its price precision
+pub const DEFAULT_PRICE_PRECISION: u32 = 6;
 
 // pub fn query_ticks(
 //     storage: &dyn Storage,
@@ -28,6 +31,13 @@ use crate::state::{DEFAULT_LIMIT, MAX_LIMIT, PREFIX_TICK};
 //     )
 // }
 
+/// price key is the 16 big endian bytes of the price atomics, return None if the key is malformed
+pub fn price_from_key(key: &[u8]) -> Option<Decimal> {
+    key.try_into()
+        .ok()
+        .map(|bytes| Decimal::raw(u128::from_be_bytes(bytes)))
+}
+
 pub fn query_ticks_prices(
     storage: &dyn Storage,
     pair_key: &[u8],
@@ -85,6 +95,10 @@ pub fn query_ticks_with_end(
 
     let position_bucket: ReadonlyBucket<u64> =
         ReadonlyBucket::multilevel(storage, &[PREFIX_TICK, pair_key, direction.as_bytes()]);
+    let volume_bucket: ReadonlyBucket<Uint128> = ReadonlyBucket::multilevel(
+        storage,
+        &[PREFIX_TICK_VOLUME, pair_key, direction.as_bytes()],
+    );
 
     let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
     let start_after = start_after.map(|id| id.atomics().to_be_bytes().to_vec());
@@ -101,15 +115,20 @@ pub fn query_ticks_with_end(
         _ => (end, start_after, OrderBy::Descending),
     };
 
+    // malformed tick entries are skipped, they can be listed with query_malformed_tick_keys
     let ticks = position_bucket
         .range(start.as_deref(), end.as_deref(), order_by)
+        .filter_map(|item| {
+            let (k, total_orders) = item.ok()?;
+            Some((price_from_key(&k)?, k, total_orders))
+        })
         .take(limit)
-        .map(|item| {
-            let (k, total_orders) = item?;
-            let price = Decimal::raw(u128::from_be_bytes(k.try_into().unwrap()));
+        .map(|(price, k, total_orders)| {
+            let total_base_amount = volume_bucket.may_load(&k)?.unwrap_or_default();
             Ok(TickResponse {
                 price,
                 total_orders,
+                total_base_amount,
             })
         })
         .collect::<StdResult<Vec<TickResponse>>>()?;
@@ -127,9 +146,92 @@ pub fn query_tick(
     let total_orders =
         ReadonlyBucket::<u64>::multilevel(storage, &[PREFIX_TICK, pair_key, direction.as_bytes()])
             .load(&price_key)?;
+    let total_base_amount = ReadonlyBucket::<Uint128>::multilevel(
+        storage,
+        &[PREFIX_TICK_VOLUME, pair_key, direction.as_bytes()],
+    )
+    .may_load(&price_key)?
+    .unwrap_or_default();
 
     Ok(TickResponse {
         price,
         total_orders,
+        total_base_amount,
     })
 }
+
+/// round the price half up to the decimal places, return the rounded price and the range [lower, upper)
+/// of the prices rounding to it
+pub fn price_level_range(price: Decimal, precision: u32) -> (Decimal, Decimal, Decimal) {
+    let unit = 10u128.pow(Decimal::DECIMAL_PLACES - precision.min(Decimal::DECIMAL_PLACES));
+    let half = unit / 2;
+    let rounded = price.atomics().u128().saturating_add(half) / unit * unit;
+
+    (
+        Decimal::raw(rounded),
+        Decimal::raw(rounded.saturating_sub(half)),
+        Decimal::raw(rounded.saturating_add(unit - half)),
+    )
+}
+
+/// ticks with price in [lower, upper), ascending, at most MAX_LIMIT ticks are read
+pub fn query_ticks_in_range(
+    storage: &dyn Storage,
+    pair_key: &[u8],
+    direction: OrderDirection,
+    lower: Decimal,
+    upper: Decimal,
+) -> StdResult<Vec<TickResponse>> {
+    let position_bucket: ReadonlyBucket<u64> =
+        ReadonlyBucket::multilevel(storage, &[PREFIX_TICK, pair_key, direction.as_bytes()]);
+    let volume_bucket: ReadonlyBucket<Uint128> = ReadonlyBucket::multilevel(
+        storage,
+        &[PREFIX_TICK_VOLUME, pair_key, direction.as_bytes()],
+    );
+
+    let start = lower.atomics().to_be_bytes();
+    let end = upper.atomics().to_be_bytes();
+
+    position_bucket
+        .range(
+            Some(start.as_slice()),
+            Some(end.as_slice()),
+            OrderBy::Ascending,
+        )
+        .filter_map(|item| {
+            let (k, total_orders) = item.ok()?;
+            Some((price_from_key(&k)?, k, total_orders))
+        })
+        .take(MAX_LIMIT as usize)
+        .map(|(price, k, total_orders)| {
+            let total_base_amount = volume_bucket.may_load(&k)?.unwrap_or_default();
+            Ok(TickResponse {
+                price,
+                total_orders,
+                total_base_amount,
+            })
+        })
+        .collect()
+}
+
+/// list raw keys of the tick index whose price or total orders can not be decoded
+pub fn query_malformed_tick_keys(
+    storage: &dyn Storage,
+    pair_key: &[u8],
+    direction: OrderDirection,
+    limit: Option<u32>,
+) -> StdResult<MalformedTickKeysResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    let keys = ReadonlyPrefixedStorage::multilevel(
+        storage,
+        &[PREFIX_TICK, pair_key, direction.as_bytes()],
+    )
+    .range(None, None, OrderBy::Ascending)
+    .filter(|(k, v)| price_from_key(k).is_none() || from_slice::<u64>(v).is_err())
+    .take(limit)
+    .map(|(k, _)| Binary::from(k))
+    .collect();
+
+    Ok(MalformedTickKeysResponse { keys })
+}
@@ -5,18 +5,24 @@ use cosmwasm_std::{
     StdResult, Uint128,
 };
 
-use oraiswap::asset::ORAI_DENOM;
+use oraiswap::asset::{AssetInfo, ORAI_DENOM};
 use oraiswap::oracle::{
-    ContractInfo, ContractInfoResponse, ExchangeRateItem, ExchangeRateResponse,
-    ExchangeRatesResponse, ExecuteMsg, MigrateMsg, OracleContractQuery, OracleExchangeQuery,
-    OracleTreasuryQuery, QueryMsg, TaxCapResponse, TaxRateResponse,
+    ContractInfo, ContractInfoResponse, ConvertAmountResponse, ConvertRateItem, ExchangeRateItem,
+    ExchangeRateResponse, ExchangeRatesResponse, ExecuteMsg, FeederResponse, MigrateMsg,
+    OracleContractQuery, OracleExchangeQuery, OracleTreasuryQuery, QueryMsg, TaxCapResponse,
+    TaxInfo, TaxRateResponse,
 };
+use oraiswap::pair::{QueryMsg as PairQueryMsg, SpotPriceResponse};
+use oraiswap::querier::query_pair_info_from_pair;
 
 use oraiswap::error::ContractError;
 use oraiswap::oracle::InstantiateMsg;
 
 // use crate::msg::{ExecuteMsg, InstantiateMsg};
-use crate::state::{CONTRACT_INFO, EXCHANGE_RATES, TAX_CAP, TAX_RATE};
+use crate::state::{
+    CONTRACT_INFO, EXCHANGE_RATES, FEEDERS, PRICE_PAIRS, PRICE_UPDATED_AT, TAX_CAP, TAX_DISABLED,
+    TAX_RATE,
+};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:oraiswap_oracle";
@@ -59,7 +65,7 @@ pub fn instantiate(
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
@@ -71,7 +77,16 @@ pub fn execute(
         ExecuteMsg::DeleteExchangeRate { denom } => execute_delete_exchange_rate(deps, info, denom),
         ExecuteMsg::UpdateTaxCap { cap, denom } => execute_update_tax_cap(deps, info, denom, cap),
         ExecuteMsg::UpdateTaxRate { rate } => execute_update_tax_rate(deps, info, rate),
+        ExecuteMsg::UpdateTaxEnabled { enabled } => execute_update_tax_enabled(deps, info, enabled),
         ExecuteMsg::UpdateAdmin { admin } => execute_update_admin(deps, info, admin),
+        ExecuteMsg::RegisterFeeder { symbol, feeder } => {
+            execute_register_feeder(deps, info, symbol, feeder)
+        }
+        ExecuteMsg::DeregisterFeeder { symbol } => execute_deregister_feeder(deps, info, symbol),
+        ExecuteMsg::FeedPrices { prices } => execute_feed_prices(deps, env, info, prices),
+        ExecuteMsg::UpdatePricePair { denom, pair_addr } => {
+            execute_update_price_pair(deps, info, denom, pair_addr)
+        }
     }
 }
 
@@ -117,6 +132,25 @@ pub fn execute_update_tax_rate(
     Ok(Response::default())
 }
 
+pub fn execute_update_tax_enabled(
+    deps: DepsMut,
+    info: MessageInfo,
+    enabled: bool,
+) -> Result<Response, ContractError> {
+    let contract_info = CONTRACT_INFO.load(deps.storage)?;
+    let sender_addr = deps.api.addr_canonicalize(info.sender.as_str())?;
+
+    // check authorized
+    if contract_info.admin.ne(&sender_addr) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    TAX_DISABLED.save(deps.storage, &!enabled)?;
+
+    // return nothing new
+    Ok(Response::default())
+}
+
 pub fn execute_update_admin(
     deps: DepsMut,
     info: MessageInfo,
@@ -175,12 +209,172 @@ pub fn execute_delete_exchange_rate(
     Ok(Response::default())
 }
 
+pub fn execute_register_feeder(
+    deps: DepsMut,
+    info: MessageInfo,
+    symbol: String,
+    feeder: Addr,
+) -> Result<Response, ContractError> {
+    let contract_info = CONTRACT_INFO.load(deps.storage)?;
+    let sender_addr = deps.api.addr_canonicalize(info.sender.as_str())?;
+
+    // check authorized
+    if contract_info.admin.ne(&sender_addr) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // orai is the base of all exchange rates
+    if symbol == ORAI_DENOM {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Cannot feed the price of the base denom",
+        )));
+    }
+
+    FEEDERS.save(
+        deps.storage,
+        symbol.as_bytes(),
+        &deps.api.addr_canonicalize(feeder.as_str())?,
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "register_feeder"),
+        ("symbol", &symbol),
+        ("feeder", feeder.as_str()),
+    ]))
+}
+
+pub fn execute_deregister_feeder(
+    deps: DepsMut,
+    info: MessageInfo,
+    symbol: String,
+) -> Result<Response, ContractError> {
+    let contract_info = CONTRACT_INFO.load(deps.storage)?;
+    let sender_addr = deps.api.addr_canonicalize(info.sender.as_str())?;
+
+    // check authorized
+    if contract_info.admin.ne(&sender_addr) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    FEEDERS.remove(deps.storage, symbol.as_bytes());
+
+    Ok(Response::new().add_attributes(vec![("action", "deregister_feeder"), ("symbol", &symbol)]))
+}
+
+pub fn execute_update_price_pair(
+    deps: DepsMut,
+    info: MessageInfo,
+    denom: String,
+    pair_addr: Option<Addr>,
+) -> Result<Response, ContractError> {
+    let contract_info = CONTRACT_INFO.load(deps.storage)?;
+    let sender_addr = deps.api.addr_canonicalize(info.sender.as_str())?;
+
+    // check authorized
+    if contract_info.admin.ne(&sender_addr) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let pair_addr = match pair_addr {
+        Some(pair_addr) => pair_addr,
+        None => {
+            PRICE_PAIRS.remove(deps.storage, denom.as_bytes());
+            return Ok(Response::new().add_attributes(vec![
+                ("action", "update_price_pair"),
+                ("denom", &denom),
+                ("pair_addr", ""),
+            ]));
+        }
+    };
+
+    // the pair must trade the denom against orai
+    let pair_info = query_pair_info_from_pair(&deps.querier, pair_addr.clone())?;
+    let has_asset = |denom: &str| {
+        pair_info
+            .asset_infos
+            .iter()
+            .any(|asset_info| asset_info.to_string() == denom)
+    };
+    if denom == ORAI_DENOM || !has_asset(&denom) || !has_asset(ORAI_DENOM) {
+        return Err(ContractError::AssetMismatch {});
+    }
+
+    PRICE_PAIRS.save(
+        deps.storage,
+        denom.as_bytes(),
+        &deps.api.addr_canonicalize(pair_addr.as_str())?,
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "update_price_pair"),
+        ("denom", &denom),
+        ("pair_addr", pair_addr.as_str()),
+    ]))
+}
+
+/// feeders push exchange rates to orai, each symbol only accepts prices from its feeder
+/// and newer than the last fed price, prices from the future are rejected
+pub fn execute_feed_prices(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    prices: Vec<(String, Decimal, u64)>,
+) -> Result<Response, ContractError> {
+    let sender_addr = deps.api.addr_canonicalize(info.sender.as_str())?;
+
+    for (symbol, exchange_rate, timestamp) in prices.iter() {
+        let feeder = FEEDERS.may_load(deps.storage, symbol.as_bytes())?;
+        if feeder.ne(&Some(sender_addr.clone())) {
+            return Err(ContractError::Unauthorized {});
+        }
+
+        if exchange_rate.is_zero() {
+            return Err(ContractError::Std(StdError::generic_err(format!(
+                "Invalid zero price for {}",
+                symbol
+            ))));
+        }
+
+        if *timestamp > env.block.time.seconds() {
+            return Err(ContractError::Std(StdError::generic_err(format!(
+                "Price of {} is fed from the future",
+                symbol
+            ))));
+        }
+
+        let last_updated = PRICE_UPDATED_AT.may_load(deps.storage, symbol.as_bytes())?;
+        if last_updated.map_or(false, |last_updated| *timestamp <= last_updated) {
+            return Err(ContractError::Std(StdError::generic_err(format!(
+                "Price of {} is older than the last fed price",
+                symbol
+            ))));
+        }
+
+        EXCHANGE_RATES.save(deps.storage, symbol.as_bytes(), exchange_rate)?;
+        PRICE_UPDATED_AT.save(deps.storage, symbol.as_bytes(), timestamp)?;
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "feed_prices"),
+        ("feeder", info.sender.as_str()),
+        (
+            "prices",
+            &prices
+                .iter()
+                .map(|(symbol, exchange_rate, _)| format!("{}{}", exchange_rate, symbol))
+                .collect::<Vec<String>>()
+                .join(","),
+        ),
+    ]))
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Treasury(query_data) => match query_data {
             OracleTreasuryQuery::TaxRate {} => to_binary(&query_tax_rate(deps)?),
             OracleTreasuryQuery::TaxCap { denom } => to_binary(&query_tax_cap(deps, denom)?),
+            OracleTreasuryQuery::TaxInfo { denom } => to_binary(&query_tax_info(deps, denom)?),
         },
         QueryMsg::Exchange(query_data) => match query_data {
             OracleExchangeQuery::ExchangeRate {
@@ -199,17 +393,34 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
                 base_denom.unwrap_or(ORAI_DENOM.to_string()),
                 quote_denoms,
             )?),
+            OracleExchangeQuery::ConvertAmount { from, to, amount } => {
+                to_binary(&query_convert_amount(deps, from, to, amount)?)
+            }
         },
         QueryMsg::Contract(query_data) => match query_data {
             OracleContractQuery::ContractInfo {} => to_binary(&query_contract_info(deps)?),
             OracleContractQuery::RewardPool { denom } => {
                 to_binary(&query_contract_balance(deps, env, denom)?)
             }
+            OracleContractQuery::Feeder { symbol } => to_binary(&query_feeder(deps, symbol)?),
         },
     }
 }
 
+fn is_tax_disabled(deps: Deps) -> bool {
+    TAX_DISABLED
+        .may_load(deps.storage)
+        .unwrap_or_default()
+        .unwrap_or_default()
+}
+
 pub fn query_tax_rate(deps: Deps) -> StdResult<TaxRateResponse> {
+    if is_tax_disabled(deps) {
+        return Ok(TaxRateResponse {
+            rate: Decimal::zero(),
+        });
+    }
+
     if let Ok(Some(rate)) = TAX_RATE.may_load(deps.storage) {
         return Ok(TaxRateResponse { rate });
     }
@@ -220,6 +431,12 @@ pub fn query_tax_rate(deps: Deps) -> StdResult<TaxRateResponse> {
 }
 
 pub fn query_tax_cap(deps: Deps, denom: String) -> StdResult<TaxCapResponse> {
+    if is_tax_disabled(deps) {
+        return Ok(TaxCapResponse {
+            cap: Uint128::zero(),
+        });
+    }
+
     if let Ok(Some(cap)) = TAX_CAP.may_load(deps.storage, denom.as_bytes()) {
         return Ok(TaxCapResponse { cap });
     }
@@ -229,6 +446,13 @@ pub fn query_tax_cap(deps: Deps, denom: String) -> StdResult<TaxCapResponse> {
     })
 }
 
+pub fn query_tax_info(deps: Deps, denom: String) -> StdResult<TaxInfo> {
+    Ok(TaxInfo {
+        rate: query_tax_rate(deps)?.rate,
+        cap: query_tax_cap(deps, denom)?.cap,
+    })
+}
+
 pub fn query_exchange_rate(
     deps: Deps,
     base_denom: String,
@@ -273,6 +497,32 @@ pub fn query_exchange_rates(
     Ok(res)
 }
 
+/// amount of to = amount of from * (from / orai) / (to / orai)
+pub fn query_convert_amount(
+    deps: Deps,
+    from: String,
+    to: String,
+    amount: Uint128,
+) -> StdResult<ConvertAmountResponse> {
+    let from_rate = get_convert_rate(deps, from)?;
+    let to_rate = get_convert_rate(deps, to)?;
+
+    if to_rate.exchange_rate.is_zero() {
+        return Err(StdError::generic_err(format!(
+            "Invalid zero exchange rate for {}",
+            to_rate.denom
+        )));
+    }
+
+    Ok(ConvertAmountResponse {
+        amount: amount.multiply_ratio(
+            from_rate.exchange_rate.atomics(),
+            to_rate.exchange_rate.atomics(),
+        ),
+        path: vec![from_rate, to_rate],
+    })
+}
+
 pub fn query_contract_info(deps: Deps) -> StdResult<ContractInfoResponse> {
     let info = CONTRACT_INFO.load(deps.storage)?;
     Ok(ContractInfoResponse {
@@ -290,6 +540,20 @@ pub fn query_contract_balance(deps: Deps, env: Env, denom: String) -> StdResult<
     deps.querier.query_balance(env.contract.address, &denom)
 }
 
+pub fn query_feeder(deps: Deps, symbol: String) -> StdResult<FeederResponse> {
+    let feeder = FEEDERS
+        .may_load(deps.storage, symbol.as_bytes())?
+        .ok_or_else(|| StdError::NotFound {
+            kind: format!("Feeder not found for symbol: {}", symbol),
+        })?;
+
+    Ok(FeederResponse {
+        feeder: deps.api.addr_humanize(&feeder)?,
+        last_updated: PRICE_UPDATED_AT.may_load(deps.storage, symbol.as_bytes())?,
+        symbol,
+    })
+}
+
 fn get_orai_exchange_rate(deps: Deps, denom: &str) -> StdResult<Decimal> {
     if denom == ORAI_DENOM {
         return Ok(Decimal::one());
@@ -298,6 +562,62 @@ fn get_orai_exchange_rate(deps: Deps, denom: &str) -> StdResult<Decimal> {
     EXCHANGE_RATES.load(deps.storage, denom.as_bytes())
 }
 
+/// exchange rate of the denom to orai, falls back to the spot price of the price pair without exchange rate
+fn get_convert_rate(deps: Deps, denom: String) -> StdResult<ConvertRateItem> {
+    if denom == ORAI_DENOM {
+        return Ok(ConvertRateItem {
+            denom,
+            exchange_rate: Decimal::one(),
+            pair_addr: None,
+        });
+    }
+
+    if let Some(exchange_rate) = EXCHANGE_RATES.may_load(deps.storage, denom.as_bytes())? {
+        return Ok(ConvertRateItem {
+            denom,
+            exchange_rate,
+            pair_addr: None,
+        });
+    }
+
+    let pair_addr = match PRICE_PAIRS.may_load(deps.storage, denom.as_bytes())? {
+        Some(pair_addr) => deps.api.addr_humanize(&pair_addr)?,
+        None => {
+            return Err(StdError::NotFound {
+                kind: format!("Exchange rate not found for denom: {}", denom),
+            })
+        }
+    };
+
+    let pair_info = query_pair_info_from_pair(&deps.querier, pair_addr.clone())?;
+    let offer_asset_info = pair_info
+        .asset_infos
+        .into_iter()
+        .find(|asset_info| asset_info.to_string() == denom)
+        .ok_or_else(|| StdError::generic_err(format!("Price pair does not trade {}", denom)))?;
+
+    let res: SpotPriceResponse = deps.querier.query_wasm_smart(
+        pair_addr.clone(),
+        &PairQueryMsg::SpotPrice {
+            offer_asset_info,
+            ask_asset_info: AssetInfo::NativeToken {
+                denom: ORAI_DENOM.to_string(),
+            },
+        },
+    )?;
+    let exchange_rate = Decimal::from_atomics(
+        Uint128::try_from(res.price.atomics())?,
+        Decimal::DECIMAL_PLACES,
+    )
+    .map_err(|err| StdError::generic_err(err.to_string()))?;
+
+    Ok(ConvertRateItem {
+        denom,
+        exchange_rate,
+        pair_addr: Some(pair_addr),
+    })
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
     Ok(Response::default())
@@ -1,4 +1,4 @@
-use cosmwasm_std::{Decimal, Uint128};
+use cosmwasm_std::{CanonicalAddr, Decimal, Uint128};
 use cw_storage_plus::{Item, Map};
 use oraiswap::oracle::ContractInfo;
 
@@ -7,6 +7,15 @@ pub const CONTRACT_INFO: Item<ContractInfo> = Item::new("\u{0}\u{13}contract_inf
 pub const TAX_RATE: Item<Decimal> = Item::new("\u{0}\u{8}tax_rate");
 
 pub const TAX_CAP: Map<&[u8], Uint128> = Map::new("tax_cap");
+// no tax is charged when disabled
+pub const TAX_DISABLED: Item<bool> = Item::new("tax_disabled");
 /// Exchange rate of denom to Orai
 /// (QUOTE_DENOM / ORAI)  / (BASE_DENOM / ORAI) = QUOTE_DENOM / BASE_DENOM
 pub const EXCHANGE_RATES: Map<&[u8], Decimal> = Map::new("exchange_rates");
+
+// the address allowed to feed the exchange rate of a symbol
+pub const FEEDERS: Map<&[u8], CanonicalAddr> = Map::new("feeders");
+// timestamp of the last fed exchange rate of a symbol, older prices are rejected
+pub const PRICE_UPDATED_AT: Map<&[u8], u64> = Map::new("price_updated_at");
+// pair of a denom with orai, its spot price is the exchange rate of the denom without one
+pub const PRICE_PAIRS: Map<&[u8], CanonicalAddr> = Map::new("price_pairs");
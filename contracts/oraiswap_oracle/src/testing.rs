@@ -1,9 +1,9 @@
-use cosmwasm_std::testing::MOCK_CONTRACT_ADDR;
+use cosmwasm_std::testing::{mock_env, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{to_binary, Addr, BankMsg, Coin, CosmosMsg, Decimal, Uint128, WasmMsg};
 
 use oraiswap::asset::{Asset, AssetInfo, ORAI_DENOM};
 use oraiswap::create_entry_points_testing;
-use oraiswap::oracle::{ExecuteMsg, OracleContract};
+use oraiswap::oracle::{ConvertRateItem, ExecuteMsg, FeederResponse, OracleContract, TaxInfo};
 use oraiswap::testing::{MockApp, APP_OWNER};
 
 fn setup_contract() -> MockApp {
@@ -193,3 +193,289 @@ fn test_asset() {
         })
     );
 }
+
+#[test]
+fn tax_info_and_disabled_tax() {
+    let mut app = setup_contract();
+
+    app.set_tax(
+        Decimal::percent(1),
+        &[(&"uusd".to_string(), &Uint128::from(1000000u128))],
+    );
+
+    let orai_oracle = OracleContract(app.oracle_addr.clone());
+
+    let tax_info = orai_oracle
+        .query_tax_info(&app.as_querier(), "uusd".to_string())
+        .unwrap();
+    assert_eq!(
+        tax_info,
+        TaxInfo {
+            rate: Decimal::percent(1),
+            cap: Uint128::from(1000000u128),
+        }
+    );
+    assert_eq!(
+        tax_info.compute_tax(Uint128::from(123123u128)).unwrap(),
+        Uint128::from(1220u128)
+    );
+
+    // only admin can disable tax
+    let res = app.execute(
+        Addr::unchecked("addr0000"),
+        app.oracle_addr.clone(),
+        &ExecuteMsg::UpdateTaxEnabled { enabled: false },
+        &[],
+    );
+    app.assert_fail(res);
+
+    app.execute(
+        Addr::unchecked(APP_OWNER),
+        app.oracle_addr.clone(),
+        &ExecuteMsg::UpdateTaxEnabled { enabled: false },
+        &[],
+    )
+    .unwrap();
+
+    // no tax even for denoms without tax cap
+    let native_token_asset = Asset {
+        amount: Uint128::from(123123u128),
+        info: AssetInfo::NativeToken {
+            denom: "airi".to_string(),
+        },
+    };
+    assert_eq!(
+        native_token_asset
+            .compute_tax(&orai_oracle, &app.as_querier())
+            .unwrap(),
+        Uint128::zero()
+    );
+    assert_eq!(
+        orai_oracle
+            .query_tax_info(&app.as_querier(), "uusd".to_string())
+            .unwrap(),
+        TaxInfo {
+            rate: Decimal::zero(),
+            cap: Uint128::zero(),
+        }
+    );
+}
+
+#[test]
+fn feed_prices() {
+    let mut app = setup_contract();
+    let oracle_contract = OracleContract(app.oracle_addr.clone());
+    // block time of the app starts from the mock env
+    let genesis_time = mock_env().block.time.seconds();
+
+    // only admin can register feeders
+    let register_msg = ExecuteMsg::RegisterFeeder {
+        symbol: "usdt".to_string(),
+        feeder: Addr::unchecked("feeder"),
+    };
+    let res = app.execute(
+        Addr::unchecked("addr0000"),
+        app.oracle_addr.clone(),
+        &register_msg,
+        &[],
+    );
+    app.assert_fail(res);
+    app.execute(
+        Addr::unchecked(APP_OWNER),
+        app.oracle_addr.clone(),
+        &register_msg,
+        &[],
+    )
+    .unwrap();
+
+    // orai is the base of all exchange rates
+    let res = app.execute(
+        Addr::unchecked(APP_OWNER),
+        app.oracle_addr.clone(),
+        &ExecuteMsg::RegisterFeeder {
+            symbol: ORAI_DENOM.to_string(),
+            feeder: Addr::unchecked("feeder"),
+        },
+        &[],
+    );
+    app.assert_fail(res);
+
+    let feed_msg = |symbol: &str, exchange_rate: Decimal, timestamp: u64| ExecuteMsg::FeedPrices {
+        prices: vec![(symbol.to_string(), exchange_rate, timestamp)],
+    };
+
+    // only the feeder of the symbol can feed it
+    let res = app.execute(
+        Addr::unchecked("addr0000"),
+        app.oracle_addr.clone(),
+        &feed_msg("usdt", Decimal::percent(10), genesis_time),
+        &[],
+    );
+    app.assert_fail(res);
+    let res = app.execute(
+        Addr::unchecked("feeder"),
+        app.oracle_addr.clone(),
+        &feed_msg("airi", Decimal::percent(1), genesis_time),
+        &[],
+    );
+    app.assert_fail(res);
+
+    // 1 orai = 10 usdt
+    app.execute(
+        Addr::unchecked("feeder"),
+        app.oracle_addr.clone(),
+        &feed_msg("usdt", Decimal::percent(10), genesis_time),
+        &[],
+    )
+    .unwrap();
+    let exchange_rate_res = oracle_contract
+        .query_exchange_rate(
+            &app.as_querier(),
+            "usdt".to_string(),
+            ORAI_DENOM.to_string(),
+        )
+        .unwrap();
+    assert_eq!("10", exchange_rate_res.item.exchange_rate.to_string());
+
+    // prices not newer than the last fed price or from the future are rejected
+    for timestamp in [genesis_time, genesis_time + 1000000] {
+        let res = app.execute(
+            Addr::unchecked("feeder"),
+            app.oracle_addr.clone(),
+            &feed_msg("usdt", Decimal::percent(20), timestamp),
+            &[],
+        );
+        app.assert_fail(res);
+    }
+
+    app.execute(
+        Addr::unchecked("feeder"),
+        app.oracle_addr.clone(),
+        &feed_msg("usdt", Decimal::percent(20), genesis_time + 1),
+        &[],
+    )
+    .unwrap();
+    let exchange_rate_res = oracle_contract
+        .query_exchange_rate(
+            &app.as_querier(),
+            "usdt".to_string(),
+            ORAI_DENOM.to_string(),
+        )
+        .unwrap();
+    assert_eq!("5", exchange_rate_res.item.exchange_rate.to_string());
+
+    assert_eq!(
+        oracle_contract
+            .query_feeder(&app.as_querier(), "usdt")
+            .unwrap(),
+        FeederResponse {
+            symbol: "usdt".to_string(),
+            feeder: Addr::unchecked("feeder"),
+            last_updated: Some(genesis_time + 1),
+        }
+    );
+
+    // deregistered feeder can no longer feed
+    app.execute(
+        Addr::unchecked(APP_OWNER),
+        app.oracle_addr.clone(),
+        &ExecuteMsg::DeregisterFeeder {
+            symbol: "usdt".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    let res = app.execute(
+        Addr::unchecked("feeder"),
+        app.oracle_addr.clone(),
+        &feed_msg("usdt", Decimal::percent(30), genesis_time + 2),
+        &[],
+    );
+    app.assert_fail(res);
+    assert!(oracle_contract
+        .query_feeder(&app.as_querier(), "usdt")
+        .is_err());
+}
+
+#[test]
+fn convert_amount() {
+    let mut app = setup_contract();
+    let oracle_contract = OracleContract(app.oracle_addr.clone());
+
+    // 1 orai = 10 usdt, 1 orai = 2 atom
+    for (denom, exchange_rate) in [
+        ("usdt", Decimal::percent(10)),
+        ("atom", Decimal::percent(50)),
+    ] {
+        app.execute(
+            Addr::unchecked(APP_OWNER),
+            app.oracle_addr.clone(),
+            &ExecuteMsg::UpdateExchangeRate {
+                denom: denom.to_string(),
+                exchange_rate,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    let res = oracle_contract
+        .query_convert_amount(&app.as_querier(), "usdt", "atom", Uint128::from(100u128))
+        .unwrap();
+    assert_eq!(res.amount, Uint128::from(20u128));
+    assert_eq!(
+        res.path,
+        vec![
+            ConvertRateItem {
+                denom: "usdt".to_string(),
+                exchange_rate: Decimal::percent(10),
+                pair_addr: None,
+            },
+            ConvertRateItem {
+                denom: "atom".to_string(),
+                exchange_rate: Decimal::percent(50),
+                pair_addr: None,
+            }
+        ]
+    );
+
+    let res = oracle_contract
+        .query_convert_amount(
+            &app.as_querier(),
+            "atom",
+            ORAI_DENOM,
+            Uint128::from(100u128),
+        )
+        .unwrap();
+    assert_eq!(res.amount, Uint128::from(50u128));
+
+    // no exchange rate nor price pair
+    assert!(oracle_contract
+        .query_convert_amount(
+            &app.as_querier(),
+            "airi",
+            ORAI_DENOM,
+            Uint128::from(100u128)
+        )
+        .is_err());
+
+    // only admin can set the price pair, which must be a pair of the denom with orai
+    let update_msg = ExecuteMsg::UpdatePricePair {
+        denom: "airi".to_string(),
+        pair_addr: Some(app.oracle_addr.clone()),
+    };
+    let res = app.execute(
+        Addr::unchecked("addr0000"),
+        app.oracle_addr.clone(),
+        &update_msg,
+        &[],
+    );
+    app.assert_fail(res);
+    let res = app.execute(
+        Addr::unchecked(APP_OWNER),
+        app.oracle_addr.clone(),
+        &update_msg,
+        &[],
+    );
+    app.assert_fail(res);
+}
@@ -1,30 +1,42 @@
-use crate::state::PAIR_INFO;
+use crate::state::{
+    CONVERTER, GUARDIAN, LAST_PROVIDE_PRICES, MAX_TRADE_BPS, PAIR_INFO, PAUSED, REGISTRY, ROUTER,
+    ROUTER_COMMISSION_RATE, STAKING_CONTRACT,
+};
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 
 use cosmwasm_std::{
-    from_binary, to_binary, Addr, Binary, CanonicalAddr, Coin, CosmosMsg, Decimal, Decimal256,
-    Deps, DepsMut, Env, MessageInfo, Reply, Response, StdError, StdResult, SubMsg, Uint128,
-    Uint256, WasmMsg,
+    coins, from_binary, to_binary, Addr, BankMsg, Binary, CanonicalAddr, Coin, CosmosMsg, Decimal,
+    Decimal256, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdError, StdResult, SubMsg,
+    Uint128, Uint256, WasmMsg,
 };
 
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg, MinterResponse};
 use cw20_base::msg::InstantiateMsg as TokenInstantiateMsg;
+use cw_utils::parse_reply_instantiate_data;
 use integer_sqrt::IntegerSquareRoot;
-use oraiswap::asset::{Asset, AssetInfo, PairInfoRaw};
+use oraiswap::asset::{Asset, AssetInfo, PairInfoRaw, ORAI_DENOM};
+use oraiswap::converter::{
+    asset_into_convert_msg, ConvertInfoResponse, ConvertLimitResponse,
+    QueryMsg as ConverterQueryMsg, TokenRatio,
+};
 use oraiswap::error::ContractError;
+use oraiswap::math::Converter128;
 use oraiswap::oracle::OracleContract;
 use oraiswap::pair::{
-    compute_offer_amount, compute_swap, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg,
-    PairResponse, PoolResponse, QueryMsg, ReverseSimulationResponse, SimulationResponse,
-    DEFAULT_COMMISSION_RATE,
+    assert_invariant, assert_max_spread, compute_offer_amount, compute_swap, Cw20HookMsg,
+    ExecuteMsg, InstantiateMsg, LpTokenType, LpValueResponse, MaxTradeBpsResponse, MigrateMsg,
+    NetReceiveEstimateResponse, PairResponse, PoolResponse, QueryMsg, ReverseSimulationResponse,
+    RouterCommissionResponse, SimulationResponse, SpotPriceResponse, DEFAULT_COMMISSION_RATE,
+    DEFAULT_LP_TOKEN_NAME, DEFAULT_LP_TOKEN_SYMBOL, LP_SUBDENOM,
 };
-use oraiswap::querier::query_supply;
-use oraiswap::response::MsgInstantiateContractResponse;
-use std::convert::TryFrom;
+use oraiswap::querier::{query_asset_decimals, query_supply};
+use oraiswap::staking::Cw20HookMsg as StakingCw20HookMsg;
+use oraiswap::tokenfactory::{burn_msg, create_denom_msg, mint_msg, tokenfactory_denom};
 use std::str::FromStr;
 
 const INSTANTIATE_REPLY_ID: u64 = 1;
+const MAX_TRADE_BPS_LIMIT: u16 = 10000;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -33,7 +45,7 @@ pub fn instantiate(
     _info: MessageInfo,
     msg: InstantiateMsg,
 ) -> StdResult<Response> {
-    let pair_info = &PairInfoRaw {
+    let mut pair_info = PairInfoRaw {
         // return infomation from oracle, update by multisig wallet
         oracle_addr: deps.api.addr_canonicalize(msg.oracle_addr.as_str())?,
         // the current contract address
@@ -49,24 +61,63 @@ pub fn instantiate(
         commission_rate: msg
             .commission_rate
             .unwrap_or(DEFAULT_COMMISSION_RATE.to_string()),
+        lp_denom: None,
     };
 
-    PAIR_INFO.save(deps.storage, pair_info)?;
+    if let Some(guardian) = msg.guardian {
+        GUARDIAN.save(
+            deps.storage,
+            &deps.api.addr_canonicalize(guardian.as_str())?,
+        )?;
+    }
+
+    if let Some(staking_contract) = msg.staking_contract {
+        STAKING_CONTRACT.save(
+            deps.storage,
+            &deps.api.addr_canonicalize(staking_contract.as_str())?,
+        )?;
+    }
+
+    // the pair is the admin of its tokenfactory LP denom, so it is set as the liquidity token
+    if msg.lp_token_type.unwrap_or_default() == LpTokenType::TokenFactory {
+        if msg.lp_token_info.is_some() {
+            return Err(StdError::generic_err(
+                "lp_token_info is only supported by cw20 liquidity tokens",
+            ));
+        }
+
+        let lp_denom = tokenfactory_denom(&env.contract.address, LP_SUBDENOM);
+        pair_info.liquidity_token = pair_info.contract_addr.clone();
+        pair_info.lp_denom = Some(lp_denom.clone());
+        PAIR_INFO.save(deps.storage, &pair_info)?;
+
+        return Ok(Response::new()
+            .add_message(create_denom_msg(&env.contract.address, LP_SUBDENOM)?)
+            .add_attribute("lp_denom", lp_denom));
+    }
+
+    PAIR_INFO.save(deps.storage, &pair_info)?;
+
+    let lp_token_info = msg.lp_token_info.unwrap_or_default();
 
     Ok(Response::new().add_submessage(SubMsg::reply_on_success(
         WasmMsg::Instantiate {
             admin: None,
             code_id: msg.token_code_id,
             msg: to_binary(&TokenInstantiateMsg {
-                name: "oraiswap liquidity token".to_string(),
-                symbol: "uLP".to_string(),
+                name: lp_token_info
+                    .name
+                    .unwrap_or(DEFAULT_LP_TOKEN_NAME.to_string()),
+                symbol: lp_token_info
+                    .symbol
+                    .unwrap_or(DEFAULT_LP_TOKEN_SYMBOL.to_string()),
                 decimals: 6,
                 initial_balances: vec![],
                 mint: Some(MinterResponse {
                     minter: env.contract.address.to_string(),
                     cap: None,
                 }),
-                marketing: None,
+                marketing: lp_token_info.marketing,
             })?,
             funds: vec![],
             label: "lp".to_string(),
@@ -90,18 +141,55 @@ pub fn execute(
             assets,
             slippage_tolerance,
             receiver,
-        } => provide_liquidity(deps, env, info, assets, slippage_tolerance, receiver),
+            auto_stake,
+        } => {
+            assert_not_paused(deps.as_ref())?;
+            provide_liquidity(
+                deps,
+                env,
+                info,
+                assets,
+                slippage_tolerance,
+                receiver,
+                auto_stake.unwrap_or_default(),
+            )
+        }
+        ExecuteMsg::WithdrawLiquidity {} => {
+            let pair_info: PairInfoRaw = PAIR_INFO.load(deps.storage)?;
+            let lp_denom = match pair_info.lp_denom {
+                Some(lp_denom) => lp_denom,
+                None => return Err(ContractError::Unauthorized {}),
+            };
+            if info.funds.len() != 1 {
+                return Err(ContractError::Std(StdError::generic_err(
+                    "Only the LP shares must be sent",
+                )));
+            }
+            let amount = amount_of(&info.funds, lp_denom);
+            if amount.is_zero() {
+                return Err(ContractError::InvalidZeroAmount {});
+            }
+            let sender = info.sender.clone();
+            withdraw_liquidity(deps, env, info, sender, amount)
+        }
+        ExecuteMsg::Donate { assets } => {
+            assert_not_paused(deps.as_ref())?;
+            donate(deps, env, info, assets)
+        }
         // swap token, can not swap native token directly
         ExecuteMsg::Swap {
             offer_asset,
             belief_price,
             max_spread,
             to,
+            return_asset_info,
         } => {
             if !offer_asset.is_native_token() {
                 return Err(ContractError::Unauthorized {});
             }
 
+            assert_not_paused(deps.as_ref())?;
+
             swap(
                 deps,
                 env,
@@ -111,9 +199,182 @@ pub fn execute(
                 belief_price,
                 max_spread,
                 to,
+                return_asset_info,
             )
         }
+        ExecuteMsg::SwapExactOut {
+            ask_asset,
+            max_offer_amount,
+            to,
+        } => {
+            assert_not_paused(deps.as_ref())?;
+
+            // the offer asset is the other side of the pair, it must be sent as native funds
+            let pair_info: PairInfoRaw = PAIR_INFO.load(deps.storage)?;
+            let offer_info = if ask_asset
+                .info
+                .eq(&pair_info.asset_infos[0].to_normal(deps.api)?)
+            {
+                pair_info.asset_infos[1].to_normal(deps.api)?
+            } else {
+                pair_info.asset_infos[0].to_normal(deps.api)?
+            };
+
+            let sent_amount = match &offer_info {
+                AssetInfo::NativeToken { denom } => amount_of(&info.funds, denom.to_string()),
+                AssetInfo::Token { .. } => return Err(ContractError::Unauthorized {}),
+            };
+
+            swap_exact_out(
+                deps,
+                env,
+                info.sender,
+                Asset {
+                    info: offer_info,
+                    amount: sent_amount,
+                },
+                ask_asset,
+                max_offer_amount,
+                to,
+            )
+        }
+        ExecuteMsg::Pause {} => execute_set_paused(deps, info, true),
+        ExecuteMsg::Unpause {} => execute_set_paused(deps, info, false),
+        ExecuteMsg::UpdateMaxTradeBps { max_trade_bps } => {
+            execute_update_max_trade_bps(deps, info, max_trade_bps)
+        }
+        ExecuteMsg::Skim { to } => execute_skim(deps, env, info, to),
+    }
+}
+
+pub fn execute_set_paused(
+    deps: DepsMut,
+    info: MessageInfo,
+    paused: bool,
+) -> Result<Response, ContractError> {
+    let guardian = GUARDIAN.may_load(deps.storage)?;
+    if guardian != Some(deps.api.addr_canonicalize(info.sender.as_str())?) {
+        return Err(ContractError::Unauthorized {});
     }
+
+    PAUSED.save(deps.storage, &paused)?;
+
+    Ok(Response::new().add_attribute("action", if paused { "pause" } else { "unpause" }))
+}
+
+/// the pools are the balances of the pair, so only denoms other than the pool assets
+/// and the LP shares, sent to the pair by mistake, can be skimmed
+pub fn execute_skim(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    to: Option<Addr>,
+) -> Result<Response, ContractError> {
+    let guardian = GUARDIAN.may_load(deps.storage)?;
+    if guardian != Some(deps.api.addr_canonicalize(info.sender.as_str())?) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let pair_info: PairInfoRaw = PAIR_INFO.load(deps.storage)?;
+    let pool_denoms = pair_info
+        .asset_infos
+        .iter()
+        .map(|asset_info| asset_info.to_normal(deps.api).map(|info| info.to_string()))
+        .collect::<StdResult<Vec<String>>>()?;
+
+    let amount: Vec<Coin> = deps
+        .querier
+        .query_all_balances(env.contract.address)?
+        .into_iter()
+        .filter(|coin| {
+            !coin.amount.is_zero()
+                && !pool_denoms.contains(&coin.denom)
+                && pair_info.lp_denom.as_ref() != Some(&coin.denom)
+        })
+        .collect();
+    if amount.is_empty() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+
+    let receiver = to.unwrap_or(info.sender);
+    let skimmed = amount
+        .iter()
+        .map(|coin| coin.to_string())
+        .collect::<Vec<String>>()
+        .join(",");
+
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: receiver.to_string(),
+            amount,
+        })
+        .add_attributes(vec![
+            ("action", "skim"),
+            ("receiver", receiver.as_str()),
+            ("amount", &skimmed),
+        ]))
+}
+
+fn assert_not_paused(deps: Deps) -> Result<(), ContractError> {
+    if PAUSED.may_load(deps.storage)?.unwrap_or_default() {
+        return Err(ContractError::Paused {});
+    }
+    Ok(())
+}
+
+pub fn execute_update_max_trade_bps(
+    deps: DepsMut,
+    info: MessageInfo,
+    max_trade_bps: Option<u16>,
+) -> Result<Response, ContractError> {
+    let guardian = GUARDIAN.may_load(deps.storage)?;
+    if guardian != Some(deps.api.addr_canonicalize(info.sender.as_str())?) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    save_max_trade_bps(deps, max_trade_bps)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "update_max_trade_bps"),
+        (
+            "max_trade_bps",
+            &max_trade_bps.map_or("none".to_string(), |bps| bps.to_string()),
+        ),
+    ]))
+}
+
+fn save_max_trade_bps(deps: DepsMut, max_trade_bps: Option<u16>) -> Result<(), ContractError> {
+    match max_trade_bps {
+        Some(max_trade_bps) => {
+            if max_trade_bps == 0 || max_trade_bps > MAX_TRADE_BPS_LIMIT {
+                return Err(ContractError::Std(StdError::generic_err(format!(
+                    "max_trade_bps must be between 1 and {}",
+                    MAX_TRADE_BPS_LIMIT
+                ))));
+            }
+            MAX_TRADE_BPS.save(deps.storage, &max_trade_bps)?;
+        }
+        None => MAX_TRADE_BPS.remove(deps.storage),
+    }
+    Ok(())
+}
+
+// reject swaps offering more than the max trade fraction of the offer pool in one call
+fn assert_max_trade(
+    deps: Deps,
+    offer_pool: Uint128,
+    offer_amount: Uint128,
+) -> Result<(), ContractError> {
+    if let Some(max_trade_bps) = MAX_TRADE_BPS.may_load(deps.storage)? {
+        let max_trade_amount = offer_pool.multiply_ratio(max_trade_bps, MAX_TRADE_BPS_LIMIT);
+        if offer_amount > max_trade_amount {
+            return Err(ContractError::MaxTradeExceeded {
+                offer_amount,
+                max_trade_amount,
+            });
+        }
+    }
+    Ok(())
 }
 
 pub fn receive_cw20(
@@ -129,6 +390,7 @@ pub fn receive_cw20(
             belief_price,
             max_spread,
             to,
+            return_asset_info,
         }) => {
             // only asset contract can execute this message
             let mut authorized: bool = false;
@@ -144,10 +406,13 @@ pub fn receive_cw20(
                 }
             }
 
-            if !authorized {
+            // other tokens are accepted if the converter converts them into a pool asset
+            if !authorized && !CONVERTER.exists(deps.storage) {
                 return Err(ContractError::Unauthorized {});
             }
 
+            assert_not_paused(deps.as_ref())?;
+
             let to_addr = if let Some(to_addr) = to {
                 Some(deps.api.addr_validate(to_addr.as_str())?)
             } else {
@@ -166,12 +431,42 @@ pub fn receive_cw20(
                 belief_price,
                 max_spread,
                 to_addr,
+                return_asset_info,
+            )
+        }
+        Ok(Cw20HookMsg::SwapExactOut {
+            ask_asset,
+            max_offer_amount,
+            to,
+        }) => {
+            assert_not_paused(deps.as_ref())?;
+
+            let to_addr = if let Some(to_addr) = to {
+                Some(deps.api.addr_validate(to_addr.as_str())?)
+            } else {
+                None
+            };
+
+            // swap_exact_out checks the sent token belongs to the pool
+            swap_exact_out(
+                deps,
+                env,
+                Addr::unchecked(cw20_msg.sender),
+                Asset {
+                    info: AssetInfo::Token { contract_addr },
+                    amount: cw20_msg.amount,
+                },
+                ask_asset,
+                max_offer_amount,
+                to_addr,
             )
         }
         // remove liquidity
         Ok(Cw20HookMsg::WithdrawLiquidity {}) => {
             let config: PairInfoRaw = PAIR_INFO.load(deps.storage)?;
-            if deps.api.addr_canonicalize(info.sender.as_str())? != config.liquidity_token {
+            if config.lp_denom.is_some()
+                || deps.api.addr_canonicalize(info.sender.as_str())? != config.liquidity_token
+            {
                 return Err(ContractError::Unauthorized {});
             }
             let sender_addr = deps.api.addr_validate(cw20_msg.sender.as_str())?;
@@ -184,15 +479,23 @@ pub fn receive_cw20(
 /// This just stores the result for future query
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> StdResult<Response> {
-    let data = msg.result.unwrap().data.unwrap();
+    if msg.id != INSTANTIATE_REPLY_ID {
+        return Err(StdError::generic_err(format!(
+            "unknown reply id: {}",
+            msg.id
+        )));
+    }
 
-    let res = MsgInstantiateContractResponse::try_from(data.as_slice()).map_err(|_| {
-        StdError::parse_err("MsgInstantiateContractResponse", "failed to parse data")
-    })?;
-    let liquidity_token = &res.address;
+    let res =
+        parse_reply_instantiate_data(msg).map_err(|err| StdError::generic_err(err.to_string()))?;
+    let liquidity_token = &res.contract_address;
 
     let api = deps.api;
     PAIR_INFO.update(deps.storage, |mut meta| -> StdResult<_> {
+        // liquidity token can only be set once, by the instantiate submessage of this pair
+        if !meta.liquidity_token.is_empty() {
+            return Err(StdError::generic_err("liquidity token is already set"));
+        }
         meta.liquidity_token = api.addr_canonicalize(liquidity_token)?;
         Ok(meta)
     })?;
@@ -208,6 +511,7 @@ pub fn provide_liquidity(
     assets: [Asset; 2],
     slippage_tolerance: Option<Decimal>,
     receiver: Option<Addr>,
+    auto_stake: bool,
 ) -> Result<Response, ContractError> {
     for asset in assets.iter() {
         asset.assert_sent_native_token_balance(&info)?;
@@ -253,8 +557,7 @@ pub fn provide_liquidity(
     // assert slippage tolerance
     assert_slippage_tolerance(&slippage_tolerance, &deposits, &pools)?;
 
-    let liquidity_token = deps.api.addr_humanize(&pair_info.liquidity_token)?;
-    let total_share = query_supply(&deps.querier, liquidity_token)?;
+    let total_share = query_total_share(deps.as_ref(), &pair_info)?;
     let share = if total_share == Uint128::zero() {
         // Initial share = collateral amount
         Uint128::from((deposits[0].u128() * deposits[1].u128()).integer_sqrt())
@@ -275,19 +578,63 @@ pub fn provide_liquidity(
         return Err(ContractError::InvalidZeroAmount {});
     }
 
-    // mint LP token to sender
     let receiver = receiver.unwrap_or(info.sender.clone());
-    messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: deps
-            .api
-            .addr_humanize(&pair_info.liquidity_token)?
-            .to_string(),
-        msg: to_binary(&Cw20ExecuteMsg::Mint {
-            recipient: receiver.to_string(),
-            amount: share,
-        })?,
-        funds: vec![],
-    }));
+
+    // track the pool price after providing, so the position value can be compared with it later
+    LAST_PROVIDE_PRICES.save(
+        deps.storage,
+        deps.api.addr_canonicalize(receiver.as_str())?.as_slice(),
+        &Decimal256::from_ratio(pools[1].amount + deposits[1], pools[0].amount + deposits[0]),
+    )?;
+
+    if auto_stake {
+        // the staking contract bonds cw20 LP tokens only
+        if pair_info.lp_denom.is_some() {
+            return Err(ContractError::Std(StdError::generic_err(
+                "Auto staking is not supported by tokenfactory LP shares",
+            )));
+        }
+        let liquidity_token = deps.api.addr_humanize(&pair_info.liquidity_token)?;
+        let staking_contract = match STAKING_CONTRACT.may_load(deps.storage)? {
+            Some(staking_contract) => deps.api.addr_humanize(&staking_contract)?,
+            None => {
+                return Err(ContractError::Std(StdError::generic_err(
+                    "Auto staking is not supported by this pair",
+                )))
+            }
+        };
+
+        // mint LP token to the pair, then bond it to the staking contract for the receiver
+        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: liquidity_token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Mint {
+                recipient: env.contract.address.to_string(),
+                amount: share,
+            })?,
+            funds: vec![],
+        }));
+        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: liquidity_token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: staking_contract.to_string(),
+                amount: share,
+                msg: to_binary(&StakingCw20HookMsg::BondFor {
+                    asset_info: staking_asset_info(&pools),
+                    staker_addr: receiver.clone(),
+                })?,
+            })?,
+            funds: vec![],
+        }));
+    } else {
+        // mint LP token to sender
+        messages.extend(mint_share_msgs(
+            deps.as_ref(),
+            &env,
+            &pair_info,
+            &receiver,
+            share,
+        )?);
+    }
 
     Ok(Response::new().add_messages(messages).add_attributes(vec![
         ("action", "provide_liquidity"),
@@ -295,9 +642,136 @@ pub fn provide_liquidity(
         ("receiver", receiver.as_str()),
         ("assets", &format!("{}, {}", assets[0], assets[1])),
         ("share", &share.to_string()),
+        ("auto_stake", &auto_stake.to_string()),
+    ]))
+}
+
+/// add assets to the reserves without minting LP tokens, native assets are already in the balance
+pub fn donate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    assets: [Asset; 2],
+) -> Result<Response, ContractError> {
+    for asset in assets.iter() {
+        asset.assert_sent_native_token_balance(&info)?;
+    }
+
+    let pair_info: PairInfoRaw = PAIR_INFO.load(deps.storage)?;
+    let pools: [Asset; 2] =
+        pair_info.query_pools(&deps.querier, deps.api, env.contract.address.clone())?;
+    for asset in assets.iter() {
+        if !pools.iter().any(|pool| pool.info.eq(&asset.info)) {
+            return Err(ContractError::AssetMismatch {});
+        }
+    }
+
+    if assets.iter().all(|asset| asset.amount.is_zero()) {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+
+    // nobody owns an empty pool, the donation would go to the first provider
+    if query_total_share(deps.as_ref(), &pair_info)?.is_zero() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Cannot donate to an empty pool",
+        )));
+    }
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    for asset in assets.iter() {
+        if let AssetInfo::Token { contract_addr, .. } = &asset.info {
+            if !asset.amount.is_zero() {
+                messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: contract_addr.to_string(),
+                    msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+                        owner: info.sender.to_string(),
+                        recipient: env.contract.address.to_string(),
+                        amount: asset.amount,
+                    })?,
+                    funds: vec![],
+                }));
+            }
+        }
+    }
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "donate"),
+        ("sender", info.sender.as_str()),
+        ("assets", &format!("{}, {}", assets[0], assets[1])),
     ]))
 }
 
+/// staking pools of pairs are registered under the token asset, or the non orai asset of native pairs
+fn staking_asset_info(pools: &[Asset; 2]) -> AssetInfo {
+    match (&pools[0].info, &pools[1].info) {
+        (AssetInfo::Token { .. }, _) => pools[0].info.clone(),
+        (_, AssetInfo::Token { .. }) => pools[1].info.clone(),
+        (AssetInfo::NativeToken { denom }, _) if denom.ne(ORAI_DENOM) => pools[0].info.clone(),
+        _ => pools[1].info.clone(),
+    }
+}
+
+/// total supply of the LP shares, a tokenfactory denom or the cw20 liquidity token
+fn query_total_share(deps: Deps, pair_info: &PairInfoRaw) -> StdResult<Uint128> {
+    match &pair_info.lp_denom {
+        Some(lp_denom) => Ok(deps.querier.query_supply(lp_denom)?.amount),
+        None => query_supply(
+            &deps.querier,
+            deps.api.addr_humanize(&pair_info.liquidity_token)?,
+        ),
+    }
+}
+
+/// tokenfactory shares are minted to the pair then sent, as the admin can only mint to itself
+fn mint_share_msgs(
+    deps: Deps,
+    env: &Env,
+    pair_info: &PairInfoRaw,
+    recipient: &Addr,
+    amount: Uint128,
+) -> StdResult<Vec<CosmosMsg>> {
+    match &pair_info.lp_denom {
+        Some(lp_denom) => Ok(vec![
+            mint_msg(&env.contract.address, lp_denom, amount)?,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: recipient.to_string(),
+                amount: coins(amount.u128(), lp_denom),
+            }),
+        ]),
+        None => Ok(vec![CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: deps
+                .api
+                .addr_humanize(&pair_info.liquidity_token)?
+                .to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Mint {
+                recipient: recipient.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        })]),
+    }
+}
+
+/// burn the LP shares received by the pair
+fn burn_share_msg(
+    deps: Deps,
+    env: &Env,
+    pair_info: &PairInfoRaw,
+    amount: Uint128,
+) -> StdResult<CosmosMsg> {
+    match &pair_info.lp_denom {
+        Some(lp_denom) => burn_msg(&env.contract.address, lp_denom, amount),
+        None => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: deps
+                .api
+                .addr_humanize(&pair_info.liquidity_token)?
+                .to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Burn { amount })?,
+            funds: vec![],
+        })),
+    }
+}
+
 pub fn withdraw_liquidity(
     deps: DepsMut,
     env: Env,
@@ -306,11 +780,10 @@ pub fn withdraw_liquidity(
     amount: Uint128,
 ) -> Result<Response, ContractError> {
     let pair_info: PairInfoRaw = PAIR_INFO.load(deps.storage)?;
-    let liquidity_addr = deps.api.addr_humanize(&pair_info.liquidity_token)?;
 
     let pools: [Asset; 2] =
         pair_info.query_pools(&deps.querier, deps.api, env.contract.address.clone())?;
-    let total_share: Uint128 = query_supply(&deps.querier, liquidity_addr)?;
+    let total_share: Uint128 = query_total_share(deps.as_ref(), &pair_info)?;
 
     let share_ratio = Decimal::from_ratio(amount, total_share);
     if share_ratio.is_zero() {
@@ -335,15 +808,7 @@ pub fn withdraw_liquidity(
             .clone()
             .into_msg(Some(&oracle_contract), &deps.querier, sender.clone())?,
         // burn liquidity token
-        WasmMsg::Execute {
-            contract_addr: deps
-                .api
-                .addr_humanize(&pair_info.liquidity_token)?
-                .to_string(),
-            msg: to_binary(&Cw20ExecuteMsg::Burn { amount })?,
-            funds: vec![],
-        }
-        .into(),
+        burn_share_msg(deps.as_ref(), &env, &pair_info, amount)?,
     ];
 
     // update pool info
@@ -370,6 +835,7 @@ pub fn swap(
     belief_price: Option<Decimal>,
     max_spread: Option<Decimal>,
     to: Option<Addr>,
+    return_asset_info: Option<AssetInfo>,
 ) -> Result<Response, ContractError> {
     offer_asset.assert_sent_native_token_balance(&info)?;
 
@@ -378,35 +844,83 @@ pub fn swap(
     let pools: [Asset; 2] =
         pair_info.query_pools(&deps.querier, deps.api, env.contract.address.clone())?;
 
+    let mut messages: Vec<CosmosMsg> = vec![];
+
+    // a legacy offer asset is converted into its pool asset first, so the pool balance
+    // does not include the converted amount yet
+    let (offer_asset, converted_from) = if pools.iter().any(|pool| pool.info.eq(&offer_asset.info))
+    {
+        (offer_asset, None)
+    } else {
+        let (converter_addr, token_ratio) =
+            query_legacy_conversion(deps.as_ref(), &offer_asset.info, &pools)?;
+        let converted_amount = offer_asset.amount * token_ratio.ratio;
+        let limit: ConvertLimitResponse = deps.querier.query_wasm_smart(
+            converter_addr.clone(),
+            &ConverterQueryMsg::ConvertLimit {
+                asset_info: offer_asset.info.clone(),
+            },
+        )?;
+        let converted_amount = converted_amount.checked_sub(
+            limit
+                .limit
+                .map(|limit| limit.compute_fee(converted_amount))
+                .unwrap_or_default(),
+        )?;
+
+        messages.push(asset_into_convert_msg(
+            converter_addr,
+            offer_asset.clone(),
+            None,
+        )?);
+        (
+            Asset {
+                info: token_ratio.info,
+                amount: converted_amount,
+            },
+            Some(offer_asset.info),
+        )
+    };
+
     let offer_pool: Asset;
     let ask_pool: Asset;
 
     // If the asset balance is already increased
     // To calculated properly we should subtract user deposit from the pool
+    let deposited = if converted_from.is_none() {
+        offer_asset.amount
+    } else {
+        Uint128::zero()
+    };
     if offer_asset.info.eq(&pools[0].info) {
         offer_pool = Asset {
-            amount: pools[0].amount.checked_sub(offer_asset.amount)?,
+            amount: pools[0].amount.checked_sub(deposited)?,
             info: pools[0].info.clone(),
         };
         ask_pool = pools[1].clone();
-    } else if offer_asset.info.eq(&pools[1].info) {
+    } else {
         offer_pool = Asset {
-            amount: pools[1].amount.checked_sub(offer_asset.amount)?,
+            amount: pools[1].amount.checked_sub(deposited)?,
             info: pools[1].info.clone(),
         };
         ask_pool = pools[0].clone();
-    } else {
-        return Err(ContractError::AssetMismatch {});
     }
 
-    let commission_rate = Decimal256::from_str(&pair_info.commission_rate)?;
+    let commission_rate = swap_commission_rate(deps.as_ref(), &pair_info, &sender)?;
     let offer_amount = offer_asset.amount;
+    assert_max_trade(deps.as_ref(), offer_pool.amount, offer_amount)?;
     let (return_amount, spread_amount, commission_amount) = compute_swap(
         offer_pool.amount,
         ask_pool.amount,
         offer_amount,
         commission_rate,
     )?;
+    assert_invariant(
+        offer_pool.amount,
+        ask_pool.amount,
+        offer_amount,
+        return_amount,
+    )?;
 
     // check max spread limit if exist
     assert_max_spread(
@@ -425,30 +939,222 @@ pub fn swap(
 
     let oracle_contract = OracleContract(deps.api.addr_humanize(&pair_info.oracle_addr)?);
 
-    let tax_amount = return_asset.compute_tax(&oracle_contract, &deps.querier)?;
+    // the tax is only deducted when the return asset is sent to the receiver, not to the converter
+    let converted_return = matches!(&return_asset_info, Some(info) if !info.eq(&ask_pool.info));
+    let tax_amount = if converted_return {
+        Uint128::zero()
+    } else {
+        return_asset.compute_tax(&oracle_contract, &deps.querier)?
+    };
+    let net_return_amount = return_amount.checked_sub(tax_amount)?;
     let receiver = to.unwrap_or_else(|| sender.clone());
 
+    let mut attributes = vec![
+        ("action", "swap".to_string()),
+        ("sender", sender.to_string()),
+        ("receiver", receiver.to_string()),
+        ("offer_asset", offer_asset.info.to_string()),
+        ("ask_asset", ask_pool.info.to_string()),
+        ("offer_amount", offer_amount.to_string()),
+        ("return_amount", return_amount.to_string()),
+        ("tax_amount", tax_amount.to_string()),
+        ("net_return_amount", net_return_amount.to_string()),
+        ("spread_amount", spread_amount.to_string()),
+        ("commission_amount", commission_amount.to_string()),
+    ];
+    if let Some(converted_from) = converted_from {
+        attributes.push(("converted_from", converted_from.to_string()));
+    }
+
     // update oracle_contract
-    let mut messages: Vec<CosmosMsg> = vec![];
-    if !return_amount.is_zero() {
-        messages.push(return_asset.into_msg(
+    match return_asset_info {
+        // the return asset goes through the converter, which sends the legacy asset back to the pair
+        Some(return_asset_info) if !return_asset_info.eq(&ask_pool.info) => {
+            let (converter_addr, token_ratio) =
+                query_legacy_conversion(deps.as_ref(), &return_asset_info, &pools)?;
+            if !token_ratio.info.eq(&ask_pool.info) {
+                return Err(ContractError::AssetMismatch {});
+            }
+            let legacy_asset = Asset {
+                info: return_asset_info,
+                amount: return_amount.checked_div_decimal(token_ratio.ratio)?,
+            };
+
+            messages.push(asset_into_convert_msg(
+                converter_addr,
+                return_asset,
+                Some(legacy_asset.info.clone()),
+            )?);
+            if !legacy_asset.amount.is_zero() {
+                messages.push(legacy_asset.into_msg(None, &deps.querier, receiver)?);
+            }
+            attributes.push(("converted_to", legacy_asset.info.to_string()));
+            attributes.push(("converted_amount", legacy_asset.amount.to_string()));
+        }
+        _ => {
+            if !return_amount.is_zero() {
+                messages.push(return_asset.into_msg(
+                    Some(&oracle_contract),
+                    &deps.querier,
+                    receiver,
+                )?);
+            }
+        }
+    }
+
+    // 1. send collateral token from the contract to a user
+    // 2. send inactive commission to collector
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attributes(attributes))
+}
+
+/// Swaps sent by the router are charged the router commission rate, other swaps the pair commission rate
+fn swap_commission_rate(
+    deps: Deps,
+    pair_info: &PairInfoRaw,
+    sender: &Addr,
+) -> StdResult<Decimal256> {
+    let commission_rate = Decimal256::from_str(&pair_info.commission_rate)?;
+    match ROUTER.may_load(deps.storage)? {
+        Some(router) if router == deps.api.addr_canonicalize(sender.as_str())? => {
+            Ok(ROUTER_COMMISSION_RATE
+                .may_load(deps.storage)?
+                .unwrap_or(commission_rate))
+        }
+        _ => Ok(commission_rate),
+    }
+}
+
+/// Find the pool asset the converter converts a legacy asset into, together with the converter
+fn query_legacy_conversion(
+    deps: Deps,
+    legacy_asset_info: &AssetInfo,
+    pools: &[Asset; 2],
+) -> Result<(Addr, TokenRatio), ContractError> {
+    let converter_addr = match CONVERTER.may_load(deps.storage)? {
+        Some(converter) => deps.api.addr_humanize(&converter)?,
+        None => return Err(ContractError::AssetMismatch {}),
+    };
+
+    let res: ConvertInfoResponse = deps
+        .querier
+        .query_wasm_smart(
+            converter_addr.clone(),
+            &ConverterQueryMsg::ConvertInfo {
+                asset_info: legacy_asset_info.clone(),
+            },
+        )
+        .map_err(|_| ContractError::AssetMismatch {})?;
+    if !pools.iter().any(|pool| pool.info.eq(&res.token_ratio.info)) {
+        return Err(ContractError::AssetMismatch {});
+    }
+
+    Ok((converter_addr, res.token_ratio))
+}
+
+/// Swap the sent asset for an exact ask amount, the offer amount is computed by
+/// reverse simulation and the unused part of the sent asset is refunded to the sender
+pub fn swap_exact_out(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    sent_asset: Asset,
+    ask_asset: Asset,
+    max_offer_amount: Uint128,
+    to: Option<Addr>,
+) -> Result<Response, ContractError> {
+    let pair_info: PairInfoRaw = PAIR_INFO.load(deps.storage)?;
+
+    let pools: [Asset; 2] =
+        pair_info.query_pools(&deps.querier, deps.api, env.contract.address.clone())?;
+
+    let offer_pool: Asset;
+    let ask_pool: Asset;
+
+    // the sent asset is already in the contract balance, subtract it from the pool
+    if sent_asset.info.eq(&pools[0].info) && ask_asset.info.eq(&pools[1].info) {
+        offer_pool = Asset {
+            amount: pools[0].amount.checked_sub(sent_asset.amount)?,
+            info: pools[0].info.clone(),
+        };
+        ask_pool = pools[1].clone();
+    } else if sent_asset.info.eq(&pools[1].info) && ask_asset.info.eq(&pools[0].info) {
+        offer_pool = Asset {
+            amount: pools[1].amount.checked_sub(sent_asset.amount)?,
+            info: pools[1].info.clone(),
+        };
+        ask_pool = pools[0].clone();
+    } else {
+        return Err(ContractError::AssetMismatch {});
+    }
+
+    let commission_rate = swap_commission_rate(deps.as_ref(), &pair_info, &sender)?;
+    let (offer_amount, spread_amount, commission_amount) = compute_offer_amount(
+        offer_pool.amount,
+        ask_pool.amount,
+        ask_asset.amount,
+        commission_rate,
+    )?;
+
+    if offer_amount > max_offer_amount {
+        return Err(ContractError::MaxOfferAssertion {
+            offer_amount,
+            max_offer_amount,
+        });
+    }
+
+    assert_max_trade(deps.as_ref(), offer_pool.amount, offer_amount)?;
+    assert_invariant(
+        offer_pool.amount,
+        ask_pool.amount,
+        offer_amount,
+        ask_asset.amount,
+    )?;
+
+    if offer_amount > sent_asset.amount {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "Insufficient offer amount, required {} but sent {}",
+            offer_amount, sent_asset.amount
+        ))));
+    }
+
+    let oracle_contract = OracleContract(deps.api.addr_humanize(&pair_info.oracle_addr)?);
+
+    let tax_amount = ask_asset.compute_tax(&oracle_contract, &deps.querier)?;
+    let net_return_amount = ask_asset.amount.checked_sub(tax_amount)?;
+    let receiver = to.unwrap_or_else(|| sender.clone());
+    let refund_amount = sent_asset.amount - offer_amount;
+    let refund_asset = Asset {
+        info: sent_asset.info.clone(),
+        amount: refund_amount,
+    };
+    let refund_tax_amount = refund_asset.compute_tax(&oracle_contract, &deps.querier)?;
+
+    let mut messages: Vec<CosmosMsg> =
+        vec![ask_asset.into_msg(Some(&oracle_contract), &deps.querier, receiver.clone())?];
+
+    // refund the unused offer
+    if !refund_amount.is_zero() {
+        messages.push(refund_asset.into_msg(
             Some(&oracle_contract),
             &deps.querier,
-            receiver.clone(),
+            sender.clone(),
         )?);
     }
 
-    // 1. send collateral token from the contract to a user
-    // 2. send inactive commission to collector
     Ok(Response::new().add_messages(messages).add_attributes(vec![
-        ("action", "swap"),
+        ("action", "swap_exact_out"),
         ("sender", sender.as_str()),
         ("receiver", receiver.as_str()),
-        ("offer_asset", &offer_asset.info.to_string()),
+        ("offer_asset", &sent_asset.info.to_string()),
         ("ask_asset", &ask_pool.info.to_string()),
         ("offer_amount", &offer_amount.to_string()),
-        ("return_amount", &return_amount.to_string()),
+        ("return_amount", &ask_asset.amount.to_string()),
+        ("refund_amount", &refund_amount.to_string()),
         ("tax_amount", &tax_amount.to_string()),
+        ("net_return_amount", &net_return_amount.to_string()),
+        ("refund_tax_amount", &refund_tax_amount.to_string()),
         ("spread_amount", &spread_amount.to_string()),
         ("commission_amount", &commission_amount.to_string()),
     ]))
@@ -465,9 +1171,44 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> Result<Binary, ContractErr
         QueryMsg::ReverseSimulation { ask_asset } => {
             Ok(to_binary(&query_reverse_simulation(deps, ask_asset)?)?)
         }
+        QueryMsg::SpotPrice {
+            offer_asset_info,
+            ask_asset_info,
+        } => Ok(to_binary(&query_spot_price(
+            deps,
+            offer_asset_info,
+            ask_asset_info,
+        )?)?),
+        QueryMsg::MaxTradeBps {} => Ok(to_binary(&MaxTradeBpsResponse {
+            max_trade_bps: MAX_TRADE_BPS.may_load(deps.storage)?,
+        })?),
+        QueryMsg::LpValue { amount, address } => {
+            Ok(to_binary(&query_lp_value(deps, amount, address)?)?)
+        }
+        QueryMsg::RouterCommission {} => Ok(to_binary(&query_router_commission(deps)?)?),
+        QueryMsg::NetReceiveEstimate { offer_asset } => {
+            Ok(to_binary(&query_net_receive_estimate(deps, offer_asset)?)?)
+        }
     }
 }
 
+pub fn query_router_commission(deps: Deps) -> StdResult<RouterCommissionResponse> {
+    let pair_info: PairInfoRaw = PAIR_INFO.load(deps.storage)?;
+    let router = match ROUTER.may_load(deps.storage)? {
+        Some(router) => Some(deps.api.addr_humanize(&router)?),
+        None => None,
+    };
+    let commission_rate = match &router {
+        Some(router) => swap_commission_rate(deps, &pair_info, router)?.to_string(),
+        None => pair_info.commission_rate,
+    };
+
+    Ok(RouterCommissionResponse {
+        router,
+        commission_rate,
+    })
+}
+
 pub fn query_pair_info(deps: Deps) -> StdResult<PairResponse> {
     let pair_info: PairInfoRaw = PAIR_INFO.load(deps.storage)?;
     pair_info
@@ -479,10 +1220,7 @@ pub fn query_pool(deps: Deps) -> Result<PoolResponse, ContractError> {
     let pair_info: PairInfoRaw = PAIR_INFO.load(deps.storage)?;
     let contract_addr = deps.api.addr_humanize(&pair_info.contract_addr)?;
     let assets: [Asset; 2] = pair_info.query_pools(&deps.querier, deps.api, contract_addr)?;
-    let total_share: Uint128 = query_supply(
-        &deps.querier,
-        deps.api.addr_humanize(&pair_info.liquidity_token)?,
-    )?;
+    let total_share: Uint128 = query_total_share(deps, &pair_info)?;
 
     let resp = PoolResponse {
         assets,
@@ -492,6 +1230,47 @@ pub fn query_pool(deps: Deps) -> Result<PoolResponse, ContractError> {
     Ok(resp)
 }
 
+pub fn query_lp_value(
+    deps: Deps,
+    amount: Uint128,
+    address: Option<String>,
+) -> Result<LpValueResponse, ContractError> {
+    let PoolResponse {
+        assets,
+        total_share,
+    } = query_pool(deps)?;
+
+    // same share of the pools as withdrawing the amount
+    let share_ratio = if total_share.is_zero() {
+        Decimal::zero()
+    } else {
+        Decimal::from_ratio(amount, total_share)
+    };
+
+    let price = if assets[0].amount.is_zero() {
+        Decimal256::zero()
+    } else {
+        Decimal256::from_ratio(assets[1].amount, assets[0].amount)
+    };
+
+    let last_provide_price = match address {
+        Some(address) => LAST_PROVIDE_PRICES.may_load(
+            deps.storage,
+            deps.api.addr_canonicalize(&address)?.as_slice(),
+        )?,
+        None => None,
+    };
+
+    Ok(LpValueResponse {
+        assets: assets.map(|asset| Asset {
+            info: asset.info,
+            amount: asset.amount * share_ratio,
+        }),
+        price,
+        last_provide_price,
+    })
+}
+
 pub fn query_simulation(
     deps: Deps,
     offer_asset: Asset,
@@ -528,6 +1307,36 @@ pub fn query_simulation(
     })
 }
 
+pub fn query_net_receive_estimate(
+    deps: Deps,
+    offer_asset: Asset,
+) -> Result<NetReceiveEstimateResponse, ContractError> {
+    let pair_info: PairInfoRaw = PAIR_INFO.load(deps.storage)?;
+    let ask_info = if offer_asset
+        .info
+        .eq(&pair_info.asset_infos[0].to_normal(deps.api)?)
+    {
+        pair_info.asset_infos[1].to_normal(deps.api)?
+    } else {
+        pair_info.asset_infos[0].to_normal(deps.api)?
+    };
+
+    let simulation = query_simulation(deps, offer_asset)?;
+    let oracle_contract = OracleContract(deps.api.addr_humanize(&pair_info.oracle_addr)?);
+    // compute_tax applies the tax cap of the denom, like the transfer of the swap
+    let tax_amount = Asset {
+        info: ask_info,
+        amount: simulation.return_amount,
+    }
+    .compute_tax(&oracle_contract, &deps.querier)?;
+
+    Ok(NetReceiveEstimateResponse {
+        return_amount: simulation.return_amount,
+        tax_amount,
+        net_return_amount: simulation.return_amount.checked_sub(tax_amount)?,
+    })
+}
+
 pub fn query_reverse_simulation(
     deps: Deps,
     ask_asset: Asset,
@@ -564,6 +1373,49 @@ pub fn query_reverse_simulation(
     })
 }
 
+/// price of one offer asset in ask asset, both reserves are scaled down by their decimals
+pub fn query_spot_price(
+    deps: Deps,
+    offer_asset_info: AssetInfo,
+    ask_asset_info: AssetInfo,
+) -> Result<SpotPriceResponse, ContractError> {
+    let pair_info: PairInfoRaw = PAIR_INFO.load(deps.storage)?;
+
+    let contract_addr = deps.api.addr_humanize(&pair_info.contract_addr)?;
+    let pools: [Asset; 2] = pair_info.query_pools(&deps.querier, deps.api, contract_addr)?;
+
+    let offer_pool: Asset;
+    let ask_pool: Asset;
+    if offer_asset_info.eq(&pools[0].info) && ask_asset_info.eq(&pools[1].info) {
+        offer_pool = pools[0].clone();
+        ask_pool = pools[1].clone();
+    } else if offer_asset_info.eq(&pools[1].info) && ask_asset_info.eq(&pools[0].info) {
+        offer_pool = pools[1].clone();
+        ask_pool = pools[0].clone();
+    } else {
+        return Err(ContractError::AssetMismatch {});
+    }
+
+    if offer_pool.amount.is_zero() {
+        return Err(ContractError::Std(StdError::generic_err("Pool is empty")));
+    }
+
+    let registry = match REGISTRY.may_load(deps.storage)? {
+        Some(registry) => Some(deps.api.addr_humanize(&registry)?),
+        None => None,
+    };
+    let offer_decimals = query_asset_decimals(&deps.querier, registry.clone(), &offer_pool.info)?;
+    let ask_decimals = query_asset_decimals(&deps.querier, registry, &ask_pool.info)?;
+
+    // price = (ask_pool / 10^ask_decimals) / (offer_pool / 10^offer_decimals)
+    let price = Decimal256::from_ratio(
+        Uint256::from(ask_pool.amount) * Uint256::from(10u128.pow(offer_decimals.into())),
+        Uint256::from(offer_pool.amount) * Uint256::from(10u128.pow(ask_decimals.into())),
+    );
+
+    Ok(SpotPriceResponse { price })
+}
+
 pub fn amount_of(coins: &[Coin], denom: String) -> Uint128 {
     match coins.iter().find(|x| x.denom == denom) {
         Some(coin) => coin.amount,
@@ -571,47 +1423,6 @@ pub fn amount_of(coins: &[Coin], denom: String) -> Uint128 {
     }
 }
 
-/// If `belief_price` and `max_spread` both are given,
-/// we compute new spread else we just use oraiswap
-/// spread to check `max_spread`
-pub fn assert_max_spread(
-    belief_price: Option<Decimal>,
-    max_spread: Option<Decimal>,
-    offer_amount: Uint128,
-    return_amount: Uint128,
-    spread_amount: Uint128,
-) -> Result<(), ContractError> {
-    let offer_amount: Uint256 = offer_amount.into();
-    let return_amount: Uint256 = return_amount.into();
-    let spread_amount: Uint256 = spread_amount.into();
-
-    if let (Some(max_spread), Some(belief_price)) = (max_spread, belief_price) {
-        let belief_price: Decimal256 = belief_price.into();
-        let max_spread: Decimal256 = max_spread.into();
-        // mul with belief_price inv
-        let expected_return = offer_amount * (Decimal256::one() / belief_price);
-
-        let spread_amount = if expected_return > return_amount {
-            expected_return - return_amount
-        } else {
-            Uint256::zero()
-        };
-
-        if return_amount < expected_return
-            && Decimal256::from_ratio(spread_amount, expected_return) > max_spread
-        {
-            return Err(ContractError::MaxSpreadAssertion {});
-        }
-    } else if let Some(max_spread) = max_spread {
-        let max_spread: Decimal256 = max_spread.into();
-        if Decimal256::from_ratio(spread_amount, return_amount + spread_amount) > max_spread {
-            return Err(ContractError::MaxSpreadAssertion {});
-        }
-    }
-
-    Ok(())
-}
-
 fn assert_slippage_tolerance(
     slippage_tolerance: &Option<Decimal>,
     deposits: &[Uint128; 2],
@@ -641,6 +1452,53 @@ fn assert_slippage_tolerance(
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    if let Some(guardian) = msg.guardian {
+        GUARDIAN.save(
+            deps.storage,
+            &deps.api.addr_canonicalize(guardian.as_str())?,
+        )?;
+    }
+
+    if let Some(staking_contract) = msg.staking_contract {
+        STAKING_CONTRACT.save(
+            deps.storage,
+            &deps.api.addr_canonicalize(staking_contract.as_str())?,
+        )?;
+    }
+
+    if let Some(converter) = msg.converter {
+        CONVERTER.save(
+            deps.storage,
+            &deps.api.addr_canonicalize(converter.as_str())?,
+        )?;
+    }
+
+    if let Some(registry) = msg.registry {
+        REGISTRY.save(
+            deps.storage,
+            &deps.api.addr_canonicalize(registry.as_str())?,
+        )?;
+    }
+
+    if let Some(router) = msg.router {
+        ROUTER.save(deps.storage, &deps.api.addr_canonicalize(router.as_str())?)?;
+    }
+
+    if let Some(router_commission_rate) = msg.router_commission_rate {
+        let pair_info: PairInfoRaw = PAIR_INFO.load(deps.storage)?;
+        let router_commission_rate = Decimal256::from_str(&router_commission_rate)?;
+        if router_commission_rate > Decimal256::from_str(&pair_info.commission_rate)? {
+            return Err(ContractError::Std(StdError::generic_err(
+                "router commission rate must not exceed the commission rate",
+            )));
+        }
+        ROUTER_COMMISSION_RATE.save(deps.storage, &router_commission_rate)?;
+    }
+
+    if msg.max_trade_bps.is_some() {
+        save_max_trade_bps(deps, msg.max_trade_bps)?;
+    }
+
     Ok(Response::default())
 }
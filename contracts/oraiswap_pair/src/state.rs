@@ -1,9 +1,33 @@
-use cw_storage_plus::Item;
+use cosmwasm_std::{CanonicalAddr, Decimal256};
+use cw_storage_plus::{Item, Map};
 use oraiswap::asset::PairInfoRaw;
 
 // put the length bytes at the first for compatibility with legacy singleton store
 pub const PAIR_INFO: Item<PairInfoRaw> = Item::new("\u{0}\u{9}pair_info");
 
+// guardian can pause swaps and providing liquidity
+pub const GUARDIAN: Item<CanonicalAddr> = Item::new("guardian");
+pub const PAUSED: Item<bool> = Item::new("paused");
+
+// staking contract that bonds LP tokens of auto staked liquidity
+pub const STAKING_CONTRACT: Item<CanonicalAddr> = Item::new("staking_contract");
+
+// converter that converts legacy assets offered or asked in swaps from and into the pool assets
+pub const CONVERTER: Item<CanonicalAddr> = Item::new("converter");
+
+// registry providing the decimals of the pool assets for the spot price
+pub const REGISTRY: Item<CanonicalAddr> = Item::new("registry");
+
+// router whose swaps are charged the discounted commission rate
+pub const ROUTER: Item<CanonicalAddr> = Item::new("router");
+pub const ROUTER_COMMISSION_RATE: Item<Decimal256> = Item::new("router_commission_rate");
+
+// max offer amount of a swap in basis points of the offer pool
+pub const MAX_TRADE_BPS: Item<u16> = Item::new("max_trade_bps");
+
+// pool price, asset 1 per asset 0, after the last provide of each receiver
+pub const LAST_PROVIDE_PRICES: Map<&[u8], Decimal256> = Map::new("last_provide_prices");
+
 #[cfg(test)]
 mod test {
 
@@ -41,6 +65,7 @@ mod test {
                 contract_addr: deps.api.addr_canonicalize("pair0000").unwrap(),
                 liquidity_token: deps.api.addr_canonicalize("liquidity0000").unwrap(),
                 commission_rate: DEFAULT_COMMISSION_RATE.to_string(),
+                lp_denom: None,
             },
         )
         .unwrap();
@@ -1,10 +1,34 @@
-use cosmwasm_std::testing::MOCK_CONTRACT_ADDR;
-use cosmwasm_std::{attr, to_binary, Addr, Coin, Decimal, Uint128};
-use cw20::Cw20ReceiveMsg;
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_dependencies_with_balance, mock_env, mock_info, MOCK_CONTRACT_ADDR,
+};
+use cosmwasm_std::{
+    attr, coins, from_binary, to_binary, Addr, Api, BankMsg, Binary, Coin, ContractResult, Decimal,
+    Decimal256, Reply, StdError, SubMsg, SubMsgResponse, SubMsgResult, SystemResult, Uint128,
+    WasmMsg, WasmQuery,
+};
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg, TokenInfoResponse};
 use oraiswap::asset::{Asset, AssetInfo, ORAI_DENOM};
+use oraiswap::converter::{
+    ConvertInfoResponse, ConvertLimit, ConvertLimitResponse, ExecuteMsg as ConverterExecuteMsg,
+    QueryMsg as ConverterQueryMsg, TokenRatio,
+};
 use oraiswap::create_entry_points_testing;
-use oraiswap::pair::{Cw20HookMsg, ExecuteMsg, InstantiateMsg, PairResponse};
+use oraiswap::error::ContractError;
+use oraiswap::math::Converter128;
+use oraiswap::oracle::TaxInfo;
+use oraiswap::pair::{
+    assert_invariant, compute_swap, Cw20HookMsg, ExecuteMsg, InstantiateMsg, LpTokenInfo,
+    LpTokenType, LpValueResponse, MaxTradeBpsResponse, MigrateMsg, NetReceiveEstimateResponse,
+    PairResponse, QueryMsg, RouterCommissionResponse, SimulationResponse, SpotPriceResponse,
+    DEFAULT_COMMISSION_RATE, LP_SUBDENOM,
+};
+use oraiswap::staking::Cw20HookMsg as StakingCw20HookMsg;
 use oraiswap::testing::{MockApp, ATOM_DENOM};
+use oraiswap::tokenfactory::{burn_msg, create_denom_msg, mint_msg};
+
+use crate::contract::{execute, instantiate, migrate, query, reply};
+use crate::state::{PAIR_INFO, PAUSED};
+use std::str::FromStr;
 
 #[test]
 fn provide_liquidity_both_native() {
@@ -46,6 +70,10 @@ fn provide_liquidity_both_native() {
         ],
         token_code_id: app.token_id,
         commission_rate: None,
+        lp_token_info: None,
+        guardian: None,
+        staking_contract: None,
+        lp_token_type: None,
     };
 
     // we can just call .unwrap() to assert this was a success
@@ -75,6 +103,7 @@ fn provide_liquidity_both_native() {
         ],
         slippage_tolerance: None,
         receiver: None,
+        auto_stake: None,
     };
 
     let res = app
@@ -138,6 +167,10 @@ fn provide_liquidity() {
         ],
         token_code_id: app.token_id,
         commission_rate: None,
+        lp_token_info: None,
+        guardian: None,
+        staking_contract: None,
+        lp_token_type: None,
     };
 
     // we can just call .unwrap() to assert this was a success
@@ -179,6 +212,7 @@ fn provide_liquidity() {
         ],
         slippage_tolerance: None,
         receiver: None,
+        auto_stake: None,
     };
 
     let _res = app
@@ -223,6 +257,7 @@ fn provide_liquidity() {
         ],
         slippage_tolerance: None,
         receiver: Some(Addr::unchecked("staking0000")), // try changing receiver
+        auto_stake: None,
     };
 
     // only accept 100, then 50 share will be generated with 100 * (100 / 200)
@@ -256,6 +291,7 @@ fn provide_liquidity() {
         ],
         slippage_tolerance: None,
         receiver: None,
+        auto_stake: None,
     };
 
     let res = app.execute(
@@ -309,6 +345,10 @@ fn withdraw_liquidity() {
         ],
         token_code_id: app.token_id,
         commission_rate: None,
+        lp_token_info: None,
+        guardian: None,
+        staking_contract: None,
+        lp_token_type: None,
     };
 
     let pair_id = app.upload(Box::new(
@@ -350,6 +390,7 @@ fn withdraw_liquidity() {
         slippage_tolerance: None,
         // we send lq token to pair and later call it directly to test
         receiver: Some(pair_addr.clone()),
+        auto_stake: None,
     };
 
     // only accept 100, then 50 share will be generated with 100 * (100 / 200)
@@ -396,3 +437,1742 @@ fn withdraw_liquidity() {
         )
     );
 }
+
+#[test]
+fn reply_liquidity_token_only_once() {
+    let mut deps = mock_dependencies();
+
+    let msg = InstantiateMsg {
+        oracle_addr: Addr::unchecked("oracle"),
+        asset_infos: [
+            AssetInfo::NativeToken {
+                denom: ORAI_DENOM.to_string(),
+            },
+            AssetInfo::NativeToken {
+                denom: ATOM_DENOM.to_string(),
+            },
+        ],
+        token_code_id: 10u64,
+        commission_rate: None,
+        lp_token_info: None,
+        guardian: None,
+        staking_contract: None,
+        lp_token_type: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    let instantiate_reply = |id: u64, address: &str| {
+        // protobuf encoded MsgInstantiateContractResponse { address }
+        let mut data = vec![0x0a, address.len() as u8];
+        data.extend_from_slice(address.as_bytes());
+        Reply {
+            id,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: Some(Binary::from(data)),
+            }),
+        }
+    };
+
+    // unknown reply id
+    let err = reply(
+        deps.as_mut(),
+        mock_env(),
+        instantiate_reply(2, "liquidity0000"),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unknown reply id: 2"));
+
+    reply(
+        deps.as_mut(),
+        mock_env(),
+        instantiate_reply(1, "liquidity0000"),
+    )
+    .unwrap();
+
+    // can not override the liquidity token
+    let err = reply(
+        deps.as_mut(),
+        mock_env(),
+        instantiate_reply(1, "liquidity0001"),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("liquidity token is already set"));
+
+    let pair_info = PAIR_INFO.load(&deps.storage).unwrap();
+    assert_eq!(
+        pair_info.liquidity_token,
+        deps.api.addr_canonicalize("liquidity0000").unwrap()
+    );
+}
+
+#[test]
+fn pause_by_guardian() {
+    let mut deps = mock_dependencies();
+
+    let msg = InstantiateMsg {
+        oracle_addr: Addr::unchecked("oracle"),
+        asset_infos: [
+            AssetInfo::NativeToken {
+                denom: ORAI_DENOM.to_string(),
+            },
+            AssetInfo::NativeToken {
+                denom: ATOM_DENOM.to_string(),
+            },
+        ],
+        token_code_id: 10u64,
+        commission_rate: None,
+        lp_token_info: None,
+        guardian: Some(Addr::unchecked("guardian")),
+        staking_contract: None,
+        lp_token_type: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    // only guardian can pause
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::Pause {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("guardian", &[]),
+        ExecuteMsg::Pause {},
+    )
+    .unwrap();
+
+    let offer_asset = Asset {
+        info: AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        amount: Uint128::from(100u128),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::Swap {
+            offer_asset: offer_asset.clone(),
+            belief_price: None,
+            max_spread: None,
+            to: None,
+            return_asset_info: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Paused {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::ProvideLiquidity {
+            assets: [
+                offer_asset,
+                Asset {
+                    info: AssetInfo::NativeToken {
+                        denom: ATOM_DENOM.to_string(),
+                    },
+                    amount: Uint128::from(100u128),
+                },
+            ],
+            slippage_tolerance: None,
+            receiver: None,
+            auto_stake: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Paused {});
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("guardian", &[]),
+        ExecuteMsg::Unpause {},
+    )
+    .unwrap();
+    assert!(!PAUSED.load(&deps.storage).unwrap());
+}
+
+#[test]
+fn skim_by_guardian() {
+    let mut deps = mock_dependencies_with_balance(&[
+        Coin::new(1000u128, ORAI_DENOM),
+        Coin::new(1000u128, ATOM_DENOM),
+        Coin::new(100u128, "uusd"),
+    ]);
+
+    let msg = InstantiateMsg {
+        oracle_addr: Addr::unchecked("oracle"),
+        asset_infos: [
+            AssetInfo::NativeToken {
+                denom: ORAI_DENOM.to_string(),
+            },
+            AssetInfo::NativeToken {
+                denom: ATOM_DENOM.to_string(),
+            },
+        ],
+        token_code_id: 10u64,
+        commission_rate: None,
+        lp_token_info: None,
+        guardian: Some(Addr::unchecked("guardian")),
+        staking_contract: None,
+        lp_token_type: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    // only guardian can skim
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::Skim { to: None },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // the pool assets are kept, only the denom sent by mistake is skimmed
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("guardian", &[]),
+        ExecuteMsg::Skim {
+            to: Some(Addr::unchecked("addr0000")),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: "addr0000".to_string(),
+            amount: coins(100u128, "uusd"),
+        })]
+    );
+
+    // nothing left to skim
+    deps.querier
+        .update_balance(MOCK_CONTRACT_ADDR, coins(1000u128, ORAI_DENOM));
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("guardian", &[]),
+        ExecuteMsg::Skim { to: None },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidZeroAmount {});
+}
+
+#[test]
+fn swap_invariant() {
+    // the product of the pools can not decrease after a swap
+    let (return_amount, _, _) = compute_swap(
+        Uint128::from(1000u128),
+        Uint128::from(1000u128),
+        Uint128::from(100u128),
+        Decimal256::zero(),
+    )
+    .unwrap();
+    assert_invariant(
+        Uint128::from(1000u128),
+        Uint128::from(1000u128),
+        Uint128::from(100u128),
+        return_amount,
+    )
+    .unwrap();
+    let err = assert_invariant(
+        Uint128::from(1000u128),
+        Uint128::from(1000u128),
+        Uint128::from(100u128),
+        Uint128::from(100u128),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvariantViolated {});
+}
+
+#[test]
+fn swap_exact_out() {
+    let mut app = MockApp::new(&[(
+        &"addr0000".to_string(),
+        &[
+            Coin {
+                denom: ORAI_DENOM.to_string(),
+                amount: Uint128::from(2000000u128),
+            },
+            Coin {
+                denom: ATOM_DENOM.to_string(),
+                amount: Uint128::from(2000000u128),
+            },
+        ],
+    )]);
+
+    app.set_oracle_contract(Box::new(create_entry_points_testing!(oraiswap_oracle)));
+
+    app.set_tax(
+        Decimal::zero(),
+        &[
+            (&ORAI_DENOM.to_string(), &Uint128::from(1000000u128)),
+            (&ATOM_DENOM.to_string(), &Uint128::from(1000000u128)),
+        ],
+    );
+
+    app.set_token_contract(Box::new(create_entry_points_testing!(oraiswap_token)));
+
+    let msg = InstantiateMsg {
+        oracle_addr: app.oracle_addr.clone(),
+        asset_infos: [
+            AssetInfo::NativeToken {
+                denom: ORAI_DENOM.to_string(),
+            },
+            AssetInfo::NativeToken {
+                denom: ATOM_DENOM.to_string(),
+            },
+        ],
+        token_code_id: app.token_id,
+        commission_rate: None,
+        lp_token_info: None,
+        guardian: None,
+        staking_contract: None,
+        lp_token_type: None,
+    };
+
+    let code_id = app.upload(Box::new(
+        create_entry_points_testing!(crate).with_reply(crate::contract::reply),
+    ));
+    let pair_addr = app
+        .instantiate(code_id, Addr::unchecked("owner"), &msg, &[], "pair")
+        .unwrap();
+
+    app.execute(
+        Addr::unchecked("addr0000"),
+        pair_addr.clone(),
+        &ExecuteMsg::ProvideLiquidity {
+            assets: [
+                Asset {
+                    info: AssetInfo::NativeToken {
+                        denom: ORAI_DENOM.to_string(),
+                    },
+                    amount: Uint128::from(1000000u128),
+                },
+                Asset {
+                    info: AssetInfo::NativeToken {
+                        denom: ATOM_DENOM.to_string(),
+                    },
+                    amount: Uint128::from(1000000u128),
+                },
+            ],
+            slippage_tolerance: None,
+            receiver: None,
+            auto_stake: None,
+        },
+        &[
+            Coin {
+                denom: ORAI_DENOM.to_string(),
+                amount: Uint128::from(1000000u128),
+            },
+            Coin {
+                denom: ATOM_DENOM.to_string(),
+                amount: Uint128::from(1000000u128),
+            },
+        ],
+    )
+    .unwrap();
+
+    let ask_asset = Asset {
+        info: AssetInfo::NativeToken {
+            denom: ATOM_DENOM.to_string(),
+        },
+        amount: Uint128::from(1000u128),
+    };
+
+    // required offer is 1004 orai, more than max offer amount
+    let res = app.execute(
+        Addr::unchecked("addr0000"),
+        pair_addr.clone(),
+        &ExecuteMsg::SwapExactOut {
+            ask_asset: ask_asset.clone(),
+            max_offer_amount: Uint128::from(1000u128),
+            to: None,
+        },
+        &[Coin {
+            denom: ORAI_DENOM.to_string(),
+            amount: Uint128::from(1500u128),
+        }],
+    );
+    app.assert_fail(res);
+
+    // not enough offer sent
+    let res = app.execute(
+        Addr::unchecked("addr0000"),
+        pair_addr.clone(),
+        &ExecuteMsg::SwapExactOut {
+            ask_asset: ask_asset.clone(),
+            max_offer_amount: Uint128::from(2000u128),
+            to: None,
+        },
+        &[Coin {
+            denom: ORAI_DENOM.to_string(),
+            amount: Uint128::from(1000u128),
+        }],
+    );
+    app.assert_fail(res);
+
+    // pay 1004 orai for exactly 1000 atom, the rest of 1500 orai is refunded
+    app.execute(
+        Addr::unchecked("addr0000"),
+        pair_addr.clone(),
+        &ExecuteMsg::SwapExactOut {
+            ask_asset,
+            max_offer_amount: Uint128::from(2000u128),
+            to: None,
+        },
+        &[Coin {
+            denom: ORAI_DENOM.to_string(),
+            amount: Uint128::from(1500u128),
+        }],
+    )
+    .unwrap();
+
+    assert_eq!(
+        app.query_balance(Addr::unchecked("addr0000"), ORAI_DENOM.to_string())
+            .unwrap(),
+        Uint128::from(998996u128)
+    );
+    // 1000 atom minus the minimum tax rate of the oracle
+    assert_eq!(
+        app.query_balance(Addr::unchecked("addr0000"), ATOM_DENOM.to_string())
+            .unwrap(),
+        Uint128::from(1000999u128)
+    );
+    assert_eq!(
+        app.query_balance(pair_addr, ORAI_DENOM.to_string())
+            .unwrap(),
+        Uint128::from(1001004u128)
+    );
+}
+
+#[test]
+fn max_trade_bps() {
+    let mut app = MockApp::new(&[(
+        &"addr0000".to_string(),
+        &[
+            Coin {
+                denom: ORAI_DENOM.to_string(),
+                amount: Uint128::from(2000000u128),
+            },
+            Coin {
+                denom: ATOM_DENOM.to_string(),
+                amount: Uint128::from(2000000u128),
+            },
+        ],
+    )]);
+
+    app.set_oracle_contract(Box::new(create_entry_points_testing!(oraiswap_oracle)));
+
+    app.set_tax(
+        Decimal::zero(),
+        &[
+            (&ORAI_DENOM.to_string(), &Uint128::from(1000000u128)),
+            (&ATOM_DENOM.to_string(), &Uint128::from(1000000u128)),
+        ],
+    );
+
+    app.set_token_contract(Box::new(create_entry_points_testing!(oraiswap_token)));
+
+    let msg = InstantiateMsg {
+        oracle_addr: app.oracle_addr.clone(),
+        asset_infos: [
+            AssetInfo::NativeToken {
+                denom: ORAI_DENOM.to_string(),
+            },
+            AssetInfo::NativeToken {
+                denom: ATOM_DENOM.to_string(),
+            },
+        ],
+        token_code_id: app.token_id,
+        commission_rate: None,
+        lp_token_info: None,
+        guardian: Some(Addr::unchecked("guardian")),
+        staking_contract: None,
+        lp_token_type: None,
+    };
+
+    let code_id = app.upload(Box::new(
+        create_entry_points_testing!(crate).with_reply(crate::contract::reply),
+    ));
+    let pair_addr = app
+        .instantiate(code_id, Addr::unchecked("owner"), &msg, &[], "pair")
+        .unwrap();
+
+    app.execute(
+        Addr::unchecked("addr0000"),
+        pair_addr.clone(),
+        &ExecuteMsg::ProvideLiquidity {
+            assets: [
+                Asset {
+                    info: AssetInfo::NativeToken {
+                        denom: ORAI_DENOM.to_string(),
+                    },
+                    amount: Uint128::from(1000000u128),
+                },
+                Asset {
+                    info: AssetInfo::NativeToken {
+                        denom: ATOM_DENOM.to_string(),
+                    },
+                    amount: Uint128::from(1000000u128),
+                },
+            ],
+            slippage_tolerance: None,
+            receiver: None,
+            auto_stake: None,
+        },
+        &[
+            Coin {
+                denom: ORAI_DENOM.to_string(),
+                amount: Uint128::from(1000000u128),
+            },
+            Coin {
+                denom: ATOM_DENOM.to_string(),
+                amount: Uint128::from(1000000u128),
+            },
+        ],
+    )
+    .unwrap();
+
+    // only guardian can update the max trade
+    let res = app.execute(
+        Addr::unchecked("owner"),
+        pair_addr.clone(),
+        &ExecuteMsg::UpdateMaxTradeBps {
+            max_trade_bps: Some(100),
+        },
+        &[],
+    );
+    app.assert_fail(res);
+
+    // zero is not a valid limit
+    let res = app.execute(
+        Addr::unchecked("guardian"),
+        pair_addr.clone(),
+        &ExecuteMsg::UpdateMaxTradeBps {
+            max_trade_bps: Some(0),
+        },
+        &[],
+    );
+    app.assert_fail(res);
+
+    // at most 1% of the offer pool in one swap
+    app.execute(
+        Addr::unchecked("guardian"),
+        pair_addr.clone(),
+        &ExecuteMsg::UpdateMaxTradeBps {
+            max_trade_bps: Some(100),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let res: MaxTradeBpsResponse = app
+        .query(pair_addr.clone(), &QueryMsg::MaxTradeBps {})
+        .unwrap();
+    assert_eq!(res.max_trade_bps, Some(100));
+
+    let swap = |amount: u128| ExecuteMsg::Swap {
+        offer_asset: Asset {
+            info: AssetInfo::NativeToken {
+                denom: ORAI_DENOM.to_string(),
+            },
+            amount: Uint128::from(amount),
+        },
+        belief_price: None,
+        max_spread: None,
+        to: None,
+        return_asset_info: None,
+    };
+
+    let res = app.execute(
+        Addr::unchecked("addr0000"),
+        pair_addr.clone(),
+        &swap(10001),
+        &[Coin {
+            denom: ORAI_DENOM.to_string(),
+            amount: Uint128::from(10001u128),
+        }],
+    );
+    app.assert_fail(res);
+
+    app.execute(
+        Addr::unchecked("addr0000"),
+        pair_addr.clone(),
+        &swap(10000),
+        &[Coin {
+            denom: ORAI_DENOM.to_string(),
+            amount: Uint128::from(10000u128),
+        }],
+    )
+    .unwrap();
+
+    // the required offer of an exact out swap is limited as well
+    let res = app.execute(
+        Addr::unchecked("addr0000"),
+        pair_addr.clone(),
+        &ExecuteMsg::SwapExactOut {
+            ask_asset: Asset {
+                info: AssetInfo::NativeToken {
+                    denom: ATOM_DENOM.to_string(),
+                },
+                amount: Uint128::from(20000u128),
+            },
+            max_offer_amount: Uint128::from(30000u128),
+            to: None,
+        },
+        &[Coin {
+            denom: ORAI_DENOM.to_string(),
+            amount: Uint128::from(30000u128),
+        }],
+    );
+    app.assert_fail(res);
+
+    // remove the limit
+    app.execute(
+        Addr::unchecked("guardian"),
+        pair_addr.clone(),
+        &ExecuteMsg::UpdateMaxTradeBps {
+            max_trade_bps: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute(
+        Addr::unchecked("addr0000"),
+        pair_addr.clone(),
+        &swap(20000),
+        &[Coin {
+            denom: ORAI_DENOM.to_string(),
+            amount: Uint128::from(20000u128),
+        }],
+    )
+    .unwrap();
+
+    let res: MaxTradeBpsResponse = app.query(pair_addr, &QueryMsg::MaxTradeBps {}).unwrap();
+    assert_eq!(res.max_trade_bps, None);
+}
+
+#[test]
+fn spot_price_with_decimals() {
+    // 1 orai (6 decimals) against 2 tokens (18 decimals)
+    let mut deps = mock_dependencies_with_balance(&coins(1000000u128, ORAI_DENOM));
+    deps.querier.update_wasm(|query| match query {
+        WasmQuery::Smart { msg, .. } => {
+            let res = match from_binary(msg).unwrap() {
+                Cw20QueryMsg::TokenInfo {} => to_binary(&TokenInfoResponse {
+                    name: "token".to_string(),
+                    symbol: "TOKEN".to_string(),
+                    decimals: 18,
+                    total_supply: Uint128::from(2000000000000000000u128),
+                }),
+                Cw20QueryMsg::Balance { .. } => to_binary(&BalanceResponse {
+                    balance: Uint128::from(2000000000000000000u128),
+                }),
+                _ => panic!("unexpected query"),
+            };
+            SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+        }
+        _ => panic!("unexpected query"),
+    });
+
+    let orai_info = AssetInfo::NativeToken {
+        denom: ORAI_DENOM.to_string(),
+    };
+    let token_info = AssetInfo::Token {
+        contract_addr: Addr::unchecked("token"),
+    };
+
+    let msg = InstantiateMsg {
+        oracle_addr: Addr::unchecked("oracle"),
+        asset_infos: [orai_info.clone(), token_info.clone()],
+        token_code_id: 10u64,
+        commission_rate: None,
+        lp_token_info: None,
+        guardian: None,
+        staking_contract: None,
+        lp_token_type: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    let res: SpotPriceResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::SpotPrice {
+                offer_asset_info: orai_info.clone(),
+                ask_asset_info: token_info.clone(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.price, Decimal256::from_ratio(2u128, 1u128));
+
+    let res: SpotPriceResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::SpotPrice {
+                offer_asset_info: token_info.clone(),
+                ask_asset_info: orai_info.clone(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.price, Decimal256::from_ratio(1u128, 2u128));
+
+    // asset not in the pair
+    let err = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::SpotPrice {
+            offer_asset_info: AssetInfo::NativeToken {
+                denom: ATOM_DENOM.to_string(),
+            },
+            ask_asset_info: orai_info,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::AssetMismatch {});
+}
+
+#[test]
+fn provide_liquidity_with_auto_stake() {
+    let mut deps = mock_dependencies_with_balance(&[
+        Coin {
+            denom: ORAI_DENOM.to_string(),
+            amount: Uint128::from(100u128),
+        },
+        Coin {
+            denom: ATOM_DENOM.to_string(),
+            amount: Uint128::from(100u128),
+        },
+    ]);
+    deps.querier.update_wasm(|_| {
+        SystemResult::Ok(ContractResult::Ok(
+            to_binary(&TokenInfoResponse {
+                name: "liquidity".to_string(),
+                symbol: "uLP".to_string(),
+                decimals: 6,
+                total_supply: Uint128::zero(),
+            })
+            .unwrap(),
+        ))
+    });
+
+    let msg = InstantiateMsg {
+        oracle_addr: Addr::unchecked("oracle"),
+        asset_infos: [
+            AssetInfo::NativeToken {
+                denom: ORAI_DENOM.to_string(),
+            },
+            AssetInfo::NativeToken {
+                denom: ATOM_DENOM.to_string(),
+            },
+        ],
+        token_code_id: 10u64,
+        commission_rate: None,
+        lp_token_info: None,
+        guardian: None,
+        staking_contract: Some(Addr::unchecked("staking")),
+        lp_token_type: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    let mut pair_info = PAIR_INFO.load(&deps.storage).unwrap();
+    pair_info.liquidity_token = deps.api.addr_canonicalize("liquidity").unwrap();
+    PAIR_INFO.save(&mut deps.storage, &pair_info).unwrap();
+
+    let funds = [
+        Coin {
+            denom: ORAI_DENOM.to_string(),
+            amount: Uint128::from(100u128),
+        },
+        Coin {
+            denom: ATOM_DENOM.to_string(),
+            amount: Uint128::from(100u128),
+        },
+    ];
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &funds),
+        ExecuteMsg::ProvideLiquidity {
+            assets: [
+                Asset {
+                    info: AssetInfo::NativeToken {
+                        denom: ORAI_DENOM.to_string(),
+                    },
+                    amount: Uint128::from(100u128),
+                },
+                Asset {
+                    info: AssetInfo::NativeToken {
+                        denom: ATOM_DENOM.to_string(),
+                    },
+                    amount: Uint128::from(100u128),
+                },
+            ],
+            slippage_tolerance: None,
+            receiver: None,
+            auto_stake: Some(true),
+        },
+    )
+    .unwrap();
+
+    // LP tokens are minted to the pair then bonded for the provider
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: "liquidity".to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Mint {
+                    recipient: MOCK_CONTRACT_ADDR.to_string(),
+                    amount: Uint128::from(100u128),
+                })
+                .unwrap(),
+                funds: vec![],
+            }),
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: "liquidity".to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Send {
+                    contract: "staking".to_string(),
+                    amount: Uint128::from(100u128),
+                    msg: to_binary(&StakingCw20HookMsg::BondFor {
+                        asset_info: AssetInfo::NativeToken {
+                            denom: ATOM_DENOM.to_string(),
+                        },
+                        staker_addr: Addr::unchecked("addr0000"),
+                    })
+                    .unwrap(),
+                })
+                .unwrap(),
+                funds: vec![],
+            }),
+        ]
+    );
+}
+
+#[test]
+fn donate() {
+    let mut deps = mock_dependencies_with_balance(&[Coin {
+        denom: ORAI_DENOM.to_string(),
+        amount: Uint128::from(100u128),
+    }]);
+    deps.querier.update_wasm(liquidity_token_querier(0u128));
+
+    let msg = InstantiateMsg {
+        oracle_addr: Addr::unchecked("oracle"),
+        asset_infos: [
+            AssetInfo::NativeToken {
+                denom: ORAI_DENOM.to_string(),
+            },
+            AssetInfo::Token {
+                contract_addr: Addr::unchecked("asset0000"),
+            },
+        ],
+        token_code_id: 10u64,
+        commission_rate: None,
+        lp_token_info: None,
+        guardian: None,
+        staking_contract: None,
+        lp_token_type: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    let mut pair_info = PAIR_INFO.load(&deps.storage).unwrap();
+    pair_info.liquidity_token = deps.api.addr_canonicalize("liquidity").unwrap();
+    PAIR_INFO.save(&mut deps.storage, &pair_info).unwrap();
+
+    let assets = [
+        Asset {
+            info: AssetInfo::NativeToken {
+                denom: ORAI_DENOM.to_string(),
+            },
+            amount: Uint128::from(100u128),
+        },
+        Asset {
+            info: AssetInfo::Token {
+                contract_addr: Addr::unchecked("asset0000"),
+            },
+            amount: Uint128::from(50u128),
+        },
+    ];
+    let funds = coins(100u128, ORAI_DENOM);
+
+    // can not donate before anyone provides liquidity
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &funds),
+        ExecuteMsg::Donate {
+            assets: assets.clone(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Std(StdError::generic_err("Cannot donate to an empty pool"))
+    );
+
+    deps.querier.update_wasm(liquidity_token_querier(1000u128));
+
+    // native asset must be sent
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::Donate {
+            assets: assets.clone(),
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::Std(_)));
+
+    // token is pulled from the donor, no LP token is minted
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &funds),
+        ExecuteMsg::Donate { assets },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(WasmMsg::Execute {
+            contract_addr: "asset0000".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+                owner: "addr0000".to_string(),
+                recipient: MOCK_CONTRACT_ADDR.to_string(),
+                amount: Uint128::from(50u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        })]
+    );
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "donate"),
+            attr("sender", "addr0000"),
+            attr("assets", format!("100{}, 50asset0000", ORAI_DENOM)),
+        ]
+    );
+}
+
+#[test]
+fn lp_value() {
+    let mut deps = mock_dependencies_with_balance(&[Coin {
+        denom: ORAI_DENOM.to_string(),
+        amount: Uint128::from(3000u128),
+    }]);
+    // 1000 LP tokens and 1000 asset tokens in the pool
+    deps.querier.update_wasm(liquidity_token_querier(1000u128));
+
+    let msg = InstantiateMsg {
+        oracle_addr: Addr::unchecked("oracle"),
+        asset_infos: [
+            AssetInfo::NativeToken {
+                denom: ORAI_DENOM.to_string(),
+            },
+            AssetInfo::Token {
+                contract_addr: Addr::unchecked("asset0000"),
+            },
+        ],
+        token_code_id: 10u64,
+        commission_rate: None,
+        lp_token_info: None,
+        guardian: None,
+        staking_contract: None,
+        lp_token_type: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    let mut pair_info = PAIR_INFO.load(&deps.storage).unwrap();
+    pair_info.liquidity_token = deps.api.addr_canonicalize("liquidity").unwrap();
+    PAIR_INFO.save(&mut deps.storage, &pair_info).unwrap();
+
+    // provide at price 0.5 to addr0001, the orai pool is 2000 before the deposit
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &coins(1000u128, ORAI_DENOM)),
+        ExecuteMsg::ProvideLiquidity {
+            assets: [
+                Asset {
+                    info: AssetInfo::NativeToken {
+                        denom: ORAI_DENOM.to_string(),
+                    },
+                    amount: Uint128::from(1000u128),
+                },
+                Asset {
+                    info: AssetInfo::Token {
+                        contract_addr: Addr::unchecked("asset0000"),
+                    },
+                    amount: Uint128::from(500u128),
+                },
+            ],
+            slippage_tolerance: None,
+            receiver: Some(Addr::unchecked("addr0001")),
+            auto_stake: None,
+        },
+    )
+    .unwrap();
+
+    let query_lp_value = |address: Option<&str>| -> LpValueResponse {
+        from_binary(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::LpValue {
+                    amount: Uint128::from(100u128),
+                    address: address.map(|address| address.to_string()),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap()
+    };
+
+    // the mocked balances did not change, 10% of the LP token backs 300 orai and 100 asset tokens
+    let res = query_lp_value(Some("addr0001"));
+    assert_eq!(
+        res.assets.map(|asset| asset.amount),
+        [Uint128::from(300u128), Uint128::from(100u128)]
+    );
+    assert_eq!(res.price, Decimal256::from_ratio(1u128, 3u128));
+    assert_eq!(res.last_provide_price, Some(Decimal256::percent(50)));
+
+    // the price is only tracked for the receiver
+    assert_eq!(query_lp_value(Some("addr0000")).last_provide_price, None);
+    assert_eq!(query_lp_value(None).last_provide_price, None);
+}
+
+#[test]
+fn swap_legacy_assets_through_converter() {
+    // the pair holds the pools and the sent legacy coins
+    let mut deps = mock_dependencies_with_balance(&[
+        Coin {
+            denom: ORAI_DENOM.to_string(),
+            amount: Uint128::from(1000000u128),
+        },
+        Coin {
+            denom: "ulegacy".to_string(),
+            amount: Uint128::from(100u128),
+        },
+    ]);
+    // 1 ulegacy => 2 orai, with a 1% conversion fee
+    deps.querier.update_wasm(|query| match query {
+        WasmQuery::Smart { contract_addr, msg } if contract_addr == "converter" => {
+            let res = match from_binary(msg).unwrap() {
+                ConverterQueryMsg::ConvertInfo { asset_info } => {
+                    if asset_info
+                        != (AssetInfo::NativeToken {
+                            denom: "ulegacy".to_string(),
+                        })
+                    {
+                        return SystemResult::Ok(ContractResult::Err("not found".to_string()));
+                    }
+                    to_binary(&ConvertInfoResponse {
+                        token_ratio: TokenRatio {
+                            info: AssetInfo::NativeToken {
+                                denom: ORAI_DENOM.to_string(),
+                            },
+                            ratio: Decimal::percent(200),
+                        },
+                    })
+                }
+                ConverterQueryMsg::ConvertLimit { .. } => to_binary(&ConvertLimitResponse {
+                    limit: Some(ConvertLimit {
+                        fee_bps: 100,
+                        daily_cap: None,
+                    }),
+                    daily_volume: Uint128::zero(),
+                    collected_fee: Uint128::zero(),
+                }),
+                _ => panic!("unexpected query"),
+            };
+            SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+        }
+        WasmQuery::Smart { msg, .. } => {
+            let res = match from_binary(msg).unwrap() {
+                Cw20QueryMsg::Balance { .. } => to_binary(&BalanceResponse {
+                    balance: Uint128::from(1000000u128),
+                }),
+                _ => panic!("unexpected query"),
+            };
+            SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+        }
+        _ => panic!("unexpected query"),
+    });
+
+    let legacy_info = AssetInfo::NativeToken {
+        denom: "ulegacy".to_string(),
+    };
+    let msg = InstantiateMsg {
+        oracle_addr: Addr::unchecked("oracle"),
+        asset_infos: [
+            AssetInfo::NativeToken {
+                denom: ORAI_DENOM.to_string(),
+            },
+            AssetInfo::Token {
+                contract_addr: Addr::unchecked("token"),
+            },
+        ],
+        token_code_id: 10u64,
+        commission_rate: None,
+        lp_token_info: None,
+        guardian: None,
+        staking_contract: None,
+        lp_token_type: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    let swap_legacy = ExecuteMsg::Swap {
+        offer_asset: Asset {
+            info: legacy_info.clone(),
+            amount: Uint128::from(100u128),
+        },
+        belief_price: None,
+        max_spread: None,
+        to: None,
+        return_asset_info: None,
+    };
+    let legacy_funds = coins(100u128, "ulegacy");
+
+    // legacy assets are rejected until the converter is set
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &legacy_funds),
+        swap_legacy.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::AssetMismatch {});
+
+    migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg {
+            guardian: None,
+            staking_contract: None,
+            max_trade_bps: None,
+            converter: Some(Addr::unchecked("converter")),
+            registry: None,
+            router: None,
+            router_commission_rate: None,
+        },
+    )
+    .unwrap();
+
+    // 100 ulegacy are converted into 198 orai before the swap, the orai pool is not increased yet
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &legacy_funds),
+        swap_legacy,
+    )
+    .unwrap();
+    let (return_amount, _, _) = compute_swap(
+        Uint128::from(1000000u128),
+        Uint128::from(1000000u128),
+        Uint128::from(198u128),
+        Decimal256::from_str(DEFAULT_COMMISSION_RATE).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: "converter".to_string(),
+                msg: to_binary(&ConverterExecuteMsg::Convert {}).unwrap(),
+                funds: legacy_funds,
+            }),
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: "token".to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: "addr0000".to_string(),
+                    amount: return_amount,
+                })
+                .unwrap(),
+                funds: vec![],
+            }),
+        ]
+    );
+    assert!(res.attributes.contains(&attr("offer_amount", "198")));
+
+    // the returned orai is converted back into ulegacy for the receiver
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("token", &[]),
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: "addr0000".to_string(),
+            amount: Uint128::from(200u128),
+            msg: to_binary(&Cw20HookMsg::Swap {
+                belief_price: None,
+                max_spread: None,
+                to: None,
+                return_asset_info: Some(legacy_info.clone()),
+            })
+            .unwrap(),
+        }),
+    )
+    .unwrap();
+    let (return_amount, _, _) = compute_swap(
+        Uint128::from(999800u128),
+        Uint128::from(1000000u128),
+        Uint128::from(200u128),
+        Decimal256::from_str(DEFAULT_COMMISSION_RATE).unwrap(),
+    )
+    .unwrap();
+    let legacy_amount = return_amount
+        .checked_div_decimal(Decimal::percent(200))
+        .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: "converter".to_string(),
+                msg: to_binary(&ConverterExecuteMsg::ConvertReverse {
+                    from_asset: legacy_info,
+                })
+                .unwrap(),
+                funds: coins(return_amount.u128(), ORAI_DENOM),
+            }),
+            SubMsg::new(BankMsg::Send {
+                to_address: "addr0000".to_string(),
+                amount: coins(legacy_amount.u128(), "ulegacy"),
+            }),
+        ]
+    );
+
+    // assets unknown to the converter are still rejected
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &coins(100u128, ATOM_DENOM)),
+        ExecuteMsg::Swap {
+            offer_asset: Asset {
+                info: AssetInfo::NativeToken {
+                    denom: ATOM_DENOM.to_string(),
+                },
+                amount: Uint128::from(100u128),
+            },
+            belief_price: None,
+            max_spread: None,
+            to: None,
+            return_asset_info: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::AssetMismatch {});
+}
+
+#[test]
+fn router_commission_discount() {
+    // the offered orai is already in the balance of the pair
+    let mut deps = mock_dependencies_with_balance(&[Coin {
+        denom: ORAI_DENOM.to_string(),
+        amount: Uint128::from(1010000u128),
+    }]);
+    deps.querier.update_wasm(|query| match query {
+        WasmQuery::Smart { msg, .. } => {
+            let res = match from_binary(msg).unwrap() {
+                Cw20QueryMsg::Balance { .. } => to_binary(&BalanceResponse {
+                    balance: Uint128::from(1000000u128),
+                }),
+                _ => panic!("unexpected query"),
+            };
+            SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+        }
+        _ => panic!("unexpected query"),
+    });
+
+    let msg = InstantiateMsg {
+        oracle_addr: Addr::unchecked("oracle"),
+        asset_infos: [
+            AssetInfo::NativeToken {
+                denom: ORAI_DENOM.to_string(),
+            },
+            AssetInfo::Token {
+                contract_addr: Addr::unchecked("token"),
+            },
+        ],
+        token_code_id: 10u64,
+        commission_rate: None,
+        lp_token_info: None,
+        guardian: None,
+        staking_contract: None,
+        lp_token_type: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    let migrate_msg = |router_commission_rate: &str| MigrateMsg {
+        guardian: None,
+        staking_contract: None,
+        max_trade_bps: None,
+        converter: None,
+        registry: None,
+        router: Some(Addr::unchecked("router")),
+        router_commission_rate: Some(router_commission_rate.to_string()),
+    };
+
+    // the router commission rate can not be greater than the commission rate
+    let err = migrate(deps.as_mut(), mock_env(), migrate_msg("0.01")).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Std(StdError::generic_err(
+            "router commission rate must not exceed the commission rate"
+        ))
+    );
+
+    migrate(deps.as_mut(), mock_env(), migrate_msg("0.001")).unwrap();
+
+    let res: RouterCommissionResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::RouterCommission {}).unwrap())
+            .unwrap();
+    assert_eq!(
+        res,
+        RouterCommissionResponse {
+            router: Some(Addr::unchecked("router")),
+            commission_rate: "0.001".to_string(),
+        }
+    );
+
+    let swap = ExecuteMsg::Swap {
+        offer_asset: Asset {
+            info: AssetInfo::NativeToken {
+                denom: ORAI_DENOM.to_string(),
+            },
+            amount: Uint128::from(10000u128),
+        },
+        belief_price: None,
+        max_spread: None,
+        to: None,
+        return_asset_info: None,
+    };
+    let funds = coins(10000u128, ORAI_DENOM);
+
+    // swaps routed by the router are charged the discounted commission
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("router", &funds),
+        swap.clone(),
+    )
+    .unwrap();
+    let (return_amount, _, commission_amount) = compute_swap(
+        Uint128::from(1000000u128),
+        Uint128::from(1000000u128),
+        Uint128::from(10000u128),
+        Decimal256::from_str("0.001").unwrap(),
+    )
+    .unwrap();
+    assert!(res
+        .attributes
+        .contains(&attr("return_amount", return_amount.to_string())));
+    assert!(res
+        .attributes
+        .contains(&attr("commission_amount", commission_amount.to_string())));
+
+    // other senders are charged the commission rate of the pair
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &funds),
+        swap,
+    )
+    .unwrap();
+    let (full_return_amount, _, full_commission_amount) = compute_swap(
+        Uint128::from(1000000u128),
+        Uint128::from(1000000u128),
+        Uint128::from(10000u128),
+        Decimal256::from_str(DEFAULT_COMMISSION_RATE).unwrap(),
+    )
+    .unwrap();
+    assert!(res
+        .attributes
+        .contains(&attr("return_amount", full_return_amount.to_string())));
+    assert!(res.attributes.contains(&attr(
+        "commission_amount",
+        full_commission_amount.to_string()
+    )));
+    assert!(full_return_amount < return_amount);
+}
+
+#[test]
+fn tokenfactory_lp_shares() {
+    let mut deps = mock_dependencies_with_balance(&[
+        Coin {
+            denom: ORAI_DENOM.to_string(),
+            amount: Uint128::from(100u128),
+        },
+        Coin {
+            denom: ATOM_DENOM.to_string(),
+            amount: Uint128::from(100u128),
+        },
+    ]);
+    // no tax on the refunds
+    deps.querier.update_wasm(|_| {
+        SystemResult::Ok(ContractResult::Ok(
+            to_binary(&TaxInfo {
+                rate: Decimal::zero(),
+                cap: Uint128::zero(),
+            })
+            .unwrap(),
+        ))
+    });
+
+    let mut msg = InstantiateMsg {
+        oracle_addr: Addr::unchecked("oracle"),
+        asset_infos: [
+            AssetInfo::NativeToken {
+                denom: ORAI_DENOM.to_string(),
+            },
+            AssetInfo::NativeToken {
+                denom: ATOM_DENOM.to_string(),
+            },
+        ],
+        token_code_id: 10u64,
+        commission_rate: None,
+        lp_token_info: Some(LpTokenInfo::default()),
+        guardian: None,
+        staking_contract: Some(Addr::unchecked("staking")),
+        lp_token_type: Some(LpTokenType::TokenFactory),
+    };
+
+    // lp token info is for cw20 liquidity tokens only
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+
+    msg.lp_token_info = None;
+    let res = instantiate(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+    let contract_addr = Addr::unchecked(MOCK_CONTRACT_ADDR);
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(
+            create_denom_msg(&contract_addr, LP_SUBDENOM).unwrap()
+        )]
+    );
+
+    let lp_denom = format!("factory/{}/lp", MOCK_CONTRACT_ADDR);
+    let pair_res: PairResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Pair {}).unwrap()).unwrap();
+    assert_eq!(pair_res.info.lp_denom, Some(lp_denom.clone()));
+    assert_eq!(pair_res.info.liquidity_token, contract_addr);
+
+    let provide_msg = |auto_stake: bool| ExecuteMsg::ProvideLiquidity {
+        assets: [
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: ORAI_DENOM.to_string(),
+                },
+                amount: Uint128::from(100u128),
+            },
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: ATOM_DENOM.to_string(),
+                },
+                amount: Uint128::from(100u128),
+            },
+        ],
+        slippage_tolerance: None,
+        receiver: None,
+        auto_stake: Some(auto_stake),
+    };
+    let funds = [
+        Coin {
+            denom: ORAI_DENOM.to_string(),
+            amount: Uint128::from(100u128),
+        },
+        Coin {
+            denom: ATOM_DENOM.to_string(),
+            amount: Uint128::from(100u128),
+        },
+    ];
+
+    // the staking contract bonds cw20 LP tokens only
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &funds),
+        provide_msg(true),
+    )
+    .unwrap_err();
+
+    // the shares are minted to the pair then sent to the provider
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &funds),
+        provide_msg(false),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(mint_msg(&contract_addr, &lp_denom, Uint128::from(100u128)).unwrap()),
+            SubMsg::new(BankMsg::Send {
+                to_address: "addr0000".to_string(),
+                amount: coins(100u128, lp_denom.clone()),
+            }),
+        ]
+    );
+
+    // the provider sends back the shares
+    deps.querier.update_balance(
+        MOCK_CONTRACT_ADDR,
+        vec![
+            Coin {
+                denom: ORAI_DENOM.to_string(),
+                amount: Uint128::from(100u128),
+            },
+            Coin {
+                denom: ATOM_DENOM.to_string(),
+                amount: Uint128::from(100u128),
+            },
+            Coin {
+                denom: lp_denom.clone(),
+                amount: Uint128::from(100u128),
+            },
+        ],
+    );
+
+    // shares are not withdrawn through the cw20 hook
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: "addr0000".to_string(),
+            amount: Uint128::from(100u128),
+            msg: to_binary(&Cw20HookMsg::WithdrawLiquidity {}).unwrap(),
+        }),
+    )
+    .unwrap_err();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &coins(100u128, lp_denom.clone())),
+        ExecuteMsg::WithdrawLiquidity {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(BankMsg::Send {
+                to_address: "addr0000".to_string(),
+                amount: coins(100u128, ORAI_DENOM),
+            }),
+            SubMsg::new(BankMsg::Send {
+                to_address: "addr0000".to_string(),
+                amount: coins(100u128, ATOM_DENOM),
+            }),
+            SubMsg::new(burn_msg(&contract_addr, &lp_denom, Uint128::from(100u128)).unwrap()),
+        ]
+    );
+}
+
+fn liquidity_token_querier(
+    total_supply: u128,
+) -> impl Fn(&WasmQuery) -> SystemResult<ContractResult<Binary>> {
+    move |query| match query {
+        WasmQuery::Smart { msg, .. } => {
+            let res = match from_binary(msg).unwrap() {
+                Cw20QueryMsg::TokenInfo {} => to_binary(&TokenInfoResponse {
+                    name: "liquidity".to_string(),
+                    symbol: "uLP".to_string(),
+                    decimals: 6,
+                    total_supply: Uint128::from(total_supply),
+                }),
+                Cw20QueryMsg::Balance { .. } => to_binary(&BalanceResponse {
+                    balance: Uint128::from(1000u128),
+                }),
+                _ => panic!("unexpected query"),
+            };
+            SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+        }
+        _ => panic!("unexpected query"),
+    }
+}
+
+#[test]
+fn net_receive_estimate_with_tax_cap() {
+    let mut app = MockApp::new(&[(
+        &"addr0000".to_string(),
+        &[
+            Coin {
+                denom: ORAI_DENOM.to_string(),
+                amount: Uint128::from(2000000u128),
+            },
+            Coin {
+                denom: ATOM_DENOM.to_string(),
+                amount: Uint128::from(2000000u128),
+            },
+        ],
+    )]);
+
+    app.set_oracle_contract(Box::new(create_entry_points_testing!(oraiswap_oracle)));
+
+    // 1% tax on atom transfers, capped at 5 atom
+    app.set_tax(
+        Decimal::percent(1),
+        &[
+            (&ORAI_DENOM.to_string(), &Uint128::from(1000000u128)),
+            (&ATOM_DENOM.to_string(), &Uint128::from(5u128)),
+        ],
+    );
+
+    app.set_token_contract(Box::new(create_entry_points_testing!(oraiswap_token)));
+
+    let msg = InstantiateMsg {
+        oracle_addr: app.oracle_addr.clone(),
+        asset_infos: [
+            AssetInfo::NativeToken {
+                denom: ORAI_DENOM.to_string(),
+            },
+            AssetInfo::NativeToken {
+                denom: ATOM_DENOM.to_string(),
+            },
+        ],
+        token_code_id: app.token_id,
+        commission_rate: None,
+        lp_token_info: None,
+        guardian: None,
+        staking_contract: None,
+        lp_token_type: None,
+    };
+
+    let code_id = app.upload(Box::new(
+        create_entry_points_testing!(crate).with_reply(crate::contract::reply),
+    ));
+    let pair_addr = app
+        .instantiate(code_id, Addr::unchecked("owner"), &msg, &[], "pair")
+        .unwrap();
+
+    app.execute(
+        Addr::unchecked("addr0000"),
+        pair_addr.clone(),
+        &ExecuteMsg::ProvideLiquidity {
+            assets: [
+                Asset {
+                    info: AssetInfo::NativeToken {
+                        denom: ORAI_DENOM.to_string(),
+                    },
+                    amount: Uint128::from(1000000u128),
+                },
+                Asset {
+                    info: AssetInfo::NativeToken {
+                        denom: ATOM_DENOM.to_string(),
+                    },
+                    amount: Uint128::from(1000000u128),
+                },
+            ],
+            slippage_tolerance: None,
+            receiver: None,
+            auto_stake: None,
+        },
+        &[
+            Coin {
+                denom: ORAI_DENOM.to_string(),
+                amount: Uint128::from(1000000u128),
+            },
+            Coin {
+                denom: ATOM_DENOM.to_string(),
+                amount: Uint128::from(1000000u128),
+            },
+        ],
+    )
+    .unwrap();
+
+    let offer_asset = Asset {
+        info: AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        amount: Uint128::from(100000u128),
+    };
+
+    let simulation: SimulationResponse = app
+        .query(
+            pair_addr.clone(),
+            &QueryMsg::Simulation {
+                offer_asset: offer_asset.clone(),
+            },
+        )
+        .unwrap();
+    let estimate: NetReceiveEstimateResponse = app
+        .query(
+            pair_addr.clone(),
+            &QueryMsg::NetReceiveEstimate {
+                offer_asset: offer_asset.clone(),
+            },
+        )
+        .unwrap();
+    // 1% of the return amount is more than the cap
+    assert_eq!(estimate.return_amount, simulation.return_amount);
+    assert_eq!(estimate.tax_amount, Uint128::from(5u128));
+    assert_eq!(
+        estimate.net_return_amount,
+        simulation.return_amount - Uint128::from(5u128)
+    );
+
+    let atom_balance = app
+        .query_balance(Addr::unchecked("addr0000"), ATOM_DENOM.to_string())
+        .unwrap();
+    let res = app
+        .execute(
+            Addr::unchecked("addr0000"),
+            pair_addr.clone(),
+            &ExecuteMsg::Swap {
+                offer_asset: offer_asset.clone(),
+                belief_price: None,
+                max_spread: None,
+                to: None,
+                return_asset_info: None,
+            },
+            &[Coin {
+                denom: ORAI_DENOM.to_string(),
+                amount: offer_asset.amount,
+            }],
+        )
+        .unwrap();
+
+    let find_attr = |key: &str| {
+        res.events
+            .iter()
+            .flat_map(|event| event.attributes.iter())
+            .find(|attr| attr.key == key)
+            .map(|attr| attr.value.clone())
+    };
+    assert_eq!(find_attr("tax_amount"), Some("5".to_string()));
+    assert_eq!(
+        find_attr("net_return_amount"),
+        Some(estimate.net_return_amount.to_string())
+    );
+
+    // the receiver gets exactly the estimated net amount
+    assert_eq!(
+        app.query_balance(Addr::unchecked("addr0000"), ATOM_DENOM.to_string())
+            .unwrap(),
+        atom_balance + estimate.net_return_amount
+    );
+}
@@ -0,0 +1,215 @@
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response, StdError,
+    StdResult, Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+
+use crate::state::{
+    read_config, read_pending_rebate, read_rebate_rate, store_config, store_pending_rebate,
+    store_rebate_rate, Config,
+};
+
+use oraiswap::asset::AssetInfo;
+use oraiswap::rebate::{
+    ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, PendingRebateResponse, QueryMsg,
+    RebateRateResponse,
+};
+
+// 100% in basis points
+const BPS_DENOMINATOR: u128 = 10000;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    store_config(
+        deps.storage,
+        &Config {
+            owner: deps.api.addr_canonicalize(info.sender.as_str())?,
+            router: deps.api.addr_canonicalize(msg.router.as_str())?,
+            reward_token: deps.api.addr_canonicalize(msg.reward_token.as_str())?,
+        },
+    )?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::UpdateConfig {
+            owner,
+            router,
+            reward_token,
+        } => update_config(deps, info, owner, router, reward_token),
+        ExecuteMsg::UpdateRebateRate {
+            asset_info,
+            rebate_bps,
+        } => update_rebate_rate(deps, info, asset_info, rebate_bps),
+        ExecuteMsg::RecordVolume {
+            trader,
+            asset_info,
+            amount,
+        } => record_volume(deps, info, trader, asset_info, amount),
+        ExecuteMsg::ClaimRebate {} => claim_rebate(deps, info),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+pub fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    owner: Option<Addr>,
+    router: Option<Addr>,
+    reward_token: Option<Addr>,
+) -> StdResult<Response> {
+    let mut config: Config = read_config(deps.storage)?;
+    if config.owner != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if let Some(owner) = owner {
+        config.owner = deps.api.addr_canonicalize(owner.as_str())?;
+    }
+
+    if let Some(router) = router {
+        config.router = deps.api.addr_canonicalize(router.as_str())?;
+    }
+
+    if let Some(reward_token) = reward_token {
+        config.reward_token = deps.api.addr_canonicalize(reward_token.as_str())?;
+    }
+
+    store_config(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+pub fn update_rebate_rate(
+    deps: DepsMut,
+    info: MessageInfo,
+    asset_info: AssetInfo,
+    rebate_bps: u64,
+) -> StdResult<Response> {
+    let config: Config = read_config(deps.storage)?;
+    if config.owner != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    store_rebate_rate(deps.storage, &asset_info.to_vec(deps.api)?, rebate_bps)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "update_rebate_rate"),
+        ("asset_info", &asset_info.to_string()),
+        ("rebate_bps", &rebate_bps.to_string()),
+    ]))
+}
+
+/// Router reports swap volume of a trader, volume of assets without rebate rate is ignored
+/// so that swaps are never blocked by the rebate contract
+pub fn record_volume(
+    deps: DepsMut,
+    info: MessageInfo,
+    trader: Addr,
+    asset_info: AssetInfo,
+    amount: Uint128,
+) -> StdResult<Response> {
+    let config: Config = read_config(deps.storage)?;
+    if config.router != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let rebate_bps = read_rebate_rate(deps.storage, &asset_info.to_vec(deps.api)?)?;
+    let rebate_amount = amount.multiply_ratio(rebate_bps, BPS_DENOMINATOR);
+
+    if !rebate_amount.is_zero() {
+        let trader_raw = deps.api.addr_canonicalize(trader.as_str())?;
+        let pending_rebate = read_pending_rebate(deps.storage, &trader_raw)?;
+        store_pending_rebate(
+            deps.storage,
+            &trader_raw,
+            pending_rebate.checked_add(rebate_amount)?,
+        )?;
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "record_volume"),
+        ("trader", trader.as_str()),
+        ("volume", &format!("{}{}", amount, asset_info)),
+        ("rebate_amount", &rebate_amount.to_string()),
+    ]))
+}
+
+pub fn claim_rebate(deps: DepsMut, info: MessageInfo) -> StdResult<Response> {
+    let config: Config = read_config(deps.storage)?;
+    let trader_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let amount = read_pending_rebate(deps.storage, &trader_raw)?;
+    if amount.is_zero() {
+        return Err(StdError::generic_err("no rebate to claim"));
+    }
+
+    store_pending_rebate(deps.storage, &trader_raw, Uint128::zero())?;
+
+    Ok(Response::new()
+        .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: deps.api.addr_humanize(&config.reward_token)?.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: info.sender.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        }))
+        .add_attributes(vec![
+            ("action", "claim_rebate"),
+            ("trader", info.sender.as_str()),
+            ("amount", &amount.to_string()),
+        ]))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::RebateRate { asset_info } => to_binary(&query_rebate_rate(deps, asset_info)?),
+        QueryMsg::PendingRebate { trader } => to_binary(&query_pending_rebate(deps, trader)?),
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let state = read_config(deps.storage)?;
+    let resp = ConfigResponse {
+        owner: deps.api.addr_humanize(&state.owner)?,
+        router: deps.api.addr_humanize(&state.router)?,
+        reward_token: deps.api.addr_humanize(&state.reward_token)?,
+    };
+
+    Ok(resp)
+}
+
+pub fn query_rebate_rate(deps: Deps, asset_info: AssetInfo) -> StdResult<RebateRateResponse> {
+    let rebate_bps = read_rebate_rate(deps.storage, &asset_info.to_vec(deps.api)?)?;
+
+    Ok(RebateRateResponse {
+        asset_info,
+        rebate_bps,
+    })
+}
+
+pub fn query_pending_rebate(deps: Deps, trader: Addr) -> StdResult<PendingRebateResponse> {
+    let amount = read_pending_rebate(deps.storage, &deps.api.addr_canonicalize(trader.as_str())?)?;
+
+    Ok(PendingRebateResponse { trader, amount })
+}
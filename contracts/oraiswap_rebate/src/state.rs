@@ -0,0 +1,50 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{CanonicalAddr, StdResult, Storage, Uint128};
+use cosmwasm_storage::{singleton, singleton_read, Bucket, ReadonlyBucket};
+
+static KEY_CONFIG: &[u8] = b"config";
+static PREFIX_REBATE_RATE: &[u8] = b"rebate_rate";
+static PREFIX_PENDING_REBATE: &[u8] = b"pending_rebate";
+
+#[cw_serde]
+pub struct Config {
+    pub owner: CanonicalAddr,
+    pub router: CanonicalAddr,
+    pub reward_token: CanonicalAddr,
+}
+
+pub fn store_config(storage: &mut dyn Storage, config: &Config) -> StdResult<()> {
+    singleton(storage, KEY_CONFIG).save(config)
+}
+
+pub fn read_config(storage: &dyn Storage) -> StdResult<Config> {
+    singleton_read(storage, KEY_CONFIG).load()
+}
+
+pub fn store_rebate_rate(
+    storage: &mut dyn Storage,
+    asset_key: &[u8],
+    rebate_bps: u64,
+) -> StdResult<()> {
+    Bucket::new(storage, PREFIX_REBATE_RATE).save(asset_key, &rebate_bps)
+}
+
+pub fn read_rebate_rate(storage: &dyn Storage, asset_key: &[u8]) -> StdResult<u64> {
+    ReadonlyBucket::new(storage, PREFIX_REBATE_RATE)
+        .may_load(asset_key)
+        .map(|rebate_bps| rebate_bps.unwrap_or_default())
+}
+
+pub fn store_pending_rebate(
+    storage: &mut dyn Storage,
+    trader: &CanonicalAddr,
+    amount: Uint128,
+) -> StdResult<()> {
+    Bucket::new(storage, PREFIX_PENDING_REBATE).save(trader.as_slice(), &amount)
+}
+
+pub fn read_pending_rebate(storage: &dyn Storage, trader: &CanonicalAddr) -> StdResult<Uint128> {
+    ReadonlyBucket::new(storage, PREFIX_PENDING_REBATE)
+        .may_load(trader.as_slice())
+        .map(|amount| amount.unwrap_or_default())
+}
@@ -0,0 +1,131 @@
+use crate::contract::{execute, instantiate, query_config, query_pending_rebate};
+use cosmwasm_std::{
+    testing::{mock_dependencies, mock_env, mock_info},
+    to_binary, Addr, CosmosMsg, StdError, SubMsg, Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+use oraiswap::asset::{AssetInfo, ORAI_DENOM};
+use oraiswap::rebate::{ConfigResponse, ExecuteMsg, InstantiateMsg};
+
+#[test]
+fn proper_initialization() {
+    let mut deps = mock_dependencies();
+
+    let msg = InstantiateMsg {
+        router: Addr::unchecked("router"),
+        reward_token: Addr::unchecked("oraix"),
+    };
+
+    // we can just call .unwrap() to assert this was a success
+    let _res = instantiate(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    // it worked, let's query the state
+    let config = query_config(deps.as_ref()).unwrap();
+
+    assert_eq!(
+        config,
+        ConfigResponse {
+            owner: Addr::unchecked("owner"),
+            router: Addr::unchecked("router"),
+            reward_token: Addr::unchecked("oraix"),
+        }
+    );
+}
+
+#[test]
+fn record_volume_and_claim_rebate() {
+    let mut deps = mock_dependencies();
+
+    let msg = InstantiateMsg {
+        router: Addr::unchecked("router"),
+        reward_token: Addr::unchecked("oraix"),
+    };
+    let _res = instantiate(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    let asset_info = AssetInfo::NativeToken {
+        denom: ORAI_DENOM.to_string(),
+    };
+
+    // 0.5% rebate on orai volume
+    let msg = ExecuteMsg::UpdateRebateRate {
+        asset_info: asset_info.clone(),
+        rebate_bps: 50,
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    );
+    assert_eq!(res, Err(StdError::generic_err("unauthorized")));
+    execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    // only router can record volume
+    let msg = ExecuteMsg::RecordVolume {
+        trader: Addr::unchecked("addr0000"),
+        asset_info: asset_info.clone(),
+        amount: Uint128::from(1000000u128),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    );
+    assert_eq!(res, Err(StdError::generic_err("unauthorized")));
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("router", &[]),
+        msg.clone(),
+    )
+    .unwrap();
+    execute(deps.as_mut(), mock_env(), mock_info("router", &[]), msg).unwrap();
+
+    // volume of asset without rebate rate is ignored
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("router", &[]),
+        ExecuteMsg::RecordVolume {
+            trader: Addr::unchecked("addr0000"),
+            asset_info: AssetInfo::Token {
+                contract_addr: Addr::unchecked("usdt"),
+            },
+            amount: Uint128::from(1000000u128),
+        },
+    )
+    .unwrap();
+
+    let res = query_pending_rebate(deps.as_ref(), Addr::unchecked("addr0000")).unwrap();
+    assert_eq!(res.amount, Uint128::from(10000u128));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::ClaimRebate {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "oraix".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "addr0000".to_string(),
+                amount: Uint128::from(10000u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+
+    // nothing left to claim
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::ClaimRebate {},
+    );
+    assert_eq!(res, Err(StdError::generic_err("no rebate to claim")));
+}
@@ -0,0 +1,337 @@
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError,
+    StdResult, Uint128,
+};
+
+use crate::state::{
+    add_pending_reward, read_config, read_pending_rewards, read_referrer, read_referrer_rate,
+    remove_pending_reward, store_config, store_referrer, store_referrer_rate, Config,
+};
+
+use cw20::Cw20ReceiveMsg;
+use oraiswap::asset::{Asset, AssetInfo, AssetRaw};
+use oraiswap::referral::{
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, PendingRewardsResponse,
+    QueryMsg, ReferralResponse, ReferralReward,
+};
+
+// 100% in basis points
+const BPS_DENOMINATOR: u128 = 10000;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    assert_rate(msg.default_rate_bps)?;
+    store_config(
+        deps.storage,
+        &Config {
+            owner: deps.api.addr_canonicalize(info.sender.as_str())?,
+            default_rate_bps: msg.default_rate_bps,
+            reporters: msg
+                .reporters
+                .iter()
+                .map(|addr| deps.api.addr_canonicalize(addr.as_str()))
+                .collect::<StdResult<_>>()?,
+        },
+    )?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, info, msg),
+        ExecuteMsg::UpdateConfig {
+            owner,
+            default_rate_bps,
+            reporters,
+        } => update_config(deps, info, owner, default_rate_bps, reporters),
+        ExecuteMsg::UpdateReferrerRate { referrer, rate_bps } => {
+            update_referrer_rate(deps, info, referrer, rate_bps)
+        }
+        ExecuteMsg::SetReferrer { referrer } => set_referrer(deps, info, referrer),
+        ExecuteMsg::RecordRewards { rewards } => {
+            if info.funds.len() != 1 {
+                return Err(StdError::generic_err(
+                    "rewards must be paid in one native coin",
+                ));
+            }
+            let reward_asset = Asset {
+                info: AssetInfo::NativeToken {
+                    denom: info.funds[0].denom.clone(),
+                },
+                amount: info.funds[0].amount,
+            };
+            record_rewards(deps, info.sender, reward_asset, rewards)
+        }
+        ExecuteMsg::ClaimReferralRewards { asset_info } => {
+            claim_referral_rewards(deps, info, asset_info)
+        }
+    }
+}
+
+pub fn receive_cw20(
+    deps: DepsMut,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> StdResult<Response> {
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::RecordRewards { rewards } => {
+            let reporter = deps.api.addr_validate(&cw20_msg.sender)?;
+            let reward_asset = Asset {
+                info: AssetInfo::Token {
+                    contract_addr: info.sender,
+                },
+                amount: cw20_msg.amount,
+            };
+            record_rewards(deps, reporter, reward_asset, rewards)
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+fn assert_rate(rate_bps: u64) -> StdResult<()> {
+    if rate_bps as u128 > BPS_DENOMINATOR {
+        return Err(StdError::generic_err("rate_bps must not exceed 10000"));
+    }
+    Ok(())
+}
+
+pub fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    owner: Option<Addr>,
+    default_rate_bps: Option<u64>,
+    reporters: Option<Vec<Addr>>,
+) -> StdResult<Response> {
+    let mut config: Config = read_config(deps.storage)?;
+    if config.owner != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if let Some(owner) = owner {
+        config.owner = deps.api.addr_canonicalize(owner.as_str())?;
+    }
+
+    if let Some(default_rate_bps) = default_rate_bps {
+        assert_rate(default_rate_bps)?;
+        config.default_rate_bps = default_rate_bps;
+    }
+
+    if let Some(reporters) = reporters {
+        config.reporters = reporters
+            .iter()
+            .map(|addr| deps.api.addr_canonicalize(addr.as_str()))
+            .collect::<StdResult<_>>()?;
+    }
+
+    store_config(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+pub fn update_referrer_rate(
+    deps: DepsMut,
+    info: MessageInfo,
+    referrer: Addr,
+    rate_bps: Option<u64>,
+) -> StdResult<Response> {
+    let config: Config = read_config(deps.storage)?;
+    if config.owner != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+    if let Some(rate_bps) = rate_bps {
+        assert_rate(rate_bps)?;
+    }
+
+    store_referrer_rate(
+        deps.storage,
+        &deps.api.addr_canonicalize(referrer.as_str())?,
+        rate_bps,
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "update_referrer_rate"),
+        ("referrer", referrer.as_str()),
+        (
+            "rate_bps",
+            &rate_bps.unwrap_or(config.default_rate_bps).to_string(),
+        ),
+    ]))
+}
+
+/// first-touch binding: the first referrer of a trader is kept for every venue
+pub fn set_referrer(deps: DepsMut, info: MessageInfo, referrer: Addr) -> StdResult<Response> {
+    let trader_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let referrer_raw = deps.api.addr_canonicalize(referrer.as_str())?;
+    if trader_raw == referrer_raw {
+        return Err(StdError::generic_err("cannot refer yourself"));
+    }
+    if read_referrer(deps.storage, &trader_raw)?.is_some() {
+        return Err(StdError::generic_err("referrer is already set"));
+    }
+
+    store_referrer(deps.storage, &trader_raw, &referrer_raw)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "set_referrer"),
+        ("trader", info.sender.as_str()),
+        ("referrer", referrer.as_str()),
+    ]))
+}
+
+/// Reporters pay the rewards of the referrers out of the commission of their traders, so every
+/// pending reward is backed by the paid asset
+pub fn record_rewards(
+    deps: DepsMut,
+    reporter: Addr,
+    reward_asset: Asset,
+    rewards: Vec<ReferralReward>,
+) -> StdResult<Response> {
+    let config: Config = read_config(deps.storage)?;
+    if !config
+        .reporters
+        .contains(&deps.api.addr_canonicalize(reporter.as_str())?)
+    {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let total_rewards = rewards.iter().try_fold(Uint128::zero(), |total, reward| {
+        total.checked_add(reward.amount)
+    })?;
+    if total_rewards != reward_asset.amount {
+        return Err(StdError::generic_err(format!(
+            "paid amount {} does not match the total rewards {}",
+            reward_asset.amount, total_rewards
+        )));
+    }
+
+    let reward_info = reward_asset.info.to_raw(deps.api)?;
+    for reward in rewards.iter().filter(|reward| !reward.amount.is_zero()) {
+        add_pending_reward(
+            deps.storage,
+            &deps.api.addr_canonicalize(reward.referrer.as_str())?,
+            &AssetRaw {
+                info: reward_info.clone(),
+                amount: reward.amount,
+            },
+        )?;
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "record_rewards"),
+        ("reporter", reporter.as_str()),
+        ("rewards", &reward_asset.to_string()),
+    ]))
+}
+
+/// each asset is removed and transferred on its own, so a referrer can still claim the other
+/// assets when the transfer of one fails
+pub fn claim_referral_rewards(
+    deps: DepsMut,
+    info: MessageInfo,
+    asset_info: Option<AssetInfo>,
+) -> StdResult<Response> {
+    let referrer_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let asset_info_raw = asset_info
+        .map(|asset_info| asset_info.to_raw(deps.api))
+        .transpose()?;
+    let rewards = read_pending_rewards(deps.storage, &referrer_raw)?
+        .into_iter()
+        .filter(|reward| {
+            asset_info_raw
+                .as_ref()
+                .map_or(true, |asset_info_raw| reward.info.eq(asset_info_raw))
+        })
+        .collect::<Vec<AssetRaw>>();
+    if rewards.is_empty() {
+        return Err(StdError::generic_err("no referral rewards to claim"));
+    }
+
+    let mut messages = vec![];
+    let mut claimed = vec![];
+    for reward in rewards {
+        remove_pending_reward(deps.storage, &referrer_raw, &reward.info);
+        let reward = reward.to_normal(deps.api)?;
+        messages.push(reward.into_msg(None, &deps.querier, info.sender.clone())?);
+        claimed.push(reward.to_string());
+    }
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "claim_referral_rewards"),
+        ("referrer", info.sender.as_str()),
+        ("rewards", &claimed.join(",")),
+    ]))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Referral { trader } => to_binary(&query_referral(deps, trader)?),
+        QueryMsg::PendingRewards { referrer } => to_binary(&query_pending_rewards(deps, referrer)?),
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let state = read_config(deps.storage)?;
+    let resp = ConfigResponse {
+        owner: deps.api.addr_humanize(&state.owner)?,
+        default_rate_bps: state.default_rate_bps,
+        reporters: state
+            .reporters
+            .iter()
+            .map(|addr| deps.api.addr_humanize(addr))
+            .collect::<StdResult<_>>()?,
+    };
+
+    Ok(resp)
+}
+
+pub fn query_referral(deps: Deps, trader: Addr) -> StdResult<ReferralResponse> {
+    let config = read_config(deps.storage)?;
+    let referrer_raw = read_referrer(deps.storage, &deps.api.addr_canonicalize(trader.as_str())?)?;
+    let (referrer, rate_bps) = match referrer_raw {
+        Some(referrer_raw) => (
+            Some(deps.api.addr_humanize(&referrer_raw)?),
+            read_referrer_rate(deps.storage, &config, &referrer_raw)?,
+        ),
+        None => (None, 0),
+    };
+
+    Ok(ReferralResponse {
+        trader,
+        referrer,
+        rate_bps,
+    })
+}
+
+pub fn query_pending_rewards(deps: Deps, referrer: Addr) -> StdResult<PendingRewardsResponse> {
+    let config = read_config(deps.storage)?;
+    let referrer_raw = deps.api.addr_canonicalize(referrer.as_str())?;
+    let rewards = read_pending_rewards(deps.storage, &referrer_raw)?
+        .iter()
+        .map(|reward| reward.to_normal(deps.api))
+        .collect::<StdResult<_>>()?;
+
+    Ok(PendingRewardsResponse {
+        rate_bps: read_referrer_rate(deps.storage, &config, &referrer_raw)?,
+        referrer,
+        rewards,
+    })
+}
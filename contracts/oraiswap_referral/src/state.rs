@@ -0,0 +1,106 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{CanonicalAddr, Order, StdResult, Storage};
+use cosmwasm_storage::{singleton, singleton_read, Bucket, ReadonlyBucket};
+use oraiswap::asset::{AssetInfoRaw, AssetRaw};
+
+static KEY_CONFIG: &[u8] = b"config";
+static PREFIX_REFERRER: &[u8] = b"referrer";
+static PREFIX_REFERRER_RATE: &[u8] = b"referrer_rate";
+static PREFIX_PENDING_REWARD: &[u8] = b"pending_reward";
+
+#[cw_serde]
+pub struct Config {
+    pub owner: CanonicalAddr,
+    pub default_rate_bps: u64,
+    pub reporters: Vec<CanonicalAddr>,
+}
+
+pub fn store_config(storage: &mut dyn Storage, config: &Config) -> StdResult<()> {
+    singleton(storage, KEY_CONFIG).save(config)
+}
+
+pub fn read_config(storage: &dyn Storage) -> StdResult<Config> {
+    singleton_read(storage, KEY_CONFIG).load()
+}
+
+pub fn store_referrer(
+    storage: &mut dyn Storage,
+    trader: &CanonicalAddr,
+    referrer: &CanonicalAddr,
+) -> StdResult<()> {
+    Bucket::new(storage, PREFIX_REFERRER).save(trader.as_slice(), referrer)
+}
+
+pub fn read_referrer(
+    storage: &dyn Storage,
+    trader: &CanonicalAddr,
+) -> StdResult<Option<CanonicalAddr>> {
+    ReadonlyBucket::new(storage, PREFIX_REFERRER).may_load(trader.as_slice())
+}
+
+pub fn store_referrer_rate(
+    storage: &mut dyn Storage,
+    referrer: &CanonicalAddr,
+    rate_bps: Option<u64>,
+) -> StdResult<()> {
+    let mut bucket: Bucket<u64> = Bucket::new(storage, PREFIX_REFERRER_RATE);
+    match rate_bps {
+        Some(rate_bps) => bucket.save(referrer.as_slice(), &rate_bps),
+        None => {
+            bucket.remove(referrer.as_slice());
+            Ok(())
+        }
+    }
+}
+
+/// rate of the referrer, the default rate when it has none
+pub fn read_referrer_rate(
+    storage: &dyn Storage,
+    config: &Config,
+    referrer: &CanonicalAddr,
+) -> StdResult<u64> {
+    ReadonlyBucket::new(storage, PREFIX_REFERRER_RATE)
+        .may_load(referrer.as_slice())
+        .map(|rate_bps| rate_bps.unwrap_or(config.default_rate_bps))
+}
+
+/// pending rewards of a referrer are keyed by the asset, so each asset is paid in kind
+pub fn add_pending_reward(
+    storage: &mut dyn Storage,
+    referrer: &CanonicalAddr,
+    reward: &AssetRaw,
+) -> StdResult<()> {
+    let mut bucket: Bucket<AssetRaw> =
+        Bucket::multilevel(storage, &[PREFIX_PENDING_REWARD, referrer.as_slice()]);
+    let key = reward.info.as_bytes();
+    let amount = bucket
+        .may_load(key)?
+        .map(|pending| pending.amount)
+        .unwrap_or_default();
+    bucket.save(
+        key,
+        &AssetRaw {
+            info: reward.info.clone(),
+            amount: amount.checked_add(reward.amount)?,
+        },
+    )
+}
+
+pub fn read_pending_rewards(
+    storage: &dyn Storage,
+    referrer: &CanonicalAddr,
+) -> StdResult<Vec<AssetRaw>> {
+    ReadonlyBucket::multilevel(storage, &[PREFIX_PENDING_REWARD, referrer.as_slice()])
+        .range(None, None, Order::Ascending)
+        .map(|item| item.map(|(_, reward)| reward))
+        .collect()
+}
+
+pub fn remove_pending_reward(
+    storage: &mut dyn Storage,
+    referrer: &CanonicalAddr,
+    asset_info: &AssetInfoRaw,
+) {
+    Bucket::<AssetRaw>::multilevel(storage, &[PREFIX_PENDING_REWARD, referrer.as_slice()])
+        .remove(asset_info.as_bytes());
+}
@@ -0,0 +1,244 @@
+use crate::contract::{execute, instantiate, query_config, query_pending_rewards, query_referral};
+use cosmwasm_std::{
+    coin,
+    testing::{mock_dependencies, mock_env, mock_info},
+    to_binary, Addr, BankMsg, CosmosMsg, StdError, SubMsg, Uint128, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use oraiswap::asset::{Asset, AssetInfo, ORAI_DENOM};
+use oraiswap::referral::{ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, ReferralReward};
+
+#[test]
+fn proper_initialization() {
+    let mut deps = mock_dependencies();
+
+    let msg = InstantiateMsg {
+        default_rate_bps: 100,
+        reporters: vec![Addr::unchecked("router"), Addr::unchecked("limit_order")],
+    };
+
+    // we can just call .unwrap() to assert this was a success
+    let _res = instantiate(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    // it worked, let's query the state
+    let config = query_config(deps.as_ref()).unwrap();
+
+    assert_eq!(
+        config,
+        ConfigResponse {
+            owner: Addr::unchecked("owner"),
+            default_rate_bps: 100,
+            reporters: vec![Addr::unchecked("router"), Addr::unchecked("limit_order")],
+        }
+    );
+}
+
+#[test]
+fn first_touch_referral() {
+    let mut deps = mock_dependencies();
+
+    let msg = InstantiateMsg {
+        default_rate_bps: 100,
+        reporters: vec![Addr::unchecked("router")],
+    };
+    let _res = instantiate(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::SetReferrer {
+            referrer: Addr::unchecked("addr0000"),
+        },
+    );
+    assert_eq!(res, Err(StdError::generic_err("cannot refer yourself")));
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::SetReferrer {
+            referrer: Addr::unchecked("referrer"),
+        },
+    )
+    .unwrap();
+
+    // the first referrer is kept
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::SetReferrer {
+            referrer: Addr::unchecked("referrer2"),
+        },
+    );
+    assert_eq!(res, Err(StdError::generic_err("referrer is already set")));
+
+    let res = query_referral(deps.as_ref(), Addr::unchecked("addr0000")).unwrap();
+    assert_eq!(res.referrer, Some(Addr::unchecked("referrer")));
+    assert_eq!(res.rate_bps, 100);
+
+    // owner sets a custom rate for the referrer
+    let msg = ExecuteMsg::UpdateReferrerRate {
+        referrer: Addr::unchecked("referrer"),
+        rate_bps: Some(250),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    );
+    assert_eq!(res, Err(StdError::generic_err("unauthorized")));
+    execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    let res = query_referral(deps.as_ref(), Addr::unchecked("addr0000")).unwrap();
+    assert_eq!(res.rate_bps, 250);
+
+    let res = query_referral(deps.as_ref(), Addr::unchecked("addr0001")).unwrap();
+    assert_eq!(res.referrer, None);
+    assert_eq!(res.rate_bps, 0);
+}
+
+#[test]
+fn record_rewards_and_claim_referral_rewards() {
+    let mut deps = mock_dependencies();
+
+    let msg = InstantiateMsg {
+        default_rate_bps: 100,
+        reporters: vec![Addr::unchecked("limit_order")],
+    };
+    let _res = instantiate(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    let rewards = vec![
+        ReferralReward {
+            referrer: Addr::unchecked("referrer"),
+            amount: Uint128::from(150u128),
+        },
+        ReferralReward {
+            referrer: Addr::unchecked("referrer2"),
+            amount: Uint128::from(50u128),
+        },
+    ];
+
+    // only reporters can record rewards
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[coin(200u128, ORAI_DENOM)]),
+        ExecuteMsg::RecordRewards {
+            rewards: rewards.clone(),
+        },
+    );
+    assert_eq!(res, Err(StdError::generic_err("unauthorized")));
+
+    // the rewards must be paid along
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("limit_order", &[coin(100u128, ORAI_DENOM)]),
+        ExecuteMsg::RecordRewards {
+            rewards: rewards.clone(),
+        },
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "paid amount 100orai does not match the total rewards 200"
+        ))
+    );
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("limit_order", &[coin(200u128, ORAI_DENOM)]),
+        ExecuteMsg::RecordRewards { rewards },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("usdt", &[]),
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: "limit_order".to_string(),
+            amount: Uint128::from(300u128),
+            msg: to_binary(&Cw20HookMsg::RecordRewards {
+                rewards: vec![ReferralReward {
+                    referrer: Addr::unchecked("referrer"),
+                    amount: Uint128::from(300u128),
+                }],
+            })
+            .unwrap(),
+        }),
+    )
+    .unwrap();
+
+    let orai_reward = Asset {
+        info: AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        amount: Uint128::from(150u128),
+    };
+    let usdt_reward = Asset {
+        info: AssetInfo::Token {
+            contract_addr: Addr::unchecked("usdt"),
+        },
+        amount: Uint128::from(300u128),
+    };
+    let res = query_pending_rewards(deps.as_ref(), Addr::unchecked("referrer")).unwrap();
+    assert_eq!(res.rewards.len(), 2);
+    assert!(res.rewards.contains(&orai_reward));
+    assert!(res.rewards.contains(&usdt_reward));
+
+    // the usdt reward is claimed on its own
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("referrer", &[]),
+        ExecuteMsg::ClaimReferralRewards {
+            asset_info: Some(usdt_reward.info.clone()),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "usdt".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "referrer".to_string(),
+                amount: Uint128::from(300u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+    let res = query_pending_rewards(deps.as_ref(), Addr::unchecked("referrer")).unwrap();
+    assert_eq!(res.rewards, vec![orai_reward]);
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("referrer", &[]),
+        ExecuteMsg::ClaimReferralRewards { asset_info: None },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "referrer".to_string(),
+            amount: vec![coin(150u128, ORAI_DENOM)],
+        }))]
+    );
+
+    // nothing left to claim
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("referrer", &[]),
+        ExecuteMsg::ClaimReferralRewards { asset_info: None },
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err("no referral rewards to claim"))
+    );
+}
@@ -0,0 +1,233 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
+};
+
+use crate::state::{
+    read_asset_metadata, read_asset_metadata_list, read_config, read_contract, read_contracts,
+    remove_asset_metadata, store_asset_metadata, store_config, store_contract, AssetMetadataInfo,
+    Config, ContractInfo,
+};
+
+use oraiswap::asset::AssetInfo;
+use oraiswap::registry::{
+    AssetMetadata, AssetMetadataListResponse, ConfigResponse, ContractKey, ContractResponse,
+    ContractsResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
+};
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    mut deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    store_config(
+        deps.storage,
+        &Config {
+            owner: deps.api.addr_canonicalize(info.sender.as_str())?,
+        },
+    )?;
+
+    for contract in msg.contracts {
+        set_contract(deps.branch(), contract.key, contract.address)?;
+    }
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::UpdateOwner { owner } => update_owner(deps, info, owner),
+        ExecuteMsg::UpdateContract { key, address } => update_contract(deps, info, key, address),
+        ExecuteMsg::UpdateAssetMetadata { metadata } => update_asset_metadata(deps, info, metadata),
+        ExecuteMsg::RemoveAssetMetadata { asset_info } => {
+            remove_asset_metadata_info(deps, info, asset_info)
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+fn assert_owner(deps: Deps, info: &MessageInfo) -> StdResult<Config> {
+    let config: Config = read_config(deps.storage)?;
+    if config.owner != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+    Ok(config)
+}
+
+pub fn update_owner(deps: DepsMut, info: MessageInfo, owner: Addr) -> StdResult<Response> {
+    let mut config = assert_owner(deps.as_ref(), &info)?;
+    config.owner = deps.api.addr_canonicalize(owner.as_str())?;
+    store_config(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![("action", "update_owner"), ("owner", owner.as_str())]))
+}
+
+pub fn update_contract(
+    deps: DepsMut,
+    info: MessageInfo,
+    key: ContractKey,
+    address: Addr,
+) -> StdResult<Response> {
+    assert_owner(deps.as_ref(), &info)?;
+    let version = set_contract(deps, key, address.clone())?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "update_contract"),
+        ("key", key.as_str()),
+        ("address", address.as_str()),
+        ("version", &version.to_string()),
+    ]))
+}
+
+pub fn update_asset_metadata(
+    deps: DepsMut,
+    info: MessageInfo,
+    metadata: Vec<AssetMetadata>,
+) -> StdResult<Response> {
+    assert_owner(deps.as_ref(), &info)?;
+
+    for asset_metadata in metadata.iter() {
+        store_asset_metadata(
+            deps.storage,
+            &AssetMetadataInfo {
+                asset_info: asset_metadata.asset_info.to_raw(deps.api)?,
+                decimals: asset_metadata.decimals,
+                symbol: asset_metadata.symbol.clone(),
+            },
+        )?;
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "update_asset_metadata"),
+        ("count", &metadata.len().to_string()),
+    ]))
+}
+
+pub fn remove_asset_metadata_info(
+    deps: DepsMut,
+    info: MessageInfo,
+    asset_info: AssetInfo,
+) -> StdResult<Response> {
+    assert_owner(deps.as_ref(), &info)?;
+    remove_asset_metadata(deps.storage, &asset_info.to_vec(deps.api)?);
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "remove_asset_metadata"),
+        ("asset_info", &asset_info.to_string()),
+    ]))
+}
+
+/// store the new address of the contract under the next version
+fn set_contract(deps: DepsMut, key: ContractKey, address: Addr) -> StdResult<u64> {
+    let version = read_contract(deps.storage, key)?
+        .map(|contract_info| contract_info.version)
+        .unwrap_or_default()
+        + 1;
+
+    store_contract(
+        deps.storage,
+        &ContractInfo {
+            key,
+            address: deps.api.addr_canonicalize(address.as_str())?,
+            version,
+        },
+    )?;
+
+    Ok(version)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Contract { key } => to_binary(&query_contract(deps, key)?),
+        QueryMsg::Contracts {} => to_binary(&query_contracts(deps)?),
+        QueryMsg::AssetMetadata { asset_info } => {
+            to_binary(&query_asset_metadata(deps, asset_info)?)
+        }
+        QueryMsg::AssetMetadataList { start_after, limit } => {
+            to_binary(&query_asset_metadata_list(deps, start_after, limit)?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let state = read_config(deps.storage)?;
+    let resp = ConfigResponse {
+        owner: deps.api.addr_humanize(&state.owner)?,
+    };
+
+    Ok(resp)
+}
+
+pub fn query_contract(deps: Deps, key: ContractKey) -> StdResult<ContractResponse> {
+    match read_contract(deps.storage, key)? {
+        Some(contract_info) => to_response(deps, contract_info),
+        None => Err(StdError::not_found(format!("contract {}", key.as_str()))),
+    }
+}
+
+pub fn query_contracts(deps: Deps) -> StdResult<ContractsResponse> {
+    let contracts = read_contracts(deps.storage)?
+        .into_iter()
+        .map(|contract_info| to_response(deps, contract_info))
+        .collect::<StdResult<Vec<ContractResponse>>>()?;
+
+    Ok(ContractsResponse { contracts })
+}
+
+pub fn query_asset_metadata(deps: Deps, asset_info: AssetInfo) -> StdResult<AssetMetadata> {
+    match read_asset_metadata(deps.storage, &asset_info.to_vec(deps.api)?)? {
+        Some(metadata) => to_asset_metadata(deps, metadata),
+        None => Err(StdError::not_found(format!(
+            "asset metadata {}",
+            asset_info
+        ))),
+    }
+}
+
+pub fn query_asset_metadata_list(
+    deps: Deps,
+    start_after: Option<AssetInfo>,
+    limit: Option<u32>,
+) -> StdResult<AssetMetadataListResponse> {
+    let start_after = match start_after {
+        Some(start_after) => Some(start_after.to_vec(deps.api)?),
+        None => None,
+    };
+    let metadata = read_asset_metadata_list(deps.storage, start_after, limit)?
+        .into_iter()
+        .map(|metadata| to_asset_metadata(deps, metadata))
+        .collect::<StdResult<Vec<AssetMetadata>>>()?;
+
+    Ok(AssetMetadataListResponse { metadata })
+}
+
+fn to_asset_metadata(deps: Deps, metadata: AssetMetadataInfo) -> StdResult<AssetMetadata> {
+    Ok(AssetMetadata {
+        asset_info: metadata.asset_info.to_normal(deps.api)?,
+        decimals: metadata.decimals,
+        symbol: metadata.symbol,
+    })
+}
+
+fn to_response(deps: Deps, contract_info: ContractInfo) -> StdResult<ContractResponse> {
+    Ok(ContractResponse {
+        key: contract_info.key,
+        address: deps.api.addr_humanize(&contract_info.address)?,
+        version: contract_info.version,
+    })
+}
@@ -0,0 +1,88 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{CanonicalAddr, Order, StdResult, Storage};
+use cosmwasm_storage::{singleton, singleton_read, Bucket, ReadonlyBucket};
+use oraiswap::asset::AssetInfoRaw;
+use oraiswap::querier::calc_range_start;
+use oraiswap::registry::ContractKey;
+
+static KEY_CONFIG: &[u8] = b"config";
+static PREFIX_CONTRACT: &[u8] = b"contract";
+static PREFIX_ASSET_METADATA: &[u8] = b"asset_metadata";
+
+// settings for pagination
+const MAX_LIMIT: u32 = 100;
+const DEFAULT_LIMIT: u32 = 30;
+
+#[cw_serde]
+pub struct Config {
+    pub owner: CanonicalAddr,
+}
+
+#[cw_serde]
+pub struct ContractInfo {
+    pub key: ContractKey,
+    pub address: CanonicalAddr,
+    pub version: u64,
+}
+
+#[cw_serde]
+pub struct AssetMetadataInfo {
+    pub asset_info: AssetInfoRaw,
+    pub decimals: u8,
+    pub symbol: String,
+}
+
+pub fn store_config(storage: &mut dyn Storage, config: &Config) -> StdResult<()> {
+    singleton(storage, KEY_CONFIG).save(config)
+}
+
+pub fn read_config(storage: &dyn Storage) -> StdResult<Config> {
+    singleton_read(storage, KEY_CONFIG).load()
+}
+
+pub fn store_contract(storage: &mut dyn Storage, contract_info: &ContractInfo) -> StdResult<()> {
+    Bucket::new(storage, PREFIX_CONTRACT).save(contract_info.key.as_str().as_bytes(), contract_info)
+}
+
+pub fn read_contract(storage: &dyn Storage, key: ContractKey) -> StdResult<Option<ContractInfo>> {
+    ReadonlyBucket::new(storage, PREFIX_CONTRACT).may_load(key.as_str().as_bytes())
+}
+
+pub fn read_contracts(storage: &dyn Storage) -> StdResult<Vec<ContractInfo>> {
+    ReadonlyBucket::new(storage, PREFIX_CONTRACT)
+        .range(None, None, Order::Ascending)
+        .map(|item| item.map(|(_, contract_info)| contract_info))
+        .collect()
+}
+
+pub fn store_asset_metadata(
+    storage: &mut dyn Storage,
+    metadata: &AssetMetadataInfo,
+) -> StdResult<()> {
+    Bucket::new(storage, PREFIX_ASSET_METADATA).save(metadata.asset_info.as_bytes(), metadata)
+}
+
+pub fn remove_asset_metadata(storage: &mut dyn Storage, asset_key: &[u8]) {
+    Bucket::<AssetMetadataInfo>::new(storage, PREFIX_ASSET_METADATA).remove(asset_key)
+}
+
+pub fn read_asset_metadata(
+    storage: &dyn Storage,
+    asset_key: &[u8],
+) -> StdResult<Option<AssetMetadataInfo>> {
+    ReadonlyBucket::new(storage, PREFIX_ASSET_METADATA).may_load(asset_key)
+}
+
+pub fn read_asset_metadata_list(
+    storage: &dyn Storage,
+    start_after: Option<Vec<u8>>,
+    limit: Option<u32>,
+) -> StdResult<Vec<AssetMetadataInfo>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = calc_range_start(start_after);
+    ReadonlyBucket::new(storage, PREFIX_ASSET_METADATA)
+        .range(start.as_deref(), None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, metadata)| metadata))
+        .collect()
+}
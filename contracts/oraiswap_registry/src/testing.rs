@@ -0,0 +1,205 @@
+use crate::contract::{
+    execute, instantiate, query_asset_metadata, query_asset_metadata_list, query_config,
+    query_contract, query_contracts,
+};
+use cosmwasm_std::{
+    testing::{mock_dependencies, mock_env, mock_info},
+    Addr, StdError,
+};
+use oraiswap::asset::AssetInfo;
+use oraiswap::registry::{
+    AssetMetadata, ConfigResponse, ContractKey, ContractResponse, ExecuteMsg, InstantiateMsg,
+    RegistryContract,
+};
+
+#[test]
+fn proper_initialization() {
+    let mut deps = mock_dependencies();
+
+    let msg = InstantiateMsg {
+        contracts: vec![RegistryContract {
+            key: ContractKey::Factory,
+            address: Addr::unchecked("factory"),
+        }],
+    };
+
+    // we can just call .unwrap() to assert this was a success
+    let _res = instantiate(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    // it worked, let's query the state
+    let config = query_config(deps.as_ref()).unwrap();
+    assert_eq!(
+        config,
+        ConfigResponse {
+            owner: Addr::unchecked("owner"),
+        }
+    );
+
+    let contract = query_contract(deps.as_ref(), ContractKey::Factory).unwrap();
+    assert_eq!(
+        contract,
+        ContractResponse {
+            key: ContractKey::Factory,
+            address: Addr::unchecked("factory"),
+            version: 1,
+        }
+    );
+
+    // not registered yet
+    let err = query_contract(deps.as_ref(), ContractKey::Router).unwrap_err();
+    assert_eq!(err, StdError::not_found("contract router"));
+}
+
+#[test]
+fn update_contract() {
+    let mut deps = mock_dependencies();
+
+    let msg = InstantiateMsg {
+        contracts: vec![RegistryContract {
+            key: ContractKey::Factory,
+            address: Addr::unchecked("factory"),
+        }],
+    };
+    let _res = instantiate(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    // only owner can update contracts
+    let msg = ExecuteMsg::UpdateContract {
+        key: ContractKey::Factory,
+        address: Addr::unchecked("factory_v2"),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    );
+    assert_eq!(res, Err(StdError::generic_err("unauthorized")));
+
+    // each update increases the version of the contract
+    execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateContract {
+            key: ContractKey::Router,
+            address: Addr::unchecked("router"),
+        },
+    )
+    .unwrap();
+
+    let contracts = query_contracts(deps.as_ref()).unwrap().contracts;
+    assert_eq!(
+        contracts,
+        vec![
+            ContractResponse {
+                key: ContractKey::Factory,
+                address: Addr::unchecked("factory_v2"),
+                version: 2,
+            },
+            ContractResponse {
+                key: ContractKey::Router,
+                address: Addr::unchecked("router"),
+                version: 1,
+            },
+        ]
+    );
+
+    // hand over the registry
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateOwner {
+            owner: Addr::unchecked("new_owner"),
+        },
+    )
+    .unwrap();
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateOwner {
+            owner: Addr::unchecked("owner"),
+        },
+    );
+    assert_eq!(res, Err(StdError::generic_err("unauthorized")));
+    assert_eq!(
+        query_config(deps.as_ref()).unwrap().owner,
+        Addr::unchecked("new_owner")
+    );
+}
+
+#[test]
+fn update_asset_metadata() {
+    let mut deps = mock_dependencies();
+
+    let msg = InstantiateMsg { contracts: vec![] };
+    let _res = instantiate(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    let orai_metadata = AssetMetadata {
+        asset_info: AssetInfo::NativeToken {
+            denom: "orai".to_string(),
+        },
+        decimals: 6,
+        symbol: "ORAI".to_string(),
+    };
+    let atom_metadata = AssetMetadata {
+        asset_info: AssetInfo::NativeToken {
+            denom: "atom".to_string(),
+        },
+        decimals: 6,
+        symbol: "ATOM".to_string(),
+    };
+
+    // only owner can update metadata
+    let msg = ExecuteMsg::UpdateAssetMetadata {
+        metadata: vec![orai_metadata.clone(), atom_metadata.clone()],
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    );
+    assert_eq!(res, Err(StdError::generic_err("unauthorized")));
+    execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    assert_eq!(
+        query_asset_metadata(deps.as_ref(), orai_metadata.asset_info.clone()).unwrap(),
+        orai_metadata
+    );
+    assert_eq!(
+        query_asset_metadata_list(deps.as_ref(), None, None)
+            .unwrap()
+            .metadata,
+        vec![atom_metadata.clone(), orai_metadata.clone()]
+    );
+    assert_eq!(
+        query_asset_metadata_list(deps.as_ref(), Some(atom_metadata.asset_info.clone()), None)
+            .unwrap()
+            .metadata,
+        vec![orai_metadata.clone()]
+    );
+
+    // remove the metadata, queries fall back to not found
+    let msg = ExecuteMsg::RemoveAssetMetadata {
+        asset_info: orai_metadata.asset_info.clone(),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    );
+    assert_eq!(res, Err(StdError::generic_err("unauthorized")));
+    execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    assert!(query_asset_metadata(deps.as_ref(), orai_metadata.asset_info).is_err());
+    assert_eq!(
+        query_asset_metadata_list(deps.as_ref(), None, None)
+            .unwrap()
+            .metadata,
+        vec![atom_metadata]
+    );
+}
@@ -1,20 +1,25 @@
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, QuerierWrapper, Response,
-    StdError, StdResult, Uint128, WasmMsg,
+    from_binary, to_binary, Addr, Binary, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo,
+    QuerierWrapper, Response, StdError, StdResult, Uint128, WasmMsg,
 };
+use cw20::Cw20ReceiveMsg;
 
 use crate::state::{
-    read_config, read_last_distributed, store_config, store_last_distributed, Config,
+    read_config, read_fee_route, read_fee_swap, read_last_distributed, read_orderbook_incentive,
+    store_config, store_fee_route, store_fee_swap, store_last_distributed,
+    store_orderbook_incentive, Config, FeeSwapRaw, OrderBookIncentiveRaw,
 };
 
 use oraiswap::staking::QueryMsg as StakingQueryMsg;
 use oraiswap::staking::{ExecuteMsg as StakingExecuteMsg, RewardsPerSecResponse};
 
 use oraiswap::rewarder::{
-    ConfigResponse, DistributionInfoResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
-    RewardAmountPerSecondResponse,
+    ConfigResponse, Cw20HookMsg, DistributionInfoResponse, ExecuteMsg, FeeRouteResponse, FeeSwap,
+    FeeSwapResponse, InstantiateMsg, MigrateMsg, OrderBookIncentive, OrderBookIncentiveResponse,
+    QueryMsg, RewardAmountPerSecondResponse,
 };
+use oraiswap::router::{RouterController, SwapHopSimulation, SwapOperation};
 
 use oraiswap::asset::{Asset, AssetInfo};
 
@@ -46,6 +51,7 @@ pub fn instantiate(
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
     match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, info, msg),
         ExecuteMsg::UpdateConfig {
             owner,
             staking_contract,
@@ -53,6 +59,47 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
         } => update_config(deps, info, owner, staking_contract, distribution_interval),
 
         ExecuteMsg::Distribute { asset_infos } => distribute(deps, env, asset_infos),
+        ExecuteMsg::UpdateOrderBookIncentive { incentive } => {
+            update_orderbook_incentive(deps, info, incentive)
+        }
+        ExecuteMsg::DistributeOrderBookIncentive { recipient, points } => {
+            distribute_orderbook_incentive(deps, info, recipient, points)
+        }
+        ExecuteMsg::UpdateFeeSwap { fee_swap } => update_fee_swap(deps, info, fee_swap),
+        ExecuteMsg::UpdateFeeRoute {
+            asset_info,
+            operations,
+        } => update_fee_route(deps, info, asset_info, operations),
+        ExecuteMsg::DepositFees {} => {
+            let fees = info
+                .funds
+                .into_iter()
+                .map(|coin| Asset {
+                    info: AssetInfo::NativeToken { denom: coin.denom },
+                    amount: coin.amount,
+                })
+                .collect();
+            deposit_fees(deps, fees)
+        }
+    }
+}
+
+pub fn receive_cw20(
+    deps: DepsMut,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> StdResult<Response> {
+    match from_binary(&cw20_msg.msg) {
+        Ok(Cw20HookMsg::DepositFees {}) => deposit_fees(
+            deps,
+            vec![Asset {
+                info: AssetInfo::Token {
+                    contract_addr: info.sender,
+                },
+                amount: cw20_msg.amount,
+            }],
+        ),
+        Err(_) => Err(StdError::generic_err("invalid cw20 hook message")),
     }
 }
 
@@ -135,6 +182,213 @@ pub fn distribute(deps: DepsMut, env: Env, asset_infos: Vec<AssetInfo>) -> StdRe
         .add_attribute("action", "distribute"))
 }
 
+pub fn update_orderbook_incentive(
+    deps: DepsMut,
+    info: MessageInfo,
+    incentive: Option<OrderBookIncentive>,
+) -> StdResult<Response> {
+    let config: Config = read_config(deps.storage)?;
+    if config.owner != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let incentive = match incentive {
+        Some(incentive) => Some(OrderBookIncentiveRaw {
+            orderbook_contract: deps
+                .api
+                .addr_canonicalize(incentive.orderbook_contract.as_str())?,
+            reward_asset: incentive.reward_asset.to_raw(deps.api)?,
+            reward_per_point: incentive.reward_per_point,
+        }),
+        None => None,
+    };
+    store_orderbook_incentive(deps.storage, incentive.as_ref())?;
+
+    Ok(Response::new().add_attribute("action", "update_orderbook_incentive"))
+}
+
+/// Only the order book contract can pay the points it has taken from the maker
+pub fn distribute_orderbook_incentive(
+    deps: DepsMut,
+    info: MessageInfo,
+    recipient: Addr,
+    points: Uint128,
+) -> StdResult<Response> {
+    let incentive = read_orderbook_incentive(deps.storage)?
+        .ok_or_else(|| StdError::generic_err("order book incentive is not set"))?;
+    if incentive.orderbook_contract != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let reward = Asset {
+        info: incentive.reward_asset.to_normal(deps.api)?,
+        amount: points * incentive.reward_per_point,
+    };
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    if !reward.amount.is_zero() {
+        messages.push(reward.into_msg(None, &deps.querier, recipient.clone())?);
+    }
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "distribute_orderbook_incentive"),
+        ("recipient", recipient.as_str()),
+        ("points", &points.to_string()),
+        ("reward", &reward.to_string()),
+    ]))
+}
+
+pub fn update_fee_swap(
+    deps: DepsMut,
+    info: MessageInfo,
+    fee_swap: Option<FeeSwap>,
+) -> StdResult<Response> {
+    let config: Config = read_config(deps.storage)?;
+    if config.owner != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let fee_swap = match fee_swap {
+        Some(fee_swap) => {
+            if fee_swap.reward_assets.is_empty() {
+                return Err(StdError::generic_err("reward assets must not be empty"));
+            }
+            if fee_swap.max_spread > Decimal::one() {
+                return Err(StdError::generic_err("max spread must not be over 1"));
+            }
+            Some(FeeSwapRaw {
+                router: deps.api.addr_canonicalize(fee_swap.router.as_str())?,
+                reward_assets: fee_swap
+                    .reward_assets
+                    .iter()
+                    .map(|asset_info| asset_info.to_raw(deps.api))
+                    .collect::<StdResult<_>>()?,
+                max_spread: fee_swap.max_spread,
+            })
+        }
+        None => None,
+    };
+    store_fee_swap(deps.storage, fee_swap.as_ref())?;
+
+    Ok(Response::new().add_attribute("action", "update_fee_swap"))
+}
+
+/// the route must start with the fee asset, chain its operations and end with a reward asset
+pub fn update_fee_route(
+    deps: DepsMut,
+    info: MessageInfo,
+    asset_info: AssetInfo,
+    operations: Option<Vec<SwapOperation>>,
+) -> StdResult<Response> {
+    let config: Config = read_config(deps.storage)?;
+    if config.owner != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if let Some(operations) = &operations {
+        let fee_swap = read_fee_swap(deps.storage)?
+            .ok_or_else(|| StdError::generic_err("fee swap is not set"))?;
+
+        let mut offer_asset_info = asset_info.clone();
+        for operation in operations {
+            if !operation.get_offer_asset_info().eq(&offer_asset_info) {
+                return Err(StdError::generic_err(format!(
+                    "swap operation does not offer {}",
+                    offer_asset_info
+                )));
+            }
+            offer_asset_info = operation.get_target_asset_info();
+        }
+
+        let target_raw = offer_asset_info.to_raw(deps.api)?;
+        if operations.is_empty() || !fee_swap.reward_assets.iter().any(|r| r.eq(&target_raw)) {
+            return Err(StdError::generic_err(
+                "the route must end with a reward asset",
+            ));
+        }
+    }
+
+    store_fee_route(
+        deps.storage,
+        &asset_info.to_vec(deps.api)?,
+        operations.as_ref(),
+    )?;
+
+    Ok(Response::new().add_attributes([
+        ("action", "update_fee_route"),
+        ("asset_info", &asset_info.to_string()),
+    ]))
+}
+
+/// Swap the deposited fees into the reward assets by their routes, the returned assets are sent
+/// to the staking contract where they fund the distributed rewards
+pub fn deposit_fees(deps: DepsMut, fees: Vec<Asset>) -> StdResult<Response> {
+    let config: Config = read_config(deps.storage)?;
+    let fee_swap =
+        read_fee_swap(deps.storage)?.ok_or_else(|| StdError::generic_err("fee swap is not set"))?;
+    let staking_contract = deps.api.addr_humanize(&config.staking_contract)?;
+    let router = RouterController(deps.api.addr_humanize(&fee_swap.router)?.to_string());
+
+    let fees: Vec<Asset> = fees
+        .into_iter()
+        .filter(|fee| !fee.amount.is_zero())
+        .collect();
+    if fees.is_empty() {
+        return Err(StdError::generic_err("no fees deposited"));
+    }
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    for fee in fees.iter() {
+        // reward assets do not need to be swapped
+        let fee_raw = fee.info.to_raw(deps.api)?;
+        if fee_swap.reward_assets.iter().any(|r| r.eq(&fee_raw)) {
+            messages.push(fee.into_msg(None, &deps.querier, staking_contract.clone())?);
+            continue;
+        }
+
+        let operations = read_fee_route(deps.storage, &fee.info.to_vec(deps.api)?)?
+            .ok_or_else(|| StdError::generic_err(format!("no route to swap {}", fee.info)))?;
+        let simulation = router.simulate_swap(&deps.querier, fee.amount, operations.clone())?;
+        for hop in simulation.hops.iter() {
+            assert_max_spread(hop, fee_swap.max_spread)?;
+        }
+
+        messages.push(router.execute_operations(
+            fee.info.clone(),
+            fee.amount,
+            operations,
+            Some(simulation.amount),
+            Some(staking_contract.clone()),
+        )?);
+    }
+
+    Ok(Response::new().add_messages(messages).add_attributes([
+        ("action", "deposit_fees"),
+        (
+            "fees",
+            &fees
+                .iter()
+                .map(|fee| fee.to_string())
+                .collect::<Vec<String>>()
+                .join(","),
+        ),
+    ]))
+}
+
+// the spread is relative to the amount the operation returns at the pool price
+fn assert_max_spread(hop: &SwapHopSimulation, max_spread: Decimal) -> StdResult<()> {
+    let expected_return = hop.return_amount + hop.spread_amount + hop.commission_amount;
+    if !expected_return.is_zero()
+        && Decimal::from_ratio(hop.spread_amount, expected_return) > max_spread
+    {
+        return Err(StdError::generic_err(format!(
+            "spread of swapping to {} is over the max spread",
+            hop.operation.get_target_asset_info()
+        )));
+    }
+    Ok(())
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
@@ -145,6 +399,9 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::RewardAmountPerSec { asset_info } => {
             to_binary(&query_reward_amount_per_sec(deps, asset_info)?)
         }
+        QueryMsg::OrderBookIncentive {} => to_binary(&query_orderbook_incentive(deps)?),
+        QueryMsg::FeeSwap {} => to_binary(&query_fee_swap(deps)?),
+        QueryMsg::FeeRoute { asset_info } => to_binary(&query_fee_route(deps, asset_info)?),
     }
 }
 
@@ -184,6 +441,42 @@ pub fn query_reward_amount_per_sec(
     Ok(RewardAmountPerSecondResponse { reward_amount })
 }
 
+pub fn query_orderbook_incentive(deps: Deps) -> StdResult<OrderBookIncentiveResponse> {
+    let incentive = match read_orderbook_incentive(deps.storage)? {
+        Some(incentive) => Some(OrderBookIncentive {
+            orderbook_contract: deps.api.addr_humanize(&incentive.orderbook_contract)?,
+            reward_asset: incentive.reward_asset.to_normal(deps.api)?,
+            reward_per_point: incentive.reward_per_point,
+        }),
+        None => None,
+    };
+
+    Ok(OrderBookIncentiveResponse { incentive })
+}
+
+pub fn query_fee_swap(deps: Deps) -> StdResult<FeeSwapResponse> {
+    let fee_swap = match read_fee_swap(deps.storage)? {
+        Some(fee_swap) => Some(FeeSwap {
+            router: deps.api.addr_humanize(&fee_swap.router)?,
+            reward_assets: fee_swap
+                .reward_assets
+                .iter()
+                .map(|asset_info| asset_info.to_normal(deps.api))
+                .collect::<StdResult<_>>()?,
+            max_spread: fee_swap.max_spread,
+        }),
+        None => None,
+    };
+
+    Ok(FeeSwapResponse { fee_swap })
+}
+
+pub fn query_fee_route(deps: Deps, asset_info: AssetInfo) -> StdResult<FeeRouteResponse> {
+    let operations = read_fee_route(deps.storage, &asset_info.to_vec(deps.api)?)?;
+
+    Ok(FeeRouteResponse { operations })
+}
+
 fn _read_pool_reward_per_sec(
     querier: &QuerierWrapper,
     staking_contract: Addr,
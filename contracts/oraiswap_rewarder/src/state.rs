@@ -1,9 +1,14 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{CanonicalAddr, StdResult, Storage};
+use cosmwasm_std::{CanonicalAddr, Decimal, StdResult, Storage};
 use cosmwasm_storage::{singleton, singleton_read, Bucket, ReadonlyBucket};
+use oraiswap::asset::AssetInfoRaw;
+use oraiswap::router::SwapOperation;
 
 static KEY_CONFIG: &[u8] = b"config";
 static KEY_LAST_DISTRIBUTED: &[u8] = b"last_distributed";
+static KEY_ORDERBOOK_INCENTIVE: &[u8] = b"orderbook_incentive";
+static KEY_FEE_SWAP: &[u8] = b"fee_swap";
+static PREFIX_FEE_ROUTE: &[u8] = b"fee_route";
 
 #[cw_serde]
 pub struct Config {
@@ -32,3 +37,70 @@ pub fn store_last_distributed(
 pub fn read_last_distributed(storage: &dyn Storage, asset_key: &[u8]) -> StdResult<u64> {
     ReadonlyBucket::new(storage, KEY_LAST_DISTRIBUTED).load(asset_key)
 }
+
+#[cw_serde]
+pub struct OrderBookIncentiveRaw {
+    pub orderbook_contract: CanonicalAddr,
+    pub reward_asset: AssetInfoRaw,
+    pub reward_per_point: Decimal,
+}
+
+pub fn store_orderbook_incentive(
+    storage: &mut dyn Storage,
+    incentive: Option<&OrderBookIncentiveRaw>,
+) -> StdResult<()> {
+    match incentive {
+        Some(incentive) => singleton(storage, KEY_ORDERBOOK_INCENTIVE).save(incentive),
+        None => {
+            singleton::<OrderBookIncentiveRaw>(storage, KEY_ORDERBOOK_INCENTIVE).remove();
+            Ok(())
+        }
+    }
+}
+
+pub fn read_orderbook_incentive(storage: &dyn Storage) -> StdResult<Option<OrderBookIncentiveRaw>> {
+    singleton_read(storage, KEY_ORDERBOOK_INCENTIVE).may_load()
+}
+
+#[cw_serde]
+pub struct FeeSwapRaw {
+    pub router: CanonicalAddr,
+    pub reward_assets: Vec<AssetInfoRaw>,
+    pub max_spread: Decimal,
+}
+
+pub fn store_fee_swap(storage: &mut dyn Storage, fee_swap: Option<&FeeSwapRaw>) -> StdResult<()> {
+    match fee_swap {
+        Some(fee_swap) => singleton(storage, KEY_FEE_SWAP).save(fee_swap),
+        None => {
+            singleton::<FeeSwapRaw>(storage, KEY_FEE_SWAP).remove();
+            Ok(())
+        }
+    }
+}
+
+pub fn read_fee_swap(storage: &dyn Storage) -> StdResult<Option<FeeSwapRaw>> {
+    singleton_read(storage, KEY_FEE_SWAP).may_load()
+}
+
+pub fn store_fee_route(
+    storage: &mut dyn Storage,
+    asset_key: &[u8],
+    operations: Option<&Vec<SwapOperation>>,
+) -> StdResult<()> {
+    let mut bucket = Bucket::<Vec<SwapOperation>>::new(storage, PREFIX_FEE_ROUTE);
+    match operations {
+        Some(operations) => bucket.save(asset_key, operations),
+        None => {
+            bucket.remove(asset_key);
+            Ok(())
+        }
+    }
+}
+
+pub fn read_fee_route(
+    storage: &dyn Storage,
+    asset_key: &[u8],
+) -> StdResult<Option<Vec<SwapOperation>>> {
+    ReadonlyBucket::new(storage, PREFIX_FEE_ROUTE).may_load(asset_key)
+}
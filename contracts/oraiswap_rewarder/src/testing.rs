@@ -1,9 +1,18 @@
-use crate::contract::{instantiate, query_config};
+use crate::contract::{
+    execute, instantiate, query_config, query_fee_route, query_orderbook_incentive,
+};
 use cosmwasm_std::{
+    coins,
     testing::{mock_dependencies, mock_env, mock_info},
-    Addr,
+    to_binary, Addr, BankMsg, ContractResult, Decimal, StdError, SubMsg, SystemResult, Uint128,
+    WasmMsg, WasmQuery,
+};
+use oraiswap::asset::AssetInfo;
+use oraiswap::rewarder::{ConfigResponse, ExecuteMsg, FeeSwap, InstantiateMsg, OrderBookIncentive};
+use oraiswap::router::{
+    ExecuteMsg as RouterExecuteMsg, SimulateSwapOperationsResponse, SwapHopSimulation,
+    SwapOperation,
 };
-use oraiswap::rewarder::{ConfigResponse, InstantiateMsg};
 
 #[test]
 fn proper_initialization() {
@@ -29,3 +38,230 @@ fn proper_initialization() {
         }
     );
 }
+
+#[test]
+fn distribute_orderbook_incentive() {
+    let mut deps = mock_dependencies();
+
+    let msg = InstantiateMsg {
+        staking_contract: Addr::unchecked("staking"),
+        distribution_interval: Some(600),
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    let distribute_msg = ExecuteMsg::DistributeOrderBookIncentive {
+        recipient: Addr::unchecked("maker"),
+        points: Uint128::from(1000u128),
+    };
+
+    // no incentive program yet
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("limit_order", &[]),
+        distribute_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("order book incentive is not set")
+    );
+
+    let incentive = OrderBookIncentive {
+        orderbook_contract: Addr::unchecked("limit_order"),
+        reward_asset: AssetInfo::NativeToken {
+            denom: "orai".to_string(),
+        },
+        reward_per_point: Decimal::percent(50),
+    };
+    let msg = ExecuteMsg::UpdateOrderBookIncentive {
+        incentive: Some(incentive.clone()),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+    execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+    assert_eq!(
+        query_orderbook_incentive(deps.as_ref()).unwrap().incentive,
+        Some(incentive)
+    );
+
+    // only the order book contract can distribute
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("maker", &[]),
+        distribute_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("limit_order", &[]),
+        distribute_msg,
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: "maker".to_string(),
+            amount: coins(500, "orai"),
+        })]
+    );
+}
+
+#[test]
+fn deposit_fees() {
+    let mut deps = mock_dependencies();
+
+    let msg = InstantiateMsg {
+        staking_contract: Addr::unchecked("staking"),
+        distribution_interval: Some(600),
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    let orai = AssetInfo::NativeToken {
+        denom: "orai".to_string(),
+    };
+    let atom = AssetInfo::NativeToken {
+        denom: "atom".to_string(),
+    };
+    let route = vec![SwapOperation::OraiSwap {
+        offer_asset_info: atom.clone(),
+        ask_asset_info: orai.clone(),
+    }];
+
+    // the fee swap must be set first
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("fee_collector", &coins(1000, "atom")),
+        ExecuteMsg::DepositFees {},
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("fee swap is not set"));
+
+    let msg = ExecuteMsg::UpdateFeeSwap {
+        fee_swap: Some(FeeSwap {
+            router: Addr::unchecked("router"),
+            reward_assets: vec![orai.clone()],
+            max_spread: Decimal::percent(5),
+        }),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    // routes must end with a reward asset
+    let msg = ExecuteMsg::UpdateFeeRoute {
+        asset_info: atom.clone(),
+        operations: Some(vec![SwapOperation::OraiSwap {
+            offer_asset_info: atom.clone(),
+            ask_asset_info: AssetInfo::NativeToken {
+                denom: "usdt".to_string(),
+            },
+        }]),
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("the route must end with a reward asset")
+    );
+
+    let msg = ExecuteMsg::UpdateFeeRoute {
+        asset_info: atom.clone(),
+        operations: Some(route.clone()),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+    assert_eq!(
+        query_fee_route(deps.as_ref(), atom.clone())
+            .unwrap()
+            .operations,
+        Some(route.clone())
+    );
+
+    // reward assets are sent to staking as they are
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("fee_collector", &coins(1000, "orai")),
+        ExecuteMsg::DepositFees {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: "staking".to_string(),
+            amount: coins(1000, "orai"),
+        })]
+    );
+
+    // 1000 atom are swapped to 1960 orai with a spread of 20 orai
+    let simulate = |spread_amount: u128| {
+        move |_: &WasmQuery| {
+            SystemResult::Ok(ContractResult::Ok(
+                to_binary(&SimulateSwapOperationsResponse {
+                    amount: Uint128::from(1960u128),
+                    hops: vec![SwapHopSimulation {
+                        operation: SwapOperation::OraiSwap {
+                            offer_asset_info: AssetInfo::NativeToken {
+                                denom: "atom".to_string(),
+                            },
+                            ask_asset_info: AssetInfo::NativeToken {
+                                denom: "orai".to_string(),
+                            },
+                        },
+                        venue: Addr::unchecked("pair"),
+                        offer_amount: Uint128::from(1000u128),
+                        return_amount: Uint128::from(1960u128),
+                        spread_amount: Uint128::from(spread_amount),
+                        commission_amount: Uint128::from(20u128),
+                        tax_amount: Uint128::zero(),
+                    }],
+                })
+                .unwrap(),
+            ))
+        }
+    };
+    deps.querier.update_wasm(simulate(20));
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("fee_collector", &coins(1000, "atom")),
+        ExecuteMsg::DepositFees {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(WasmMsg::Execute {
+            contract_addr: "router".to_string(),
+            msg: to_binary(&RouterExecuteMsg::ExecuteSwapOperations {
+                operations: route,
+                minimum_receive: Some(Uint128::from(1960u128)),
+                to: Some(Addr::unchecked("staking")),
+                callback: None,
+            })
+            .unwrap(),
+            funds: coins(1000, "atom"),
+        })]
+    );
+
+    // the swap reverts when the pool is moved
+    deps.querier.update_wasm(simulate(500));
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("fee_collector", &coins(1000, "atom")),
+        ExecuteMsg::DepositFees {},
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("spread of swapping to orai is over the max spread")
+    );
+}
@@ -7,18 +7,23 @@ use cosmwasm_std::{
 };
 use oraiswap::error::ContractError;
 
-use crate::operations::{execute_swap_operation, execute_swap_operations};
-use crate::state::{Config, CONFIG};
+use crate::operations::{
+    assert_allowed_venue, execute_forward_callback, execute_swap_operation,
+    execute_swap_operations, load_converter_addr, load_pair_addr, simulate_convert,
+};
+use crate::state::{Config, CONFIG, PAIR_ADDRS, PAUSED};
 
 use cw20::Cw20ReceiveMsg;
-use oraiswap::asset::{Asset, AssetInfo, PairInfo};
+use oraiswap::asset::{pair_key, Asset, AssetInfo};
 use oraiswap::oracle::OracleContract;
 use oraiswap::pair::{QueryMsg as PairQueryMsg, SimulationResponse};
-use oraiswap::querier::{query_pair_config, query_pair_info};
+use oraiswap::querier::{query_pair_config, query_weighted_pair_info};
 use oraiswap::router::{
-    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
-    SimulateSwapOperationsResponse, SwapOperation,
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg,
+    NetReceiveEstimateResponse, QueryMsg, SimulateSwapOperationsResponse, SwapHopSimulation,
+    SwapOperation,
 };
+use oraiswap::weighted_pair::{QueryMsg as WeightedPairQueryMsg, WeightedPairInfo};
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -32,6 +37,16 @@ pub fn instantiate(
         &Config {
             factory_addr: deps.api.addr_canonicalize(msg.factory_addr.as_str())?,
             factory_addr_v2: deps.api.addr_canonicalize(msg.factory_addr_v2.as_str())?,
+            converter_addr: msg
+                .converter_addr
+                .map(|addr| deps.api.addr_canonicalize(addr.as_str()))
+                .transpose()?,
+            guardian: msg
+                .guardian
+                .map(|addr| deps.api.addr_canonicalize(addr.as_str()))
+                .transpose()?,
+            rebate_contract: None,
+            allowed_venues: None,
         },
     )?;
 
@@ -39,7 +54,27 @@ pub fn instantiate(
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> StdResult<Response> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if let Some(converter_addr) = msg.converter_addr {
+        config.converter_addr = Some(deps.api.addr_canonicalize(converter_addr.as_str())?);
+    }
+    if let Some(guardian) = msg.guardian {
+        config.guardian = Some(deps.api.addr_canonicalize(guardian.as_str())?);
+    }
+    if let Some(rebate_contract) = msg.rebate_contract {
+        config.rebate_contract = Some(deps.api.addr_canonicalize(rebate_contract.as_str())?);
+    }
+    if let Some(allowed_venues) = msg.allowed_venues {
+        config.allowed_venues = Some(
+            allowed_venues
+                .iter()
+                .map(|addr| deps.api.addr_canonicalize(addr.as_str()))
+                .collect::<StdResult<_>>()?,
+        );
+    }
+    CONFIG.save(deps.storage, &config)?;
+
     Ok(Response::default())
 }
 
@@ -56,10 +91,48 @@ pub fn execute(
             operations,
             minimum_receive,
             to,
-        } => execute_swap_operations(deps, env, info.sender, operations, minimum_receive, to),
+            callback,
+        } => {
+            assert_not_paused(deps.as_ref())?;
+
+            let offer_amount = native_offer_amount(&info, &operations);
+            execute_swap_operations(
+                deps,
+                env,
+                info.sender,
+                operations,
+                offer_amount,
+                minimum_receive,
+                to,
+                callback,
+            )
+        }
+        ExecuteMsg::ExecuteCycle {
+            operations,
+            min_profit,
+        } => {
+            let offer_amount = native_offer_amount(&info, &operations);
+            execute_cycle(deps, env, info.sender, operations, offer_amount, min_profit)
+        }
         ExecuteMsg::ExecuteSwapOperation { operation, to } => {
             execute_swap_operation(deps, env, info, operation, to)
         }
+        ExecuteMsg::ForwardCallback {
+            asset_info,
+            prev_balance,
+            minimum_receive,
+            receiver,
+            callback,
+        } => execute_forward_callback(
+            deps.as_ref(),
+            env,
+            info,
+            asset_info,
+            prev_balance,
+            minimum_receive,
+            receiver,
+            callback,
+        ),
 
         ExecuteMsg::AssertMinimumReceive {
             asset_info,
@@ -73,7 +146,114 @@ pub fn execute(
             minimum_receive,
             receiver.into(),
         ),
+        ExecuteMsg::Pause {} => execute_set_paused(deps, info, true),
+        ExecuteMsg::Unpause {} => execute_set_paused(deps, info, false),
+        ExecuteMsg::InvalidatePairCache { asset_infos } => {
+            execute_invalidate_pair_cache(deps, info, asset_infos)
+        }
+    }
+}
+
+/// offer amount is the sent native token of the first operation
+fn native_offer_amount(info: &MessageInfo, operations: &[SwapOperation]) -> Uint128 {
+    match operations.first().map(|op| op.get_offer_asset_info()) {
+        Some(AssetInfo::NativeToken { denom }) => info
+            .funds
+            .iter()
+            .find(|coin| coin.denom.eq(&denom))
+            .map(|coin| coin.amount)
+            .unwrap_or_default(),
+        _ => Uint128::zero(),
+    }
+}
+
+pub fn execute_cycle(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    operations: Vec<SwapOperation>,
+    offer_amount: Uint128,
+    min_profit: Uint128,
+) -> Result<Response, ContractError> {
+    assert_not_paused(deps.as_ref())?;
+
+    let (first, last) = match (operations.first(), operations.last()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => return Err(ContractError::NoSwapOperation {}),
+    };
+    if first.get_offer_asset_info() != last.get_target_asset_info() {
+        return Err(ContractError::InvalidCycle {});
+    }
+
+    // the sender must get back the offer amount plus the expected profit
+    let minimum_receive = offer_amount.checked_add(min_profit)?;
+    let res = execute_swap_operations(
+        deps,
+        env,
+        sender,
+        operations,
+        offer_amount,
+        Some(minimum_receive),
+        None,
+        None,
+    )?;
+
+    Ok(res.add_attributes(vec![
+        ("action", "execute_cycle".to_string()),
+        ("offer_amount", offer_amount.to_string()),
+        ("min_profit", min_profit.to_string()),
+    ]))
+}
+
+pub fn execute_set_paused(
+    deps: DepsMut,
+    info: MessageInfo,
+    paused: bool,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.guardian != Some(deps.api.addr_canonicalize(info.sender.as_str())?) {
+        return Err(ContractError::Unauthorized {});
     }
+
+    PAUSED.save(deps.storage, &paused)?;
+
+    Ok(Response::new().add_attribute("action", if paused { "pause" } else { "unpause" }))
+}
+
+/// the factories do not notify the router of their pairs, so only the guardian can invalidate
+/// a cached pair once the factories resolve another pair for its assets
+pub fn execute_invalidate_pair_cache(
+    deps: DepsMut,
+    info: MessageInfo,
+    asset_infos: [AssetInfo; 2],
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.guardian != Some(deps.api.addr_canonicalize(info.sender.as_str())?) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    PAIR_ADDRS.remove(
+        deps.storage,
+        &pair_key(&[
+            asset_infos[0].to_raw(deps.api)?,
+            asset_infos[1].to_raw(deps.api)?,
+        ]),
+    );
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "invalidate_pair_cache"),
+        (
+            "pair",
+            &format!("{} - {}", &asset_infos[0], &asset_infos[1]),
+        ),
+    ]))
+}
+
+fn assert_not_paused(deps: Deps) -> Result<(), ContractError> {
+    if PAUSED.may_load(deps.storage)?.unwrap_or_default() {
+        return Err(ContractError::Paused {});
+    }
+    Ok(())
 }
 
 pub fn receive_cw20(
@@ -90,10 +270,26 @@ pub fn receive_cw20(
             operations,
             minimum_receive,
             to,
+            callback,
         } => {
+            assert_not_paused(deps.as_ref())?;
+
             let receiver = to.map_or(None, |addr| deps.api.addr_validate(addr.as_str()).ok());
-            execute_swap_operations(deps, env, sender, operations, minimum_receive, receiver)
+            execute_swap_operations(
+                deps,
+                env,
+                sender,
+                operations,
+                cw20_msg.amount,
+                minimum_receive,
+                receiver,
+                callback,
+            )
         }
+        Cw20HookMsg::ExecuteCycle {
+            operations,
+            min_profit,
+        } => execute_cycle(deps, env, sender, operations, cw20_msg.amount, min_profit),
     }
 }
 
@@ -125,6 +321,10 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             offer_amount,
             operations,
         } => to_binary(&simulate_swap_operations(deps, offer_amount, operations)?),
+        QueryMsg::NetReceiveEstimate {
+            offer_amount,
+            operations,
+        } => to_binary(&query_net_receive_estimate(deps, offer_amount, operations)?),
     }
 }
 
@@ -133,11 +333,55 @@ pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
     let resp = ConfigResponse {
         factory_addr: deps.api.addr_humanize(&state.factory_addr)?,
         factory_addr_v2: deps.api.addr_humanize(&state.factory_addr_v2)?,
+        converter_addr: state
+            .converter_addr
+            .map(|addr| deps.api.addr_humanize(&addr))
+            .transpose()?,
+        guardian: state
+            .guardian
+            .map(|addr| deps.api.addr_humanize(&addr))
+            .transpose()?,
+        paused: PAUSED.may_load(deps.storage)?.unwrap_or_default(),
+        rebate_contract: state
+            .rebate_contract
+            .map(|addr| deps.api.addr_humanize(&addr))
+            .transpose()?,
+        allowed_venues: state
+            .allowed_venues
+            .map(|venues| {
+                venues
+                    .iter()
+                    .map(|addr| deps.api.addr_humanize(addr))
+                    .collect::<StdResult<_>>()
+            })
+            .transpose()?,
     };
 
     Ok(resp)
 }
 
+fn query_net_receive_estimate(
+    deps: Deps,
+    offer_amount: Uint128,
+    operations: Vec<SwapOperation>,
+) -> StdResult<NetReceiveEstimateResponse> {
+    let simulation = simulate_swap_operations(deps, offer_amount, operations)?;
+    let mut tax_amount = Uint128::zero();
+    let mut convert_fee_amount = Uint128::zero();
+    for hop in simulation.hops {
+        match hop.operation {
+            SwapOperation::Convert { .. } => convert_fee_amount += hop.tax_amount,
+            _ => tax_amount += hop.tax_amount,
+        }
+    }
+
+    Ok(NetReceiveEstimateResponse {
+        amount: simulation.amount,
+        tax_amount,
+        convert_fee_amount,
+    })
+}
+
 fn simulate_swap_operations(
     deps: Deps,
     offer_amount: Uint128,
@@ -154,27 +398,22 @@ fn simulate_swap_operations(
     }
 
     let mut offer_amount = offer_amount;
+    let mut hops = vec![];
     for operation in operations.into_iter() {
         let pair_config = query_pair_config(&deps.querier, factory_addr.clone())
             .or_else(|_| query_pair_config(&deps.querier, factory_addr_v2.clone()))?;
         let oracle_contract = OracleContract(pair_config.oracle_addr);
-        match operation {
+        let hop = match operation.clone() {
             SwapOperation::OraiSwap {
                 offer_asset_info,
                 ask_asset_info,
             } => {
-                let pair_info = query_pair_info(
-                    &deps.querier,
-                    factory_addr.clone(),
+                let (pair_addr, _) = load_pair_addr(
+                    deps,
+                    &config,
                     &[offer_asset_info.clone(), ask_asset_info.clone()],
-                )
-                .or_else(|_| -> StdResult<PairInfo> {
-                    query_pair_info(
-                        &deps.querier,
-                        factory_addr_v2.clone(),
-                        &[offer_asset_info.clone(), ask_asset_info.clone()],
-                    )
-                })?;
+                )?;
+                assert_allowed_venue(deps, &config, &pair_addr)?;
 
                 let return_asset = Asset {
                     info: offer_asset_info.clone(),
@@ -182,15 +421,15 @@ fn simulate_swap_operations(
                 };
 
                 // Deduct tax before querying simulation, with native token only
-                offer_amount = offer_amount
-                    .checked_sub(return_asset.compute_tax(&oracle_contract, &deps.querier)?)?;
+                let offer_tax = return_asset.compute_tax(&oracle_contract, &deps.querier)?;
+                let swap_amount = offer_amount.checked_sub(offer_tax)?;
 
-                let mut res: SimulationResponse = deps.querier.query_wasm_smart(
-                    pair_info.contract_addr,
+                let res: SimulationResponse = deps.querier.query_wasm_smart(
+                    pair_addr.clone(),
                     &PairQueryMsg::Simulation {
                         offer_asset: Asset {
                             info: offer_asset_info,
-                            amount: offer_amount,
+                            amount: swap_amount,
                         },
                     },
                 )?;
@@ -201,16 +440,97 @@ fn simulate_swap_operations(
                 };
 
                 // Deduct tax after querying simulation, with native token only
-                res.return_amount = res
-                    .return_amount
-                    .checked_sub(return_asset.compute_tax(&oracle_contract, &deps.querier)?)?;
+                let return_tax = return_asset.compute_tax(&oracle_contract, &deps.querier)?;
 
-                offer_amount = res.return_amount;
+                SwapHopSimulation {
+                    operation,
+                    venue: pair_addr,
+                    offer_amount,
+                    return_amount: res.return_amount.checked_sub(return_tax)?,
+                    spread_amount: res.spread_amount,
+                    commission_amount: res.commission_amount,
+                    tax_amount: offer_tax + return_tax,
+                }
             }
-        }
+            SwapOperation::Convert { from, to } => {
+                let converter_addr = load_converter_addr(deps, &config)?;
+                assert_allowed_venue(deps, &config, &converter_addr)?;
+                let (return_amount, fee_amount) =
+                    simulate_convert(deps, converter_addr.clone(), &from, &to, offer_amount)?;
+
+                SwapHopSimulation {
+                    operation,
+                    venue: converter_addr,
+                    offer_amount,
+                    return_amount,
+                    spread_amount: Uint128::zero(),
+                    commission_amount: Uint128::zero(),
+                    tax_amount: fee_amount,
+                }
+            }
+            SwapOperation::WeightedSwap {
+                pool_asset_infos,
+                offer_asset_info,
+                ask_asset_info,
+            } => {
+                let pair_info = query_weighted_pair_info(
+                    &deps.querier,
+                    factory_addr.clone(),
+                    &pool_asset_infos,
+                )
+                .or_else(|_| -> StdResult<WeightedPairInfo> {
+                    query_weighted_pair_info(
+                        &deps.querier,
+                        factory_addr_v2.clone(),
+                        &pool_asset_infos,
+                    )
+                })?;
+                assert_allowed_venue(deps, &config, &pair_info.contract_addr)?;
+
+                // Deduct tax before querying simulation, with native token only
+                let offer_tax = Asset {
+                    info: offer_asset_info.clone(),
+                    amount: offer_amount,
+                }
+                .compute_tax(&oracle_contract, &deps.querier)?;
+                let swap_amount = offer_amount.checked_sub(offer_tax)?;
+
+                let res: SimulationResponse = deps.querier.query_wasm_smart(
+                    pair_info.contract_addr.clone(),
+                    &WeightedPairQueryMsg::Simulation {
+                        offer_asset: Asset {
+                            info: offer_asset_info,
+                            amount: swap_amount,
+                        },
+                        ask_asset_info: ask_asset_info.clone(),
+                    },
+                )?;
+
+                // Deduct tax after querying simulation, with native token only
+                let return_tax = Asset {
+                    info: ask_asset_info,
+                    amount: res.return_amount,
+                }
+                .compute_tax(&oracle_contract, &deps.querier)?;
+
+                SwapHopSimulation {
+                    operation,
+                    venue: pair_info.contract_addr,
+                    offer_amount,
+                    return_amount: res.return_amount.checked_sub(return_tax)?,
+                    spread_amount: res.spread_amount,
+                    commission_amount: res.commission_amount,
+                    tax_amount: offer_tax + return_tax,
+                }
+            }
+        };
+
+        offer_amount = hop.return_amount;
+        hops.push(hop);
     }
 
     Ok(SimulateSwapOperationsResponse {
         amount: offer_amount,
+        hops,
     })
 }
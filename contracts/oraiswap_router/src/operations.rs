@@ -1,19 +1,30 @@
 use std::collections::HashMap;
 
 use cosmwasm_std::{
-    to_binary, Addr, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo, Response, StdError,
-    StdResult, Uint128, WasmMsg,
+    to_binary, Addr, Binary, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo,
+    QuerierWrapper, Response, StdError, StdResult, Uint128, WasmMsg,
 };
 use oraiswap::error::ContractError;
 
-use crate::state::{Config, CONFIG};
+use crate::state::{Config, CONFIG, PAIR_ADDRS};
 
 use cw20::Cw20ExecuteMsg;
-use oraiswap::asset::{Asset, AssetInfo, PairInfo};
+use oraiswap::asset::{pair_key, Asset, AssetInfo, PairInfo};
+use oraiswap::converter::{
+    asset_into_convert_msg, ConvertInfoResponse, ConvertLimitResponse,
+    QueryMsg as ConverterQueryMsg,
+};
+use oraiswap::math::Converter128;
 use oraiswap::oracle::OracleContract;
 use oraiswap::pair::{ExecuteMsg as PairExecuteMsg, PairExecuteMsgCw20};
-use oraiswap::querier::{query_pair_config, query_pair_info, query_token_balance};
+use oraiswap::querier::{
+    query_pair_config, query_pair_info, query_token_balance, query_weighted_pair_info,
+};
+use oraiswap::rebate::ExecuteMsg as RebateExecuteMsg;
 use oraiswap::router::{ExecuteMsg, SwapOperation};
+use oraiswap::weighted_pair::{
+    Cw20HookMsg as WeightedPairCw20HookMsg, ExecuteMsg as WeightedPairExecuteMsg, WeightedPairInfo,
+};
 
 /// Execute swap operation
 /// swap all offer asset to ask asset
@@ -35,61 +46,283 @@ pub fn execute_swap_operation(
         .or_else(|_| query_pair_config(&deps.querier, factory_addr_v2.clone()))?;
     let oracle_contract = OracleContract(pair_config.oracle_addr.clone());
 
-    let messages: Vec<CosmosMsg> = match operation {
+    // the offer of the hop and the tax deducted when it is sent to the venue
+    let (messages, offer_asset, tax_amount): (Vec<CosmosMsg>, Asset, Uint128) = match operation {
         SwapOperation::OraiSwap {
             offer_asset_info,
             ask_asset_info,
         } => {
-            let pair_info: PairInfo = query_pair_info(
-                &deps.querier,
-                factory_addr,
-                &[offer_asset_info.clone(), ask_asset_info.clone()],
-            )
-            .or_else(|_| -> StdResult<PairInfo> {
-                query_pair_info(
-                    &deps.querier,
-                    factory_addr_v2.clone(),
-                    &[offer_asset_info.clone(), ask_asset_info.clone()],
-                )
-            })?;
-
-            let amount = match offer_asset_info.clone() {
-                AssetInfo::NativeToken { denom } => {
-                    deps.querier
-                        .query_balance(env.contract.address, &denom)?
-                        .amount
-                }
-                AssetInfo::Token { contract_addr } => {
-                    query_token_balance(&deps.querier, contract_addr.into(), env.contract.address)?
-                }
-            };
+            let asset_infos = [offer_asset_info.clone(), ask_asset_info.clone()];
+            let (pair_addr, cached) = load_pair_addr(deps.as_ref(), &config, &asset_infos)?;
+            assert_allowed_venue(deps.as_ref(), &config, &pair_addr)?;
+
+            // cache the pair so the next swaps on this route skip the factory queries
+            if !cached {
+                PAIR_ADDRS.save(
+                    deps.storage,
+                    &pair_key(&[
+                        asset_infos[0].to_raw(deps.api)?,
+                        asset_infos[1].to_raw(deps.api)?,
+                    ]),
+                    &deps.api.addr_canonicalize(pair_addr.as_str())?,
+                )?;
+            }
+
             let offer_asset: Asset = Asset {
+                amount: query_offer_amount(deps.as_ref(), &env, &offer_asset_info)?,
                 info: offer_asset_info,
-                amount,
             };
 
             // swap token in smart contract
-            vec![asset_into_swap_msg(
+            let (message, tax_amount) = asset_into_swap_msg(
                 deps.as_ref(),
                 &oracle_contract,
-                pair_info.contract_addr,
-                offer_asset,
+                pair_addr,
+                offer_asset.clone(),
                 None,
                 to,
-            )?]
+            )?;
+            (vec![message], offer_asset, tax_amount)
+        }
+        SwapOperation::Convert {
+            from,
+            to: to_asset_info,
+        } => {
+            let converter_addr = load_converter_addr(deps.as_ref(), &config)?;
+            assert_allowed_venue(deps.as_ref(), &config, &converter_addr)?;
+            let offer_asset = Asset {
+                amount: query_offer_amount(deps.as_ref(), &env, &from)?,
+                info: from,
+            };
+            let (reverse, ratio) = query_convert_ratio(
+                &deps.querier,
+                converter_addr.clone(),
+                &offer_asset.info,
+                &to_asset_info,
+            )?;
+
+            let mut messages = vec![asset_into_convert_msg(
+                converter_addr.clone(),
+                offer_asset.clone(),
+                if reverse {
+                    Some(to_asset_info.clone())
+                } else {
+                    None
+                },
+            )?];
+
+            // the converter always pays back to the router, so forward the result on the last hop
+            if let Some(to) = to {
+                let return_amount = if reverse {
+                    offer_asset.amount.checked_div_decimal(ratio)?
+                } else {
+                    deduct_convert_fee(
+                        &deps.querier,
+                        converter_addr.clone(),
+                        &offer_asset.info,
+                        offer_asset.amount * ratio,
+                    )?
+                };
+                messages.push(
+                    Asset {
+                        info: to_asset_info,
+                        amount: return_amount,
+                    }
+                    .into_msg(None, &deps.querier, to)?,
+                );
+            }
+
+            (messages, offer_asset, Uint128::zero())
+        }
+        SwapOperation::WeightedSwap {
+            pool_asset_infos,
+            offer_asset_info,
+            ask_asset_info,
+        } => {
+            let pair_info =
+                query_weighted_pair_info(&deps.querier, factory_addr, &pool_asset_infos).or_else(
+                    |_| -> StdResult<WeightedPairInfo> {
+                        query_weighted_pair_info(&deps.querier, factory_addr_v2, &pool_asset_infos)
+                    },
+                )?;
+            assert_allowed_venue(deps.as_ref(), &config, &pair_info.contract_addr)?;
+
+            let offer_asset: Asset = Asset {
+                amount: query_offer_amount(deps.as_ref(), &env, &offer_asset_info)?,
+                info: offer_asset_info,
+            };
+
+            let (message, tax_amount) = asset_into_weighted_swap_msg(
+                deps.as_ref(),
+                &oracle_contract,
+                pair_info.contract_addr,
+                offer_asset.clone(),
+                ask_asset_info,
+                to,
+            )?;
+            (vec![message], offer_asset, tax_amount)
         }
     };
 
-    Ok(Response::new().add_messages(messages))
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "execute_swap_operation"),
+        ("offer_asset", &offer_asset.info.to_string()),
+        ("offer_amount", &offer_asset.amount.to_string()),
+        ("tax_amount", &tax_amount.to_string()),
+    ]))
+}
+
+/// pair contract of the assets, read from the cache or resolved from the factories,
+/// the flag tells whether it was cached
+pub fn load_pair_addr(
+    deps: Deps,
+    config: &Config,
+    asset_infos: &[AssetInfo; 2],
+) -> StdResult<(Addr, bool)> {
+    let key = pair_key(&[
+        asset_infos[0].to_raw(deps.api)?,
+        asset_infos[1].to_raw(deps.api)?,
+    ]);
+    if let Some(pair_addr) = PAIR_ADDRS.may_load(deps.storage, &key)? {
+        return Ok((deps.api.addr_humanize(&pair_addr)?, true));
+    }
+
+    let factory_addr = deps.api.addr_humanize(&config.factory_addr)?;
+    let factory_addr_v2 = deps.api.addr_humanize(&config.factory_addr_v2)?;
+    let pair_info = query_pair_info(&deps.querier, factory_addr, asset_infos).or_else(
+        |_| -> StdResult<PairInfo> { query_pair_info(&deps.querier, factory_addr_v2, asset_infos) },
+    )?;
+
+    Ok((pair_info.contract_addr, false))
+}
+
+fn query_offer_amount(deps: Deps, env: &Env, offer_asset_info: &AssetInfo) -> StdResult<Uint128> {
+    match offer_asset_info {
+        AssetInfo::NativeToken { denom } => Ok(deps
+            .querier
+            .query_balance(env.contract.address.clone(), denom)?
+            .amount),
+        AssetInfo::Token { contract_addr } => query_token_balance(
+            &deps.querier,
+            contract_addr.clone(),
+            env.contract.address.clone(),
+        ),
+    }
+}
+
+pub fn load_converter_addr(deps: Deps, config: &Config) -> StdResult<Addr> {
+    match &config.converter_addr {
+        Some(converter_addr) => deps.api.addr_humanize(converter_addr),
+        None => Err(StdError::generic_err("converter contract is not set")),
+    }
+}
+
+/// Reject venues outside the configured allowlist, so a route can only move funds through trusted contracts
+pub fn assert_allowed_venue(deps: Deps, config: &Config, venue: &Addr) -> StdResult<()> {
+    if let Some(allowed_venues) = &config.allowed_venues {
+        let venue_raw = deps.api.addr_canonicalize(venue.as_str())?;
+        if !allowed_venues.contains(&venue_raw) {
+            return Err(StdError::generic_err(format!(
+                "{} is not an allowed venue",
+                venue
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Find the converter pair linking `from` and `to`.
+/// Returns whether the conversion must run in reverse together with the stored ratio
+fn query_convert_ratio(
+    querier: &QuerierWrapper,
+    converter_addr: Addr,
+    from: &AssetInfo,
+    to: &AssetInfo,
+) -> StdResult<(bool, Decimal)> {
+    if let Ok(res) = querier.query_wasm_smart::<ConvertInfoResponse>(
+        converter_addr.clone(),
+        &ConverterQueryMsg::ConvertInfo {
+            asset_info: from.clone(),
+        },
+    ) {
+        if res.token_ratio.info.eq(to) {
+            return Ok((false, res.token_ratio.ratio));
+        }
+    }
+
+    let res: ConvertInfoResponse = querier.query_wasm_smart(
+        converter_addr,
+        &ConverterQueryMsg::ConvertInfo {
+            asset_info: to.clone(),
+        },
+    )?;
+    if !res.token_ratio.info.eq(from) {
+        return Err(StdError::generic_err(
+            "invalid convert operation; pair is not registered in converter",
+        ));
+    }
+
+    Ok((true, res.token_ratio.ratio))
+}
+
+/// Returns the converted amount together with the fee taken by the converter
+pub fn simulate_convert(
+    deps: Deps,
+    converter_addr: Addr,
+    from: &AssetInfo,
+    to: &AssetInfo,
+    amount: Uint128,
+) -> StdResult<(Uint128, Uint128)> {
+    let (reverse, ratio) = query_convert_ratio(&deps.querier, converter_addr.clone(), from, to)?;
+    if reverse {
+        Ok((amount.checked_div_decimal(ratio)?, Uint128::zero()))
+    } else {
+        let amount = amount * ratio;
+        let fee = convert_fee(&deps.querier, converter_addr, from, amount)?;
+        Ok((amount.checked_sub(fee)?, fee))
+    }
+}
+
+/// Deduct the fee the converter takes when converting `from`, if the pair has a convert limit
+fn deduct_convert_fee(
+    querier: &QuerierWrapper,
+    converter_addr: Addr,
+    from: &AssetInfo,
+    amount: Uint128,
+) -> StdResult<Uint128> {
+    let fee = convert_fee(querier, converter_addr, from, amount)?;
+    Ok(amount.checked_sub(fee)?)
+}
+
+fn convert_fee(
+    querier: &QuerierWrapper,
+    converter_addr: Addr,
+    from: &AssetInfo,
+    amount: Uint128,
+) -> StdResult<Uint128> {
+    let res: ConvertLimitResponse = querier.query_wasm_smart(
+        converter_addr,
+        &ConverterQueryMsg::ConvertLimit {
+            asset_info: from.clone(),
+        },
+    )?;
+    Ok(res
+        .limit
+        .map(|limit| limit.compute_fee(amount))
+        .unwrap_or_default())
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn execute_swap_operations(
     deps: DepsMut,
     env: Env,
     sender: Addr,
     operations: Vec<SwapOperation>,
+    offer_amount: Uint128,
     minimum_receive: Option<Uint128>,
     to: Option<Addr>,
+    callback: Option<Binary>,
 ) -> Result<Response, ContractError> {
     let operations_len = operations.len();
     if operations_len == 0 {
@@ -99,9 +332,29 @@ pub fn execute_swap_operations(
     // Assert the operations are properly set
     assert_operations(&operations)?;
 
+    // report swap volume of the trader to the rebate contract
+    let rebate_msg = match CONFIG.load(deps.storage)?.rebate_contract {
+        Some(rebate_contract) if !offer_amount.is_zero() => {
+            Some(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: deps.api.addr_humanize(&rebate_contract)?.to_string(),
+                funds: vec![],
+                msg: to_binary(&RebateExecuteMsg::RecordVolume {
+                    trader: sender.clone(),
+                    asset_info: operations.first().unwrap().get_offer_asset_info(),
+                    amount: offer_amount,
+                })?,
+            }))
+        }
+        _ => None,
+    };
+
     let to = to.unwrap_or(sender);
+    let offer_asset_info = operations.first().unwrap().get_offer_asset_info();
     let target_asset_info = operations.last().unwrap().get_target_asset_info();
 
+    // the callback is only forwarded to contracts, wallets receive the output directly
+    let callback = callback.filter(|_| deps.querier.query_wasm_contract_info(&to).is_ok());
+
     let mut operation_index = 0;
     let mut messages: Vec<CosmosMsg> = operations
         .into_iter()
@@ -112,7 +365,8 @@ pub fn execute_swap_operations(
                 funds: vec![],
                 msg: to_binary(&ExecuteMsg::ExecuteSwapOperation {
                     operation: op,
-                    to: if operation_index == operations_len {
+                    // with a callback the last hop pays the router, which forwards the output
+                    to: if operation_index == operations_len && callback.is_none() {
                         Some(to.clone())
                     } else {
                         None
@@ -122,8 +376,27 @@ pub fn execute_swap_operations(
         })
         .collect::<StdResult<Vec<CosmosMsg>>>()?;
 
-    // Execute minimum amount assertion
-    if let Some(minimum_receive) = minimum_receive {
+    if let Some(callback) = callback {
+        // the offer received by the router is spent by the swaps and is not part of the output
+        let mut prev_balance =
+            target_asset_info.query_pool(&deps.querier, env.contract.address.clone())?;
+        if offer_asset_info == target_asset_info {
+            prev_balance = prev_balance.saturating_sub(offer_amount);
+        }
+
+        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: env.contract.address.to_string(),
+            funds: vec![],
+            msg: to_binary(&ExecuteMsg::ForwardCallback {
+                asset_info: target_asset_info,
+                prev_balance,
+                minimum_receive,
+                receiver: to,
+                callback,
+            })?,
+        }))
+    } else if let Some(minimum_receive) = minimum_receive {
+        // Execute minimum amount assertion
         let receiver_balance = target_asset_info.query_pool(&deps.querier, to.clone())?;
 
         messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
@@ -138,9 +411,70 @@ pub fn execute_swap_operations(
         }))
     }
 
+    if let Some(rebate_msg) = rebate_msg {
+        messages.push(rebate_msg);
+    }
+
     Ok(Response::new().add_messages(messages))
 }
 
+/// Send the output received by the router since the swaps started to the receiver contract,
+/// executing the callback with the funds attached or as the hook of a cw20 `Send`
+#[allow(clippy::too_many_arguments)]
+pub fn execute_forward_callback(
+    deps: Deps,
+    env: Env,
+    info: MessageInfo,
+    asset_info: AssetInfo,
+    prev_balance: Uint128,
+    minimum_receive: Option<Uint128>,
+    receiver: Addr,
+    callback: Binary,
+) -> Result<Response, ContractError> {
+    if env.contract.address != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let balance = asset_info.query_pool(&deps.querier, env.contract.address)?;
+    let swap_amount = balance.checked_sub(prev_balance)?;
+    if let Some(minium_receive) = minimum_receive {
+        if swap_amount < minium_receive {
+            return Err(ContractError::SwapAssertionFailure {
+                minium_receive,
+                swap_amount,
+            });
+        }
+    }
+
+    let message = match &asset_info {
+        AssetInfo::NativeToken { denom } => WasmMsg::Execute {
+            contract_addr: receiver.to_string(),
+            funds: vec![Coin {
+                denom: denom.clone(),
+                amount: swap_amount,
+            }],
+            msg: callback,
+        },
+        AssetInfo::Token { contract_addr } => WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: receiver.to_string(),
+                amount: swap_amount,
+                msg: callback,
+            })?,
+        },
+    };
+
+    Ok(Response::new().add_message(message).add_attributes(vec![
+        ("action", "forward_callback"),
+        ("receiver", receiver.as_str()),
+        ("return_asset", &asset_info.to_string()),
+        ("return_amount", &swap_amount.to_string()),
+    ]))
+}
+
+/// swap message of the offer asset, along with the tax deducted from the native offer
 fn asset_into_swap_msg(
     deps: Deps,
     oracle_contract: &OracleContract,
@@ -148,7 +482,7 @@ fn asset_into_swap_msg(
     offer_asset: Asset,
     max_spread: Option<Decimal>,
     to: Option<Addr>,
-) -> StdResult<CosmosMsg> {
+) -> StdResult<(CosmosMsg, Uint128)> {
     match offer_asset.info.clone() {
         AssetInfo::NativeToken { denom } => {
             let return_asset = Asset {
@@ -159,49 +493,106 @@ fn asset_into_swap_msg(
             };
 
             // deduct tax first
-            let amount = offer_asset
-                .amount
-                .checked_sub(return_asset.compute_tax(oracle_contract, &deps.querier)?)?;
+            let tax_amount = return_asset.compute_tax(oracle_contract, &deps.querier)?;
+            let amount = offer_asset.amount.checked_sub(tax_amount)?;
 
-            Ok(CosmosMsg::Wasm(WasmMsg::Execute {
-                contract_addr: pair_contract.to_string(),
-                funds: vec![Coin { denom, amount }],
-                msg: to_binary(&PairExecuteMsg::Swap {
-                    offer_asset: Asset {
-                        amount,
-                        ..offer_asset
-                    },
-                    belief_price: None,
-                    max_spread,
-                    to,
+            Ok((
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: pair_contract.to_string(),
+                    funds: vec![Coin { denom, amount }],
+                    msg: to_binary(&PairExecuteMsg::Swap {
+                        offer_asset: Asset {
+                            amount,
+                            ..offer_asset
+                        },
+                        belief_price: None,
+                        max_spread,
+                        to,
+                        return_asset_info: None,
+                    })?,
+                }),
+                tax_amount,
+            ))
+        }
+        AssetInfo::Token { contract_addr } => Ok((
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: contract_addr.to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Send {
+                    contract: pair_contract.to_string(),
+                    amount: offer_asset.amount,
+                    msg: to_binary(&PairExecuteMsgCw20::Swap {
+                        belief_price: None,
+                        max_spread,
+                        to,
+                    })?,
                 })?,
-            }))
+            }),
+            Uint128::zero(),
+        )),
+    }
+}
+
+/// weighted swap message of the offer asset, along with the tax deducted from the native offer
+fn asset_into_weighted_swap_msg(
+    deps: Deps,
+    oracle_contract: &OracleContract,
+    pair_contract: Addr,
+    offer_asset: Asset,
+    ask_asset_info: AssetInfo,
+    to: Option<Addr>,
+) -> StdResult<(CosmosMsg, Uint128)> {
+    match offer_asset.info.clone() {
+        AssetInfo::NativeToken { denom } => {
+            // deduct tax first
+            let tax_amount = offer_asset.compute_tax(oracle_contract, &deps.querier)?;
+            let amount = offer_asset.amount.checked_sub(tax_amount)?;
+
+            Ok((
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: pair_contract.to_string(),
+                    funds: vec![Coin { denom, amount }],
+                    msg: to_binary(&WeightedPairExecuteMsg::Swap {
+                        offer_asset: Asset {
+                            amount,
+                            ..offer_asset
+                        },
+                        ask_asset_info,
+                        belief_price: None,
+                        max_spread: None,
+                        to,
+                    })?,
+                }),
+                tax_amount,
+            ))
         }
-        AssetInfo::Token { contract_addr } => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: contract_addr.to_string(),
-            funds: vec![],
-            msg: to_binary(&Cw20ExecuteMsg::Send {
-                contract: pair_contract.to_string(),
-                amount: offer_asset.amount,
-                msg: to_binary(&PairExecuteMsgCw20::Swap {
-                    belief_price: None,
-                    max_spread,
-                    to,
+        AssetInfo::Token { contract_addr } => Ok((
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: contract_addr.to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Send {
+                    contract: pair_contract.to_string(),
+                    amount: offer_asset.amount,
+                    msg: to_binary(&WeightedPairCw20HookMsg::Swap {
+                        ask_asset_info,
+                        belief_price: None,
+                        max_spread: None,
+                        to: to.map(|to| to.to_string()),
+                    })?,
                 })?,
-            })?,
-        })),
+            }),
+            Uint128::zero(),
+        )),
     }
 }
 
 pub fn assert_operations(operations: &[SwapOperation]) -> StdResult<()> {
     let mut ask_asset_map: HashMap<String, bool> = HashMap::new();
     for operation in operations.iter() {
-        let (offer_asset, ask_asset) = match operation {
-            SwapOperation::OraiSwap {
-                offer_asset_info,
-                ask_asset_info,
-            } => (offer_asset_info.clone(), ask_asset_info.clone()),
-        };
+        let (offer_asset, ask_asset) = (
+            operation.get_offer_asset_info(),
+            operation.get_target_asset_info(),
+        );
 
         ask_asset_map.remove(&offer_asset.to_string());
         ask_asset_map.insert(ask_asset.to_string(), true);
@@ -1,17 +1,28 @@
 use cosmwasm_schema::cw_serde;
 
 use cosmwasm_std::CanonicalAddr;
-use cw_storage_plus::Item;
+use cw_storage_plus::{Item, Map};
 
 #[cw_serde]
 pub struct Config {
     pub factory_addr: CanonicalAddr,
     pub factory_addr_v2: CanonicalAddr,
+    pub converter_addr: Option<CanonicalAddr>,
+    pub guardian: Option<CanonicalAddr>,
+    pub rebate_contract: Option<CanonicalAddr>,
+    // pair and converter contracts swap operations may execute on, any factory pair when unset
+    pub allowed_venues: Option<Vec<CanonicalAddr>>,
 }
 
 // put the length bytes at the first for compatibility with legacy singleton store
 pub const CONFIG: Item<Config> = Item::new("\u{0}\u{6}config");
 
+// swap operations are paused by the guardian
+pub const PAUSED: Item<bool> = Item::new("paused");
+
+// pair contracts resolved from the factories by previous swaps, keyed by the pair key of the assets
+pub const PAIR_ADDRS: Map<&[u8], CanonicalAddr> = Map::new("pair_addrs");
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -36,6 +47,10 @@ mod test {
             &Config {
                 factory_addr: deps.api.addr_canonicalize("addr0000").unwrap(),
                 factory_addr_v2: deps.api.addr_canonicalize("addr0000_v2").unwrap(),
+                converter_addr: None,
+                guardian: None,
+                rebate_contract: None,
+                allowed_venues: None,
             },
         )
         .unwrap();
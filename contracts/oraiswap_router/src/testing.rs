@@ -1,10 +1,24 @@
-use cosmwasm_std::{Addr, Coin, Decimal, Uint128};
-use oraiswap::asset::{Asset, AssetInfo, ORAI_DENOM};
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_dependencies_with_balance, mock_env, mock_info, MOCK_CONTRACT_ADDR,
+};
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, Binary, Coin, ContractInfoResponse, ContractResult, CosmosMsg,
+    Decimal, QuerierResult, SystemError, SystemResult, Uint128, WasmMsg, WasmQuery,
+};
+use oraiswap::asset::{Asset, AssetInfo, PairInfo, ORAI_DENOM};
+use oraiswap::converter::TokenInfo;
 use oraiswap::create_entry_points_testing;
+use oraiswap::error::ContractError;
+use oraiswap::factory::{ConfigResponse as FactoryConfigResponse, QueryMsg as FactoryQueryMsg};
+use oraiswap::rebate::ExecuteMsg as RebateExecuteMsg;
 use oraiswap::router::{
-    ExecuteMsg, InstantiateMsg, QueryMsg, SimulateSwapOperationsResponse, SwapOperation,
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg,
+    NetReceiveEstimateResponse, QueryMsg, SimulateSwapOperationsResponse, SwapOperation,
 };
 
+use crate::contract::{execute, instantiate, migrate, query_config};
+
+use oraiswap::testing::router_harness::{RouterHarness, RouterHarnessContracts};
 use oraiswap::testing::{MockApp, ATOM_DENOM};
 
 #[test]
@@ -77,12 +91,13 @@ fn simulate_swap_operations_test() {
         ],
         slippage_tolerance: None,
         receiver: None,
+        auto_stake: None,
     };
 
     let _res = app
         .execute(
             Addr::unchecked("addr0000"),
-            pair_addr,
+            pair_addr.clone(),
             &msg,
             &[
                 Coin {
@@ -100,6 +115,8 @@ fn simulate_swap_operations_test() {
     let msg = InstantiateMsg {
         factory_addr: app.factory_addr.clone(),
         factory_addr_v2: Addr::unchecked("addr0000_v2"),
+        converter_addr: None,
+        guardian: None,
     };
 
     let code_id = app.upload(Box::new(create_entry_points_testing!(crate)));
@@ -109,20 +126,49 @@ fn simulate_swap_operations_test() {
         .instantiate(code_id, Addr::unchecked("addr0000"), &msg, &[], "router")
         .unwrap();
 
+    let operations = vec![SwapOperation::OraiSwap {
+        offer_asset_info: AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        ask_asset_info: AssetInfo::NativeToken {
+            denom: ATOM_DENOM.to_string(),
+        },
+    }];
     let msg = QueryMsg::SimulateSwapOperations {
         offer_amount: Uint128::from(100u128),
-        operations: vec![SwapOperation::OraiSwap {
-            offer_asset_info: AssetInfo::NativeToken {
-                denom: ORAI_DENOM.to_string(),
-            },
-            ask_asset_info: AssetInfo::NativeToken {
-                denom: ATOM_DENOM.to_string(),
-            },
-        }],
+        operations: operations.clone(),
     };
 
-    let res: SimulateSwapOperationsResponse = app.query(router_addr, &msg).unwrap();
-    println!("{:?}", res);
+    let res: SimulateSwapOperationsResponse = app.query(router_addr.clone(), &msg).unwrap();
+
+    // the hop shows where the amount differs from the spot estimate
+    assert_eq!(res.hops.len(), 1);
+    let hop = &res.hops[0];
+    assert_eq!(hop.venue, pair_addr);
+    assert_eq!(hop.offer_amount, Uint128::from(100u128));
+    assert_eq!(hop.return_amount, res.amount);
+    assert!(!hop.spread_amount.is_zero());
+    assert_eq!(
+        hop.operation,
+        SwapOperation::OraiSwap {
+            offer_asset_info: asset_infos[0].clone(),
+            ask_asset_info: asset_infos[1].clone(),
+        }
+    );
+
+    // the estimate is the simulated amount, with the taxes of the swap hops summed up
+    let estimate: NetReceiveEstimateResponse = app
+        .query(
+            router_addr,
+            &QueryMsg::NetReceiveEstimate {
+                offer_amount: Uint128::from(100u128),
+                operations,
+            },
+        )
+        .unwrap();
+    assert_eq!(estimate.amount, res.amount);
+    assert_eq!(estimate.tax_amount, hop.tax_amount);
+    assert_eq!(estimate.convert_fee_amount, Uint128::zero());
 }
 
 #[test]
@@ -210,6 +256,7 @@ fn execute_swap_operations() {
         ],
         slippage_tolerance: None,
         receiver: None,
+        auto_stake: None,
     };
 
     // set allowance
@@ -254,6 +301,7 @@ fn execute_swap_operations() {
         ],
         slippage_tolerance: None,
         receiver: None,
+        auto_stake: None,
     };
 
     // set allowance
@@ -284,6 +332,8 @@ fn execute_swap_operations() {
     let msg = InstantiateMsg {
         factory_addr: app.factory_addr.clone(),
         factory_addr_v2: Addr::unchecked("addr0000_v2"),
+        converter_addr: None,
+        guardian: None,
     };
 
     let code_id = app.upload(Box::new(create_entry_points_testing!(crate)));
@@ -297,6 +347,7 @@ fn execute_swap_operations() {
         operations: vec![],
         minimum_receive: None,
         to: None,
+        callback: None,
     };
 
     let res = app.execute(Addr::unchecked("addr0000"), router_addr.clone(), &msg, &[]);
@@ -323,6 +374,7 @@ fn execute_swap_operations() {
         ],
         minimum_receive: None,
         to: None,
+        callback: None,
     };
 
     let res = app
@@ -345,3 +397,813 @@ fn execute_swap_operations() {
 
     println!("{:?}", res.events);
 }
+
+#[test]
+fn execute_convert_operations() {
+    let mut app = MockApp::new(&[(
+        &"addr0000".to_string(),
+        &[Coin {
+            denom: ORAI_DENOM.to_string(),
+            amount: Uint128::from(1000u128),
+        }],
+    )]);
+
+    app.set_oracle_contract(Box::new(create_entry_points_testing!(oraiswap_oracle)));
+
+    app.set_token_contract(Box::new(create_entry_points_testing!(oraiswap_token)));
+
+    app.set_factory_and_pair_contract(
+        Box::new(
+            create_entry_points_testing!(oraiswap_factory)
+                .with_reply(oraiswap_factory::contract::reply),
+        ),
+        Box::new(
+            create_entry_points_testing!(oraiswap_pair).with_reply(oraiswap_pair::contract::reply),
+        ),
+    );
+
+    let asset_addr = app.create_token("asset");
+
+    app.set_token_balances(&[(
+        &"asset".to_string(),
+        &[(&"addr0000".to_string(), &Uint128::from(1000u128))],
+    )]);
+
+    // converter: 1 orai => 1 asset
+    let converter_code_id = app.upload(Box::new(create_entry_points_testing!(oraiswap_converter)));
+    let converter_addr = app
+        .instantiate(
+            converter_code_id,
+            Addr::unchecked("addr0000"),
+            &oraiswap::converter::InstantiateMsg {},
+            &[],
+            "converter",
+        )
+        .unwrap();
+
+    app.execute(
+        Addr::unchecked("addr0000"),
+        converter_addr.clone(),
+        &oraiswap::converter::ExecuteMsg::UpdatePair {
+            from: TokenInfo {
+                info: AssetInfo::NativeToken {
+                    denom: ORAI_DENOM.to_string(),
+                },
+                decimals: 6,
+            },
+            to: TokenInfo {
+                info: AssetInfo::Token {
+                    contract_addr: asset_addr.clone(),
+                },
+                decimals: 6,
+            },
+        },
+        &[],
+    )
+    .unwrap();
+
+    // fund converter with asset token
+    app.execute(
+        Addr::unchecked("addr0000"),
+        asset_addr.clone(),
+        &cw20::Cw20ExecuteMsg::Transfer {
+            recipient: converter_addr.to_string(),
+            amount: Uint128::from(500u128),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let code_id = app.upload(Box::new(create_entry_points_testing!(crate)));
+    let router_addr = app
+        .instantiate(
+            code_id,
+            Addr::unchecked("addr0000"),
+            &InstantiateMsg {
+                factory_addr: app.factory_addr.clone(),
+                factory_addr_v2: Addr::unchecked("addr0000_v2"),
+                converter_addr: Some(converter_addr.clone()),
+                guardian: None,
+            },
+            &[],
+            "router",
+        )
+        .unwrap();
+
+    let operations = vec![SwapOperation::Convert {
+        from: AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        to: AssetInfo::Token {
+            contract_addr: asset_addr.clone(),
+        },
+    }];
+
+    let res: SimulateSwapOperationsResponse = app
+        .query(
+            router_addr.clone(),
+            &QueryMsg::SimulateSwapOperations {
+                offer_amount: Uint128::from(100u128),
+                operations: operations.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(res.amount, Uint128::from(100u128));
+    assert_eq!(res.hops[0].venue, converter_addr);
+
+    // convert orai => asset
+    app.execute(
+        Addr::unchecked("addr0000"),
+        router_addr.clone(),
+        &ExecuteMsg::ExecuteSwapOperations {
+            operations,
+            minimum_receive: Some(Uint128::from(100u128)),
+            to: None,
+            callback: None,
+        },
+        &[Coin {
+            denom: ORAI_DENOM.to_string(),
+            amount: Uint128::from(100u128),
+        }],
+    )
+    .unwrap();
+
+    let res: cw20::BalanceResponse = app
+        .query(
+            asset_addr.clone(),
+            &cw20::Cw20QueryMsg::Balance {
+                address: "addr0000".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(res.balance, Uint128::from(600u128));
+
+    // convert back asset => orai, resolved by the converter as a reverse conversion
+    app.execute(
+        Addr::unchecked("addr0000"),
+        asset_addr.clone(),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: router_addr.to_string(),
+            amount: Uint128::from(50u128),
+            msg: to_binary(&Cw20HookMsg::ExecuteSwapOperations {
+                operations: vec![SwapOperation::Convert {
+                    from: AssetInfo::Token {
+                        contract_addr: asset_addr.clone(),
+                    },
+                    to: AssetInfo::NativeToken {
+                        denom: ORAI_DENOM.to_string(),
+                    },
+                }],
+                minimum_receive: Some(Uint128::from(50u128)),
+                to: None,
+                callback: None,
+            })
+            .unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    assert_eq!(
+        app.query_balance(Addr::unchecked("addr0000"), ORAI_DENOM.to_string())
+            .unwrap(),
+        Uint128::from(950u128)
+    );
+}
+
+#[test]
+fn pause_swap_operations() {
+    let mut app = MockApp::new(&[(
+        &"addr0000".to_string(),
+        &[Coin {
+            denom: ORAI_DENOM.to_string(),
+            amount: Uint128::from(1000u128),
+        }],
+    )]);
+
+    let msg = InstantiateMsg {
+        factory_addr: app.factory_addr.clone(),
+        factory_addr_v2: Addr::unchecked("addr0000_v2"),
+        converter_addr: None,
+        guardian: Some(Addr::unchecked("guardian")),
+    };
+
+    let code_id = app.upload(Box::new(create_entry_points_testing!(crate)));
+    let router_addr = app
+        .instantiate(code_id, Addr::unchecked("addr0000"), &msg, &[], "router")
+        .unwrap();
+
+    // only guardian can pause
+    let res = app.execute(
+        Addr::unchecked("addr0000"),
+        router_addr.clone(),
+        &ExecuteMsg::Pause {},
+        &[],
+    );
+    app.assert_fail(res);
+
+    app.execute(
+        Addr::unchecked("guardian"),
+        router_addr.clone(),
+        &ExecuteMsg::Pause {},
+        &[],
+    )
+    .unwrap();
+
+    let config: ConfigResponse = app
+        .query(router_addr.clone(), &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(config.guardian, Some(Addr::unchecked("guardian")));
+    assert!(config.paused);
+
+    let msg = ExecuteMsg::ExecuteSwapOperations {
+        operations: vec![SwapOperation::OraiSwap {
+            offer_asset_info: AssetInfo::NativeToken {
+                denom: ORAI_DENOM.to_string(),
+            },
+            ask_asset_info: AssetInfo::NativeToken {
+                denom: ATOM_DENOM.to_string(),
+            },
+        }],
+        minimum_receive: None,
+        to: None,
+        callback: None,
+    };
+    let res = app.execute(
+        Addr::unchecked("addr0000"),
+        router_addr.clone(),
+        &msg,
+        &[Coin {
+            denom: ORAI_DENOM.to_string(),
+            amount: Uint128::from(100u128),
+        }],
+    );
+    app.assert_fail(res);
+
+    app.execute(
+        Addr::unchecked("guardian"),
+        router_addr.clone(),
+        &ExecuteMsg::Unpause {},
+        &[],
+    )
+    .unwrap();
+
+    let config: ConfigResponse = app.query(router_addr, &QueryMsg::Config {}).unwrap();
+    assert!(!config.paused);
+}
+
+#[test]
+fn report_swap_volume_to_rebate_contract() {
+    let mut deps = mock_dependencies();
+
+    let msg = InstantiateMsg {
+        factory_addr: Addr::unchecked("factory"),
+        factory_addr_v2: Addr::unchecked("factory_v2"),
+        converter_addr: None,
+        guardian: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+
+    migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg {
+            converter_addr: None,
+            guardian: None,
+            rebate_contract: Some(Addr::unchecked("rebate")),
+            allowed_venues: None,
+        },
+    )
+    .unwrap();
+    let config = query_config(deps.as_ref()).unwrap();
+    assert_eq!(config.rebate_contract, Some(Addr::unchecked("rebate")));
+
+    let msg = ExecuteMsg::ExecuteSwapOperations {
+        operations: vec![SwapOperation::OraiSwap {
+            offer_asset_info: AssetInfo::NativeToken {
+                denom: ORAI_DENOM.to_string(),
+            },
+            ask_asset_info: AssetInfo::NativeToken {
+                denom: ATOM_DENOM.to_string(),
+            },
+        }],
+        minimum_receive: None,
+        to: None,
+        callback: None,
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(
+            "addr0000",
+            &[Coin {
+                denom: ORAI_DENOM.to_string(),
+                amount: Uint128::from(1000u128),
+            }],
+        ),
+        msg,
+    )
+    .unwrap();
+
+    // offer volume is recorded after the swap operations
+    assert_eq!(res.messages.len(), 2);
+    assert_eq!(
+        res.messages[1].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "rebate".to_string(),
+            funds: vec![],
+            msg: to_binary(&RebateExecuteMsg::RecordVolume {
+                trader: Addr::unchecked("addr0000"),
+                asset_info: AssetInfo::NativeToken {
+                    denom: ORAI_DENOM.to_string(),
+                },
+                amount: Uint128::from(1000u128),
+            })
+            .unwrap(),
+        })
+    );
+}
+
+#[test]
+fn reject_swap_on_venue_not_allowed() {
+    let mut deps = mock_dependencies();
+    deps.querier.update_wasm(|query| match query {
+        WasmQuery::Smart { msg, .. } => {
+            let res = match from_binary(msg).unwrap() {
+                FactoryQueryMsg::Config {} => to_binary(&FactoryConfigResponse {
+                    owner: Addr::unchecked("owner"),
+                    oracle_addr: Addr::unchecked("oracle"),
+                    pair_code_id: 1,
+                    token_code_id: 2,
+                    commission_rate: "0.003".to_string(),
+                    pair_templates: vec![],
+                    guardian: None,
+                    staking_contract: None,
+                }),
+                FactoryQueryMsg::Pair { asset_infos } => to_binary(&PairInfo {
+                    asset_infos,
+                    contract_addr: Addr::unchecked("fake_pair"),
+                    liquidity_token: Addr::unchecked("liquidity"),
+                    oracle_addr: Addr::unchecked("oracle"),
+                    commission_rate: "0.003".to_string(),
+                    lp_denom: None,
+                }),
+                _ => panic!("unexpected query"),
+            };
+            SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+        }
+        _ => panic!("unexpected query"),
+    });
+
+    let msg = InstantiateMsg {
+        factory_addr: Addr::unchecked("factory"),
+        factory_addr_v2: Addr::unchecked("factory_v2"),
+        converter_addr: None,
+        guardian: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+
+    migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg {
+            converter_addr: None,
+            guardian: None,
+            rebate_contract: None,
+            allowed_venues: Some(vec![Addr::unchecked("pair0000")]),
+        },
+    )
+    .unwrap();
+    let config = query_config(deps.as_ref()).unwrap();
+    assert_eq!(
+        config.allowed_venues,
+        Some(vec![Addr::unchecked("pair0000")])
+    );
+
+    let msg = ExecuteMsg::ExecuteSwapOperation {
+        operation: SwapOperation::OraiSwap {
+            offer_asset_info: AssetInfo::NativeToken {
+                denom: ORAI_DENOM.to_string(),
+            },
+            ask_asset_info: AssetInfo::NativeToken {
+                denom: ATOM_DENOM.to_string(),
+            },
+        },
+        to: None,
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        msg,
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Generic error: fake_pair is not an allowed venue"
+    );
+}
+
+/// factory resolving every pair of assets to the pair contract
+fn mock_factory_query(pair_addr: &'static str) -> impl Fn(&WasmQuery) -> QuerierResult {
+    move |query| match query {
+        WasmQuery::Smart { msg, .. } => {
+            let res = match from_binary(msg).unwrap() {
+                FactoryQueryMsg::Config {} => to_binary(&FactoryConfigResponse {
+                    owner: Addr::unchecked("owner"),
+                    oracle_addr: Addr::unchecked("oracle"),
+                    pair_code_id: 1,
+                    token_code_id: 2,
+                    commission_rate: "0.003".to_string(),
+                    pair_templates: vec![],
+                    guardian: None,
+                    staking_contract: None,
+                }),
+                FactoryQueryMsg::Pair { asset_infos } => to_binary(&PairInfo {
+                    asset_infos,
+                    contract_addr: Addr::unchecked(pair_addr),
+                    liquidity_token: Addr::unchecked("liquidity"),
+                    oracle_addr: Addr::unchecked("oracle"),
+                    commission_rate: "0.003".to_string(),
+                    lp_denom: None,
+                }),
+                _ => panic!("unexpected query"),
+            };
+            SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+        }
+        _ => panic!("unexpected query"),
+    }
+}
+
+#[test]
+fn cache_pair_addrs() {
+    let mut deps = mock_dependencies_with_balance(&[Coin {
+        denom: ORAI_DENOM.to_string(),
+        amount: Uint128::from(1000u128),
+    }]);
+    deps.querier.update_wasm(mock_factory_query("pair0000"));
+
+    let msg = InstantiateMsg {
+        factory_addr: Addr::unchecked("factory"),
+        factory_addr_v2: Addr::unchecked("factory_v2"),
+        converter_addr: None,
+        guardian: Some(Addr::unchecked("guardian")),
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+
+    let asset_infos = [
+        AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: ATOM_DENOM.to_string(),
+        },
+    ];
+    let msg = ExecuteMsg::ExecuteSwapOperation {
+        operation: SwapOperation::OraiSwap {
+            offer_asset_info: asset_infos[0].clone(),
+            ask_asset_info: asset_infos[1].clone(),
+        },
+        to: None,
+    };
+    let swap_venue = |res: &cosmwasm_std::Response| match &res.messages[0].msg {
+        CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, .. }) => contract_addr.clone(),
+        _ => panic!("unexpected message"),
+    };
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        msg.clone(),
+    )
+    .unwrap();
+    assert_eq!(swap_venue(&res), "pair0000");
+
+    // the cached pair is used even when the factory resolves another pair
+    deps.querier.update_wasm(mock_factory_query("pair0001"));
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        msg.clone(),
+    )
+    .unwrap();
+    assert_eq!(swap_venue(&res), "pair0000");
+
+    // only the guardian can invalidate the cache
+    let invalidate_msg = ExecuteMsg::InvalidatePairCache {
+        asset_infos: [asset_infos[1].clone(), asset_infos[0].clone()],
+    };
+    for sender in ["addr0000", "factory"] {
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(sender, &[]),
+            invalidate_msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+    }
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("guardian", &[]),
+        invalidate_msg,
+    )
+    .unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        msg,
+    )
+    .unwrap();
+    assert_eq!(swap_venue(&res), "pair0001");
+}
+
+#[test]
+fn execute_cycle() {
+    let mut deps = mock_dependencies();
+
+    let msg = InstantiateMsg {
+        factory_addr: Addr::unchecked("factory"),
+        factory_addr_v2: Addr::unchecked("factory_v2"),
+        converter_addr: None,
+        guardian: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+
+    let orai = AssetInfo::NativeToken {
+        denom: ORAI_DENOM.to_string(),
+    };
+    let atom = AssetInfo::NativeToken {
+        denom: ATOM_DENOM.to_string(),
+    };
+    let funds = [Coin {
+        denom: ORAI_DENOM.to_string(),
+        amount: Uint128::from(1000u128),
+    }];
+
+    // route does not come back to the offer asset
+    let msg = ExecuteMsg::ExecuteCycle {
+        operations: vec![SwapOperation::OraiSwap {
+            offer_asset_info: orai.clone(),
+            ask_asset_info: atom.clone(),
+        }],
+        min_profit: Uint128::from(10u128),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &funds),
+        msg,
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidCycle {});
+
+    let msg = ExecuteMsg::ExecuteCycle {
+        operations: vec![
+            SwapOperation::OraiSwap {
+                offer_asset_info: orai.clone(),
+                ask_asset_info: atom.clone(),
+            },
+            SwapOperation::OraiSwap {
+                offer_asset_info: atom,
+                ask_asset_info: orai.clone(),
+            },
+        ],
+        min_profit: Uint128::from(10u128),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &funds),
+        msg,
+    )
+    .unwrap();
+
+    // the sender must receive back the offer amount plus the profit
+    assert_eq!(res.messages.len(), 3);
+    assert_eq!(
+        res.messages[2].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+            funds: vec![],
+            msg: to_binary(&ExecuteMsg::AssertMinimumReceive {
+                asset_info: orai,
+                prev_balance: Uint128::zero(),
+                minimum_receive: Uint128::from(1010u128),
+                receiver: Addr::unchecked("addr0000"),
+            })
+            .unwrap(),
+        })
+    );
+}
+
+#[test]
+fn forward_callback_to_contract() {
+    let mut deps = mock_dependencies_with_balance(&[Coin {
+        denom: ATOM_DENOM.to_string(),
+        amount: Uint128::from(500u128),
+    }]);
+    // only the merchant is a contract
+    deps.querier.update_wasm(|query| match query {
+        WasmQuery::ContractInfo { contract_addr } if contract_addr == "merchant" => {
+            SystemResult::Ok(ContractResult::Ok(
+                to_binary(&ContractInfoResponse::new(1, "creator")).unwrap(),
+            ))
+        }
+        WasmQuery::ContractInfo { contract_addr } => {
+            SystemResult::Err(SystemError::NoSuchContract {
+                addr: contract_addr.to_string(),
+            })
+        }
+        _ => panic!("unexpected query"),
+    });
+
+    let msg = InstantiateMsg {
+        factory_addr: Addr::unchecked("factory"),
+        factory_addr_v2: Addr::unchecked("factory_v2"),
+        converter_addr: None,
+        guardian: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+
+    let operation = SwapOperation::OraiSwap {
+        offer_asset_info: AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        ask_asset_info: AssetInfo::NativeToken {
+            denom: ATOM_DENOM.to_string(),
+        },
+    };
+    let funds = [Coin {
+        denom: ORAI_DENOM.to_string(),
+        amount: Uint128::from(1000u128),
+    }];
+    let callback = Binary::from(br#"{"pay":{"invoice_id":"1"}}"#.as_slice());
+
+    // the callback is dropped for wallets, which receive the output from the last hop
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &funds),
+        ExecuteMsg::ExecuteSwapOperations {
+            operations: vec![operation.clone()],
+            minimum_receive: None,
+            to: Some(Addr::unchecked("addr0001")),
+            callback: Some(callback.clone()),
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+            funds: vec![],
+            msg: to_binary(&ExecuteMsg::ExecuteSwapOperation {
+                operation: operation.clone(),
+                to: Some(Addr::unchecked("addr0001")),
+            })
+            .unwrap(),
+        })
+    );
+
+    // for contracts the last hop pays the router, which forwards the output with the callback
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &funds),
+        ExecuteMsg::ExecuteSwapOperations {
+            operations: vec![operation.clone()],
+            minimum_receive: Some(Uint128::from(400u128)),
+            to: Some(Addr::unchecked("merchant")),
+            callback: Some(callback.clone()),
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 2);
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+            funds: vec![],
+            msg: to_binary(&ExecuteMsg::ExecuteSwapOperation {
+                operation,
+                to: None,
+            })
+            .unwrap(),
+        })
+    );
+    let forward_msg = ExecuteMsg::ForwardCallback {
+        asset_info: AssetInfo::NativeToken {
+            denom: ATOM_DENOM.to_string(),
+        },
+        prev_balance: Uint128::from(500u128),
+        minimum_receive: Some(Uint128::from(400u128)),
+        receiver: Addr::unchecked("merchant"),
+        callback: callback.clone(),
+    };
+    assert_eq!(
+        res.messages[1].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+            funds: vec![],
+            msg: to_binary(&forward_msg).unwrap(),
+        })
+    );
+
+    // internal use only
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        forward_msg,
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // the swap returned 450 atom to the router
+    deps.querier.update_balance(
+        MOCK_CONTRACT_ADDR,
+        vec![Coin {
+            denom: ATOM_DENOM.to_string(),
+            amount: Uint128::from(950u128),
+        }],
+    );
+    let forward_msg = |minimum_receive: u128| ExecuteMsg::ForwardCallback {
+        asset_info: AssetInfo::NativeToken {
+            denom: ATOM_DENOM.to_string(),
+        },
+        prev_balance: Uint128::from(500u128),
+        minimum_receive: Some(Uint128::from(minimum_receive)),
+        receiver: Addr::unchecked("merchant"),
+        callback: callback.clone(),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        forward_msg(460),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::SwapAssertionFailure {
+            minium_receive: Uint128::from(460u128),
+            swap_amount: Uint128::from(450u128),
+        }
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        forward_msg(400),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "merchant".to_string(),
+            funds: vec![Coin {
+                denom: ATOM_DENOM.to_string(),
+                amount: Uint128::from(450u128),
+            }],
+            msg: callback,
+        })
+    );
+}
+
+#[test]
+fn simulation_parity_with_random_swaps() {
+    let mut harness = RouterHarness::new(
+        RouterHarnessContracts {
+            oracle: Box::new(create_entry_points_testing!(oraiswap_oracle)),
+            token: Box::new(create_entry_points_testing!(oraiswap_token)),
+            factory: Box::new(
+                create_entry_points_testing!(oraiswap_factory)
+                    .with_reply(oraiswap_factory::contract::reply),
+            ),
+            pair: Box::new(
+                create_entry_points_testing!(oraiswap_pair)
+                    .with_reply(oraiswap_pair::contract::reply),
+            ),
+            router: Box::new(create_entry_points_testing!(crate)),
+        },
+        &[ORAI_DENOM],
+        &["ASSETA", "ASSETB"],
+        Uint128::from(1000000000u128),
+        42,
+    );
+
+    // native and cw20 offers, up to 3 hops
+    let results = harness.run_random_swaps(20, 3, Uint128::from(1000000u128));
+    assert_eq!(results.len(), 20);
+    assert!(results
+        .iter()
+        .all(|result| !result.received_amount.is_zero()));
+}
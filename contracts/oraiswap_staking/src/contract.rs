@@ -1,27 +1,34 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 
-// use crate::migration::migrate_rewards_store;
+use crate::migration::{run_migrations, CONTRACT_NAME, CONTRACT_VERSION};
 use crate::rewards::{
-    deposit_reward, process_reward_assets, query_all_reward_infos, query_reward_info,
-    withdraw_reward, withdraw_reward_others,
+    clawback_unallocated_rewards, deposit_reward, process_reward_assets, query_all_reward_infos,
+    query_projected_reward, query_reward_info, withdraw_reward, withdraw_reward_all,
+    withdraw_reward_others,
 };
 use crate::staking::{auto_stake, auto_stake_hook, bond, unbond, update_list_stakers};
 use crate::state::{
-    read_config, read_pool_info, read_rewards_per_sec, stakers_read, store_config, store_pool_info,
-    store_rewards_per_sec, Config, MigrationParams, PoolInfo,
+    read_config, read_hooks, read_max_pending_reward, read_max_total_bond, read_paused,
+    read_pool_info, read_pool_infos, read_rewards_per_sec, read_stake_checkpoint,
+    read_staker_whitelist, read_total_bond_amount, stakers_read, store_config, store_hooks,
+    store_max_pending_reward, store_max_total_bond, store_paused, store_pool_info,
+    store_rewards_per_sec, store_staker_whitelist, Config, MigrationParams, PoolInfo,
 };
 
 use cosmwasm_std::{
-    from_binary, to_binary, Addr, Binary, CanonicalAddr, Decimal, Deps, DepsMut, Env, MessageInfo,
-    Order, Response, StdError, StdResult, Uint128,
+    from_binary, to_binary, Addr, Api, Binary, CanonicalAddr, Decimal, Deps, DepsMut, Env,
+    MessageInfo, Order, Response, StdError, StdResult, Storage, Uint128,
 };
-use oraiswap::asset::{Asset, AssetInfo, AssetRaw, ORAI_DENOM};
+use oraiswap::asset::{Asset, AssetInfo, AssetInfoRaw, AssetRaw, ORAI_DENOM};
+use oraiswap::oracle::OracleContract;
+use oraiswap::querier::{query_pair_info, query_pool, query_token_balance};
 use oraiswap::staking::{
-    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, PoolInfoResponse,
-    QueryMsg, RewardsPerSecResponse,
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, HooksResponse, InstantiateMsg, MigrateMsg,
+    PoolInfoResponse, PoolOverviewResponse, QueryMsg, RewardsPerSecResponse, VotingPowerResponse,
 };
 
+use cw2::set_contract_version;
 use cw20::Cw20ReceiveMsg;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -42,8 +49,10 @@ pub fn instantiate(
             factory_addr: deps.api.addr_canonicalize(msg.factory_addr.as_str())?,
             // default base_denom pass to factory is orai token
             base_denom: msg.base_denom.unwrap_or(ORAI_DENOM.to_string()),
+            guardian: None,
         },
     )?;
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     Ok(Response::default())
 }
@@ -51,12 +60,37 @@ pub fn instantiate(
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
     match msg {
-        ExecuteMsg::Receive(msg) => receive_cw20(deps, info, msg),
-        ExecuteMsg::UpdateConfig { rewarder, owner } => update_config(deps, info, owner, rewarder),
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::UpdateConfig {
+            rewarder,
+            owner,
+            guardian,
+        } => update_config(deps, info, owner, rewarder, guardian),
+        ExecuteMsg::Pause {} => set_paused(deps, info, true),
+        ExecuteMsg::Unpause {} => set_paused(deps, info, false),
         ExecuteMsg::UpdateRewardsPerSec { asset_info, assets } => {
             update_rewards_per_sec(deps, info, asset_info, assets)
         }
+        ExecuteMsg::UpdateMaxPendingReward {
+            asset_info,
+            max_pending_reward,
+        } => update_max_pending_reward(deps, info, asset_info, max_pending_reward),
+        ExecuteMsg::UpdateMaxTotalBond {
+            asset_info,
+            max_total_bond,
+        } => update_max_total_bond(deps, info, asset_info, max_total_bond),
+        ExecuteMsg::UpdateStakerWhitelist {
+            asset_info,
+            staker_whitelist,
+        } => update_staker_whitelist(deps, info, asset_info, staker_whitelist),
+        ExecuteMsg::ClawbackUnallocatedRewards { asset_info } => {
+            clawback_unallocated_rewards(deps, info, asset_info)
+        }
         ExecuteMsg::DepositReward { rewards } => deposit_reward(deps, info, rewards),
+        ExecuteMsg::SweepUnaccountedTokens {
+            asset_info,
+            recipient,
+        } => sweep_unaccounted_tokens(deps, env, info, asset_info, recipient),
         ExecuteMsg::RegisterAsset {
             asset_info,
             staking_token,
@@ -65,10 +99,18 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
             asset_info,
             new_staking_token,
         } => deprecate_staking_token(deps, info, asset_info, new_staking_token),
+        ExecuteMsg::BondNative { asset_info } => bond_native(deps, env, info, asset_info),
         ExecuteMsg::Unbond { asset_info, amount } => {
             unbond(deps, env, info.sender, asset_info, amount)
         }
-        ExecuteMsg::Withdraw { asset_info } => withdraw_reward(deps, env, info, asset_info),
+        ExecuteMsg::Withdraw {
+            asset_info,
+            auto_bond,
+        } => withdraw_reward(deps, env, info, asset_info, auto_bond),
+        ExecuteMsg::WithdrawAll {
+            asset_infos,
+            auto_bond,
+        } => withdraw_reward_all(deps, env, info, asset_infos, auto_bond),
         ExecuteMsg::WithdrawOthers {
             asset_info,
             staker_addrs,
@@ -76,7 +118,10 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
         ExecuteMsg::AutoStake {
             assets,
             slippage_tolerance,
-        } => auto_stake(deps, env, info, assets, slippage_tolerance),
+        } => {
+            assert_not_paused(deps.storage)?;
+            auto_stake(deps, env, info, assets, slippage_tolerance)
+        }
         ExecuteMsg::AutoStakeHook {
             asset_info,
             staking_token,
@@ -95,47 +140,100 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
             asset_info,
             stakers,
         } => update_list_stakers(deps, env, info, asset_info, stakers),
+        ExecuteMsg::AddHook { contract_addr } => add_hook(deps, info, contract_addr),
+        ExecuteMsg::RemoveHook { contract_addr } => remove_hook(deps, info, contract_addr),
     }
 }
 
 pub fn receive_cw20(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     cw20_msg: Cw20ReceiveMsg,
 ) -> StdResult<Response> {
-    match from_binary(&cw20_msg.msg) {
-        Ok(Cw20HookMsg::Bond { asset_info }) => {
-            // check permission
-            let asset_key = asset_info.to_vec(deps.api)?;
-            let pool_info: PoolInfo = read_pool_info(deps.storage, &asset_key)?;
-
-            // only staking token contract can execute this message
-            let token_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
-            if pool_info.staking_token != token_raw {
-                // if user is trying to bond old token, return friendly error message
-                if let Some(params) = pool_info.migration_params {
-                    if params.deprecated_staking_token == token_raw {
-                        let staking_token_addr =
-                            deps.api.addr_humanize(&pool_info.staking_token)?;
-                        return Err(StdError::generic_err(format!(
-                            "The staking token for this asset has been migrated to {}",
-                            staking_token_addr
-                        )));
-                    }
-                }
-
-                return Err(StdError::generic_err("unauthorized"));
-            }
+    let (asset_info, staker_addr) = match from_binary(&cw20_msg.msg) {
+        Ok(Cw20HookMsg::Bond { asset_info }) => (asset_info, Addr::unchecked(cw20_msg.sender)),
+        Ok(Cw20HookMsg::BondFor {
+            asset_info,
+            staker_addr,
+        }) => (asset_info, staker_addr),
+        Err(_) => return Err(StdError::generic_err("invalid cw20 hook message")),
+    };
+
+    assert_not_paused(deps.storage)?;
+
+    // check permission
+    let asset_key = asset_info.to_vec(deps.api)?;
+    let pool_info: PoolInfo = read_pool_info(deps.storage, &asset_key)?;
+
+    // only staking token contract can execute this message
+    let token_raw = AssetInfoRaw::Token {
+        contract_addr: deps.api.addr_canonicalize(info.sender.as_str())?,
+    };
+    assert_staking_token(deps.api, &pool_info, &token_raw)?;
+
+    // the tokens are transferred before the hook is called, so the balance of the contract must cover
+    // the bonded amounts plus the new bond, otherwise the hook does not come with real tokens
+    let total_bond_amount = read_total_bond_amount(deps.storage, &token_raw)?;
+    let balance = query_token_balance(
+        &deps.querier,
+        info.sender.clone(),
+        env.contract.address.clone(),
+    )?;
+    if balance < total_bond_amount.checked_add(cw20_msg.amount)? {
+        return Err(StdError::generic_err(
+            "bond amount is not received by the contract",
+        ));
+    }
+
+    bond(deps, env, staker_addr, asset_info, cw20_msg.amount)
+}
 
-            bond(
-                deps,
-                Addr::unchecked(cw20_msg.sender),
-                asset_info,
-                cw20_msg.amount,
-            )
+pub fn bond_native(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asset_info: AssetInfo,
+) -> StdResult<Response> {
+    assert_not_paused(deps.storage)?;
+
+    let asset_key = asset_info.to_vec(deps.api)?;
+    let pool_info: PoolInfo = read_pool_info(deps.storage, &asset_key)?;
+
+    // only the native staking token of the pool can be bonded, and nothing else can be sent along
+    let (denom, amount) = match &info.funds[..] {
+        [coin] if !coin.amount.is_zero() => (coin.denom.clone(), coin.amount),
+        _ => {
+            return Err(StdError::generic_err(
+                "must send exactly one native staking token",
+            ))
+        }
+    };
+    assert_staking_token(deps.api, &pool_info, &AssetInfoRaw::NativeToken { denom })?;
+
+    bond(deps, env, info.sender, asset_info, amount)
+}
+
+fn assert_staking_token(
+    api: &dyn Api,
+    pool_info: &PoolInfo,
+    staking_token: &AssetInfoRaw,
+) -> StdResult<()> {
+    if !pool_info.staking_token.eq(staking_token) {
+        // if user is trying to bond old token, return friendly error message
+        if let Some(params) = &pool_info.migration_params {
+            if params.deprecated_staking_token.eq(staking_token) {
+                return Err(StdError::generic_err(format!(
+                    "The staking token for this asset has been migrated to {}",
+                    pool_info.staking_token.to_normal(api)?
+                )));
+            }
         }
-        Err(_) => Err(StdError::generic_err("invalid cw20 hook message")),
+
+        return Err(StdError::generic_err("unauthorized"));
     }
+
+    Ok(())
 }
 
 pub fn update_config(
@@ -143,6 +241,7 @@ pub fn update_config(
     info: MessageInfo,
     owner: Option<Addr>,
     rewarder: Option<Addr>,
+    guardian: Option<Addr>,
 ) -> StdResult<Response> {
     let mut config: Config = read_config(deps.storage)?;
 
@@ -158,10 +257,77 @@ pub fn update_config(
         config.rewarder = deps.api.addr_canonicalize(rewarder.as_str())?;
     }
 
+    if let Some(guardian) = guardian {
+        config.guardian = Some(deps.api.addr_canonicalize(guardian.as_str())?);
+    }
+
     store_config(deps.storage, &config)?;
     Ok(Response::new().add_attribute("action", "update_config"))
 }
 
+fn add_hook(deps: DepsMut, info: MessageInfo, contract_addr: Addr) -> StdResult<Response> {
+    let config: Config = read_config(deps.storage)?;
+
+    if deps.api.addr_canonicalize(info.sender.as_str())? != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let contract_addr_raw = deps.api.addr_canonicalize(contract_addr.as_str())?;
+    let mut hooks = read_hooks(deps.storage)?;
+    if hooks.contains(&contract_addr_raw) {
+        return Err(StdError::generic_err("Hook was already registered"));
+    }
+    hooks.push(contract_addr_raw);
+    store_hooks(deps.storage, hooks)?;
+
+    Ok(Response::new().add_attributes([
+        ("action", "add_hook"),
+        ("contract_addr", contract_addr.as_str()),
+    ]))
+}
+
+fn remove_hook(deps: DepsMut, info: MessageInfo, contract_addr: Addr) -> StdResult<Response> {
+    let config: Config = read_config(deps.storage)?;
+
+    if deps.api.addr_canonicalize(info.sender.as_str())? != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let contract_addr_raw = deps.api.addr_canonicalize(contract_addr.as_str())?;
+    let mut hooks = read_hooks(deps.storage)?;
+    if !hooks.contains(&contract_addr_raw) {
+        return Err(StdError::generic_err("Hook was not registered"));
+    }
+    hooks.retain(|hook| hook != &contract_addr_raw);
+    store_hooks(deps.storage, hooks)?;
+
+    Ok(Response::new().add_attributes([
+        ("action", "remove_hook"),
+        ("contract_addr", contract_addr.as_str()),
+    ]))
+}
+
+// owner or guardian can pause and unpause bonding
+fn set_paused(deps: DepsMut, info: MessageInfo, paused: bool) -> StdResult<Response> {
+    let config: Config = read_config(deps.storage)?;
+    let sender_addr_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+
+    if sender_addr_raw != config.owner && config.guardian != Some(sender_addr_raw) {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    store_paused(deps.storage, paused)?;
+
+    Ok(Response::new().add_attribute("action", if paused { "pause" } else { "unpause" }))
+}
+
+fn assert_not_paused(storage: &dyn Storage) -> StdResult<()> {
+    if read_paused(storage) {
+        return Err(StdError::generic_err("contract is paused"));
+    }
+    Ok(())
+}
+
 // need to withdraw all rewards of the stakers belong to the pool
 // may need to call withdraw from backend side by querying all stakers with pagination in case out of gas
 fn update_rewards_per_sec(
@@ -210,11 +376,135 @@ fn update_rewards_per_sec(
     Ok(Response::new().add_attribute("action", "update_rewards_per_sec"))
 }
 
+fn update_max_pending_reward(
+    deps: DepsMut,
+    info: MessageInfo,
+    asset_info: AssetInfo,
+    max_pending_reward: Option<Uint128>,
+) -> StdResult<Response> {
+    let config: Config = read_config(deps.storage)?;
+
+    if deps.api.addr_canonicalize(info.sender.as_str())? != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let asset_key = asset_info.to_vec(deps.api)?;
+    // the pool must be registered
+    read_pool_info(deps.storage, &asset_key)?;
+
+    store_max_pending_reward(deps.storage, &asset_key, max_pending_reward)?;
+
+    Ok(Response::new().add_attribute("action", "update_max_pending_reward"))
+}
+
+fn update_max_total_bond(
+    deps: DepsMut,
+    info: MessageInfo,
+    asset_info: AssetInfo,
+    max_total_bond: Option<Uint128>,
+) -> StdResult<Response> {
+    let config: Config = read_config(deps.storage)?;
+
+    if deps.api.addr_canonicalize(info.sender.as_str())? != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let asset_key = asset_info.to_vec(deps.api)?;
+    // the pool must be registered
+    read_pool_info(deps.storage, &asset_key)?;
+
+    // the cap only applies to new bonds, stakers above it keep their bonds
+    store_max_total_bond(deps.storage, &asset_key, max_total_bond)?;
+
+    Ok(Response::new().add_attribute("action", "update_max_total_bond"))
+}
+
+fn update_staker_whitelist(
+    deps: DepsMut,
+    info: MessageInfo,
+    asset_info: AssetInfo,
+    staker_whitelist: Option<Vec<Addr>>,
+) -> StdResult<Response> {
+    let config: Config = read_config(deps.storage)?;
+
+    if deps.api.addr_canonicalize(info.sender.as_str())? != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let asset_key = asset_info.to_vec(deps.api)?;
+    // the pool must be registered
+    read_pool_info(deps.storage, &asset_key)?;
+
+    let staker_whitelist = staker_whitelist
+        .map(|stakers| {
+            stakers
+                .iter()
+                .map(|staker| deps.api.addr_canonicalize(staker.as_str()))
+                .collect::<StdResult<Vec<CanonicalAddr>>>()
+        })
+        .transpose()?;
+    store_staker_whitelist(deps.storage, &asset_key, staker_whitelist)?;
+
+    Ok(Response::new().add_attribute("action", "update_staker_whitelist"))
+}
+
+// tokens held by the contract beyond the bonded amounts do not belong to any staker and can be recovered,
+// rewards and deprecated staking tokens are still owed to the stakers so they are never swept
+fn sweep_unaccounted_tokens(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asset_info: AssetInfo,
+    recipient: Addr,
+) -> StdResult<Response> {
+    let config: Config = read_config(deps.storage)?;
+
+    if config.owner != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let token_raw = asset_info.to_raw(deps.api)?;
+    for (asset_key, pool_info) in read_pool_infos(deps.storage)? {
+        if let Some(params) = &pool_info.migration_params {
+            if params.deprecated_staking_token.eq(&token_raw) {
+                return Err(StdError::generic_err(
+                    "can not sweep a deprecated staking token",
+                ));
+            }
+        }
+
+        let rewards_per_sec = read_rewards_per_sec(deps.storage, &asset_key).unwrap_or_default();
+        if rewards_per_sec.iter().any(|ra| ra.info.eq(&token_raw)) {
+            return Err(StdError::generic_err("can not sweep a reward token"));
+        }
+    }
+
+    let total_bond_amount = read_total_bond_amount(deps.storage, &token_raw)?;
+    let balance = asset_info.query_pool(&deps.querier, env.contract.address)?;
+    let amount = balance.checked_sub(total_bond_amount)?;
+    if amount.is_zero() {
+        return Err(StdError::generic_err("no unaccounted tokens to sweep"));
+    }
+
+    let msg = Asset {
+        info: asset_info.clone(),
+        amount,
+    }
+    .into_msg(None, &deps.querier, recipient.clone())?;
+
+    Ok(Response::new().add_message(msg).add_attributes([
+        ("action", "sweep_unaccounted_tokens"),
+        ("asset_info", &asset_info.to_string()),
+        ("recipient", recipient.as_str()),
+        ("amount", &amount.to_string()),
+    ]))
+}
+
 fn register_asset(
     deps: DepsMut,
     info: MessageInfo,
     asset_info: AssetInfo,
-    staking_token: Addr,
+    staking_token: AssetInfo,
 ) -> StdResult<Response> {
     let config: Config = read_config(deps.storage)?;
 
@@ -232,7 +522,7 @@ fn register_asset(
         deps.storage,
         &asset_key,
         &PoolInfo {
-            staking_token: deps.api.addr_canonicalize(staking_token.as_str())?,
+            staking_token: staking_token.to_raw(deps.api)?,
             total_bond_amount: Uint128::zero(),
             reward_index: Decimal::zero(),
             pending_reward: Uint128::zero(),
@@ -250,7 +540,7 @@ fn deprecate_staking_token(
     deps: DepsMut,
     info: MessageInfo,
     asset_info: AssetInfo,
-    new_staking_token: Addr,
+    new_staking_token: AssetInfo,
 ) -> StdResult<Response> {
     let config: Config = read_config(deps.storage)?;
 
@@ -267,24 +557,21 @@ fn deprecate_staking_token(
         ));
     }
 
-    let deprecated_token_addr = deps.api.addr_humanize(&pool_info.staking_token)?;
+    let deprecated_token = pool_info.staking_token.to_normal(deps.api)?;
 
     pool_info.total_bond_amount = Uint128::zero();
     pool_info.migration_params = Some(MigrationParams {
         index_snapshot: pool_info.reward_index,
         deprecated_staking_token: pool_info.staking_token,
     });
-    pool_info.staking_token = deps.api.addr_canonicalize(new_staking_token.as_str())?;
+    pool_info.staking_token = new_staking_token.to_raw(deps.api)?;
 
     store_pool_info(deps.storage, &asset_key, &pool_info)?;
 
     Ok(Response::new().add_attributes([
         ("action", "depcrecate_staking_token"),
         ("asset_info", &asset_info.to_string()),
-        (
-            "deprecated_staking_token",
-            &deprecated_token_addr.to_string(),
-        ),
+        ("deprecated_staking_token", &deprecated_token.to_string()),
         ("new_staking_token", &new_staking_token.to_string()),
     ]))
 }
@@ -306,13 +593,33 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             start_after,
             limit,
             order,
+            cursor,
         } => to_binary(&query_all_reward_infos(
             deps,
             asset_info,
             start_after,
             limit,
             order,
+            cursor,
         )?),
+        QueryMsg::ProjectedReward {
+            staker_addr,
+            asset_info,
+            seconds_ahead,
+        } => to_binary(&query_projected_reward(
+            deps,
+            staker_addr,
+            asset_info,
+            seconds_ahead,
+        )?),
+        QueryMsg::VotingPowerAt { address, height } => {
+            to_binary(&query_voting_power_at(deps, address, height)?)
+        }
+        QueryMsg::Hooks {} => to_binary(&query_hooks(deps)?),
+        QueryMsg::PoolOverview {
+            asset_info,
+            usd_denom,
+        } => to_binary(&query_pool_overview(deps, asset_info, usd_denom)?),
     }
 }
 
@@ -324,6 +631,11 @@ pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
         oracle_addr: deps.api.addr_humanize(&state.oracle_addr)?,
         factory_addr: deps.api.addr_humanize(&state.factory_addr)?,
         base_denom: state.base_denom,
+        guardian: state
+            .guardian
+            .map(|guardian| deps.api.addr_humanize(&guardian))
+            .transpose()?,
+        paused: read_paused(deps.storage),
     };
 
     Ok(resp)
@@ -334,18 +646,100 @@ pub fn query_pool_info(deps: Deps, asset_info: AssetInfo) -> StdResult<PoolInfoR
     let pool_info: PoolInfo = read_pool_info(deps.storage, &asset_key)?;
     Ok(PoolInfoResponse {
         asset_info,
-        staking_token: deps.api.addr_humanize(&pool_info.staking_token)?,
+        staking_token: pool_info.staking_token.to_normal(deps.api)?,
         total_bond_amount: pool_info.total_bond_amount,
         reward_index: pool_info.reward_index,
         pending_reward: pool_info.pending_reward,
-        migration_deprecated_staking_token: pool_info.migration_params.clone().map(|params| {
-            deps.api
-                .addr_humanize(&params.deprecated_staking_token)
-                .unwrap()
-        }),
+        migration_deprecated_staking_token: pool_info
+            .migration_params
+            .clone()
+            .map(|params| params.deprecated_staking_token.to_normal(deps.api))
+            .transpose()?,
         migration_index_snapshot: pool_info
             .migration_params
             .map(|params| params.index_snapshot),
+        max_pending_reward: read_max_pending_reward(deps.storage, &asset_key)?,
+        max_total_bond: read_max_total_bond(deps.storage, &asset_key)?,
+        staker_whitelist: read_staker_whitelist(deps.storage, &asset_key)?
+            .map(|stakers| {
+                stakers
+                    .iter()
+                    .map(|staker| deps.api.addr_humanize(staker))
+                    .collect::<StdResult<Vec<Addr>>>()
+            })
+            .transpose()?,
+    })
+}
+
+pub fn query_pool_overview(
+    deps: Deps,
+    asset_info: AssetInfo,
+    usd_denom: Option<String>,
+) -> StdResult<PoolOverviewResponse> {
+    let config = read_config(deps.storage)?;
+    let pool_info = query_pool_info(deps, asset_info.clone())?;
+    let rewards_per_sec = query_rewards_per_sec(deps, asset_info.clone())?.assets;
+
+    let base_asset_info = AssetInfo::NativeToken {
+        denom: config.base_denom.clone(),
+    };
+    let pair_info = query_pair_info(
+        &deps.querier,
+        deps.api.addr_humanize(&config.factory_addr)?,
+        &[asset_info, base_asset_info.clone()],
+    )
+    .ok();
+
+    let (reserves, lp_supply) = match &pair_info {
+        Some(pair_info) => {
+            let pool = query_pool(&deps.querier, pair_info.contract_addr.clone())?;
+            (pool.assets.to_vec(), pool.total_share)
+        }
+        None => (vec![], Uint128::zero()),
+    };
+    let bonded_reserves = reserves
+        .iter()
+        .map(|reserve| Asset {
+            info: reserve.info.clone(),
+            amount: reserve
+                .amount
+                .multiply_ratio(pool_info.total_bond_amount, lp_supply.max(Uint128::one())),
+        })
+        .collect();
+
+    // the pair keeps both reserves at the same value, so the pool is worth twice its base denom reserve
+    let total_value = match (
+        usd_denom,
+        reserves
+            .iter()
+            .find(|reserve| reserve.info == base_asset_info),
+    ) {
+        (Some(usd_denom), Some(base_reserve)) => {
+            OracleContract(deps.api.addr_humanize(&config.oracle_addr)?)
+                .query_convert_amount(
+                    &deps.querier,
+                    config.base_denom,
+                    usd_denom,
+                    base_reserve.amount.checked_mul(Uint128::from(2u128))?,
+                )
+                .map(|res| res.amount)
+                .ok()
+        }
+        _ => None,
+    };
+    let bonded_value = total_value.map(|value| {
+        value.multiply_ratio(pool_info.total_bond_amount, lp_supply.max(Uint128::one()))
+    });
+
+    Ok(PoolOverviewResponse {
+        pool_info,
+        rewards_per_sec,
+        pair_addr: pair_info.map(|pair_info| pair_info.contract_addr),
+        reserves,
+        lp_supply,
+        bonded_reserves,
+        total_value,
+        bonded_value,
     })
 }
 
@@ -365,28 +759,33 @@ pub fn query_rewards_per_sec(
     Ok(RewardsPerSecResponse { assets })
 }
 
+pub fn query_voting_power_at(
+    deps: Deps,
+    address: Addr,
+    height: u64,
+) -> StdResult<VotingPowerResponse> {
+    let staker_addr_raw = deps.api.addr_canonicalize(address.as_str())?;
+    let voting_power = read_stake_checkpoint(deps.storage, &staker_addr_raw, height)?;
+
+    Ok(VotingPowerResponse {
+        address,
+        height,
+        voting_power,
+    })
+}
+
+pub fn query_hooks(deps: Deps) -> StdResult<HooksResponse> {
+    let hooks = read_hooks(deps.storage)?
+        .into_iter()
+        .map(|hook| deps.api.addr_humanize(&hook))
+        .collect::<StdResult<Vec<Addr>>>()?;
+
+    Ok(HooksResponse { hooks })
+}
+
 // migrate contract
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
-    // migrate_pool_infos(deps.storage)?;
-    // migrate_config(deps.storage)?;
-    // migrate_rewards_store(deps.storage, deps.api, msg.staker_addrs)?;
-    // migrate_total_reward_amount(deps.storage, deps.api, msg.amount_infos)?;
-
-    // when the migration is executed, deprecate directly the MIR pool
-    // let config = read_config(deps.storage)?;
-    // let self_info = MessageInfo {
-    //     sender: deps.api.addr_humanize(&config.owner)?,
-    //     sent_funds: vec![],
-    // };
-
-    // depricate old one
-    // deprecate_staking_token(
-    //     deps,
-    //     self_info,
-    //     msg.asset_info_to_deprecate,
-    //     msg.new_staking_token,
-    // )?;
-
-    Ok(Response::default())
+pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> StdResult<Response> {
+    // each storage layout change is a step of the migration registry keyed by the cw2 version
+    run_migrations(deps, &msg)
 }
@@ -1,8 +1,17 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Api, CanonicalAddr, Decimal, Order, StdResult, Storage, Uint128};
+use cosmwasm_std::{
+    Addr, Api, CanonicalAddr, Decimal, DepsMut, Order, Response, StdError, StdResult, Storage,
+    Uint128,
+};
 use cosmwasm_storage::ReadonlyBucket;
+use cw2::{get_contract_version, set_contract_version};
+use oraiswap::asset::AssetInfoRaw;
+use oraiswap::staking::MigrateMsg;
 
-use crate::state::{rewards_store, MigrationParams, RewardInfo, PREFIX_REWARD};
+use crate::state::{
+    rewards_store, store_pool_info, MigrationParams, PoolInfo, RewardInfo, PREFIX_POOL_INFO,
+    PREFIX_REWARD,
+};
 
 #[cw_serde]
 pub struct LegacyPoolInfo {
@@ -16,7 +25,23 @@ pub struct LegacyPoolInfo {
     pub premium_rate: Decimal,
     pub short_reward_weight: Decimal,
     pub premium_updated_time: u64,
-    pub migration_params: Option<MigrationParams>,
+    pub migration_params: Option<LegacyMigrationParams>,
+}
+
+// pool info when the staking token could only be a cw20 token
+#[cw_serde]
+pub struct LegacyPoolInfoV2 {
+    pub staking_token: CanonicalAddr,
+    pub pending_reward: Uint128, // not distributed amount due to zero bonding
+    pub total_bond_amount: Uint128,
+    pub reward_index: Decimal,
+    pub migration_params: Option<LegacyMigrationParams>,
+}
+
+#[cw_serde]
+pub struct LegacyMigrationParams {
+    pub index_snapshot: Decimal,
+    pub deprecated_staking_token: CanonicalAddr,
 }
 
 // migrate reward store
@@ -71,3 +96,115 @@ pub fn migrate_rewards_store(
 
     Ok(())
 }
+
+// convert the cw20 staking tokens of the pools to asset info
+pub fn migrate_pool_infos(store: &mut dyn Storage) -> StdResult<()> {
+    let legacy_pool_infos = ReadonlyBucket::<LegacyPoolInfoV2>::new(store, PREFIX_POOL_INFO)
+        .range(None, None, Order::Ascending)
+        .collect::<StdResult<Vec<(Vec<u8>, LegacyPoolInfoV2)>>>()?;
+
+    for (asset_key, pool_info) in legacy_pool_infos {
+        let new_pool_info = PoolInfo {
+            staking_token: AssetInfoRaw::Token {
+                contract_addr: pool_info.staking_token,
+            },
+            pending_reward: pool_info.pending_reward,
+            total_bond_amount: pool_info.total_bond_amount,
+            reward_index: pool_info.reward_index,
+            migration_params: pool_info.migration_params.map(|params| MigrationParams {
+                index_snapshot: params.index_snapshot,
+                deprecated_staking_token: AssetInfoRaw::Token {
+                    contract_addr: params.deprecated_staking_token,
+                },
+            }),
+        };
+        store_pool_info(store, &asset_key, &new_pool_info)?;
+    }
+
+    Ok(())
+}
+
+pub const CONTRACT_NAME: &str = "crates.io:oraiswap-staking";
+pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// a storage transformation bringing the contract storage to the layout of `version`
+pub struct MigrationStep {
+    pub version: &'static str,
+    pub name: &'static str,
+    pub migrate: fn(DepsMut, &MigrateMsg) -> StdResult<()>,
+}
+
+/// migration steps in ascending version order, a change of the storage layout appends its step here
+pub const MIGRATIONS: &[MigrationStep] = &[MigrationStep {
+    version: "0.1.1",
+    name: "pool_infos_asset_info",
+    migrate: migrate_pool_infos_step,
+}];
+
+fn migrate_pool_infos_step(deps: DepsMut, _msg: &MigrateMsg) -> StdResult<()> {
+    migrate_pool_infos(deps.storage)
+}
+
+/// apply in order the steps newer than the stored cw2 version up to the contract version,
+/// contracts deployed before the version was stored start from msg.from_version, or apply every step
+pub fn run_migrations(mut deps: DepsMut, msg: &MigrateMsg) -> StdResult<Response> {
+    let from_version = match get_contract_version(deps.storage).ok() {
+        Some(contract_version) => {
+            if contract_version.contract != CONTRACT_NAME {
+                return Err(StdError::generic_err(format!(
+                    "cannot migrate from contract {}",
+                    contract_version.contract
+                )));
+            }
+            contract_version.version
+        }
+        None => msg
+            .from_version
+            .clone()
+            .unwrap_or_else(|| "0.0.0".to_string()),
+    };
+
+    let from = parse_version(&from_version)?;
+    let to = parse_version(CONTRACT_VERSION)?;
+    if from > to {
+        return Err(StdError::generic_err(format!(
+            "cannot migrate from newer version {}",
+            from_version
+        )));
+    }
+
+    let mut applied_steps = vec![];
+    for step in MIGRATIONS {
+        let version = parse_version(step.version)?;
+        if version > from && version <= to {
+            (step.migrate)(deps.branch(), msg)?;
+            applied_steps.push(step.name);
+        }
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "migrate"),
+        ("from_version", &from_version),
+        ("to_version", CONTRACT_VERSION),
+        ("applied_steps", &applied_steps.join(",")),
+    ]))
+}
+
+/// major, minor and patch of a version, so versions compare numerically
+fn parse_version(version: &str) -> StdResult<(u64, u64, u64)> {
+    let parts = version
+        .split('.')
+        .map(|part| part.parse::<u64>())
+        .collect::<Result<Vec<u64>, _>>()
+        .map_err(|_| StdError::generic_err(format!("invalid version {}", version)))?;
+
+    match parts.as_slice() {
+        [major, minor, patch] => Ok((*major, *minor, *patch)),
+        _ => Err(StdError::generic_err(format!(
+            "invalid version {}",
+            version
+        ))),
+    }
+}
@@ -1,16 +1,20 @@
 use std::convert::TryFrom;
 
+use crate::staking::{bond_staking_tokens, hook_messages};
 use crate::state::{
-    read_config, read_is_migrated, read_pool_info, read_rewards_per_sec, rewards_read,
-    rewards_store, stakers_read, store_pool_info, PoolInfo, RewardInfo,
+    read_config, read_is_migrated, read_max_pending_reward, read_pool_info, read_rewards_per_sec,
+    rewards_read, rewards_store, stakers_read, store_pool_info, PoolInfo, RewardInfo,
 };
 use cosmwasm_std::{
     Addr, Api, CanonicalAddr, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo, Order, Response,
     StdError, StdResult, Storage, Uint128,
 };
 use oraiswap::asset::{Asset, AssetInfo, AssetRaw};
-use oraiswap::querier::calc_range_start;
-use oraiswap::staking::{RewardInfoResponse, RewardInfoResponseItem};
+use oraiswap::pagination::{next_cursor, Cursor};
+use oraiswap::staking::{
+    ProjectedRewardResponse, RewardInfoResponse, RewardInfoResponseItem, RewardInfosResponse,
+    StakingHookMsg,
+};
 
 const DEFAULT_LIMIT: u32 = 10;
 const MAX_LIMIT: u32 = 30;
@@ -29,6 +33,8 @@ pub fn deposit_reward(
     }
 
     let mut rewards_amount = Uint128::zero();
+    // rewards over the max pending reward of pools without bonding
+    let mut excess_assets: Vec<AssetRaw> = vec![];
 
     for asset in rewards.iter() {
         let asset_key = asset.info.to_vec(deps.api)?;
@@ -40,6 +46,15 @@ pub fn deposit_reward(
         // normal rewards are array of Assets
         if pool_info.total_bond_amount.is_zero() {
             pool_info.pending_reward += normal_reward;
+            if let Some(max_pending_reward) = read_max_pending_reward(deps.storage, &asset_key)? {
+                if pool_info.pending_reward > max_pending_reward {
+                    let excess = pool_info.pending_reward - max_pending_reward;
+                    for ra in compute_reward_assets(deps.storage, &asset_key, excess)? {
+                        update_reward_assets_amount(&mut excess_assets, ra.clone(), ra.amount);
+                    }
+                    pool_info.pending_reward = max_pending_reward;
+                }
+            }
         } else {
             normal_reward += pool_info.pending_reward;
             let normal_reward_per_bond =
@@ -53,36 +68,196 @@ pub fn deposit_reward(
         rewards_amount += asset.amount;
     }
 
-    Ok(Response::new().add_attributes([
+    // return the excess to the rewarder
+    let messages = excess_assets
+        .into_iter()
+        .map(|ra| {
+            Ok(ra
+                .to_normal(deps.api)?
+                .into_msg(None, &deps.querier, info.sender.clone())?)
+        })
+        .collect::<StdResult<Vec<CosmosMsg>>>()?;
+
+    Ok(Response::new().add_messages(messages).add_attributes([
         ("action", "deposit_reward"),
         ("rewards_amount", &rewards_amount.to_string()),
     ]))
 }
 
+pub fn clawback_unallocated_rewards(
+    deps: DepsMut,
+    info: MessageInfo,
+    asset_info: AssetInfo,
+) -> StdResult<Response> {
+    let config = read_config(deps.storage)?;
+
+    // only admin can execute this message
+    if config.owner != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let asset_key = asset_info.to_vec(deps.api)?;
+    let mut pool_info: PoolInfo = read_pool_info(deps.storage, &asset_key)?;
+    if pool_info.pending_reward.is_zero() {
+        return Err(StdError::generic_err("no unallocated rewards"));
+    }
+
+    let rewarder = deps.api.addr_humanize(&config.rewarder)?;
+    let messages = compute_reward_assets(deps.storage, &asset_key, pool_info.pending_reward)?
+        .into_iter()
+        .map(|ra| {
+            Ok(ra
+                .to_normal(deps.api)?
+                .into_msg(None, &deps.querier, rewarder.clone())?)
+        })
+        .collect::<StdResult<Vec<CosmosMsg>>>()?;
+
+    let clawback_amount = pool_info.pending_reward;
+    pool_info.pending_reward = Uint128::zero();
+    store_pool_info(deps.storage, &asset_key, &pool_info)?;
+
+    Ok(Response::new().add_messages(messages).add_attributes([
+        ("action", "clawback_unallocated_rewards"),
+        ("asset_info", &asset_info.to_string()),
+        ("clawback_amount", &clawback_amount.to_string()),
+    ]))
+}
+
+// split the reward amount of the pool into reward assets by the rewards per second weights
+fn compute_reward_assets(
+    storage: &dyn Storage,
+    asset_key: &[u8],
+    amount: Uint128,
+) -> StdResult<Vec<AssetRaw>> {
+    let rewards_per_sec = read_rewards_per_sec(storage, asset_key)?;
+    let total_amount: Uint128 = rewards_per_sec.iter().map(|rw| rw.amount).sum();
+
+    Ok(rewards_per_sec
+        .into_iter()
+        .filter(|rw| !rw.amount.is_zero())
+        .map(|rw| AssetRaw {
+            amount: amount * Decimal::from_ratio(rw.amount, total_amount),
+            info: rw.info,
+        })
+        .filter(|ra| !ra.amount.is_zero())
+        .collect())
+}
+
 // withdraw all rewards or single reward depending on asset_token
 pub fn withdraw_reward(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     asset_info: Option<AssetInfo>,
+    auto_bond: Option<AssetInfo>,
 ) -> StdResult<Response> {
     let staker_addr = deps.api.addr_canonicalize(info.sender.as_str())?;
     let asset_key = asset_info.map_or(None, |a| a.to_vec(deps.api).ok());
 
     let reward_assets = process_reward_assets(deps.storage, &staker_addr, &asset_key, true)?;
 
-    let messages = reward_assets
+    let (messages, bond_amount) =
+        claim_messages(deps, &env, &info.sender, reward_assets, auto_bond)?;
+
+    Ok(Response::new().add_messages(messages).add_attributes([
+        ("action", "withdraw_reward"),
+        ("bond_amount", &bond_amount.to_string()),
+    ]))
+}
+
+pub fn withdraw_reward_all(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asset_infos: Option<Vec<AssetInfo>>,
+    auto_bond: Option<AssetInfo>,
+) -> StdResult<Response> {
+    let staker_addr = deps.api.addr_canonicalize(info.sender.as_str())?;
+
+    let reward_assets = match asset_infos {
+        None => process_reward_assets(deps.storage, &staker_addr, &None, true)?,
+        Some(asset_infos) => {
+            // merge rewards of the given pools by reward asset
+            let mut reward_assets: Vec<AssetRaw> = vec![];
+            for asset_info in asset_infos {
+                let asset_key = Some(asset_info.to_vec(deps.api)?);
+                for ra in process_reward_assets(deps.storage, &staker_addr, &asset_key, true)? {
+                    update_reward_assets_amount(&mut reward_assets, ra.clone(), ra.amount);
+                }
+            }
+            reward_assets
+        }
+    };
+
+    let reward_assets = reward_assets
         .into_iter()
-        .map(|ra| {
-            Ok(ra
-                .to_normal(deps.api)?
-                .into_msg(None, &deps.querier, info.sender.clone())?)
-        })
+        .filter(|ra| !ra.amount.is_zero())
+        .collect();
+    let (messages, bond_amount) =
+        claim_messages(deps, &env, &info.sender, reward_assets, auto_bond)?;
+
+    Ok(Response::new().add_messages(messages).add_attributes([
+        ("action", "withdraw_reward_all"),
+        ("bond_amount", &bond_amount.to_string()),
+    ]))
+}
+
+// transfer the claimed rewards to the staker, then notify the hook contracts.
+// With auto_bond, the rewards in the staking token of that pool, such as the LP token of another pool,
+// are bonded to it for the staker instead, return the bonded amount
+fn claim_messages(
+    deps: DepsMut,
+    env: &Env,
+    staker_addr: &Addr,
+    reward_assets: Vec<AssetRaw>,
+    auto_bond: Option<AssetInfo>,
+) -> StdResult<(Vec<CosmosMsg>, Uint128)> {
+    let rewards = reward_assets
+        .into_iter()
+        .map(|ra| ra.to_normal(deps.api))
+        .collect::<StdResult<Vec<Asset>>>()?;
+
+    let bond_token = match &auto_bond {
+        Some(asset_info) => Some(
+            read_pool_info(deps.storage, &asset_info.to_vec(deps.api)?)?
+                .staking_token
+                .to_normal(deps.api)?,
+        ),
+        None => None,
+    };
+    let (bonded, transferred): (Vec<&Asset>, Vec<&Asset>) = rewards
+        .iter()
+        .partition(|asset| bond_token.as_ref() == Some(&asset.info));
+    let bond_amount: Uint128 = bonded.iter().map(|asset| asset.amount).sum();
+
+    let mut messages = transferred
+        .into_iter()
+        .map(|asset| asset.into_msg(None, &deps.querier, staker_addr.clone()))
         .collect::<StdResult<Vec<CosmosMsg>>>()?;
 
-    Ok(Response::new()
-        .add_messages(messages)
-        .add_attribute("action", "withdraw_reward"))
+    if !rewards.is_empty() {
+        messages.extend(hook_messages(
+            deps.as_ref(),
+            StakingHookMsg::Claimed {
+                staker_addr: staker_addr.clone(),
+                rewards,
+            },
+        )?);
+    }
+
+    if let Some(asset_info) = auto_bond {
+        if !bond_amount.is_zero() {
+            messages.extend(bond_staking_tokens(
+                deps,
+                env,
+                staker_addr,
+                &asset_info,
+                bond_amount,
+            )?);
+        }
+    }
+
+    Ok((messages, bond_amount))
 }
 
 pub fn withdraw_reward_others(
@@ -234,32 +409,110 @@ pub fn query_reward_info(
     })
 }
 
+pub fn query_projected_reward(
+    deps: Deps,
+    staker_addr: Addr,
+    asset_info: AssetInfo,
+    seconds_ahead: u64,
+) -> StdResult<ProjectedRewardResponse> {
+    let staker_addr_raw = deps.api.addr_canonicalize(staker_addr.as_str())?;
+    let asset_key = asset_info.to_vec(deps.api)?;
+    let pool_info = read_pool_info(deps.storage, &asset_key)?;
+
+    let reward_info = _read_reward_infos_response(
+        deps.api,
+        deps.storage,
+        &staker_addr_raw,
+        &Some(asset_info.clone()),
+    )?
+    .pop();
+
+    let (bond_amount, pending_reward, mut projected_reward_assets) = match reward_info {
+        Some(item) => (item.bond_amount, item.pending_reward, item.pending_withdraw),
+        None => (Uint128::zero(), Uint128::zero(), vec![]),
+    };
+
+    let rewards_per_sec = read_rewards_per_sec(deps.storage, &asset_key).unwrap_or_default();
+    let total_amount: Uint128 = rewards_per_sec.iter().map(|rw| rw.amount).sum();
+
+    // positions waiting for migration do not receive new rewards
+    let projected_reward = if pool_info.total_bond_amount.is_zero()
+        || (pool_info.migration_params.is_some()
+            && !read_is_migrated(deps.storage, &asset_key, &staker_addr_raw))
+    {
+        pending_reward
+    } else {
+        let emission = total_amount.checked_mul(Uint128::from(seconds_ahead))?;
+        pending_reward + emission.multiply_ratio(bond_amount, pool_info.total_bond_amount)
+    };
+
+    // split the projected reward the same way as process_reward_assets
+    if !projected_reward.is_zero() {
+        for rw in rewards_per_sec {
+            if rw.amount.is_zero() {
+                continue;
+            }
+            let amount = projected_reward * Decimal::from_ratio(rw.amount, total_amount);
+            let info = rw.info.to_normal(deps.api)?;
+            match projected_reward_assets
+                .iter_mut()
+                .find(|ra| ra.info.eq(&info))
+            {
+                None => projected_reward_assets.push(Asset { info, amount }),
+                Some(reward_asset) => reward_asset.amount += amount,
+            }
+        }
+    }
+
+    Ok(ProjectedRewardResponse {
+        staker_addr,
+        asset_info,
+        bond_amount,
+        pending_reward,
+        projected_reward,
+        projected_reward_assets,
+    })
+}
+
 pub fn query_all_reward_infos(
     deps: Deps,
     asset_info: AssetInfo,
     start_after: Option<Addr>,
     limit: Option<u32>,
     order: Option<i32>,
-) -> StdResult<Vec<RewardInfoResponse>> {
-    // default is Ascending
-    let order_by = Order::try_from(order.unwrap_or(1))?;
-    let asset_key = asset_info.to_vec(deps.api)?;
-    let start_after = start_after
-        .map_or(None, |a| deps.api.addr_canonicalize(a.as_str()).ok())
-        .map(|c| c.to_vec());
+    cursor: Option<String>,
+) -> StdResult<RewardInfosResponse> {
+    let (order_by, (start, end)) = match cursor {
+        Some(cursor) => {
+            let cursor = Cursor::decode(&cursor)?;
+            (cursor.order, cursor.range_bounds())
+        }
+        None => {
+            // default is Ascending
+            let order_by = Order::try_from(order.unwrap_or(1))?;
+            let start_after = start_after
+                .map_or(None, |a| deps.api.addr_canonicalize(a.as_str()).ok())
+                .map(|c| c.to_vec());
+            let bounds = start_after.map_or((None, None), |key| {
+                Cursor::new(key, order_by).range_bounds()
+            });
+            (order_by, bounds)
+        }
+    };
 
+    let asset_key = asset_info.to_vec(deps.api)?;
     let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
 
-    let (start, end) = match order_by {
-        Order::Ascending => (calc_range_start(start_after), None),
-        Order::Descending => (None, start_after),
-    };
-
-    let info_responses = stakers_read(deps.storage, &asset_key)
+    let stakers = stakers_read(deps.storage, &asset_key)
         .range(start.as_deref(), end.as_deref(), order_by)
         .take(limit)
-        .map(|item| {
-            let (k, _) = item?;
+        .map(|item| item.map(|(k, _)| k))
+        .collect::<StdResult<Vec<Vec<u8>>>>()?;
+    let next_cursor = next_cursor(stakers.last().cloned(), order_by, stakers.len(), limit);
+
+    let reward_infos = stakers
+        .into_iter()
+        .map(|k| {
             let staker_addr_raw = CanonicalAddr::from(k);
             let reward_infos: Vec<RewardInfoResponseItem> = _read_reward_infos_response(
                 deps.api,
@@ -275,7 +528,10 @@ pub fn query_all_reward_infos(
         })
         .collect::<StdResult<Vec<RewardInfoResponse>>>()?;
 
-    Ok(info_responses)
+    Ok(RewardInfosResponse {
+        reward_infos,
+        next_cursor,
+    })
 }
 
 fn _read_reward_infos_response(
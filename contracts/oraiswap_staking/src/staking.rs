@@ -1,35 +1,30 @@
 use crate::rewards::before_share_change;
 use crate::state::{
-    read_config, read_is_migrated, read_pool_info, rewards_read, rewards_store, stakers_store,
-    store_is_migrated, store_pool_info, Config, PoolInfo, RewardInfo,
+    read_config, read_hooks, read_is_migrated, read_max_total_bond, read_pool_info,
+    read_staker_whitelist, rewards_read, rewards_store, stakers_store, store_is_migrated,
+    store_pool_info, store_stake_checkpoint, Config, PoolInfo, RewardInfo,
 };
 use cosmwasm_std::{
-    attr, to_binary, Addr, Api, CanonicalAddr, Coin, CosmosMsg, Decimal, DepsMut, Env, MessageInfo,
-    Response, StdError, StdResult, Storage, Uint128, WasmMsg,
+    attr, to_binary, Addr, Api, CanonicalAddr, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env,
+    MessageInfo, Order, Response, StdError, StdResult, Storage, Uint128, WasmMsg,
 };
 use cw20::Cw20ExecuteMsg;
-use oraiswap::asset::{Asset, AssetInfo, PairInfo};
+use oraiswap::asset::{Asset, AssetInfo, AssetInfoRaw, PairInfo};
 use oraiswap::oracle::OracleContract;
 use oraiswap::pair::ExecuteMsg as PairExecuteMsg;
 use oraiswap::querier::{query_pair_info, query_token_balance};
-use oraiswap::staking::ExecuteMsg;
+use oraiswap::staking::{ExecuteMsg, StakingHookMsg};
 
 pub fn bond(
     deps: DepsMut,
+    env: Env,
     staker_addr: Addr,
     asset_info: AssetInfo,
     amount: Uint128,
 ) -> StdResult<Response> {
-    let staker_addr_raw: CanonicalAddr = deps.api.addr_canonicalize(staker_addr.as_str())?;
-    _increase_bond_amount(
-        deps.storage,
-        deps.api,
-        &staker_addr_raw,
-        &asset_info,
-        amount,
-    )?;
+    let hook_messages = bond_staking_tokens(deps, &env, &staker_addr, &asset_info, amount)?;
 
-    Ok(Response::new().add_attributes([
+    Ok(Response::new().add_messages(hook_messages).add_attributes([
         ("action", "bond"),
         ("staker_addr", staker_addr.as_str()),
         ("asset_info", &asset_info.to_string()),
@@ -37,9 +32,62 @@ pub fn bond(
     ]))
 }
 
+// bond staking tokens already held by the contract for the staker, return the hook messages
+pub fn bond_staking_tokens(
+    deps: DepsMut,
+    env: &Env,
+    staker_addr: &Addr,
+    asset_info: &AssetInfo,
+    amount: Uint128,
+) -> StdResult<Vec<CosmosMsg>> {
+    let staker_addr_raw: CanonicalAddr = deps.api.addr_canonicalize(staker_addr.as_str())?;
+    assert_bond_allowed(
+        deps.storage,
+        &asset_info.to_vec(deps.api)?,
+        &staker_addr_raw,
+        amount,
+    )?;
+    _increase_bond_amount(deps.storage, deps.api, &staker_addr_raw, asset_info, amount)?;
+    _update_stake_checkpoint(deps.storage, &staker_addr_raw, env.block.height)?;
+    hook_messages(
+        deps.as_ref(),
+        StakingHookMsg::Bonded {
+            staker_addr: staker_addr.clone(),
+            asset_info: asset_info.clone(),
+            amount,
+        },
+    )
+}
+
+// guarded pools only accept bonds of the whitelisted stakers, up to the max total bond
+fn assert_bond_allowed(
+    storage: &dyn Storage,
+    asset_key: &[u8],
+    staker_addr: &CanonicalAddr,
+    amount: Uint128,
+) -> StdResult<()> {
+    if let Some(staker_whitelist) = read_staker_whitelist(storage, asset_key)? {
+        if !staker_whitelist.contains(staker_addr) {
+            return Err(StdError::generic_err("staker is not whitelisted"));
+        }
+    }
+
+    if let Some(max_total_bond) = read_max_total_bond(storage, asset_key)? {
+        let pool_info: PoolInfo = read_pool_info(storage, asset_key)?;
+        if pool_info.total_bond_amount.checked_add(amount)? > max_total_bond {
+            return Err(StdError::generic_err(format!(
+                "total bond amount exceeds the max total bond {}",
+                max_total_bond
+            )));
+        }
+    }
+
+    Ok(())
+}
+
 pub fn unbond(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     staker_addr: Addr,
     asset_info: AssetInfo,
     amount: Uint128,
@@ -52,16 +100,13 @@ pub fn unbond(
         &asset_info,
         amount,
     )?;
-    let staking_token_addr = deps.api.addr_humanize(&staking_token)?;
-    let mut messages = vec![WasmMsg::Execute {
-        contract_addr: staking_token_addr.to_string(),
-        msg: to_binary(&Cw20ExecuteMsg::Transfer {
-            recipient: staker_addr.to_string(),
-            amount,
-        })?,
-        funds: vec![],
+    _update_stake_checkpoint(deps.storage, &staker_addr_raw, env.block.height)?;
+    let staking_token = staking_token.to_normal(deps.api)?;
+    let mut messages = vec![Asset {
+        info: staking_token.clone(),
+        amount,
     }
-    .into()];
+    .into_msg(None, &deps.querier, staker_addr.clone())?];
 
     // withdraw pending_withdraw assets (accumulated when changing reward_per_sec)
     messages.extend(
@@ -70,13 +115,21 @@ pub fn unbond(
             .map(|ra| Ok(ra.into_msg(None, &deps.querier, staker_addr.clone())?))
             .collect::<StdResult<Vec<CosmosMsg>>>()?,
     );
+    messages.extend(hook_messages(
+        deps.as_ref(),
+        StakingHookMsg::Unbonded {
+            staker_addr: staker_addr.clone(),
+            asset_info: asset_info.clone(),
+            amount,
+        },
+    )?);
 
     Ok(Response::new().add_messages(messages).add_attributes([
         attr("action", "unbond"),
         attr("staker_addr", staker_addr.as_str()),
         attr("asset_info", &asset_info.to_string()),
         attr("amount", &amount.to_string()),
-        attr("staking_token", staking_token_addr.as_str()),
+        attr("staking_token", &staking_token.to_string()),
     ]))
 }
 
@@ -147,11 +200,12 @@ pub fn auto_stake(
         &deps.api.addr_canonicalize(token_addr.as_str())?,
     )?;
 
-    if pool_info.staking_token
-        != deps
+    let liquidity_token = AssetInfoRaw::Token {
+        contract_addr: deps
             .api
-            .addr_canonicalize(oraiswap_pair.liquidity_token.as_str())?
-    {
+            .addr_canonicalize(oraiswap_pair.liquidity_token.as_str())?,
+    };
+    if !pool_info.staking_token.eq(&liquidity_token) {
         return Err(StdError::generic_err("Invalid staking token"));
     }
 
@@ -208,6 +262,7 @@ pub fn auto_stake(
                     ],
                     slippage_tolerance,
                     receiver: None,
+                    auto_stake: None,
                 })?,
                 funds: vec![Coin {
                     denom: native_asset.info.to_string(),
@@ -253,7 +308,29 @@ pub fn auto_stake_hook(
         query_token_balance(&deps.querier, staking_token, env.contract.address)?;
     let amount_to_stake = current_staking_token_amount.checked_sub(prev_staking_token_amount)?;
 
-    bond(deps, staker_addr, asset_info, amount_to_stake)
+    bond(deps, env, staker_addr, asset_info, amount_to_stake)
+}
+
+// notify the registered hook contracts of the staking event
+pub fn hook_messages(deps: Deps, msg: StakingHookMsg) -> StdResult<Vec<CosmosMsg>> {
+    read_hooks(deps.storage)?
+        .into_iter()
+        .map(|hook| msg.clone().into_cosmos_msg(deps.api.addr_humanize(&hook)?))
+        .collect()
+}
+
+// snapshot the total bond amount of the staker, used as voting power at the current height
+fn _update_stake_checkpoint(
+    storage: &mut dyn Storage,
+    staker_addr: &CanonicalAddr,
+    height: u64,
+) -> StdResult<()> {
+    let total_bond_amount = rewards_read(storage, staker_addr)
+        .range(None, None, Order::Ascending)
+        .map(|item| Ok(item?.1.bond_amount))
+        .sum::<StdResult<Uint128>>()?;
+
+    store_stake_checkpoint(storage, staker_addr, height, total_bond_amount)
 }
 
 fn _increase_bond_amount(
@@ -313,7 +390,7 @@ fn _decrease_bond_amount(
     staker_addr: &CanonicalAddr,
     asset_info: &AssetInfo,
     amount: Uint128,
-) -> StdResult<(CanonicalAddr, Vec<Asset>)> {
+) -> StdResult<(AssetInfoRaw, Vec<Asset>)> {
     let asset_key = &asset_info.to_vec(api)?;
     let mut pool_info: PoolInfo = read_pool_info(storage, asset_key)?;
     let mut reward_info: RewardInfo = rewards_read(storage, staker_addr).load(asset_key)?;
@@ -1,15 +1,21 @@
 use cosmwasm_schema::cw_serde;
-use oraiswap::asset::AssetRaw;
+use oraiswap::asset::{AssetInfoRaw, AssetRaw};
 
-use cosmwasm_std::{CanonicalAddr, Decimal, StdResult, Storage, Uint128};
+use cosmwasm_std::{CanonicalAddr, Decimal, Order, StdResult, Storage, Uint128};
 use cosmwasm_storage::{singleton, singleton_read, Bucket, ReadonlyBucket};
 
 pub static KEY_CONFIG: &[u8] = b"config_v2";
+static KEY_PAUSED: &[u8] = b"paused";
+static KEY_HOOKS: &[u8] = b"hooks";
 pub static PREFIX_POOL_INFO: &[u8] = b"pool_info_v2";
 pub static PREFIX_REWARD: &[u8] = b"reward_v2";
 static PREFIX_STAKER: &[u8] = b"staker";
 static PREFIX_IS_MIGRATED: &[u8] = b"is_migrated";
 static PREFIX_REWARDS_PER_SEC: &[u8] = b"rewards_per_sec";
+static PREFIX_STAKE_CHECKPOINT: &[u8] = b"stake_checkpoint";
+static PREFIX_MAX_PENDING_REWARD: &[u8] = b"max_pending_reward";
+static PREFIX_MAX_TOTAL_BOND: &[u8] = b"max_total_bond";
+static PREFIX_STAKER_WHITELIST: &[u8] = b"staker_whitelist";
 
 #[cw_serde]
 pub struct Config {
@@ -18,6 +24,7 @@ pub struct Config {
     pub oracle_addr: CanonicalAddr,
     pub factory_addr: CanonicalAddr,
     pub base_denom: String,
+    pub guardian: Option<CanonicalAddr>,
 }
 
 pub fn store_config(storage: &mut dyn Storage, config: &Config) -> StdResult<()> {
@@ -28,9 +35,27 @@ pub fn read_config(storage: &dyn Storage) -> StdResult<Config> {
     singleton_read(storage, KEY_CONFIG).load()
 }
 
+pub fn store_paused(storage: &mut dyn Storage, paused: bool) -> StdResult<()> {
+    singleton(storage, KEY_PAUSED).save(&paused)
+}
+
+pub fn read_paused(storage: &dyn Storage) -> bool {
+    singleton_read(storage, KEY_PAUSED).load().unwrap_or(false)
+}
+
+pub fn store_hooks(storage: &mut dyn Storage, hooks: Vec<CanonicalAddr>) -> StdResult<()> {
+    singleton(storage, KEY_HOOKS).save(&hooks)
+}
+
+pub fn read_hooks(storage: &dyn Storage) -> StdResult<Vec<CanonicalAddr>> {
+    Ok(singleton_read(storage, KEY_HOOKS)
+        .may_load()?
+        .unwrap_or_default())
+}
+
 #[cw_serde]
 pub struct PoolInfo {
-    pub staking_token: CanonicalAddr,
+    pub staking_token: AssetInfoRaw,
     pub pending_reward: Uint128, // not distributed amount due to zero bonding
     pub total_bond_amount: Uint128,
     pub reward_index: Decimal,
@@ -40,7 +65,7 @@ pub struct PoolInfo {
 #[cw_serde]
 pub struct MigrationParams {
     pub index_snapshot: Decimal,
-    pub deprecated_staking_token: CanonicalAddr,
+    pub deprecated_staking_token: AssetInfoRaw,
 }
 
 pub fn store_pool_info(
@@ -55,6 +80,31 @@ pub fn read_pool_info(storage: &dyn Storage, asset_key: &[u8]) -> StdResult<Pool
     ReadonlyBucket::new(storage, PREFIX_POOL_INFO).load(asset_key)
 }
 
+/// returns all registered pools with their asset keys
+pub fn read_pool_infos(storage: &dyn Storage) -> StdResult<Vec<(Vec<u8>, PoolInfo)>> {
+    ReadonlyBucket::new(storage, PREFIX_POOL_INFO)
+        .range(None, None, Order::Ascending)
+        .collect()
+}
+
+/// sums the bond amounts of the pools staking the given token
+pub fn read_total_bond_amount(
+    storage: &dyn Storage,
+    staking_token: &AssetInfoRaw,
+) -> StdResult<Uint128> {
+    ReadonlyBucket::<PoolInfo>::new(storage, PREFIX_POOL_INFO)
+        .range(None, None, Order::Ascending)
+        .map(|item| {
+            let (_, pool_info) = item?;
+            if pool_info.staking_token.eq(staking_token) {
+                Ok(pool_info.total_bond_amount)
+            } else {
+                Ok(Uint128::zero())
+            }
+        })
+        .sum()
+}
+
 #[cw_serde]
 pub struct RewardInfo {
     pub native_token: bool,
@@ -121,3 +171,96 @@ pub fn read_rewards_per_sec(storage: &dyn Storage, asset_key: &[u8]) -> StdResul
         ReadonlyBucket::new(storage, PREFIX_REWARDS_PER_SEC);
     weight_bucket.load(asset_key)
 }
+
+pub fn store_max_pending_reward(
+    storage: &mut dyn Storage,
+    asset_key: &[u8],
+    max_pending_reward: Option<Uint128>,
+) -> StdResult<()> {
+    let mut bucket: Bucket<Uint128> = Bucket::new(storage, PREFIX_MAX_PENDING_REWARD);
+    match max_pending_reward {
+        Some(max_pending_reward) => bucket.save(asset_key, &max_pending_reward),
+        None => {
+            bucket.remove(asset_key);
+            Ok(())
+        }
+    }
+}
+
+pub fn read_max_pending_reward(
+    storage: &dyn Storage,
+    asset_key: &[u8],
+) -> StdResult<Option<Uint128>> {
+    ReadonlyBucket::new(storage, PREFIX_MAX_PENDING_REWARD).may_load(asset_key)
+}
+
+pub fn store_max_total_bond(
+    storage: &mut dyn Storage,
+    asset_key: &[u8],
+    max_total_bond: Option<Uint128>,
+) -> StdResult<()> {
+    let mut bucket: Bucket<Uint128> = Bucket::new(storage, PREFIX_MAX_TOTAL_BOND);
+    match max_total_bond {
+        Some(max_total_bond) => bucket.save(asset_key, &max_total_bond),
+        None => {
+            bucket.remove(asset_key);
+            Ok(())
+        }
+    }
+}
+
+pub fn read_max_total_bond(storage: &dyn Storage, asset_key: &[u8]) -> StdResult<Option<Uint128>> {
+    ReadonlyBucket::new(storage, PREFIX_MAX_TOTAL_BOND).may_load(asset_key)
+}
+
+pub fn store_staker_whitelist(
+    storage: &mut dyn Storage,
+    asset_key: &[u8],
+    staker_whitelist: Option<Vec<CanonicalAddr>>,
+) -> StdResult<()> {
+    let mut bucket: Bucket<Vec<CanonicalAddr>> = Bucket::new(storage, PREFIX_STAKER_WHITELIST);
+    match staker_whitelist {
+        Some(staker_whitelist) => bucket.save(asset_key, &staker_whitelist),
+        None => {
+            bucket.remove(asset_key);
+            Ok(())
+        }
+    }
+}
+
+pub fn read_staker_whitelist(
+    storage: &dyn Storage,
+    asset_key: &[u8],
+) -> StdResult<Option<Vec<CanonicalAddr>>> {
+    ReadonlyBucket::new(storage, PREFIX_STAKER_WHITELIST).may_load(asset_key)
+}
+
+/// stores the total bond amount of the staker across all pools at the given block height
+pub fn store_stake_checkpoint(
+    storage: &mut dyn Storage,
+    staker: &CanonicalAddr,
+    height: u64,
+    amount: Uint128,
+) -> StdResult<()> {
+    Bucket::multilevel(storage, &[PREFIX_STAKE_CHECKPOINT, staker.as_slice()])
+        .save(&height.to_be_bytes(), &amount)
+}
+
+/// returns the latest checkpoint of the staker at or before the given block height
+pub fn read_stake_checkpoint(
+    storage: &dyn Storage,
+    staker: &CanonicalAddr,
+    height: u64,
+) -> StdResult<Uint128> {
+    let checkpoint_bucket: ReadonlyBucket<Uint128> =
+        ReadonlyBucket::multilevel(storage, &[PREFIX_STAKE_CHECKPOINT, staker.as_slice()]);
+    // height is big endian encoded so the range is sorted by height, end is exclusive
+    let end = height.checked_add(1).map(|h| h.to_be_bytes().to_vec());
+    match checkpoint_bucket
+        .range(None, end.as_deref(), Order::Descending)
+        .next()
+    {
+        Some(item) => Ok(item?.1),
+        None => Ok(Uint128::zero()),
+    }
+}
@@ -1,4 +1,5 @@
 use crate::contract::{execute, instantiate, query};
+use crate::testing::mock_token_balance;
 use cosmwasm_std::testing::{
     mock_dependencies, mock_dependencies_with_balance, mock_env, mock_info,
 };
@@ -7,7 +8,7 @@ use cw20::Cw20ReceiveMsg;
 use oraiswap::asset::{Asset, AssetInfo, ORAI_DENOM};
 use oraiswap::staking::{
     ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, PoolInfoResponse, QueryMsg,
-    RewardInfoResponse,
+    RewardInfosResponse,
 };
 
 #[test]
@@ -38,6 +39,8 @@ fn proper_initialization() {
             oracle_addr: Addr::unchecked("oracle"),
             factory_addr: Addr::unchecked("factory"),
             base_denom: ORAI_DENOM.to_string(),
+            guardian: None,
+            paused: false,
         },
         config
     );
@@ -64,6 +67,7 @@ fn update_config() {
     let msg = ExecuteMsg::UpdateConfig {
         owner: Some(Addr::unchecked("owner2")),
         rewarder: None,
+        guardian: None,
     };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
@@ -79,6 +83,8 @@ fn update_config() {
             oracle_addr: Addr::unchecked("oracle"),
             factory_addr: Addr::unchecked("factory"),
             base_denom: ORAI_DENOM.to_string(),
+            guardian: None,
+            paused: false,
         },
         config
     );
@@ -88,6 +94,7 @@ fn update_config() {
     let msg = ExecuteMsg::UpdateConfig {
         rewarder: None,
         owner: None,
+        guardian: None,
     };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg);
@@ -97,6 +104,83 @@ fn update_config() {
     }
 }
 
+#[test]
+fn pause_bonding() {
+    let mut deps = mock_dependencies();
+    mock_token_balance(&mut deps.querier, Uint128::from(10000000000u128));
+
+    let msg = InstantiateMsg {
+        owner: Some(Addr::unchecked("owner")),
+        rewarder: Addr::unchecked("reward"),
+        minter: Some(Addr::unchecked("mint")),
+        oracle_addr: Addr::unchecked("oracle"),
+        factory_addr: Addr::unchecked("factory"),
+        base_denom: None,
+    };
+
+    let info = mock_info("addr", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let msg = ExecuteMsg::RegisterAsset {
+        asset_info: AssetInfo::Token {
+            contract_addr: Addr::unchecked("asset"),
+        },
+        staking_token: AssetInfo::Token {
+            contract_addr: Addr::unchecked("staking"),
+        },
+    };
+    let info = mock_info("owner", &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let msg = ExecuteMsg::UpdateConfig {
+        rewarder: None,
+        owner: None,
+        guardian: Some(Addr::unchecked("guardian")),
+    };
+    let info = mock_info("owner", &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // only owner or guardian can pause
+    let info = mock_info("addr", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::Pause {});
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "unauthorized"),
+        _ => panic!("Must return unauthorized error"),
+    }
+
+    let info = mock_info("guardian", &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::Pause {}).unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(config.guardian, Some(Addr::unchecked("guardian")));
+    assert!(config.paused);
+
+    let bond_msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr".to_string(),
+        amount: Uint128::from(100u128),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            asset_info: AssetInfo::Token {
+                contract_addr: Addr::unchecked("asset"),
+            },
+        })
+        .unwrap(),
+    });
+    let info = mock_info("staking", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, bond_msg.clone());
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "contract is paused"),
+        _ => panic!("Must return paused error"),
+    }
+
+    // owner unpause then bonding works again
+    let info = mock_info("owner", &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::Unpause {}).unwrap();
+
+    let info = mock_info("staking", &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info, bond_msg).unwrap();
+}
+
 #[test]
 fn test_register() {
     let mut deps = mock_dependencies();
@@ -119,7 +203,9 @@ fn test_register() {
         asset_info: AssetInfo::Token {
             contract_addr: Addr::unchecked("asset"),
         },
-        staking_token: Addr::unchecked("staking"),
+        staking_token: AssetInfo::Token {
+            contract_addr: Addr::unchecked("staking"),
+        },
     };
 
     // failed with unauthorized error
@@ -157,12 +243,17 @@ fn test_register() {
             asset_info: AssetInfo::Token {
                 contract_addr: Addr::unchecked("asset")
             },
-            staking_token: Addr::unchecked("staking"),
+            staking_token: AssetInfo::Token {
+                contract_addr: Addr::unchecked("staking"),
+            },
             total_bond_amount: Uint128::zero(),
             reward_index: Decimal::zero(),
             pending_reward: Uint128::zero(),
             migration_deprecated_staking_token: None,
             migration_index_snapshot: None,
+            max_pending_reward: None,
+            max_total_bond: None,
+            staker_whitelist: None,
         }
     );
 }
@@ -170,6 +261,7 @@ fn test_register() {
 #[test]
 fn test_query_staker_pagination() {
     let mut deps = mock_dependencies_with_balance(&[coin(10000000000u128, ORAI_DENOM)]);
+    mock_token_balance(&mut deps.querier, Uint128::from(10000000000u128));
 
     let msg = InstantiateMsg {
         owner: Some(Addr::unchecked("owner")),
@@ -203,7 +295,9 @@ fn test_query_staker_pagination() {
         asset_info: AssetInfo::Token {
             contract_addr: Addr::unchecked("asset"),
         },
-        staking_token: Addr::unchecked("staking"),
+        staking_token: AssetInfo::Token {
+            contract_addr: Addr::unchecked("staking"),
+        },
     };
 
     let info = mock_info("owner", &[]);
@@ -238,14 +332,46 @@ fn test_query_staker_pagination() {
                 limit: Some(10),
                 order: Some(Order::Ascending.into()),
                 start_after: start_after.clone(),
+                cursor: None,
             },
         )
         .unwrap();
-        let res: Vec<RewardInfoResponse> = from_binary(&data).unwrap();
-        let stakers: Vec<Addr> = res.into_iter().map(|r| r.staker_addr).collect();
+        let res: RewardInfosResponse = from_binary(&data).unwrap();
+        let stakers: Vec<Addr> = res
+            .reward_infos
+            .into_iter()
+            .map(|r| r.staker_addr)
+            .collect();
         let staker_addrs: Vec<String> =
             stakers.clone().into_iter().map(|s| s.to_string()).collect();
         start_after = stakers.into_iter().last();
         println!("{:?}", staker_addrs);
     }
+
+    // query stakers with cursor in descending order, the last page has no next cursor
+    let mut cursor: Option<String> = None;
+    let mut stakers: Vec<Addr> = vec![];
+    for page in 0..100 / 30 + 1 {
+        let data = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::RewardInfos {
+                asset_info: AssetInfo::Token {
+                    contract_addr: Addr::unchecked("asset"),
+                },
+                limit: Some(30),
+                order: Some(Order::Descending.into()),
+                start_after: None,
+                cursor: cursor.clone(),
+            },
+        )
+        .unwrap();
+        let res: RewardInfosResponse = from_binary(&data).unwrap();
+        stakers.extend(res.reward_infos.into_iter().map(|r| r.staker_addr));
+        cursor = res.next_cursor;
+        assert_eq!(cursor.is_none(), page == 100 / 30);
+    }
+    stakers.sort();
+    stakers.dedup();
+    assert_eq!(stakers.len(), 100);
 }
@@ -1,5 +1,6 @@
 use crate::contract::{execute, instantiate, query};
 use crate::state::{read_pool_info, store_pool_info};
+use crate::testing::mock_token_balance;
 use cosmwasm_std::testing::{mock_dependencies_with_balance, mock_env, mock_info};
 use cosmwasm_std::{
     coin, from_binary, to_binary, Addr, Api, Decimal, StdError, SubMsg, Uint128, WasmMsg,
@@ -18,6 +19,7 @@ fn test_deprecate() {
         coin(10000000000u128, ORAI_DENOM),
         coin(20000000000u128, ATOM_DENOM),
     ]);
+    mock_token_balance(&mut deps.querier, Uint128::from(10000000000u128));
 
     let msg = InstantiateMsg {
         owner: Some(Addr::unchecked("owner")),
@@ -35,7 +37,9 @@ fn test_deprecate() {
         asset_info: AssetInfo::Token {
             contract_addr: Addr::unchecked("asset"),
         },
-        staking_token: Addr::unchecked("staking"),
+        staking_token: AssetInfo::Token {
+            contract_addr: Addr::unchecked("staking"),
+        },
     };
 
     let info = mock_info("owner", &[]);
@@ -155,7 +159,9 @@ fn test_deprecate() {
         asset_info: AssetInfo::Token {
             contract_addr: Addr::unchecked("asset"),
         },
-        new_staking_token: Addr::unchecked("new_staking"),
+        new_staking_token: AssetInfo::Token {
+            contract_addr: Addr::unchecked("new_staking"),
+        },
     };
     let info = mock_info("owner", &[]);
     execute(deps.as_mut(), mock_env(), info, msg).unwrap();
@@ -190,11 +196,15 @@ fn test_deprecate() {
     assert_eq!(
         res_cmp,
         PoolInfoResponse {
-            staking_token: Addr::unchecked("new_staking"),
+            staking_token: AssetInfo::Token {
+                contract_addr: Addr::unchecked("new_staking"),
+            },
             total_bond_amount: Uint128::zero(), // reset
             reward_index: Decimal::from_ratio(100u128, 100u128), // stays the same
             migration_index_snapshot: Some(Decimal::from_ratio(100u128, 100u128)),
-            migration_deprecated_staking_token: Some(Addr::unchecked("staking")),
+            migration_deprecated_staking_token: Some(AssetInfo::Token {
+                contract_addr: Addr::unchecked("staking"),
+            }),
             pending_reward: Uint128::from(100u128), // new reward waiting here
             ..res
         }
@@ -1,9 +1,17 @@
-use crate::migration::{migrate_rewards_store, LegacyPoolInfo, LegacyRewardInfo};
-use crate::state::PREFIX_POOL_INFO;
+use crate::contract::migrate;
+use crate::migration::{
+    migrate_pool_infos, migrate_rewards_store, LegacyMigrationParams, LegacyPoolInfo,
+    LegacyPoolInfoV2, LegacyRewardInfo, CONTRACT_NAME, CONTRACT_VERSION,
+};
+use crate::state::{read_pool_info, MigrationParams, PoolInfo, PREFIX_POOL_INFO};
 use crate::state::{rewards_read, RewardInfo, PREFIX_REWARD};
-use cosmwasm_std::{testing::mock_dependencies, Api};
+use cosmwasm_std::testing::{mock_dependencies, mock_env};
+use cosmwasm_std::{attr, Api, StdError};
 use cosmwasm_std::{CanonicalAddr, Decimal, Storage, Uint128};
 use cosmwasm_storage::Bucket;
+use cw2::{get_contract_version, set_contract_version};
+use oraiswap::asset::AssetInfoRaw;
+use oraiswap::staking::MigrateMsg;
 
 pub fn pool_infos_old_store(storage: &mut dyn Storage) -> Bucket<LegacyPoolInfo> {
     Bucket::new(storage, PREFIX_POOL_INFO)
@@ -95,3 +103,127 @@ fn test_migration() {
         }
     );
 }
+
+#[test]
+fn test_migrate_pool_infos() {
+    let mut deps = mock_dependencies();
+    let staking_token = deps.api.addr_canonicalize("staking").unwrap();
+    let deprecated_staking_token = deps.api.addr_canonicalize("old_staking").unwrap();
+    let asset_key = deps.api.addr_canonicalize("asset").unwrap();
+
+    Bucket::new(&mut deps.storage, PREFIX_POOL_INFO)
+        .save(
+            asset_key.as_slice(),
+            &LegacyPoolInfoV2 {
+                staking_token: staking_token.clone(),
+                pending_reward: Uint128::from(1u128),
+                total_bond_amount: Uint128::from(100u128),
+                reward_index: Decimal::percent(1),
+                migration_params: Some(LegacyMigrationParams {
+                    index_snapshot: Decimal::percent(2),
+                    deprecated_staking_token: deprecated_staking_token.clone(),
+                }),
+            },
+        )
+        .unwrap();
+
+    migrate_pool_infos(&mut deps.storage).unwrap();
+
+    assert_eq!(
+        read_pool_info(&deps.storage, asset_key.as_slice()).unwrap(),
+        PoolInfo {
+            staking_token: AssetInfoRaw::Token {
+                contract_addr: staking_token,
+            },
+            pending_reward: Uint128::from(1u128),
+            total_bond_amount: Uint128::from(100u128),
+            reward_index: Decimal::percent(1),
+            migration_params: Some(MigrationParams {
+                index_snapshot: Decimal::percent(2),
+                deprecated_staking_token: AssetInfoRaw::Token {
+                    contract_addr: deprecated_staking_token,
+                },
+            }),
+        }
+    );
+}
+
+#[test]
+fn test_versioned_migrations() {
+    let mut deps = mock_dependencies();
+    let staking_token = deps.api.addr_canonicalize("staking").unwrap();
+    let asset_key = deps.api.addr_canonicalize("asset").unwrap();
+
+    Bucket::new(&mut deps.storage, PREFIX_POOL_INFO)
+        .save(
+            asset_key.as_slice(),
+            &LegacyPoolInfoV2 {
+                staking_token: staking_token.clone(),
+                pending_reward: Uint128::zero(),
+                total_bond_amount: Uint128::from(100u128),
+                reward_index: Decimal::zero(),
+                migration_params: None,
+            },
+        )
+        .unwrap();
+
+    let msg = MigrateMsg {
+        staker_addrs: vec![],
+        from_version: None,
+    };
+
+    // contract without stored version applies every step
+    let res = migrate(deps.as_mut(), mock_env(), msg.clone()).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "migrate"),
+            attr("from_version", "0.0.0"),
+            attr("to_version", CONTRACT_VERSION),
+            attr("applied_steps", "pool_infos_asset_info"),
+        ]
+    );
+    assert_eq!(
+        read_pool_info(&deps.storage, asset_key.as_slice())
+            .unwrap()
+            .staking_token,
+        AssetInfoRaw::Token {
+            contract_addr: staking_token,
+        }
+    );
+    let contract_version = get_contract_version(&deps.storage).unwrap();
+    assert_eq!(contract_version.contract, CONTRACT_NAME);
+    assert_eq!(contract_version.version, CONTRACT_VERSION);
+
+    // migrating again from the stored version applies no step
+    let res = migrate(deps.as_mut(), mock_env(), msg.clone()).unwrap();
+    assert_eq!(res.attributes[1], attr("from_version", CONTRACT_VERSION));
+    assert_eq!(res.attributes[3], attr("applied_steps", ""));
+
+    // downgrade and other contracts are rejected
+    set_contract_version(&mut deps.storage, CONTRACT_NAME, "99.0.0").unwrap();
+    let err = migrate(deps.as_mut(), mock_env(), msg.clone()).unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("cannot migrate from newer version 99.0.0")
+    );
+    set_contract_version(&mut deps.storage, "crates.io:other", CONTRACT_VERSION).unwrap();
+    let err = migrate(deps.as_mut(), mock_env(), msg).unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("cannot migrate from contract crates.io:other")
+    );
+
+    // contract deployed at the latest layout before the version was stored skips the steps
+    let mut deps = mock_dependencies();
+    let res = migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg {
+            staker_addrs: vec![],
+            from_version: Some(CONTRACT_VERSION.to_string()),
+        },
+    )
+    .unwrap();
+    assert_eq!(res.attributes[3], attr("applied_steps", ""));
+}
@@ -1,5 +1,19 @@
+use cosmwasm_std::testing::MockQuerier;
+use cosmwasm_std::{to_binary, ContractResult, SystemResult, Uint128};
+use cw20::BalanceResponse;
+
 mod contract_test;
 mod deprecate_test;
 mod migrate_test;
 mod reward_test;
 mod staking_test;
+
+// the cw20 staking tokens answer the balance queries with the given balance,
+// as if the contract had received the tokens of the bond hooks
+pub fn mock_token_balance(querier: &mut MockQuerier, balance: Uint128) {
+    querier.update_wasm(move |_| {
+        SystemResult::Ok(ContractResult::Ok(
+            to_binary(&BalanceResponse { balance }).unwrap(),
+        ))
+    });
+}
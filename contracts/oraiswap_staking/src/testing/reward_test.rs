@@ -1,13 +1,17 @@
 use crate::contract::{execute, instantiate, query};
 use crate::state::{read_pool_info, rewards_read, store_pool_info, PoolInfo, RewardInfo};
+use crate::testing::mock_token_balance;
 use cosmwasm_std::testing::{mock_dependencies_with_balance, mock_env, mock_info};
-use cosmwasm_std::{coin, from_binary, to_binary, Addr, Api, Decimal, Uint128};
-use cw20::Cw20ReceiveMsg;
+use cosmwasm_std::{
+    attr, coin, from_binary, to_binary, Addr, Api, BankMsg, Decimal, StdError, SubMsg, Uint128,
+    WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
 use oraiswap::asset::{Asset, AssetInfo, ORAI_DENOM};
 use oraiswap::create_entry_points_testing;
 use oraiswap::staking::{
-    Cw20HookMsg, ExecuteMsg, InstantiateMsg, PoolInfoResponse, QueryMsg, RewardInfoResponse,
-    RewardInfoResponseItem,
+    Cw20HookMsg, ExecuteMsg, InstantiateMsg, PoolInfoResponse, ProjectedRewardResponse, QueryMsg,
+    RewardInfoResponse, RewardInfoResponseItem,
 };
 use oraiswap::testing::{MockApp, ATOM_DENOM};
 
@@ -17,6 +21,7 @@ fn test_deposit_reward() {
         coin(10000000000u128, ORAI_DENOM),
         coin(20000000000u128, ATOM_DENOM),
     ]);
+    mock_token_balance(&mut deps.querier, Uint128::from(10000000000u128));
 
     let msg = InstantiateMsg {
         owner: Some(Addr::unchecked("owner")),
@@ -57,7 +62,9 @@ fn test_deposit_reward() {
         asset_info: AssetInfo::Token {
             contract_addr: Addr::unchecked("asset"),
         },
-        staking_token: Addr::unchecked("staking"),
+        staking_token: AssetInfo::Token {
+            contract_addr: Addr::unchecked("staking"),
+        },
     };
 
     let info = mock_info("owner", &[]);
@@ -201,7 +208,9 @@ fn test_deposit_reward_when_no_bonding() {
         asset_info: AssetInfo::Token {
             contract_addr: Addr::unchecked("asset"),
         },
-        staking_token: Addr::unchecked("staking"),
+        staking_token: AssetInfo::Token {
+            contract_addr: Addr::unchecked("staking"),
+        },
     };
 
     let info = mock_info("owner", &[]);
@@ -285,12 +294,147 @@ fn test_deposit_reward_when_no_bonding() {
     );
 }
 
+#[test]
+fn test_max_pending_reward_and_clawback() {
+    let mut deps = mock_dependencies_with_balance(&[
+        coin(10000000000u128, ORAI_DENOM),
+        coin(20000000000u128, ATOM_DENOM),
+    ]);
+
+    let msg = InstantiateMsg {
+        owner: Some(Addr::unchecked("owner")),
+        rewarder: Addr::unchecked("rewarder"),
+        minter: Some(Addr::unchecked("mint")),
+        oracle_addr: Addr::unchecked("oracle"),
+        factory_addr: Addr::unchecked("factory"),
+        base_denom: None,
+    };
+
+    let info = mock_info("addr", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let asset_info = AssetInfo::Token {
+        contract_addr: Addr::unchecked("asset"),
+    };
+
+    let msg = ExecuteMsg::UpdateRewardsPerSec {
+        asset_info: asset_info.clone(),
+        assets: vec![
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: ORAI_DENOM.to_string(),
+                },
+                amount: 100u128.into(),
+            },
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: ATOM_DENOM.to_string(),
+                },
+                amount: 300u128.into(),
+            },
+        ],
+    };
+    let info = mock_info("owner", &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let msg = ExecuteMsg::RegisterAsset {
+        asset_info: asset_info.clone(),
+        staking_token: AssetInfo::Token {
+            contract_addr: Addr::unchecked("staking"),
+        },
+    };
+    let info = mock_info("owner", &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // only owner can set the cap
+    let msg = ExecuteMsg::UpdateMaxPendingReward {
+        asset_info: asset_info.clone(),
+        max_pending_reward: Some(Uint128::from(400u128)),
+    };
+    let info = mock_info("addr", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg.clone());
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "unauthorized"),
+        _ => panic!("Must return unauthorized error"),
+    }
+    let info = mock_info("owner", &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // nobody bonds, only 400 is kept and the rest is returned to the rewarder
+    let msg = ExecuteMsg::DepositReward {
+        rewards: vec![Asset {
+            info: asset_info.clone(),
+            amount: Uint128::from(1000u128),
+        }],
+    };
+    let info = mock_info("rewarder", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(BankMsg::Send {
+                to_address: "rewarder".to_string(),
+                amount: vec![coin(150u128, ORAI_DENOM)],
+            }),
+            SubMsg::new(BankMsg::Send {
+                to_address: "rewarder".to_string(),
+                amount: vec![coin(450u128, ATOM_DENOM)],
+            }),
+        ]
+    );
+
+    let res: PoolInfoResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::PoolInfo {
+                asset_info: asset_info.clone(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.pending_reward, Uint128::from(400u128));
+    assert_eq!(res.max_pending_reward, Some(Uint128::from(400u128)));
+
+    // clawback the undistributed rewards
+    let msg = ExecuteMsg::ClawbackUnallocatedRewards {
+        asset_info: asset_info.clone(),
+    };
+    let info = mock_info("owner", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(BankMsg::Send {
+                to_address: "rewarder".to_string(),
+                amount: vec![coin(100u128, ORAI_DENOM)],
+            }),
+            SubMsg::new(BankMsg::Send {
+                to_address: "rewarder".to_string(),
+                amount: vec![coin(300u128, ATOM_DENOM)],
+            }),
+        ]
+    );
+
+    let pool_info = read_pool_info(&deps.storage, &asset_info.to_vec(&deps.api).unwrap()).unwrap();
+    assert_eq!(pool_info.pending_reward, Uint128::zero());
+
+    let info = mock_info("owner", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg);
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "no unallocated rewards"),
+        _ => panic!("Must return no unallocated rewards error"),
+    }
+}
+
 #[test]
 fn test_before_share_changes() {
     let mut deps = mock_dependencies_with_balance(&[
         coin(10000000000u128, ORAI_DENOM),
         coin(20000000000u128, ATOM_DENOM),
     ]);
+    mock_token_balance(&mut deps.querier, Uint128::from(10000000000u128));
 
     let msg = InstantiateMsg {
         owner: Some(Addr::unchecked("owner")),
@@ -331,7 +475,9 @@ fn test_before_share_changes() {
         asset_info: AssetInfo::Token {
             contract_addr: Addr::unchecked("asset"),
         },
-        staking_token: Addr::unchecked("staking"),
+        staking_token: AssetInfo::Token {
+            contract_addr: Addr::unchecked("staking"),
+        },
     };
 
     let info = mock_info("owner", &[]);
@@ -556,12 +702,19 @@ fn test_withdraw() {
         .unwrap();
 
     let lp_addr = app.create_token("lptoken");
+    // the staking contract holds the bonded lp tokens
+    app.set_token_balances(&[(
+        &"lptoken".to_string(),
+        &[(&staking_addr.to_string(), &Uint128::from(100u128))],
+    )]);
 
     let msg = ExecuteMsg::RegisterAsset {
         asset_info: AssetInfo::Token {
             contract_addr: asset_addr.clone(),
         },
-        staking_token: lp_addr.clone(),
+        staking_token: AssetInfo::Token {
+            contract_addr: lp_addr.clone(),
+        },
     };
 
     let _res = app
@@ -614,6 +767,7 @@ fn test_withdraw() {
         asset_info: Some(AssetInfo::Token {
             contract_addr: asset_addr.clone(),
         }),
+        auto_bond: None,
     };
 
     let res = app
@@ -623,12 +777,278 @@ fn test_withdraw() {
     println!("{:?}", res);
 }
 
+#[test]
+fn test_withdraw_all() {
+    let mut deps = mock_dependencies_with_balance(&[
+        coin(10000000000u128, ORAI_DENOM),
+        coin(20000000000u128, ATOM_DENOM),
+    ]);
+    mock_token_balance(&mut deps.querier, Uint128::from(10000000000u128));
+
+    let msg = InstantiateMsg {
+        owner: Some(Addr::unchecked("owner")),
+        rewarder: Addr::unchecked("rewarder"),
+        minter: Some(Addr::unchecked("mint")),
+        oracle_addr: Addr::unchecked("oracle"),
+        factory_addr: Addr::unchecked("factory"),
+        base_denom: None,
+    };
+
+    let info = mock_info("addr", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // register 2 pools with the same reward assets and bond 100 tokens to each
+    for (asset, staking_token) in [("asset", "staking"), ("asset2", "staking2")] {
+        let asset_info = AssetInfo::Token {
+            contract_addr: Addr::unchecked(asset),
+        };
+        let msg = ExecuteMsg::UpdateRewardsPerSec {
+            asset_info: asset_info.clone(),
+            assets: vec![
+                Asset {
+                    info: AssetInfo::NativeToken {
+                        denom: ORAI_DENOM.to_string(),
+                    },
+                    amount: 100u128.into(),
+                },
+                Asset {
+                    info: AssetInfo::NativeToken {
+                        denom: ATOM_DENOM.to_string(),
+                    },
+                    amount: 300u128.into(),
+                },
+            ],
+        };
+        let info = mock_info("owner", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let msg = ExecuteMsg::RegisterAsset {
+            asset_info: asset_info.clone(),
+            staking_token: AssetInfo::Token {
+                contract_addr: Addr::unchecked(staking_token),
+            },
+        };
+        let info = mock_info("owner", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: "addr".into(),
+            amount: Uint128::from(100u128),
+            msg: to_binary(&Cw20HookMsg::Bond { asset_info }).unwrap(),
+        });
+        let info = mock_info(staking_token, &[]);
+        let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    }
+
+    // each pool accrues 100 rewards for the staker
+    let asset_keys = vec![
+        deps.api.addr_canonicalize("asset").unwrap().to_vec(),
+        deps.api.addr_canonicalize("asset2").unwrap().to_vec(),
+    ];
+    for asset_key in asset_keys.iter() {
+        let mut pool_info = read_pool_info(&deps.storage, asset_key).unwrap();
+        pool_info.reward_index = Decimal::one();
+        store_pool_info(&mut deps.storage, asset_key, &pool_info).unwrap();
+    }
+
+    // rewards of both pools are merged into one transfer per denom
+    let msg = ExecuteMsg::WithdrawAll {
+        asset_infos: None,
+        auto_bond: None,
+    };
+    let info = mock_info("addr", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(BankMsg::Send {
+                to_address: "addr".to_string(),
+                amount: vec![coin(50u128, ORAI_DENOM)],
+            }),
+            SubMsg::new(BankMsg::Send {
+                to_address: "addr".to_string(),
+                amount: vec![coin(150u128, ATOM_DENOM)],
+            }),
+        ]
+    );
+
+    for asset_key in asset_keys.iter() {
+        let mut pool_info = read_pool_info(&deps.storage, asset_key).unwrap();
+        pool_info.reward_index = Decimal::from_ratio(2u128, 1u128);
+        store_pool_info(&mut deps.storage, asset_key, &pool_info).unwrap();
+    }
+
+    // only withdraw the rewards of the given pool
+    let msg = ExecuteMsg::WithdrawAll {
+        asset_infos: Some(vec![AssetInfo::Token {
+            contract_addr: Addr::unchecked("asset"),
+        }]),
+        auto_bond: None,
+    };
+    let info = mock_info("addr", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(BankMsg::Send {
+                to_address: "addr".to_string(),
+                amount: vec![coin(25u128, ORAI_DENOM)],
+            }),
+            SubMsg::new(BankMsg::Send {
+                to_address: "addr".to_string(),
+                amount: vec![coin(75u128, ATOM_DENOM)],
+            }),
+        ]
+    );
+
+    let reward_info: RewardInfo =
+        rewards_read(&deps.storage, &deps.api.addr_canonicalize("addr").unwrap())
+            .load(&asset_keys[1])
+            .unwrap();
+    assert_eq!(reward_info.index, Decimal::one());
+}
+
+#[test]
+fn test_withdraw_lp_reward_with_auto_bond() {
+    let mut deps = mock_dependencies_with_balance(&[]);
+    mock_token_balance(&mut deps.querier, Uint128::from(10000000000u128));
+
+    let msg = InstantiateMsg {
+        owner: Some(Addr::unchecked("owner")),
+        rewarder: Addr::unchecked("rewarder"),
+        minter: Some(Addr::unchecked("mint")),
+        oracle_addr: Addr::unchecked("oracle"),
+        factory_addr: Addr::unchecked("factory"),
+        base_denom: None,
+    };
+    let info = mock_info("addr", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // stakers of the pool of asset earn the LP token of the pool of asset2
+    let asset_info = AssetInfo::Token {
+        contract_addr: Addr::unchecked("asset"),
+    };
+    let asset_info2 = AssetInfo::Token {
+        contract_addr: Addr::unchecked("asset2"),
+    };
+    let lp_reward = AssetInfo::Token {
+        contract_addr: Addr::unchecked("staking2"),
+    };
+    for (asset_info, staking_token) in [(&asset_info, "staking"), (&asset_info2, "staking2")] {
+        let msg = ExecuteMsg::RegisterAsset {
+            asset_info: asset_info.clone(),
+            staking_token: AssetInfo::Token {
+                contract_addr: Addr::unchecked(staking_token),
+            },
+        };
+        let info = mock_info("owner", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    }
+    let msg = ExecuteMsg::UpdateRewardsPerSec {
+        asset_info: asset_info.clone(),
+        assets: vec![Asset {
+            info: lp_reward.clone(),
+            amount: 100u128.into(),
+        }],
+    };
+    let info = mock_info("owner", &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr".into(),
+        amount: Uint128::from(100u128),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            asset_info: asset_info.clone(),
+        })
+        .unwrap(),
+    });
+    let info = mock_info("staking", &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // the rewarder deposits 100 LP tokens of the pool of asset2
+    let msg = ExecuteMsg::DepositReward {
+        rewards: vec![Asset {
+            info: asset_info.clone(),
+            amount: Uint128::from(100u128),
+        }],
+    };
+    let info = mock_info("rewarder", &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // the LP reward is transferred like a token reward
+    let msg = ExecuteMsg::Withdraw {
+        asset_info: Some(asset_info.clone()),
+        auto_bond: None,
+    };
+    let info = mock_info("addr", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(WasmMsg::Execute {
+            contract_addr: "staking2".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "addr".to_string(),
+                amount: Uint128::from(100u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        })]
+    );
+
+    // with auto bond, the LP reward is bonded to the pool of asset2 instead
+    let msg = ExecuteMsg::DepositReward {
+        rewards: vec![Asset {
+            info: asset_info.clone(),
+            amount: Uint128::from(100u128),
+        }],
+    };
+    let info = mock_info("rewarder", &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let msg = ExecuteMsg::Withdraw {
+        asset_info: Some(asset_info.clone()),
+        auto_bond: Some(asset_info2.clone()),
+    };
+    let info = mock_info("addr", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(res.messages, vec![]);
+    assert!(res.attributes.contains(&attr("bond_amount", "100")));
+
+    let res: RewardInfoResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::RewardInfo {
+                staker_addr: Addr::unchecked("addr"),
+                asset_info: Some(asset_info2.clone()),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.reward_infos[0].bond_amount, Uint128::from(100u128));
+
+    let res: PoolInfoResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::PoolInfo {
+                asset_info: asset_info2,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.total_bond_amount, Uint128::from(100u128));
+}
+
 #[test]
 fn test_update_rewards_per_sec() {
     let mut deps = mock_dependencies_with_balance(&[
         coin(10000000000u128, ORAI_DENOM),
         coin(20000000000u128, ATOM_DENOM),
     ]);
+    mock_token_balance(&mut deps.querier, Uint128::from(10000000000u128));
 
     let msg = InstantiateMsg {
         owner: Some(Addr::unchecked("owner")),
@@ -669,7 +1089,9 @@ fn test_update_rewards_per_sec() {
         asset_info: AssetInfo::Token {
             contract_addr: Addr::unchecked("asset"),
         },
-        staking_token: Addr::unchecked("staking"),
+        staking_token: AssetInfo::Token {
+            contract_addr: Addr::unchecked("staking"),
+        },
     };
 
     let info = mock_info("owner", &[]);
@@ -791,6 +1213,7 @@ fn test_update_rewards_per_sec_with_multiple_bond() {
         coin(10000000000u128, ORAI_DENOM),
         coin(20000000000u128, ATOM_DENOM),
     ]);
+    mock_token_balance(&mut deps.querier, Uint128::from(10000000000u128));
 
     let msg = InstantiateMsg {
         owner: Some(Addr::unchecked("owner")),
@@ -831,7 +1254,9 @@ fn test_update_rewards_per_sec_with_multiple_bond() {
         asset_info: AssetInfo::Token {
             contract_addr: Addr::unchecked("asset"),
         },
-        staking_token: Addr::unchecked("staking"),
+        staking_token: AssetInfo::Token {
+            contract_addr: Addr::unchecked("staking"),
+        },
     };
 
     let info = mock_info("owner", &[]);
@@ -1013,3 +1438,135 @@ fn test_update_rewards_per_sec_with_multiple_bond() {
         }
     );
 }
+
+#[test]
+fn test_query_projected_reward() {
+    let mut deps = mock_dependencies_with_balance(&[
+        coin(10000000000u128, ORAI_DENOM),
+        coin(20000000000u128, ATOM_DENOM),
+    ]);
+    mock_token_balance(&mut deps.querier, Uint128::from(10000000000u128));
+
+    let msg = InstantiateMsg {
+        owner: Some(Addr::unchecked("owner")),
+        rewarder: Addr::unchecked("rewarder"),
+        minter: Some(Addr::unchecked("mint")),
+        oracle_addr: Addr::unchecked("oracle"),
+        factory_addr: Addr::unchecked("factory"),
+        base_denom: None,
+    };
+
+    let info = mock_info("addr", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let msg = ExecuteMsg::UpdateRewardsPerSec {
+        asset_info: AssetInfo::Token {
+            contract_addr: Addr::unchecked("asset"),
+        },
+        assets: vec![
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: ORAI_DENOM.to_string(),
+                },
+                amount: 100u128.into(),
+            },
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: ATOM_DENOM.to_string(),
+                },
+                amount: 300u128.into(),
+            },
+        ],
+    };
+    let info = mock_info("owner", &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let msg = ExecuteMsg::RegisterAsset {
+        asset_info: AssetInfo::Token {
+            contract_addr: Addr::unchecked("asset"),
+        },
+        staking_token: AssetInfo::Token {
+            contract_addr: Addr::unchecked("staking"),
+        },
+    };
+    let info = mock_info("owner", &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // bond 100 tokens for addr and 300 tokens for addr2
+    for (staker, amount) in [("addr", 100u128), ("addr2", 300u128)] {
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: staker.into(),
+            amount: Uint128::from(amount),
+            msg: to_binary(&Cw20HookMsg::Bond {
+                asset_info: AssetInfo::Token {
+                    contract_addr: Addr::unchecked("asset"),
+                },
+            })
+            .unwrap(),
+        });
+        let info = mock_info("staking", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    }
+
+    // 400 rewards per second over 10 seconds, addr owns 1/4 of the pool
+    let res: ProjectedRewardResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::ProjectedReward {
+                staker_addr: Addr::unchecked("addr"),
+                asset_info: AssetInfo::Token {
+                    contract_addr: Addr::unchecked("asset"),
+                },
+                seconds_ahead: 10,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res,
+        ProjectedRewardResponse {
+            staker_addr: Addr::unchecked("addr"),
+            asset_info: AssetInfo::Token {
+                contract_addr: Addr::unchecked("asset"),
+            },
+            bond_amount: Uint128::from(100u128),
+            pending_reward: Uint128::zero(),
+            projected_reward: Uint128::from(1000u128),
+            projected_reward_assets: vec![
+                Asset {
+                    info: AssetInfo::NativeToken {
+                        denom: ORAI_DENOM.to_string(),
+                    },
+                    amount: Uint128::from(250u128),
+                },
+                Asset {
+                    info: AssetInfo::NativeToken {
+                        denom: ATOM_DENOM.to_string(),
+                    },
+                    amount: Uint128::from(750u128),
+                },
+            ],
+        }
+    );
+
+    // a staker without position has nothing projected
+    let res: ProjectedRewardResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::ProjectedReward {
+                staker_addr: Addr::unchecked("addr3"),
+                asset_info: AssetInfo::Token {
+                    contract_addr: Addr::unchecked("asset"),
+                },
+                seconds_ahead: 10,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.projected_reward, Uint128::zero());
+    assert!(res.projected_reward_assets.is_empty());
+}
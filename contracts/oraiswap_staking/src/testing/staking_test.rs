@@ -1,4 +1,5 @@
 use crate::contract::{execute, instantiate, query};
+use crate::testing::mock_token_balance;
 use cosmwasm_std::testing::{
     mock_dependencies, mock_dependencies_with_balance, mock_env, mock_info,
 };
@@ -11,14 +12,15 @@ use oraiswap::asset::{Asset, AssetInfo, ORAI_DENOM};
 use oraiswap::create_entry_points_testing;
 use oraiswap::pair::PairResponse;
 use oraiswap::staking::{
-    Cw20HookMsg, ExecuteMsg, InstantiateMsg, PoolInfoResponse, QueryMsg, RewardInfoResponse,
-    RewardInfoResponseItem,
+    Cw20HookMsg, ExecuteMsg, HooksResponse, InstantiateMsg, PoolInfoResponse, PoolOverviewResponse,
+    QueryMsg, RewardInfoResponse, RewardInfoResponseItem, StakingHookMsg, VotingPowerResponse,
 };
 use oraiswap::testing::{AttributeUtil, MockApp, ATOM_DENOM};
 
 #[test]
 fn test_bond_tokens() {
     let mut deps = mock_dependencies();
+    mock_token_balance(&mut deps.querier, Uint128::from(10000000000u128));
 
     let msg = InstantiateMsg {
         owner: Some(Addr::unchecked("owner")),
@@ -36,7 +38,9 @@ fn test_bond_tokens() {
         asset_info: AssetInfo::Token {
             contract_addr: Addr::unchecked("asset"),
         },
-        staking_token: Addr::unchecked("staking"),
+        staking_token: AssetInfo::Token {
+            contract_addr: Addr::unchecked("staking"),
+        },
     };
 
     let info = mock_info("owner", &[]);
@@ -101,12 +105,17 @@ fn test_bond_tokens() {
             asset_info: AssetInfo::Token {
                 contract_addr: Addr::unchecked("asset")
             },
-            staking_token: Addr::unchecked("staking"),
+            staking_token: AssetInfo::Token {
+                contract_addr: Addr::unchecked("staking"),
+            },
             total_bond_amount: Uint128::from(100u128),
             reward_index: Decimal::zero(),
             pending_reward: Uint128::zero(),
             migration_deprecated_staking_token: None,
             migration_index_snapshot: None,
+            max_pending_reward: None,
+            max_total_bond: None,
+            staker_whitelist: None,
         }
     );
 
@@ -141,12 +150,17 @@ fn test_bond_tokens() {
             asset_info: AssetInfo::Token {
                 contract_addr: Addr::unchecked("asset")
             },
-            staking_token: Addr::unchecked("staking"),
+            staking_token: AssetInfo::Token {
+                contract_addr: Addr::unchecked("staking"),
+            },
             total_bond_amount: Uint128::from(200u128),
             reward_index: Decimal::zero(),
             pending_reward: Uint128::zero(),
             migration_deprecated_staking_token: None,
             migration_index_snapshot: None,
+            max_pending_reward: None,
+            max_total_bond: None,
+            staker_whitelist: None,
         }
     );
 
@@ -170,12 +184,327 @@ fn test_bond_tokens() {
     }
 }
 
+#[test]
+fn test_bond_for_staker() {
+    let mut deps = mock_dependencies();
+    mock_token_balance(&mut deps.querier, Uint128::from(10000000000u128));
+
+    let msg = InstantiateMsg {
+        owner: Some(Addr::unchecked("owner")),
+        rewarder: Addr::unchecked("rewarder"),
+        minter: Some(Addr::unchecked("mint")),
+        oracle_addr: Addr::unchecked("oracle"),
+        factory_addr: Addr::unchecked("factory"),
+        base_denom: None,
+    };
+
+    let info = mock_info("addr", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let msg = ExecuteMsg::RegisterAsset {
+        asset_info: AssetInfo::Token {
+            contract_addr: Addr::unchecked("asset"),
+        },
+        staking_token: AssetInfo::Token {
+            contract_addr: Addr::unchecked("staking"),
+        },
+    };
+
+    let info = mock_info("owner", &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // the pair sends minted LP tokens and bonds them for the provider
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "pair".to_string(),
+        amount: Uint128::from(100u128),
+        msg: to_binary(&Cw20HookMsg::BondFor {
+            asset_info: AssetInfo::Token {
+                contract_addr: Addr::unchecked("asset"),
+            },
+            staker_addr: Addr::unchecked("addr"),
+        })
+        .unwrap(),
+    });
+
+    let info = mock_info("staking", &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let data = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::RewardInfo {
+            asset_info: None,
+            staker_addr: Addr::unchecked("addr"),
+        },
+    )
+    .unwrap();
+    let res: RewardInfoResponse = from_binary(&data).unwrap();
+    assert_eq!(res.reward_infos[0].bond_amount, Uint128::from(100u128));
+
+    let data = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::RewardInfo {
+            asset_info: None,
+            staker_addr: Addr::unchecked("pair"),
+        },
+    )
+    .unwrap();
+    let res: RewardInfoResponse = from_binary(&data).unwrap();
+    assert_eq!(res.reward_infos, vec![]);
+}
+
+#[test]
+fn test_bond_native() {
+    let mut deps = mock_dependencies();
+
+    let msg = InstantiateMsg {
+        owner: Some(Addr::unchecked("owner")),
+        rewarder: Addr::unchecked("rewarder"),
+        minter: Some(Addr::unchecked("mint")),
+        oracle_addr: Addr::unchecked("oracle"),
+        factory_addr: Addr::unchecked("factory"),
+        base_denom: None,
+    };
+
+    let info = mock_info("addr", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // the staking token is a tokenfactory LP denom
+    let lp_denom = "factory/pair/lp";
+    let msg = ExecuteMsg::RegisterAsset {
+        asset_info: AssetInfo::Token {
+            contract_addr: Addr::unchecked("asset"),
+        },
+        staking_token: AssetInfo::NativeToken {
+            denom: lp_denom.to_string(),
+        },
+    };
+
+    let info = mock_info("owner", &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // cw20 tokens can not be bonded to the pool
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr".to_string(),
+        amount: Uint128::from(100u128),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            asset_info: AssetInfo::Token {
+                contract_addr: Addr::unchecked("asset"),
+            },
+        })
+        .unwrap(),
+    });
+    let info = mock_info("staking", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(res, StdError::generic_err("unauthorized"));
+
+    let msg = ExecuteMsg::BondNative {
+        asset_info: AssetInfo::Token {
+            contract_addr: Addr::unchecked("asset"),
+        },
+    };
+
+    // no funds sent
+    let info = mock_info("addr", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap_err();
+    assert_eq!(
+        res,
+        StdError::generic_err("must send exactly one native staking token")
+    );
+
+    // wrong denom
+    let info = mock_info("addr", &[coin(100u128, ORAI_DENOM)]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap_err();
+    assert_eq!(res, StdError::generic_err("unauthorized"));
+
+    let info = mock_info("addr", &[coin(100u128, lp_denom)]);
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let data = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::PoolInfo {
+            asset_info: AssetInfo::Token {
+                contract_addr: Addr::unchecked("asset"),
+            },
+        },
+    )
+    .unwrap();
+    let pool_info: PoolInfoResponse = from_binary(&data).unwrap();
+    assert_eq!(
+        pool_info.staking_token,
+        AssetInfo::NativeToken {
+            denom: lp_denom.to_string(),
+        }
+    );
+    assert_eq!(pool_info.total_bond_amount, Uint128::from(100u128));
+
+    // unbond returns the native staking token
+    let msg = ExecuteMsg::Unbond {
+        asset_info: AssetInfo::Token {
+            contract_addr: Addr::unchecked("asset"),
+        },
+        amount: Uint128::from(100u128),
+    };
+    let info = mock_info("addr", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "addr".to_string(),
+            amount: vec![coin(100u128, lp_denom)],
+        }))]
+    );
+}
+
+#[test]
+fn test_bond_with_max_total_bond_and_whitelist() {
+    let mut deps = mock_dependencies();
+    mock_token_balance(&mut deps.querier, Uint128::from(10000000000u128));
+
+    let msg = InstantiateMsg {
+        owner: Some(Addr::unchecked("owner")),
+        rewarder: Addr::unchecked("rewarder"),
+        minter: Some(Addr::unchecked("mint")),
+        oracle_addr: Addr::unchecked("oracle"),
+        factory_addr: Addr::unchecked("factory"),
+        base_denom: None,
+    };
+    let _res = instantiate(deps.as_mut(), mock_env(), mock_info("addr", &[]), msg).unwrap();
+
+    let asset_info = AssetInfo::Token {
+        contract_addr: Addr::unchecked("asset"),
+    };
+    let msg = ExecuteMsg::RegisterAsset {
+        asset_info: asset_info.clone(),
+        staking_token: AssetInfo::Token {
+            contract_addr: Addr::unchecked("staking"),
+        },
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    // only owner can guard the pool
+    let msg = ExecuteMsg::UpdateMaxTotalBond {
+        asset_info: asset_info.clone(),
+        max_total_bond: Some(Uint128::from(150u128)),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::UpdateStakerWhitelist {
+        asset_info: asset_info.clone(),
+        staker_whitelist: Some(vec![Addr::unchecked("addr"), Addr::unchecked("addr2")]),
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    let res: PoolInfoResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::PoolInfo {
+                asset_info: asset_info.clone(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.max_total_bond, Some(Uint128::from(150u128)));
+    assert_eq!(
+        res.staker_whitelist,
+        Some(vec![Addr::unchecked("addr"), Addr::unchecked("addr2")])
+    );
+
+    let bond_msg = |sender: &str, amount: u128| {
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: sender.to_string(),
+            amount: Uint128::from(amount),
+            msg: to_binary(&Cw20HookMsg::Bond {
+                asset_info: asset_info.clone(),
+            })
+            .unwrap(),
+        })
+    };
+
+    // stakers not in the whitelist can not bond
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("staking", &[]),
+        bond_msg("addr3", 100u128),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("staker is not whitelisted"));
+
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("staking", &[]),
+        bond_msg("addr", 100u128),
+    )
+    .unwrap();
+
+    // the total bond can not exceed the cap
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("staking", &[]),
+        bond_msg("addr2", 100u128),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("total bond amount exceeds the max total bond 150")
+    );
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("staking", &[]),
+        bond_msg("addr2", 50u128),
+    )
+    .unwrap();
+
+    // lifting the limits opens the pool to everyone
+    let msg = ExecuteMsg::UpdateMaxTotalBond {
+        asset_info: asset_info.clone(),
+        max_total_bond: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+    let msg = ExecuteMsg::UpdateStakerWhitelist {
+        asset_info: asset_info.clone(),
+        staker_whitelist: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("staking", &[]),
+        bond_msg("addr3", 100u128),
+    )
+    .unwrap();
+
+    let res: PoolInfoResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::PoolInfo { asset_info }).unwrap())
+            .unwrap();
+    assert_eq!(res.total_bond_amount, Uint128::from(250u128));
+    assert_eq!(res.max_total_bond, None);
+    assert_eq!(res.staker_whitelist, None);
+}
+
 #[test]
 fn test_unbond() {
     let mut deps = mock_dependencies_with_balance(&[
         coin(10000000000u128, ORAI_DENOM),
         coin(20000000000u128, ATOM_DENOM),
     ]);
+    mock_token_balance(&mut deps.querier, Uint128::from(10000000000u128));
 
     let msg = InstantiateMsg {
         owner: Some(Addr::unchecked("owner")),
@@ -217,7 +546,9 @@ fn test_unbond() {
         asset_info: AssetInfo::Token {
             contract_addr: Addr::unchecked("asset"),
         },
-        staking_token: Addr::unchecked("staking"),
+        staking_token: AssetInfo::Token {
+            contract_addr: Addr::unchecked("staking"),
+        },
     };
 
     let info = mock_info("owner", &[]);
@@ -338,12 +669,17 @@ fn test_unbond() {
             asset_info: AssetInfo::Token {
                 contract_addr: Addr::unchecked("asset")
             },
-            staking_token: Addr::unchecked("staking"),
+            staking_token: AssetInfo::Token {
+                contract_addr: Addr::unchecked("staking"),
+            },
             total_bond_amount: Uint128::zero(),
             reward_index: Decimal::from_ratio(300u128, 100u128),
             pending_reward: Uint128::zero(),
             migration_deprecated_staking_token: None,
             migration_index_snapshot: None,
+            max_pending_reward: None,
+            max_total_bond: None,
+            staker_whitelist: None,
         }
     );
 
@@ -446,6 +782,7 @@ fn test_auto_stake() {
         ],
         slippage_tolerance: None,
         receiver: None,
+        auto_stake: None,
     };
 
     let _res = app
@@ -492,7 +829,9 @@ fn test_auto_stake() {
         asset_info: AssetInfo::Token {
             contract_addr: asset_addr.clone(),
         },
-        staking_token: pair_info.liquidity_token.clone(),
+        staking_token: AssetInfo::Token {
+            contract_addr: pair_info.liquidity_token.clone(),
+        },
     };
 
     let _res = app
@@ -647,12 +986,445 @@ fn test_auto_stake() {
             asset_info: AssetInfo::Token {
                 contract_addr: asset_addr.clone()
             },
-            staking_token: pair_info.liquidity_token.clone(),
+            staking_token: AssetInfo::Token {
+                contract_addr: pair_info.liquidity_token.clone(),
+            },
             total_bond_amount: Uint128::from(2u128),
             reward_index: Decimal::zero(),
             pending_reward: Uint128::zero(),
             migration_deprecated_staking_token: None,
             migration_index_snapshot: None,
+            max_pending_reward: None,
+            max_total_bond: None,
+            staker_whitelist: None,
         }
     );
+
+    // the overview joins the pool info with the reserves of the pair, valued in orai by the oracle
+    let pool: oraiswap::pair::PoolResponse = app
+        .query(pair_addr.clone(), &oraiswap::pair::QueryMsg::Pool {})
+        .unwrap();
+    let overview: PoolOverviewResponse = app
+        .query(
+            staking_addr.clone(),
+            &QueryMsg::PoolOverview {
+                asset_info: AssetInfo::Token {
+                    contract_addr: asset_addr.clone(),
+                },
+                usd_denom: Some(ORAI_DENOM.to_string()),
+            },
+        )
+        .unwrap();
+    assert_eq!(overview.pool_info, pool_info);
+    assert_eq!(overview.pair_addr, Some(pair_addr));
+    assert_eq!(overview.reserves, pool.assets.to_vec());
+    assert_eq!(overview.lp_supply, pool.total_share);
+    assert_eq!(
+        overview.bonded_reserves,
+        pool.assets
+            .iter()
+            .map(|reserve| Asset {
+                info: reserve.info.clone(),
+                amount: reserve.amount.multiply_ratio(2u128, pool.total_share),
+            })
+            .collect::<Vec<Asset>>()
+    );
+    let orai_reserve = pool
+        .assets
+        .iter()
+        .find(|reserve| reserve.info.is_native_token())
+        .unwrap()
+        .amount;
+    assert_eq!(
+        overview.total_value,
+        Some(orai_reserve * Uint128::from(2u128))
+    );
+    assert_eq!(
+        overview.bonded_value,
+        Some((orai_reserve * Uint128::from(2u128)).multiply_ratio(2u128, pool.total_share))
+    );
+
+    // without a usd denom the pool is not valued
+    let overview: PoolOverviewResponse = app
+        .query(
+            staking_addr.clone(),
+            &QueryMsg::PoolOverview {
+                asset_info: AssetInfo::Token {
+                    contract_addr: asset_addr,
+                },
+                usd_denom: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(overview.total_value, None);
+}
+
+#[test]
+fn test_voting_power_at() {
+    let mut deps = mock_dependencies();
+    mock_token_balance(&mut deps.querier, Uint128::from(10000000000u128));
+
+    let msg = InstantiateMsg {
+        owner: Some(Addr::unchecked("owner")),
+        rewarder: Addr::unchecked("rewarder"),
+        minter: Some(Addr::unchecked("mint")),
+        oracle_addr: Addr::unchecked("oracle"),
+        factory_addr: Addr::unchecked("factory"),
+        base_denom: None,
+    };
+
+    let info = mock_info("addr", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    for (asset, staking_token) in [("asset", "staking"), ("asset2", "staking2")] {
+        let msg = ExecuteMsg::RegisterAsset {
+            asset_info: AssetInfo::Token {
+                contract_addr: Addr::unchecked(asset),
+            },
+            staking_token: AssetInfo::Token {
+                contract_addr: Addr::unchecked(staking_token),
+            },
+        };
+        let info = mock_info("owner", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    }
+
+    // bond 100 tokens at height 100 and 50 tokens to the other pool at height 110
+    for (height, asset, staking_token, amount) in [
+        (100u64, "asset", "staking", 100u128),
+        (110u64, "asset2", "staking2", 50u128),
+    ] {
+        let mut env = mock_env();
+        env.block.height = height;
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: "addr".to_string(),
+            amount: Uint128::from(amount),
+            msg: to_binary(&Cw20HookMsg::Bond {
+                asset_info: AssetInfo::Token {
+                    contract_addr: Addr::unchecked(asset),
+                },
+            })
+            .unwrap(),
+        });
+        let info = mock_info(staking_token, &[]);
+        let _res = execute(deps.as_mut(), env, info, msg).unwrap();
+    }
+
+    // unbond 30 tokens at height 120
+    let mut env = mock_env();
+    env.block.height = 120;
+    let msg = ExecuteMsg::Unbond {
+        asset_info: AssetInfo::Token {
+            contract_addr: Addr::unchecked("asset"),
+        },
+        amount: Uint128::from(30u128),
+    };
+    let info = mock_info("addr", &[]);
+    let _res = execute(deps.as_mut(), env, info, msg).unwrap();
+
+    for (height, voting_power) in [
+        (99u64, 0u128),
+        (100u64, 100u128),
+        (109u64, 100u128),
+        (110u64, 150u128),
+        (120u64, 120u128),
+        (1000u64, 120u128),
+    ] {
+        let res: VotingPowerResponse = from_binary(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::VotingPowerAt {
+                    address: Addr::unchecked("addr"),
+                    height,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            VotingPowerResponse {
+                address: Addr::unchecked("addr"),
+                height,
+                voting_power: Uint128::from(voting_power),
+            }
+        );
+    }
+}
+
+#[test]
+fn test_staking_hooks() {
+    let mut deps = mock_dependencies_with_balance(&[coin(10000000000u128, ORAI_DENOM)]);
+    mock_token_balance(&mut deps.querier, Uint128::from(10000000000u128));
+
+    let msg = InstantiateMsg {
+        owner: Some(Addr::unchecked("owner")),
+        rewarder: Addr::unchecked("rewarder"),
+        minter: Some(Addr::unchecked("mint")),
+        oracle_addr: Addr::unchecked("oracle"),
+        factory_addr: Addr::unchecked("factory"),
+        base_denom: None,
+    };
+    let info = mock_info("addr", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let asset_info = AssetInfo::Token {
+        contract_addr: Addr::unchecked("asset"),
+    };
+    let msg = ExecuteMsg::RegisterAsset {
+        asset_info: asset_info.clone(),
+        staking_token: AssetInfo::Token {
+            contract_addr: Addr::unchecked("staking"),
+        },
+    };
+    let info = mock_info("owner", &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let msg = ExecuteMsg::UpdateRewardsPerSec {
+        asset_info: asset_info.clone(),
+        assets: vec![Asset {
+            info: AssetInfo::NativeToken {
+                denom: ORAI_DENOM.to_string(),
+            },
+            amount: 100u128.into(),
+        }],
+    };
+    let info = mock_info("owner", &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // only owner can register hooks
+    let msg = ExecuteMsg::AddHook {
+        contract_addr: Addr::unchecked("hook"),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        msg.clone(),
+    )
+    .unwrap();
+    let err = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap_err();
+    assert_eq!(err, StdError::generic_err("Hook was already registered"));
+
+    let data = query(deps.as_ref(), mock_env(), QueryMsg::Hooks {}).unwrap();
+    let res: HooksResponse = from_binary(&data).unwrap();
+    assert_eq!(res.hooks, vec![Addr::unchecked("hook")]);
+
+    // bond 100 tokens
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr".to_string(),
+        amount: Uint128::from(100u128),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            asset_info: asset_info.clone(),
+        })
+        .unwrap(),
+    });
+    let info = mock_info("staking", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(
+            StakingHookMsg::Bonded {
+                staker_addr: Addr::unchecked("addr"),
+                asset_info: asset_info.clone(),
+                amount: Uint128::from(100u128),
+            }
+            .into_cosmos_msg("hook")
+            .unwrap()
+        )]
+    );
+
+    // claim the deposited rewards
+    let msg = ExecuteMsg::DepositReward {
+        rewards: vec![Asset {
+            info: asset_info.clone(),
+            amount: Uint128::from(100u128),
+        }],
+    };
+    let info = mock_info("rewarder", &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let msg = ExecuteMsg::Withdraw {
+        asset_info: Some(asset_info.clone()),
+        auto_bond: None,
+    };
+    let info = mock_info("addr", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "addr".to_string(),
+                amount: vec![coin(100u128, ORAI_DENOM)],
+            })),
+            SubMsg::new(
+                StakingHookMsg::Claimed {
+                    staker_addr: Addr::unchecked("addr"),
+                    rewards: vec![Asset {
+                        info: AssetInfo::NativeToken {
+                            denom: ORAI_DENOM.to_string(),
+                        },
+                        amount: Uint128::from(100u128),
+                    }],
+                }
+                .into_cosmos_msg("hook")
+                .unwrap()
+            ),
+        ]
+    );
+
+    // unbond notifies the hook after returning the staking tokens
+    let msg = ExecuteMsg::Unbond {
+        asset_info: asset_info.clone(),
+        amount: Uint128::from(100u128),
+    };
+    let info = mock_info("addr", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(res.messages.len(), 2);
+    assert_eq!(
+        res.messages[1],
+        SubMsg::new(
+            StakingHookMsg::Unbonded {
+                staker_addr: Addr::unchecked("addr"),
+                asset_info: asset_info.clone(),
+                amount: Uint128::from(100u128),
+            }
+            .into_cosmos_msg("hook")
+            .unwrap()
+        )
+    );
+
+    // removed hooks are no longer notified
+    let msg = ExecuteMsg::RemoveHook {
+        contract_addr: Addr::unchecked("hook"),
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr".to_string(),
+        amount: Uint128::from(100u128),
+        msg: to_binary(&Cw20HookMsg::Bond { asset_info }).unwrap(),
+    });
+    let info = mock_info("staking", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert!(res.messages.is_empty());
+}
+
+#[test]
+fn test_bond_verification_and_sweep() {
+    let mut deps = mock_dependencies();
+
+    let msg = InstantiateMsg {
+        owner: Some(Addr::unchecked("owner")),
+        rewarder: Addr::unchecked("rewarder"),
+        minter: Some(Addr::unchecked("mint")),
+        oracle_addr: Addr::unchecked("oracle"),
+        factory_addr: Addr::unchecked("factory"),
+        base_denom: None,
+    };
+    let _res = instantiate(deps.as_mut(), mock_env(), mock_info("addr", &[]), msg).unwrap();
+
+    let asset_info = AssetInfo::Token {
+        contract_addr: Addr::unchecked("asset"),
+    };
+    let msg = ExecuteMsg::RegisterAsset {
+        asset_info: asset_info.clone(),
+        staking_token: AssetInfo::Token {
+            contract_addr: Addr::unchecked("staking"),
+        },
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::UpdateRewardsPerSec {
+        asset_info: asset_info.clone(),
+        assets: vec![Asset {
+            info: AssetInfo::NativeToken {
+                denom: ORAI_DENOM.to_string(),
+            },
+            amount: Uint128::from(100u128),
+        }],
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    let bond_msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr".to_string(),
+        amount: Uint128::from(100u128),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            asset_info: asset_info.clone(),
+        })
+        .unwrap(),
+    });
+
+    // the contract received the 100 tokens of the first bond
+    mock_token_balance(&mut deps.querier, Uint128::from(100u128));
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("staking", &[]),
+        bond_msg.clone(),
+    )
+    .unwrap();
+
+    // a hook without the tokens transferred can not inflate the bonds
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("staking", &[]),
+        bond_msg,
+    )
+    .unwrap_err();
+    assert_eq!(
+        res,
+        StdError::generic_err("bond amount is not received by the contract")
+    );
+
+    // 50 tokens are transferred to the contract by mistake
+    mock_token_balance(&mut deps.querier, Uint128::from(150u128));
+    let msg = ExecuteMsg::SweepUnaccountedTokens {
+        asset_info: AssetInfo::Token {
+            contract_addr: Addr::unchecked("staking"),
+        },
+        recipient: Addr::unchecked("addr"),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(res, StdError::generic_err("unauthorized"));
+
+    let res = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(WasmMsg::Execute {
+            contract_addr: "staking".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "addr".to_string(),
+                amount: Uint128::from(50u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        })]
+    );
+
+    // rewards are owed to the stakers
+    let msg = ExecuteMsg::SweepUnaccountedTokens {
+        asset_info: AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        recipient: Addr::unchecked("addr"),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap_err();
+    assert_eq!(res, StdError::generic_err("can not sweep a reward token"));
 }
@@ -1,15 +1,22 @@
 use cosmwasm_std::entry_point;
-use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+use cosmwasm_std::{
+    to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult,
+};
 
-use cw20::Cw20ExecuteMsg;
+use cw20::{BalanceResponse, Cw20ExecuteMsg};
+use cw20_base::state::{BALANCES, TOKEN_INFO};
 use cw20_base::ContractError;
 use cw20_base::{
     contract::{
         execute as cw20_execute, instantiate as cw20_instantiate, migrate as cw20_migrate,
         query as cw20_query,
     },
-    msg::{InstantiateMsg, MigrateMsg, QueryMsg},
+    msg::{InstantiateMsg, MigrateMsg as Cw20MigrateMsg, QueryMsg as Cw20QueryMsg},
 };
+use cw_storage_plus::Bound;
+
+use crate::msg::{MigrateMsg, QueryMsg, TotalSupplyAtResponse};
+use crate::state::{BALANCE_SNAPSHOTS, SNAPSHOT_INTERVAL, TOTAL_SUPPLY_SNAPSHOTS};
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -23,22 +30,179 @@ pub fn instantiate(
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     msg: Cw20ExecuteMsg,
 ) -> Result<Response, ContractError> {
+    let interval = SNAPSHOT_INTERVAL
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    if interval > 0 {
+        let start = env.block.height - env.block.height % interval;
+        snapshot(deps.branch(), &info.sender, &msg, start)?;
+    }
+
     cw20_execute(deps, env, info, msg)
 }
 
+/// store the balances and total supply the message is going to change, if they were not stored yet for the interval
+fn snapshot(deps: DepsMut, sender: &Addr, msg: &Cw20ExecuteMsg, start: u64) -> StdResult<()> {
+    let (addresses, supply_changed) = match msg {
+        Cw20ExecuteMsg::Transfer { recipient, .. } => {
+            (vec![sender.to_string(), recipient.clone()], false)
+        }
+        Cw20ExecuteMsg::Send { contract, .. } => {
+            (vec![sender.to_string(), contract.clone()], false)
+        }
+        Cw20ExecuteMsg::Burn { .. } => (vec![sender.to_string()], true),
+        Cw20ExecuteMsg::Mint { recipient, .. } => (vec![recipient.clone()], true),
+        Cw20ExecuteMsg::TransferFrom {
+            owner, recipient, ..
+        } => (vec![owner.clone(), recipient.clone()], false),
+        Cw20ExecuteMsg::SendFrom {
+            owner, contract, ..
+        } => (vec![owner.clone(), contract.clone()], false),
+        Cw20ExecuteMsg::BurnFrom { owner, .. } => (vec![owner.clone()], true),
+        _ => (vec![], false),
+    };
+
+    for address in addresses {
+        // invalid addresses are rejected by the cw20 handler right after
+        let address = match deps.api.addr_validate(&address) {
+            Ok(address) => address,
+            Err(_) => continue,
+        };
+        if !BALANCE_SNAPSHOTS.has(deps.storage, (&address, start)) {
+            let balance = BALANCES
+                .may_load(deps.storage, &address)?
+                .unwrap_or_default();
+            BALANCE_SNAPSHOTS.save(deps.storage, (&address, start), &balance)?;
+        }
+    }
+
+    if supply_changed && !TOTAL_SUPPLY_SNAPSHOTS.has(deps.storage, start) {
+        let total_supply = TOKEN_INFO.load(deps.storage)?.total_supply;
+        TOTAL_SUPPLY_SNAPSHOTS.save(deps.storage, start, &total_supply)?;
+    }
+
+    Ok(())
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    cw20_query(deps, env, msg)
+    match msg {
+        QueryMsg::BalanceAt { address, height } => {
+            to_binary(&query_balance_at(deps, address, height)?)
+        }
+        QueryMsg::TotalSupplyAt { height } => to_binary(&query_total_supply_at(deps, height)?),
+        QueryMsg::Balance { address } => cw20_query(deps, env, Cw20QueryMsg::Balance { address }),
+        QueryMsg::TokenInfo {} => cw20_query(deps, env, Cw20QueryMsg::TokenInfo {}),
+        QueryMsg::Minter {} => cw20_query(deps, env, Cw20QueryMsg::Minter {}),
+        QueryMsg::Allowance { owner, spender } => {
+            cw20_query(deps, env, Cw20QueryMsg::Allowance { owner, spender })
+        }
+        QueryMsg::AllAllowances {
+            owner,
+            start_after,
+            limit,
+        } => cw20_query(
+            deps,
+            env,
+            Cw20QueryMsg::AllAllowances {
+                owner,
+                start_after,
+                limit,
+            },
+        ),
+        QueryMsg::AllSpenderAllowances {
+            spender,
+            start_after,
+            limit,
+        } => cw20_query(
+            deps,
+            env,
+            Cw20QueryMsg::AllSpenderAllowances {
+                spender,
+                start_after,
+                limit,
+            },
+        ),
+        QueryMsg::AllAccounts { start_after, limit } => {
+            cw20_query(deps, env, Cw20QueryMsg::AllAccounts { start_after, limit })
+        }
+        QueryMsg::MarketingInfo {} => cw20_query(deps, env, Cw20QueryMsg::MarketingInfo {}),
+        QueryMsg::DownloadLogo {} => cw20_query(deps, env, Cw20QueryMsg::DownloadLogo {}),
+    }
+}
+
+/// start height of the snapshot interval containing the height
+fn interval_start(deps: Deps, height: u64) -> StdResult<Option<u64>> {
+    let interval = SNAPSHOT_INTERVAL
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    Ok((interval > 0).then(|| height - height % interval))
+}
+
+pub fn query_balance_at(deps: Deps, address: String, height: u64) -> StdResult<BalanceResponse> {
+    let address = deps.api.addr_validate(&address)?;
+
+    // the first snapshot taken from the interval on holds the balance before any later change
+    let snapshot = match interval_start(deps, height)? {
+        Some(start) => BALANCE_SNAPSHOTS
+            .prefix(&address)
+            .range(
+                deps.storage,
+                Some(Bound::inclusive(start)),
+                None,
+                Order::Ascending,
+            )
+            .next()
+            .transpose()?
+            .map(|(_, balance)| balance),
+        None => None,
+    };
+
+    let balance = match snapshot {
+        Some(balance) => balance,
+        None => BALANCES
+            .may_load(deps.storage, &address)?
+            .unwrap_or_default(),
+    };
+
+    Ok(BalanceResponse { balance })
+}
+
+pub fn query_total_supply_at(deps: Deps, height: u64) -> StdResult<TotalSupplyAtResponse> {
+    let snapshot = match interval_start(deps, height)? {
+        Some(start) => TOTAL_SUPPLY_SNAPSHOTS
+            .range(
+                deps.storage,
+                Some(Bound::inclusive(start)),
+                None,
+                Order::Ascending,
+            )
+            .next()
+            .transpose()?
+            .map(|(_, total_supply)| total_supply),
+        None => None,
+    };
+
+    let total_supply = match snapshot {
+        Some(total_supply) => total_supply,
+        None => TOKEN_INFO.load(deps.storage)?.total_supply,
+    };
+
+    Ok(TotalSupplyAtResponse { total_supply })
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
-    cw20_migrate(deps, env, msg)
+    if let Some(snapshot_interval) = msg.snapshot_interval {
+        SNAPSHOT_INTERVAL.save(deps.storage, &snapshot_interval)?;
+    }
+
+    cw20_migrate(deps, env, Cw20MigrateMsg {})
 }
 
 #[test]
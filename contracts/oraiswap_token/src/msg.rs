@@ -0,0 +1,54 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::Uint128;
+
+#[cw_serde]
+pub struct MigrateMsg {
+    /// take balance snapshots every snapshot_interval blocks, 0 disables them
+    pub snapshot_interval: Option<u64>,
+}
+
+/// The cw20-base queries extended with the snapshot queries
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(cw20::BalanceResponse)]
+    Balance { address: String },
+    #[returns(cw20::TokenInfoResponse)]
+    TokenInfo {},
+    #[returns(cw20::MinterResponse)]
+    Minter {},
+    #[returns(cw20::AllowanceResponse)]
+    Allowance { owner: String, spender: String },
+    #[returns(cw20::AllAllowancesResponse)]
+    AllAllowances {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(cw20::AllSpenderAllowancesResponse)]
+    AllSpenderAllowances {
+        spender: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(cw20::AllAccountsResponse)]
+    AllAccounts {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(cw20::MarketingInfoResponse)]
+    MarketingInfo {},
+    #[returns(cw20::DownloadLogoResponse)]
+    DownloadLogo {},
+    /// Balance of the address at the start of the snapshot interval containing the height
+    #[returns(cw20::BalanceResponse)]
+    BalanceAt { address: String, height: u64 },
+    /// Total supply at the start of the snapshot interval containing the height
+    #[returns(TotalSupplyAtResponse)]
+    TotalSupplyAt { height: u64 },
+}
+
+#[cw_serde]
+pub struct TotalSupplyAtResponse {
+    pub total_supply: Uint128,
+}
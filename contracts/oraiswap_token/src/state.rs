@@ -0,0 +1,11 @@
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::{Item, Map};
+
+// number of blocks between balance snapshots, snapshots are disabled when not set or 0
+pub const SNAPSHOT_INTERVAL: Item<u64> = Item::new("snapshot_interval");
+
+// balance before its first change in an interval, by address and start height of the interval
+pub const BALANCE_SNAPSHOTS: Map<(&Addr, u64), Uint128> = Map::new("balance_snapshot");
+
+// total supply before the first mint or burn in an interval, by start height of the interval
+pub const TOTAL_SUPPLY_SNAPSHOTS: Map<u64, Uint128> = Map::new("total_supply_snapshot");
@@ -0,0 +1,164 @@
+use crate::contract::{execute, instantiate, migrate, query_balance_at, query_total_supply_at};
+use crate::msg::MigrateMsg;
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{DepsMut, Env, Uint128};
+use cw20::{Cw20Coin, Cw20ExecuteMsg, MinterResponse};
+use cw20_base::contract::{query_balance, query_token_info};
+use cw20_base::msg::InstantiateMsg;
+
+fn do_instantiate(deps: DepsMut) {
+    let msg = InstantiateMsg {
+        name: "oraiswap token".to_string(),
+        symbol: "ORAIX".to_string(),
+        decimals: 6,
+        initial_balances: vec![Cw20Coin {
+            address: "addr0000".to_string(),
+            amount: Uint128::from(1000u128),
+        }],
+        mint: Some(MinterResponse {
+            minter: "minter".to_string(),
+            cap: None,
+        }),
+        marketing: None,
+    };
+    instantiate(deps, mock_env(), mock_info("creator", &[]), msg).unwrap();
+}
+
+fn env_at(height: u64) -> Env {
+    let mut env = mock_env();
+    env.block.height = height;
+    env
+}
+
+#[test]
+fn burn_from() {
+    let mut deps = mock_dependencies();
+    do_instantiate(deps.as_mut());
+
+    let msg = Cw20ExecuteMsg::BurnFrom {
+        owner: "addr0000".to_string(),
+        amount: Uint128::from(100u128),
+    };
+
+    // no allowance
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("spender", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        Cw20ExecuteMsg::IncreaseAllowance {
+            spender: "spender".to_string(),
+            amount: Uint128::from(100u128),
+            expires: None,
+        },
+    )
+    .unwrap();
+    execute(deps.as_mut(), mock_env(), mock_info("spender", &[]), msg).unwrap();
+
+    assert_eq!(
+        query_balance(deps.as_ref(), "addr0000".to_string())
+            .unwrap()
+            .balance,
+        Uint128::from(900u128)
+    );
+    assert_eq!(
+        query_token_info(deps.as_ref()).unwrap().total_supply,
+        Uint128::from(900u128)
+    );
+}
+
+#[test]
+fn snapshots() {
+    let mut deps = mock_dependencies();
+    do_instantiate(deps.as_mut());
+
+    // snapshots are disabled until an interval is set, the current values are returned
+    execute(
+        deps.as_mut(),
+        env_at(95),
+        mock_info("addr0000", &[]),
+        Cw20ExecuteMsg::Transfer {
+            recipient: "addr0001".to_string(),
+            amount: Uint128::from(100u128),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        query_balance_at(deps.as_ref(), "addr0000".to_string(), 90)
+            .unwrap()
+            .balance,
+        Uint128::from(900u128)
+    );
+
+    migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg {
+            snapshot_interval: Some(10),
+        },
+    )
+    .unwrap();
+
+    // interval [100, 110): transfer then mint
+    execute(
+        deps.as_mut(),
+        env_at(103),
+        mock_info("addr0000", &[]),
+        Cw20ExecuteMsg::Transfer {
+            recipient: "addr0001".to_string(),
+            amount: Uint128::from(100u128),
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        env_at(105),
+        mock_info("minter", &[]),
+        Cw20ExecuteMsg::Mint {
+            recipient: "addr0000".to_string(),
+            amount: Uint128::from(500u128),
+        },
+    )
+    .unwrap();
+
+    // interval [120, 130): burn
+    execute(
+        deps.as_mut(),
+        env_at(121),
+        mock_info("addr0000", &[]),
+        Cw20ExecuteMsg::Burn {
+            amount: Uint128::from(300u128),
+        },
+    )
+    .unwrap();
+
+    let balance_at = |height: u64, address: &str| {
+        query_balance_at(deps.as_ref(), address.to_string(), height)
+            .unwrap()
+            .balance
+    };
+    assert_eq!(balance_at(109, "addr0000"), Uint128::from(900u128));
+    assert_eq!(balance_at(109, "addr0001"), Uint128::from(100u128));
+    // no change in [110, 120), the balance at its start is the one before the burn
+    assert_eq!(balance_at(115, "addr0000"), Uint128::from(1300u128));
+    assert_eq!(balance_at(115, "addr0001"), Uint128::from(200u128));
+    assert_eq!(balance_at(125, "addr0000"), Uint128::from(1300u128));
+    // no later snapshot, current balance
+    assert_eq!(balance_at(135, "addr0000"), Uint128::from(1000u128));
+
+    let total_supply_at = |height: u64| {
+        query_total_supply_at(deps.as_ref(), height)
+            .unwrap()
+            .total_supply
+    };
+    assert_eq!(total_supply_at(100), Uint128::from(1000u128));
+    assert_eq!(total_supply_at(110), Uint128::from(1500u128));
+    assert_eq!(total_supply_at(130), Uint128::from(1200u128));
+}
@@ -0,0 +1,587 @@
+use crate::state::PAIR_INFO;
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, Binary, CanonicalAddr, CosmosMsg, Decimal, Decimal256, Deps,
+    DepsMut, Env, MessageInfo, Reply, Response, StdError, StdResult, SubMsg, Uint128, Uint256,
+    WasmMsg,
+};
+
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg, MinterResponse};
+use cw20_base::msg::InstantiateMsg as TokenInstantiateMsg;
+use cw_utils::parse_reply_instantiate_data;
+use oraiswap::asset::{Asset, AssetInfo, AssetInfoRaw};
+use oraiswap::error::ContractError;
+use oraiswap::oracle::OracleContract;
+use oraiswap::pair::{
+    assert_max_spread, SimulationResponse, DEFAULT_COMMISSION_RATE, DEFAULT_LP_TOKEN_NAME,
+    DEFAULT_LP_TOKEN_SYMBOL,
+};
+use oraiswap::querier::query_supply;
+use oraiswap::weighted_pair::{
+    compute_weighted_swap, validate_weighted_asset_infos, Cw20HookMsg, ExecuteMsg, InstantiateMsg,
+    MigrateMsg, QueryMsg, WeightedAssetInfoRaw, WeightedPairInfoRaw, WeightedPairResponse,
+    WeightedPoolResponse,
+};
+use std::str::FromStr;
+
+const INSTANTIATE_REPLY_ID: u64 = 1;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    validate_weighted_asset_infos(&msg.asset_infos)?;
+
+    let commission_rate = msg
+        .commission_rate
+        .unwrap_or(DEFAULT_COMMISSION_RATE.to_string());
+    if Decimal::from_str(&commission_rate)? >= Decimal::one() {
+        return Err(StdError::generic_err("commission rate must be less than 1"));
+    }
+
+    let pair_info = &WeightedPairInfoRaw {
+        oracle_addr: deps.api.addr_canonicalize(msg.oracle_addr.as_str())?,
+        contract_addr: deps.api.addr_canonicalize(env.contract.address.as_str())?,
+        // set by the reply of the liquidity token instantiation
+        liquidity_token: CanonicalAddr::from(vec![]),
+        asset_infos: msg
+            .asset_infos
+            .iter()
+            .map(|asset_info| {
+                Ok(WeightedAssetInfoRaw {
+                    info: asset_info.info.to_raw(deps.api)?,
+                    weight: asset_info.weight,
+                })
+            })
+            .collect::<StdResult<_>>()?,
+        commission_rate,
+    };
+
+    PAIR_INFO.save(deps.storage, pair_info)?;
+
+    let lp_token_info = msg.lp_token_info.unwrap_or_default();
+
+    Ok(Response::new().add_submessage(SubMsg::reply_on_success(
+        WasmMsg::Instantiate {
+            admin: None,
+            code_id: msg.token_code_id,
+            msg: to_binary(&TokenInstantiateMsg {
+                name: lp_token_info
+                    .name
+                    .unwrap_or(DEFAULT_LP_TOKEN_NAME.to_string()),
+                symbol: lp_token_info
+                    .symbol
+                    .unwrap_or(DEFAULT_LP_TOKEN_SYMBOL.to_string()),
+                decimals: 6,
+                initial_balances: vec![],
+                mint: Some(MinterResponse {
+                    minter: env.contract.address.to_string(),
+                    cap: None,
+                }),
+                marketing: lp_token_info.marketing,
+            })?,
+            funds: vec![],
+            label: "lp".to_string(),
+        },
+        INSTANTIATE_REPLY_ID,
+    )))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::ProvideLiquidity { assets, receiver } => {
+            provide_liquidity(deps, env, info, assets, receiver)
+        }
+        // tokens are swapped through Receive
+        ExecuteMsg::Swap {
+            offer_asset,
+            ask_asset_info,
+            belief_price,
+            max_spread,
+            to,
+        } => {
+            if !offer_asset.is_native_token() {
+                return Err(ContractError::Unauthorized {});
+            }
+
+            swap(
+                deps,
+                env,
+                info.clone(),
+                info.sender,
+                offer_asset,
+                ask_asset_info,
+                belief_price,
+                max_spread,
+                to,
+            )
+        }
+    }
+}
+
+pub fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let contract_addr = info.sender.clone();
+
+    match from_binary(&cw20_msg.msg) {
+        Ok(Cw20HookMsg::Swap {
+            ask_asset_info,
+            belief_price,
+            max_spread,
+            to,
+        }) => {
+            // only asset contract can execute this message
+            let pair_info: WeightedPairInfoRaw = PAIR_INFO.load(deps.storage)?;
+            let sender_info = AssetInfoRaw::Token {
+                contract_addr: deps.api.addr_canonicalize(info.sender.as_str())?,
+            };
+            if !pair_info
+                .asset_infos
+                .iter()
+                .any(|asset_info| asset_info.info.eq(&sender_info))
+            {
+                return Err(ContractError::Unauthorized {});
+            }
+
+            let to_addr = if let Some(to_addr) = to {
+                Some(deps.api.addr_validate(to_addr.as_str())?)
+            } else {
+                None
+            };
+
+            swap(
+                deps,
+                env,
+                info,
+                Addr::unchecked(cw20_msg.sender),
+                Asset {
+                    info: AssetInfo::Token { contract_addr },
+                    amount: cw20_msg.amount,
+                },
+                ask_asset_info,
+                belief_price,
+                max_spread,
+                to_addr,
+            )
+        }
+        // remove liquidity
+        Ok(Cw20HookMsg::WithdrawLiquidity {}) => {
+            let pair_info: WeightedPairInfoRaw = PAIR_INFO.load(deps.storage)?;
+            if deps.api.addr_canonicalize(info.sender.as_str())? != pair_info.liquidity_token {
+                return Err(ContractError::Unauthorized {});
+            }
+            let sender_addr = deps.api.addr_validate(cw20_msg.sender.as_str())?;
+            withdraw_liquidity(deps, env, sender_addr, cw20_msg.amount)
+        }
+        Err(err) => Err(ContractError::Std(err)),
+    }
+}
+
+/// This just stores the result for future query
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> StdResult<Response> {
+    if msg.id != INSTANTIATE_REPLY_ID {
+        return Err(StdError::generic_err(format!(
+            "unknown reply id: {}",
+            msg.id
+        )));
+    }
+
+    let res =
+        parse_reply_instantiate_data(msg).map_err(|err| StdError::generic_err(err.to_string()))?;
+    let liquidity_token = &res.contract_address;
+
+    let api = deps.api;
+    PAIR_INFO.update(deps.storage, |mut meta| -> StdResult<_> {
+        // liquidity token can only be set once, by the instantiate submessage of this pair
+        if !meta.liquidity_token.is_empty() {
+            return Err(StdError::generic_err("liquidity token is already set"));
+        }
+        meta.liquidity_token = api.addr_canonicalize(liquidity_token)?;
+        Ok(meta)
+    })?;
+
+    Ok(Response::new().add_attribute("liquidity_token_addr", liquidity_token))
+}
+
+/// CONTRACT - should approve contract to use the amount of token
+/// every pool asset is deposited, only the amounts in proportion of the reserves are used
+pub fn provide_liquidity(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    assets: Vec<Asset>,
+    receiver: Option<Addr>,
+) -> Result<Response, ContractError> {
+    for asset in assets.iter() {
+        asset.assert_sent_native_token_balance(&info)?;
+    }
+
+    let pair_info: WeightedPairInfoRaw = PAIR_INFO.load(deps.storage)?;
+    let mut pools: Vec<Asset> =
+        pair_info.query_pools(&deps.querier, deps.api, env.contract.address.clone())?;
+    if assets.len() != pools.len() {
+        return Err(ContractError::AssetMismatch {});
+    }
+
+    let mut deposits: Vec<Uint128> = vec![];
+    for pool in pools.iter_mut() {
+        let deposit = assets
+            .iter()
+            .find(|asset| asset.info.eq(&pool.info))
+            .map(|asset| asset.amount)
+            .ok_or(ContractError::AssetMismatch {})?;
+
+        // If the asset is native token, balance is already increased
+        // To calculated properly we should subtract user deposit from the pool
+        if pool.info.is_native_token() {
+            pool.amount = pool.amount.checked_sub(deposit)?;
+        }
+        deposits.push(deposit);
+    }
+
+    let liquidity_token = deps.api.addr_humanize(&pair_info.liquidity_token)?;
+    let total_share = query_supply(&deps.querier, liquidity_token.clone())?;
+    let (share, used_deposits) = if total_share.is_zero() {
+        // Initial share = value of the deposits in the first asset, since each asset holds its weight of the value
+        let share = deposits[0].multiply_ratio(
+            Decimal::one().atomics(),
+            pair_info.asset_infos[0].weight.atomics(),
+        );
+        (share, deposits.clone())
+    } else {
+        // the share of the least deposited asset relative to its pool, the other assets are used in this proportion
+        let share = deposits
+            .iter()
+            .zip(pools.iter())
+            .map(|(deposit, pool)| deposit.multiply_ratio(total_share, pool.amount))
+            .min()
+            .unwrap_or_default();
+        let used_deposits = deposits
+            .iter()
+            .zip(pools.iter())
+            .map(|(deposit, pool)| {
+                // rounded up in favor of the pool
+                let used = (Uint256::from(share) * Uint256::from(pool.amount)
+                    + Uint256::from(total_share)
+                    - Uint256::one())
+                    / Uint256::from(total_share);
+                Ok(Uint128::try_from(used)?.min(*deposit))
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+        (share, used_deposits)
+    };
+
+    // prevent providing free token
+    if share.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut used_assets: Vec<String> = vec![];
+    for ((pool, deposit), used) in pools.iter().zip(deposits.iter()).zip(used_deposits.iter()) {
+        match &pool.info {
+            // If the pool is token contract, then we need to execute TransferFrom msg to receive funds
+            AssetInfo::Token { contract_addr } => {
+                if !used.is_zero() {
+                    messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                        contract_addr: contract_addr.to_string(),
+                        msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+                            owner: info.sender.to_string(),
+                            recipient: env.contract.address.to_string(),
+                            amount: *used,
+                        })?,
+                        funds: vec![],
+                    }));
+                }
+            }
+            // refund the native tokens exceeding the proportion
+            AssetInfo::NativeToken { .. } => {
+                let refund_amount = deposit.checked_sub(*used)?;
+                if !refund_amount.is_zero() {
+                    messages.push(
+                        Asset {
+                            info: pool.info.clone(),
+                            amount: refund_amount,
+                        }
+                        .into_msg(
+                            None,
+                            &deps.querier,
+                            info.sender.clone(),
+                        )?,
+                    );
+                }
+            }
+        }
+        used_assets.push(
+            Asset {
+                info: pool.info.clone(),
+                amount: *used,
+            }
+            .to_string(),
+        );
+    }
+
+    // mint LP token to receiver
+    let receiver = receiver.unwrap_or(info.sender.clone());
+    messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: liquidity_token.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Mint {
+            recipient: receiver.to_string(),
+            amount: share,
+        })?,
+        funds: vec![],
+    }));
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "provide_liquidity"),
+        ("sender", info.sender.as_str()),
+        ("receiver", receiver.as_str()),
+        ("assets", &used_assets.join(", ")),
+        ("share", &share.to_string()),
+    ]))
+}
+
+pub fn withdraw_liquidity(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let pair_info: WeightedPairInfoRaw = PAIR_INFO.load(deps.storage)?;
+    let liquidity_addr = deps.api.addr_humanize(&pair_info.liquidity_token)?;
+
+    let pools: Vec<Asset> =
+        pair_info.query_pools(&deps.querier, deps.api, env.contract.address.clone())?;
+    let total_share: Uint128 = query_supply(&deps.querier, liquidity_addr.clone())?;
+
+    let share_ratio = Decimal::from_ratio(amount, total_share);
+    if share_ratio.is_zero() {
+        return Err(ContractError::InvalidZeroRatio {});
+    }
+
+    let refund_assets: Vec<Asset> = pools
+        .iter()
+        .map(|a| Asset {
+            info: a.info.clone(),
+            amount: a.amount * share_ratio,
+        })
+        .collect();
+
+    let oracle_contract = OracleContract(deps.api.addr_humanize(&pair_info.oracle_addr)?);
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    for refund_asset in refund_assets.iter() {
+        if !refund_asset.amount.is_zero() {
+            messages.push(refund_asset.clone().into_msg(
+                Some(&oracle_contract),
+                &deps.querier,
+                sender.clone(),
+            )?);
+        }
+    }
+    // burn liquidity token
+    messages.push(
+        WasmMsg::Execute {
+            contract_addr: liquidity_addr.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Burn { amount })?,
+            funds: vec![],
+        }
+        .into(),
+    );
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "withdraw_liquidity"),
+        ("sender", sender.as_str()),
+        ("withdrawn_share", &amount.to_string()),
+        (
+            "refund_assets",
+            &refund_assets
+                .iter()
+                .map(|asset| asset.to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+        ),
+    ]))
+}
+
+/// CONTRACT - a user must do token approval
+#[allow(clippy::too_many_arguments)]
+pub fn swap(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    sender: Addr,
+    offer_asset: Asset,
+    ask_asset_info: AssetInfo,
+    belief_price: Option<Decimal>,
+    max_spread: Option<Decimal>,
+    to: Option<Addr>,
+) -> Result<Response, ContractError> {
+    offer_asset.assert_sent_native_token_balance(&info)?;
+
+    let pair_info: WeightedPairInfoRaw = PAIR_INFO.load(deps.storage)?;
+    let pools: Vec<Asset> =
+        pair_info.query_pools(&deps.querier, deps.api, env.contract.address.clone())?;
+    let (offer_index, ask_index) = find_swap_pools(&pools, &offer_asset.info, &ask_asset_info)?;
+
+    // the offer asset is already in the contract balance, subtract it from the pool
+    let offer_pool = pools[offer_index].amount.checked_sub(offer_asset.amount)?;
+    let ask_pool = &pools[ask_index];
+
+    let commission_rate = Decimal256::from_str(&pair_info.commission_rate)?;
+    let offer_amount = offer_asset.amount;
+    let (return_amount, spread_amount, commission_amount) = compute_weighted_swap(
+        offer_pool,
+        pair_info.asset_infos[offer_index].weight,
+        ask_pool.amount,
+        pair_info.asset_infos[ask_index].weight,
+        offer_amount,
+        commission_rate,
+    )?;
+
+    // check max spread limit if exist
+    assert_max_spread(
+        belief_price,
+        max_spread,
+        offer_amount,
+        return_amount + commission_amount,
+        spread_amount,
+    )?;
+
+    // compute tax
+    let return_asset = Asset {
+        info: ask_pool.info.clone(),
+        amount: return_amount,
+    };
+
+    let oracle_contract = OracleContract(deps.api.addr_humanize(&pair_info.oracle_addr)?);
+
+    let tax_amount = return_asset.compute_tax(&oracle_contract, &deps.querier)?;
+    let receiver = to.unwrap_or_else(|| sender.clone());
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    if !return_amount.is_zero() {
+        messages.push(return_asset.into_msg(
+            Some(&oracle_contract),
+            &deps.querier,
+            receiver.clone(),
+        )?);
+    }
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "swap"),
+        ("sender", sender.as_str()),
+        ("receiver", receiver.as_str()),
+        ("offer_asset", &offer_asset.info.to_string()),
+        ("ask_asset", &ask_pool.info.to_string()),
+        ("offer_amount", &offer_amount.to_string()),
+        ("return_amount", &return_amount.to_string()),
+        ("tax_amount", &tax_amount.to_string()),
+        ("spread_amount", &spread_amount.to_string()),
+        ("commission_amount", &commission_amount.to_string()),
+    ]))
+}
+
+/// indexes of the offer and ask pools, which must be two different assets of the pool
+fn find_swap_pools(
+    pools: &[Asset],
+    offer_asset_info: &AssetInfo,
+    ask_asset_info: &AssetInfo,
+) -> Result<(usize, usize), ContractError> {
+    let offer_index = pools.iter().position(|pool| pool.info.eq(offer_asset_info));
+    let ask_index = pools.iter().position(|pool| pool.info.eq(ask_asset_info));
+    match (offer_index, ask_index) {
+        (Some(offer_index), Some(ask_index)) if offer_index != ask_index => {
+            Ok((offer_index, ask_index))
+        }
+        _ => Err(ContractError::AssetMismatch {}),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
+    match msg {
+        QueryMsg::Pair {} => Ok(to_binary(&query_pair_info(deps)?)?),
+        QueryMsg::Pool {} => Ok(to_binary(&query_pool(deps)?)?),
+        QueryMsg::Simulation {
+            offer_asset,
+            ask_asset_info,
+        } => Ok(to_binary(&query_simulation(
+            deps,
+            offer_asset,
+            ask_asset_info,
+        )?)?),
+    }
+}
+
+pub fn query_pair_info(deps: Deps) -> StdResult<WeightedPairResponse> {
+    let pair_info: WeightedPairInfoRaw = PAIR_INFO.load(deps.storage)?;
+    pair_info
+        .to_normal(deps.api)
+        .map(|info| WeightedPairResponse { info })
+}
+
+pub fn query_pool(deps: Deps) -> Result<WeightedPoolResponse, ContractError> {
+    let pair_info: WeightedPairInfoRaw = PAIR_INFO.load(deps.storage)?;
+    let contract_addr = deps.api.addr_humanize(&pair_info.contract_addr)?;
+    let assets: Vec<Asset> = pair_info.query_pools(&deps.querier, deps.api, contract_addr)?;
+    let total_share: Uint128 = query_supply(
+        &deps.querier,
+        deps.api.addr_humanize(&pair_info.liquidity_token)?,
+    )?;
+
+    Ok(WeightedPoolResponse {
+        assets,
+        total_share,
+    })
+}
+
+pub fn query_simulation(
+    deps: Deps,
+    offer_asset: Asset,
+    ask_asset_info: AssetInfo,
+) -> Result<SimulationResponse, ContractError> {
+    let pair_info: WeightedPairInfoRaw = PAIR_INFO.load(deps.storage)?;
+
+    let contract_addr = deps.api.addr_humanize(&pair_info.contract_addr)?;
+    let pools: Vec<Asset> = pair_info.query_pools(&deps.querier, deps.api, contract_addr)?;
+    let (offer_index, ask_index) = find_swap_pools(&pools, &offer_asset.info, &ask_asset_info)?;
+
+    let commission_rate = Decimal256::from_str(&pair_info.commission_rate)?;
+    let (return_amount, spread_amount, commission_amount) = compute_weighted_swap(
+        pools[offer_index].amount,
+        pair_info.asset_infos[offer_index].weight,
+        pools[ask_index].amount,
+        pair_info.asset_infos[ask_index].weight,
+        offer_asset.amount,
+        commission_rate,
+    )?;
+
+    Ok(SimulationResponse {
+        return_amount,
+        spread_amount,
+        commission_amount,
+    })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    Ok(Response::default())
+}
@@ -0,0 +1,4 @@
+use cw_storage_plus::Item;
+use oraiswap::weighted_pair::WeightedPairInfoRaw;
+
+pub const PAIR_INFO: Item<WeightedPairInfoRaw> = Item::new("pair_info");
@@ -0,0 +1,383 @@
+use cosmwasm_std::{coin, to_binary, Addr, Decimal, Decimal256, Uint128};
+use cw20::Cw20ExecuteMsg;
+use oraiswap::asset::{Asset, AssetInfo, ORAI_DENOM};
+use oraiswap::create_entry_points_testing;
+use oraiswap::pair::{compute_swap, SimulationResponse};
+use oraiswap::testing::{MockApp, APP_OWNER, ATOM_DENOM};
+use oraiswap::weighted_pair::{
+    compute_weighted_swap, pow_decimal, Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg,
+    WeightedAssetInfo, WeightedPairResponse, WeightedPoolResponse,
+};
+use std::str::FromStr;
+
+fn weighted_asset_infos(token_addr: &Addr) -> Vec<WeightedAssetInfo> {
+    vec![
+        WeightedAssetInfo {
+            info: AssetInfo::NativeToken {
+                denom: ORAI_DENOM.to_string(),
+            },
+            weight: Decimal::percent(50),
+        },
+        WeightedAssetInfo {
+            info: AssetInfo::NativeToken {
+                denom: ATOM_DENOM.to_string(),
+            },
+            weight: Decimal::percent(25),
+        },
+        WeightedAssetInfo {
+            info: AssetInfo::Token {
+                contract_addr: token_addr.clone(),
+            },
+            weight: Decimal::percent(25),
+        },
+    ]
+}
+
+fn provide_assets(token_addr: &Addr, orai: u128, atom: u128, token: u128) -> Vec<Asset> {
+    vec![
+        Asset {
+            info: AssetInfo::NativeToken {
+                denom: ORAI_DENOM.to_string(),
+            },
+            amount: Uint128::from(orai),
+        },
+        Asset {
+            info: AssetInfo::NativeToken {
+                denom: ATOM_DENOM.to_string(),
+            },
+            amount: Uint128::from(atom),
+        },
+        Asset {
+            info: AssetInfo::Token {
+                contract_addr: token_addr.clone(),
+            },
+            amount: Uint128::from(token),
+        },
+    ]
+}
+
+#[test]
+fn provide_liquidity_and_swap() {
+    let mut app = MockApp::new(&[]);
+    app.set_oracle_contract(Box::new(create_entry_points_testing!(oraiswap_oracle)));
+    app.set_token_contract(Box::new(create_entry_points_testing!(oraiswap_token)));
+    app.set_tax(
+        Decimal::zero(),
+        &[
+            (&ORAI_DENOM.to_string(), &Uint128::from(1000000u128)),
+            (&ATOM_DENOM.to_string(), &Uint128::from(1000000u128)),
+        ],
+    );
+
+    let token_addr = app.set_token_balances(&[(
+        &"asset".to_string(),
+        &[(&APP_OWNER.to_string(), &Uint128::from(10000000u128))],
+    )])[0]
+        .clone();
+
+    let code_id = app.upload(Box::new(
+        create_entry_points_testing!(crate).with_reply(crate::contract::reply),
+    ));
+
+    let owner = Addr::unchecked(APP_OWNER);
+    let pair_addr = app
+        .instantiate(
+            code_id,
+            owner.clone(),
+            &InstantiateMsg {
+                asset_infos: weighted_asset_infos(&token_addr),
+                token_code_id: app.token_id,
+                oracle_addr: app.oracle_addr.clone(),
+                commission_rate: None,
+                lp_token_info: None,
+            },
+            &[],
+            "weighted pair",
+        )
+        .unwrap();
+
+    let pair_res: WeightedPairResponse = app.query(pair_addr.clone(), &QueryMsg::Pair {}).unwrap();
+    assert_eq!(pair_res.info.asset_infos, weighted_asset_infos(&token_addr));
+    let liquidity_token = pair_res.info.liquidity_token;
+
+    app.execute(
+        owner.clone(),
+        token_addr.clone(),
+        &Cw20ExecuteMsg::IncreaseAllowance {
+            spender: pair_addr.to_string(),
+            amount: Uint128::from(10000000u128),
+            expires: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // initial share is the value of the deposits in the first asset: 1000000 / 0.5
+    app.execute(
+        owner.clone(),
+        pair_addr.clone(),
+        &ExecuteMsg::ProvideLiquidity {
+            assets: provide_assets(&token_addr, 1000000, 500000, 500000),
+            receiver: None,
+        },
+        &[coin(1000000, ORAI_DENOM), coin(500000, ATOM_DENOM)],
+    )
+    .unwrap();
+
+    // a partial asset list is rejected
+    assert!(app
+        .execute(
+            owner.clone(),
+            pair_addr.clone(),
+            &ExecuteMsg::ProvideLiquidity {
+                assets: provide_assets(&token_addr, 1000, 500, 500)[..2].to_vec(),
+                receiver: None,
+            },
+            &[coin(1000, ORAI_DENOM), coin(500, ATOM_DENOM)],
+        )
+        .is_err());
+
+    // the least deposited asset sets the share, the extra atom is refunded
+    let atom_balance = app
+        .query_balance(owner.clone(), ATOM_DENOM.to_string())
+        .unwrap();
+    app.execute(
+        owner.clone(),
+        pair_addr.clone(),
+        &ExecuteMsg::ProvideLiquidity {
+            assets: provide_assets(&token_addr, 100000, 60000, 50000),
+            receiver: None,
+        },
+        &[coin(100000, ORAI_DENOM), coin(60000, ATOM_DENOM)],
+    )
+    .unwrap();
+    assert_eq!(
+        app.query_balance(owner.clone(), ATOM_DENOM.to_string())
+            .unwrap(),
+        atom_balance - Uint128::from(50000u128)
+    );
+
+    let pool_res: WeightedPoolResponse = app.query(pair_addr.clone(), &QueryMsg::Pool {}).unwrap();
+    assert_eq!(pool_res.total_share, Uint128::from(2200000u128));
+    assert_eq!(
+        pool_res.assets,
+        provide_assets(&token_addr, 1100000, 550000, 550000)
+    );
+    let lp_balance: cw20::BalanceResponse = app
+        .query(
+            liquidity_token.clone(),
+            &cw20::Cw20QueryMsg::Balance {
+                address: APP_OWNER.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(lp_balance.balance, Uint128::from(2200000u128));
+
+    // orai => atom, the spot price is (550000 / 0.25) / (1100000 / 0.5) = 1
+    let offer_asset = Asset {
+        info: AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        amount: Uint128::from(110000u128),
+    };
+    let atom_info = AssetInfo::NativeToken {
+        denom: ATOM_DENOM.to_string(),
+    };
+    let simulation: SimulationResponse = app
+        .query(
+            pair_addr.clone(),
+            &QueryMsg::Simulation {
+                offer_asset: offer_asset.clone(),
+                ask_asset_info: atom_info.clone(),
+            },
+        )
+        .unwrap();
+    // 550000 * (1 - (1100000 / 1210000) ^ 2) = 95454, commission 0.3% = 286
+    assert_eq!(
+        simulation,
+        SimulationResponse {
+            return_amount: Uint128::from(95168u128),
+            spread_amount: Uint128::from(14546u128),
+            commission_amount: Uint128::from(286u128),
+        }
+    );
+
+    // too much spread
+    assert!(app
+        .execute(
+            owner.clone(),
+            pair_addr.clone(),
+            &ExecuteMsg::Swap {
+                offer_asset: offer_asset.clone(),
+                ask_asset_info: atom_info.clone(),
+                belief_price: None,
+                max_spread: Some(Decimal::percent(10)),
+                to: None,
+            },
+            &[coin(110000, ORAI_DENOM)],
+        )
+        .is_err());
+
+    let atom_balance = app
+        .query_balance(owner.clone(), ATOM_DENOM.to_string())
+        .unwrap();
+    app.execute(
+        owner.clone(),
+        pair_addr.clone(),
+        &ExecuteMsg::Swap {
+            offer_asset,
+            ask_asset_info: atom_info.clone(),
+            belief_price: None,
+            max_spread: Some(Decimal::percent(20)),
+            to: None,
+        },
+        &[coin(110000, ORAI_DENOM)],
+    )
+    .unwrap();
+    assert_eq!(
+        app.query_balance(owner.clone(), ATOM_DENOM.to_string())
+            .unwrap(),
+        atom_balance + simulation.return_amount
+    );
+
+    // token => orai through the cw20 hook
+    app.execute(
+        owner.clone(),
+        token_addr.clone(),
+        &Cw20ExecuteMsg::Send {
+            contract: pair_addr.to_string(),
+            amount: Uint128::from(1000u128),
+            msg: to_binary(&Cw20HookMsg::Swap {
+                ask_asset_info: AssetInfo::NativeToken {
+                    denom: ORAI_DENOM.to_string(),
+                },
+                belief_price: None,
+                max_spread: None,
+                to: None,
+            })
+            .unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // the same asset can not be offered and asked
+    assert!(app
+        .execute(
+            owner.clone(),
+            pair_addr.clone(),
+            &ExecuteMsg::Swap {
+                offer_asset: Asset {
+                    info: atom_info.clone(),
+                    amount: Uint128::from(100u128),
+                },
+                ask_asset_info: atom_info,
+                belief_price: None,
+                max_spread: None,
+                to: None,
+            },
+            &[coin(100, ATOM_DENOM)],
+        )
+        .is_err());
+
+    // withdraw all the liquidity, every pool asset is returned
+    app.execute(
+        owner.clone(),
+        liquidity_token,
+        &Cw20ExecuteMsg::Send {
+            contract: pair_addr.to_string(),
+            amount: Uint128::from(2200000u128),
+            msg: to_binary(&Cw20HookMsg::WithdrawLiquidity {}).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+    let pool_res: WeightedPoolResponse = app.query(pair_addr, &QueryMsg::Pool {}).unwrap();
+    assert_eq!(pool_res.total_share, Uint128::zero());
+    assert!(pool_res.assets.iter().all(|asset| asset.amount.is_zero()));
+}
+
+#[test]
+fn instantiate_invalid_weights() {
+    let mut app = MockApp::new(&[]);
+    app.set_oracle_contract(Box::new(create_entry_points_testing!(oraiswap_oracle)));
+    app.set_token_contract(Box::new(create_entry_points_testing!(oraiswap_token)));
+    let code_id = app.upload(Box::new(
+        create_entry_points_testing!(crate).with_reply(crate::contract::reply),
+    ));
+
+    let token_addr = Addr::unchecked("asset");
+    let mut asset_infos = weighted_asset_infos(&token_addr);
+    asset_infos[0].weight = Decimal::percent(40);
+
+    let mut duplicated_asset_infos = weighted_asset_infos(&token_addr);
+    duplicated_asset_infos[2].info = duplicated_asset_infos[1].info.clone();
+
+    for asset_infos in [
+        asset_infos,
+        duplicated_asset_infos,
+        weighted_asset_infos(&token_addr)[..1].to_vec(),
+    ] {
+        let res = app.instantiate(
+            code_id,
+            Addr::unchecked(APP_OWNER),
+            &InstantiateMsg {
+                asset_infos,
+                token_code_id: app.token_id,
+                oracle_addr: app.oracle_addr.clone(),
+                commission_rate: None,
+                lp_token_info: None,
+            },
+            &[],
+            "weighted pair",
+        );
+        assert!(res.is_err());
+    }
+}
+
+#[test]
+fn weighted_swap_math() {
+    let commission_rate = Decimal256::from_str("0.003").unwrap();
+
+    // equal weights match the constant product
+    let (return_amount, _, commission_amount) = compute_weighted_swap(
+        Uint128::from(1000000u128),
+        Decimal::percent(50),
+        Uint128::from(2000000u128),
+        Decimal::percent(50),
+        Uint128::from(100000u128),
+        commission_rate,
+    )
+    .unwrap();
+    let (xyk_return_amount, _, xyk_commission_amount) = compute_swap(
+        Uint128::from(1000000u128),
+        Uint128::from(2000000u128),
+        Uint128::from(100000u128),
+        commission_rate,
+    )
+    .unwrap();
+    assert!(return_amount.abs_diff(xyk_return_amount) <= Uint128::one());
+    assert!(commission_amount.abs_diff(xyk_commission_amount) <= Uint128::one());
+
+    // fractional exponent: 0.9 ^ 1.5 = 0.853814968245462...
+    let power = pow_decimal(
+        Decimal256::from_str("0.9").unwrap(),
+        Decimal256::from_str("1.5").unwrap(),
+    )
+    .unwrap();
+    assert!(
+        power.abs_diff(Decimal256::from_str("0.853814968245462").unwrap())
+            < Decimal256::raw(1_000_000_000)
+    );
+
+    // the offer amount is capped to 30% of the offer pool
+    compute_weighted_swap(
+        Uint128::from(1000000u128),
+        Decimal::percent(60),
+        Uint128::from(1000000u128),
+        Decimal::percent(40),
+        Uint128::from(300001u128),
+        commission_rate,
+    )
+    .unwrap_err();
+}
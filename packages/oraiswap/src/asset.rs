@@ -2,15 +2,22 @@ use cosmwasm_schema::cw_serde;
 use std::fmt;
 
 use crate::oracle::OracleContract;
-use crate::querier::query_token_balance;
+use crate::querier::{query_token_balance, query_token_info};
 
 use cosmwasm_std::{
-    coin, to_binary, Addr, Api, BankMsg, CanonicalAddr, CosmosMsg, Decimal, MessageInfo,
-    QuerierWrapper, StdError, StdResult, Uint128, WasmMsg,
+    coin, to_binary, Addr, Api, BankMsg, CanonicalAddr, CosmosMsg, MessageInfo, QuerierWrapper,
+    StdError, StdResult, Uint128, WasmMsg,
 };
 use cw20::Cw20ExecuteMsg;
 
 pub const ORAI_DENOM: &str = "orai";
+/// native denoms on the chain use 6 decimals
+pub const NATIVE_DECIMALS: u8 = 6;
+
+/// separates the assets of the string form of a pair key, it is not allowed in denoms nor addresses
+const PAIR_KEY_SEPARATOR: char = '|';
+const NATIVE_TOKEN_PREFIX: &str = "native:";
+const TOKEN_PREFIX: &str = "token:";
 
 #[cw_serde]
 pub struct Asset {
@@ -39,16 +46,10 @@ impl Asset {
             if denom == ORAI_DENOM {
                 Ok(Uint128::from(0u64))
             } else {
-                // get oracle params from oracle contract
-                let tax_rate = oracle_contract.query_tax_rate(querier)?.rate;
-                let tax_cap = oracle_contract
-                    .query_tax_cap(querier, denom.to_string())?
-                    .cap;
-
-                Ok(std::cmp::min(
-                    amount.checked_sub(amount * (Decimal::one() / (tax_rate + Decimal::one())))?,
-                    tax_cap,
-                ))
+                // get tax rate and tax cap from oracle contract in one query
+                oracle_contract
+                    .query_tax_info(querier, denom.to_string())?
+                    .compute_tax(amount)
             }
         } else {
             Ok(Uint128::from(0u64))
@@ -186,6 +187,15 @@ impl AssetInfo {
         }
     }
 
+    pub fn query_decimals(&self, querier: &QuerierWrapper) -> StdResult<u8> {
+        match self {
+            AssetInfo::Token { contract_addr, .. } => {
+                query_token_info(querier, contract_addr.to_owned()).map(|info| info.decimals)
+            }
+            AssetInfo::NativeToken { .. } => Ok(NATIVE_DECIMALS),
+        }
+    }
+
     pub fn eq(&self, asset: &AssetInfo) -> bool {
         match self {
             AssetInfo::Token { contract_addr, .. } => {
@@ -212,6 +222,12 @@ pub struct AssetRaw {
     pub amount: Uint128,
 }
 
+impl fmt::Display for AssetRaw {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.amount, self.info)
+    }
+}
+
 impl AssetRaw {
     pub fn to_normal(&self, api: &dyn Api) -> StdResult<Asset> {
         Ok(Asset {
@@ -234,6 +250,16 @@ pub enum AssetInfoRaw {
     NativeToken { denom: String },
 }
 
+/// denom of native tokens, hex of the canonical address of tokens
+impl fmt::Display for AssetInfoRaw {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AssetInfoRaw::NativeToken { denom } => write!(f, "{}", denom),
+            AssetInfoRaw::Token { contract_addr } => write!(f, "{}", contract_addr),
+        }
+    }
+}
+
 impl AssetInfoRaw {
     pub fn to_normal(&self, api: &dyn Api) -> StdResult<AssetInfo> {
         match self {
@@ -284,6 +310,9 @@ pub struct PairInfo {
 
     pub oracle_addr: Addr,
     pub commission_rate: String,
+    /// tokenfactory denom of the LP shares, the liquidity token is then the pair contract itself
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lp_denom: Option<String>,
 }
 
 #[cw_serde]
@@ -295,6 +324,8 @@ pub struct PairInfoRaw {
     // oracle contract
     pub oracle_addr: CanonicalAddr,
     pub commission_rate: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lp_denom: Option<String>,
 }
 
 impl PairInfoRaw {
@@ -308,6 +339,7 @@ impl PairInfoRaw {
                 self.asset_infos[1].to_normal(api)?,
             ],
             commission_rate: self.commission_rate.clone(),
+            lp_denom: self.lp_denom.clone(),
         })
     }
 
@@ -343,3 +375,98 @@ pub fn pair_key_from_asset_keys(ask_asset_key: &[u8], offer_asset_key: &[u8]) ->
         false => [offer_asset_key, ask_asset_key].concat(),
     }
 }
+
+/// storage key of the pair of the assets in any order
+pub fn pair_key_from_infos(api: &dyn Api, asset_infos: &[AssetInfo; 2]) -> StdResult<Vec<u8>> {
+    Ok(pair_key(&[
+        asset_infos[0].to_raw(api)?,
+        asset_infos[1].to_raw(api)?,
+    ]))
+}
+
+/// stable string form of the pair key, the assets are ordered like the pair key so any order of the assets
+/// gives the same string, e.g. native:orai|token:orai1...
+pub fn pair_key_to_string(api: &dyn Api, asset_infos: &[AssetInfo; 2]) -> StdResult<String> {
+    let mut asset_infos = [
+        (asset_infos[0].to_raw(api)?, &asset_infos[0]),
+        (asset_infos[1].to_raw(api)?, &asset_infos[1]),
+    ];
+    asset_infos.sort_by(|a, b| a.0.as_bytes().cmp(b.0.as_bytes()));
+
+    Ok(asset_infos
+        .iter()
+        .map(|(_, asset_info)| match asset_info {
+            AssetInfo::NativeToken { denom } => format!("{}{}", NATIVE_TOKEN_PREFIX, denom),
+            AssetInfo::Token { contract_addr } => format!("{}{}", TOKEN_PREFIX, contract_addr),
+        })
+        .collect::<Vec<String>>()
+        .join(&PAIR_KEY_SEPARATOR.to_string()))
+}
+
+/// assets of the string form of a pair key, the raw pair key concatenates the asset keys
+/// without their lengths so only the string form can be decoded
+pub fn decode_pair_key(api: &dyn Api, key: &str) -> StdResult<[AssetInfo; 2]> {
+    let decode_asset_info = |asset_key: &str| -> StdResult<AssetInfo> {
+        if let Some(denom) = asset_key.strip_prefix(NATIVE_TOKEN_PREFIX) {
+            if !denom.is_empty() {
+                return Ok(AssetInfo::NativeToken {
+                    denom: denom.to_string(),
+                });
+            }
+        }
+        if let Some(contract_addr) = asset_key.strip_prefix(TOKEN_PREFIX) {
+            return Ok(AssetInfo::Token {
+                contract_addr: api.addr_validate(contract_addr)?,
+            });
+        }
+        Err(StdError::generic_err(format!(
+            "invalid asset in pair key: {}",
+            asset_key
+        )))
+    };
+
+    match key.split(PAIR_KEY_SEPARATOR).collect::<Vec<&str>>()[..] {
+        [first, second] => Ok([decode_asset_info(first)?, decode_asset_info(second)?]),
+        _ => Err(StdError::generic_err(format!("invalid pair key: {}", key))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::MockApi;
+
+    #[test]
+    fn pair_key_string_roundtrip() {
+        let api = MockApi::default();
+        let asset_infos = [
+            AssetInfo::Token {
+                contract_addr: Addr::unchecked("token0000"),
+            },
+            AssetInfo::NativeToken {
+                denom: ORAI_DENOM.to_string(),
+            },
+        ];
+        let reversed = [asset_infos[1].clone(), asset_infos[0].clone()];
+
+        assert_eq!(
+            pair_key_from_infos(&api, &asset_infos).unwrap(),
+            pair_key_from_infos(&api, &reversed).unwrap()
+        );
+
+        let key = pair_key_to_string(&api, &asset_infos).unwrap();
+        assert_eq!(key, pair_key_to_string(&api, &reversed).unwrap());
+
+        // decoded assets are ordered like the pair key
+        let decoded = decode_pair_key(&api, &key).unwrap();
+        assert_eq!(
+            pair_key_from_infos(&api, &decoded).unwrap(),
+            pair_key_from_infos(&api, &asset_infos).unwrap()
+        );
+        assert!(decoded.contains(&asset_infos[0]) && decoded.contains(&asset_infos[1]));
+
+        assert!(decode_pair_key(&api, "native:orai").is_err());
+        assert!(decode_pair_key(&api, "native:orai|orai").is_err());
+        assert!(decode_pair_key(&api, "native:orai|native:usdt|native:atom").is_err());
+    }
+}
@@ -1,9 +1,9 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 
-use cosmwasm_std::{Addr, Decimal};
+use cosmwasm_std::{to_binary, Addr, Coin, CosmosMsg, Decimal, StdResult, Uint128, WasmMsg};
 
-use crate::asset::AssetInfo;
-use cw20::Cw20ReceiveMsg;
+use crate::asset::{Asset, AssetInfo};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
 
 #[cw_serde]
 pub struct TokenInfo {
@@ -17,6 +17,21 @@ pub struct TokenRatio {
     pub ratio: Decimal,
 }
 
+/// Throttles the conversions of a pair, applied when converting `from` into `to`
+#[cw_serde]
+pub struct ConvertLimit {
+    /// fee taken from the converted amount, in basis points
+    pub fee_bps: u16,
+    /// max amount of the `from` token that can be converted per day
+    pub daily_cap: Option<Uint128>,
+}
+
+impl ConvertLimit {
+    pub fn compute_fee(&self, amount: Uint128) -> Uint128 {
+        amount.multiply_ratio(self.fee_bps, 10000u128)
+    }
+}
+
 #[cw_serde]
 pub struct InstantiateMsg {}
 #[cw_serde]
@@ -40,12 +55,29 @@ pub enum ExecuteMsg {
     UnregisterPair {
         from: TokenInfo,
     },
+    /// set or remove (with None) the conversion fee and daily cap of a pair
+    UpdateConvertLimit {
+        from: AssetInfo,
+        limit: Option<ConvertLimit>,
+    },
     ConvertReverse {
         from_asset: AssetInfo,
     },
     WithdrawTokens {
         asset_infos: Vec<AssetInfo>,
     },
+    /// create the tokenfactory denom factory/{converter}/{subdenom} backed 1:1 by the cw20 token
+    RegisterWrappedToken {
+        cw20: Addr,
+        subdenom: String,
+    },
+    /// pause or resume wrapping and unwrapping the cw20 token
+    UpdateWrappedToken {
+        cw20: Addr,
+        paused: bool,
+    },
+    /// burn the wrapped denoms sent as funds and return the backing cw20 tokens
+    Unwrap {},
 }
 
 #[cw_serde]
@@ -55,12 +87,17 @@ pub enum QueryMsg {
     Config {},
     #[returns(ConvertInfoResponse)]
     ConvertInfo { asset_info: AssetInfo },
+    #[returns(ConvertLimitResponse)]
+    ConvertLimit { asset_info: AssetInfo },
+    #[returns(WrappedTokenResponse)]
+    WrappedToken { cw20: Addr },
 }
 
 #[cw_serde]
 pub enum Cw20HookMsg {
     Convert {},
     ConvertReverse { from: AssetInfo },
+    Wrap {},
 }
 
 // We define a custom struct for each query response
@@ -73,3 +110,55 @@ pub struct ConfigResponse {
 pub struct ConvertInfoResponse {
     pub token_ratio: TokenRatio,
 }
+
+#[cw_serde]
+pub struct ConvertLimitResponse {
+    pub limit: Option<ConvertLimit>,
+    // amount of the from token converted today
+    pub daily_volume: Uint128,
+    // total fee taken in the to token
+    pub collected_fee: Uint128,
+}
+
+#[cw_serde]
+pub struct WrappedTokenResponse {
+    pub cw20: Addr,
+    pub denom: String,
+    // wrapped amount in circulation, backed by the cw20 balance of the converter
+    pub supply: Uint128,
+    pub paused: bool,
+}
+
+/// Build the message sending the asset to the converter, converting it back into
+/// `reverse_from` when set
+pub fn asset_into_convert_msg(
+    converter_addr: Addr,
+    offer_asset: Asset,
+    reverse_from: Option<AssetInfo>,
+) -> StdResult<CosmosMsg> {
+    match offer_asset.info {
+        AssetInfo::NativeToken { denom } => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: converter_addr.to_string(),
+            funds: vec![Coin {
+                denom,
+                amount: offer_asset.amount,
+            }],
+            msg: match reverse_from {
+                Some(from_asset) => to_binary(&ExecuteMsg::ConvertReverse { from_asset })?,
+                None => to_binary(&ExecuteMsg::Convert {})?,
+            },
+        })),
+        AssetInfo::Token { contract_addr } => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: converter_addr.to_string(),
+                amount: offer_asset.amount,
+                msg: match reverse_from {
+                    Some(from) => to_binary(&Cw20HookMsg::ConvertReverse { from })?,
+                    None => to_binary(&Cw20HookMsg::Convert {})?,
+                },
+            })?,
+        })),
+    }
+}
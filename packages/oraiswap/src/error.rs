@@ -1,4 +1,4 @@
-use cosmwasm_std::{OverflowError, StdError, Uint128};
+use cosmwasm_std::{Decimal, OverflowError, StdError, Uint128};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -21,6 +21,12 @@ pub enum ContractError {
     #[error("Max slippage assertion")]
     MaxSlippageAssertion {},
 
+    #[error("Required offer amount {offer_amount} exceeds max offer amount {max_offer_amount}")]
+    MaxOfferAssertion {
+        offer_amount: Uint128,
+        max_offer_amount: Uint128,
+    },
+
     #[error("Slippage_tolerance cannot bigger than 1")]
     InvalidExceedOneSlippage {},
 
@@ -53,6 +59,9 @@ pub enum ContractError {
     #[error("must provide operations")]
     NoSwapOperation {},
 
+    #[error("Swap operations must start and end with the same asset")]
+    InvalidCycle {},
+
     #[error("invalid cw20 hook message")]
     InvalidCw20HookMessage {},
 
@@ -62,6 +71,12 @@ pub enum ContractError {
     #[error("Order book pair already exists")]
     OrderBookAlreadyExists {},
 
+    #[error("Base {base_coin} and quote {quote_coin} of the order book are the same asset")]
+    IdenticalOrderBookAssets {
+        base_coin: String,
+        quote_coin: String,
+    },
+
     #[error("Order asset must not be zero")]
     AssetMustNotBeZero {},
 
@@ -73,4 +88,55 @@ pub enum ContractError {
         quote_coin: String,
         min_quote_amount: Uint128,
     },
+
+    #[error("Order notional {notional} is less than the min quote notional {min_quote_notional}")]
+    TooSmallQuoteNotional {
+        notional: Uint128,
+        min_quote_notional: Uint128,
+    },
+
+    #[error("Contract is paused")]
+    Paused {},
+
+    #[error("Offer amount {offer_amount} exceeds max trade amount {max_trade_amount}")]
+    MaxTradeExceeded {
+        offer_amount: Uint128,
+        max_trade_amount: Uint128,
+    },
+
+    #[error("Order {order_id} can not be cancelled within {min_order_lifetime_seconds} seconds")]
+    OrderLifetimeNotReached {
+        order_id: u64,
+        min_order_lifetime_seconds: u64,
+    },
+
+    #[error("Order price {price} deviates too far from reference price {reference_price}")]
+    PriceOutOfBand {
+        price: Decimal,
+        reference_price: Decimal,
+    },
+
+    #[error("Bidder already has {max_orders_per_user} open orders in the order book")]
+    MaxOrdersPerUserReached { max_orders_per_user: u32 },
+
+    #[error("Invalid order signature")]
+    InvalidSignature {},
+
+    #[error("Invalid nonce, expected {expected}")]
+    InvalidNonce { expected: u64 },
+
+    #[error("Next auction of the order book is at {next_auction_time}")]
+    AuctionNotDue { next_auction_time: u64 },
+
+    #[error("Order book is delisting, no new orders are accepted")]
+    OrderBookDelisting {},
+
+    #[error("Order book is delisted at {delisting_time}")]
+    OrderBookDelisted { delisting_time: u64 },
+
+    #[error("Order book is not delisted until {delisting_time:?}")]
+    OrderBookNotDelisted { delisting_time: Option<u64> },
+
+    #[error("Pool invariant violated, the product of the pools decreased after the swap")]
+    InvariantViolated {},
 }
@@ -1,7 +1,9 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Binary};
+use cosmwasm_std::{Addr, Binary, Decimal, Uint128};
 
-use crate::asset::{AssetInfo, PairInfo};
+use crate::asset::{Asset, AssetInfo, PairInfo};
+use crate::pair::{LpTokenInfo, LpTokenType};
+use crate::weighted_pair::{WeightedAssetInfo, WeightedPairInfo};
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -12,28 +14,102 @@ pub struct InstantiateMsg {
     pub commission_rate: Option<String>,
 }
 
+#[cw_serde]
+#[derive(Copy, Default)]
+pub enum PairType {
+    #[default]
+    Xyk,
+    Weighted,
+}
+
+impl PairType {
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            PairType::Xyk => b"xyk",
+            PairType::Weighted => b"weighted",
+        }
+    }
+}
+
+/// PairTemplate holds the parameters applied to every new pair of the given type
+#[cw_serde]
+pub struct PairTemplate {
+    pub pair_type: PairType,
+    pub pair_code_id: u64,
+    pub commission_rate: String,
+}
+
 #[cw_serde]
 pub enum ExecuteMsg {
-    /// UpdateConfig update relevant code IDs
+    /// UpdateConfig update relevant code IDs and the default commission rate
     UpdateConfig {
         owner: Option<String>,
         token_code_id: Option<u64>,
         pair_code_id: Option<u64>,
+        commission_rate: Option<String>,
+        /// guardian set to new pairs, it can pause swaps and providing liquidity
+        guardian: Option<String>,
+        /// staking contract set to new pairs, it receives auto staked LP tokens
+        staking_contract: Option<String>,
+    },
+    /// UpdatePairTemplate set the parameters of new pairs of a pair type
+    UpdatePairTemplate {
+        template: PairTemplate,
     },
     /// CreatePair instantiates pair contract
     CreatePair {
         /// Asset infos
         asset_infos: [AssetInfo; 2],
         pair_admin: Option<String>,
+        /// Pair type, default is xyk
+        pair_type: Option<PairType>,
+        /// Liquidity token name, symbol and marketing info
+        lp_token_info: Option<LpTokenInfo>,
+        /// LP shares as a cw20 contract or a tokenfactory denom, default is cw20
+        lp_token_type: Option<LpTokenType>,
+    },
+    /// CreateWeightedPair instantiates a weighted pool of 2 to 4 assets from the template of weighted pairs
+    CreateWeightedPair {
+        asset_infos: Vec<WeightedAssetInfo>,
+        pair_admin: Option<String>,
+        lp_token_info: Option<LpTokenInfo>,
+    },
+    /// CreatePairs instantiates the pair contracts of a batch, failing if any of the pairs exists
+    CreatePairs {
+        pairs: Vec<CreatePairParams>,
     },
     AddPair {
         pair_info: PairInfo,
     },
+    /// ProvideLiquidityToPair looks up the pair of the assets and provides liquidity on behalf of the sender,
+    /// native assets are forwarded and tokens need an allowance to the factory
+    ProvideLiquidityToPair {
+        asset_infos: [AssetInfo; 2],
+        assets: [Asset; 2],
+        slippage_tolerance: Option<Decimal>,
+        auto_stake: Option<bool>,
+    },
     MigrateContract {
         contract_addr: String,
         new_code_id: u64,
         msg: Binary,
     },
+    /// UpdateTvl values the reserves of a page of pairs in orai at the oracle exchange rates, anyone can call it
+    UpdateTvl {
+        /// next_cursor attribute of the previous call, from the first pair when not set
+        cursor: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+/// CreatePairParams are the parameters of CreatePair for one pair of a CreatePairs batch
+#[cw_serde]
+pub struct CreatePairParams {
+    pub asset_infos: [AssetInfo; 2],
+    pub pair_admin: Option<String>,
+    pub pair_type: Option<PairType>,
+    pub lp_token_info: Option<LpTokenInfo>,
+    pub lp_token_type: Option<LpTokenType>,
 }
 
 #[cw_serde]
@@ -47,7 +123,18 @@ pub enum QueryMsg {
     Pairs {
         start_after: Option<[AssetInfo; 2]>,
         limit: Option<u32>,
+        /// next_cursor of the previous page, takes precedence over start_after
+        cursor: Option<String>,
     },
+    /// weighted pool of the assets, in any order
+    #[returns(WeightedPairInfo)]
+    WeightedPair { asset_infos: Vec<AssetInfo> },
+    /// value locked in the pair at its last UpdateTvl
+    #[returns(TvlResponse)]
+    Tvl { asset_infos: [AssetInfo; 2] },
+    /// value locked in all pairs
+    #[returns(TotalTvlResponse)]
+    TotalTvl {},
 }
 
 // We define a custom struct for each query response
@@ -57,6 +144,25 @@ pub struct ConfigResponse {
     pub oracle_addr: Addr,
     pub pair_code_id: u64,
     pub token_code_id: u64,
+    pub commission_rate: String,
+    pub pair_templates: Vec<PairTemplate>,
+    pub guardian: Option<Addr>,
+    pub staking_contract: Option<Addr>,
+}
+
+/// TvlResponse is the value of the reserves of a pair in orai,
+/// updated_at is None before the first UpdateTvl of the pair
+#[cw_serde]
+pub struct TvlResponse {
+    pub asset_infos: [AssetInfo; 2],
+    pub tvl: Uint128,
+    pub updated_at: Option<u64>,
+}
+
+#[cw_serde]
+pub struct TotalTvlResponse {
+    pub total_tvl: Uint128,
+    pub denom: String,
 }
 
 /// We currently take no arguments for migrations
@@ -67,4 +173,6 @@ pub struct MigrateMsg {}
 #[cw_serde]
 pub struct PairsResponse {
     pub pairs: Vec<PairInfo>,
+    /// cursor of the next page, none when there are no more pairs
+    pub next_cursor: Option<String>,
 }
@@ -0,0 +1,96 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+
+use cosmwasm_std::{Addr, Decimal, Uint128};
+
+use crate::asset::AssetInfo;
+use crate::router::SwapOperation;
+
+/// Recipient receives `weight / total weight` of the distributed target asset
+#[cw_serde]
+pub struct Recipient {
+    pub address: Addr,
+    pub weight: u64,
+}
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// default is sender
+    pub owner: Option<Addr>,
+    pub router: Addr,
+    /// all collected fees are swapped into this asset before being distributed
+    pub target_asset_info: AssetInfo,
+    pub recipients: Vec<Recipient>,
+    /// cw20 token (ORAIX) bought back and burned by BuybackAndBurn
+    pub burn_token: Option<Addr>,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    ///////////////////
+    /// Owner Operations
+    ///////////////////
+    UpdateConfig {
+        owner: Option<Addr>,
+        router: Option<Addr>,
+        target_asset_info: Option<AssetInfo>,
+        recipients: Option<Vec<Recipient>>,
+        burn_token: Option<Addr>,
+    },
+    /// Set the router operations used to swap the asset into the target asset,
+    /// empty operations will remove the route and use the direct pair instead
+    UpdateSwapRoute {
+        asset_info: AssetInfo,
+        operations: Vec<SwapOperation>,
+    },
+    /// Swap the collected balance of the asset into the burn token via the router then burn it
+    BuybackAndBurn {
+        asset: AssetInfo,
+        max_spread: Option<Decimal>,
+    },
+
+    ///////////////////
+    /// Permissionless Operations
+    ///////////////////
+    /// Swap all collected balances of the given assets into the target asset via the router
+    Swap { asset_infos: Vec<AssetInfo> },
+    /// Distribute the target asset balance to the recipients
+    Distribute {},
+
+    /// Internal use
+    /// Burn the burn token received since prev_balance
+    BurnReceived { prev_balance: Uint128 },
+}
+
+#[cw_serde]
+pub struct MigrateMsg {}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(ConfigResponse)]
+    Config {},
+    #[returns(SwapRouteResponse)]
+    SwapRoute { asset_info: AssetInfo },
+    #[returns(TotalBurnedResponse)]
+    TotalBurned {},
+}
+
+// We define a custom struct for each query response
+#[cw_serde]
+pub struct ConfigResponse {
+    pub owner: Addr,
+    pub router: Addr,
+    pub target_asset_info: AssetInfo,
+    pub recipients: Vec<Recipient>,
+    pub burn_token: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct SwapRouteResponse {
+    pub operations: Vec<SwapOperation>,
+}
+
+#[cw_serde]
+pub struct TotalBurnedResponse {
+    pub amount: Uint128,
+}
@@ -2,16 +2,23 @@ pub mod asset;
 pub mod converter;
 pub mod error;
 pub mod factory;
+pub mod fee_collector;
 pub mod ibc;
 pub mod limit_order;
 pub mod math;
 pub mod oracle;
+pub mod pagination;
 pub mod pair;
 pub mod querier;
+pub mod rebate;
+pub mod referral;
+pub mod registry;
 pub mod response;
 pub mod rewarder;
 pub mod router;
 pub mod staking;
+pub mod tokenfactory;
+pub mod weighted_pair;
 
 #[cfg(not(target_arch = "wasm32"))]
 pub use cw_multi_test;
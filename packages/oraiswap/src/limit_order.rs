@@ -1,6 +1,6 @@
 use crate::asset::{Asset, AssetInfo};
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, CanonicalAddr, Decimal, Uint128};
+use cosmwasm_std::{Addr, Binary, CanonicalAddr, Decimal, Uint128};
 use cw20::Cw20ReceiveMsg;
 
 #[cw_serde]
@@ -12,6 +12,100 @@ pub struct ContractInfo {
     pub commission_rate: String,
     pub reward_address: CanonicalAddr,
     pub spread_address: CanonicalAddr,
+    // guardian can pause user operations along with the admin
+    pub guardian: Option<CanonicalAddr>,
+    // rate of the matched quote amount of maker orders rebated from the taker commission
+    pub maker_rebate_rate: Option<Decimal>,
+    // events emitted when matching orders, verbose by default
+    pub event_mode: Option<EventMode>,
+    // rewarder paying the incentive points of the makers
+    pub rewarder: Option<CanonicalAddr>,
+    // referral contract paid the share of the commission of the referred traders
+    pub referral: Option<CanonicalAddr>,
+    // oracle converting the fees quoted in a usd stable denom into the assets of the order books
+    pub oracle: Option<CanonicalAddr>,
+    // converter of the legacy assets, an order book can not pair an asset with its converted asset
+    pub converter: Option<CanonicalAddr>,
+}
+
+/// Verbose emits a matched_order event per filled order,
+/// Compact emits one matched_orders event with the aggregated count, volume and fees
+#[cw_serde]
+#[derive(Copy)]
+pub enum EventMode {
+    Verbose,
+    Compact,
+}
+
+impl EventMode {
+    pub fn as_str(&self) -> &str {
+        match self {
+            EventMode::Verbose => "verbose",
+            EventMode::Compact => "compact",
+        }
+    }
+}
+
+impl Default for EventMode {
+    fn default() -> Self {
+        EventMode::Verbose
+    }
+}
+
+/// SuperAdmin holds every role and grants or revokes them, the admin is always a SuperAdmin.
+/// Operator manages the order books, FeeManager the commission and maker rebate rates,
+/// Pauser pauses and unpauses user operations along with the guardian
+#[cw_serde]
+#[derive(Copy)]
+pub enum Role {
+    SuperAdmin,
+    Operator,
+    FeeManager,
+    Pauser,
+}
+
+impl Role {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Role::SuperAdmin => "super_admin",
+            Role::Operator => "operator",
+            Role::FeeManager => "fee_manager",
+            Role::Pauser => "pauser",
+        }
+    }
+}
+
+/// Interval of the OHLCV candles maintained for each order book
+#[cw_serde]
+#[derive(Copy)]
+pub enum CandleInterval {
+    Minute,
+    Hour,
+    Day,
+}
+
+impl CandleInterval {
+    pub const ALL: [CandleInterval; 3] = [
+        CandleInterval::Minute,
+        CandleInterval::Hour,
+        CandleInterval::Day,
+    ];
+
+    pub fn seconds(&self) -> u64 {
+        match self {
+            CandleInterval::Minute => 60,
+            CandleInterval::Hour => 3600,
+            CandleInterval::Day => 86400,
+        }
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            CandleInterval::Minute => &[0u8],
+            CandleInterval::Hour => &[1u8],
+            CandleInterval::Day => &[2u8],
+        }
+    }
 }
 
 #[cw_serde]
@@ -73,12 +167,37 @@ pub enum ExecuteMsg {
         admin: Addr,
     },
 
+    /// SuperAdmin grant a role to an address
+    GrantRole {
+        address: Addr,
+        role: Role,
+    },
+
+    /// SuperAdmin revoke a role from an address
+    RevokeRole {
+        address: Addr,
+        role: Role,
+    },
+
     UpdateConfig {
         reward_address: Option<Addr>,
         spread_address: Option<Addr>,
         commission_rate: Option<String>,
+        guardian: Option<Addr>,
+        maker_rebate_rate: Option<Decimal>,
+        event_mode: Option<EventMode>,
+        rewarder: Option<Addr>,
+        referral: Option<Addr>,
+        oracle: Option<Addr>,
+        converter: Option<Addr>,
     },
 
+    /// Pause submitting and matching orders, can be called by pauser or guardian
+    Pause {},
+
+    /// Unpause submitting and matching orders, can be called by pauser or guardian
+    Unpause {},
+
     CreateOrderBookPair {
         base_coin_info: AssetInfo,
         quote_coin_info: AssetInfo,
@@ -86,12 +205,84 @@ pub enum ExecuteMsg {
         min_quote_coin_amount: Uint128,
     },
 
+    /// Operator set protection and fee settings of an order book.
+    /// Only the given settings are updated, a zero amount or an empty address or denom removes the setting
+    UpdateOrderBookPair {
+        asset_infos: [AssetInfo; 2],
+        settings: OrderBookSettings,
+    },
+
+    /// Operator set the operator of an order book, who can update its non-critical parameters
+    UpdateOrderBookOperator {
+        asset_infos: [AssetInfo; 2],
+        operator: Option<Addr>,
+    },
+
+    /// Operator switch an order book to call market mode, where orders are only matched once every
+    /// interval_seconds at the single clearing price that maximizes the matched volume.
+    /// None switches back to continuous matching
+    UpdateOrderBookAuction {
+        asset_infos: [AssetInfo; 2],
+        interval_seconds: Option<u64>,
+    },
+
+    /// Operator of the order book or Operator role update the spread, min quote coin amount or paused state of an order book,
+    /// submitting and matching orders of a paused order book is rejected
+    UpdateOrderBookParams {
+        asset_infos: [AssetInfo; 2],
+        spread: Option<Decimal>,
+        min_quote_coin_amount: Option<Uint128>,
+        paused: Option<bool>,
+        /// decimal places of the price levels of OrdersAtPrice, at most 18
+        price_precision: Option<u32>,
+        /// min quote value (price * remaining base amount) of submitted and amended orders, zero removes it
+        min_quote_notional: Option<Uint128>,
+        /// min rate of the remaining base amount of the sell price level a match must fill, zero removes it
+        min_fill_ratio: Option<Decimal>,
+        /// a match filling at least this base amount, or all the remaining, passes the min fill ratio, zero removes it
+        min_fill_base: Option<Uint128>,
+    },
+
     ///////////////////////
     /// User Operations ///
     ///////////////////////
     SubmitOrder {
         direction: OrderDirection, // default is buy, with sell then it is reversed
         assets: [Asset; 2],
+        /// id of the order in the trading system of the bidder, unique among the open orders of the bidder
+        client_id: Option<String>,
+    },
+
+    /// Submit an order of quote_amount at the price, the base amount is derived with the decimals of the order book
+    /// and rounded down, then the quote amount is rounded down to the base amount at the price.
+    /// A buy order pays the quote amount and a sell order the base amount, native funds sent above it are refunded
+    SubmitQuoteOrder {
+        asset_infos: [AssetInfo; 2],
+        direction: OrderDirection,
+        price: Decimal,
+        quote_amount: Uint128,
+        client_id: Option<String>,
+    },
+
+    /// Submit multiple native token orders at once, the sent funds must cover all orders collectively
+    SubmitOrders {
+        direction_and_assets: Vec<DirectionAndAssets>,
+    },
+
+    /// Submit an order signed by the bidder, the sender pays the gas and the offer token is pulled
+    /// from the bidder's allowance. The signature is the secp256k1 signature of the sha256 hash
+    /// of the json OrderPermitPayload, by the public key registered with SetOrderSigner
+    SubmitOrderOnBehalf {
+        order: OrderPermit,
+        signature: Binary,
+        /// must equal the nonce of the bidder, which is increased on every submitted permit
+        nonce: u64,
+    },
+
+    /// Register the secp256k1 public key signing the orders of the sender for SubmitOrderOnBehalf,
+    /// none removes it
+    SetOrderSigner {
+        pubkey: Option<Binary>,
     },
 
     CancelOrder {
@@ -99,16 +290,106 @@ pub enum ExecuteMsg {
         asset_infos: [AssetInfo; 2],
     },
 
+    /// Amend the offer and ask amounts of an open order, an increase of a native offer amount must be sent
+    /// along, a decrease is refunded. Orders offering a token are topped up with Cw20HookMsg::UpdateOrder
+    UpdateOrder {
+        order_id: u64,
+        assets: [Asset; 2],
+    },
+
     /// Arbitrager execute order book pair
     ExecuteOrderBookPair {
         asset_infos: [AssetInfo; 2],
         limit: Option<u32>,
     },
 
-    /// Arbitrager remove order book
+    /// Simulate ExecuteOrderBookPair without changing the state,
+    /// the would-be fills are returned as MatchOrdersDryRunResponse in the data field
+    MatchOrdersDryRun {
+        asset_infos: [AssetInfo; 2],
+        limit: Option<u32>,
+    },
+
+    /// Operator remove order book, right away when there is no grace period, otherwise the order book
+    /// is delisted: new orders are rejected and matching continues until the grace period ends
     RemoveOrderBookPair {
         asset_infos: [AssetInfo; 2],
+        grace_period_seconds: Option<u64>,
     },
+
+    /// Operator refund the remaining orders of a delisted order book after its grace period in batches of limit,
+    /// the order book is removed along with the last batch
+    RefundDelistedOrders {
+        asset_infos: [AssetInfo; 2],
+        limit: Option<u32>,
+    },
+
+    /// SuperAdmin import orders exported from the previous contract, only allowed after migrating with allow_import_orders
+    ImportOrders {
+        asset_infos: [AssetInfo; 2],
+        orders: Vec<OrderRecord>,
+    },
+
+    /// Maker claim rebates accrued when their orders are matched
+    ClaimRebates {},
+
+    /// Trader claim payouts kept in escrow after their transfer failed
+    ClaimEscrow {},
+
+    /// Maker claim incentive points accrued by resting orders, paid by the rewarder
+    ClaimIncentives {},
+}
+
+/// Settings of UpdateOrderBookPair, an omitted setting is kept
+#[cw_serde]
+#[derive(Default)]
+#[serde(default)]
+pub struct OrderBookSettings {
+    /// orders cancelled within this time are charged cancel_fee_rate of the remaining offer amount
+    /// sent to the reward address, or rejected if there is no cancel fee
+    pub min_order_lifetime_seconds: Option<u64>,
+    pub cancel_fee_rate: Option<Decimal>,
+    /// orders never filled for this many blocks since submitted are cancelled for free
+    pub free_cancel_blocks: Option<u64>,
+    /// ExecuteOrderBookPair calls beyond this limit in the same block are a no-op
+    pub max_executions_per_block: Option<u32>,
+    /// orders whose price deviates more than max_price_deviation from the price_reference pair pool are rejected
+    pub price_reference: Option<Addr>,
+    pub max_price_deviation: Option<Decimal>,
+    pub relayer_fee_rate: Option<Decimal>,
+    pub min_relayer_fee: Option<Uint128>,
+    pub max_relayer_fee: Option<Uint128>,
+    /// the min and max relayer fees are amounts of this usd stable denom, converted into the base asset
+    /// through the oracle at match time
+    pub fee_usd_denom: Option<String>,
+    /// a bidder can not have more open orders in the order book
+    pub max_orders_per_user: Option<u32>,
+    /// after each matching, resting orders within this band of the mid price accrue incentive points
+    pub incentive_price_band: Option<Decimal>,
+}
+
+#[cw_serde]
+pub struct DirectionAndAssets {
+    pub direction: OrderDirection,
+    pub assets: [Asset; 2],
+}
+
+/// OrderPermit is an order the bidder authorizes others to submit
+#[cw_serde]
+pub struct OrderPermit {
+    pub bidder: Addr,
+    pub direction: OrderDirection,
+    pub assets: [Asset; 2],
+    pub client_id: Option<String>,
+}
+
+/// OrderPermitPayload is the document signed for SubmitOrderOnBehalf, bound to the contract and chain
+#[cw_serde]
+pub struct OrderPermitPayload {
+    pub contract_addr: Addr,
+    pub chain_id: String,
+    pub nonce: u64,
+    pub order: OrderPermit,
 }
 
 #[cw_serde]
@@ -116,6 +397,18 @@ pub enum Cw20HookMsg {
     SubmitOrder {
         direction: OrderDirection,
         assets: [Asset; 2],
+        client_id: Option<String>,
+    },
+    /// Submit multiple orders paid by the sent token, the sent amount must cover all orders collectively
+    SubmitOrders {
+        direction_and_assets: Vec<DirectionAndAssets>,
+    },
+    /// Amend an open order offering the sent token, the sent amount must equal the increase of the offer amount
+    UpdateOrder { order_id: u64, assets: [Asset; 2] },
+    /// Execute order book pair with the token sender as the relayer, the sent token is returned
+    ExecutePair {
+        asset_infos: [AssetInfo; 2],
+        limit: Option<u32>,
     },
 }
 
@@ -145,6 +438,9 @@ pub enum QueryMsg {
         order_id: u64,
         asset_infos: [AssetInfo; 2],
     },
+    /// open order of a bidder by the client id given on submit
+    #[returns(OrderResponse)]
+    OrderByClientId { bidder: Addr, client_id: String },
     #[returns(OrdersResponse)]
     Orders {
         asset_infos: [AssetInfo; 2],
@@ -153,6 +449,17 @@ pub enum QueryMsg {
         start_after: Option<u64>,
         limit: Option<u32>,
         order_by: Option<i32>, // convert OrderBy to i32
+        /// next_cursor of the previous page, takes precedence over start_after and order_by
+        cursor: Option<String>,
+    },
+    /// orders of the price level the price rounds to at the price precision of the order book,
+    /// so the price does not need to match the stored price exactly
+    #[returns(PriceLevelResponse)]
+    OrdersAtPrice {
+        asset_infos: [AssetInfo; 2],
+        price: Decimal,
+        direction: OrderDirection,
+        limit: Option<u32>,
     },
     #[returns(TickResponse)]
     Tick {
@@ -169,10 +476,67 @@ pub enum QueryMsg {
         limit: Option<u32>,
         order_by: Option<i32>, // convert OrderBy to i32
     },
+    /// Raw keys of the tick index that can not be decoded, these ticks are skipped when matching and querying
+    #[returns(MalformedTickKeysResponse)]
+    MalformedTickKeys {
+        asset_infos: [AssetInfo; 2],
+        direction: OrderDirection,
+        limit: Option<u32>,
+    },
     #[returns(LastOrderIdResponse)]
     LastOrderId {},
+    /// Ordering rules and parameters of ExecuteOrderBookPair, so external simulators can replicate the matching
+    #[returns(MatchingRulesResponse)]
+    MatchingRules {},
+    /// Latest order id and the orders created by direction in an order book, tracked since this query was added
+    #[returns(PairSequenceResponse)]
+    PairSequence { asset_infos: [AssetInfo; 2] },
     #[returns(OrderBookMatchableResponse)]
     OrderBookMatchable { asset_infos: [AssetInfo; 2] },
+    #[returns(ExportOrdersResponse)]
+    ExportOrders {
+        asset_infos: [AssetInfo; 2],
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    #[returns(OrderPositionResponse)]
+    OrderPosition {
+        asset_infos: [AssetInfo; 2],
+        order_id: u64,
+    },
+    /// Matched volume and collected fees of an order book, all time if period is None,
+    /// otherwise of the day period = block time / 86400
+    #[returns(PairStatsResponse)]
+    PairStats {
+        asset_infos: [AssetInfo; 2],
+        period: Option<u64>,
+    },
+    #[returns(MakerRebatesResponse)]
+    MakerRebates { address: Addr },
+    #[returns(EscrowResponse)]
+    Escrow { address: Addr },
+    #[returns(IncentivePointsResponse)]
+    IncentivePoints { address: Addr },
+    /// Remaining offer amount of the open orders in the asset, checked against the contract balance
+    #[returns(TotalEscrowResponse)]
+    TotalEscrow { asset_info: AssetInfo },
+    /// Public key and next nonce of the order permits of a bidder
+    #[returns(OrderSignerResponse)]
+    OrderSigner { bidder: Addr },
+    /// OHLCV candles of the order book with start time in [start, end), only the latest candles are kept
+    #[returns(CandlesResponse)]
+    Candles {
+        asset_infos: [AssetInfo; 2],
+        interval: CandleInterval,
+        start: Option<u64>,
+        end: Option<u64>,
+    },
+    /// Addresses granted roles with their roles, the admin holds every role and is not listed
+    #[returns(RolesResponse)]
+    Roles {
+        start_after: Option<Addr>,
+        limit: Option<u32>,
+    },
 }
 
 #[cw_serde]
@@ -182,6 +546,14 @@ pub struct ContractInfoResponse {
 
     // admin can update the parameter, may be multisig
     pub admin: Addr,
+    pub guardian: Option<Addr>,
+    pub paused: bool,
+    pub maker_rebate_rate: Option<Decimal>,
+    pub event_mode: EventMode,
+    pub rewarder: Option<Addr>,
+    pub referral: Option<Addr>,
+    pub oracle: Option<Addr>,
+    pub converter: Option<Addr>,
 }
 
 #[cw_serde]
@@ -194,6 +566,121 @@ pub struct OrderResponse {
     pub ask_asset: Asset,
     pub filled_offer_amount: Uint128,
     pub filled_ask_amount: Uint128,
+    pub client_id: Option<String>,
+}
+
+#[cw_serde]
+pub struct MatchOrdersDryRunResponse {
+    pub total_matched_orders: u64,
+    // orders with filled amounts and status after matching
+    pub orders: Vec<OrderResponse>,
+}
+
+/// OrderRecord is the raw order with its fill state, used to migrate order books
+#[cw_serde]
+pub struct OrderRecord {
+    pub order_id: u64,
+    pub status: OrderStatus,
+    pub direction: OrderDirection,
+    pub bidder_addr: Addr,
+    pub offer_amount: Uint128,
+    pub ask_amount: Uint128,
+    pub filled_offer_amount: Uint128,
+    pub filled_ask_amount: Uint128,
+}
+
+#[cw_serde]
+pub struct ExportOrdersResponse {
+    pub orders: Vec<OrderRecord>,
+}
+
+/// amounts are remaining offer amounts of the orders on the same side, which are matched before the order
+#[cw_serde]
+pub struct OrderPositionResponse {
+    pub order_id: u64,
+    pub direction: OrderDirection,
+    pub price: Decimal,
+    // rank at its price level, starting from 1
+    pub position: u64,
+    pub amount_ahead_at_price: Uint128,
+    // including orders at better prices
+    pub total_amount_ahead: Uint128,
+}
+
+/// fees are valued in quote asset at the matched price
+#[cw_serde]
+pub struct PairStatsResponse {
+    pub base_coin_info: AssetInfo,
+    pub quote_coin_info: AssetInfo,
+    pub period: Option<u64>,
+    pub base_volume: Uint128,
+    pub quote_volume: Uint128,
+    pub buy_commission: Uint128,
+    pub sell_commission: Uint128,
+    pub relayer_fee: Uint128,
+    pub maker_rebate: Uint128,
+    pub total_trades: u64,
+}
+
+#[cw_serde]
+pub struct MakerRebatesResponse {
+    pub rebates: Vec<Asset>,
+}
+
+#[cw_serde]
+pub struct IncentivePointsResponse {
+    pub points: Uint128,
+}
+
+#[cw_serde]
+pub struct EscrowResponse {
+    pub assets: Vec<Asset>,
+}
+
+#[cw_serde]
+pub struct TotalEscrowResponse {
+    pub asset_info: AssetInfo,
+    pub total_escrow: Uint128,
+    pub balance: Uint128,
+    // non zero when the balance no longer covers the open orders, such as a leak from matching
+    pub deficit: Uint128,
+}
+
+#[cw_serde]
+pub struct RoleHolder {
+    pub address: Addr,
+    pub roles: Vec<Role>,
+}
+
+#[cw_serde]
+pub struct RolesResponse {
+    pub holders: Vec<RoleHolder>,
+}
+
+#[cw_serde]
+pub struct OrderSignerResponse {
+    pub pubkey: Option<Binary>,
+    pub nonce: u64,
+}
+
+/// prices are the matched prices, the first and last matched in the interval are open and close
+#[cw_serde]
+pub struct CandleResponse {
+    pub start_time: u64,
+    pub open: Decimal,
+    pub high: Decimal,
+    pub low: Decimal,
+    pub close: Decimal,
+    pub base_volume: Uint128,
+    pub quote_volume: Uint128,
+}
+
+#[cw_serde]
+pub struct CandlesResponse {
+    pub base_coin_info: AssetInfo,
+    pub quote_coin_info: AssetInfo,
+    pub interval: CandleInterval,
+    pub candles: Vec<CandleResponse>,
 }
 
 #[cw_serde]
@@ -202,6 +689,36 @@ pub struct OrderBookResponse {
     pub quote_coin_info: AssetInfo,
     pub spread: Option<Decimal>,
     pub min_quote_coin_amount: Uint128,
+    pub min_quote_notional: Option<Uint128>,
+    pub min_fill_ratio: Option<Decimal>,
+    pub min_fill_base: Option<Uint128>,
+    pub min_order_lifetime_seconds: Option<u64>,
+    pub cancel_fee_rate: Option<Decimal>,
+    pub free_cancel_blocks: Option<u64>,
+    pub max_executions_per_block: Option<u32>,
+    pub price_reference: Option<Addr>,
+    pub max_price_deviation: Option<Decimal>,
+    pub relayer_fee_rate: Option<Decimal>,
+    pub min_relayer_fee: Option<Uint128>,
+    pub max_relayer_fee: Option<Uint128>,
+    pub fee_usd_denom: Option<String>,
+    pub max_orders_per_user: Option<u32>,
+    pub incentive_price_band: Option<Decimal>,
+    pub price_precision: Option<u32>,
+    pub base_decimals: Option<u8>,
+    pub quote_decimals: Option<u8>,
+    pub operator: Option<Addr>,
+    pub paused: bool,
+    pub auction_interval_seconds: Option<u64>,
+    pub last_auction_time: Option<u64>,
+    pub delisting_time: Option<u64>,
+    /// block height and sender of the last ExecuteOrderBookPair
+    pub last_executed_height: Option<u64>,
+    pub last_executed_by: Option<Addr>,
+    /// ExecuteOrderBookPair calls in the block of last_executed_height
+    pub executions_in_block: u32,
+    /// total orders matched by ExecuteOrderBookPair
+    pub orders_matched_total: u64,
 }
 
 #[cw_serde]
@@ -212,12 +729,25 @@ pub struct OrderBooksResponse {
 #[cw_serde]
 pub struct OrdersResponse {
     pub orders: Vec<OrderResponse>,
+    pub next_cursor: Option<String>,
 }
 
 #[cw_serde]
 pub struct TickResponse {
     pub price: Decimal,
     pub total_orders: u64,
+    // remaining base amount of the orders at this price
+    pub total_base_amount: Uint128,
+}
+
+#[cw_serde]
+pub struct PriceLevelResponse {
+    // price rounded to the price precision of the order book
+    pub price: Decimal,
+    pub total_orders: u64,
+    pub total_base_amount: Uint128,
+    // orders of the ticks rounding to the price, first in first out at each tick
+    pub orders: Vec<OrderResponse>,
 }
 
 #[cw_serde]
@@ -225,16 +755,53 @@ pub struct TicksResponse {
     pub ticks: Vec<TickResponse>,
 }
 
+#[cw_serde]
+pub struct MalformedTickKeysResponse {
+    pub keys: Vec<Binary>,
+}
+
 #[cw_serde]
 pub struct LastOrderIdResponse {
     pub last_order_id: u64,
 }
 
+#[cw_serde]
+pub struct MatchingRulesResponse {
+    /// buy price levels from the highest price, sell price levels from the lowest price
+    pub price_priority: String,
+    /// orders of a price level from the lowest order id, which is the earliest submitted
+    pub time_priority: String,
+    /// continuous matching fills at the buy price, call markets at the clearing price
+    pub match_price: String,
+    /// a price level whose remaining volume is at most this amount is considered filled
+    pub dust_volume: Uint128,
+    /// default and max number of price levels of each side matched by one call
+    pub default_limit: u32,
+    pub max_limit: u32,
+    pub commission_rate: String,
+    /// flat relayer fee of the order books without a relayer fee rate
+    pub relayer_fee: Uint128,
+}
+
+#[cw_serde]
+pub struct PairSequenceResponse {
+    pub base_coin_info: AssetInfo,
+    pub quote_coin_info: AssetInfo,
+    /// None before the first order created in the order book
+    pub last_order_id: Option<u64>,
+    pub buy_order_count: u64,
+    pub sell_order_count: u64,
+}
+
 #[cw_serde]
 pub struct OrderBookMatchableResponse {
     pub is_matchable: bool,
 }
 
-/// We currently take no arguments for migrations
 #[cw_serde]
-pub struct MigrateMsg {}
+pub struct MigrateMsg {
+    /// enable or disable ImportOrders while migrating order books
+    pub allow_import_orders: Option<bool>,
+    /// set the registry providing the decimals of the assets of new order books
+    pub registry: Option<Addr>,
+}
@@ -1,4 +1,4 @@
-use cosmwasm_std::{Decimal, StdError, StdResult, Uint128};
+use cosmwasm_std::{Decimal, Decimal256, Isqrt, StdError, StdResult, Uint128, Uint256, Uint512};
 
 pub trait Converter128 {
     fn checked_div_decimal(&self, denominator: Decimal) -> StdResult<Self>
@@ -14,3 +14,139 @@ impl Converter128 for Uint128 {
             .map(|coeff| self.clone() * coeff)
     }
 }
+
+/// 256 bit arithmetic used by the invariant math of the pools, products are computed
+/// in 512 bits so only results over Uint256::MAX overflow
+pub trait Converter256 {
+    /// self * rhs rounded down
+    fn checked_mul_floor(&self, rhs: Decimal256) -> StdResult<Uint256>;
+
+    /// self / rhs rounded up
+    fn checked_div_ceil(&self, rhs: Decimal256) -> StdResult<Uint256>;
+
+    /// largest integer whose square is not over self
+    fn sqrt(&self) -> Uint256;
+}
+
+impl Converter256 for Uint256 {
+    fn checked_mul_floor(&self, rhs: Decimal256) -> StdResult<Uint256> {
+        let product = Uint512::from(*self).checked_mul(Uint512::from(rhs.atomics()))?;
+        to_uint256(product / Uint512::from(Decimal256::one().atomics()))
+    }
+
+    fn checked_div_ceil(&self, rhs: Decimal256) -> StdResult<Uint256> {
+        let numerator =
+            Uint512::from(*self).checked_mul(Uint512::from(Decimal256::one().atomics()))?;
+        let denominator = Uint512::from(rhs.atomics());
+        let quotient = numerator.checked_div(denominator)?;
+        if numerator.checked_rem(denominator)?.is_zero() {
+            to_uint256(quotient)
+        } else {
+            to_uint256(quotient.checked_add(Uint512::one())?)
+        }
+    }
+
+    fn sqrt(&self) -> Uint256 {
+        self.isqrt()
+    }
+}
+
+fn to_uint256(value: Uint512) -> StdResult<Uint256> {
+    Uint256::try_from(value).map_err(|err| StdError::generic_err(err.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // deterministic pseudo random values covering small and large magnitudes
+    fn samples() -> Vec<u128> {
+        let mut seed = 0x2545_f491_4f6c_dd1du128;
+        (0..200)
+            .map(|i| {
+                seed = seed
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                seed >> (i % 120)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn mul_floor_and_div_ceil() {
+        let value = Uint256::from(100u128);
+        let rate = Decimal256::percent(33);
+        assert_eq!(
+            value.checked_mul_floor(rate).unwrap(),
+            Uint256::from(33u128)
+        );
+        assert_eq!(
+            value.checked_div_ceil(rate).unwrap(),
+            Uint256::from(304u128)
+        );
+        assert_eq!(
+            Uint256::from(99u128)
+                .checked_div_ceil(Decimal256::percent(33))
+                .unwrap(),
+            Uint256::from(300u128)
+        );
+
+        // the product is computed in 512 bits
+        assert_eq!(
+            Uint256::MAX
+                .checked_mul_floor(Decimal256::percent(50))
+                .unwrap(),
+            Uint256::MAX / Uint256::from(2u128)
+        );
+        assert!(Uint256::MAX
+            .checked_mul_floor(Decimal256::percent(200))
+            .is_err());
+        assert!(Uint256::MAX
+            .checked_div_ceil(Decimal256::percent(50))
+            .is_err());
+        assert!(value.checked_div_ceil(Decimal256::zero()).is_err());
+    }
+
+    #[test]
+    fn mul_floor_and_div_ceil_properties() {
+        let samples = samples();
+        for (value, rate) in samples.iter().zip(samples.iter().rev()) {
+            let value = Uint256::from(*value);
+            let rate = Decimal256::new(Uint256::from(*rate % 10u128.pow(20) + 1));
+
+            // floor(value * rate) <= value * rate < floor(value * rate) + 1
+            let floor = value.checked_mul_floor(rate).unwrap();
+            let exact = Uint512::from(value) * Uint512::from(rate.atomics());
+            let one = Uint512::from(Decimal256::one().atomics());
+            assert!(Uint512::from(floor) * one <= exact);
+            assert!(exact < (Uint512::from(floor) + Uint512::one()) * one);
+
+            // ceil(value / rate) * rate >= value > (ceil(value / rate) - 1) * rate
+            let ceil = value.checked_div_ceil(rate).unwrap();
+            let value = Uint512::from(value) * one;
+            assert!(Uint512::from(ceil) * Uint512::from(rate.atomics()) >= value);
+            if !ceil.is_zero() {
+                assert!(
+                    (Uint512::from(ceil) - Uint512::one()) * Uint512::from(rate.atomics()) < value
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn sqrt_properties() {
+        assert_eq!(Uint256::zero().sqrt(), Uint256::zero());
+        assert_eq!(Uint256::from(1u128).sqrt(), Uint256::from(1u128));
+        assert_eq!(Uint256::from(15u128).sqrt(), Uint256::from(3u128));
+        assert_eq!(Uint256::from(16u128).sqrt(), Uint256::from(4u128));
+        assert_eq!(Uint256::MAX.sqrt(), Uint256::from(u128::MAX));
+
+        for sample in samples() {
+            // sqrt^2 <= value < (sqrt + 1)^2, squares are compared in 512 bits
+            let value = Uint256::from(sample) * Uint256::from(sample >> 64) + Uint256::from(sample);
+            let root = Uint512::from(value.sqrt());
+            assert!(root * root <= Uint512::from(value));
+            assert!((root + Uint512::one()) * (root + Uint512::one()) > Uint512::from(value));
+        }
+    }
+}
@@ -36,6 +36,27 @@ pub enum ExecuteMsg {
     UpdateTaxRate {
         rate: Decimal,
     },
+    // disable tax on chains without tax, then no tax is deducted from transfers
+    UpdateTaxEnabled {
+        enabled: bool,
+    },
+    // admin sets the only address allowed to feed the price of a symbol
+    RegisterFeeder {
+        symbol: String,
+        feeder: Addr,
+    },
+    DeregisterFeeder {
+        symbol: String,
+    },
+    // admin sets the pair of the denom with orai used when the denom has no exchange rate, none removes it
+    UpdatePricePair {
+        denom: String,
+        pair_addr: Option<Addr>,
+    },
+    // push (symbol, exchange rate to orai, timestamp in seconds) from an external feed like Band or Slinky
+    FeedPrices {
+        prices: Vec<(String, Decimal, u64)>,
+    },
 }
 
 /// QueryMsg is defines available query datas
@@ -67,6 +88,9 @@ pub enum OracleTreasuryQuery {
     TaxRate {},
     #[returns(TaxCapResponse)]
     TaxCap { denom: String },
+    // tax rate and tax cap of the denom in one query
+    #[returns(TaxInfo)]
+    TaxInfo { denom: String },
 }
 
 #[cw_serde]
@@ -82,6 +106,13 @@ pub enum OracleExchangeQuery {
         base_denom: Option<String>,
         quote_denoms: Vec<String>,
     },
+    // convert an amount of a denom to another through their exchange rates to orai
+    #[returns(ConvertAmountResponse)]
+    ConvertAmount {
+        from: String,
+        to: String,
+        amount: Uint128,
+    },
 }
 
 #[cw_serde]
@@ -91,6 +122,8 @@ pub enum OracleContractQuery {
     ContractInfo {},
     #[returns(cosmwasm_std::Coin)]
     RewardPool { denom: String },
+    #[returns(FeederResponse)]
+    Feeder { symbol: String },
 }
 
 /// TaxRateResponse is data format returned from TreasuryRequest::TaxRate query
@@ -105,6 +138,27 @@ pub struct TaxCapResponse {
     pub cap: Uint128,
 }
 
+/// TaxInfo is data format returned from TreasuryRequest::TaxInfo query
+#[cw_serde]
+pub struct TaxInfo {
+    pub rate: Decimal,
+    pub cap: Uint128,
+}
+
+impl TaxInfo {
+    /// compute the tax deducted from the amount, zero rate or cap means no tax
+    pub fn compute_tax(&self, amount: Uint128) -> StdResult<Uint128> {
+        if self.rate.is_zero() || self.cap.is_zero() {
+            return Ok(Uint128::zero());
+        }
+
+        Ok(std::cmp::min(
+            amount.checked_sub(amount * (Decimal::one() / (self.rate + Decimal::one())))?,
+            self.cap,
+        ))
+    }
+}
+
 /// ExchangeRateItem is data format returned from OracleRequest::ExchangeRates query
 #[cw_serde]
 pub struct ExchangeRateItem {
@@ -126,6 +180,31 @@ pub struct ExchangeRateResponse {
     pub item: ExchangeRateItem,
 }
 
+/// ConvertRateItem is the exchange rate of a denom to orai used by a conversion,
+/// pair_addr is set when the rate is the spot price of the price pair
+#[cw_serde]
+pub struct ConvertRateItem {
+    pub denom: String,
+    pub exchange_rate: Decimal,
+    pub pair_addr: Option<Addr>,
+}
+
+/// ConvertAmountResponse is data format returned from OracleExchangeQuery::ConvertAmount query
+#[cw_serde]
+pub struct ConvertAmountResponse {
+    pub amount: Uint128,
+    pub path: Vec<ConvertRateItem>,
+}
+
+/// FeederResponse is data format returned from OracleContractQuery::Feeder query
+#[cw_serde]
+pub struct FeederResponse {
+    pub symbol: String,
+    pub feeder: Addr,
+    // block time in seconds of the last fed price, none before the first feed
+    pub last_updated: Option<u64>,
+}
+
 /// ContractInfo is data format stored
 #[cw_serde]
 pub struct ContractInfo {
@@ -215,6 +294,18 @@ impl OracleContract {
         self.query(querier, request)
     }
 
+    pub fn query_tax_info<T: Into<String>>(
+        &self,
+        querier: &QuerierWrapper,
+        denom: T,
+    ) -> StdResult<TaxInfo> {
+        let request = QueryMsg::Treasury(OracleTreasuryQuery::TaxInfo {
+            denom: denom.into(),
+        });
+
+        self.query(querier, request)
+    }
+
     // this is for CEX
     pub fn query_exchange_rate<T: Into<String>>(
         &self,
@@ -244,6 +335,22 @@ impl OracleContract {
         self.query(querier, request)
     }
 
+    pub fn query_convert_amount<T: Into<String>>(
+        &self,
+        querier: &QuerierWrapper,
+        from: T,
+        to: T,
+        amount: Uint128,
+    ) -> StdResult<ConvertAmountResponse> {
+        let request = QueryMsg::Exchange(OracleExchangeQuery::ConvertAmount {
+            from: from.into(),
+            to: to.into(),
+            amount,
+        });
+
+        self.query(querier, request)
+    }
+
     pub fn query_contract_info<T: Into<String>>(
         &self,
         querier: &QuerierWrapper,
@@ -252,6 +359,18 @@ impl OracleContract {
 
         self.query(querier, request)
     }
+
+    pub fn query_feeder<T: Into<String>>(
+        &self,
+        querier: &QuerierWrapper,
+        symbol: T,
+    ) -> StdResult<FeederResponse> {
+        let request = QueryMsg::Contract(OracleContractQuery::Feeder {
+            symbol: symbol.into(),
+        });
+
+        self.query(querier, request)
+    }
 }
 
 /// This is a respresentation of OracleContract for storage.
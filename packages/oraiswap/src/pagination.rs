@@ -0,0 +1,83 @@
+use cosmwasm_std::{Binary, Order, StdError, StdResult};
+
+/// Position of a list query: the storage key of the last returned item and the iteration order.
+/// It is passed to clients as an opaque base64 string, so they do not depend on the key layout
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cursor {
+    pub key: Vec<u8>,
+    pub order: Order,
+}
+
+impl Cursor {
+    pub fn new(key: Vec<u8>, order: Order) -> Self {
+        Self { key, order }
+    }
+
+    pub fn encode(&self) -> String {
+        let mut data = vec![match self.order {
+            Order::Ascending => 1u8,
+            Order::Descending => 2u8,
+        }];
+        data.extend_from_slice(&self.key);
+        Binary(data).to_base64()
+    }
+
+    pub fn decode(cursor: &str) -> StdResult<Self> {
+        let data = Binary::from_base64(cursor)?;
+        match data.split_first() {
+            Some((1, key)) => Ok(Self::new(key.to_vec(), Order::Ascending)),
+            Some((2, key)) => Ok(Self::new(key.to_vec(), Order::Descending)),
+            _ => Err(StdError::generic_err("Invalid cursor")),
+        }
+    }
+
+    /// range bounds continuing after the cursor key, start is inclusive and end is exclusive
+    pub fn range_bounds(&self) -> (Option<Vec<u8>>, Option<Vec<u8>>) {
+        match self.order {
+            // the smallest key greater than the cursor key
+            Order::Ascending => (Some([self.key.as_slice(), &[0]].concat()), None),
+            Order::Descending => (None, Some(self.key.clone())),
+        }
+    }
+}
+
+/// Cursor of the next page from the key of the last item, none when the page is not full
+pub fn next_cursor(
+    last_key: Option<Vec<u8>>,
+    order: Order,
+    page_len: usize,
+    limit: usize,
+) -> Option<String> {
+    if page_len < limit {
+        return None;
+    }
+    last_key.map(|key| Cursor::new(key, order).encode())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cursor_roundtrip() {
+        let cursor = Cursor::new(vec![0, 1, 255], Order::Descending);
+        assert_eq!(Cursor::decode(&cursor.encode()).unwrap(), cursor);
+        assert_eq!(cursor.range_bounds(), (None, Some(vec![0, 1, 255])));
+
+        let cursor = Cursor::new(vec![0, 1, 255], Order::Ascending);
+        assert_eq!(Cursor::decode(&cursor.encode()).unwrap(), cursor);
+        assert_eq!(cursor.range_bounds(), (Some(vec![0, 1, 255, 0]), None));
+
+        assert!(Cursor::decode("").is_err());
+        assert!(Cursor::decode(&Binary(vec![3, 1]).to_base64()).is_err());
+    }
+
+    #[test]
+    fn next_cursor_of_full_page() {
+        assert_eq!(next_cursor(Some(vec![1]), Order::Ascending, 1, 2), None);
+        assert_eq!(
+            next_cursor(Some(vec![1]), Order::Ascending, 2, 2),
+            Some(Cursor::new(vec![1], Order::Ascending).encode())
+        );
+    }
+}
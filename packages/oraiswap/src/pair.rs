@@ -9,11 +9,38 @@ use cosmwasm_std::{Decimal256, StdError, Uint256};
 
 use cosmwasm_std::{Addr, Decimal, Uint128};
 use cw20::Cw20ReceiveMsg;
+use cw20_base::msg::InstantiateMarketingInfo;
 
 /// Default commission rate == 0.3%
 /// in the future need to update ?
 pub const DEFAULT_COMMISSION_RATE: &str = "0.003";
 
+pub const DEFAULT_LP_TOKEN_NAME: &str = "oraiswap liquidity token";
+pub const DEFAULT_LP_TOKEN_SYMBOL: &str = "uLP";
+
+/// Optional metadata of the liquidity token, so explorers and wallets can distinguish LP tokens
+#[cw_serde]
+#[derive(Default)]
+pub struct LpTokenInfo {
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub marketing: Option<InstantiateMarketingInfo>,
+}
+
+/// LpTokenType is how the LP shares of a pair are represented
+#[cw_serde]
+#[derive(Copy, Default)]
+pub enum LpTokenType {
+    /// a cw20 contract instantiated by the pair
+    #[default]
+    Cw20,
+    /// a native denom created by the pair through the tokenfactory module
+    TokenFactory,
+}
+
+/// subdenom of the tokenfactory LP denom, factory/{pair}/lp
+pub const LP_SUBDENOM: &str = "lp";
+
 #[cw_serde]
 pub struct InstantiateMsg {
     /// Asset infos
@@ -25,6 +52,18 @@ pub struct InstantiateMsg {
     pub oracle_addr: Addr,
 
     pub commission_rate: Option<String>,
+
+    /// Liquidity token metadata, default is "oraiswap liquidity token"/"uLP"
+    pub lp_token_info: Option<LpTokenInfo>,
+
+    /// Guardian can pause swaps and providing liquidity
+    pub guardian: Option<Addr>,
+
+    /// Staking contract that receives LP tokens of auto staked liquidity
+    pub staking_contract: Option<Addr>,
+
+    /// cw20 by default, lp_token_info only applies to cw20 liquidity tokens
+    pub lp_token_type: Option<LpTokenType>,
 }
 
 #[cw_serde]
@@ -35,6 +74,14 @@ pub enum ExecuteMsg {
         assets: [Asset; 2],
         slippage_tolerance: Option<Decimal>,
         receiver: Option<Addr>,
+        /// bond the minted LP tokens to the staking contract for the receiver
+        auto_stake: Option<bool>,
+    },
+    /// WithdrawLiquidity burns the tokenfactory LP shares sent as funds, cw20 LP tokens are sent with the WithdrawLiquidity hook
+    WithdrawLiquidity {},
+    /// Donate assets to the pool reserves without minting LP shares, increasing the value of all existing shares
+    Donate {
+        assets: [Asset; 2],
     },
     /// Swap an offer asset to the other
     Swap {
@@ -42,6 +89,28 @@ pub enum ExecuteMsg {
         belief_price: Option<Decimal>,
         max_spread: Option<Decimal>,
         to: Option<Addr>,
+        /// legacy asset registered in the converter to convert the return asset into
+        #[serde(skip_serializing_if = "Option::is_none")]
+        return_asset_info: Option<AssetInfo>,
+    },
+    /// Swap the other asset for an exact ask amount, the unused offer is refunded
+    SwapExactOut {
+        ask_asset: Asset,
+        max_offer_amount: Uint128,
+        to: Option<Addr>,
+    },
+    /// Pause swaps and providing liquidity, only guardian
+    Pause {},
+    /// Unpause swaps and providing liquidity, only guardian
+    Unpause {},
+    /// Limit the offer amount of a swap to a fraction of the offer pool in basis points,
+    /// None removes the limit, only guardian
+    UpdateMaxTradeBps {
+        max_trade_bps: Option<u16>,
+    },
+    /// Transfer the native balances of denoms other than the pool assets to the receiver, only guardian
+    Skim {
+        to: Option<Addr>,
     },
 }
 
@@ -62,6 +131,15 @@ pub enum Cw20HookMsg {
         belief_price: Option<Decimal>,
         max_spread: Option<Decimal>,
         to: Option<String>,
+        /// legacy asset registered in the converter to convert the return asset into
+        #[serde(skip_serializing_if = "Option::is_none")]
+        return_asset_info: Option<AssetInfo>,
+    },
+    /// Buy an exact amount of the other asset, the unused offer is refunded
+    SwapExactOut {
+        ask_asset: Asset,
+        max_offer_amount: Uint128,
+        to: Option<String>,
     },
     WithdrawLiquidity {},
 }
@@ -77,6 +155,26 @@ pub enum QueryMsg {
     Simulation { offer_asset: Asset },
     #[returns(ReverseSimulationResponse)]
     ReverseSimulation { ask_asset: Asset },
+    #[returns(SpotPriceResponse)]
+    SpotPrice {
+        offer_asset_info: AssetInfo,
+        ask_asset_info: AssetInfo,
+    },
+    #[returns(MaxTradeBpsResponse)]
+    MaxTradeBps {},
+    /// the router whose swaps are charged the discounted commission rate
+    #[returns(RouterCommissionResponse)]
+    RouterCommission {},
+    /// pool assets backing an amount of LP token, with the pool price at the last provide of the address
+    #[returns(LpValueResponse)]
+    LpValue {
+        amount: Uint128,
+        address: Option<String>,
+    },
+    /// Simulation of the swap minus the tax deducted when the return asset is sent,
+    /// which is capped by the oracle tax cap of the denom
+    #[returns(NetReceiveEstimateResponse)]
+    NetReceiveEstimate { offer_asset: Asset },
 }
 
 // We define a custom struct for each query response
@@ -99,6 +197,14 @@ pub struct SimulationResponse {
     pub commission_amount: Uint128,
 }
 
+/// NetReceiveEstimateResponse returns the amount the receiver gets after the transfer tax
+#[cw_serde]
+pub struct NetReceiveEstimateResponse {
+    pub return_amount: Uint128,
+    pub tax_amount: Uint128,
+    pub net_return_amount: Uint128,
+}
+
 /// ReverseSimulationResponse returns reverse swap simulation response
 #[cw_serde]
 pub struct ReverseSimulationResponse {
@@ -107,9 +213,52 @@ pub struct ReverseSimulationResponse {
     pub commission_amount: Uint128,
 }
 
-/// We currently take no arguments for migrations
+/// SpotPriceResponse returns the ask amount of one offer unit, normalized for asset decimals
 #[cw_serde]
-pub struct MigrateMsg {}
+pub struct SpotPriceResponse {
+    pub price: Decimal256,
+}
+
+/// LpValueResponse returns the assets withdrawn for the LP amount, prices are asset 1 per asset 0 in atomics
+/// so wallets can estimate the impermanent loss since the last provide
+#[cw_serde]
+pub struct LpValueResponse {
+    pub assets: [Asset; 2],
+    pub price: Decimal256,
+    /// None when the address has not provided liquidity since the price was tracked
+    pub last_provide_price: Option<Decimal256>,
+}
+
+#[cw_serde]
+pub struct MaxTradeBpsResponse {
+    pub max_trade_bps: Option<u16>,
+}
+
+/// RouterCommissionResponse returns the commission rate of swaps sent by the router,
+/// the pair commission rate when no router is set
+#[cw_serde]
+pub struct RouterCommissionResponse {
+    pub router: Option<Addr>,
+    pub commission_rate: String,
+}
+
+#[cw_serde]
+pub struct MigrateMsg {
+    /// set the guardian of pairs created before it was introduced
+    pub guardian: Option<Addr>,
+    /// set the staking contract of pairs created before auto staking was introduced
+    pub staking_contract: Option<Addr>,
+    /// limit the offer amount of a swap to a fraction of the offer pool in basis points
+    pub max_trade_bps: Option<u16>,
+    /// set the converter that converts legacy assets offered or asked in swaps
+    pub converter: Option<Addr>,
+    /// set the registry providing the decimals of the pool assets
+    pub registry: Option<Addr>,
+    /// set the router whose swaps are charged the router commission rate
+    pub router: Option<Addr>,
+    /// discounted commission rate of swaps sent by the router, at most the pair commission rate
+    pub router_commission_rate: Option<String>,
+}
 
 pub fn compute_swap(
     offer_pool: Uint128,
@@ -200,3 +349,64 @@ pub fn compute_offer_amount(
             .map_err(|err| StdError::from(err))?,
     ))
 }
+
+/// The product of the pools must not decrease after a swap, the commission kept by the pool increases it.
+/// One unit of each asset is tolerated for the rounding of the computed amounts
+pub fn assert_invariant(
+    offer_pool: Uint128,
+    ask_pool: Uint128,
+    offer_amount: Uint128,
+    return_amount: Uint128,
+) -> Result<(), ContractError> {
+    let k_before = Uint256::from(offer_pool).checked_mul(ask_pool.into())?;
+    let offer_pool_after = Uint256::from(offer_pool.checked_add(offer_amount)?) + Uint256::one();
+    let ask_pool_after = Uint256::from(ask_pool.checked_sub(return_amount)?) + Uint256::one();
+    let k_after = offer_pool_after.checked_mul(ask_pool_after)?;
+
+    if k_after < k_before {
+        return Err(ContractError::InvariantViolated {});
+    }
+
+    Ok(())
+}
+
+/// If `belief_price` and `max_spread` both are given,
+/// we compute new spread else we just use oraiswap
+/// spread to check `max_spread`
+pub fn assert_max_spread(
+    belief_price: Option<Decimal>,
+    max_spread: Option<Decimal>,
+    offer_amount: Uint128,
+    return_amount: Uint128,
+    spread_amount: Uint128,
+) -> Result<(), ContractError> {
+    let offer_amount: Uint256 = offer_amount.into();
+    let return_amount: Uint256 = return_amount.into();
+    let spread_amount: Uint256 = spread_amount.into();
+
+    if let (Some(max_spread), Some(belief_price)) = (max_spread, belief_price) {
+        let belief_price: Decimal256 = belief_price.into();
+        let max_spread: Decimal256 = max_spread.into();
+        // mul with belief_price inv
+        let expected_return = offer_amount * (Decimal256::one() / belief_price);
+
+        let spread_amount = if expected_return > return_amount {
+            expected_return - return_amount
+        } else {
+            Uint256::zero()
+        };
+
+        if return_amount < expected_return
+            && Decimal256::from_ratio(spread_amount, expected_return) > max_spread
+        {
+            return Err(ContractError::MaxSpreadAssertion {});
+        }
+    } else if let Some(max_spread) = max_spread {
+        let max_spread: Decimal256 = max_spread.into();
+        if Decimal256::from_ratio(spread_amount, return_amount + spread_amount) > max_spread {
+            return Err(ContractError::MaxSpreadAssertion {});
+        }
+    }
+
+    Ok(())
+}
@@ -1,9 +1,13 @@
-use crate::asset::{Asset, AssetInfo, PairInfo};
+use crate::asset::{Asset, AssetInfo, PairInfo, NATIVE_DECIMALS};
 use crate::factory::{ConfigResponse, QueryMsg as FactoryQueryMsg};
 use crate::pair::{
-    PairResponse, QueryMsg as PairQueryMsg, ReverseSimulationResponse, SimulationResponse,
+    NetReceiveEstimateResponse, PairResponse, PoolResponse, QueryMsg as PairQueryMsg,
+    ReverseSimulationResponse, SimulationResponse,
 };
+use crate::registry::{AssetMetadata, ContractKey, ContractResponse, QueryMsg as RegistryQueryMsg};
+use crate::weighted_pair::WeightedPairInfo;
 
+use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, QuerierWrapper, StdResult, Uint128};
 use cw20::{BalanceResponse as Cw20BalanceResponse, Cw20QueryMsg, TokenInfoResponse};
 
@@ -36,6 +40,61 @@ pub fn query_supply(querier: &QuerierWrapper, contract_addr: Addr) -> StdResult<
     query_token_info(querier, contract_addr).map(|token_info| token_info.total_supply)
 }
 
+/// balances of an address, in the order of the queried asset infos
+#[cw_serde]
+pub struct AddressBalances {
+    pub address: Addr,
+    pub balances: Vec<Asset>,
+}
+
+#[cw_serde]
+pub struct ContractTokenInfo {
+    pub contract_addr: Addr,
+    pub token_info: TokenInfoResponse,
+}
+
+/// balances of every address in every asset, in the order of the addresses
+pub fn query_balances(
+    querier: &QuerierWrapper,
+    addrs: &[Addr],
+    asset_infos: &[AssetInfo],
+) -> StdResult<Vec<AddressBalances>> {
+    addrs
+        .iter()
+        .map(|addr| {
+            let balances = asset_infos
+                .iter()
+                .map(|asset_info| {
+                    Ok(Asset {
+                        info: asset_info.clone(),
+                        amount: asset_info.query_pool(querier, addr.clone())?,
+                    })
+                })
+                .collect::<StdResult<Vec<Asset>>>()?;
+            Ok(AddressBalances {
+                address: addr.clone(),
+                balances,
+            })
+        })
+        .collect()
+}
+
+/// token infos of the cw20 contracts, in the order of the contracts
+pub fn query_token_infos(
+    querier: &QuerierWrapper,
+    contracts: &[Addr],
+) -> StdResult<Vec<ContractTokenInfo>> {
+    contracts
+        .iter()
+        .map(|contract_addr| {
+            Ok(ContractTokenInfo {
+                contract_addr: contract_addr.clone(),
+                token_info: query_token_info(querier, contract_addr.clone())?,
+            })
+        })
+        .collect()
+}
+
 pub fn query_pair_info(
     querier: &QuerierWrapper,
     factory_addr: Addr,
@@ -49,6 +108,19 @@ pub fn query_pair_info(
     )
 }
 
+pub fn query_weighted_pair_info(
+    querier: &QuerierWrapper,
+    factory_addr: Addr,
+    asset_infos: &[AssetInfo],
+) -> StdResult<WeightedPairInfo> {
+    querier.query_wasm_smart(
+        factory_addr,
+        &FactoryQueryMsg::WeightedPair {
+            asset_infos: asset_infos.to_vec(),
+        },
+    )
+}
+
 pub fn query_pair_config(
     querier: &QuerierWrapper,
     factory_addr: Addr,
@@ -69,6 +141,19 @@ pub fn simulate(
     )
 }
 
+pub fn estimate_net_receive(
+    querier: &QuerierWrapper,
+    pair_addr: Addr,
+    offer_asset: &Asset,
+) -> StdResult<NetReceiveEstimateResponse> {
+    querier.query_wasm_smart(
+        pair_addr,
+        &PairQueryMsg::NetReceiveEstimate {
+            offer_asset: offer_asset.clone(),
+        },
+    )
+}
+
 pub fn reverse_simulate(
     querier: &QuerierWrapper,
     pair_addr: Addr,
@@ -90,6 +175,65 @@ pub fn query_pair_info_from_pair(
     Ok(res.info)
 }
 
+pub fn query_pool(querier: &QuerierWrapper, pair_contract: Addr) -> StdResult<PoolResponse> {
+    querier.query_wasm_smart(pair_contract, &PairQueryMsg::Pool {})
+}
+
+/// address of an oraiswap contract registered in the registry
+pub fn query_registry_contract(
+    querier: &QuerierWrapper,
+    registry_contract: Addr,
+    key: ContractKey,
+) -> StdResult<Addr> {
+    let res: ContractResponse =
+        querier.query_wasm_smart(registry_contract, &RegistryQueryMsg::Contract { key })?;
+    Ok(res.address)
+}
+
+/// metadata of the asset set in the registry, otherwise the decimals and symbol of the cw20 token,
+/// or the native default decimals with the denom as symbol
+pub fn query_asset_metadata(
+    querier: &QuerierWrapper,
+    registry_contract: Option<Addr>,
+    asset_info: &AssetInfo,
+) -> StdResult<AssetMetadata> {
+    if let Some(registry_contract) = registry_contract {
+        let res: StdResult<AssetMetadata> = querier.query_wasm_smart(
+            registry_contract,
+            &RegistryQueryMsg::AssetMetadata {
+                asset_info: asset_info.clone(),
+            },
+        );
+        if let Ok(metadata) = res {
+            return Ok(metadata);
+        }
+    }
+
+    match asset_info {
+        AssetInfo::Token { contract_addr } => {
+            let token_info = query_token_info(querier, contract_addr.clone())?;
+            Ok(AssetMetadata {
+                asset_info: asset_info.clone(),
+                decimals: token_info.decimals,
+                symbol: token_info.symbol,
+            })
+        }
+        AssetInfo::NativeToken { denom } => Ok(AssetMetadata {
+            asset_info: asset_info.clone(),
+            decimals: NATIVE_DECIMALS,
+            symbol: denom.clone(),
+        }),
+    }
+}
+
+pub fn query_asset_decimals(
+    querier: &QuerierWrapper,
+    registry_contract: Option<Addr>,
+    asset_info: &AssetInfo,
+) -> StdResult<u8> {
+    query_asset_metadata(querier, registry_contract, asset_info).map(|metadata| metadata.decimals)
+}
+
 // upper bound key by 1, for Order::Ascending
 pub fn calc_range_start(start_after: Option<Vec<u8>>) -> Option<Vec<u8>> {
     start_after.map(|mut input| {
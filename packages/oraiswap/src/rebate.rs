@@ -0,0 +1,79 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+
+use cosmwasm_std::{Addr, Uint128};
+
+use crate::asset::AssetInfo;
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// router contract that reports swap volume
+    pub router: Addr,
+    /// ORAIX token paid as rebate
+    pub reward_token: Addr,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    ///////////////////
+    /// Owner Operations
+    ///////////////////
+    UpdateConfig {
+        owner: Option<Addr>,
+        router: Option<Addr>,
+        reward_token: Option<Addr>,
+    },
+
+    /// rebate amount is volume * rebate_bps / 10000, zero rebate_bps disables rebate for the asset
+    UpdateRebateRate {
+        asset_info: AssetInfo,
+        rebate_bps: u64,
+    },
+
+    ///////////////////
+    /// Router Operations
+    ///////////////////
+    RecordVolume {
+        trader: Addr,
+        asset_info: AssetInfo,
+        amount: Uint128,
+    },
+
+    /// Claim all accrued rebate of the sender
+    ClaimRebate {},
+}
+
+#[cw_serde]
+pub struct MigrateMsg {}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(ConfigResponse)]
+    Config {},
+    #[returns(RebateRateResponse)]
+    RebateRate { asset_info: AssetInfo },
+    #[returns(PendingRebateResponse)]
+    PendingRebate { trader: Addr },
+}
+
+// We define a custom struct for each query response
+#[cw_serde]
+pub struct ConfigResponse {
+    pub owner: Addr,
+    pub router: Addr,
+    pub reward_token: Addr,
+}
+
+// We define a custom struct for each query response
+#[cw_serde]
+pub struct RebateRateResponse {
+    pub asset_info: AssetInfo,
+    pub rebate_bps: u64,
+}
+
+// We define a custom struct for each query response
+#[cw_serde]
+pub struct PendingRebateResponse {
+    pub trader: Addr,
+    pub amount: Uint128,
+}
@@ -0,0 +1,134 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+
+use cosmwasm_std::{to_binary, Addr, Coin, CosmosMsg, StdResult, Uint128, WasmMsg};
+
+use crate::asset::{Asset, AssetInfo};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// referral rate of referrers without their own rate, in basis points of the commission paid by their traders
+    pub default_rate_bps: u64,
+    /// venues that pay the referral rewards out of their commission, e.g the limit order contract
+    pub reporters: Vec<Addr>,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    Receive(Cw20ReceiveMsg),
+
+    ///////////////////
+    /// Owner Operations
+    ///////////////////
+    UpdateConfig {
+        owner: Option<Addr>,
+        default_rate_bps: Option<u64>,
+        reporters: Option<Vec<Addr>>,
+    },
+
+    /// set the rate of a referrer, None restores the default rate
+    UpdateReferrerRate {
+        referrer: Addr,
+        rate_bps: Option<u64>,
+    },
+
+    ///////////////////
+    /// Trader Operations
+    ///////////////////
+    /// bind the referrer of the sender, the first binding is final
+    SetReferrer {
+        referrer: Addr,
+    },
+
+    ///////////////////
+    /// Reporter Operations
+    ///////////////////
+    /// credit the rewards paid along in one native coin, the sent amount must equal their total
+    RecordRewards {
+        rewards: Vec<ReferralReward>,
+    },
+
+    /// Claim the accrued referral rewards of the sender in one asset, or in all assets when None
+    ClaimReferralRewards {
+        asset_info: Option<AssetInfo>,
+    },
+}
+
+#[cw_serde]
+pub enum Cw20HookMsg {
+    /// credit the rewards paid along in the sent token, the sent amount must equal their total
+    RecordRewards { rewards: Vec<ReferralReward> },
+}
+
+#[cw_serde]
+pub struct ReferralReward {
+    pub referrer: Addr,
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+pub struct MigrateMsg {}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(ConfigResponse)]
+    Config {},
+    #[returns(ReferralResponse)]
+    Referral { trader: Addr },
+    #[returns(PendingRewardsResponse)]
+    PendingRewards { referrer: Addr },
+}
+
+// We define a custom struct for each query response
+#[cw_serde]
+pub struct ConfigResponse {
+    pub owner: Addr,
+    pub default_rate_bps: u64,
+    pub reporters: Vec<Addr>,
+}
+
+// We define a custom struct for each query response
+#[cw_serde]
+pub struct ReferralResponse {
+    pub trader: Addr,
+    pub referrer: Option<Addr>,
+    /// share of the commission paid by the trader the referrer earns, in basis points
+    pub rate_bps: u64,
+}
+
+// We define a custom struct for each query response
+#[cw_serde]
+pub struct PendingRewardsResponse {
+    pub referrer: Addr,
+    pub rate_bps: u64,
+    pub rewards: Vec<Asset>,
+}
+
+/// Build the message paying the rewards of the referrers to the referral contract in the
+/// reward asset, whose amount is their total
+pub fn asset_into_rewards_msg(
+    referral_addr: Addr,
+    reward_asset: Asset,
+    rewards: Vec<ReferralReward>,
+) -> StdResult<CosmosMsg> {
+    match reward_asset.info {
+        AssetInfo::NativeToken { denom } => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: referral_addr.to_string(),
+            funds: vec![Coin {
+                denom,
+                amount: reward_asset.amount,
+            }],
+            msg: to_binary(&ExecuteMsg::RecordRewards { rewards })?,
+        })),
+        AssetInfo::Token { contract_addr } => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: referral_addr.to_string(),
+                amount: reward_asset.amount,
+                msg: to_binary(&Cw20HookMsg::RecordRewards { rewards })?,
+            })?,
+        })),
+    }
+}
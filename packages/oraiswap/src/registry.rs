@@ -0,0 +1,122 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+
+use cosmwasm_std::Addr;
+
+use crate::asset::AssetInfo;
+
+/// oraiswap contracts tracked by the registry
+#[cw_serde]
+#[derive(Copy)]
+pub enum ContractKey {
+    Factory,
+    Router,
+    LimitOrder,
+    Staking,
+    Oracle,
+    FeeCollector,
+}
+
+impl ContractKey {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ContractKey::Factory => "factory",
+            ContractKey::Router => "router",
+            ContractKey::LimitOrder => "limit_order",
+            ContractKey::Staking => "staking",
+            ContractKey::Oracle => "oracle",
+            ContractKey::FeeCollector => "fee_collector",
+        }
+    }
+}
+
+#[cw_serde]
+pub struct RegistryContract {
+    pub key: ContractKey,
+    pub address: Addr,
+}
+
+/// decimals and symbol of a native denom or cw20 token
+#[cw_serde]
+pub struct AssetMetadata {
+    pub asset_info: AssetInfo,
+    pub decimals: u8,
+    pub symbol: String,
+}
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// contracts registered at version 1
+    pub contracts: Vec<RegistryContract>,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    ///////////////////
+    /// Owner Operations
+    ///////////////////
+    UpdateOwner {
+        owner: Addr,
+    },
+
+    /// set the address of a contract, its version is increased by one
+    UpdateContract {
+        key: ContractKey,
+        address: Addr,
+    },
+
+    /// set the decimals and symbols of the assets, replacing their previous metadata
+    UpdateAssetMetadata {
+        metadata: Vec<AssetMetadata>,
+    },
+
+    RemoveAssetMetadata {
+        asset_info: AssetInfo,
+    },
+}
+
+#[cw_serde]
+pub struct MigrateMsg {}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(ConfigResponse)]
+    Config {},
+    #[returns(ContractResponse)]
+    Contract { key: ContractKey },
+    #[returns(ContractsResponse)]
+    Contracts {},
+    #[returns(AssetMetadata)]
+    AssetMetadata { asset_info: AssetInfo },
+    #[returns(AssetMetadataListResponse)]
+    AssetMetadataList {
+        start_after: Option<AssetInfo>,
+        limit: Option<u32>,
+    },
+}
+
+// We define a custom struct for each query response
+#[cw_serde]
+pub struct ConfigResponse {
+    pub owner: Addr,
+}
+
+// We define a custom struct for each query response
+#[cw_serde]
+pub struct ContractResponse {
+    pub key: ContractKey,
+    pub address: Addr,
+    /// number of times the address has been set
+    pub version: u64,
+}
+
+// We define a custom struct for each query response
+#[cw_serde]
+pub struct ContractsResponse {
+    pub contracts: Vec<ContractResponse>,
+}
+
+#[cw_serde]
+pub struct AssetMetadataListResponse {
+    pub metadata: Vec<AssetMetadata>,
+}
@@ -1,8 +1,10 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw20::Cw20ReceiveMsg;
 
 use crate::asset::AssetInfo;
+use crate::router::SwapOperation;
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -12,6 +14,8 @@ pub struct InstantiateMsg {
 
 #[cw_serde]
 pub enum ExecuteMsg {
+    Receive(Cw20ReceiveMsg),
+
     ///////////////////
     /// Owner Operations
     ///////////////////
@@ -25,6 +29,55 @@ pub enum ExecuteMsg {
     Distribute {
         asset_infos: Vec<AssetInfo>,
     },
+
+    /// Owner set the incentive program of the order book makers, None stops it
+    UpdateOrderBookIncentive {
+        incentive: Option<OrderBookIncentive>,
+    },
+
+    /// Order book contract pays the incentive points claimed by a maker
+    DistributeOrderBookIncentive {
+        recipient: Addr,
+        points: Uint128,
+    },
+
+    /// Owner set the router and reward assets the deposited fees are swapped into, None stops it
+    UpdateFeeSwap {
+        fee_swap: Option<FeeSwap>,
+    },
+
+    /// Owner set the route swapping a fee asset into one of the reward assets, None removes it
+    UpdateFeeRoute {
+        asset_info: AssetInfo,
+        operations: Option<Vec<SwapOperation>>,
+    },
+
+    /// Deposit the native fees sent along, they are swapped into the reward assets and sent to staking
+    DepositFees {},
+}
+
+#[cw_serde]
+pub enum Cw20HookMsg {
+    /// Deposit the sent cw20 fee, it is swapped into the reward assets and sent to staking
+    DepositFees {},
+}
+
+/// Deposited fees are swapped by router into reward_assets, reverting when the spread of
+/// a swap operation is over max_spread
+#[cw_serde]
+pub struct FeeSwap {
+    pub router: Addr,
+    pub reward_assets: Vec<AssetInfo>,
+    pub max_spread: Decimal,
+}
+
+/// Incentive points accrued by the makers of orderbook_contract are paid
+/// reward_per_point of reward_asset each
+#[cw_serde]
+pub struct OrderBookIncentive {
+    pub orderbook_contract: Addr,
+    pub reward_asset: AssetInfo,
+    pub reward_per_point: Decimal,
 }
 
 #[cw_serde]
@@ -39,6 +92,12 @@ pub enum QueryMsg {
     DistributionInfo { asset_info: AssetInfo },
     #[returns(RewardAmountPerSecondResponse)]
     RewardAmountPerSec { asset_info: AssetInfo },
+    #[returns(OrderBookIncentiveResponse)]
+    OrderBookIncentive {},
+    #[returns(FeeSwapResponse)]
+    FeeSwap {},
+    #[returns(FeeRouteResponse)]
+    FeeRoute { asset_info: AssetInfo },
 }
 
 // We define a custom struct for each query response
@@ -60,3 +119,18 @@ pub struct DistributionInfoResponse {
 pub struct RewardAmountPerSecondResponse {
     pub reward_amount: Uint128,
 }
+
+#[cw_serde]
+pub struct OrderBookIncentiveResponse {
+    pub incentive: Option<OrderBookIncentive>,
+}
+
+#[cw_serde]
+pub struct FeeSwapResponse {
+    pub fee_swap: Option<FeeSwap>,
+}
+
+#[cw_serde]
+pub struct FeeRouteResponse {
+    pub operations: Option<Vec<SwapOperation>>,
+}
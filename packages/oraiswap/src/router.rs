@@ -1,6 +1,8 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 
-use cosmwasm_std::{coin, to_binary, Addr, CosmosMsg, QuerierWrapper, StdResult, Uint128, WasmMsg};
+use cosmwasm_std::{
+    coin, to_binary, Addr, Binary, CosmosMsg, QuerierWrapper, StdResult, Uint128, WasmMsg,
+};
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
 
 use crate::asset::AssetInfo;
@@ -9,10 +11,22 @@ use crate::asset::AssetInfo;
 pub struct InstantiateMsg {
     pub factory_addr: Addr,
     pub factory_addr_v2: Addr,
+    pub converter_addr: Option<Addr>,
+    /// guardian can pause swap operations
+    pub guardian: Option<Addr>,
 }
 
 #[cw_serde]
-pub struct MigrateMsg {}
+pub struct MigrateMsg {
+    /// set the converter contract used by SwapOperation::Convert
+    pub converter_addr: Option<Addr>,
+    /// set the guardian that can pause swap operations
+    pub guardian: Option<Addr>,
+    /// set the rebate contract that swap volume is reported to
+    pub rebate_contract: Option<Addr>,
+    /// restrict swap operations to these pair and converter contracts
+    pub allowed_venues: Option<Vec<Addr>>,
+}
 
 #[cw_serde]
 pub enum SwapOperation {
@@ -21,12 +35,37 @@ pub enum SwapOperation {
         offer_asset_info: AssetInfo,
         ask_asset_info: AssetInfo,
     },
+    // convert between token versions registered in the converter contract (e.g old -> new usdt)
+    Convert {
+        from: AssetInfo,
+        to: AssetInfo,
+    },
+    // swap through the weighted pool of the factory holding pool_asset_infos
+    WeightedSwap {
+        pool_asset_infos: Vec<AssetInfo>,
+        offer_asset_info: AssetInfo,
+        ask_asset_info: AssetInfo,
+    },
 }
 
 impl SwapOperation {
+    pub fn get_offer_asset_info(&self) -> AssetInfo {
+        match self {
+            SwapOperation::OraiSwap {
+                offer_asset_info, ..
+            } => offer_asset_info.clone(),
+            SwapOperation::Convert { from, .. } => from.clone(),
+            SwapOperation::WeightedSwap {
+                offer_asset_info, ..
+            } => offer_asset_info.clone(),
+        }
+    }
+
     pub fn get_target_asset_info(&self) -> AssetInfo {
         match self {
             SwapOperation::OraiSwap { ask_asset_info, .. } => ask_asset_info.clone(),
+            SwapOperation::Convert { to, .. } => to.clone(),
+            SwapOperation::WeightedSwap { ask_asset_info, .. } => ask_asset_info.clone(),
         }
     }
 }
@@ -39,6 +78,15 @@ pub enum ExecuteMsg {
         operations: Vec<SwapOperation>,
         minimum_receive: Option<Uint128>,
         to: Option<Addr>,
+        /// when `to` is a contract, the output is sent to it executing this message,
+        /// as a cw20 `Send` hook for tokens or with the native funds attached
+        callback: Option<Binary>,
+    },
+    /// Execute a circular route that starts and ends with the same asset,
+    /// reverting unless the returned amount exceeds the offer by min_profit
+    ExecuteCycle {
+        operations: Vec<SwapOperation>,
+        min_profit: Uint128,
     },
 
     /// Internal use
@@ -48,6 +96,15 @@ pub enum ExecuteMsg {
         to: Option<Addr>,
     },
     /// Internal use
+    /// Forward the swap output received by the router to the receiver contract executing the callback
+    ForwardCallback {
+        asset_info: AssetInfo,
+        prev_balance: Uint128,
+        minimum_receive: Option<Uint128>,
+        receiver: Addr,
+        callback: Binary,
+    },
+    /// Internal use
     /// Check the swap amount is exceed minimum_receive
     AssertMinimumReceive {
         asset_info: AssetInfo,
@@ -55,6 +112,14 @@ pub enum ExecuteMsg {
         minimum_receive: Uint128,
         receiver: Addr,
     },
+    /// Pause swap operations, only guardian
+    Pause {},
+    /// Unpause swap operations, only guardian
+    Unpause {},
+    /// Drop the cached pair contract of the assets, only guardian
+    InvalidatePairCache {
+        asset_infos: [AssetInfo; 2],
+    },
 }
 
 #[cw_serde]
@@ -63,6 +128,11 @@ pub enum Cw20HookMsg {
         operations: Vec<SwapOperation>,
         minimum_receive: Option<Uint128>,
         to: Option<String>,
+        callback: Option<Binary>,
+    },
+    ExecuteCycle {
+        operations: Vec<SwapOperation>,
+        min_profit: Uint128,
     },
 }
 
@@ -76,6 +146,12 @@ pub enum QueryMsg {
         offer_amount: Uint128,
         operations: Vec<SwapOperation>,
     },
+    /// Amount delivered to the receiver of the operations, with the capped taxes of every hop deducted
+    #[returns(NetReceiveEstimateResponse)]
+    NetReceiveEstimate {
+        offer_amount: Uint128,
+        operations: Vec<SwapOperation>,
+    },
 }
 
 // We define a custom struct for each query response
@@ -83,12 +159,43 @@ pub enum QueryMsg {
 pub struct ConfigResponse {
     pub factory_addr: Addr,
     pub factory_addr_v2: Addr,
+    pub converter_addr: Option<Addr>,
+    pub guardian: Option<Addr>,
+    pub paused: bool,
+    pub rebate_contract: Option<Addr>,
+    pub allowed_venues: Option<Vec<Addr>>,
 }
 
 // We define a custom struct for each query response
 #[cw_serde]
 pub struct SimulateSwapOperationsResponse {
     pub amount: Uint128,
+    /// breakdown of each operation, in the order of the operations
+    pub hops: Vec<SwapHopSimulation>,
+}
+
+#[cw_serde]
+pub struct NetReceiveEstimateResponse {
+    pub amount: Uint128,
+    /// total tax deducted from the native transfers of the swap hops
+    pub tax_amount: Uint128,
+    /// total fee of the convert hops
+    pub convert_fee_amount: Uint128,
+}
+
+/// SwapHopSimulation is the simulated result of one swap operation,
+/// return_amount is what the next operation is offered
+#[cw_serde]
+pub struct SwapHopSimulation {
+    pub operation: SwapOperation,
+    /// pair or converter contract the operation is executed by
+    pub venue: Addr,
+    pub offer_amount: Uint128,
+    pub return_amount: Uint128,
+    pub spread_amount: Uint128,
+    pub commission_amount: Uint128,
+    /// tax deducted from the native offer and return assets, or the converter fee
+    pub tax_amount: Uint128,
 }
 
 #[cw_serde]
@@ -120,6 +227,7 @@ impl RouterController {
                         operations,
                         minimum_receive,
                         to: swap_to.map(|to| to.into_string()),
+                        callback: None,
                     })?,
                 })?,
                 funds: vec![],
@@ -131,6 +239,7 @@ impl RouterController {
                     operations,
                     minimum_receive,
                     to: swap_to,
+                    callback: None,
                 })?,
                 funds: vec![coin(amount.u128(), denom)],
             }
@@ -158,4 +267,20 @@ impl RouterController {
             },
         )
     }
+
+    /// amount delivered by the operations after the capped taxes
+    pub fn net_receive_estimate(
+        &self,
+        querier: &QuerierWrapper,
+        offer_amount: Uint128,
+        operations: Vec<SwapOperation>,
+    ) -> StdResult<NetReceiveEstimateResponse> {
+        querier.query_wasm_smart(
+            self.addr(),
+            &QueryMsg::NetReceiveEstimate {
+                offer_amount,
+                operations,
+            },
+        )
+    }
 }
@@ -1,7 +1,7 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 
 use crate::asset::{Asset, AssetInfo};
-use cosmwasm_std::{Addr, Decimal, Uint128};
+use cosmwasm_std::{to_binary, Addr, CosmosMsg, Decimal, StdResult, Uint128, WasmMsg};
 use cw20::Cw20ReceiveMsg;
 
 #[cw_serde]
@@ -25,20 +25,49 @@ pub enum ExecuteMsg {
     UpdateConfig {
         rewarder: Option<Addr>,
         owner: Option<Addr>,
+        guardian: Option<Addr>,
     },
+    // pause bonding and auto staking, can be called by owner or guardian
+    Pause {},
+    Unpause {},
     RegisterAsset {
         asset_info: AssetInfo, // can be ow20 token or native token
-        staking_token: Addr,
+        // can be cw20 LP token or native denom such as tokenfactory LP shares
+        staking_token: AssetInfo,
     },
     DeprecateStakingToken {
         asset_info: AssetInfo,
-        new_staking_token: Addr,
+        new_staking_token: AssetInfo,
     },
     // update rewards per second for an asset
     UpdateRewardsPerSec {
         asset_info: AssetInfo,
         assets: Vec<Asset>,
     },
+    // cap the rewards a pool keeps while nobody bonds, the excess of deposits is returned to the rewarder
+    UpdateMaxPendingReward {
+        asset_info: AssetInfo,
+        max_pending_reward: Option<Uint128>,
+    },
+    // cap the total bond amount of the pool, none removes the cap
+    UpdateMaxTotalBond {
+        asset_info: AssetInfo,
+        max_total_bond: Option<Uint128>,
+    },
+    // only the whitelisted stakers can bond to the pool, none opens the pool to everyone
+    UpdateStakerWhitelist {
+        asset_info: AssetInfo,
+        staker_whitelist: Option<Vec<Addr>>,
+    },
+    // return the undistributed rewards of the pool to the rewarder
+    ClawbackUnallocatedRewards {
+        asset_info: AssetInfo,
+    },
+    // send the tokens held by the contract but not accounted as bonds, such as tokens transferred by mistake
+    SweepUnaccountedTokens {
+        asset_info: AssetInfo,
+        recipient: Addr,
+    },
     // reward tokens are in amount proportionaly, and used by minter contract to update amounts after checking the balance, which
     // will be used as rewards for the specified asset's staking pool.
     DepositReward {
@@ -48,6 +77,10 @@ pub enum ExecuteMsg {
     ////////////////////////
     /// User operations ///
     ////////////////////////
+    /// Bond the native staking token of the pool sent along with the message
+    BondNative {
+        asset_info: AssetInfo,
+    },
     Unbond {
         asset_info: AssetInfo,
         amount: Uint128,
@@ -56,6 +89,14 @@ pub enum ExecuteMsg {
     Withdraw {
         // If the asset token is not given, then all rewards are withdrawn
         asset_info: Option<AssetInfo>,
+        // rewards in the staking token of this pool, such as its LP token, are bonded to it instead of transferred
+        auto_bond: Option<AssetInfo>,
+    },
+    /// Withdraw pending rewards of all pools of the staker, or only the given pools,
+    /// sending one transfer per reward asset
+    WithdrawAll {
+        asset_infos: Option<Vec<AssetInfo>>,
+        auto_bond: Option<AssetInfo>,
     },
     // Withdraw for others in this pool, such as when rewards per second are changed for the pool
     WithdrawOthers {
@@ -63,7 +104,8 @@ pub enum ExecuteMsg {
         staker_addrs: Vec<Addr>,
     },
 
-    /// Provides liquidity and automatically stakes the LP tokens
+    /// Provides liquidity and automatically stakes the LP tokens,
+    /// pairs with a staking contract can also do it with `auto_stake` of ProvideLiquidity
     AutoStake {
         assets: [Asset; 2],
         slippage_tolerance: Option<Decimal>,
@@ -79,18 +121,70 @@ pub enum ExecuteMsg {
         asset_info: AssetInfo,
         stakers: Vec<Addr>,
     },
+    // register a contract notified with StakingHookMsg when stakers bond, unbond or claim rewards
+    AddHook {
+        contract_addr: Addr,
+    },
+    RemoveHook {
+        contract_addr: Addr,
+    },
 }
 
 #[cw_serde]
 pub enum Cw20HookMsg {
     // this call from LP token contract
-    Bond { asset_info: AssetInfo },
+    Bond {
+        asset_info: AssetInfo,
+    },
+    // bond on behalf of the staker, used by pairs to auto stake minted LP tokens
+    BondFor {
+        asset_info: AssetInfo,
+        staker_addr: Addr,
+    },
+}
+
+/// Staking events sent to the registered hook contracts
+#[cw_serde]
+pub enum StakingHookMsg {
+    Bonded {
+        staker_addr: Addr,
+        asset_info: AssetInfo,
+        amount: Uint128,
+    },
+    Unbonded {
+        staker_addr: Addr,
+        asset_info: AssetInfo,
+        amount: Uint128,
+    },
+    Claimed {
+        staker_addr: Addr,
+        rewards: Vec<Asset>,
+    },
+}
+
+impl StakingHookMsg {
+    /// wrap the event into the `staking_hook` execute message of the hook contract
+    pub fn into_cosmos_msg(self, contract_addr: impl Into<String>) -> StdResult<CosmosMsg> {
+        Ok(WasmMsg::Execute {
+            contract_addr: contract_addr.into(),
+            msg: to_binary(&StakingHookExecuteMsg::StakingHook(self))?,
+            funds: vec![],
+        }
+        .into())
+    }
+}
+
+/// The execute message hook contracts must accept
+#[cw_serde]
+pub enum StakingHookExecuteMsg {
+    StakingHook(StakingHookMsg),
 }
 
-/// We currently take no arguments for migrations
 #[cw_serde]
 pub struct MigrateMsg {
     pub staker_addrs: Vec<Addr>,
+    /// version of a contract deployed before its version was stored, every migration step is applied when not set
+    pub from_version: Option<String>,
     // pub amount_infos: Vec<AmountInfo>,
     // pub new_staking_token: Addr,
 }
@@ -117,7 +211,7 @@ pub enum QueryMsg {
         staker_addr: Addr,
         asset_info: Option<AssetInfo>,
     },
-    #[returns(Vec<RewardInfoResponse>)]
+    #[returns(RewardInfosResponse)]
     // Query all staker belong to the pool
     RewardInfos {
         asset_info: AssetInfo,
@@ -125,6 +219,27 @@ pub enum QueryMsg {
         limit: Option<u32>,
         // so can convert or throw error
         order: Option<i32>,
+        // next_cursor of the previous page, takes precedence over start_after and order
+        cursor: Option<String>,
+    },
+    #[returns(ProjectedRewardResponse)]
+    // Extrapolate the pending reward of the staker with the current rewards per second
+    ProjectedReward {
+        staker_addr: Addr,
+        asset_info: AssetInfo,
+        seconds_ahead: u64,
+    },
+    #[returns(VotingPowerResponse)]
+    // Total bond amount of the address across all pools at the given block height
+    VotingPowerAt { address: Addr, height: u64 },
+    #[returns(HooksResponse)]
+    Hooks {},
+    #[returns(PoolOverviewResponse)]
+    // Pool info joined with the reserves of the pair of the asset and the base denom,
+    // valued in usd_denom with the oracle when it is given
+    PoolOverview {
+        asset_info: AssetInfo,
+        usd_denom: Option<String>,
     },
 }
 
@@ -136,6 +251,8 @@ pub struct ConfigResponse {
     pub oracle_addr: Addr,
     pub factory_addr: Addr,
     pub base_denom: String,
+    pub guardian: Option<Addr>,
+    pub paused: bool,
 }
 
 #[cw_serde]
@@ -147,12 +264,15 @@ pub struct RewardsPerSecResponse {
 #[cw_serde]
 pub struct PoolInfoResponse {
     pub asset_info: AssetInfo,
-    pub staking_token: Addr,
+    pub staking_token: AssetInfo,
     pub total_bond_amount: Uint128,
     pub reward_index: Decimal,
     pub pending_reward: Uint128,
     pub migration_index_snapshot: Option<Decimal>,
-    pub migration_deprecated_staking_token: Option<Addr>,
+    pub migration_deprecated_staking_token: Option<AssetInfo>,
+    pub max_pending_reward: Option<Uint128>,
+    pub max_total_bond: Option<Uint128>,
+    pub staker_whitelist: Option<Vec<Addr>>,
 }
 
 // We define a custom struct for each query response
@@ -162,6 +282,12 @@ pub struct RewardInfoResponse {
     pub reward_infos: Vec<RewardInfoResponseItem>,
 }
 
+#[cw_serde]
+pub struct RewardInfosResponse {
+    pub reward_infos: Vec<RewardInfoResponse>,
+    pub next_cursor: Option<String>,
+}
+
 #[cw_serde]
 pub struct RewardInfoResponseItem {
     pub asset_info: AssetInfo,
@@ -172,3 +298,43 @@ pub struct RewardInfoResponseItem {
     // with the new lp token
     pub should_migrate: Option<bool>,
 }
+
+#[cw_serde]
+pub struct ProjectedRewardResponse {
+    pub staker_addr: Addr,
+    pub asset_info: AssetInfo,
+    pub bond_amount: Uint128,
+    // pending reward at the current block
+    pub pending_reward: Uint128,
+    // pending reward after seconds_ahead
+    pub projected_reward: Uint128,
+    // projected_reward splitted by the rewards per second weights, including pending withdraw
+    pub projected_reward_assets: Vec<Asset>,
+}
+
+#[cw_serde]
+pub struct VotingPowerResponse {
+    pub address: Addr,
+    pub height: u64,
+    pub voting_power: Uint128,
+}
+
+#[cw_serde]
+pub struct PoolOverviewResponse {
+    pub pool_info: PoolInfoResponse,
+    pub rewards_per_sec: Vec<Asset>,
+    // pair of the asset and the base denom, none when the factory has no such pair
+    pub pair_addr: Option<Addr>,
+    pub reserves: Vec<Asset>,
+    pub lp_supply: Uint128,
+    // share of the reserves backing the bonded staking tokens
+    pub bonded_reserves: Vec<Asset>,
+    // twice the base denom reserve converted to usd_denom by the oracle, none when it can not be priced
+    pub total_value: Option<Uint128>,
+    pub bonded_value: Option<Uint128>,
+}
+
+#[cw_serde]
+pub struct HooksResponse {
+    pub hooks: Vec<Addr>,
+}
@@ -11,6 +11,8 @@ use crate::asset::{AssetInfo, PairInfo, ORAI_DENOM};
 use crate::pair::DEFAULT_COMMISSION_RATE;
 use cw_multi_test::{next_block, App, AppResponse, Contract, Executor};
 
+pub mod router_harness;
+
 pub const ATOM_DENOM: &str = "ibc/1777D03C5392415FE659F0E8ECB2CE553C6550542A68E4707D5D46949116790B";
 pub const APP_OWNER: &str = "admin";
 
@@ -190,6 +192,9 @@ impl MockApp {
                     &crate::factory::ExecuteMsg::CreatePair {
                         asset_infos: asset_infos.clone(),
                         pair_admin: Some("admin".to_string()),
+                        pair_type: None,
+                        lp_token_info: None,
+                        lp_token_type: None,
                     },
                     &[],
                 )
@@ -428,8 +433,10 @@ mod tests {
     use cosmwasm_std::{testing::MOCK_CONTRACT_ADDR, Addr, Coin, Uint128};
 
     use crate::{
-        asset::AssetInfo,
-        querier::{query_supply, query_token_balance},
+        asset::{Asset, AssetInfo},
+        querier::{
+            query_balances, query_supply, query_token_balance, query_token_infos, AddressBalances,
+        },
         testing::MockApp,
     };
 
@@ -526,6 +533,89 @@ mod tests {
         )
     }
 
+    #[test]
+    fn batch_querier() {
+        let mut app = MockApp::new(&[(
+            &"addr00000".to_string(),
+            &[Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::from(200u128),
+            }],
+        )]);
+        app.set_token_contract(Box::new(crate::create_entry_points_testing!(cw20_base)));
+        app.set_token_balances(&[
+            (
+                &"LPA".to_string(),
+                &[(&"addr00000".to_string(), &Uint128::from(123u128))],
+            ),
+            (
+                &"LPB".to_string(),
+                &[(&"addr00001".to_string(), &Uint128::from(456u128))],
+            ),
+        ]);
+        let lpa = app.get_token_addr("LPA").unwrap();
+        let lpb = app.get_token_addr("LPB").unwrap();
+
+        let asset_infos = [
+            AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            AssetInfo::Token {
+                contract_addr: lpa.clone(),
+            },
+        ];
+        let balances = query_balances(
+            &app.as_querier(),
+            &[Addr::unchecked("addr00000"), Addr::unchecked("addr00001")],
+            &asset_infos,
+        )
+        .unwrap();
+        assert_eq!(
+            balances,
+            vec![
+                AddressBalances {
+                    address: Addr::unchecked("addr00000"),
+                    balances: vec![
+                        Asset {
+                            info: asset_infos[0].clone(),
+                            amount: Uint128::from(200u128),
+                        },
+                        Asset {
+                            info: asset_infos[1].clone(),
+                            amount: Uint128::from(123u128),
+                        },
+                    ],
+                },
+                AddressBalances {
+                    address: Addr::unchecked("addr00001"),
+                    balances: vec![
+                        Asset {
+                            info: asset_infos[0].clone(),
+                            amount: Uint128::zero(),
+                        },
+                        Asset {
+                            info: asset_infos[1].clone(),
+                            amount: Uint128::zero(),
+                        },
+                    ],
+                },
+            ]
+        );
+
+        let token_infos =
+            query_token_infos(&app.as_querier(), &[lpb.clone(), lpa.clone()]).unwrap();
+        assert_eq!(token_infos[0].contract_addr, lpb);
+        assert_eq!(
+            token_infos[0].token_info.total_supply,
+            Uint128::from(456u128)
+        );
+        assert_eq!(token_infos[1].contract_addr, lpa);
+        assert_eq!(
+            token_infos[1].token_info.total_supply,
+            Uint128::from(123u128)
+        );
+    }
+
     #[test]
     fn test_asset_info() {
         let mut app = MockApp::new(&[(
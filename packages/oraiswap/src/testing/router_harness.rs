@@ -0,0 +1,320 @@
+use cosmwasm_std::{to_binary, Addr, Coin, Empty, StdResult, Uint128};
+use cw_multi_test::Contract;
+
+use crate::asset::{Asset, AssetInfo};
+use crate::router::{
+    Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg, SimulateSwapOperationsResponse,
+    SwapOperation,
+};
+use crate::testing::MockApp;
+
+pub const LIQUIDITY_PROVIDER: &str = "liquidity_provider";
+pub const TRADER: &str = "trader";
+
+/// contract codes deployed by the harness, this package can not depend on the contracts so callers pass them in
+pub struct RouterHarnessContracts {
+    pub oracle: Box<dyn Contract<Empty>>,
+    pub token: Box<dyn Contract<Empty>>,
+    pub factory: Box<dyn Contract<Empty>>,
+    pub pair: Box<dyn Contract<Empty>>,
+    pub router: Box<dyn Contract<Empty>>,
+}
+
+/// result of one swap checked against its simulation
+#[derive(Clone, Debug, PartialEq)]
+pub struct SwapParity {
+    pub offer_amount: Uint128,
+    pub operations: Vec<SwapOperation>,
+    pub simulated_amount: Uint128,
+    pub received_amount: Uint128,
+}
+
+/// factory, pairs and router running on a MockApp, to check that SimulateSwapOperations
+/// returns exactly what ExecuteSwapOperations pays out
+pub struct RouterHarness {
+    pub app: MockApp,
+    pub router_addr: Addr,
+    pub asset_infos: Vec<AssetInfo>,
+    seed: u64,
+}
+
+impl RouterHarness {
+    /// create a pair for every two of the native denoms and cw20 tokens, each provided with `liquidity` of both sides.
+    /// The trader holds `liquidity` of every asset, swaps are randomized from `seed` so a failing run can be replayed
+    pub fn new(
+        contracts: RouterHarnessContracts,
+        denoms: &[&str],
+        tokens: &[&str],
+        liquidity: Uint128,
+        seed: u64,
+    ) -> Self {
+        let provider = LIQUIDITY_PROVIDER.to_string();
+        let trader = TRADER.to_string();
+
+        // every asset is in a pair with each other asset
+        let provider_amount = liquidity * Uint128::from((denoms.len() + tokens.len()) as u128);
+        let coins = |amount: Uint128| -> Vec<Coin> {
+            denoms
+                .iter()
+                .map(|denom| Coin {
+                    denom: denom.to_string(),
+                    amount,
+                })
+                .collect()
+        };
+
+        let mut app = MockApp::new(&[
+            (&provider, &coins(provider_amount)),
+            (&trader, &coins(liquidity)),
+        ]);
+        app.set_oracle_contract(contracts.oracle);
+        app.set_token_contract(contracts.token);
+        app.set_factory_and_pair_contract(contracts.factory, contracts.pair);
+
+        let token_names: Vec<String> = tokens.iter().map(|token| token.to_string()).collect();
+        let token_balances = [(&provider, &provider_amount), (&trader, &liquidity)];
+        let token_addrs = app.set_token_balances(
+            &token_names
+                .iter()
+                .map(|token| (token, &token_balances[..]))
+                .collect::<Vec<_>>(),
+        );
+
+        let asset_infos: Vec<AssetInfo> = denoms
+            .iter()
+            .map(|denom| AssetInfo::NativeToken {
+                denom: denom.to_string(),
+            })
+            .chain(
+                token_addrs
+                    .into_iter()
+                    .map(|contract_addr| AssetInfo::Token { contract_addr }),
+            )
+            .collect();
+
+        for (i, offer_asset_info) in asset_infos.iter().enumerate() {
+            for ask_asset_info in asset_infos[i + 1..].iter() {
+                let pair_addr = app
+                    .create_pair([offer_asset_info.clone(), ask_asset_info.clone()])
+                    .unwrap();
+
+                let mut funds: Vec<Coin> = vec![];
+                for asset_info in [offer_asset_info, ask_asset_info] {
+                    match asset_info {
+                        AssetInfo::NativeToken { denom } => funds.push(Coin {
+                            denom: denom.clone(),
+                            amount: liquidity,
+                        }),
+                        AssetInfo::Token { contract_addr } => {
+                            app.execute(
+                                Addr::unchecked(LIQUIDITY_PROVIDER),
+                                contract_addr.clone(),
+                                &cw20::Cw20ExecuteMsg::IncreaseAllowance {
+                                    spender: pair_addr.to_string(),
+                                    amount: liquidity,
+                                    expires: None,
+                                },
+                                &[],
+                            )
+                            .unwrap();
+                        }
+                    }
+                }
+
+                app.execute(
+                    Addr::unchecked(LIQUIDITY_PROVIDER),
+                    pair_addr,
+                    &crate::pair::ExecuteMsg::ProvideLiquidity {
+                        assets: [
+                            Asset {
+                                info: offer_asset_info.clone(),
+                                amount: liquidity,
+                            },
+                            Asset {
+                                info: ask_asset_info.clone(),
+                                amount: liquidity,
+                            },
+                        ],
+                        slippage_tolerance: None,
+                        receiver: None,
+                        auto_stake: None,
+                    },
+                    &funds,
+                )
+                .unwrap();
+            }
+        }
+
+        let router_id = app.upload(contracts.router);
+        let router_addr = app
+            .instantiate(
+                router_id,
+                Addr::unchecked(LIQUIDITY_PROVIDER),
+                &InstantiateMsg {
+                    factory_addr: app.factory_addr.clone(),
+                    factory_addr_v2: app.factory_addr.clone(),
+                    converter_addr: None,
+                    guardian: None,
+                },
+                &[],
+                "router",
+            )
+            .unwrap();
+
+        RouterHarness {
+            app,
+            router_addr,
+            asset_infos,
+            // xorshift never leaves zero
+            seed: seed.max(1),
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut x = self.seed;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.seed = x;
+        x
+    }
+
+    /// random route of 1 to max_hops swaps, each hop goes to a different asset
+    pub fn random_operations(&mut self, max_hops: usize) -> Vec<SwapOperation> {
+        let len = self.asset_infos.len();
+        let hops = 1 + (self.next_u64() % max_hops as u64) as usize;
+        let mut offer_index = (self.next_u64() % len as u64) as usize;
+
+        let mut operations = vec![];
+        for _ in 0..hops {
+            let ask_index = (offer_index + 1 + (self.next_u64() % (len - 1) as u64) as usize) % len;
+            operations.push(SwapOperation::OraiSwap {
+                offer_asset_info: self.asset_infos[offer_index].clone(),
+                ask_asset_info: self.asset_infos[ask_index].clone(),
+            });
+            offer_index = ask_index;
+        }
+        operations
+    }
+
+    pub fn simulate(
+        &self,
+        offer_amount: Uint128,
+        operations: Vec<SwapOperation>,
+    ) -> StdResult<Uint128> {
+        let res: SimulateSwapOperationsResponse = self.app.query(
+            self.router_addr.clone(),
+            &QueryMsg::SimulateSwapOperations {
+                offer_amount,
+                operations,
+            },
+        )?;
+        Ok(res.amount)
+    }
+
+    /// execute the swap as the trader, return the amount of the last ask asset received
+    pub fn swap(
+        &mut self,
+        offer_amount: Uint128,
+        operations: Vec<SwapOperation>,
+    ) -> Result<Uint128, String> {
+        let (offer_asset_info, ask_asset_info) = match (operations.first(), operations.last()) {
+            (
+                Some(SwapOperation::OraiSwap {
+                    offer_asset_info, ..
+                }),
+                Some(SwapOperation::OraiSwap { ask_asset_info, .. }),
+            ) => (offer_asset_info.clone(), ask_asset_info.clone()),
+            _ => return Err("harness only executes OraiSwap operations".to_string()),
+        };
+
+        let balance_before = self.balance(&ask_asset_info)?;
+        match &offer_asset_info {
+            AssetInfo::NativeToken { denom } => self.app.execute(
+                Addr::unchecked(TRADER),
+                self.router_addr.clone(),
+                &ExecuteMsg::ExecuteSwapOperations {
+                    operations,
+                    minimum_receive: None,
+                    to: None,
+                    callback: None,
+                },
+                &[Coin {
+                    denom: denom.clone(),
+                    amount: offer_amount,
+                }],
+            )?,
+            AssetInfo::Token { contract_addr } => self.app.execute(
+                Addr::unchecked(TRADER),
+                contract_addr.clone(),
+                &cw20::Cw20ExecuteMsg::Send {
+                    contract: self.router_addr.to_string(),
+                    amount: offer_amount,
+                    msg: to_binary(&Cw20HookMsg::ExecuteSwapOperations {
+                        operations,
+                        minimum_receive: None,
+                        to: None,
+                        callback: None,
+                    })
+                    .map_err(|err| err.to_string())?,
+                },
+                &[],
+            )?,
+        };
+        let mut balance_after = self.balance(&ask_asset_info)?;
+
+        // a circular route also spent the offer amount from the same balance
+        if offer_asset_info.eq(&ask_asset_info) {
+            balance_after += offer_amount;
+        }
+        Ok(balance_after - balance_before)
+    }
+
+    /// simulate then execute the swap, panic when the received amount differs from the simulation
+    pub fn check_parity(
+        &mut self,
+        offer_amount: Uint128,
+        operations: Vec<SwapOperation>,
+    ) -> SwapParity {
+        let simulated_amount = self.simulate(offer_amount, operations.clone()).unwrap();
+        let received_amount = self.swap(offer_amount, operations.clone()).unwrap();
+        let parity = SwapParity {
+            offer_amount,
+            operations,
+            simulated_amount,
+            received_amount,
+        };
+        assert_eq!(
+            simulated_amount, received_amount,
+            "simulation differs from execution: {:?}",
+            parity
+        );
+        parity
+    }
+
+    /// check `rounds` random routes, offering between half of max_offer and max_offer
+    /// so that no hop returns zero
+    pub fn run_random_swaps(
+        &mut self,
+        rounds: usize,
+        max_hops: usize,
+        max_offer: Uint128,
+    ) -> Vec<SwapParity> {
+        let min_offer = max_offer.u128() / 2;
+        (0..rounds)
+            .map(|_| {
+                let operations = self.random_operations(max_hops);
+                let offer_amount = Uint128::from(
+                    min_offer + (self.next_u64() as u128) % (max_offer.u128() - min_offer + 1),
+                );
+                self.check_parity(offer_amount, operations)
+            })
+            .collect()
+    }
+
+    fn balance(&self, asset_info: &AssetInfo) -> Result<Uint128, String> {
+        asset_info
+            .query_pool(&self.app.as_querier(), Addr::unchecked(TRADER))
+            .map_err(|err| err.to_string())
+    }
+}
@@ -0,0 +1,74 @@
+use cosmwasm_std::{Addr, Binary, CosmosMsg, StdError, StdResult, Uint128};
+use protobuf::CodedOutputStream;
+
+pub const MSG_CREATE_DENOM_TYPE_URL: &str = "/osmosis.tokenfactory.v1beta1.MsgCreateDenom";
+pub const MSG_MINT_TYPE_URL: &str = "/osmosis.tokenfactory.v1beta1.MsgMint";
+pub const MSG_BURN_TYPE_URL: &str = "/osmosis.tokenfactory.v1beta1.MsgBurn";
+
+/// full denom of a subdenom created by the creator: factory/{creator}/{subdenom}
+pub fn tokenfactory_denom(creator: &Addr, subdenom: &str) -> String {
+    format!("factory/{}/{}", creator, subdenom)
+}
+
+/// MsgCreateDenom { sender, subdenom }, the sender becomes the admin of the denom
+pub fn create_denom_msg(sender: &Addr, subdenom: &str) -> StdResult<CosmosMsg> {
+    let value = encode(|os| {
+        os.write_string(1, sender.as_str())?;
+        os.write_string(2, subdenom)
+    })?;
+
+    Ok(CosmosMsg::Stargate {
+        type_url: MSG_CREATE_DENOM_TYPE_URL.to_string(),
+        value,
+    })
+}
+
+/// MsgMint { sender, amount }, the admin of the denom mints to itself
+pub fn mint_msg(sender: &Addr, denom: &str, amount: Uint128) -> StdResult<CosmosMsg> {
+    let coin = encode_coin(denom, amount)?;
+    let value = encode(|os| {
+        os.write_string(1, sender.as_str())?;
+        os.write_bytes(2, &coin)
+    })?;
+
+    Ok(CosmosMsg::Stargate {
+        type_url: MSG_MINT_TYPE_URL.to_string(),
+        value,
+    })
+}
+
+/// MsgBurn { sender, amount }, the admin of the denom burns from its own balance
+pub fn burn_msg(sender: &Addr, denom: &str, amount: Uint128) -> StdResult<CosmosMsg> {
+    let coin = encode_coin(denom, amount)?;
+    let value = encode(|os| {
+        os.write_string(1, sender.as_str())?;
+        os.write_bytes(2, &coin)
+    })?;
+
+    Ok(CosmosMsg::Stargate {
+        type_url: MSG_BURN_TYPE_URL.to_string(),
+        value,
+    })
+}
+
+/// cosmos.base.v1beta1.Coin { denom, amount }
+fn encode_coin(denom: &str, amount: Uint128) -> StdResult<Vec<u8>> {
+    encode(|os| {
+        os.write_string(1, denom)?;
+        os.write_string(2, &amount.to_string())
+    })
+    .map(|value| value.to_vec())
+}
+
+fn encode(
+    write_fields: impl FnOnce(&mut CodedOutputStream) -> protobuf::Result<()>,
+) -> StdResult<Binary> {
+    let mut buf = vec![];
+    {
+        let mut os = CodedOutputStream::vec(&mut buf);
+        write_fields(&mut os)
+            .and_then(|_| os.flush())
+            .map_err(|err| StdError::generic_err(err.to_string()))?;
+    }
+    Ok(Binary::from(buf))
+}
@@ -0,0 +1,330 @@
+use std::convert::{TryFrom, TryInto};
+
+use crate::{
+    asset::{Asset, AssetInfo, AssetInfoRaw},
+    error::ContractError,
+    pair::{LpTokenInfo, SimulationResponse},
+};
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{
+    Addr, Api, CanonicalAddr, Decimal, Decimal256, QuerierWrapper, StdError, StdResult, Uint128,
+    Uint256,
+};
+use cw20::Cw20ReceiveMsg;
+
+/// A weighted pool holds 2 to 4 assets
+pub const MIN_WEIGHTED_ASSETS: usize = 2;
+pub const MAX_WEIGHTED_ASSETS: usize = 4;
+
+/// Max offer amount of a swap as a fraction of the offer pool == 30%,
+/// it keeps the base of the power close to 1 where the series converges quickly
+pub const MAX_IN_RATIO: Decimal256 = Decimal256::raw(300_000_000_000_000_000);
+
+/// the series of the fractional power stops at terms below 10^-10
+const POW_PRECISION: Decimal256 = Decimal256::raw(100_000_000);
+const MAX_POW_ITERATIONS: u32 = 100;
+
+#[cw_serde]
+pub struct WeightedAssetInfo {
+    pub info: AssetInfo,
+    /// weights of the pool assets sum to 1
+    pub weight: Decimal,
+}
+
+#[cw_serde]
+pub struct WeightedAssetInfoRaw {
+    pub info: AssetInfoRaw,
+    pub weight: Decimal,
+}
+
+#[cw_serde]
+pub struct WeightedPairInfo {
+    pub asset_infos: Vec<WeightedAssetInfo>,
+    pub contract_addr: Addr,
+    pub liquidity_token: Addr,
+
+    pub oracle_addr: Addr,
+    pub commission_rate: String,
+}
+
+#[cw_serde]
+pub struct WeightedPairInfoRaw {
+    pub asset_infos: Vec<WeightedAssetInfoRaw>,
+    pub contract_addr: CanonicalAddr,
+    pub liquidity_token: CanonicalAddr,
+
+    // oracle contract
+    pub oracle_addr: CanonicalAddr,
+    pub commission_rate: String,
+}
+
+impl WeightedPairInfoRaw {
+    pub fn to_normal(&self, api: &dyn Api) -> StdResult<WeightedPairInfo> {
+        Ok(WeightedPairInfo {
+            liquidity_token: api.addr_humanize(&self.liquidity_token)?,
+            contract_addr: api.addr_humanize(&self.contract_addr)?,
+            oracle_addr: api.addr_humanize(&self.oracle_addr)?,
+            asset_infos: self
+                .asset_infos
+                .iter()
+                .map(|asset_info| {
+                    Ok(WeightedAssetInfo {
+                        info: asset_info.info.to_normal(api)?,
+                        weight: asset_info.weight,
+                    })
+                })
+                .collect::<StdResult<_>>()?,
+            commission_rate: self.commission_rate.clone(),
+        })
+    }
+
+    pub fn query_pools(
+        &self,
+        querier: &QuerierWrapper,
+        api: &dyn Api,
+        contract_addr: Addr,
+    ) -> StdResult<Vec<Asset>> {
+        self.asset_infos
+            .iter()
+            .map(|asset_info| {
+                let info = asset_info.info.to_normal(api)?;
+                Ok(Asset {
+                    amount: info.query_pool(querier, contract_addr.clone())?,
+                    info,
+                })
+            })
+            .collect()
+    }
+}
+
+/// key of a weighted pool in the factory, the asset keys are sorted so any order of the assets resolves the pool
+pub fn weighted_pair_key(asset_infos: &[AssetInfoRaw]) -> Vec<u8> {
+    let mut asset_keys: Vec<&[u8]> = asset_infos.iter().map(|info| info.as_bytes()).collect();
+    asset_keys.sort();
+    asset_keys.concat()
+}
+
+/// check the number of assets, that they are distinct and that their weights sum to 1
+pub fn validate_weighted_asset_infos(asset_infos: &[WeightedAssetInfo]) -> StdResult<()> {
+    if asset_infos.len() < MIN_WEIGHTED_ASSETS || asset_infos.len() > MAX_WEIGHTED_ASSETS {
+        return Err(StdError::generic_err(format!(
+            "weighted pool must have {} to {} assets",
+            MIN_WEIGHTED_ASSETS, MAX_WEIGHTED_ASSETS
+        )));
+    }
+
+    for (i, asset_info) in asset_infos.iter().enumerate() {
+        if asset_info.weight.is_zero() {
+            return Err(StdError::generic_err("asset weight must not be zero"));
+        }
+        if asset_infos[..i]
+            .iter()
+            .any(|prev| prev.info.eq(&asset_info.info))
+        {
+            return Err(StdError::generic_err("duplicated asset in weighted pool"));
+        }
+    }
+
+    let total_weight = asset_infos
+        .iter()
+        .fold(Decimal::zero(), |total, asset_info| {
+            total + asset_info.weight
+        });
+    if total_weight != Decimal::one() {
+        return Err(StdError::generic_err("asset weights must sum to 1"));
+    }
+
+    Ok(())
+}
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// Asset infos with their weights
+    pub asset_infos: Vec<WeightedAssetInfo>,
+    /// Token contract code id for initialization
+    pub token_code_id: u64,
+
+    /// Oracle contract for query oracle information
+    pub oracle_addr: Addr,
+
+    pub commission_rate: Option<String>,
+
+    /// Liquidity token metadata, default is "oraiswap liquidity token"/"uLP"
+    pub lp_token_info: Option<LpTokenInfo>,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    Receive(Cw20ReceiveMsg),
+    /// ProvideLiquidity a user provides all pool assets in proportion of the reserves,
+    /// the assets exceeding the proportion are refunded for native tokens and not transferred for tokens
+    ProvideLiquidity {
+        assets: Vec<Asset>,
+        receiver: Option<Addr>,
+    },
+    /// Swap an offer asset to the ask asset
+    Swap {
+        offer_asset: Asset,
+        ask_asset_info: AssetInfo,
+        belief_price: Option<Decimal>,
+        max_spread: Option<Decimal>,
+        to: Option<Addr>,
+    },
+}
+
+#[cw_serde]
+pub enum Cw20HookMsg {
+    /// Sell a given amount of asset for the ask asset
+    Swap {
+        ask_asset_info: AssetInfo,
+        belief_price: Option<Decimal>,
+        max_spread: Option<Decimal>,
+        to: Option<String>,
+    },
+    WithdrawLiquidity {},
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(WeightedPairResponse)]
+    Pair {},
+    #[returns(WeightedPoolResponse)]
+    Pool {},
+    #[returns(SimulationResponse)]
+    Simulation {
+        offer_asset: Asset,
+        ask_asset_info: AssetInfo,
+    },
+}
+
+#[cw_serde]
+pub struct WeightedPairResponse {
+    pub info: WeightedPairInfo,
+}
+
+#[cw_serde]
+pub struct WeightedPoolResponse {
+    pub assets: Vec<Asset>,
+    pub total_share: Uint128,
+}
+
+/// We currently take no arguments for migrations
+#[cw_serde]
+pub struct MigrateMsg {}
+
+/// base^exp, the whole part of the exponent is computed exactly
+/// and the fractional part with the binomial series of (1 + (base - 1))^exp, so base must be in (0, 2)
+pub fn pow_decimal(base: Decimal256, exp: Decimal256) -> StdResult<Decimal256> {
+    let one = Decimal256::one();
+    let whole = exp.atomics() / one.atomics();
+    let remain = exp - Decimal256::from_ratio(whole, 1u8);
+
+    let whole: u32 = Uint128::try_from(whole)
+        .map_err(|err| StdError::from(err))?
+        .u128()
+        .try_into()
+        .map_err(|_| StdError::generic_err("exponent is too large"))?;
+    let whole_pow = base.checked_pow(whole)?;
+    if remain.is_zero() {
+        return Ok(whole_pow);
+    }
+
+    let (x, x_negative) = if base >= one {
+        (base - one, false)
+    } else {
+        (one - base, true)
+    };
+
+    let mut term = one;
+    let mut sum = one;
+    let mut negative = false;
+    for i in 1..=MAX_POW_ITERATIONS {
+        let k = Decimal256::from_ratio(i, 1u8);
+        let k_minus_one = Decimal256::from_ratio(i - 1, 1u8);
+        let (c, c_negative) = if remain >= k_minus_one {
+            (remain - k_minus_one, false)
+        } else {
+            (k_minus_one - remain, true)
+        };
+
+        term = term * c * x / k;
+        if term < POW_PRECISION {
+            break;
+        }
+
+        if x_negative {
+            negative = !negative;
+        }
+        if c_negative {
+            negative = !negative;
+        }
+        sum = if negative {
+            sum.checked_sub(term)?
+        } else {
+            sum.checked_add(term)?
+        };
+    }
+
+    Ok(whole_pow.checked_mul(sum)?)
+}
+
+/// offer => ask of a weighted pool
+/// return_amount = ask_pool * (1 - (offer_pool / (offer_pool + offer_amount)) ^ (offer_weight / ask_weight)) * (1 - commission_rate)
+pub fn compute_weighted_swap(
+    offer_pool: Uint128,
+    offer_weight: Decimal,
+    ask_pool: Uint128,
+    ask_weight: Decimal,
+    offer_amount: Uint128,
+    commission_rate: Decimal256,
+) -> Result<(Uint128, Uint128, Uint128), ContractError> {
+    if offer_pool.is_zero() {
+        return Err(ContractError::OfferPoolIsZero {});
+    }
+
+    let offer_pool: Uint256 = offer_pool.into();
+    let ask_pool: Uint256 = ask_pool.into();
+    let offer_amount: Uint256 = offer_amount.into();
+    let offer_weight: Decimal256 = offer_weight.into();
+    let ask_weight: Decimal256 = ask_weight.into();
+
+    if Decimal256::from_ratio(offer_amount, offer_pool) > MAX_IN_RATIO {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Offer amount exceeds the max in ratio of the pool",
+        )));
+    }
+
+    // the base is rounded up so the return amount is rounded down
+    let base = Decimal256::one() - Decimal256::from_ratio(offer_amount, offer_pool + offer_amount);
+    let exp = offer_weight
+        .checked_div(ask_weight)
+        .map_err(|err| StdError::generic_err(err.to_string()))?;
+    let power = pow_decimal(base, exp)?;
+
+    let return_amount = ask_pool * (Decimal256::one() - power.min(Decimal256::one()));
+
+    // calculate spread & commission, the spot price is (ask_pool / ask_weight) / (offer_pool / offer_weight)
+    let expected_return = offer_amount
+        .multiply_ratio(ask_pool, offer_pool)
+        .multiply_ratio(offer_weight.atomics(), ask_weight.atomics());
+    let spread_amount = expected_return
+        .checked_sub(return_amount)
+        .unwrap_or_default();
+
+    let commission_amount = return_amount * commission_rate;
+
+    // commission will be absorbed to pool
+    let return_amount = return_amount - commission_amount;
+    Ok((
+        return_amount
+            .try_into()
+            .map_err(|err| StdError::from(err))?,
+        spread_amount
+            .try_into()
+            .map_err(|err| StdError::from(err))?,
+        commission_amount
+            .try_into()
+            .map_err(|err| StdError::from(err))?,
+    ))
+}